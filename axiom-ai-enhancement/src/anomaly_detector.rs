@@ -6,8 +6,13 @@ use std::collections::VecDeque;
 use std::sync::Arc;
 use parking_lot::RwLock;
 
+use crate::feature_store::ChainFeatures;
+
 const HISTORY_SIZE: usize = 1000;
 const ZSCORE_THRESHOLD: f64 = 3.0;
+const HIGH_AMOUNT_PERCENTILE: f64 = 0.99;
+const FAN_OUT_THRESHOLD: usize = 20;
+const YOUNG_ADDRESS_SECS: u64 = 86400;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionFeatures {
@@ -28,6 +33,21 @@ pub struct AnomalyScore {
     pub severity: AnomalySeverity,
 }
 
+/// Scores of one block's transactions, in block order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockAnomalyReport {
+    pub height: u64,
+    pub scores: Vec<AnomalyScore>,
+    /// Indices of the transactions flagged above the oracle's threshold
+    pub anomalous: Vec<usize>,
+}
+
+impl BlockAnomalyReport {
+    pub fn is_anomalous(&self) -> bool {
+        !self.anomalous.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AnomalySeverity {
     Low,
@@ -74,6 +94,26 @@ impl AnomalyDetector {
 
     /// Check transaction for anomalies using multiple statistical methods
     pub fn check_transaction(&self, features: &TransactionFeatures) -> AnomalyScore {
+        let score = self.score_transaction(features);
+
+        // Update history and statistics
+        self.update_history(features.clone());
+
+        score
+    }
+
+    /// Score a transaction without adding it to the history, e.g. one in
+    /// a block that may still be rejected
+    pub fn score_transaction(&self, features: &TransactionFeatures) -> AnomalyScore {
+        self.score(features, None)
+    }
+
+    /// Score a transaction with its chain context from the feature store
+    pub fn score_chain_transaction(&self, features: &ChainFeatures) -> AnomalyScore {
+        self.score(&features.transaction, Some(features))
+    }
+
+    fn score(&self, features: &TransactionFeatures, chain: Option<&ChainFeatures>) -> AnomalyScore {
         let mut risk_factors = Vec::new();
         let mut scores = Vec::new();
 
@@ -112,15 +152,21 @@ impl AnomalyDetector {
             risk_factors.extend(time_result.2);
         }
 
+        // 6. Chain context (amount rank, address age, fan-out)
+        if let Some(chain) = chain {
+            let chain_result = self.chain_analysis(chain);
+            scores.push(chain_result.0);
+            if chain_result.1 {
+                risk_factors.extend(chain_result.2);
+            }
+        }
+
         // Aggregate scores (higher = more anomalous)
         let combined_score = scores.iter().sum::<f64>() / scores.len() as f64;
         let is_anomaly = !risk_factors.is_empty();
         let confidence = self.calculate_confidence(&scores);
         let severity = self.calculate_severity(combined_score, &risk_factors);
 
-        // Update history and statistics
-        self.update_history(features.clone());
-
         AnomalyScore {
             score: combined_score,
             is_anomaly,
@@ -207,7 +253,7 @@ impl AnomalyDetector {
         }
 
         let mut risk_factors = Vec::new();
-        let mut anomaly_score = 0.0;
+        let mut anomaly_score: f64 = 0.0;
 
         // Amount IQR check
         let amounts: Vec<f64> = history.iter().map(|f| f.amount).collect();
@@ -239,7 +285,7 @@ impl AnomalyDetector {
     /// Behavioral pattern analysis
     fn pattern_analysis(&self, features: &TransactionFeatures) -> (f64, bool, Vec<String>) {
         let mut risk_factors = Vec::new();
-        let mut anomaly_score = 0.0;
+        let mut anomaly_score: f64 = 0.0;
 
         // Check for suspicious patterns
         
@@ -281,7 +327,12 @@ impl AnomalyDetector {
     /// Time-based anomaly detection
     fn time_analysis(&self, features: &TransactionFeatures) -> (f64, bool, Vec<String>) {
         let mut risk_factors = Vec::new();
-        let mut anomaly_score = 0.0;
+        let mut anomaly_score: f64 = 0.0;
+
+        // A sender's first transaction has nothing before it to time
+        if features.sender_tx_count == 0 {
+            return (anomaly_score, false, risk_factors);
+        }
 
         // Rapid-fire transactions (less than 10 seconds between)
         if features.time_since_last_tx < 10 {
@@ -301,12 +352,43 @@ impl AnomalyDetector {
         (anomaly_score.min(1.0), !risk_factors.is_empty(), risk_factors)
     }
 
+    /// Anomalies visible only against the chain's recent history
+    fn chain_analysis(&self, chain: &ChainFeatures) -> (f64, bool, Vec<String>) {
+        let mut risk_factors = Vec::new();
+        let mut anomaly_score: f64 = 0.0;
+
+        if chain.amount_percentile >= HIGH_AMOUNT_PERCENTILE {
+            risk_factors.push(format!(
+                "Amount larger than {:.1}% of recent transactions",
+                chain.amount_percentile * 100.0
+            ));
+            anomaly_score += 0.5;
+        }
+
+        // Young address moving a large amount
+        if chain.sender_age < YOUNG_ADDRESS_SECS && chain.amount_percentile >= 0.9 {
+            risk_factors.push("Sender younger than a day moving a top-10% amount".to_string());
+            anomaly_score += 0.4;
+        }
+
+        // Fan-out: one sender paying many recipients in a block (peeling, dusting)
+        if chain.fan_out > FAN_OUT_THRESHOLD {
+            risk_factors.push(format!(
+                "Sender pays {} recipients in one block",
+                chain.fan_out
+            ));
+            anomaly_score += 0.6;
+        }
+
+        (anomaly_score.min(1.0), !risk_factors.is_empty(), risk_factors)
+    }
+
     /// Calculate median and Median Absolute Deviation
     fn calculate_median_mad(&self, data: &[f64]) -> (f64, f64) {
         let mut sorted = data.to_vec();
         sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
-        let median = if sorted.len() % 2 == 0 {
+        let median = if sorted.len().is_multiple_of(2) {
             (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0
         } else {
             sorted[sorted.len() / 2]
@@ -316,7 +398,7 @@ impl AnomalyDetector {
         let mut sorted_dev = deviations.clone();
         sorted_dev.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
-        let mad = if sorted_dev.len() % 2 == 0 {
+        let mad = if sorted_dev.len().is_multiple_of(2) {
             (sorted_dev[sorted_dev.len() / 2 - 1] + sorted_dev[sorted_dev.len() / 2]) / 2.0
         } else {
             sorted_dev[sorted_dev.len() / 2]
@@ -361,7 +443,7 @@ impl AnomalyDetector {
             / scores.len() as f64;
 
         // Low variance = high confidence
-        (1.0 / (1.0 + variance)).max(0.5).min(1.0)
+        (1.0 / (1.0 + variance)).clamp(0.5, 1.0)
     }
 
     /// Calculate severity based on score and risk factors
@@ -379,6 +461,11 @@ impl AnomalyDetector {
         }
     }
 
+    /// Add an accepted transaction to the history without scoring it
+    pub fn record_transaction(&self, features: TransactionFeatures) {
+        self.update_history(features);
+    }
+
     /// Update transaction history and recalculate statistics
     fn update_history(&self, features: TransactionFeatures) {
         let mut history = self.history.write();
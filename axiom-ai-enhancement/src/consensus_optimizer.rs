@@ -8,6 +8,10 @@ const HISTORY_SIZE: usize = 1000;
 const TARGET_BLOCK_TIME: u64 = 1800; // 30 minutes in seconds
 const ADJUSTMENT_INTERVAL: usize = 144; // ~3 days at 30min blocks
 
+/// Blocks of notice between approving a change and its activation, so the
+/// network hears of it before it takes effect.
+pub const ACTIVATION_DELAY_BLOCKS: u64 = ADJUSTMENT_INTERVAL as u64;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkMetrics {
     pub hashrate: f64,
@@ -29,6 +33,33 @@ pub struct OptimizationSuggestion {
     pub expected_improvement: f32,
 }
 
+/// Where a suggestion stands. Suggestions never change parameters until
+/// approved, and then only from their activation height.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProposalState {
+    Pending,
+    Approved { activation_height: u64 },
+    Rejected { reason: String },
+    Activated { height: u64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationProposal {
+    pub id: u64,
+    pub suggestion: OptimizationSuggestion,
+    pub proposed_at_height: u64,
+    pub state: ProposalState,
+}
+
+/// An approved change and the height it activates at, for the node to
+/// announce over gossip.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivationNotice {
+    pub proposal_id: u64,
+    pub suggestion: OptimizationSuggestion,
+    pub activation_height: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusParameters {
     pub difficulty: u64,
@@ -56,6 +87,8 @@ pub struct AdaptiveConsensusOptimizer {
     current_params: ConsensusParameters,
     pid_difficulty: PIDController,
     pid_gas_price: PIDController,
+    proposals: Vec<OptimizationProposal>,
+    next_proposal_id: u64,
 }
 
 /// PID Controller for smooth parameter adjustments
@@ -92,6 +125,12 @@ impl PIDController {
     }
 }
 
+impl Default for AdaptiveConsensusOptimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AdaptiveConsensusOptimizer {
     pub fn new() -> Self {
         Self {
@@ -100,6 +139,8 @@ impl AdaptiveConsensusOptimizer {
             current_params: ConsensusParameters::default(),
             pid_difficulty: PIDController::new(0.5, 0.1, 0.05),
             pid_gas_price: PIDController::new(0.3, 0.05, 0.02),
+            proposals: Vec::new(),
+            next_proposal_id: 1,
         }
     }
 
@@ -141,7 +182,7 @@ impl AdaptiveConsensusOptimizer {
         let pid_output = self.pid_difficulty.update(error, 1.0);
 
         // Calculate adjustment factor (limit to ±50% per adjustment)
-        let adjustment = (1.0 + pid_output).max(0.5).min(1.5);
+        let adjustment = (1.0 + pid_output).clamp(0.5, 1.5);
 
         // Apply hashrate compensation
         let hashrate_adjustment = 1.0 + (hashrate_trend * 0.1);
@@ -191,9 +232,7 @@ impl AdaptiveConsensusOptimizer {
         let hashrate_ratio = (avg_hashrate / baseline_hashrate).ln();
 
         let current_vdf = self.current_params.vdf_iterations as f64;
-        let suggested_vdf = (1000.0 * (1.0 + hashrate_ratio * 0.1))
-            .max(500.0)
-            .min(5000.0);
+        let suggested_vdf = (1000.0 * (1.0 + hashrate_ratio * 0.1)).clamp(500.0, 5000.0);
 
         let confidence = if recent_metrics.len() >= 50 {
             0.8
@@ -239,7 +278,7 @@ impl AdaptiveConsensusOptimizer {
         let pid_output = self.pid_gas_price.update(error, 1.0);
 
         // Calculate congestion factor
-        let congestion_factor = (avg_mempool / target_mempool).max(0.5).min(2.0);
+        let congestion_factor = (avg_mempool / target_mempool).clamp(0.5, 2.0);
 
         let current_min_gas = self.current_params.min_gas_price as f64;
         let suggested_gas =
@@ -324,7 +363,7 @@ impl AdaptiveConsensusOptimizer {
         let coefficient_of_variation = std_dev / mean;
 
         // Low CoV = high confidence
-        (1.0 / (1.0 + coefficient_of_variation)).max(0.5).min(1.0) as f32
+        (1.0 / (1.0 + coefficient_of_variation)).clamp(0.5, 1.0) as f32
     }
 
     /// Verify suggestion is within safety bounds
@@ -332,7 +371,7 @@ impl AdaptiveConsensusOptimizer {
         let ratio = suggestion.suggested_value / suggestion.current_value;
 
         match suggestion.parameter.as_str() {
-            "difficulty" => ratio >= 0.5 && ratio <= 2.0, // Max 2x change
+            "difficulty" => (0.5..=2.0).contains(&ratio), // Max 2x change
             "vdf_iterations" => {
                 suggestion.suggested_value >= 500.0 && suggestion.suggested_value <= 5000.0
             }
@@ -343,8 +382,79 @@ impl AdaptiveConsensusOptimizer {
         }
     }
 
+    /// Record a suggestion for approval, after the safety bounds check.
+    /// Returns the proposal id.
+    pub fn propose(&mut self, suggestion: OptimizationSuggestion, chain_height: u64) -> Result<u64, String> {
+        if !self.verify_safety_bounds(&suggestion) {
+            return Err(format!(
+                "Suggested {} of {} is outside safety bounds",
+                suggestion.parameter, suggestion.suggested_value
+            ));
+        }
+        let id = self.next_proposal_id;
+        self.next_proposal_id += 1;
+        self.proposals.push(OptimizationProposal {
+            id,
+            suggestion,
+            proposed_at_height: chain_height,
+            state: ProposalState::Pending,
+        });
+        Ok(id)
+    }
+
+    /// Approve pending proposal `id` (operator or on-chain signal); it
+    /// activates `ACTIVATION_DELAY_BLOCKS` after `chain_height`.
+    pub fn approve(&mut self, id: u64, chain_height: u64) -> Result<ActivationNotice, String> {
+        let proposal = self
+            .proposals
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("No optimization proposal {}", id))?;
+        if proposal.state != ProposalState::Pending {
+            return Err(format!("Optimization proposal {} is not pending", id));
+        }
+        let activation_height = chain_height + ACTIVATION_DELAY_BLOCKS;
+        proposal.state = ProposalState::Approved { activation_height };
+        Ok(ActivationNotice { proposal_id: id, suggestion: proposal.suggestion.clone(), activation_height })
+    }
+
+    pub fn reject(&mut self, id: u64, reason: String) -> Result<(), String> {
+        let proposal = self
+            .proposals
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("No optimization proposal {}", id))?;
+        if matches!(proposal.state, ProposalState::Activated { .. }) {
+            return Err(format!("Optimization proposal {} already activated", id));
+        }
+        proposal.state = ProposalState::Rejected { reason };
+        Ok(())
+    }
+
+    /// Apply the approved proposals due at `chain_height`. Returns the
+    /// suggestions applied.
+    pub fn activate_due(&mut self, chain_height: u64) -> Vec<OptimizationSuggestion> {
+        let mut due = Vec::new();
+        for proposal in &mut self.proposals {
+            if let ProposalState::Approved { activation_height } = proposal.state {
+                if activation_height <= chain_height {
+                    proposal.state = ProposalState::Activated { height: chain_height };
+                    due.push(proposal.suggestion.clone());
+                }
+            }
+        }
+        for suggestion in &due {
+            self.apply_suggestion(suggestion);
+        }
+        due
+    }
+
+    pub fn proposals(&self) -> &[OptimizationProposal] {
+        &self.proposals
+    }
+
     /// Apply a suggestion to current parameters
-    pub fn apply_suggestion(&mut self, suggestion: &OptimizationSuggestion) {
+    fn apply_suggestion(&mut self, suggestion: &OptimizationSuggestion) {
         match suggestion.parameter.as_str() {
             "difficulty" => {
                 self.current_params.difficulty = suggestion.suggested_value as u64;
@@ -376,3 +486,48 @@ impl AdaptiveConsensusOptimizer {
         self.pid_gas_price.reset();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vdf_iterations(value: f64) -> OptimizationSuggestion {
+        OptimizationSuggestion {
+            parameter: "vdf_iterations".to_string(),
+            current_value: 1000.0,
+            suggested_value: value,
+            confidence: 0.9,
+            rationale: "test".to_string(),
+            expected_improvement: 0.1,
+        }
+    }
+
+    #[test]
+    fn test_only_approved_proposals_activate_at_their_height() {
+        let mut optimizer = AdaptiveConsensusOptimizer::new();
+        assert!(optimizer.propose(vdf_iterations(50_000.0), 10).is_err());
+        let pending = optimizer.propose(vdf_iterations(1500.0), 10).unwrap();
+        let rejected = optimizer.propose(vdf_iterations(1800.0), 10).unwrap();
+        let approved = optimizer.propose(vdf_iterations(2000.0), 10).unwrap();
+
+        // However long it waits, an unapproved proposal changes nothing.
+        optimizer.reject(rejected, "not needed".to_string()).unwrap();
+        assert!(optimizer.approve(rejected, 20).is_err());
+        assert!(optimizer.activate_due(1_000_000).is_empty());
+        assert_eq!(optimizer.get_current_parameters().vdf_iterations, 1000);
+
+        let notice = optimizer.approve(approved, 20).unwrap();
+        assert_eq!(notice.activation_height, 20 + ACTIVATION_DELAY_BLOCKS);
+        assert!(optimizer.approve(approved, 21).is_err());
+        assert!(optimizer.activate_due(notice.activation_height - 1).is_empty());
+        assert_eq!(optimizer.get_current_parameters().vdf_iterations, 1000);
+
+        let applied = optimizer.activate_due(notice.activation_height);
+        assert_eq!(applied.len(), 1);
+        assert_eq!(optimizer.get_current_parameters().vdf_iterations, 2000);
+        assert!(optimizer.activate_due(notice.activation_height + 1).is_empty());
+        let state = |id| optimizer.proposals().iter().find(|p| p.id == id).unwrap().state.clone();
+        assert_eq!(state(approved), ProposalState::Activated { height: notice.activation_height });
+        assert_eq!(state(pending), ProposalState::Pending);
+    }
+}
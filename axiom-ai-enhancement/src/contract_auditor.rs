@@ -131,8 +131,6 @@ impl ContractAuditor {
         let contract_hash = self.hash_contract(bytecode);
 
         let mut vulnerabilities = Vec::new();
-        let mut safe_patterns = Vec::new();
-        let mut gas_tips = Vec::new();
 
         // 1. Pattern matching for known vulnerabilities
         vulnerabilities.extend(self.detect_known_patterns(bytecode));
@@ -147,10 +145,10 @@ impl ContractAuditor {
         vulnerabilities.extend(self.analyze_access_control(bytecode));
 
         // 5. Gas optimization detection
-        gas_tips = self.detect_gas_inefficiencies(bytecode);
+        let gas_tips = self.detect_gas_inefficiencies(bytecode);
 
         // 6. Safe pattern recognition
-        safe_patterns = self.detect_safe_patterns(bytecode);
+        let safe_patterns = self.detect_safe_patterns(bytecode);
 
         // 7. Calculate complexity
         let complexity_score = self.calculate_complexity(bytecode);
@@ -181,75 +179,75 @@ impl ContractAuditor {
 
         for i in 0..bytecode.len() {
             for (pattern, vuln_type) in &self.pattern_signatures {
-                if i + pattern.len() <= bytecode.len() {
-                    if &bytecode[i..i + pattern.len()] == pattern.as_slice() {
-                        let location = format!("Offset: 0x{:x}", i);
-
-                        // Avoid duplicate reports at same location
-                        if seen_locations.contains(&location) {
-                            continue;
-                        }
-                        seen_locations.insert(location.clone());
-
-                        let vulnerability = match vuln_type {
-                            VulnerabilityType::ReentrancyAttack => Vulnerability {
-                                vuln_type: vuln_type.clone(),
-                                severity: 9,
-                                location: location.clone(),
-                                description:
-                                    "Potential reentrancy vulnerability: external call before state change"
-                                        .to_string(),
-                                suggested_fix:
-                                    "Use checks-effects-interactions pattern or ReentrancyGuard"
-                                        .to_string(),
-                                confidence: 0.85,
-                            },
-                            VulnerabilityType::IntegerOverflow => Vulnerability {
-                                vuln_type: vuln_type.clone(),
-                                severity: 7,
-                                location: location.clone(),
-                                description: "Arithmetic operation without overflow check".to_string(),
-                                suggested_fix: "Use checked arithmetic operations".to_string(),
-                                confidence: 0.7,
-                            },
-                            VulnerabilityType::UncheckedExternalCall => Vulnerability {
-                                vuln_type: vuln_type.clone(),
-                                severity: 8,
-                                location: location.clone(),
-                                description: "External call without return value check".to_string(),
-                                suggested_fix: "Always verify external call return values".to_string(),
-                                confidence: 0.8,
-                            },
-                            VulnerabilityType::DelegateCallInjection => Vulnerability {
-                                vuln_type: vuln_type.clone(),
-                                severity: 10,
-                                location: location.clone(),
-                                description: "DELEGATECALL to potentially untrusted contract".to_string(),
-                                suggested_fix: "Whitelist allowed delegate call targets".to_string(),
-                                confidence: 0.9,
-                            },
-                            VulnerabilityType::UnprotectedSelfDestruct => Vulnerability {
-                                vuln_type: vuln_type.clone(),
-                                severity: 10,
-                                location: location.clone(),
-                                description: "SELFDESTRUCT without access control".to_string(),
-                                suggested_fix: "Add owner-only modifier".to_string(),
-                                confidence: 0.95,
-                            },
-                            VulnerabilityType::TimestampDependence => Vulnerability {
-                                vuln_type: vuln_type.clone(),
-                                severity: 5,
-                                location: location.clone(),
-                                description: "Logic depends on block timestamp (manipulable by miners)"
-                                    .to_string(),
-                                suggested_fix: "Avoid timestamp for critical logic".to_string(),
-                                confidence: 0.6,
-                            },
-                            _ => continue,
-                        };
-
-                        vulnerabilities.push(vulnerability);
+                if i + pattern.len() <= bytecode.len()
+                    && &bytecode[i..i + pattern.len()] == pattern.as_slice()
+                {
+                    let location = format!("Offset: 0x{:x}", i);
+
+                    // Avoid duplicate reports at same location
+                    if seen_locations.contains(&location) {
+                        continue;
                     }
+                    seen_locations.insert(location.clone());
+
+                    let vulnerability = match vuln_type {
+                        VulnerabilityType::ReentrancyAttack => Vulnerability {
+                            vuln_type: vuln_type.clone(),
+                            severity: 9,
+                            location: location.clone(),
+                            description:
+                                "Potential reentrancy vulnerability: external call before state change"
+                                    .to_string(),
+                            suggested_fix:
+                                "Use checks-effects-interactions pattern or ReentrancyGuard"
+                                    .to_string(),
+                            confidence: 0.85,
+                        },
+                        VulnerabilityType::IntegerOverflow => Vulnerability {
+                            vuln_type: vuln_type.clone(),
+                            severity: 7,
+                            location: location.clone(),
+                            description: "Arithmetic operation without overflow check".to_string(),
+                            suggested_fix: "Use checked arithmetic operations".to_string(),
+                            confidence: 0.7,
+                        },
+                        VulnerabilityType::UncheckedExternalCall => Vulnerability {
+                            vuln_type: vuln_type.clone(),
+                            severity: 8,
+                            location: location.clone(),
+                            description: "External call without return value check".to_string(),
+                            suggested_fix: "Always verify external call return values".to_string(),
+                            confidence: 0.8,
+                        },
+                        VulnerabilityType::DelegateCallInjection => Vulnerability {
+                            vuln_type: vuln_type.clone(),
+                            severity: 10,
+                            location: location.clone(),
+                            description: "DELEGATECALL to potentially untrusted contract".to_string(),
+                            suggested_fix: "Whitelist allowed delegate call targets".to_string(),
+                            confidence: 0.9,
+                        },
+                        VulnerabilityType::UnprotectedSelfDestruct => Vulnerability {
+                            vuln_type: vuln_type.clone(),
+                            severity: 10,
+                            location: location.clone(),
+                            description: "SELFDESTRUCT without access control".to_string(),
+                            suggested_fix: "Add owner-only modifier".to_string(),
+                            confidence: 0.95,
+                        },
+                        VulnerabilityType::TimestampDependence => Vulnerability {
+                            vuln_type: vuln_type.clone(),
+                            severity: 5,
+                            location: location.clone(),
+                            description: "Logic depends on block timestamp (manipulable by miners)"
+                                .to_string(),
+                            suggested_fix: "Avoid timestamp for critical logic".to_string(),
+                            confidence: 0.6,
+                        },
+                        _ => continue,
+                    };
+
+                    vulnerabilities.push(vulnerability);
                 }
             }
         }
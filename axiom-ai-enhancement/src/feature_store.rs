@@ -0,0 +1,336 @@
+// Online Feature Store - Transaction features derived from chain data
+// Keeps rolling per-address activity and amount history from ingested blocks
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use crate::anomaly_detector::TransactionFeatures;
+
+/// Default location of the persisted store
+pub const FEATURE_STORE_PATH: &str = "axiom_feature_store.json";
+
+/// Recent transaction amounts kept for percentile ranking
+const AMOUNT_WINDOW: usize = 10_000;
+
+/// Amounts needed before percentiles mean anything
+const MIN_PERCENTILE_SAMPLES: usize = 100;
+
+/// Addresses idle for longer than this many blocks are forgotten
+const ADDRESS_RETENTION_BLOCKS: u64 = 144 * 30; // ~30 days at 30min blocks
+
+/// Ingested blocks that can still be rolled back after a reorg
+const UNDO_DEPTH: usize = 144;
+
+/// Base units per coin (amounts and fees on chain are in base units)
+const UNITS_PER_COIN: f64 = 100_000_000.0;
+
+/// A transaction as the feature store sees it. Addresses are opaque
+/// strings (hex on the Axiom chain).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockTransaction {
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub zk_proof_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockData {
+    pub height: u64,
+    pub timestamp: u64,
+    pub transactions: Vec<BlockTransaction>,
+}
+
+/// Features of one transaction in the context of the chain so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainFeatures {
+    pub transaction: TransactionFeatures,
+    /// Share of recent transaction amounts below this one (0-1); 0.5
+    /// until the store has seen enough transactions
+    pub amount_percentile: f64,
+    /// Seconds since the sender was first seen, 0 if never
+    pub sender_age: u64,
+    /// Distinct recipients the sender pays in the same block
+    pub fan_out: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AddressActivity {
+    first_seen: u64,
+    last_seen: u64,
+    last_height: u64,
+    tx_count: u64,
+}
+
+/// What ingesting one block changed, so a reorg can take it back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BlockUndo {
+    height: u64,
+    previous_height: Option<u64>,
+    /// Addresses the block touched or pruned, as they were before it
+    addresses: Vec<(String, Option<AddressActivity>)>,
+    /// The block's amounts still in the window
+    amounts_added: usize,
+    /// Earlier amounts the block pushed out of the window, oldest first
+    amounts_evicted: Vec<u64>,
+}
+
+/// Rolling feature store, updated block by block
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeatureStore {
+    addresses: HashMap<String, AddressActivity>,
+    amounts: VecDeque<u64>,
+    last_height: Option<u64>,
+    #[serde(default)]
+    undo: VecDeque<BlockUndo>,
+}
+
+impl FeatureStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a persisted store, or start empty if there is none
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        match std::fs::read(path.as_ref()) {
+            Ok(data) => serde_json::from_slice(&data).map_err(|e| format!("Invalid feature store: {}", e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(format!("Cannot read feature store: {}", e)),
+        }
+    }
+
+    /// Persist the store (written to a temporary file, then renamed)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let data = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, data)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| format!("Cannot write feature store: {}", e))
+    }
+
+    /// Height of the last ingested block
+    pub fn last_height(&self) -> Option<u64> {
+        self.last_height
+    }
+
+    pub fn tracked_addresses(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// Features of every transaction in `block`, against the chain as
+    /// ingested so far. The store is not changed.
+    pub fn extract(&self, block: &BlockData) -> Vec<ChainFeatures> {
+        let mut recipients: HashMap<&str, HashSet<&str>> = HashMap::new();
+        for tx in &block.transactions {
+            recipients.entry(tx.from.as_str()).or_default().insert(tx.to.as_str());
+        }
+
+        block
+            .transactions
+            .iter()
+            .map(|tx| {
+                let sender = self.addresses.get(&tx.from);
+                let recipient = self.addresses.get(&tx.to);
+                ChainFeatures {
+                    transaction: TransactionFeatures {
+                        amount: tx.amount as f64 / UNITS_PER_COIN,
+                        gas_fee: tx.fee as f64 / UNITS_PER_COIN,
+                        zk_proof_size: tx.zk_proof_size,
+                        sender_tx_count: sender.map_or(0, |a| a.tx_count),
+                        recipient_tx_count: recipient.map_or(0, |a| a.tx_count),
+                        // Never seen: no earlier transaction to time, which
+                        // the detector reads from `sender_tx_count` being 0
+                        time_since_last_tx: sender.map_or(0, |a| block.timestamp.saturating_sub(a.last_seen)),
+                    },
+                    amount_percentile: self.amount_percentile(tx.amount),
+                    sender_age: sender.map_or(0, |a| block.timestamp.saturating_sub(a.first_seen)),
+                    fan_out: recipients.get(tx.from.as_str()).map_or(0, |r| r.len()),
+                }
+            })
+            .collect()
+    }
+
+    fn amount_percentile(&self, amount: u64) -> f64 {
+        if self.amounts.len() < MIN_PERCENTILE_SAMPLES {
+            return 0.5;
+        }
+        let below = self.amounts.iter().filter(|&&a| a < amount).count();
+        below as f64 / self.amounts.len() as f64
+    }
+
+    /// Add `block`'s transactions to the store. Blocks at or below the last
+    /// ingested height are ignored; returns whether it was added.
+    pub fn ingest(&mut self, block: &BlockData) -> bool {
+        if self.last_height.is_some_and(|h| block.height <= h) {
+            return false;
+        }
+        let mut undo = BlockUndo {
+            height: block.height,
+            previous_height: self.last_height,
+            addresses: Vec::new(),
+            amounts_added: block.transactions.len().min(AMOUNT_WINDOW),
+            amounts_evicted: Vec::new(),
+        };
+        let earlier_amounts = self.amounts.len();
+        let mut touched = HashSet::new();
+        for tx in &block.transactions {
+            for address in [&tx.from, &tx.to] {
+                if touched.insert(address.as_str()) {
+                    undo.addresses.push((address.clone(), self.addresses.get(address).cloned()));
+                }
+                let activity = self.addresses.entry(address.clone()).or_insert_with(|| AddressActivity {
+                    first_seen: block.timestamp,
+                    ..Default::default()
+                });
+                activity.last_seen = block.timestamp;
+                activity.last_height = block.height;
+                activity.tx_count += 1;
+            }
+            self.amounts.push_back(tx.amount);
+            if self.amounts.len() > AMOUNT_WINDOW {
+                let evicted = self.amounts.pop_front();
+                if undo.amounts_evicted.len() < earlier_amounts {
+                    undo.amounts_evicted.extend(evicted);
+                }
+            }
+        }
+        self.last_height = Some(block.height);
+
+        let cutoff = block.height.saturating_sub(ADDRESS_RETENTION_BLOCKS);
+        self.addresses.retain(|address, a| {
+            let keep = a.last_height >= cutoff;
+            if !keep {
+                undo.addresses.push((address.clone(), Some(a.clone())));
+            }
+            keep
+        });
+
+        if self.undo.len() == UNDO_DEPTH {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(undo);
+        true
+    }
+
+    /// Take back every ingested block at or above `height`, after a reorg
+    /// replaced them. Fails without changing anything if that reaches past
+    /// the last `UNDO_DEPTH` blocks ingested; the store then has to be
+    /// rebuilt. Returns how many blocks were taken back.
+    pub fn rollback_to(&mut self, height: u64) -> Result<usize, String> {
+        let count = self.undo.iter().rev().take_while(|u| u.height >= height).count();
+        let remaining = match count {
+            0 => self.last_height,
+            n => self.undo[self.undo.len() - n].previous_height,
+        };
+        if remaining.is_some_and(|h| h >= height) {
+            return Err(format!("Feature store cannot roll back below height {}", height));
+        }
+        for undo in self.undo.drain(self.undo.len() - count..).rev() {
+            for (address, before) in undo.addresses {
+                match before {
+                    Some(activity) => self.addresses.insert(address, activity),
+                    None => self.addresses.remove(&address),
+                };
+            }
+            self.amounts.truncate(self.amounts.len() - undo.amounts_added);
+            for amount in undo.amounts_evicted.into_iter().rev() {
+                self.amounts.push_front(amount);
+            }
+            self.last_height = undo.previous_height;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(from: &str, to: &str, amount: u64) -> BlockTransaction {
+        BlockTransaction { from: from.into(), to: to.into(), amount, fee: 1_000, zk_proof_size: 256 }
+    }
+
+    fn block(height: u64, transactions: Vec<BlockTransaction>) -> BlockData {
+        BlockData { height, timestamp: height * 1800, transactions }
+    }
+
+    #[test]
+    fn test_percentiles_need_enough_samples() {
+        let mut store = FeatureStore::new();
+        let probe = block(1_000, vec![tx("a", "b", 75)]);
+        for height in 0..MIN_PERCENTILE_SAMPLES as u64 {
+            assert_eq!(store.extract(&probe)[0].amount_percentile, 0.5);
+            store.ingest(&block(height, vec![tx("a", "b", height)]));
+        }
+        assert_eq!(store.extract(&probe)[0].amount_percentile, 0.75);
+    }
+
+    #[test]
+    fn test_sender_age_dormancy_and_fan_out() {
+        let mut store = FeatureStore::new();
+        let first = block(1, vec![tx("alice", "bob", 10), tx("alice", "carol", 10), tx("alice", "bob", 10)]);
+        let features = store.extract(&first);
+        assert_eq!(features[0].sender_age, 0);
+        assert_eq!(features[0].fan_out, 2);
+        // A sender never seen before has no earlier transaction to time.
+        assert_eq!(features[0].transaction.sender_tx_count, 0);
+        assert_eq!(features[0].transaction.time_since_last_tx, 0);
+        let score = crate::anomaly_detector::AnomalyDetector::new().check_transaction(&features[0].transaction);
+        assert!(!score.risk_factors.iter().any(|r| r.contains("dormancy") || r.contains("Rapid")), "{:?}", score.risk_factors);
+        store.ingest(&first);
+
+        let later = store.extract(&block(5, vec![tx("alice", "dave", 10)]));
+        assert_eq!(later[0].sender_age, 4 * 1800);
+        assert_eq!(later[0].transaction.time_since_last_tx, 4 * 1800);
+        assert_eq!(later[0].transaction.sender_tx_count, 3);
+        assert_eq!(later[0].transaction.recipient_tx_count, 0);
+        assert_eq!(later[0].fan_out, 1);
+    }
+
+    #[test]
+    fn test_persisted_store_reloads() {
+        let path = std::env::temp_dir().join(format!("axiom_feature_store_{}.json", std::process::id()));
+        let mut store = FeatureStore::new();
+        store.ingest(&block(7, vec![tx("alice", "bob", 10)]));
+        store.save(&path).unwrap();
+
+        let mut loaded = FeatureStore::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(loaded.last_height(), Some(7));
+        assert_eq!(loaded.tracked_addresses(), 2);
+        assert!(!loaded.ingest(&block(7, vec![])));
+        assert_eq!(loaded.rollback_to(7), Ok(1));
+        assert!(FeatureStore::load(&path).unwrap().last_height().is_none());
+    }
+
+    #[test]
+    fn test_rollback_takes_back_replaced_blocks() {
+        let mut store = FeatureStore::new();
+        store.ingest(&block(1, vec![tx("alice", "bob", 10)]));
+        let before = serde_json::to_value(&store).unwrap();
+
+        store.ingest(&block(2, vec![tx("alice", "carol", 20)]));
+        store.ingest(&block(3, vec![tx("bob", "dave", 30)]));
+        assert_eq!(store.tracked_addresses(), 4);
+        assert_eq!(store.rollback_to(2), Ok(2));
+        assert_eq!(serde_json::to_value(&store).unwrap(), before);
+        assert_eq!(store.last_height(), Some(1));
+
+        // The new branch ingests from the fork point.
+        assert!(store.ingest(&block(2, vec![tx("erin", "bob", 5)])));
+        let features = store.extract(&block(3, vec![tx("alice", "erin", 1)]));
+        assert_eq!(features[0].transaction.sender_tx_count, 1);
+        assert_eq!(features[0].transaction.recipient_tx_count, 1);
+
+        // Nothing to take back, and nothing older than what was kept.
+        assert_eq!(store.rollback_to(9), Ok(0));
+        for height in 10..10 + UNDO_DEPTH as u64 {
+            store.ingest(&block(height, vec![]));
+        }
+        assert!(store.rollback_to(2).is_err());
+        assert_eq!(store.last_height(), Some(9 + UNDO_DEPTH as u64));
+    }
+}
@@ -4,8 +4,10 @@
 pub mod anomaly_detector;
 pub mod consensus_optimizer;
 pub mod contract_auditor;
+pub mod feature_store;
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::sync::Arc;
 use parking_lot::RwLock;
 
@@ -38,6 +40,8 @@ pub struct AIOracle {
     anomaly_detector: Option<Arc<RwLock<anomaly_detector::AnomalyDetector>>>,
     contract_auditor: Option<Arc<RwLock<contract_auditor::ContractAuditor>>>,
     consensus_optimizer: Option<Arc<RwLock<consensus_optimizer::AdaptiveConsensusOptimizer>>>,
+    feature_store: Arc<RwLock<feature_store::FeatureStore>>,
+    feature_store_path: Option<PathBuf>,
 }
 
 impl AIOracle {
@@ -47,12 +51,22 @@ impl AIOracle {
             anomaly_detector: None,
             contract_auditor: None,
             consensus_optimizer: None,
+            feature_store: Arc::new(RwLock::new(feature_store::FeatureStore::new())),
+            feature_store_path: None,
         };
 
         oracle.initialize_modules();
         oracle
     }
 
+    /// Keep the feature store at `path`, loading what is already there
+    pub fn with_feature_store(mut self, path: impl Into<PathBuf>) -> Result<Self, String> {
+        let path = path.into();
+        self.feature_store = Arc::new(RwLock::new(feature_store::FeatureStore::load(&path)?));
+        self.feature_store_path = Some(path);
+        Ok(self)
+    }
+
     fn initialize_modules(&mut self) {
         if self.config.enable_anomaly_detection {
             let detector = anomaly_detector::AnomalyDetector::new();
@@ -89,6 +103,77 @@ impl AIOracle {
         }
     }
 
+    /// Score every transaction in a candidate block against the chain
+    /// ingested so far. Neither the feature store nor the detector learn
+    /// from the block; call `ingest_block` once it is accepted.
+    pub fn score_block(
+        &self,
+        block: &feature_store::BlockData,
+    ) -> Result<anomaly_detector::BlockAnomalyReport, String> {
+        if let Some(detector) = &self.anomaly_detector {
+            let features = self.feature_store.read().extract(block);
+            let detector = detector.read();
+            let scores: Vec<_> = features
+                .iter()
+                .map(|f| detector.score_chain_transaction(f))
+                .collect();
+            let anomalous = scores
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.is_anomaly && s.score > self.config.anomaly_threshold)
+                .map(|(i, _)| i)
+                .collect();
+
+            Ok(anomaly_detector::BlockAnomalyReport {
+                height: block.height,
+                scores,
+                anomalous,
+            })
+        } else {
+            Err("Anomaly detector not enabled".to_string())
+        }
+    }
+
+    /// Add an accepted block to the feature store (and the detector's
+    /// history), persisting the store if it has a path
+    pub fn ingest_block(&self, block: &feature_store::BlockData) -> Result<(), String> {
+        let mut store = self.feature_store.write();
+        let features = store.extract(block);
+        if !store.ingest(block) {
+            return Ok(());
+        }
+
+        if let Some(detector) = &self.anomaly_detector {
+            let detector = detector.read();
+            for f in features {
+                detector.record_transaction(f.transaction);
+            }
+        }
+
+        match &self.feature_store_path {
+            Some(path) => store.save(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Take the blocks from `height` on back out of the feature store after
+    /// a reorg replaced them; `ingest_block` the new branch afterwards. The
+    /// detector keeps the history it recorded from them.
+    pub fn rollback_feature_store(&self, height: u64) -> Result<usize, String> {
+        let mut store = self.feature_store.write();
+        let undone = store.rollback_to(height)?;
+        match &self.feature_store_path {
+            Some(path) if undone > 0 => store.save(path)?,
+            _ => {}
+        }
+        Ok(undone)
+    }
+
+    /// Height of the last block in the feature store
+    pub fn feature_store_height(&self) -> Option<u64> {
+        self.feature_store.read().last_height()
+    }
+
     /// Audit smart contract bytecode
     pub fn audit_contract(
         &self,
@@ -143,14 +228,61 @@ impl AIOracle {
         }
     }
 
-    /// Apply consensus optimization suggestion
-    pub fn apply_optimization(
+    /// Record a suggestion as a proposal awaiting approval. Parameters do
+    /// not change until it is approved and its activation height reached.
+    pub fn propose_optimization(
         &self,
-        suggestion: &consensus_optimizer::OptimizationSuggestion,
-    ) -> Result<(), String> {
+        suggestion: consensus_optimizer::OptimizationSuggestion,
+        chain_height: u64,
+    ) -> Result<u64, String> {
         if let Some(optimizer) = &self.consensus_optimizer {
-            optimizer.write().apply_suggestion(suggestion);
-            Ok(())
+            optimizer.write().propose(suggestion, chain_height)
+        } else {
+            Err("Consensus optimizer not enabled".to_string())
+        }
+    }
+
+    /// Approve a proposal (by the operator or an on-chain signal). The
+    /// returned notice carries the activation height to announce to peers.
+    pub fn approve_optimization(
+        &self,
+        proposal_id: u64,
+        chain_height: u64,
+    ) -> Result<consensus_optimizer::ActivationNotice, String> {
+        if let Some(optimizer) = &self.consensus_optimizer {
+            optimizer.write().approve(proposal_id, chain_height)
+        } else {
+            Err("Consensus optimizer not enabled".to_string())
+        }
+    }
+
+    /// Reject a proposal
+    pub fn reject_optimization(&self, proposal_id: u64, reason: String) -> Result<(), String> {
+        if let Some(optimizer) = &self.consensus_optimizer {
+            optimizer.write().reject(proposal_id, reason)
+        } else {
+            Err("Consensus optimizer not enabled".to_string())
+        }
+    }
+
+    /// Apply the approved proposals whose activation height is reached
+    pub fn activate_optimizations(
+        &self,
+        chain_height: u64,
+    ) -> Result<Vec<consensus_optimizer::OptimizationSuggestion>, String> {
+        if let Some(optimizer) = &self.consensus_optimizer {
+            Ok(optimizer.write().activate_due(chain_height))
+        } else {
+            Err("Consensus optimizer not enabled".to_string())
+        }
+    }
+
+    /// Get recorded optimization proposals
+    pub fn get_optimization_proposals(
+        &self,
+    ) -> Result<Vec<consensus_optimizer::OptimizationProposal>, String> {
+        if let Some(optimizer) = &self.consensus_optimizer {
+            Ok(optimizer.read().proposals().to_vec())
         } else {
             Err("Consensus optimizer not enabled".to_string())
         }
@@ -205,6 +337,40 @@ impl Default for AIOracle {
 }
 
 // Re-export commonly used types
-pub use anomaly_detector::{AnomalyScore, AnomalySeverity, TransactionFeatures};
-pub use consensus_optimizer::{ConsensusParameters, NetworkMetrics, OptimizationSuggestion};
+pub use anomaly_detector::{AnomalyScore, AnomalySeverity, BlockAnomalyReport, TransactionFeatures};
+pub use feature_store::{BlockData, BlockTransaction, ChainFeatures, FeatureStore};
+pub use consensus_optimizer::{
+    ActivationNotice, ConsensusParameters, NetworkMetrics, OptimizationProposal, OptimizationSuggestion, ProposalState,
+};
 pub use contract_auditor::{AuditReport, Vulnerability, VulnerabilityType};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oracle_applies_suggestions_only_once_approved_and_due() {
+        let oracle = AIOracle::default();
+        let suggestion = OptimizationSuggestion {
+            parameter: "min_gas_price".to_string(),
+            current_value: 1000.0,
+            suggested_value: 2000.0,
+            confidence: 0.9,
+            rationale: "test".to_string(),
+            expected_improvement: 0.1,
+        };
+        let id = oracle.propose_optimization(suggestion, 100).unwrap();
+        assert!(oracle.activate_optimizations(100_000).unwrap().is_empty());
+        assert_eq!(oracle.get_consensus_parameters().unwrap().min_gas_price, 1000);
+
+        let notice = oracle.approve_optimization(id, 200).unwrap();
+        assert_eq!(notice.activation_height, 200 + consensus_optimizer::ACTIVATION_DELAY_BLOCKS);
+        assert!(oracle.activate_optimizations(notice.activation_height - 1).unwrap().is_empty());
+        assert_eq!(oracle.get_consensus_parameters().unwrap().min_gas_price, 1000);
+
+        assert_eq!(oracle.activate_optimizations(notice.activation_height).unwrap().len(), 1);
+        assert_eq!(oracle.get_consensus_parameters().unwrap().min_gas_price, 2000);
+        let proposals = oracle.get_optimization_proposals().unwrap();
+        assert_eq!(proposals[0].state, ProposalState::Activated { height: notice.activation_height });
+    }
+}
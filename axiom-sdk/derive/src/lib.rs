@@ -0,0 +1,68 @@
+//! Derive macros for `axiom-sdk`. Use them through the SDK, which
+//! re-exports them: the expansions refer to `::axiom_sdk`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Implement `axiom_sdk::abi::AxiomEvent` for a struct with named fields.
+///
+/// The event is named after the struct unless `#[axiom_event(name = "...")]`
+/// says otherwise. Every field's type must implement
+/// `axiom_sdk::abi::AbiType`; fields are encoded in declaration order.
+#[proc_macro_derive(AxiomEvent, attributes(axiom_event))]
+pub fn derive_axiom_event(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_axiom_event(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand_axiom_event(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+
+    let mut name = ident.to_string();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("axiom_event")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("expected `name = \"...\"`"))
+            }
+        })?;
+    }
+
+    let fields: Vec<_> = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => named.named.iter().collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new_spanned(ident, "AxiomEvent needs named fields"));
+            }
+        },
+        _ => return Err(syn::Error::new_spanned(ident, "AxiomEvent can only be derived for structs")),
+    };
+    let names: Vec<_> = fields.iter().filter_map(|f| f.ident.as_ref()).collect();
+    let types: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+
+    let abi = quote!(::axiom_sdk::abi);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #abi::AxiomEvent for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+
+            fn field_types() -> ::std::vec::Vec<&'static str> {
+                ::std::vec![#(<#types as #abi::AbiType>::ABI_NAME),*]
+            }
+
+            fn encode_fields(&self, out: &mut ::std::vec::Vec<u8>) {
+                #(#abi::AbiType::encode(&self.#names, out);)*
+            }
+
+            fn decode_fields(input: &mut &[u8]) -> ::std::result::Result<Self, #abi::AbiError> {
+                #(let #names = <#types as #abi::AbiType>::decode(input)?;)*
+                ::std::result::Result::Ok(Self { #(#names),* })
+            }
+        }
+    })
+}
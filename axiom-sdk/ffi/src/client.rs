@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use axiom_sdk as sdk;
+
+use crate::error::AxiomError;
+use crate::wallet::Transaction;
+
+/// How soon a transaction should be mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FeePriority {
+    Low,
+    Medium,
+    High,
+}
+
+impl From<FeePriority> for sdk::FeePriority {
+    fn from(priority: FeePriority) -> Self {
+        match priority {
+            FeePriority::Low => sdk::FeePriority::Low,
+            FeePriority::Medium => sdk::FeePriority::Medium,
+            FeePriority::High => sdk::FeePriority::High,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct FeeEstimate {
+    /// Satoshis per encoded byte.
+    pub fee_rate: f64,
+    /// Satoshis for a transaction of typical size.
+    pub fee: u64,
+    /// Transactions the rate was measured on; 0 means the node's floor.
+    pub based_on_txs: u64,
+}
+
+/// Where a transaction stands on the node's best chain.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Enum)]
+pub enum TxStatus {
+    Pending,
+    Confirmed { block_height: u64, block_hash: String, confirmations: u64 },
+    /// In a checkpointed block; it will not be reorganised out.
+    Finalized { block_height: u64, block_hash: String, confirmations: u64 },
+}
+
+impl From<sdk::TxStatus> for TxStatus {
+    fn from(status: sdk::TxStatus) -> Self {
+        match status {
+            sdk::TxStatus::Pending => TxStatus::Pending,
+            sdk::TxStatus::Confirmed(r) => {
+                TxStatus::Confirmed { block_height: r.block_height, block_hash: r.block_hash, confirmations: r.confirmations }
+            }
+            sdk::TxStatus::Finalized(r) => {
+                TxStatus::Finalized { block_height: r.block_height, block_hash: r.block_hash, confirmations: r.confirmations }
+            }
+        }
+    }
+}
+
+/// A step reported by [`Client::watch_transaction`].
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Enum)]
+pub enum TxProgress {
+    Pending,
+    Included { height: u64, block_hash: String },
+    Confirmed { confirmations: u64 },
+    Finalized,
+}
+
+impl From<sdk::TxProgress> for TxProgress {
+    fn from(progress: sdk::TxProgress) -> Self {
+        match progress {
+            sdk::TxProgress::Pending => TxProgress::Pending,
+            sdk::TxProgress::Included { height, block_hash } => TxProgress::Included { height, block_hash },
+            sdk::TxProgress::Confirmed(confirmations) => TxProgress::Confirmed { confirmations },
+            sdk::TxProgress::Finalized => TxProgress::Finalized,
+        }
+    }
+}
+
+/// Implemented in Kotlin or Swift to follow a transaction, e.g. to update
+/// a notification. Called from a Rust thread.
+#[uniffi::export(callback_interface)]
+pub trait TxProgressListener: Send + Sync {
+    fn on_progress(&self, progress: TxProgress);
+}
+
+fn parse_address(address: &str) -> Result<sdk::Address, AxiomError> {
+    address.parse().map_err(AxiomError::invalid_input)
+}
+
+fn parse_hash(hash: &str) -> Result<sdk::TxHash, AxiomError> {
+    sdk::TxHash::from_hex(hash).map_err(AxiomError::invalid_input)
+}
+
+/// RPC client for an Axiom node.
+///
+/// Every call is async: a `suspend fun` in Kotlin and an `async` function
+/// in Swift, so the app's main thread is never blocked.
+#[derive(uniffi::Object)]
+pub struct Client(sdk::AxiomClient);
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Client {
+    /// An async constructor, so `Client.connect(url)` in both languages
+    /// (Kotlin has no suspending constructors).
+    #[uniffi::constructor]
+    pub async fn connect(rpc_url: String) -> Result<Arc<Self>, AxiomError> {
+        Ok(Arc::new(Client(sdk::AxiomClient::new(&rpc_url).await?)))
+    }
+
+    pub async fn get_balance(&self, address: String) -> Result<u64, AxiomError> {
+        Ok(self.0.get_balance(&parse_address(&address)?).await?.as_satoshis())
+    }
+
+    /// The nonce the account's next transaction should carry, past those
+    /// already waiting in the mempool.
+    pub async fn get_pending_nonce(&self, address: String) -> Result<u64, AxiomError> {
+        Ok(self.0.get_pending_nonce(&parse_address(&address)?).await?)
+    }
+
+    pub async fn estimate_fee(&self, priority: FeePriority) -> Result<FeeEstimate, AxiomError> {
+        let estimate = self.0.estimate_fee(priority.into()).await?;
+        Ok(FeeEstimate { fee_rate: estimate.fee_rate, fee: estimate.fee, based_on_txs: estimate.based_on_txs })
+    }
+
+    /// Broadcast a signed transaction and return its hash.
+    pub async fn broadcast_transaction(&self, tx: Transaction) -> Result<String, AxiomError> {
+        Ok(self.0.broadcast_transaction(tx.try_into()?).await?.to_hex())
+    }
+
+    pub async fn get_transaction_status(&self, hash: String) -> Result<TxStatus, AxiomError> {
+        Ok(self.0.get_transaction_status(&parse_hash(&hash)?).await?.into())
+    }
+
+    pub async fn get_block_height(&self) -> Result<u64, AxiomError> {
+        Ok(self.0.get_block_height().await?)
+    }
+
+    /// Report each change in `hash`'s progress to `listener` until it has
+    /// `depth` confirmations or is finalized. Returns when the watch ends,
+    /// with [`AxiomError::Timeout`] if the SDK's deadline passes first.
+    pub async fn watch_transaction(
+        &self,
+        hash: String,
+        depth: u64,
+        listener: Box<dyn TxProgressListener>,
+    ) -> Result<(), AxiomError> {
+        let mut watcher = self.0.watch_transaction(&parse_hash(&hash)?, depth);
+        while let Some(progress) = watcher.next().await {
+            listener.on_progress(progress?.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_calls_map_errors_for_the_app() {
+        let client = Client::connect("http://127.0.0.1:9".to_string()).await.unwrap();
+        assert!(matches!(client.get_balance("not-an-address".into()).await, Err(AxiomError::InvalidInput { .. })));
+        assert!(matches!(client.get_transaction_status("zz".into()).await, Err(AxiomError::InvalidInput { .. })));
+        // Nothing listens on port 9.
+        assert!(matches!(client.get_block_height().await, Err(AxiomError::Network { .. })));
+    }
+}
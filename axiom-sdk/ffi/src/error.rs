@@ -0,0 +1,53 @@
+use axiom_sdk as sdk;
+
+/// Errors raised to Kotlin (`AxiomException`) and Swift (`AxiomError`).
+///
+/// A narrower set than the SDK's: apps branch on what they can act on and
+/// show `message` for the rest.
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum AxiomError {
+    /// An address, hash, key or amount the app passed in is malformed.
+    #[error("invalid input: {message}")]
+    InvalidInput { message: String },
+
+    /// The node could not be reached or answered with garbage; retry later.
+    #[error("network error: {message}")]
+    Network { message: String },
+
+    /// The node rejected the request. `code` is the node's stable error
+    /// code (see `axiom_sdk::ErrorCode`), 0 when it sent none.
+    #[error("node error {code}: {message}")]
+    Node { code: u32, message: String },
+
+    #[error("proof error: {message}")]
+    Proof { message: String },
+
+    /// A wait for confirmations ran out of time.
+    #[error("timed out at {confirmations} of {depth} confirmations")]
+    Timeout { confirmations: u64, depth: u64 },
+
+    #[error("{message}")]
+    Other { message: String },
+}
+
+impl AxiomError {
+    pub(crate) fn invalid_input(message: impl Into<String>) -> Self {
+        AxiomError::InvalidInput { message: message.into() }
+    }
+}
+
+impl From<sdk::AxiomError> for AxiomError {
+    fn from(err: sdk::AxiomError) -> Self {
+        use sdk::AxiomError as E;
+        let message = err.to_string();
+        match err {
+            E::InvalidAddress(_) | E::InsufficientBalance { .. } => AxiomError::InvalidInput { message },
+            E::Network(_) | E::Http(_) | E::Io(_) | E::InvalidResponse(_) => AxiomError::Network { message },
+            E::Node { code, message } => AxiomError::Node { code: code.as_u32(), message },
+            E::TransactionFailed(message) | E::SenderLimitReached(message) => AxiomError::Node { code: 0, message },
+            E::Proof(message) => AxiomError::Proof { message },
+            E::ConfirmationTimeout { confirmations, depth, .. } => AxiomError::Timeout { confirmations, depth },
+            _ => AxiomError::Other { message },
+        }
+    }
+}
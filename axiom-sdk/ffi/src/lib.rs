@@ -0,0 +1,84 @@
+//! # Axiom SDK for Kotlin and Swift
+//!
+//! UniFFI bindings of `axiom-sdk`, so Android and iOS wallets run the same
+//! Rust core as everything else: key generation, transaction building and
+//! signing, `prove_transaction`, and the node client.
+//!
+//! Build the library for the device (`cdylib` for Android, `staticlib` for
+//! iOS), then generate the foreign sources from it:
+//!
+//! ```text
+//! cargo run -p axiom-sdk-ffi --features bindgen --bin uniffi-bindgen -- \
+//!     generate --library target/release/libaxiom_sdk_ffi.so --language kotlin --out-dir out
+//! ```
+//!
+//! Client calls are async functions on the foreign side; progress of a
+//! broadcast transaction is pushed to a [`TxProgressListener`] callback.
+
+uniffi::setup_scaffolding!();
+
+mod client;
+mod error;
+mod wallet;
+
+use axiom_sdk as sdk;
+
+pub use client::{Client, FeeEstimate, FeePriority, TxProgress, TxProgressListener, TxStatus};
+pub use error::AxiomError;
+pub use wallet::{Transaction, Wallet};
+
+/// Private witness of a spend, for [`prove_transaction`].
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ProveTransactionInput {
+    pub initial_balance: u64,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ProveTransactionOutput {
+    /// Hex of the 512-bit BLAKE3-XOF digest.
+    pub digest_512: String,
+    pub proof_bytes: Vec<u8>,
+}
+
+/// Local Proof-of-Execution for a spend; see `axiom_sdk::prove_transaction`.
+#[uniffi::export]
+pub fn prove_transaction(input: ProveTransactionInput) -> Result<ProveTransactionOutput, AxiomError> {
+    let output = sdk::prove_transaction(&sdk::ProveTransactionInput {
+        initial_balance: input.initial_balance,
+        amount: input.amount,
+        fee: input.fee,
+        nonce: input.nonce,
+    })?;
+    Ok(ProveTransactionOutput { digest_512: output.digest_512, proof_bytes: output.proof_bytes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wallet_and_prover_across_the_boundary() {
+        let wallet = Wallet::new();
+        let restored = Wallet::from_secret_key(wallet.export_secret_key()).unwrap();
+        assert_eq!(restored.address(), wallet.address());
+        assert!(matches!(Wallet::from_secret_key(vec![1; 31]), Err(AxiomError::InvalidInput { .. })));
+
+        let to = Wallet::new().address();
+        let tx = wallet.create_transaction(to.clone(), 5_000, 25, 3).unwrap();
+        assert_eq!((tx.from.as_str(), tx.to.as_str(), tx.nonce), (wallet.address().as_str(), to.as_str(), 3));
+        assert_eq!(tx.signature.len(), 64);
+        // The record converts back to the transaction that was signed.
+        let back = sdk::Transaction::try_from(tx.clone()).unwrap();
+        assert_eq!(back.hash().to_hex(), tx.hash);
+        assert!(matches!(wallet.create_transaction("axm1bogus".into(), 1, 1, 0), Err(AxiomError::InvalidInput { .. })));
+
+        let input = ProveTransactionInput { initial_balance: 10_000, amount: 1_000, fee: 50, nonce: 1 };
+        let output = prove_transaction(input.clone()).unwrap();
+        assert_eq!(output.digest_512.len(), 128);
+        let broke = ProveTransactionInput { initial_balance: 10, ..input };
+        assert!(matches!(prove_transaction(broke), Err(AxiomError::Proof { .. })));
+    }
+}
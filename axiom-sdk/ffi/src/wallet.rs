@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use axiom_sdk as sdk;
+
+use crate::error::AxiomError;
+
+/// A signed (or unsigned, with an empty `signature`) transaction.
+/// Addresses are bech32m (`axm1...`) and `hash` is hex.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct Transaction {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+    pub timestamp: u64,
+    pub signature: Vec<u8>,
+}
+
+impl From<sdk::Transaction> for Transaction {
+    fn from(tx: sdk::Transaction) -> Self {
+        Transaction {
+            hash: tx.hash().to_hex(),
+            from: tx.from.to_string(),
+            to: tx.to.to_string(),
+            amount: tx.amount,
+            fee: tx.fee,
+            nonce: tx.nonce,
+            timestamp: tx.timestamp,
+            signature: tx.signature,
+        }
+    }
+}
+
+impl TryFrom<Transaction> for sdk::Transaction {
+    type Error = AxiomError;
+
+    /// `hash` is recomputed from the fields, not trusted.
+    fn try_from(tx: Transaction) -> Result<Self, AxiomError> {
+        Ok(sdk::Transaction {
+            from: tx.from.parse().map_err(AxiomError::invalid_input)?,
+            to: tx.to.parse().map_err(AxiomError::invalid_input)?,
+            amount: tx.amount,
+            fee: tx.fee,
+            nonce: tx.nonce,
+            timestamp: tx.timestamp,
+            signature: tx.signature,
+        })
+    }
+}
+
+/// An Ed25519 key pair. The secret key never leaves Rust unless
+/// [`export_secret_key`](Wallet::export_secret_key) is called.
+#[derive(Debug, uniffi::Object)]
+pub struct Wallet(sdk::Wallet);
+
+#[uniffi::export]
+impl Wallet {
+    /// A new random wallet.
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Wallet(sdk::Wallet::new()))
+    }
+
+    /// Restore a wallet from the 32 bytes `export_secret_key` returned.
+    #[uniffi::constructor]
+    pub fn from_secret_key(secret_key: Vec<u8>) -> Result<Arc<Self>, AxiomError> {
+        let secret_key: [u8; 32] = secret_key
+            .try_into()
+            .map_err(|key: Vec<u8>| AxiomError::invalid_input(format!("secret key is {} bytes, expected 32", key.len())))?;
+        Ok(Arc::new(Wallet(sdk::Wallet::from_secret_key(secret_key)?)))
+    }
+
+    /// Keep it in the platform keystore (Android Keystore, iOS Keychain).
+    pub fn export_secret_key(&self) -> Vec<u8> {
+        self.0.export_secret_key().to_vec()
+    }
+
+    /// Checksummed bech32m address (`axm1...`).
+    pub fn address(&self) -> String {
+        self.0.address().to_string()
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.0.verifying_key().to_bytes().to_vec()
+    }
+
+    /// Build and sign a transfer. Take `nonce` from
+    /// `Client.get_pending_nonce` and `fee` from `Client.estimate_fee`.
+    pub fn create_transaction(&self, to: String, amount: u64, fee: u64, nonce: u64) -> Result<Transaction, AxiomError> {
+        Ok(self.0.create_transaction_with_nonce(&to, amount, fee, nonce)?.into())
+    }
+
+    /// Ed25519 signature of `data`.
+    pub fn sign(&self, data: Vec<u8>) -> Vec<u8> {
+        self.0.sign(&data).to_bytes().to_vec()
+    }
+}
@@ -0,0 +1,332 @@
+//! Typed contract events.
+//!
+//! A contract reports what it did by committing events to its zkVM
+//! journal, one entry per event:
+//!
+//! ```text
+//! topic (32 bytes) | data length (u32 LE) | data
+//! ```
+//!
+//! `topic` is the BLAKE3 hash of the event's signature, such as
+//! `Transfer(address,address,u64)`, and `data` holds its fields in
+//! declaration order: integers little-endian, byte strings and strings
+//! behind a u32 LE length. `#[derive(AxiomEvent)]` writes both for a
+//! struct, so the guest that emits an event and the wallet that reads it
+//! share one definition:
+//!
+//! ```
+//! use axiom_sdk::abi::{AxiomEvent, JournalEntry};
+//! use axiom_sdk::Address;
+//!
+//! #[derive(AxiomEvent, Debug, PartialEq)]
+//! struct Transfer {
+//!     from: Address,
+//!     to: Address,
+//!     amount: u64,
+//! }
+//!
+//! let transfer = Transfer { from: Address([1; 32]), to: Address([2; 32]), amount: 500 };
+//! let journal = transfer.to_entry().encode();
+//!
+//! let entries = JournalEntry::parse_journal(&journal).unwrap();
+//! assert_eq!(Transfer::from_entry(&entries[0]).unwrap(), Some(transfer));
+//! ```
+//!
+//! Events a node recorded are fetched with
+//! [`AxiomClient::get_contract_events`](crate::AxiomClient::get_contract_events)
+//! and decoded with [`ContractEvent::decode`].
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::types::Address;
+
+pub use axiom_sdk_derive::AxiomEvent;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AbiError {
+    #[error("journal ends {missing} bytes early")]
+    Truncated { missing: usize },
+    #[error("{0} trailing bytes after the event")]
+    TrailingBytes(usize),
+    #[error("invalid {kind}: {reason}")]
+    InvalidValue { kind: &'static str, reason: String },
+}
+
+/// A field type with a fixed encoding in event data.
+pub trait AbiType: Sized {
+    /// Name used in event signatures.
+    const ABI_NAME: &'static str;
+
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Read a value from the front of `input`, advancing it.
+    fn decode(input: &mut &[u8]) -> Result<Self, AbiError>;
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], AbiError> {
+    if input.len() < len {
+        return Err(AbiError::Truncated { missing: len - input.len() });
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+fn take_len(input: &mut &[u8]) -> Result<usize, AbiError> {
+    Ok(u32::decode(input)? as usize)
+}
+
+fn put_len(len: usize, out: &mut Vec<u8>) {
+    (len as u32).encode(out);
+}
+
+macro_rules! abi_int {
+    ($($ty:ty),*) => {$(
+        impl AbiType for $ty {
+            const ABI_NAME: &'static str = stringify!($ty);
+
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn decode(input: &mut &[u8]) -> Result<Self, AbiError> {
+                let bytes = take(input, std::mem::size_of::<$ty>())?;
+                Ok(<$ty>::from_le_bytes(bytes.try_into().expect("length checked")))
+            }
+        }
+    )*};
+}
+
+abi_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl AbiType for bool {
+    const ABI_NAME: &'static str = "bool";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, AbiError> {
+        match u8::decode(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(AbiError::InvalidValue { kind: "bool", reason: format!("byte {}", b) }),
+        }
+    }
+}
+
+impl AbiType for [u8; 32] {
+    const ABI_NAME: &'static str = "bytes32";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, AbiError> {
+        Ok(take(input, 32)?.try_into().expect("length checked"))
+    }
+}
+
+impl AbiType for Address {
+    const ABI_NAME: &'static str = "address";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, AbiError> {
+        Ok(Address(<[u8; 32]>::decode(input)?))
+    }
+}
+
+impl AbiType for Vec<u8> {
+    const ABI_NAME: &'static str = "bytes";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        put_len(self.len(), out);
+        out.extend_from_slice(self);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, AbiError> {
+        let len = take_len(input)?;
+        Ok(take(input, len)?.to_vec())
+    }
+}
+
+impl AbiType for String {
+    const ABI_NAME: &'static str = "string";
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        put_len(self.len(), out);
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, AbiError> {
+        let bytes = Vec::<u8>::decode(input)?;
+        String::from_utf8(bytes).map_err(|e| AbiError::InvalidValue { kind: "string", reason: e.to_string() })
+    }
+}
+
+/// An event type, usually implemented with `#[derive(AxiomEvent)]`.
+pub trait AxiomEvent: Sized {
+    const NAME: &'static str;
+
+    /// [`AbiType::ABI_NAME`] of each field, in order.
+    fn field_types() -> Vec<&'static str>;
+
+    fn encode_fields(&self, out: &mut Vec<u8>);
+
+    fn decode_fields(input: &mut &[u8]) -> Result<Self, AbiError>;
+
+    /// `Name(type,type,...)`, the string the topic is hashed from.
+    fn signature() -> String {
+        format!("{}({})", Self::NAME, Self::field_types().join(","))
+    }
+
+    fn topic() -> [u8; 32] {
+        blake3::hash(Self::signature().as_bytes()).into()
+    }
+
+    /// The journal entry a contract commits for this event.
+    fn to_entry(&self) -> JournalEntry {
+        let mut data = Vec::new();
+        self.encode_fields(&mut data);
+        JournalEntry { topic: Self::topic(), data }
+    }
+
+    /// Decode `entry`, or `None` if it is a different event.
+    fn from_entry(entry: &JournalEntry) -> Result<Option<Self>, AbiError> {
+        if entry.topic != Self::topic() {
+            return Ok(None);
+        }
+        let mut input = entry.data.as_slice();
+        let event = Self::decode_fields(&mut input)?;
+        if !input.is_empty() {
+            return Err(AbiError::TrailingBytes(input.len()));
+        }
+        Ok(Some(event))
+    }
+}
+
+/// One event in a zkVM journal, not yet decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub topic: [u8; 32],
+    pub data: Vec<u8>,
+}
+
+impl JournalEntry {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(36 + self.data.len());
+        self.topic.encode(&mut out);
+        self.data.encode(&mut out);
+        out
+    }
+
+    /// Split a whole journal into its entries.
+    pub fn parse_journal(mut journal: &[u8]) -> Result<Vec<JournalEntry>, AbiError> {
+        let mut entries = Vec::new();
+        while !journal.is_empty() {
+            let topic = <[u8; 32]>::decode(&mut journal)?;
+            let data = Vec::<u8>::decode(&mut journal)?;
+            entries.push(JournalEntry { topic, data });
+        }
+        Ok(entries)
+    }
+}
+
+/// Every `E` in `entries`, skipping other events.
+pub fn decode_events<E: AxiomEvent>(entries: &[JournalEntry]) -> Result<Vec<E>, AbiError> {
+    entries.iter().filter_map(|entry| E::from_entry(entry).transpose()).collect()
+}
+
+/// An event as recorded by a node, served at
+/// `/v1/contracts/<id>/events`. Hashes and data are hex-encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractEvent {
+    /// Image ID of the contract that emitted it.
+    pub contract_id: String,
+    pub block_height: u64,
+    pub tx_hash: String,
+    /// Position of the entry in the transaction's journal.
+    pub index: u32,
+    pub topic: String,
+    pub data: String,
+}
+
+impl ContractEvent {
+    pub fn entry(&self) -> Result<JournalEntry, AbiError> {
+        let invalid = |kind, e: hex::FromHexError| AbiError::InvalidValue { kind, reason: e.to_string() };
+        let topic = hex::decode(&self.topic)
+            .map_err(|e| invalid("topic", e))?
+            .try_into()
+            .map_err(|t: Vec<u8>| AbiError::InvalidValue { kind: "topic", reason: format!("{} bytes", t.len()) })?;
+        let data = hex::decode(&self.data).map_err(|e| invalid("data", e))?;
+        Ok(JournalEntry { topic, data })
+    }
+
+    /// Decode as `E`, or `None` if it is a different event.
+    pub fn decode<E: AxiomEvent>(&self) -> Result<Option<E>, AbiError> {
+        E::from_entry(&self.entry()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(AxiomEvent, Debug, Clone, PartialEq)]
+    struct Transfer {
+        from: Address,
+        to: Address,
+        amount: u64,
+    }
+
+    #[derive(AxiomEvent, Debug, Clone, PartialEq)]
+    #[axiom_event(name = "Listed")]
+    struct NameListed {
+        name: String,
+        payload: Vec<u8>,
+        active: bool,
+        delta: i64,
+    }
+
+    #[test]
+    fn test_derived_events_round_trip_through_a_journal() {
+        assert_eq!(Transfer::signature(), "Transfer(address,address,u64)");
+        assert_eq!(NameListed::signature(), "Listed(string,bytes,bool,i64)");
+
+        let transfer = Transfer { from: Address([1; 32]), to: Address([2; 32]), amount: 500 };
+        let listed = NameListed { name: "axiom.dex".to_string(), payload: vec![9, 8], active: true, delta: -3 };
+        let entry = transfer.to_entry();
+        assert_eq!(entry.data.len(), 32 + 32 + 8);
+
+        let mut journal = entry.encode();
+        journal.extend(listed.to_entry().encode());
+        journal.extend(transfer.to_entry().encode());
+        let entries = JournalEntry::parse_journal(&journal).unwrap();
+        assert_eq!(decode_events::<Transfer>(&entries).unwrap(), vec![transfer.clone(), transfer.clone()]);
+        assert_eq!(decode_events::<NameListed>(&entries).unwrap(), vec![listed]);
+
+        let served = ContractEvent {
+            contract_id: hex::encode([5u8; 32]),
+            block_height: 12,
+            tx_hash: hex::encode([6u8; 32]),
+            index: 0,
+            topic: hex::encode(entries[0].topic),
+            data: hex::encode(&entries[0].data),
+        };
+        assert_eq!(served.decode::<Transfer>().unwrap(), Some(transfer));
+        assert_eq!(served.decode::<NameListed>().unwrap(), None);
+
+        // Short data, extra data and a cut journal are errors, not garbage.
+        let mut short = entries[0].clone();
+        short.data.pop();
+        assert_eq!(Transfer::from_entry(&short), Err(AbiError::Truncated { missing: 1 }));
+        let mut long = entries[0].clone();
+        long.data.push(0);
+        assert_eq!(Transfer::from_entry(&long), Err(AbiError::TrailingBytes(1)));
+        assert!(JournalEntry::parse_journal(&journal[..journal.len() - 1]).is_err());
+    }
+}
@@ -1,8 +1,30 @@
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use crate::types::{Address, Balance, TxHash};
+use crate::abi::ContractEvent;
+use crate::fees::{FeeEstimate, FeePriority, FeeStats};
+use crate::types::{AccountInfo, AccountTx, Address, Balance, BridgeQuote, HistoryFilter, HistoryPage, TxHash};
 use crate::transaction::Transaction;
+use crate::receipt::{TransactionReceipt, TxStatus};
+use crate::supply::{Checkpoint, Checkpoints, SupplyAttestation, SupplyProof};
+use crate::outbox::{NonceGap, Outbox, ResubmitOutcome, Resubmission};
+use crate::wallet::Wallet;
+use crate::psat::Psat;
+use crate::signer::address_of;
 use crate::error::{AxiomError, Result};
+use crate::runtime::{self, Instant};
+
+/// Target block interval; used to size confirmation timeouts.
+pub const TARGET_BLOCK_TIME: Duration = Duration::from_secs(1800);
+
+/// How often [`AxiomClient::wait_for_confirmation`] polls the node.
+pub const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long the lowest unconfirmed nonce may wait before
+/// [`AxiomClient::find_nonce_gap`] treats it as dropped.
+pub const NONCE_STALL_AFTER: Duration = Duration::from_secs(2 * TARGET_BLOCK_TIME.as_secs());
 
 /// RPC client for Axiom network
 pub struct AxiomClient {
@@ -13,9 +35,13 @@ pub struct AxiomClient {
 impl AxiomClient {
     /// Create new RPC client
     pub async fn new(rpc_url: &str) -> Result<Self> {
+        // In a browser, `fetch` applies its own timeouts.
+        #[cfg(not(target_arch = "wasm32"))]
         let client = Client::builder()
             .timeout(std::time::Duration::from_secs(30))
             .build()?;
+        #[cfg(target_arch = "wasm32")]
+        let client = Client::builder().build()?;
         
         Ok(Self {
             rpc_url: rpc_url.to_string(),
@@ -23,66 +49,101 @@ impl AxiomClient {
         })
     }
     
+    /// Pass a successful response through; turn a failed one into the
+    /// node's coded error, or a plain HTTP error if it sent none.
+    async fn check(resp: reqwest::Response) -> Result<reqwest::Response> {
+        if resp.status().is_success() {
+            return Ok(resp);
+        }
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        Err(AxiomError::from_body(&body).unwrap_or_else(|| AxiomError::Network(format!("HTTP {}", status))))
+    }
+
     /// Get balance for an address
     pub async fn get_balance(&self, address: &Address) -> Result<Balance> {
-        #[derive(Serialize)]
-        struct BalanceRequest {
-            address: String,
-        }
-        
+        Ok(Balance(self.get_account(address).await?.balance))
+    }
+    
+    /// Get current nonce for an address: the number of transactions it has
+    /// sent, which is the nonce its next transaction must carry.
+    pub async fn get_nonce(&self, address: &Address) -> Result<u64> {
+        Ok(self.get_account(address).await?.nonce)
+    }
+
+    /// The nonce a new transaction from `address` should carry: past its
+    /// transactions already waiting in the node's mempool. After a
+    /// rejection whose code [`is_nonce_error`](crate::ErrorCode::is_nonce_error),
+    /// sign again with this one.
+    pub async fn get_pending_nonce(&self, address: &Address) -> Result<u64> {
+        let account = self.get_account(address).await?;
+        Ok(account.pending_nonce.unwrap_or(account.nonce).max(account.nonce))
+    }
+
+    /// Balance, nonce and confirmed transaction count in one request, served
+    /// from the node's account index.
+    pub async fn get_account(&self, address: &Address) -> Result<AccountInfo> {
+        let resp = self.client
+            .get(format!("{}/v1/account/{}", self.rpc_url, address.to_hex()))
+            .send()
+            .await?;
+
+        let resp = Self::check(resp).await?;
+
+        Ok(resp.json().await?)
+    }
+
+    /// Confirmed transactions touching `address`, newest first, `limit` at
+    /// a time (the node caps pages at 100).
+    pub async fn get_account_transactions(&self, address: &Address, offset: usize, limit: usize) -> Result<Vec<AccountTx>> {
         #[derive(Deserialize)]
-        struct BalanceResponse {
-            balance: u64,
+        struct AccountTxsResponse {
+            txs: Vec<AccountTx>,
         }
-        
-        let req = BalanceRequest {
-            address: address.to_hex(),
-        };
-        
+
         let resp = self.client
-            .post(format!("{}/balance", self.rpc_url))
-            .json(&req)
+            .get(format!("{}/v1/account/{}/txs", self.rpc_url, address.to_hex()))
+            .query(&[("offset", offset), ("limit", limit)])
             .send()
             .await?;
-        
-        if !resp.status().is_success() {
-            return Err(AxiomError::Network(format!("HTTP {}", resp.status())));
-        }
-        
-        let balance_resp: BalanceResponse = resp.json().await?;
-        Ok(Balance(balance_resp.balance))
+
+        let resp = Self::check(resp).await?;
+
+        let page: AccountTxsResponse = resp.json().await?;
+        Ok(page.txs)
     }
     
-    /// Get current nonce for an address
-    pub async fn get_nonce(&self, address: &Address) -> Result<u64> {
-        #[derive(Serialize)]
-        struct NonceRequest {
-            address: String,
+    /// One page of `address`'s history from the node's address index:
+    /// transfers and mining rewards, newest first, with confirmation
+    /// counts. Start with `cursor` `None`, then pass each page's
+    /// `next_cursor` until it is `None`. The node caps `limit` at 100.
+    pub async fn get_transactions(
+        &self,
+        address: &Address,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: HistoryFilter,
+    ) -> Result<HistoryPage> {
+        let mut query = vec![("limit", limit.to_string())];
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor.to_string()));
         }
-        
-        #[derive(Deserialize)]
-        struct NonceResponse {
-            nonce: u64,
+        if filter != HistoryFilter::All {
+            let direction = serde_json::to_value(filter)?;
+            query.push(("direction", direction.as_str().unwrap_or_default().to_string()));
         }
-        
-        let req = NonceRequest {
-            address: address.to_hex(),
-        };
-        
+
         let resp = self.client
-            .post(format!("{}/nonce", self.rpc_url))
-            .json(&req)
+            .get(format!("{}/v1/account/{}/history", self.rpc_url, address.to_hex()))
+            .query(&query)
             .send()
             .await?;
-        
-        if !resp.status().is_success() {
-            return Err(AxiomError::Network(format!("HTTP {}", resp.status())));
-        }
-        
-        let nonce_resp: NonceResponse = resp.json().await?;
-        Ok(nonce_resp.nonce)
+
+        let resp = Self::check(resp).await?;
+
+        Ok(resp.json().await?)
     }
-    
+
     /// Broadcast a transaction to the network
     pub async fn broadcast_transaction(&self, tx: Transaction) -> Result<TxHash> {
         #[derive(Deserialize)]
@@ -98,7 +159,8 @@ impl AxiomClient {
         
         if !resp.status().is_success() {
             let error_text = resp.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(AxiomError::TransactionFailed(error_text));
+            return Err(AxiomError::from_body(&error_text)
+                .unwrap_or_else(|| AxiomError::from_rejection(error_text)));
         }
         
         let tx_resp: TxResponse = resp.json().await?;
@@ -106,6 +168,25 @@ impl AxiomClient {
             .map_err(|e| AxiomError::InvalidResponse(e))
     }
     
+    /// Build a PSAT for a spend from the account of `public_key`, filling
+    /// in its current balance and next nonce for the offline signer.
+    pub async fn prepare_psat(
+        &self,
+        chain_id: u64,
+        public_key: [u8; 32],
+        to: &str,
+        amount: u64,
+        fee: u64,
+    ) -> Result<Psat> {
+        let account = self.get_account(&address_of(&public_key)).await?;
+        Psat::for_key(chain_id, public_key, to, amount, fee, account.nonce, account.balance)
+    }
+
+    /// Finalize a signed and proved PSAT and broadcast the transaction.
+    pub async fn broadcast_psat(&self, psat: &Psat) -> Result<TxHash> {
+        self.broadcast_transaction(psat.finalize()?).await
+    }
+
     /// Get transaction by hash
     pub async fn get_transaction(&self, hash: &TxHash) -> Result<Option<Transaction>> {
         #[derive(Serialize)]
@@ -127,14 +208,279 @@ impl AxiomClient {
             return Ok(None);
         }
         
-        if !resp.status().is_success() {
-            return Err(AxiomError::Network(format!("HTTP {}", resp.status())));
-        }
+        let resp = Self::check(resp).await?;
         
         let tx: Transaction = resp.json().await?;
         Ok(Some(tx))
     }
     
+    /// Get the confirmation receipt for a transaction, or `None` while it
+    /// is unconfirmed. Call [`TransactionReceipt::verify`] before relying
+    /// on it.
+    pub async fn get_transaction_receipt(&self, hash: &TxHash) -> Result<Option<TransactionReceipt>> {
+        let resp = self.client
+            .get(format!("{}/v1/tx/{}/receipt", self.rpc_url, hash.to_hex()))
+            .send()
+            .await?;
+
+        if resp.status() == 404 {
+            return Ok(None);
+        }
+
+        let resp = Self::check(resp).await?;
+
+        let receipt: TransactionReceipt = resp.json().await?;
+        Ok(Some(receipt))
+    }
+
+    /// Where `hash` stands on the node's best chain.
+    pub async fn get_transaction_status(&self, hash: &TxHash) -> Result<TxStatus> {
+        Ok(TxStatus::from_receipt(self.get_transaction_receipt(hash).await?))
+    }
+
+    /// Wait until `hash` has `depth` confirmations (or is finalized) and
+    /// return its status. Gives up after twice the expected time for
+    /// `depth` blocks.
+    pub async fn wait_for_confirmation(&self, hash: &TxHash, depth: u64) -> Result<TxStatus> {
+        let timeout = TARGET_BLOCK_TIME * 2 * (depth.max(1) as u32 + 1);
+        self.wait_for_confirmation_with(hash, depth, CONFIRMATION_POLL_INTERVAL, timeout).await
+    }
+
+    /// [`wait_for_confirmation`](Self::wait_for_confirmation) with explicit
+    /// polling and timeout.
+    ///
+    /// The depth is re-read from the node on every poll, so a reorg starts
+    /// the count again: a transaction moved to a new block reports that
+    /// block's confirmations, and one dropped back to the mempool reports
+    /// [`TxStatus::Pending`] until it is mined again. Failed polls are
+    /// retried until the deadline.
+    pub async fn wait_for_confirmation_with(
+        &self,
+        hash: &TxHash,
+        depth: u64,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<TxStatus> {
+        let deadline = Instant::now() + timeout;
+        let mut confirmations = 0;
+        loop {
+            match self.get_transaction_status(hash).await {
+                Ok(status) if status.reached(depth) => return Ok(status),
+                Ok(status) => confirmations = status.confirmations(),
+                Err(AxiomError::Http(_)) | Err(AxiomError::Network(_)) => {}
+                Err(e) => return Err(e),
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(AxiomError::ConfirmationTimeout {
+                    tx_hash: hash.to_hex(),
+                    confirmations,
+                    depth,
+                });
+            }
+            runtime::sleep(poll_interval.min(deadline - now)).await;
+        }
+    }
+
+    /// Compare `outbox` with the node's confirmed nonce for `address`.
+    /// Transactions the chain has passed are pruned from the outbox. A gap
+    /// is reported when a nonce is missing from the outbox or the lowest
+    /// recorded one has waited longer than [`NONCE_STALL_AFTER`].
+    pub async fn find_nonce_gap(&self, outbox: &mut Outbox, address: &Address) -> Result<Option<NonceGap>> {
+        let next_nonce = self.get_nonce(address).await?;
+        if outbox.prune(address, next_nonce) > 0 {
+            outbox.save()?;
+        }
+        let Some(gap) = outbox.gap(address, next_nonce) else {
+            return Ok(None);
+        };
+        let now = runtime::unix_now();
+        let head_age = gap.stalled.first().map_or(0, |tx| now.saturating_sub(tx.timestamp));
+        if gap.missing.contains(&next_nonce) || head_age >= NONCE_STALL_AFTER.as_secs() {
+            Ok(Some(gap))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Unblock `address` by resending its stalled transactions from
+    /// `outbox`, lowest nonce first.
+    ///
+    /// `approve` sees every [`Resubmission`] before it is broadcast and
+    /// can refuse it. A transaction the node rejects is rebuilt with a
+    /// fresh timestamp and signature when `wallet` is the sender's, and
+    /// the replacement is recorded in the outbox. The repair stops at the
+    /// first nonce it cannot fill, since everything after it would stall
+    /// again; the outcome for each nonce attempted is returned in order.
+    pub async fn repair_nonce_gap(
+        &self,
+        outbox: &mut Outbox,
+        address: &Address,
+        wallet: Option<&Wallet>,
+        mut approve: impl FnMut(&Resubmission) -> bool,
+    ) -> Result<Vec<(u64, ResubmitOutcome)>> {
+        let Some(gap) = self.find_nonce_gap(outbox, address).await? else {
+            return Ok(Vec::new());
+        };
+        let last = gap.stalled.last().map_or(gap.next_nonce, |tx| tx.nonce);
+        let wallet = wallet.filter(|w| w.address() == *address);
+
+        let mut outcomes = Vec::new();
+        for nonce in gap.next_nonce..=last {
+            let outcome = match outbox.get(address, nonce).cloned() {
+                None => ResubmitOutcome::Missing,
+                Some(original) => self.resubmit(outbox, original, wallet, &mut approve).await?,
+            };
+            let done = matches!(outcome, ResubmitOutcome::Resent(_) | ResubmitOutcome::Rebuilt(_));
+            outcomes.push((nonce, outcome));
+            if !done {
+                break;
+            }
+        }
+        Ok(outcomes)
+    }
+
+    async fn resubmit(
+        &self,
+        outbox: &mut Outbox,
+        original: Transaction,
+        wallet: Option<&Wallet>,
+        approve: &mut impl FnMut(&Resubmission) -> bool,
+    ) -> Result<ResubmitOutcome> {
+        let resend = Resubmission::Resend(original.clone());
+        if !approve(&resend) {
+            return Ok(ResubmitOutcome::Declined);
+        }
+        let reason = match self.broadcast_transaction(original.clone()).await {
+            Ok(hash) => return Ok(ResubmitOutcome::Resent(hash)),
+            Err(AxiomError::TransactionFailed(reason)) => reason,
+            Err(e) => return Err(e),
+        };
+        let Some(wallet) = wallet else {
+            return Ok(ResubmitOutcome::Rejected(reason));
+        };
+
+        let replacement = wallet.create_transaction_with_nonce(
+            &original.to.to_string(), original.amount, original.fee, original.nonce,
+        )?;
+        let rebuild = Resubmission::Rebuild { original, replacement: replacement.clone(), reason };
+        if !approve(&rebuild) {
+            return Ok(ResubmitOutcome::Declined);
+        }
+        match self.broadcast_transaction(replacement.clone()).await {
+            Ok(hash) => {
+                outbox.record(&replacement)?;
+                Ok(ResubmitOutcome::Rebuilt(hash))
+            }
+            Err(AxiomError::TransactionFailed(reason)) => Ok(ResubmitOutcome::Rejected(reason)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fetch the node's supply proof and check it locally (see
+    /// [`crate::supply`]). Errors if the node has no proof to serve yet.
+    pub async fn verify_supply(&self) -> Result<SupplyAttestation> {
+        let resp = self.client
+            .get(format!("{}/v1/supply/proof", self.rpc_url))
+            .send()
+            .await?;
+
+        if resp.status() == 404 {
+            return Err(AxiomError::Proof("node has no supply proof yet".to_string()));
+        }
+
+        let resp = Self::check(resp).await?;
+
+        let proof: SupplyProof = resp.json().await?;
+        proof.attest()
+            .ok_or_else(|| AxiomError::InvalidResponse("empty supply proof".to_string()))
+    }
+
+    /// The node's final Trust Pulse checkpoints, lowest first. A light
+    /// client should only trust ones it can confirm elsewhere.
+    pub async fn get_checkpoints(&self) -> Result<Vec<Checkpoint>> {
+        let resp = self.client
+            .get(format!("{}/v1/checkpoints", self.rpc_url))
+            .send()
+            .await?;
+        let resp = Self::check(resp).await?;
+        let body: Checkpoints = resp.json().await?;
+        Ok(body.checkpoints)
+    }
+
+    /// [`verify_supply`](Self::verify_supply) starting from a trusted
+    /// `checkpoint` instead of genesis and the node's receipt check.
+    pub async fn verify_supply_from(&self, checkpoint: &Checkpoint) -> Result<SupplyAttestation> {
+        let resp = self.client
+            .get(format!("{}/v1/supply/proof", self.rpc_url))
+            .send()
+            .await?;
+
+        if resp.status() == 404 {
+            return Err(AxiomError::Proof("node has no supply proof yet".to_string()));
+        }
+
+        let resp = Self::check(resp).await?;
+
+        let proof: SupplyProof = resp.json().await?;
+        proof.attest_from(checkpoint)
+            .ok_or_else(|| AxiomError::InvalidResponse("empty supply proof".to_string()))
+    }
+
+    /// Quote the fee for bridging `amount` satoshis from chain `from` to
+    /// chain `to` (e.g. `"Axiom"` to `"Ethereum"`). The quote is priced
+    /// from current gas; refetch it once [`BridgeQuote::is_expired`].
+    pub async fn get_bridge_quote(&self, amount: u64, from: &str, to: &str) -> Result<BridgeQuote> {
+        let resp = self.client
+            .get(format!("{}/v1/bridge/quote", self.rpc_url))
+            .query(&[("amount", amount.to_string().as_str()), ("from", from), ("to", to)])
+            .send()
+            .await?;
+
+        let resp = Self::check(resp).await?;
+
+        Ok(resp.json().await?)
+    }
+
+    /// Events emitted by contract `contract_id` (hex image ID) in blocks
+    /// `heights`, oldest first. Decode each with
+    /// [`ContractEvent::decode`]. Nodes without contract execution answer
+    /// 404, reported as an HTTP error.
+    pub async fn get_contract_events(&self, contract_id: &str, heights: RangeInclusive<u64>) -> Result<Vec<ContractEvent>> {
+        #[derive(Deserialize)]
+        struct EventsResponse {
+            events: Vec<ContractEvent>,
+        }
+
+        let resp = self.client
+            .get(format!("{}/v1/contracts/{}/events", self.rpc_url, contract_id))
+            .query(&[("from", *heights.start()), ("to", *heights.end())])
+            .send()
+            .await?;
+
+        let resp = Self::check(resp).await?;
+
+        let body: EventsResponse = resp.json().await?;
+        Ok(body.events)
+    }
+
+    /// Fee-rate percentiles of the transactions in the node's recent blocks.
+    pub async fn get_fee_stats(&self) -> Result<FeeStats> {
+        let resp = self.client
+            .get(format!("{}/v1/fees", self.rpc_url))
+            .send()
+            .await?;
+
+        let resp = Self::check(resp).await?;
+
+        Ok(resp.json().await?)
+    }
+
+    /// A fee for `priority`, priced from what recent blocks included.
+    pub async fn estimate_fee(&self, priority: FeePriority) -> Result<FeeEstimate> {
+        Ok(self.get_fee_stats().await?.estimate(priority))
+    }
+
     /// Get current block height
     pub async fn get_block_height(&self) -> Result<u64> {
         #[derive(Deserialize)]
@@ -147,9 +493,7 @@ impl AxiomClient {
             .send()
             .await?;
         
-        if !resp.status().is_success() {
-            return Err(AxiomError::Network(format!("HTTP {}", resp.status())));
-        }
+        let resp = Self::check(resp).await?;
         
         let height_resp: HeightResponse = resp.json().await?;
         Ok(height_resp.height)
@@ -175,4 +519,17 @@ mod tests {
         let client = AxiomClient::new("http://localhost:8545").await;
         assert!(client.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_wait_for_confirmation_times_out() {
+        // Nothing listens on port 9, so every poll fails and the wait
+        // ends with the typed timeout rather than the transport error.
+        let client = AxiomClient::new("http://127.0.0.1:9").await.unwrap();
+        let hash = TxHash([7u8; 32]);
+        let err = client
+            .wait_for_confirmation_with(&hash, 3, Duration::from_millis(10), Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AxiomError::ConfirmationTimeout { confirmations: 0, depth: 3, .. }));
+    }
 }
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Axiom SDK error types
@@ -15,6 +16,12 @@ pub enum AxiomError {
     #[error("Transaction failed: {0}")]
     TransactionFailed(String),
     
+    /// The node refused the transaction because the sender already holds its
+    /// full quota of pending transactions or bytes. Retry once earlier
+    /// transactions from the same address have been mined.
+    #[error("Sender mempool limit reached: {0}")]
+    SenderLimitReached(String),
+    
     #[error("Serialization error: {0}")]
     Serialization(String),
 
@@ -32,7 +39,261 @@ pub enum AxiomError {
 
     #[error("Proof error: {0}")]
     Proof(String),
+
+    #[error("Multisig error: {0}")]
+    Multisig(String),
+
+    #[error("PSAT error: {0}")]
+    Psat(String),
+
+    #[error("Swap error: {0}")]
+    Swap(String),
+
+    /// A contract event or journal did not decode.
+    #[error("ABI error: {0}")]
+    Abi(#[from] crate::abi::AbiError),
+
+    /// An external signer failed, refused, or returned a bad signature.
+    #[error("Signer error: {0}")]
+    Signer(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The node rejected the request with a coded error. Match on `code`
+    /// rather than the message, which may change between releases.
+    #[error("Node error {code}: {message}")]
+    Node { code: ErrorCode, message: String },
+
+    /// [`AxiomClient::wait_for_confirmation`](crate::AxiomClient::wait_for_confirmation)
+    /// gave up before the transaction was deep enough.
+    #[error("Timed out waiting for {tx_hash}: {confirmations} of {depth} confirmations")]
+    ConfirmationTimeout { tx_hash: String, confirmations: u64, depth: u64 },
+}
+
+/// Stable error codes returned by the node, mirroring
+/// `axiom_core::error::ErrorCode`. The thousands digit names the subsystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    InvalidTransaction = 1000,
+    InsufficientFunds = 1001,
+    NonceTooLow = 1002,
+    NonceTooHigh = 1003,
+    InvalidSignature = 1004,
+    ZeroAmount = 1005,
+    FeeTooLow = 1006,
+    DuplicateTransaction = 1007,
+    TransactionTooLarge = 1008,
+    TransactionExpired = 1009,
+    DoubleSpend = 1010,
+    SenderLimitReached = 1011,
+    ReplacementUnderpriced = 1012,
+    NonceGap = 1013,
+    InvalidBlock = 2000,
+    BlockNotFound = 2001,
+    ConsensusFailure = 3000,
+    InvalidVdf = 3001,
+    InvalidPow = 3002,
+    InvalidProof = 4000,
+    ProofGenerationFailed = 4001,
+    InvalidKey = 4002,
+    CryptoError = 4003,
+    NetworkError = 5000,
+    PeerBanned = 5001,
+    MaxPeersReached = 5002,
+    StorageError = 6000,
+    StateCorruption = 6001,
+    AccountNotFound = 6002,
+    ThreatDetected = 7000,
+    ProposalRejected = 7001,
+    OracleFailure = 7002,
+    InvariantViolation = 7003,
+    ChainHalted = 7004,
+    BridgeError = 8000,
+    InvalidConfig = 9000,
+    WalletLocked = 9100,
+    InvalidPassword = 9101,
+    WalletError = 9102,
+    InvalidRequest = 10000,
+    MethodNotFound = 10001,
+    NotFound = 10002,
+    RateLimited = 10003,
+    Unauthorized = 10004,
+    Timeout = 10005,
+    /// Internal node failures, and codes this SDK version does not know.
+    Internal = 99999,
+}
+
+impl ErrorCode {
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_u32(code: u32) -> Option<Self> {
+        use ErrorCode::*;
+        Some(match code {
+            1000 => InvalidTransaction,
+            1001 => InsufficientFunds,
+            1002 => NonceTooLow,
+            1003 => NonceTooHigh,
+            1004 => InvalidSignature,
+            1005 => ZeroAmount,
+            1006 => FeeTooLow,
+            1007 => DuplicateTransaction,
+            1008 => TransactionTooLarge,
+            1009 => TransactionExpired,
+            1010 => DoubleSpend,
+            1011 => SenderLimitReached,
+            1012 => ReplacementUnderpriced,
+            1013 => NonceGap,
+            2000 => InvalidBlock,
+            2001 => BlockNotFound,
+            3000 => ConsensusFailure,
+            3001 => InvalidVdf,
+            3002 => InvalidPow,
+            4000 => InvalidProof,
+            4001 => ProofGenerationFailed,
+            4002 => InvalidKey,
+            4003 => CryptoError,
+            5000 => NetworkError,
+            5001 => PeerBanned,
+            5002 => MaxPeersReached,
+            6000 => StorageError,
+            6001 => StateCorruption,
+            6002 => AccountNotFound,
+            7000 => ThreatDetected,
+            7001 => ProposalRejected,
+            7002 => OracleFailure,
+            7003 => InvariantViolation,
+            7004 => ChainHalted,
+            8000 => BridgeError,
+            9000 => InvalidConfig,
+            9100 => WalletLocked,
+            9101 => InvalidPassword,
+            9102 => WalletError,
+            10000 => InvalidRequest,
+            10001 => MethodNotFound,
+            10002 => NotFound,
+            10003 => RateLimited,
+            10004 => Unauthorized,
+            10005 => Timeout,
+            99999 => Internal,
+            _ => return None,
+        })
+    }
+
+    /// Whether the transaction carried the wrong nonce: one already used,
+    /// or one past the sender's pending transactions. Sign it again with
+    /// [`AxiomClient::get_pending_nonce`](crate::AxiomClient::get_pending_nonce).
+    pub fn is_nonce_error(self) -> bool {
+        matches!(self, ErrorCode::NonceTooLow | ErrorCode::NonceTooHigh | ErrorCode::NonceGap)
+    }
+
+    /// Whether the same request may succeed if sent again later.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            ErrorCode::SenderLimitReached
+                | ErrorCode::NetworkError
+                | ErrorCode::RateLimited
+                | ErrorCode::Timeout
+                | ErrorCode::ChainHalted
+        )
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{}", self.as_u32())
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.as_u32())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(Self::from_u32(u32::deserialize(deserializer)?).unwrap_or(ErrorCode::Internal))
+    }
+}
+
+/// The node's JSON error body: `{"error": "<message>", "code": 1001}`.
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: String,
+    code: ErrorCode,
+}
+
+/// Prefix the node uses for per-sender mempool quota rejections.
+pub(crate) const SENDER_LIMIT_PREFIX: &str = "Mempool sender limit";
+
+impl AxiomError {
+    /// Classify a node rejection message returned by `/broadcast`.
+    pub(crate) fn from_rejection(message: String) -> Self {
+        if message.contains(SENDER_LIMIT_PREFIX) {
+            AxiomError::SenderLimitReached(message)
+        } else {
+            AxiomError::TransactionFailed(message)
+        }
+    }
+
+    /// Decode a coded error body from the node, if `body` is one.
+    pub(crate) fn from_body(body: &str) -> Option<Self> {
+        let ErrorBody { error, code } = serde_json::from_str(body).ok()?;
+        Some(match code {
+            ErrorCode::SenderLimitReached => AxiomError::SenderLimitReached(error),
+            code => AxiomError::Node { code, message: error },
+        })
+    }
+
+    /// The node's error code, for errors that carry one.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            AxiomError::Node { code, .. } => Some(*code),
+            AxiomError::SenderLimitReached(_) => Some(ErrorCode::SenderLimitReached),
+            AxiomError::InsufficientBalance { .. } => Some(ErrorCode::InsufficientFunds),
+            AxiomError::InvalidAddress(_) => Some(ErrorCode::InvalidRequest),
+            _ => None,
+        }
+    }
 }
 
 /// Result type for Axiom SDK operations
 pub type Result<T> = std::result::Result<T, AxiomError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_limit_rejection_is_classified() {
+        let msg = "Mempool sender limit: abcd has 64 pending transactions (max: 64)".to_string();
+        assert!(matches!(AxiomError::from_rejection(msg), AxiomError::SenderLimitReached(_)));
+        assert!(matches!(
+            AxiomError::from_rejection("Insufficient balance".into()),
+            AxiomError::TransactionFailed(_)
+        ));
+    }
+
+    #[test]
+    fn test_coded_node_errors() {
+        let err = AxiomError::from_body(r#"{"error": "Invalid transaction nonce: expected 5, got 3", "code": 1002}"#).unwrap();
+        assert_eq!(err.code(), Some(ErrorCode::NonceTooLow));
+        assert!(matches!(err, AxiomError::Node { code: ErrorCode::NonceTooLow, .. }));
+        assert!(err.code().unwrap().is_nonce_error());
+        let gap = AxiomError::from_body(r#"{"error": "Nonce gap: the sender's next nonce is 4, got 6", "code": 1013}"#).unwrap();
+        assert_eq!(gap.code(), Some(ErrorCode::NonceGap));
+        assert!(gap.code().unwrap().is_nonce_error() && !gap.code().unwrap().is_retryable());
+
+        let limited = AxiomError::from_body(r#"{"error": "Mempool sender limit: x", "code": 1011}"#).unwrap();
+        assert!(matches!(limited, AxiomError::SenderLimitReached(_)));
+        assert!(limited.code().unwrap().is_retryable());
+
+        let unknown = AxiomError::from_body(r#"{"error": "new failure", "code": 4242}"#).unwrap();
+        assert_eq!(unknown.code(), Some(ErrorCode::Internal));
+        assert!(AxiomError::from_body("Not Found").is_none());
+    }
+}
@@ -0,0 +1,100 @@
+//! Fee estimation from the node's fee market.
+//!
+//! Nodes serve `/v1/fees`: percentiles of the fee rates (satoshis per
+//! encoded byte) paid by transactions in their recent blocks. A
+//! [`FeePriority`] picks one of them, and [`FeeEstimate`] turns it into a
+//! fee for a transaction of typical size.
+
+use serde::{Deserialize, Serialize};
+
+/// How soon a transaction should be mined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeePriority {
+    /// The 25th-percentile recent rate; may wait through busy periods.
+    Low,
+    /// The median recent rate.
+    Medium,
+    /// The 90th-percentile recent rate.
+    High,
+}
+
+/// Fee rates of recently included transactions, from `/v1/fees`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeStats {
+    pub tip_height: u64,
+    pub window_blocks: u64,
+    pub tx_count: u64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+    /// Median encoded size of the transactions, in bytes.
+    pub median_tx_bytes: u64,
+}
+
+impl FeeStats {
+    /// Rate, in satoshis per byte, for `priority`.
+    pub fn rate(&self, priority: FeePriority) -> f64 {
+        match priority {
+            FeePriority::Low => self.p25,
+            FeePriority::Medium => self.p50,
+            FeePriority::High => self.p90,
+        }
+    }
+
+    pub fn estimate(&self, priority: FeePriority) -> FeeEstimate {
+        let fee_rate = self.rate(priority);
+        FeeEstimate { priority, fee_rate, fee: fee_for(fee_rate, self.median_tx_bytes), based_on_txs: self.tx_count }
+    }
+}
+
+/// Typical size of a transaction when the node has seen none to measure.
+pub const DEFAULT_TX_BYTES: u64 = 250;
+
+fn fee_for(fee_rate: f64, bytes: u64) -> u64 {
+    let bytes = if bytes == 0 { DEFAULT_TX_BYTES } else { bytes };
+    (fee_rate * bytes as f64).ceil() as u64
+}
+
+/// A fee to offer, from [`AxiomClient::estimate_fee`](crate::AxiomClient::estimate_fee).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    pub priority: FeePriority,
+    /// Satoshis per encoded byte.
+    pub fee_rate: f64,
+    /// Satoshis for a transaction of the median recent size.
+    pub fee: u64,
+    /// Transactions the rate was measured on; 0 means the node's floor.
+    pub based_on_txs: u64,
+}
+
+impl FeeEstimate {
+    /// Fee at this rate for a transaction of `bytes` encoded bytes.
+    pub fn fee_for_bytes(&self, bytes: u64) -> u64 {
+        fee_for(self.fee_rate, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priorities_pick_percentiles() {
+        let stats: FeeStats = serde_json::from_str(
+            r#"{"tip_height":500,"window_blocks":100,"tx_count":40,"p10":2.0,"p25":3.0,"p50":5.5,"p75":8.0,"p90":12.0,"median_tx_bytes":200}"#,
+        )
+        .unwrap();
+        let low = stats.estimate(FeePriority::Low);
+        let medium = stats.estimate(FeePriority::Medium);
+        let high = stats.estimate(FeePriority::High);
+        assert_eq!((low.fee, medium.fee, high.fee), (600, 1_100, 2_400));
+        assert_eq!(high.fee_for_bytes(1_000), 12_000);
+        assert_eq!(medium.based_on_txs, 40);
+
+        let quiet = FeeStats { tx_count: 0, median_tx_bytes: 0, p50: 1.0, ..stats };
+        assert_eq!(quiet.estimate(FeePriority::Medium).fee, DEFAULT_TX_BYTES);
+    }
+}
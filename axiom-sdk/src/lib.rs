@@ -4,18 +4,25 @@
 //!
 //! ## Features
 //! - ✅ Wallet management (create, import, export)
+//! - ✅ m-of-n multisig accounts with partial-signature exchange
+//! - ✅ Hardware wallet / external signer support via the `Signer` trait
+//! - ✅ Offline (air-gapped) signing with PSAT envelopes
 //! - ✅ Transaction creation with ZK-STARK privacy
 //! - ✅ Cross-chain bridge integration
-//! - ✅ View keys for compliance
+//! - ✅ HTLC atomic swaps driven by either counterparty
+//! - ✅ View keys and watch-only wallets for accounting and audits
 //! - ✅ RPC client for node communication
 //! - ✅ Type-safe API
 //! - ✅ `#[axiom_contract]` macro for Provable-by-Default smart contracts
+//! - ✅ `#[derive(AxiomEvent)]` for typed contract events from zkVM journals
 //! - ✅ `prove_transaction()` for local Proof-of-Execution via RISC-V
+//! - ✅ Browser wallets on `wasm32-unknown-unknown` (`wasm` feature)
+//! - ✅ Kotlin and Swift bindings through UniFFI (the `axiom-sdk-ffi` crate)
 //!
 //! ## Quick Start
 //!
 //! ```no_run
-//! use axiom_sdk::{AxiomClient, Wallet, NetworkConfig};
+//! use axiom_sdk::{AxiomClient, FeePriority, Wallet, NetworkConfig};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,17 +32,18 @@
 //!     
 //!     // Create wallet
 //!     let wallet = Wallet::new();
-//!     println!("Address: {}", wallet.address_hex());
+//!     println!("Address: {}", wallet.address());
 //!     
 //!     // Check balance
 //!     let balance = client.get_balance(&wallet.address()).await?;
 //!     println!("Balance: {} AXM", balance.as_axm());
 //!     
-//!     // Send transaction
+//!     // Send transaction, at the fee recent blocks suggest
+//!     let fee = client.estimate_fee(FeePriority::Medium).await?;
 //!     let tx = wallet.create_transaction(
 //!         "axm1recipient...",
 //!         1_000_000_000, // 1 AXM (in satoshis)
-//!         100_000_000,   // 0.1 AXM fee
+//!         fee.fee,
 //!     )?;
 //!     
 //!     let hash = client.broadcast_transaction(tx).await?;
@@ -44,20 +52,53 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## Browser (WASM)
+//!
+//! Build with `default-features = false, features = ["wasm"]` for
+//! `wasm32-unknown-unknown`. Key generation, signing and
+//! [`prove_transaction`] work unchanged; the client sends requests through
+//! `fetch` and waits on browser timers.
+
+// Lets `#[derive(AxiomEvent)]` expansions name `::axiom_sdk` inside the SDK too.
+extern crate self as axiom_sdk;
 
+pub mod abi;
 pub mod client;
+pub mod fees;
 pub mod wallet;
 pub mod transaction;
 pub mod types;
 pub mod error;
 pub mod zk_pulse;
+pub mod receipt;
+pub mod supply;
+pub mod outbox;
+pub mod multisig;
+pub mod signer;
+pub mod psat;
+pub mod watch_only;
+pub mod watcher;
+pub mod swap;
+mod runtime;
 
 pub use client::AxiomClient;
 pub use wallet::Wallet;
 pub use transaction::Transaction;
-pub use types::{Address, Balance, TxHash};
-pub use error::{AxiomError, Result};
+pub use types::{AccountInfo, AccountTx, ActivityKind, Address, Balance, BridgeQuote, HistoryEntry, HistoryFilter, HistoryPage, TxHash};
+pub use error::{AxiomError, ErrorCode, Result};
 pub use zk_pulse::{ZkPulse, ProveTransactionInput, ProveTransactionOutput};
+pub use receipt::{TransactionReceipt, TxStatus};
+pub use supply::{Checkpoint, SupplyAttestation};
+pub use outbox::{NonceGap, Outbox, ResubmitOutcome, Resubmission};
+pub use multisig::{MultisigPolicy, MultisigWallet, PartialSignature};
+pub use signer::{ExternalSigner, ProcessTransport, Signer, UnsignedTransaction};
+pub use psat::{Psat, PsatAuthority, PsatSummary};
+pub use watcher::{TxProgress, TxWatcher};
+pub use watch_only::{Direction, ViewKey, WatchOnlyReport, WatchOnlyWallet, WatchedTx};
+pub use abi::{AxiomEvent, ContractEvent};
+pub use fees::{FeeEstimate, FeePriority, FeeStats};
+pub use swap::{AtomicSwap, HtlcTerms, SwapAction, SwapRole, SwapState, SwapTerms};
 
 /// SDK version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -167,6 +208,7 @@ pub mod prelude {
         Transaction,
         Address,
         Balance,
+        FeePriority,
         NetworkConfig,
         AxiomError,
         Result,
@@ -0,0 +1,311 @@
+//! m-of-n Ed25519 multisig accounts.
+//!
+//! A [`MultisigPolicy`] names a threshold and up to
+//! [`MAX_MULTISIG_KEYS`] cosigner public keys; its address holds AXM like
+//! any other. To spend, one cosigner proposes an unsigned transaction with
+//! [`MultisigWallet::propose_transaction`], every cosigner who approves
+//! returns a [`PartialSignature`] (exchanged as a hex string), and
+//! [`MultisigWallet::combine`] packs the threshold of them into the
+//! transaction's signature field for broadcast.
+//!
+//! The policy and signature encoding match the node's, which verifies the
+//! spend against the sender address.
+//!
+//! ```
+//! use axiom_sdk::multisig::{MultisigPolicy, MultisigWallet, PartialSignature};
+//! use axiom_sdk::Wallet;
+//!
+//! let (a, b, c) = (Wallet::new(), Wallet::new(), Wallet::new());
+//! let policy = MultisigPolicy::new(2, [&a, &b, &c].map(|w| w.verifying_key().to_bytes()).to_vec())?;
+//! let treasury = MultisigWallet::new(policy);
+//!
+//! let tx = treasury.propose_transaction(&Wallet::new().address().to_string(), 5_000, 100, 0)?;
+//! let from_a = treasury.sign_partial(&tx, &a)?.encode();
+//! let from_c = treasury.sign_partial(&tx, &c)?.encode();
+//!
+//! let partials = [PartialSignature::decode(&from_a)?, PartialSignature::decode(&from_c)?];
+//! let signed = treasury.combine(&tx, &partials)?;
+//! assert!(treasury.verify(&signed).is_ok());
+//! # Ok::<(), axiom_sdk::AxiomError>(())
+//! ```
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AxiomError, Result};
+use crate::transaction::Transaction;
+use crate::types::Address;
+use crate::wallet::Wallet;
+
+/// Marks a transaction signature as a multisig spend.
+pub const MULTISIG_TAG: &[u8; 4] = b"AXMS";
+
+/// Most cosigners a policy may name.
+pub const MAX_MULTISIG_KEYS: usize = 15;
+
+const ADDRESS_DOMAIN: &[u8] = b"axiom-multisig-v1";
+
+fn multisig_error(message: impl Into<String>) -> AxiomError {
+    AxiomError::Multisig(message.into())
+}
+
+/// Threshold and cosigner public keys, kept sorted so that the same set in
+/// any order gives the same address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    threshold: u8,
+    keys: Vec<[u8; 32]>,
+}
+
+impl MultisigPolicy {
+    pub fn new(threshold: usize, mut keys: Vec<[u8; 32]>) -> Result<Self> {
+        if keys.is_empty() || keys.len() > MAX_MULTISIG_KEYS {
+            return Err(multisig_error(format!(
+                "a policy names between 1 and {} cosigners, got {}",
+                MAX_MULTISIG_KEYS,
+                keys.len()
+            )));
+        }
+        if threshold == 0 || threshold > keys.len() {
+            return Err(multisig_error(format!(
+                "threshold {} is not between 1 and {} cosigners",
+                threshold,
+                keys.len()
+            )));
+        }
+        keys.sort();
+        if let Some(pair) = keys.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(multisig_error(format!("cosigner {} appears twice", hex::encode(pair[0]))));
+        }
+        if let Some(key) = keys.iter().find(|key| VerifyingKey::from_bytes(key).is_err()) {
+            return Err(multisig_error(format!("{} is not an Ed25519 public key", hex::encode(key))));
+        }
+        Ok(Self { threshold: threshold as u8, keys })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold as usize
+    }
+
+    pub fn keys(&self) -> &[[u8; 32]] {
+        &self.keys
+    }
+
+    /// The account address: BLAKE3 over the threshold and sorted keys.
+    pub fn address(&self) -> Address {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(ADDRESS_DOMAIN);
+        hasher.update(&[self.threshold, self.keys.len() as u8]);
+        for key in &self.keys {
+            hasher.update(key);
+        }
+        Address(*hasher.finalize().as_bytes())
+    }
+
+    fn index_of(&self, key: &[u8; 32]) -> Option<u8> {
+        self.keys.binary_search(key).ok().map(|i| i as u8)
+    }
+}
+
+/// One cosigner's signature over a proposed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    /// The cosigner's public key.
+    pub signer: [u8; 32],
+    /// Hash of the proposed transaction.
+    pub tx_hash: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl PartialSignature {
+    /// Hex of signer, transaction hash and signature (256 characters).
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(128);
+        bytes.extend_from_slice(&self.signer);
+        bytes.extend_from_slice(&self.tx_hash);
+        bytes.extend_from_slice(&self.signature);
+        hex::encode(bytes)
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self> {
+        let bytes = hex::decode(encoded.trim()).map_err(|_| multisig_error("partial signature is not hex"))?;
+        if bytes.len() != 128 {
+            return Err(multisig_error("partial signature must be 128 bytes"));
+        }
+        Ok(Self {
+            signer: bytes[..32].try_into().expect("length checked"),
+            tx_hash: bytes[32..64].try_into().expect("length checked"),
+            signature: bytes[64..].to_vec(),
+        })
+    }
+}
+
+/// A multisig account as seen by one of its cosigners.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigWallet {
+    policy: MultisigPolicy,
+}
+
+impl MultisigWallet {
+    pub fn new(policy: MultisigPolicy) -> Self {
+        Self { policy }
+    }
+
+    pub fn policy(&self) -> &MultisigPolicy {
+        &self.policy
+    }
+
+    pub fn address(&self) -> Address {
+        self.policy.address()
+    }
+
+    /// An unsigned spend from the account, for the cosigners to sign.
+    pub fn propose_transaction(&self, to: &str, amount: u64, fee: u64, nonce: u64) -> Result<Transaction> {
+        let to: Address = to.parse().map_err(AxiomError::InvalidAddress)?;
+        let timestamp = crate::runtime::unix_now();
+        Ok(Transaction { from: self.address(), to, amount, fee, nonce, timestamp, signature: vec![] })
+    }
+
+    /// Sign a proposed spend as `cosigner`.
+    pub fn sign_partial(&self, tx: &Transaction, cosigner: &Wallet) -> Result<PartialSignature> {
+        if tx.from != self.address() {
+            return Err(multisig_error("transaction is not from this multisig account"));
+        }
+        let signer = cosigner.verifying_key().to_bytes();
+        if self.policy.index_of(&signer).is_none() {
+            return Err(multisig_error(format!("{} is not a cosigner of this account", hex::encode(signer))));
+        }
+        Ok(PartialSignature {
+            signer,
+            tx_hash: tx.hash().0,
+            signature: cosigner.sign(&tx.signing_message()).to_bytes().to_vec(),
+        })
+    }
+
+    /// Check the partial signatures against `tx` and return it carrying the
+    /// multisig spend once at least the threshold are valid.
+    pub fn combine(&self, tx: &Transaction, partials: &[PartialSignature]) -> Result<Transaction> {
+        if tx.from != self.address() {
+            return Err(multisig_error("transaction is not from this multisig account"));
+        }
+        let (tx_hash, message) = (tx.hash().0, tx.signing_message());
+        let mut signatures: Vec<(u8, [u8; 64])> = Vec::new();
+        for partial in partials {
+            if partial.tx_hash != tx_hash {
+                return Err(multisig_error("partial signature is for a different transaction"));
+            }
+            let index = self
+                .policy
+                .index_of(&partial.signer)
+                .ok_or_else(|| multisig_error(format!("{} is not a cosigner of this account", hex::encode(partial.signer))))?;
+            let signature: [u8; 64] = partial
+                .signature
+                .as_slice()
+                .try_into()
+                .map_err(|_| multisig_error("signature must be 64 bytes"))?;
+            verify_one(&partial.signer, &message, &signature)?;
+            if signatures.iter().all(|(i, _)| *i != index) {
+                signatures.push((index, signature));
+            }
+        }
+        if signatures.len() < self.policy.threshold() {
+            return Err(multisig_error(format!(
+                "{} of {} required signatures",
+                signatures.len(),
+                self.policy.threshold()
+            )));
+        }
+        signatures.sort_by_key(|(index, _)| *index);
+        signatures.truncate(self.policy.threshold());
+
+        let mut signed = tx.clone();
+        signed.signature = encode_spend(&self.policy, &signatures);
+        Ok(signed)
+    }
+
+    /// Check a combined spend the way the node will.
+    pub fn verify(&self, tx: &Transaction) -> Result<()> {
+        if tx.from != self.address() || !tx.signature.starts_with(MULTISIG_TAG) {
+            return Err(multisig_error("transaction is not a spend from this multisig account"));
+        }
+        let expected = self.policy.keys.len() * 32 + 7;
+        let body = tx.signature.get(expected..).ok_or_else(|| multisig_error("truncated multisig spend"))?;
+        if tx.signature[..expected - 1] != encode_spend(&self.policy, &[])[..expected - 1] {
+            return Err(multisig_error("spend carries a different policy"));
+        }
+        let message = tx.signing_message();
+        let count = tx.signature[expected - 1] as usize;
+        if body.len() != count * 65 {
+            return Err(multisig_error("malformed multisig spend"));
+        }
+        let mut previous: Option<u8> = None;
+        for entry in body.chunks(65) {
+            let index = entry[0];
+            if previous.is_some_and(|p| index <= p) {
+                return Err(multisig_error("signatures not in ascending cosigner order"));
+            }
+            previous = Some(index);
+            let key = self.policy.keys.get(index as usize).ok_or_else(|| multisig_error("cosigner index out of range"))?;
+            verify_one(key, &message, entry[1..].try_into().expect("64 bytes"))?;
+        }
+        if count < self.policy.threshold() {
+            return Err(multisig_error(format!("{} of {} required signatures", count, self.policy.threshold())));
+        }
+        Ok(())
+    }
+}
+
+fn verify_one(key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Result<()> {
+    VerifyingKey::from_bytes(key)
+        .map_err(|_| multisig_error(format!("{} is not an Ed25519 public key", hex::encode(key))))?
+        .verify(message, &Signature::from_bytes(signature))
+        .map_err(|_| multisig_error(format!("signature by {} does not verify", hex::encode(key))))
+}
+
+/// `"AXMS" | m | n | keys | k | k × (index, signature)`, as the node
+/// decodes it.
+fn encode_spend(policy: &MultisigPolicy, signatures: &[(u8, [u8; 64])]) -> Vec<u8> {
+    let mut bytes = MULTISIG_TAG.to_vec();
+    bytes.push(policy.threshold);
+    bytes.push(policy.keys.len() as u8);
+    for key in &policy.keys {
+        bytes.extend_from_slice(key);
+    }
+    bytes.push(signatures.len() as u8);
+    for (index, signature) in signatures {
+        bytes.push(*index);
+        bytes.extend_from_slice(signature);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treasury(threshold: usize, cosigners: &[&Wallet]) -> MultisigWallet {
+        let keys = cosigners.iter().map(|w| w.verifying_key().to_bytes()).collect();
+        MultisigWallet::new(MultisigPolicy::new(threshold, keys).unwrap())
+    }
+
+    #[test]
+    fn test_threshold_is_enforced() {
+        let (a, b, c, outsider) = (Wallet::new(), Wallet::new(), Wallet::new(), Wallet::new());
+        let account = treasury(2, &[&a, &b, &c]);
+        assert_eq!(account.address(), treasury(2, &[&c, &a, &b]).address());
+
+        let tx = account.propose_transaction(&Address([9u8; 32]).to_string(), 1_000, 10, 0).unwrap();
+        let from_b = account.sign_partial(&tx, &b).unwrap();
+        assert!(account.sign_partial(&tx, &outsider).is_err());
+        assert!(account.combine(&tx, &[from_b.clone(), from_b.clone()]).is_err());
+
+        let from_a = PartialSignature::decode(&account.sign_partial(&tx, &a).unwrap().encode()).unwrap();
+        let signed = account.combine(&tx, &[from_b, from_a]).unwrap();
+        assert!(account.verify(&signed).is_ok());
+
+        let mut tampered = signed.clone();
+        tampered.amount += 1;
+        assert!(account.verify(&tampered).is_err());
+        assert!(treasury(1, &[&a, &b]).verify(&signed).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! Local record of sent transactions, used to repair nonce gaps.
+//!
+//! The node only mines an address's transactions in nonce order, so when
+//! one of them is dropped (evicted from a full mempool, lost with a
+//! restarting node) every later nonce stalls behind it. The node cannot
+//! help: it never saw, or no longer has, the missing transaction. The
+//! sender can, if it kept a copy.
+//!
+//! Record every transaction in an [`Outbox`] when you broadcast it. Then
+//! [`AxiomClient::find_nonce_gap`](crate::AxiomClient::find_nonce_gap)
+//! compares the outbox with the node's confirmed nonce, and
+//! [`AxiomClient::repair_nonce_gap`](crate::AxiomClient::repair_nonce_gap)
+//! resends the stalled transactions in order, rebuilding any the node
+//! refuses. Nothing is sent without the caller's approval.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::transaction::Transaction;
+use crate::types::{Address, TxHash};
+
+/// Signed transactions per sender, keyed by nonce.
+#[derive(Debug, Default)]
+pub struct Outbox {
+    by_sender: HashMap<Address, BTreeMap<u64, Transaction>>,
+    path: Option<PathBuf>,
+}
+
+impl Outbox {
+    /// An outbox that is not persisted.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open the outbox stored at `path`; a missing file is an empty outbox.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut outbox = Self { path: Some(path.to_path_buf()), ..Self::default() };
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(outbox),
+            Err(e) => return Err(e),
+        };
+        let txs: Vec<Transaction> = serde_json::from_slice(&data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        for tx in txs {
+            outbox.insert(tx);
+        }
+        Ok(outbox)
+    }
+
+    /// Write the outbox back to the file it was loaded from. A no-op for
+    /// in-memory outboxes.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let txs: Vec<&Transaction> = self.by_sender.values().flat_map(|txs| txs.values()).collect();
+        let data = serde_json::to_vec_pretty(&txs)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Remember a broadcast transaction and persist the outbox. A later
+    /// transaction with the same sender and nonce replaces the earlier one.
+    pub fn record(&mut self, tx: &Transaction) -> io::Result<()> {
+        self.insert(tx.clone());
+        self.save()
+    }
+
+    fn insert(&mut self, tx: Transaction) {
+        self.by_sender.entry(tx.from).or_default().insert(tx.nonce, tx);
+    }
+
+    /// The recorded transaction for `sender` at `nonce`.
+    pub fn get(&self, sender: &Address, nonce: u64) -> Option<&Transaction> {
+        self.by_sender.get(sender)?.get(&nonce)
+    }
+
+    /// Recorded transactions for `sender` from `nonce` on, in nonce order.
+    pub fn pending(&self, sender: &Address, from_nonce: u64) -> Vec<&Transaction> {
+        self.by_sender
+            .get(sender)
+            .map(|txs| txs.range(from_nonce..).map(|(_, tx)| tx).collect())
+            .unwrap_or_default()
+    }
+
+    /// Forget `sender`'s transactions below `confirmed_nonce`: the chain
+    /// has them, or something else at their nonce. Returns how many went.
+    pub fn prune(&mut self, sender: &Address, confirmed_nonce: u64) -> usize {
+        let Some(txs) = self.by_sender.get_mut(sender) else {
+            return 0;
+        };
+        let kept = txs.split_off(&confirmed_nonce);
+        let pruned = txs.len();
+        *txs = kept;
+        if txs.is_empty() {
+            self.by_sender.remove(sender);
+        }
+        pruned
+    }
+
+    /// Work out what blocks `sender`, given the nonce the chain expects
+    /// next. `None` when nothing recorded is waiting.
+    pub fn gap(&self, sender: &Address, next_nonce: u64) -> Option<NonceGap> {
+        let pending = self.pending(sender, next_nonce);
+        let last = pending.last()?.nonce;
+        let missing = (next_nonce..last).filter(|n| self.get(sender, *n).is_none()).collect();
+        Some(NonceGap {
+            address: *sender,
+            next_nonce,
+            stalled: pending.into_iter().cloned().collect(),
+            missing,
+        })
+    }
+}
+
+/// Transactions of one address that are stuck behind the nonce the chain
+/// expects next.
+#[derive(Debug, Clone)]
+pub struct NonceGap {
+    pub address: Address,
+    /// The node's confirmed nonce: the next transaction it will mine.
+    pub next_nonce: u64,
+    /// Recorded, unconfirmed transactions from `next_nonce` on.
+    pub stalled: Vec<Transaction>,
+    /// Nonces below the highest stalled one that the outbox has no
+    /// transaction for. These cannot be resent; only a new transaction
+    /// at the nonce unblocks what follows.
+    pub missing: Vec<u64>,
+}
+
+impl NonceGap {
+    /// Whether everything stalled can be resent from the outbox.
+    pub fn is_recoverable(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// What [`AxiomClient::repair_nonce_gap`](crate::AxiomClient::repair_nonce_gap)
+/// is about to send; the approval callback sees each one first.
+#[derive(Debug, Clone)]
+pub enum Resubmission {
+    /// Broadcast the recorded transaction again, unchanged.
+    Resend(Transaction),
+    /// The node refused the recorded transaction; broadcast a freshly
+    /// signed one with the same recipient, amount, fee and nonce instead.
+    Rebuild { original: Transaction, replacement: Transaction, reason: String },
+}
+
+impl Resubmission {
+    /// The transaction that would be broadcast.
+    pub fn transaction(&self) -> &Transaction {
+        match self {
+            Resubmission::Resend(tx) => tx,
+            Resubmission::Rebuild { replacement, .. } => replacement,
+        }
+    }
+}
+
+/// How each stalled nonce fared in a repair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResubmitOutcome {
+    Resent(TxHash),
+    Rebuilt(TxHash),
+    /// The callback said no; later nonces were left alone.
+    Declined,
+    /// The node refused it and there was no wallet to rebuild it with, or
+    /// it refused the rebuilt one too.
+    Rejected(String),
+    /// The outbox has nothing at this nonce.
+    Missing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(from: u8, nonce: u64) -> Transaction {
+        Transaction {
+            from: Address([from; 32]),
+            to: Address([9u8; 32]),
+            amount: 1_000,
+            fee: 10,
+            nonce,
+            timestamp: 1_700_000_000 + nonce,
+            signature: vec![],
+        }
+    }
+
+    #[test]
+    fn test_gap_detection() {
+        let alice = Address([1u8; 32]);
+        let mut outbox = Outbox::in_memory();
+        for nonce in [3, 4, 6] {
+            outbox.record(&tx(1, nonce)).unwrap();
+        }
+        outbox.record(&tx(2, 0)).unwrap();
+
+        // The chain is at nonce 3: 3, 4 and 6 are stalled and 5 is lost.
+        let gap = outbox.gap(&alice, 3).unwrap();
+        assert_eq!(gap.stalled.iter().map(|t| t.nonce).collect::<Vec<_>>(), vec![3, 4, 6]);
+        assert_eq!(gap.missing, vec![5]);
+        assert!(!gap.is_recoverable());
+
+        // Once 3 and 4 are mined the chain waits on 5, which only a new
+        // transaction can fill.
+        assert_eq!(outbox.prune(&alice, 5), 2);
+        let gap = outbox.gap(&alice, 5).unwrap();
+        assert_eq!(gap.missing, vec![5]);
+
+        assert_eq!(outbox.prune(&alice, 7), 1);
+        assert!(outbox.gap(&alice, 7).is_none());
+        assert!(outbox.gap(&Address([2u8; 32]), 0).unwrap().is_recoverable());
+    }
+
+    #[test]
+    fn test_outbox_persists() {
+        let path = std::env::temp_dir().join(format!("axiom_sdk_outbox_{}.json", std::process::id()));
+        let mut outbox = Outbox::load(&path).unwrap();
+        outbox.record(&tx(1, 0)).unwrap();
+        outbox.record(&tx(1, 1)).unwrap();
+
+        let reopened = Outbox::load(&path).unwrap();
+        assert_eq!(reopened.pending(&Address([1u8; 32]), 0).len(), 2);
+        fs::remove_file(&path).unwrap();
+    }
+}
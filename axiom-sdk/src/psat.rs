@@ -0,0 +1,320 @@
+//! Partially signed Axiom transactions (PSAT) for offline signing.
+//!
+//! A [`Psat`] carries everything an air-gapped machine needs to approve a
+//! spend without talking to a node: the unsigned transaction, the key or
+//! multisig policy that must sign it, and the sender balance that the ZK
+//! proof is built from. The flow is:
+//!
+//! 1. Online: [`AxiomClient::prepare_psat`](crate::AxiomClient::prepare_psat)
+//!    (or [`Psat::for_key`] with a known balance and nonce) builds the
+//!    envelope; [`Psat::encode`] turns it into a `psat1…` string or file.
+//! 2. Offline: [`Psat::decode`], check [`Psat::summary`], then
+//!    [`Psat::prove`] and [`Psat::sign`] with any [`Signer`]. Multisig
+//!    cosigners each sign their own copy; [`Psat::merge`] collects them.
+//! 3. Online again: [`Psat::finalize`] checks the proof and signatures and
+//!    returns the transaction, or
+//!    [`AxiomClient::broadcast_psat`](crate::AxiomClient::broadcast_psat)
+//!    finalizes and broadcasts in one step.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AxiomError, Result};
+use crate::multisig::{MultisigPolicy, MultisigWallet, PartialSignature};
+use crate::signer::{address_of, Signer, UnsignedTransaction};
+use crate::transaction::Transaction;
+use crate::types::Address;
+use crate::wallet::Wallet;
+use crate::zk_pulse::{ProveTransactionInput, ZkPulse};
+
+/// Envelope format version.
+pub const PSAT_VERSION: u32 = 1;
+
+/// Prefix of the text encoding.
+pub const PSAT_PREFIX: &str = "psat1";
+
+fn psat_error(message: impl Into<String>) -> AxiomError {
+    AxiomError::Psat(message.into())
+}
+
+/// Who must sign.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum PsatAuthority {
+    /// One Ed25519 key, hex-encoded.
+    Single { public_key: String },
+    Multisig { policy: MultisigPolicy },
+}
+
+/// The ZK proof made offline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PsatProof {
+    pub digest_512: String,
+    /// Hex-encoded proof bytes.
+    pub proof: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Psat {
+    pub version: u32,
+    pub chain_id: u64,
+    /// The transaction, with an empty signature.
+    pub tx: Transaction,
+    pub authority: PsatAuthority,
+    /// Sender balance when the envelope was made: the ZK proof's witness.
+    pub balance: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<PsatProof>,
+    /// Signatures collected so far, as [`PartialSignature::encode`] strings.
+    #[serde(default)]
+    pub signatures: Vec<String>,
+}
+
+/// What the offline signer is approving, for display before signing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsatSummary {
+    pub chain_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+    pub signatures: usize,
+    pub required: usize,
+    pub proved: bool,
+}
+
+impl Psat {
+    /// An envelope for a spend from the account of `public_key`.
+    pub fn for_key(
+        chain_id: u64,
+        public_key: [u8; 32],
+        to: &str,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+        balance: u64,
+    ) -> Result<Self> {
+        let unsigned = Wallet::create_unsigned_transaction(public_key, to, amount, fee, nonce)?;
+        Ok(Self::new(chain_id, unsigned.tx, PsatAuthority::Single { public_key: hex::encode(public_key) }, balance))
+    }
+
+    /// An envelope for a spend from a multisig account.
+    pub fn for_multisig(
+        chain_id: u64,
+        account: &MultisigWallet,
+        to: &str,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+        balance: u64,
+    ) -> Result<Self> {
+        let tx = account.propose_transaction(to, amount, fee, nonce)?;
+        Ok(Self::new(chain_id, tx, PsatAuthority::Multisig { policy: account.policy().clone() }, balance))
+    }
+
+    fn new(chain_id: u64, tx: Transaction, authority: PsatAuthority, balance: u64) -> Self {
+        Self { version: PSAT_VERSION, chain_id, tx, authority, balance, proof: None, signatures: Vec::new() }
+    }
+
+    /// `psat1` followed by the hex of the JSON envelope.
+    pub fn encode(&self) -> String {
+        format!("{}{}", PSAT_PREFIX, hex::encode(serde_json::to_vec(self).expect("PSAT serializes")))
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self> {
+        let body = encoded.trim().strip_prefix(PSAT_PREFIX).ok_or_else(|| psat_error("missing psat1 prefix"))?;
+        let bytes = hex::decode(body).map_err(|_| psat_error("PSAT body is not hex"))?;
+        let psat: Psat = serde_json::from_slice(&bytes)?;
+        if psat.version != PSAT_VERSION {
+            return Err(psat_error(format!("unsupported PSAT version {}", psat.version)));
+        }
+        psat.check_sender()?;
+        Ok(psat)
+    }
+
+    /// The sender must be the account of the named key or policy, so that
+    /// a tampered envelope cannot redirect whose funds are spent.
+    fn check_sender(&self) -> Result<()> {
+        if self.tx.from != self.account()? {
+            return Err(psat_error("transaction sender does not match the signing authority"));
+        }
+        Ok(())
+    }
+
+    fn account(&self) -> Result<Address> {
+        Ok(match &self.authority {
+            PsatAuthority::Single { .. } => address_of(&self.single_key()?),
+            PsatAuthority::Multisig { policy } => policy.address(),
+        })
+    }
+
+    fn single_key(&self) -> Result<[u8; 32]> {
+        let PsatAuthority::Single { public_key } = &self.authority else {
+            return Err(psat_error("not a single-key PSAT"));
+        };
+        hex::decode(public_key)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| psat_error("invalid public key"))
+    }
+
+    fn required(&self) -> usize {
+        match &self.authority {
+            PsatAuthority::Single { .. } => 1,
+            PsatAuthority::Multisig { policy } => policy.threshold(),
+        }
+    }
+
+    pub fn summary(&self) -> PsatSummary {
+        PsatSummary {
+            chain_id: self.chain_id,
+            from: self.tx.from,
+            to: self.tx.to,
+            amount: self.tx.amount,
+            fee: self.tx.fee,
+            nonce: self.tx.nonce,
+            signatures: self.signatures.len(),
+            required: self.required(),
+            proved: self.proof.is_some(),
+        }
+    }
+
+    fn proof_input(&self) -> ProveTransactionInput {
+        ProveTransactionInput {
+            initial_balance: self.balance,
+            amount: self.tx.amount,
+            fee: self.tx.fee,
+            nonce: self.tx.nonce,
+        }
+    }
+
+    /// Generate the ZK proof from the envelope's balance.
+    pub fn prove(&mut self) -> Result<()> {
+        let output = ZkPulse::prove_transaction(&self.proof_input())?;
+        self.proof = Some(PsatProof { digest_512: output.digest_512, proof: hex::encode(output.proof_bytes) });
+        Ok(())
+    }
+
+    fn verify_proof(&self) -> Result<()> {
+        let proof = self.proof.as_ref().ok_or_else(|| psat_error("no ZK proof yet"))?;
+        let expected = ZkPulse::prove_transaction(&self.proof_input())?;
+        if proof.digest_512 != expected.digest_512 || proof.proof != hex::encode(expected.proof_bytes) {
+            return Err(psat_error("ZK proof does not match the transaction"));
+        }
+        Ok(())
+    }
+
+    /// Add `signer`'s signature. It must be the envelope's key or one of
+    /// its multisig cosigners.
+    pub fn sign(&mut self, signer: &impl Signer) -> Result<()> {
+        self.check_sender()?;
+        let public_key = signer.public_key()?;
+        let eligible = match &self.authority {
+            PsatAuthority::Single { .. } => self.single_key()? == public_key,
+            PsatAuthority::Multisig { policy } => policy.keys().contains(&public_key),
+        };
+        if !eligible {
+            return Err(psat_error("signer is not an authority for this PSAT"));
+        }
+        let signature = signer.sign(&self.tx.signing_message(), Some(&self.tx))?;
+        self.add_signature(PartialSignature { signer: public_key, tx_hash: self.tx.hash().0, signature: signature.to_vec() });
+        Ok(())
+    }
+
+    fn add_signature(&mut self, partial: PartialSignature) {
+        self.signatures.retain(|s| PartialSignature::decode(s).map(|p| p.signer != partial.signer).unwrap_or(false));
+        self.signatures.push(partial.encode());
+    }
+
+    /// Take the signatures and proof from another copy of the same PSAT,
+    /// e.g. one signed by a different cosigner.
+    pub fn merge(&mut self, other: &Psat) -> Result<()> {
+        if other.tx != self.tx || other.authority != self.authority || other.chain_id != self.chain_id {
+            return Err(psat_error("PSATs are for different transactions"));
+        }
+        for encoded in &other.signatures {
+            let partial = PartialSignature::decode(encoded)?;
+            if !self.signatures.contains(encoded) {
+                self.add_signature(partial);
+            }
+        }
+        if self.proof.is_none() {
+            self.proof = other.proof.clone();
+        }
+        Ok(())
+    }
+
+    /// Check the proof and signatures and return the signed transaction.
+    pub fn finalize(&self) -> Result<Transaction> {
+        self.check_sender()?;
+        self.verify_proof()?;
+        let partials = self
+            .signatures
+            .iter()
+            .map(|s| PartialSignature::decode(s))
+            .collect::<Result<Vec<_>>>()?;
+        match &self.authority {
+            PsatAuthority::Single { .. } => {
+                let partial = partials.first().ok_or_else(|| psat_error("not signed yet"))?;
+                let unsigned = UnsignedTransaction { tx: self.tx.clone(), public_key: self.single_key()? };
+                unsigned.attach_signature(&partial.signature)
+            }
+            PsatAuthority::Multisig { policy } => MultisigWallet::new(policy.clone()).combine(&self.tx, &partials),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_round_trip() {
+        let cold = Wallet::from_secret_key([5u8; 32]).unwrap();
+        let to = Address([2u8; 32]).to_string();
+
+        // Online: only the public key is needed.
+        let exported = Psat::for_key(84000, cold.verifying_key().to_bytes(), &to, 1_000, 10, 4, 50_000).unwrap().encode();
+
+        // Offline.
+        let mut psat = Psat::decode(&exported).unwrap();
+        assert_eq!(psat.summary().required, 1);
+        assert!(psat.finalize().is_err(), "neither proved nor signed");
+        assert!(psat.sign(&Wallet::from_secret_key([6u8; 32]).unwrap()).is_err());
+        psat.prove().unwrap();
+        psat.sign(&cold).unwrap();
+        let returned = psat.encode();
+
+        // Online again.
+        let tx = Psat::decode(&returned).unwrap().finalize().unwrap();
+        assert_eq!((tx.from, tx.nonce, tx.signature.len()), (cold.address(), 4, 64));
+
+        // A proof for a different balance or a redirected sender is refused.
+        let mut tampered = Psat::decode(&returned).unwrap();
+        tampered.balance = 40_000;
+        assert!(tampered.finalize().is_err());
+        let mut redirected = Psat::decode(&returned).unwrap();
+        redirected.tx.from = Address([9u8; 32]);
+        assert!(Psat::decode(&redirected.encode()).is_err());
+    }
+
+    #[test]
+    fn test_multisig_cosigners_sign_separate_copies() {
+        let (a, b, c) = (Wallet::new(), Wallet::new(), Wallet::new());
+        let keys = [&a, &b, &c].map(|w| w.verifying_key().to_bytes()).to_vec();
+        let account = MultisigWallet::new(MultisigPolicy::new(2, keys).unwrap());
+        let exported = Psat::for_multisig(84000, &account, &Address([2u8; 32]).to_string(), 1_000, 10, 0, 5_000).unwrap();
+
+        let mut first = exported.clone();
+        first.prove().unwrap();
+        first.sign(&a).unwrap();
+        assert!(first.finalize().is_err(), "one of two signatures");
+
+        let mut second = exported.clone();
+        second.sign(&c).unwrap();
+        first.merge(&second).unwrap();
+        assert_eq!(first.summary().signatures, 2);
+        let tx = first.finalize().unwrap();
+        assert!(account.verify(&tx).is_ok());
+    }
+}
@@ -0,0 +1,230 @@
+//! Transaction receipts and Merkle inclusion proof verification.
+//!
+//! A node serves a [`TransactionReceipt`] for every confirmed transaction at
+//! `/v1/tx/<hash>/receipt`. The receipt's proof links the transaction hash to
+//! the block's transaction root (`tx_root`), which is also carried in the
+//! block header. Checking the proof needs no trust in the node that served
+//! it. Comparing `tx_root` with a header from an independent source then
+//! shows the transaction is in that block.
+//!
+//! Tree rules match the node's `axiom_core::receipt`:
+//! `leaf = BLAKE3(0x00 || txid)`, `node = BLAKE3(0x01 || left || right)`.
+//! An odd node at the end of a level is promoted unchanged.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AxiomError, Result};
+use crate::types::TxHash;
+
+fn leaf_hash(txid: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0x00]);
+    hasher.update(txid);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn decode_hash(field: &str, hex_str: &str) -> Result<[u8; 32]> {
+    hex::decode(hex_str)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| AxiomError::InvalidResponse(format!("{} is not a 32-byte hex hash", field)))
+}
+
+/// Merkle path from a transaction to its block's `tx_root`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub index: u32,
+    pub leaf_count: u32,
+    /// Hex sibling hashes, leaf level first.
+    pub siblings: Vec<String>,
+}
+
+impl MerkleProof {
+    /// Recompute the root from `txid` and check it equals `root`.
+    pub fn verify(&self, txid: &[u8; 32], root: &[u8; 32]) -> Result<()> {
+        if self.index >= self.leaf_count {
+            return Err(AxiomError::Proof(format!(
+                "index {} outside a block of {} transactions",
+                self.index, self.leaf_count
+            )));
+        }
+        let mut hash = leaf_hash(txid);
+        let mut position = self.index as u64;
+        let mut width = self.leaf_count as u64;
+        let mut siblings = self.siblings.iter();
+        let missing = || AxiomError::Proof("proof is missing sibling hashes".to_string());
+        while width > 1 {
+            if position % 2 == 1 {
+                let left = decode_hash("sibling", siblings.next().ok_or_else(missing)?)?;
+                hash = node_hash(&left, &hash);
+            } else if position + 1 < width {
+                let right = decode_hash("sibling", siblings.next().ok_or_else(missing)?)?;
+                hash = node_hash(&hash, &right);
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        if siblings.next().is_some() {
+            return Err(AxiomError::Proof("proof has extra sibling hashes".to_string()));
+        }
+        if &hash != root {
+            return Err(AxiomError::Proof("transaction is not in the block's tx root".to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptStatus {
+    Success,
+}
+
+/// Confirmation receipt returned by [`AxiomClient::get_transaction_receipt`](crate::AxiomClient::get_transaction_receipt).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub tx_hash: String,
+    pub block_hash: String,
+    pub block_height: u64,
+    pub timestamp: u64,
+    pub position: u32,
+    pub fee: u64,
+    pub status: ReceiptStatus,
+    pub confirmations: u64,
+    pub tx_root: String,
+    pub proof: MerkleProof,
+    /// The block is at or below the node's latest checkpoint and can no
+    /// longer be reorganised. Nodes without checkpoints omit it.
+    #[serde(default)]
+    pub finalized: bool,
+}
+
+impl TransactionReceipt {
+    /// Verify the inclusion proof against the receipt's own `tx_root`.
+    pub fn verify(&self) -> Result<()> {
+        self.verify_against(&decode_hash("tx_root", &self.tx_root)?)
+    }
+
+    /// Verify the inclusion proof against a `tx_root` taken from a block
+    /// header the caller trusts.
+    pub fn verify_against(&self, tx_root: &[u8; 32]) -> Result<()> {
+        if self.proof.index != self.position {
+            return Err(AxiomError::Proof("proof index does not match receipt position".to_string()));
+        }
+        self.proof.verify(&decode_hash("tx_hash", &self.tx_hash)?, tx_root)
+    }
+
+    pub fn hash(&self) -> Result<TxHash> {
+        TxHash::from_hex(&self.tx_hash).map_err(AxiomError::InvalidResponse)
+    }
+}
+
+/// Where a transaction stands, as seen by the node that was asked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxStatus {
+    /// Not in any block on the node's best chain: waiting in a mempool, or
+    /// reorganised out of the block that held it.
+    Pending,
+    /// In a block on the best chain, with the receipt's `confirmations`.
+    Confirmed(TransactionReceipt),
+    /// In a checkpointed block; it will not be reorganised out.
+    Finalized(TransactionReceipt),
+}
+
+impl TxStatus {
+    pub fn from_receipt(receipt: Option<TransactionReceipt>) -> Self {
+        match receipt {
+            None => TxStatus::Pending,
+            Some(receipt) if receipt.finalized => TxStatus::Finalized(receipt),
+            Some(receipt) => TxStatus::Confirmed(receipt),
+        }
+    }
+
+    pub fn receipt(&self) -> Option<&TransactionReceipt> {
+        match self {
+            TxStatus::Pending => None,
+            TxStatus::Confirmed(receipt) | TxStatus::Finalized(receipt) => Some(receipt),
+        }
+    }
+
+    pub fn confirmations(&self) -> u64 {
+        self.receipt().map_or(0, |r| r.confirmations)
+    }
+
+    /// Whether a wait for `depth` confirmations is over. Finality counts
+    /// as deep enough whatever the depth.
+    pub fn reached(&self, depth: u64) -> bool {
+        match self {
+            TxStatus::Pending => false,
+            TxStatus::Confirmed(receipt) => receipt.confirmations >= depth.max(1),
+            TxStatus::Finalized(_) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_leaf_receipt() {
+        // root = node(node(leaf(a), leaf(b)), leaf(c)); c is promoted.
+        let (a, b, c) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        let ab = node_hash(&leaf_hash(&a), &leaf_hash(&b));
+        let root = node_hash(&ab, &leaf_hash(&c));
+
+        let mut receipt = TransactionReceipt {
+            tx_hash: hex::encode(c),
+            block_hash: hex::encode([0u8; 32]),
+            block_height: 7,
+            timestamp: 0,
+            position: 2,
+            fee: 1,
+            status: ReceiptStatus::Success,
+            confirmations: 1,
+            tx_root: hex::encode(root),
+            proof: MerkleProof { index: 2, leaf_count: 3, siblings: vec![hex::encode(ab)] },
+            finalized: false,
+        };
+        receipt.verify().unwrap();
+
+        let b_proof = MerkleProof { index: 1, leaf_count: 3, siblings: vec![hex::encode(leaf_hash(&a)), hex::encode(leaf_hash(&c))] };
+        b_proof.verify(&b, &root).unwrap();
+
+        receipt.tx_hash = hex::encode(a);
+        assert!(matches!(receipt.verify(), Err(AxiomError::Proof(_))));
+        assert!(receipt.verify_against(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_status_depth() {
+        let receipt: TransactionReceipt = serde_json::from_value(serde_json::json!({
+            "tx_hash": hex::encode([1u8; 32]),
+            "block_hash": hex::encode([2u8; 32]),
+            "block_height": 10,
+            "timestamp": 0,
+            "position": 0,
+            "fee": 1,
+            "status": "success",
+            "confirmations": 2,
+            "tx_root": hex::encode([3u8; 32]),
+            "proof": { "index": 0, "leaf_count": 1, "siblings": [] },
+        }))
+        .unwrap();
+        assert!(!receipt.finalized, "nodes without checkpoints omit the flag");
+
+        assert!(!TxStatus::Pending.reached(0));
+        let confirmed = TxStatus::from_receipt(Some(receipt.clone()));
+        assert!(confirmed.reached(2) && !confirmed.reached(3));
+        let finalized = TxStatus::from_receipt(Some(TransactionReceipt { finalized: true, ..receipt }));
+        assert!(matches!(finalized, TxStatus::Finalized(_)) && finalized.reached(100));
+    }
+}
@@ -0,0 +1,74 @@
+//! Timers and clocks for the SDK's polling loops, on either runtime.
+//!
+//! Natively they come from tokio and `std`. On `wasm32-unknown-unknown`
+//! tokio does not build and `std::time::{Instant, SystemTime}` panic, so
+//! the `wasm` feature uses browser timers (`gloo-timers`) and the JS clock
+//! instead. HTTP needs nothing here: reqwest speaks `fetch` on wasm32.
+
+use std::time::Duration;
+
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("axiom-sdk on wasm32 needs `default-features = false, features = [\"wasm\"]`");
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "native")))]
+compile_error!("axiom-sdk needs the `native` feature outside wasm32");
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use tokio::time::Instant;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+/// Seconds since the Unix epoch.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm::{sleep, unix_now, Instant};
+
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use std::ops::{Add, Sub};
+    use std::time::Duration;
+
+    /// A point in time from the JS clock, in milliseconds.
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+    pub(crate) struct Instant(f64);
+
+    impl Instant {
+        pub(crate) fn now() -> Self {
+            Instant(js_sys::Date::now())
+        }
+    }
+
+    impl Add<Duration> for Instant {
+        type Output = Instant;
+
+        fn add(self, rhs: Duration) -> Instant {
+            Instant(self.0 + rhs.as_secs_f64() * 1000.0)
+        }
+    }
+
+    impl Sub for Instant {
+        type Output = Duration;
+
+        fn sub(self, rhs: Instant) -> Duration {
+            Duration::from_secs_f64((self.0 - rhs.0).max(0.0) / 1000.0)
+        }
+    }
+
+    pub(crate) async fn sleep(duration: Duration) {
+        gloo_timers::future::sleep(duration).await
+    }
+
+    pub(crate) fn unix_now() -> u64 {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+}
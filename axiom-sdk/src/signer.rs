@@ -0,0 +1,271 @@
+//! Key custody behind a [`Signer`].
+//!
+//! Building a transaction does not need the secret key; only signing does.
+//! [`Wallet::create_unsigned_transaction`] builds the transaction from a
+//! public key alone and returns an [`UnsignedTransaction`] whose payload
+//! can be shown on and signed by a device. The detached signature that
+//! comes back is checked and attached with
+//! [`UnsignedTransaction::attach_signature`].
+//!
+//! [`Wallet::create_transaction_with_signer`] does both steps with any
+//! [`Signer`]: a [`Wallet`] holding its key in memory, or an
+//! [`ExternalSigner`] that forwards requests to a hardware wallet bridge
+//! or signing daemon.
+//!
+//! ## External signer protocol
+//!
+//! [`ProcessTransport`] runs the configured program once per request,
+//! writes one JSON object to its stdin and reads one from its stdout:
+//!
+//! ```text
+//! {"method": "public_key"}                       -> {"public_key": "<64 hex>"}
+//! {"method": "sign", "payload": "<hex>",
+//!  "summary": {"to": "axm1...", "amount": 1, "fee": 1, "nonce": 0}}
+//!                                                 -> {"signature": "<128 hex>"}
+//! {"error": "rejected on device"}                 (either request)
+//! ```
+//!
+//! The summary lets the device display what it is being asked to approve.
+//! Every returned signature is verified against the device's public key
+//! before it is used.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::error::{AxiomError, Result};
+use crate::transaction::Transaction;
+use crate::types::Address;
+use crate::wallet::Wallet;
+
+/// Something that holds an Ed25519 key and signs with it.
+pub trait Signer {
+    /// The Ed25519 public key.
+    fn public_key(&self) -> Result<[u8; 32]>;
+
+    /// Sign `message`. `tx` is the transaction being signed, when there is
+    /// one, for signers that show it to the user first.
+    fn sign(&self, message: &[u8], tx: Option<&Transaction>) -> Result<[u8; 64]>;
+
+    /// The account address for the key.
+    fn address(&self) -> Result<Address> {
+        Ok(address_of(&self.public_key()?))
+    }
+}
+
+/// Address of an Ed25519 public key: SHA-256 of the key.
+pub fn address_of(public_key: &[u8; 32]) -> Address {
+    Address(Sha256::digest(public_key).into())
+}
+
+impl Signer for Wallet {
+    fn public_key(&self) -> Result<[u8; 32]> {
+        Ok(self.verifying_key().to_bytes())
+    }
+
+    fn sign(&self, message: &[u8], _tx: Option<&Transaction>) -> Result<[u8; 64]> {
+        Ok(Wallet::sign(self, message).to_bytes())
+    }
+
+    fn address(&self) -> Result<Address> {
+        Ok(Wallet::address(self))
+    }
+}
+
+/// A transaction waiting for a detached signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    pub tx: Transaction,
+    /// The key the signature must verify against.
+    pub public_key: [u8; 32],
+}
+
+impl UnsignedTransaction {
+    /// The exact bytes to sign.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        self.tx.signing_message()
+    }
+
+    /// `signing_payload` as hex, for signers reached over text channels.
+    pub fn payload_hex(&self) -> String {
+        hex::encode(self.signing_payload())
+    }
+
+    /// Attach a detached signature, checking it against `public_key` first.
+    pub fn attach_signature(self, signature: &[u8]) -> Result<Transaction> {
+        let signature: [u8; 64] = signature
+            .try_into()
+            .map_err(|_| AxiomError::Signer(format!("signature must be 64 bytes, got {}", signature.len())))?;
+        verify_signature(&self.public_key, &self.signing_payload(), &signature)?;
+        let mut tx = self.tx;
+        tx.signature = signature.to_vec();
+        Ok(tx)
+    }
+}
+
+fn verify_signature(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> Result<()> {
+    VerifyingKey::from_bytes(public_key)
+        .map_err(|_| AxiomError::Signer("signer public key is not a valid Ed25519 key".into()))?
+        .verify(message, &Signature::from_bytes(signature))
+        .map_err(|_| AxiomError::Signer("signature does not verify against the signer's public key".into()))
+}
+
+/// How an [`ExternalSigner`] reaches its device: one JSON request in, one
+/// JSON response out.
+pub trait SignerTransport {
+    fn call(&self, request: &Value) -> Result<Value>;
+}
+
+impl<F: Fn(&Value) -> Result<Value>> SignerTransport for F {
+    fn call(&self, request: &Value) -> Result<Value> {
+        self(request)
+    }
+}
+
+/// Runs a signing program (a USB device bridge or a client for a signing
+/// daemon) for each request, speaking the protocol described in the
+/// module docs over its stdin and stdout.
+#[derive(Debug, Clone)]
+pub struct ProcessTransport {
+    program: String,
+    args: Vec<String>,
+}
+
+impl ProcessTransport {
+    pub fn new(program: impl Into<String>, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self { program: program.into(), args: args.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl SignerTransport for ProcessTransport {
+    fn call(&self, request: &Value) -> Result<Value> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| AxiomError::Signer(format!("cannot start {}: {}", self.program, e)))?;
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+        child.stdin.take().expect("stdin is piped").write_all(&line)?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(AxiomError::Signer(format!("{} exited with {}", self.program, output.status)));
+        }
+        let reply = output
+            .stdout
+            .split(|b| *b == b'\n')
+            .find(|l| !l.iter().all(u8::is_ascii_whitespace))
+            .ok_or_else(|| AxiomError::Signer(format!("{} returned nothing", self.program)))?;
+        Ok(serde_json::from_slice(reply)?)
+    }
+}
+
+/// A key held outside this process, e.g. on a hardware wallet.
+#[derive(Debug, Clone)]
+pub struct ExternalSigner<T = ProcessTransport> {
+    transport: T,
+    public_key: [u8; 32],
+}
+
+impl<T: SignerTransport> ExternalSigner<T> {
+    /// Ask the device for its public key.
+    pub fn connect(transport: T) -> Result<Self> {
+        let reply = checked(transport.call(&json!({"method": "public_key"}))?)?;
+        let public_key = hex_field::<32>(&reply, "public_key")?;
+        VerifyingKey::from_bytes(&public_key)
+            .map_err(|_| AxiomError::Signer("device returned an invalid public key".into()))?;
+        Ok(Self { transport, public_key })
+    }
+}
+
+impl<T: SignerTransport> Signer for ExternalSigner<T> {
+    fn public_key(&self) -> Result<[u8; 32]> {
+        Ok(self.public_key)
+    }
+
+    fn sign(&self, message: &[u8], tx: Option<&Transaction>) -> Result<[u8; 64]> {
+        let mut request = json!({"method": "sign", "payload": hex::encode(message)});
+        if let Some(tx) = tx {
+            request["summary"] = json!({"to": tx.to.to_string(), "amount": tx.amount, "fee": tx.fee, "nonce": tx.nonce});
+        }
+        let signature = hex_field::<64>(&checked(self.transport.call(&request)?)?, "signature")?;
+        verify_signature(&self.public_key, message, &signature)?;
+        Ok(signature)
+    }
+}
+
+fn checked(reply: Value) -> Result<Value> {
+    match reply.get("error").and_then(Value::as_str) {
+        Some(error) => Err(AxiomError::Signer(error.to_string())),
+        None => Ok(reply),
+    }
+}
+
+fn hex_field<const N: usize>(reply: &Value, field: &str) -> Result<[u8; N]> {
+    reply
+        .get(field)
+        .and_then(Value::as_str)
+        .and_then(|s| hex::decode(s).ok())
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AxiomError::Signer(format!("device reply has no valid `{}`", field)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A device that signs with `key` unless the amount is over `limit`.
+    fn device(key: [u8; 32], limit: u64) -> impl Fn(&Value) -> Result<Value> {
+        move |request: &Value| {
+            let wallet = Wallet::from_secret_key(key)?;
+            Ok(match request["method"].as_str() {
+                Some("public_key") => json!({"public_key": hex::encode(wallet.verifying_key().to_bytes())}),
+                Some("sign") if request["summary"]["amount"].as_u64().unwrap_or(0) > limit => {
+                    json!({"error": "rejected on device"})
+                }
+                Some("sign") => {
+                    let payload = hex::decode(request["payload"].as_str().unwrap()).unwrap();
+                    json!({"signature": hex::encode(wallet.sign(&payload).to_bytes())})
+                }
+                _ => json!({"error": "unknown method"}),
+            })
+        }
+    }
+
+    #[test]
+    fn test_external_signer_builds_valid_transactions() {
+        let signer = ExternalSigner::connect(device([7u8; 32], 5_000)).unwrap();
+        let local = Wallet::from_secret_key([7u8; 32]).unwrap();
+        assert_eq!(Signer::address(&signer).unwrap(), local.address());
+
+        let to = Address([2u8; 32]).to_string();
+        let tx = Wallet::create_transaction_with_signer(&signer, &to, 1_000, 10, 3).unwrap();
+        assert_eq!((tx.from, tx.nonce), (local.address(), 3));
+        let key = VerifyingKey::from_bytes(&local.verifying_key().to_bytes()).unwrap();
+        assert!(key.verify(&tx.signing_message(), &Signature::from_slice(&tx.signature).unwrap()).is_ok());
+
+        let refused = Wallet::create_transaction_with_signer(&signer, &to, 9_000, 10, 4);
+        assert!(matches!(refused, Err(AxiomError::Signer(message)) if message == "rejected on device"));
+    }
+
+    #[test]
+    fn test_detached_signature_is_checked() {
+        let owner = Wallet::from_secret_key([1u8; 32]).unwrap();
+        let public_key = owner.verifying_key().to_bytes();
+        let unsigned = Wallet::create_unsigned_transaction(public_key, &Address([2u8; 32]).to_string(), 500, 5, 0).unwrap();
+        assert!(unsigned.tx.signature.is_empty());
+
+        let wrong = Wallet::from_secret_key([2u8; 32]).unwrap().sign(&unsigned.signing_payload());
+        assert!(unsigned.clone().attach_signature(&wrong.to_bytes()).is_err());
+        assert!(unsigned.clone().attach_signature(&[0u8; 10]).is_err());
+
+        let detached = owner.sign(&hex::decode(unsigned.payload_hex()).unwrap());
+        let tx = unsigned.attach_signature(&detached.to_bytes()).unwrap();
+        assert_eq!(tx.signature, detached.to_bytes().to_vec());
+    }
+}
@@ -0,0 +1,245 @@
+//! Light-client verification of the circulating supply.
+//!
+//! Nodes serve `/v1/supply/proof`: the latest block pulse carrying a STARK
+//! supply receipt, followed by every block pulse since. [`SupplyProof::attest`]
+//! checks locally that
+//!
+//! - each pulse commits to the one before it (`prev_pulse_hash` is the
+//!   BLAKE3-512 of the previous pulse's bincode encoding),
+//! - every pulse's figures obey the supply law (`total_mined + remaining`
+//!   equals the cap) and never exceed the emission schedule for its height,
+//! - the chain starts at a receipt-bearing pulse the serving node verified.
+//!
+//! The STARK seal itself is checked by the serving node. The SDK does not
+//! embed the proving system, so query several nodes if that matters, or
+//! start from a [`Checkpoint`] obtained from a source you trust:
+//! [`SupplyProof::attest_from`] then relies on the checkpoint's pulse
+//! instead of the node's word.
+
+use serde::{Deserialize, Serialize};
+
+/// Supply cap pulses report `remaining` against, in smallest units.
+pub const MAX_SUPPLY_UNITS: u64 = 124_000_000_000_000_000;
+
+/// Emission schedule: initial block reward and halving interval.
+pub const INITIAL_REWARD: u64 = 5_000_000_000;
+pub const HALVING_INTERVAL: u64 = 1_240_000;
+
+/// Most coins that can exist after `height` blocks.
+pub fn emission_cap(height: u64) -> u64 {
+    let mut total = 0u64;
+    let mut counted = 0u64;
+    let mut era = 0u32;
+    while counted < height && era < 64 {
+        let blocks = HALVING_INTERVAL.min(height - counted);
+        total = total.saturating_add((INITIAL_REWARD >> era).saturating_mul(blocks));
+        counted += blocks;
+        era += 1;
+    }
+    total
+}
+
+/// A block pulse as gossiped on `axiom/realtime/pulse/v1`. Field order and
+/// types mirror the node's `AxiomPulse` so the bincode encoding matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pulse {
+    pub height: u64,
+    pub total_mined: u64,
+    pub remaining: u64,
+    pub block_hash: Vec<u8>,
+    pub oracle_seal: Vec<u8>,
+    pub prev_pulse_hash: Vec<u8>,
+    pub timestamp: i64,
+    #[serde(default)]
+    pub stark_receipt: Option<Vec<u8>>,
+    /// Ed25519 signature by the block's miner. Covered by the link hash.
+    #[serde(default)]
+    pub signature: Vec<u8>,
+}
+
+impl Pulse {
+    /// BLAKE3-512 link hash carried by the next pulse.
+    pub fn link_hash(&self) -> [u8; 64] {
+        let encoded = bincode::serialize(self).unwrap_or_default();
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&encoded);
+        let mut out = [0u8; 64];
+        hasher.finalize_xof().fill(&mut out);
+        out
+    }
+
+    fn obeys_supply_law(&self) -> bool {
+        self.total_mined.checked_add(self.remaining) == Some(MAX_SUPPLY_UNITS)
+            && self.total_mined <= emission_cap(self.height)
+    }
+}
+
+/// Bundle returned by `/v1/supply/proof`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplyProof {
+    pub pulses: Vec<Pulse>,
+    pub stark_verified: bool,
+}
+
+/// A Trust Pulse checkpoint as served on `/v1/checkpoints`: a block the
+/// node will never reorganise away, and the link hash of its pulse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub height: u64,
+    /// Hex `Block::hash()` of the checkpointed block.
+    pub block_hash: String,
+    /// Hex [`Pulse::link_hash`] of the block's pulse.
+    pub pulse_hash: String,
+    pub total_mined: u64,
+}
+
+/// Body of `/v1/checkpoints`, lowest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Checkpoints {
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+/// Outcome of [`AxiomClient::verify_supply`](crate::AxiomClient::verify_supply).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupplyAttestation {
+    /// Height of the newest pulse in the proof.
+    pub height: u64,
+    /// Coins mined as of `height`, in smallest units.
+    pub total_mined: u64,
+    /// Height of the pulse whose STARK receipt anchors the proof.
+    pub proven_height: u64,
+    pub verified: bool,
+}
+
+impl SupplyProof {
+    /// Run the local checks and summarise the result.
+    pub fn attest(&self) -> Option<SupplyAttestation> {
+        let first = self.pulses.first()?;
+        let verified = self.stark_verified
+            && first.stark_receipt.as_ref().is_some_and(|seal| !seal.is_empty())
+            && Self::linked(&self.pulses);
+        Self::summarise(&self.pulses, verified)
+    }
+
+    /// Like [`attest`](Self::attest), but anchored at `checkpoint` rather
+    /// than the serving node's receipt check: the proof must contain the
+    /// checkpoint's pulse, and only it and the pulses after it count.
+    pub fn attest_from(&self, checkpoint: &Checkpoint) -> Option<SupplyAttestation> {
+        let start = self.pulses.iter().position(|p| {
+            p.height == checkpoint.height && hex::encode(p.link_hash()) == checkpoint.pulse_hash
+        });
+        match start {
+            Some(start) => {
+                let pulses = &self.pulses[start..];
+                Self::summarise(pulses, Self::linked(pulses))
+            }
+            None => self.pulses.last().map(|tip| SupplyAttestation {
+                height: tip.height,
+                total_mined: tip.total_mined,
+                proven_height: checkpoint.height,
+                verified: false,
+            }),
+        }
+    }
+
+    fn linked(pulses: &[Pulse]) -> bool {
+        pulses.windows(2).all(|pair| {
+            pair[1].height > pair[0].height && pair[1].prev_pulse_hash.as_slice() == pair[0].link_hash().as_slice()
+        })
+    }
+
+    fn summarise(pulses: &[Pulse], verified: bool) -> Option<SupplyAttestation> {
+        let first = pulses.first()?;
+        let tip = pulses.last()?;
+        Some(SupplyAttestation {
+            height: tip.height,
+            total_mined: tip.total_mined,
+            proven_height: first.height,
+            verified: verified && pulses.iter().all(Pulse::obeys_supply_law),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pulse(height: u64, prev: Vec<u8>) -> Pulse {
+        let total_mined = (height - 1) * INITIAL_REWARD;
+        Pulse {
+            height,
+            total_mined,
+            remaining: MAX_SUPPLY_UNITS - total_mined,
+            block_hash: vec![height as u8; 64],
+            oracle_seal: vec![0u8; 64],
+            prev_pulse_hash: prev,
+            timestamp: 0,
+            stark_receipt: None,
+            signature: vec![0u8; 64],
+        }
+    }
+
+    fn proof() -> SupplyProof {
+        let mut first = pulse(100, vec![0u8; 64]);
+        first.stark_receipt = Some(vec![1, 2, 3]);
+        let second = pulse(101, first.link_hash().to_vec());
+        let third = pulse(102, second.link_hash().to_vec());
+        SupplyProof { pulses: vec![first, second, third], stark_verified: true }
+    }
+
+    #[test]
+    fn test_linked_proof_is_verified() {
+        let attestation = proof().attest().unwrap();
+        assert_eq!(attestation, SupplyAttestation {
+            height: 102,
+            total_mined: 101 * INITIAL_REWARD,
+            proven_height: 100,
+            verified: true,
+        });
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let mut edited = proof();
+        edited.pulses[1].total_mined += 1;
+        assert!(!edited.attest().unwrap().verified);
+
+        // Inflation that keeps the sum consistent still breaks the link.
+        let mut inflated = proof();
+        inflated.pulses[0].total_mined -= 10;
+        inflated.pulses[0].remaining += 10;
+        assert!(!inflated.attest().unwrap().verified);
+
+        let mut unproven = proof();
+        unproven.stark_verified = false;
+        assert!(!unproven.attest().unwrap().verified);
+        assert!(SupplyProof { pulses: vec![], stark_verified: true }.attest().is_none());
+    }
+
+    #[test]
+    fn test_attest_from_checkpoint() {
+        let mut proof = proof();
+        // The checkpoint vouches for the pulse, not the node's receipt check.
+        proof.stark_verified = false;
+        let checkpoint = Checkpoint {
+            height: 101,
+            block_hash: String::new(),
+            pulse_hash: hex::encode(proof.pulses[1].link_hash()),
+            total_mined: proof.pulses[1].total_mined,
+        };
+        let attestation = proof.attest_from(&checkpoint).unwrap();
+        assert!(attestation.verified);
+        assert_eq!((attestation.proven_height, attestation.height), (101, 102));
+
+        let elsewhere = Checkpoint { pulse_hash: hex::encode([7u8; 64]), ..checkpoint.clone() };
+        assert!(!proof.attest_from(&elsewhere).unwrap().verified);
+        proof.pulses[2].total_mined += 1;
+        assert!(!proof.attest_from(&checkpoint).unwrap().verified);
+    }
+
+    #[test]
+    fn test_emission_cap() {
+        assert_eq!(emission_cap(0), 0);
+        assert_eq!(emission_cap(HALVING_INTERVAL + 2), HALVING_INTERVAL * INITIAL_REWARD + 2 * (INITIAL_REWARD / 2));
+    }
+}
@@ -0,0 +1,357 @@
+//! Cross-chain atomic swaps over hash-time-locked contracts (HTLCs).
+//!
+//! Two counterparties trade assets on two chains without trusting each
+//! other or the bridge. Each locks funds in an HTLC payable to the other
+//! with the preimage of one shared hash, or back to themselves after a
+//! timeout:
+//!
+//! 1. The initiator picks a secret, agrees [`SwapTerms`] with the
+//!    participant and locks the initiator leg.
+//! 2. The participant sees that lock and locks the participant leg, which
+//!    times out at least [`MIN_TIMEOUT_GAP_SECS`] earlier.
+//! 3. The initiator claims the participant leg, revealing the secret.
+//! 4. The participant reads the secret from that claim and claims the
+//!    initiator leg.
+//!
+//! Either side keeps an [`AtomicSwap`], feeds it what it sees on chain
+//! (`observe_*`) and performs whatever [`AtomicSwap::next_action`] returns.
+//!
+//! ```
+//! use axiom_sdk::swap::{AtomicSwap, HtlcTerms, SwapAction};
+//!
+//! # fn main() -> axiom_sdk::Result<()> {
+//! let leg = |chain: &str, from: &str, to: &str, timeout| HtlcTerms {
+//!     chain: chain.into(), hash_lock: [0; 32], amount: 1_000, sender: from.into(), recipient: to.into(), timeout,
+//! };
+//! let now = 1_700_000_000;
+//! let mut alice = AtomicSwap::initiate(
+//!     leg("axiom", "axm1alice", "axm1bob", now + 48 * 3600),
+//!     leg("ethereum", "0xb0b", "0xa11ce", now + 24 * 3600),
+//!     now,
+//! )?;
+//! let bob = AtomicSwap::join(alice.terms().clone(), now)?;
+//! assert!(matches!(alice.next_action(now), SwapAction::Lock(_)));
+//! assert_eq!(bob.terms(), alice.terms());
+//! # Ok(())
+//! # }
+//! ```
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{AxiomError, Result};
+
+/// Least time between the participant leg's timeout and the initiator
+/// leg's. After claiming with the secret the initiator could otherwise
+/// refund their own leg before the participant gets to claim it.
+pub const MIN_TIMEOUT_GAP_SECS: u64 = 6 * 60 * 60;
+
+/// The SHA-256 hash lock of a swap secret, as the node's `BridgeSecret`.
+pub fn hash_lock(secret: &[u8; 32]) -> [u8; 32] {
+    Sha256::digest(secret).into()
+}
+
+/// One side's hash-time-locked contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HtlcTerms {
+    /// Chain the contract lives on, e.g. "axiom" or "ethereum".
+    pub chain: String,
+    pub hash_lock: [u8; 32],
+    pub amount: u64,
+    /// Who locks the funds, and gets them back after `timeout`.
+    pub sender: String,
+    /// Who can claim the funds with the secret before `timeout`.
+    pub recipient: String,
+    /// Unix time from which the sender may refund.
+    pub timeout: u64,
+}
+
+/// What the counterparties agree on before anything is locked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapTerms {
+    /// Locked first, by the initiator, for the participant.
+    pub initiator_leg: HtlcTerms,
+    /// Locked second, by the participant, for the initiator.
+    pub participant_leg: HtlcTerms,
+}
+
+impl SwapTerms {
+    fn validate(&self, now: u64) -> Result<()> {
+        let (a, b) = (&self.initiator_leg, &self.participant_leg);
+        if a.hash_lock != b.hash_lock {
+            return Err(AxiomError::Swap("legs use different hash locks".into()));
+        }
+        if a.amount == 0 || b.amount == 0 {
+            return Err(AxiomError::Swap("swap amounts must be positive".into()));
+        }
+        if b.timeout <= now {
+            return Err(AxiomError::Swap("participant leg already timed out".into()));
+        }
+        if a.timeout < b.timeout.saturating_add(MIN_TIMEOUT_GAP_SECS) {
+            return Err(AxiomError::Swap(format!(
+                "initiator leg must time out at least {}s after the participant leg",
+                MIN_TIMEOUT_GAP_SECS
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapRole {
+    Initiator,
+    Participant,
+}
+
+/// Progress of a swap, as far as this side has observed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapState {
+    /// Terms agreed, nothing locked.
+    Agreed,
+    InitiatorLocked,
+    BothLocked,
+    /// The participant leg was claimed, so the secret is public.
+    SecretRevealed,
+    /// Both legs claimed.
+    Completed,
+    /// This side took its own leg back after the timeout.
+    Refunded,
+    /// Gave up before this side locked anything.
+    Abandoned,
+}
+
+/// The next thing this side should do on chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwapAction {
+    Lock(HtlcTerms),
+    Claim { htlc: HtlcTerms, preimage: [u8; 32] },
+    Refund(HtlcTerms),
+    /// Nothing to do until the counterparty acts or a timeout passes.
+    Wait,
+    /// This side's part of the swap is over.
+    Done,
+}
+
+/// One counterparty's view of a swap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtomicSwap {
+    role: SwapRole,
+    terms: SwapTerms,
+    /// Known to the initiator from the start, to the participant once the
+    /// initiator claims.
+    secret: Option<[u8; 32]>,
+    state: SwapState,
+}
+
+impl AtomicSwap {
+    /// Start a swap with a fresh secret. The hash lock in both legs is
+    /// replaced by the secret's.
+    pub fn initiate(initiator_leg: HtlcTerms, participant_leg: HtlcTerms, now: u64) -> Result<Self> {
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let lock = hash_lock(&secret);
+        let mut terms = SwapTerms { initiator_leg, participant_leg };
+        terms.initiator_leg.hash_lock = lock;
+        terms.participant_leg.hash_lock = lock;
+        terms.validate(now)?;
+        Ok(Self { role: SwapRole::Initiator, terms, secret: Some(secret), state: SwapState::Agreed })
+    }
+
+    /// Take part in a swap proposed by an initiator.
+    pub fn join(terms: SwapTerms, now: u64) -> Result<Self> {
+        terms.validate(now)?;
+        Ok(Self { role: SwapRole::Participant, terms, secret: None, state: SwapState::Agreed })
+    }
+
+    pub fn role(&self) -> SwapRole {
+        self.role
+    }
+
+    pub fn state(&self) -> SwapState {
+        self.state
+    }
+
+    /// The terms to send to the counterparty.
+    pub fn terms(&self) -> &SwapTerms {
+        &self.terms
+    }
+
+    /// The secret, once this side knows it.
+    pub fn secret(&self) -> Option<[u8; 32]> {
+        self.secret
+    }
+
+    /// The initiator leg is on chain as `htlc`.
+    pub fn observe_initiator_lock(&mut self, htlc: &HtlcTerms) -> Result<()> {
+        self.expect_leg(htlc, &self.terms.initiator_leg)?;
+        if self.state == SwapState::Agreed {
+            self.state = SwapState::InitiatorLocked;
+        }
+        Ok(())
+    }
+
+    /// The participant leg is on chain as `htlc`.
+    pub fn observe_participant_lock(&mut self, htlc: &HtlcTerms) -> Result<()> {
+        self.expect_leg(htlc, &self.terms.participant_leg)?;
+        if self.state == SwapState::InitiatorLocked {
+            self.state = SwapState::BothLocked;
+        }
+        Ok(())
+    }
+
+    /// The participant leg was claimed with `preimage`.
+    pub fn observe_secret(&mut self, preimage: [u8; 32]) -> Result<()> {
+        if hash_lock(&preimage) != self.terms.participant_leg.hash_lock {
+            return Err(AxiomError::Swap("preimage does not match the hash lock".into()));
+        }
+        self.secret = Some(preimage);
+        if self.state == SwapState::BothLocked {
+            self.state = SwapState::SecretRevealed;
+        }
+        Ok(())
+    }
+
+    /// The initiator leg was claimed.
+    pub fn observe_completed(&mut self) {
+        if self.state == SwapState::SecretRevealed {
+            self.state = SwapState::Completed;
+        }
+    }
+
+    /// This side's own leg was refunded.
+    pub fn observe_refund(&mut self) {
+        self.state = SwapState::Refunded;
+    }
+
+    fn expect_leg(&self, seen: &HtlcTerms, agreed: &HtlcTerms) -> Result<()> {
+        if seen != agreed {
+            return Err(AxiomError::Swap(format!(
+                "HTLC on {} does not match the agreed terms",
+                seen.chain
+            )));
+        }
+        Ok(())
+    }
+
+    /// What to do at `now` (unix seconds).
+    pub fn next_action(&mut self, now: u64) -> SwapAction {
+        let (mine, theirs) = match self.role {
+            SwapRole::Initiator => (&self.terms.initiator_leg, &self.terms.participant_leg),
+            SwapRole::Participant => (&self.terms.participant_leg, &self.terms.initiator_leg),
+        };
+        match (self.role, self.state) {
+            (_, SwapState::Completed | SwapState::Refunded | SwapState::Abandoned) => SwapAction::Done,
+
+            (SwapRole::Initiator, SwapState::Agreed) if now < theirs.timeout => SwapAction::Lock(mine.clone()),
+            (SwapRole::Participant, SwapState::Agreed) if now < mine.timeout => SwapAction::Wait,
+            (_, SwapState::Agreed) => {
+                self.state = SwapState::Abandoned;
+                SwapAction::Done
+            }
+
+            (SwapRole::Participant, SwapState::InitiatorLocked) if now < mine.timeout => SwapAction::Lock(mine.clone()),
+            (SwapRole::Participant, SwapState::InitiatorLocked) => {
+                self.state = SwapState::Abandoned;
+                SwapAction::Done
+            }
+
+            (SwapRole::Initiator, SwapState::BothLocked) if now < theirs.timeout => SwapAction::Claim {
+                htlc: theirs.clone(),
+                preimage: self.secret.expect("initiator holds the secret"),
+            },
+            (SwapRole::Participant, SwapState::SecretRevealed) if now < theirs.timeout => SwapAction::Claim {
+                htlc: theirs.clone(),
+                preimage: self.secret.expect("secret observed"),
+            },
+            // The initiator's part ends with their claim; the participant's
+            // ends if they miss theirs.
+            (_, SwapState::SecretRevealed) => SwapAction::Done,
+
+            // Locked and stuck: refund once our own leg times out.
+            _ if now >= mine.timeout => SwapAction::Refund(mine.clone()),
+            _ => SwapAction::Wait,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn legs() -> (HtlcTerms, HtlcTerms) {
+        let leg = |chain: &str, sender: &str, recipient: &str, amount, timeout| HtlcTerms {
+            chain: chain.into(),
+            hash_lock: [0; 32],
+            amount,
+            sender: sender.into(),
+            recipient: recipient.into(),
+            timeout,
+        };
+        (
+            leg("axiom", "axm1alice", "axm1bob", 5_000, NOW + 48 * 3600),
+            leg("ethereum", "0xb0b", "0xa11ce", 2, NOW + 24 * 3600),
+        )
+    }
+
+    #[test]
+    fn test_swap_completes_for_both_sides() {
+        let (a, b) = legs();
+        let mut alice = AtomicSwap::initiate(a, b, NOW).unwrap();
+        let mut bob = AtomicSwap::join(alice.terms().clone(), NOW).unwrap();
+        assert_eq!(bob.next_action(NOW), SwapAction::Wait);
+
+        let SwapAction::Lock(initiator_leg) = alice.next_action(NOW) else { panic!("alice locks first") };
+        alice.observe_initiator_lock(&initiator_leg).unwrap();
+        bob.observe_initiator_lock(&initiator_leg).unwrap();
+
+        let SwapAction::Lock(participant_leg) = bob.next_action(NOW + 60) else { panic!("bob locks second") };
+        alice.observe_participant_lock(&participant_leg).unwrap();
+        bob.observe_participant_lock(&participant_leg).unwrap();
+        assert_eq!(bob.next_action(NOW + 60), SwapAction::Wait);
+
+        let SwapAction::Claim { htlc, preimage } = alice.next_action(NOW + 120) else { panic!("alice claims") };
+        assert_eq!(htlc.chain, "ethereum");
+        alice.observe_secret(preimage).unwrap();
+        assert_eq!(alice.next_action(NOW + 120), SwapAction::Done);
+
+        assert!(bob.observe_secret([7; 32]).is_err());
+        bob.observe_secret(preimage).unwrap();
+        let SwapAction::Claim { htlc, .. } = bob.next_action(NOW + 180) else { panic!("bob claims") };
+        assert_eq!(htlc.chain, "axiom");
+        bob.observe_completed();
+        assert_eq!((bob.state(), bob.next_action(NOW + 180)), (SwapState::Completed, SwapAction::Done));
+    }
+
+    #[test]
+    fn test_timeouts_refund_and_bad_terms_rejected() {
+        let (a, b) = legs();
+        let mut alice = AtomicSwap::initiate(a.clone(), b.clone(), NOW).unwrap();
+        let mut bob = AtomicSwap::join(alice.terms().clone(), NOW).unwrap();
+        let initiator_leg = alice.terms().initiator_leg.clone();
+        alice.observe_initiator_lock(&initiator_leg).unwrap();
+
+        // A lock that differs from the agreed terms is not accepted.
+        let mut short = initiator_leg.clone();
+        short.amount -= 1;
+        assert!(bob.observe_initiator_lock(&short).is_err());
+
+        // Bob never locks: alice waits out her own timeout, then refunds.
+        assert_eq!(alice.next_action(a.timeout - 1), SwapAction::Wait);
+        assert_eq!(alice.next_action(a.timeout), SwapAction::Refund(initiator_leg));
+        alice.observe_refund();
+        assert_eq!(alice.next_action(a.timeout), SwapAction::Done);
+        // Too late for bob to lock safely.
+        bob.observe_initiator_lock(&alice.terms().initiator_leg.clone()).unwrap();
+        assert_eq!(bob.next_action(b.timeout), SwapAction::Done);
+        assert_eq!(bob.state(), SwapState::Abandoned);
+
+        let mut tight = a;
+        tight.timeout = b.timeout + MIN_TIMEOUT_GAP_SECS - 1;
+        assert!(matches!(AtomicSwap::initiate(tight, b, NOW), Err(AxiomError::Swap(_))));
+    }
+}
@@ -3,7 +3,7 @@ use sha2::{Sha256, Digest};
 use crate::types::{Address, TxHash};
 
 /// Axiom transaction
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Transaction {
     pub from: Address,
     pub to: Address,
@@ -1,6 +1,15 @@
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m, Hrp};
 use serde::{Deserialize, Serialize};
 
+/// Human-readable part of bech32m addresses.
+pub const ADDRESS_HRP: &str = "axm";
+
 /// Axiom address (32 bytes)
+///
+/// Shown to users as bech32m (`axm1...`, see [`Address::to_bech32`]), whose
+/// checksum catches mistyped characters. The older `axm1<64 hex>` form has
+/// no checksum; [`migrate_hex_address`] converts it.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Address(pub [u8; 32]);
 
@@ -9,10 +18,14 @@ impl Address {
         Self(bytes)
     }
     
+    /// Legacy hex form, `axm1<64 hex>`, as the node's HTTP routes take it.
     pub fn to_hex(&self) -> String {
         format!("axm1{}", hex::encode(&self.0))
     }
     
+    /// Parse the legacy hex form, with or without the `axm1` prefix. There
+    /// is no checksum, so typos go unnoticed; prefer [`Address::from_bech32`]
+    /// for anything a person typed.
     pub fn from_hex(s: &str) -> Result<Self, String> {
         let s = s.strip_prefix("axm1").unwrap_or(s);
         let bytes = hex::decode(s).map_err(|e| e.to_string())?;
@@ -23,6 +36,26 @@ impl Address {
         arr.copy_from_slice(&bytes);
         Ok(Self(arr))
     }
+
+    /// bech32m encoding with the `axm` prefix.
+    pub fn to_bech32(&self) -> String {
+        let hrp = Hrp::parse_unchecked(ADDRESS_HRP);
+        bech32::encode::<Bech32m>(hrp, &self.0).expect("32 bytes fit in a bech32m string")
+    }
+
+    /// Parse a bech32m address, rejecting a bad checksum, a foreign prefix,
+    /// the older bech32 checksum and anything that is not 32 bytes.
+    pub fn from_bech32(s: &str) -> Result<Self, String> {
+        let checked = CheckedHrpstring::new::<Bech32m>(s.trim()).map_err(|e| format!("Invalid address: {}", e))?;
+        if checked.hrp() != Hrp::parse_unchecked(ADDRESS_HRP) {
+            return Err(format!("Invalid address prefix: expected {}1, got {}1", ADDRESS_HRP, checked.hrp()));
+        }
+        let bytes: Vec<u8> = checked.byte_iter().collect();
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|b: Vec<u8>| format!("Invalid address length: {}", b.len()))?;
+        Ok(Self(bytes))
+    }
     
     pub fn as_bytes(&self) -> &[u8; 32] {
         &self.0
@@ -31,7 +64,33 @@ impl Address {
 
 impl std::fmt::Display for Address {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_hex())
+        write!(f, "{}", self.to_bech32())
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = String;
+
+    /// Strict: bech32m only. See [`migrate_hex_address`] for hex.
+    fn from_str(s: &str) -> Result<Self, String> {
+        Self::from_bech32(s)
+    }
+}
+
+/// Whether `s` looks like a legacy hex address rather than bech32m.
+pub fn is_hex_address(s: &str) -> bool {
+    let s = s.strip_prefix("axm1").unwrap_or(s);
+    s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Convert a legacy hex address (`axm1<64 hex>` or bare hex) to bech32m.
+/// Addresses already in bech32m are checked and returned unchanged.
+pub fn migrate_hex_address(s: &str) -> Result<String, String> {
+    let s = s.trim();
+    if is_hex_address(s) {
+        Address::from_hex(s).map(|a| a.to_bech32())
+    } else {
+        Address::from_bech32(s).map(|a| a.to_bech32())
     }
 }
 
@@ -95,6 +154,111 @@ impl std::fmt::Display for TxHash {
     }
 }
 
+/// Account summary from the node's account index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountInfo {
+    /// Balance in satoshis.
+    pub balance: u64,
+    /// Transactions sent so far; the next one must carry this nonce.
+    pub nonce: u64,
+    /// The nonce after the account's transactions pending in the node's
+    /// mempool, i.e. the one a new transaction should carry. Older nodes
+    /// do not report it.
+    #[serde(default)]
+    pub pending_nonce: Option<u64>,
+    /// Confirmed transactions touching the account.
+    pub tx_count: usize,
+}
+
+/// Pointer to a confirmed transaction touching an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountTx {
+    /// Transaction hash, hex.
+    pub hash: String,
+    pub height: u64,
+    /// Index within the block's transactions.
+    pub position: u32,
+}
+
+/// Which activity [`AxiomClient::get_transactions`](crate::AxiomClient::get_transactions) lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryFilter {
+    #[default]
+    All,
+    Sent,
+    /// Incoming transfers and mining rewards.
+    Received,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    MiningReward,
+    Received,
+    /// `fee` was paid on top of `amount`.
+    Sent,
+}
+
+/// One entry of an address's history, from the node's address index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub height: u64,
+    pub timestamp: u64,
+    pub kind: ActivityKind,
+    pub amount: u64,
+    pub fee: u64,
+    /// Other side of a transfer, hex; `None` for mining rewards.
+    pub counterparty: Option<String>,
+    /// Transaction hash, hex; `None` for mining rewards.
+    pub tx_hash: Option<String>,
+    /// Blocks on top of (and including) the entry's block.
+    pub confirmations: u64,
+}
+
+/// A page of history, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// Pass to the next call to continue; `None` on the last page.
+    pub next_cursor: Option<String>,
+    pub tip_height: u64,
+}
+
+/// Fee quote for a bridge transfer, from the node's `/v1/bridge/quote`.
+/// Chains are named as the node names them (`"Axiom"`, `"Ethereum"`,
+/// `"Solana"`, ...); amounts are in satoshis unless noted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BridgeQuote {
+    pub amount: u64,
+    pub from: String,
+    pub to: String,
+    pub protocol_fee: u64,
+    /// Destination gas, converted to satoshis.
+    pub gas_fee: u64,
+    pub gas_units: u64,
+    /// Per gas unit, in the destination's smallest native unit.
+    pub gas_price: u128,
+    pub native_token: String,
+    pub native_price_usd: f64,
+    pub axm_price_usd: f64,
+    pub total_fee: u64,
+    pub quoted_at: u64,
+    /// Unix time after which the quote should be fetched again.
+    pub expires_at: u64,
+}
+
+impl BridgeQuote {
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// What arrives on the destination after fees.
+    pub fn received(&self) -> u64 {
+        self.amount.saturating_sub(self.total_fee)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,6 +272,29 @@ mod tests {
         let decoded = Address::from_hex(&hex).unwrap();
         assert_eq!(addr, decoded);
     }
+
+    #[test]
+    fn test_address_bech32() {
+        let addr = Address([1u8; 32]);
+        let encoded = addr.to_bech32();
+        assert!(encoded.starts_with("axm1") && encoded.len() == 62);
+        assert_eq!(addr.to_string(), encoded);
+        assert_eq!(encoded.parse::<Address>(), Ok(addr));
+        assert_eq!(Address::from_bech32(&encoded.to_uppercase()), Ok(addr));
+
+        // One mistyped character is caught by the checksum.
+        let mut typo = encoded.clone().into_bytes();
+        typo[20] = if typo[20] == b'q' { b'p' } else { b'q' };
+        assert!(Address::from_bech32(std::str::from_utf8(&typo).unwrap()).is_err());
+        // Hex has no checksum and is not accepted where bech32m is expected.
+        assert!(addr.to_hex().parse::<Address>().is_err());
+        let foreign = bech32::encode::<Bech32m>(Hrp::parse("btc").unwrap(), &addr.0).unwrap();
+        assert!(Address::from_bech32(&foreign).is_err());
+
+        assert_eq!(migrate_hex_address(&addr.to_hex()), Ok(encoded.clone()));
+        assert_eq!(migrate_hex_address(&hex::encode(addr.0)), Ok(encoded.clone()));
+        assert_eq!(migrate_hex_address(&encoded), Ok(encoded));
+    }
     
     #[test]
     fn test_balance_conversion() {
@@ -116,6 +303,17 @@ mod tests {
         assert_eq!(balance.as_axm(), 1.5);
     }
     
+    #[test]
+    fn test_history_page_decodes() {
+        let page: HistoryPage = serde_json::from_str(
+            r#"{"entries":[{"height":7,"timestamp":12600,"kind":"mining_reward","amount":5000000000,"fee":0,"counterparty":null,"tx_hash":null,"confirmations":3}],"next_cursor":"7.0","tip_height":10}"#,
+        )
+        .unwrap();
+        assert_eq!(page.entries[0].kind, ActivityKind::MiningReward);
+        assert_eq!(page.next_cursor.as_deref(), Some("7.0"));
+        assert_eq!(serde_json::to_string(&HistoryFilter::Received).unwrap(), "\"received\"");
+    }
+
     #[test]
     fn test_tx_hash_hex() {
         let hash = TxHash([255u8; 32]);
@@ -1,8 +1,9 @@
-use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer};
+use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer as _};
 use sha2::{Sha256, Digest};
 use crate::types::Address;
 use crate::transaction::Transaction;
 use crate::error::{AxiomError, Result};
+use crate::signer::{address_of, Signer, UnsignedTransaction};
 
 /// Axiom wallet with keypair
 pub struct Wallet {
@@ -73,7 +74,8 @@ impl Wallet {
         self.address
     }
     
-    /// Get address as hex string
+    /// Get address as a legacy hex string. Show users `address()` instead,
+    /// which displays as checksummed bech32m.
     pub fn address_hex(&self) -> String {
         self.address.to_hex()
     }
@@ -90,37 +92,57 @@ impl Wallet {
         amount: u64,
         fee: u64,
     ) -> Result<Transaction> {
-        let to_addr = Address::from_hex(to)
-            .map_err(|e| AxiomError::InvalidAddress(e))?;
+        self.create_transaction_with_nonce(to, amount, fee, 0)
+    }
+
+    /// Create a transaction carrying an explicit nonce, e.g. to fill a
+    /// nonce gap (see [`crate::outbox`]).
+    pub fn create_transaction_with_nonce(
+        &self,
+        to: &str,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+    ) -> Result<Transaction> {
+        Self::create_transaction_with_signer(self, to, amount, fee, nonce)
+    }
+
+    /// Build a transaction from `public_key` without signing it, for a key
+    /// held elsewhere (see [`crate::signer`]).
+    pub fn create_unsigned_transaction(
+        public_key: [u8; 32],
+        to: &str,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+    ) -> Result<UnsignedTransaction> {
+        let to_addr: Address = to.parse().map_err(AxiomError::InvalidAddress)?;
         
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let timestamp = crate::runtime::unix_now();
 
-        // Create transaction structure
-        let mut tx = Transaction {
-            from: self.address,
+        let tx = Transaction {
+            from: address_of(&public_key),
             to: to_addr,
             amount,
             fee,
-            nonce: 0, // Incremented by the network layer when the transaction is submitted
+            nonce,
             timestamp,
             signature: vec![],
         };
-        
-        // Sign transaction
-        let signature = self.sign_transaction(&tx)?;
-        tx.signature = signature.to_bytes().to_vec();
-        
-        Ok(tx)
+        Ok(UnsignedTransaction { tx, public_key })
     }
-    
-    /// Sign a transaction
-    fn sign_transaction(&self, tx: &Transaction) -> Result<Signature> {
-        let message = tx.signing_message();
-        let signature = self.signing_key.sign(&message);
-        Ok(signature)
+
+    /// Build a transaction and have `signer` sign it.
+    pub fn create_transaction_with_signer(
+        signer: &impl Signer,
+        to: &str,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+    ) -> Result<Transaction> {
+        let unsigned = Self::create_unsigned_transaction(signer.public_key()?, to, amount, fee, nonce)?;
+        let signature = signer.sign(&unsigned.signing_payload(), Some(&unsigned.tx))?;
+        unsigned.attach_signature(&signature)
     }
     
     /// Sign arbitrary data
@@ -158,11 +180,17 @@ mod tests {
     #[test]
     fn test_create_transaction() {
         let wallet = Wallet::new();
-        let to = Address([2u8; 32]).to_hex();
+        let to = Address([2u8; 32]).to_string();
         
         let tx = wallet.create_transaction(&to, 1_000_000, 100_000).unwrap();
         assert_eq!(tx.amount, 1_000_000);
         assert_eq!(tx.fee, 100_000);
         assert!(!tx.signature.is_empty());
+
+        // Unchecked hex and mistyped bech32m are refused.
+        let hex = Address([2u8; 32]).to_hex();
+        assert!(matches!(wallet.create_transaction(&hex, 1, 1), Err(AxiomError::InvalidAddress(_))));
+        let typo = to.replacen('q', "p", 1);
+        assert!(matches!(wallet.create_transaction(&typo, 1, 1), Err(AxiomError::InvalidAddress(_))));
     }
 }
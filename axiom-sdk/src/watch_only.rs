@@ -0,0 +1,316 @@
+//! Watch-only wallets for accounting systems and auditors.
+//!
+//! The account owner hands out their address and [`ViewKey`] (see
+//! [`Wallet::view_key`]); [`Wallet::watch_only`] turns those into a
+//! [`WatchOnlyWallet`] that follows the account through the node's account
+//! index. It keeps the balance, nonce and a classified history of incoming
+//! and outgoing transfers, and holds no spending key, so nothing built on
+//! it can sign.
+//!
+//! ```no_run
+//! use axiom_sdk::{AxiomClient, Wallet};
+//!
+//! # async fn run(owner: Wallet) -> axiom_sdk::Result<()> {
+//! let client = AxiomClient::new("http://localhost:8545").await?;
+//! let mut books = Wallet::watch_only(owner.address(), owner.view_key())?;
+//! books.sync(&client).await?;
+//! let report = books.report();
+//! println!("received {} sent {} fees {}", report.total_received, report.total_sent, report.total_fees);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::client::AxiomClient;
+use crate::error::{AxiomError, Result};
+use crate::transaction::Transaction;
+use crate::types::{AccountTx, Address, TxHash};
+use crate::wallet::Wallet;
+
+/// Page size used when walking the account index.
+const SCAN_PAGE: usize = 100;
+
+/// Read access to an account's transactions. Same layout and derivation as
+/// the node's `privacy::ViewKey`; the secret half is optional so a key can
+/// be shared for identification only.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewKey {
+    pub view_public_key: [u8; 32],
+    pub view_secret_key: Option<[u8; 32]>,
+}
+
+impl ViewKey {
+    /// Derive the view key for a spending secret.
+    pub fn derive(spend_secret: &[u8; 32]) -> Self {
+        let view_secret: [u8; 32] = Sha256::new()
+            .chain_update(b"axiom_view_key_derivation")
+            .chain_update(spend_secret)
+            .finalize()
+            .into();
+        Self { view_public_key: view_public_of(&view_secret), view_secret_key: Some(view_secret) }
+    }
+
+    /// The key without its secret half.
+    pub fn public_only(&self) -> Self {
+        Self { view_public_key: self.view_public_key, view_secret_key: None }
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self.view_secret_key {
+            Some(secret) if view_public_of(&secret) != self.view_public_key => {
+                Err(AxiomError::Wallet("view key halves do not match".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn view_public_of(view_secret: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(b"axiom_view_public_derivation")
+        .chain_update(view_secret)
+        .finalize()
+        .into()
+}
+
+impl Wallet {
+    /// The view key to hand to an auditor along with [`Wallet::address`].
+    pub fn view_key(&self) -> ViewKey {
+        ViewKey::derive(&self.export_secret_key())
+    }
+
+    /// A wallet that can follow `address` but never sign for it.
+    pub fn watch_only(address: Address, view_key: ViewKey) -> Result<WatchOnlyWallet> {
+        view_key.validate()?;
+        Ok(WatchOnlyWallet { address, view_key, balance: 0, nonce: 0, history: Vec::new(), seen: HashSet::new() })
+    }
+}
+
+/// Which way a transfer moved value, seen from the watched account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Incoming,
+    Outgoing,
+    /// Sent to itself: only the fee leaves the account.
+    SelfTransfer,
+}
+
+/// A confirmed transaction touching the watched account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedTx {
+    pub hash: TxHash,
+    pub height: u64,
+    pub direction: Direction,
+    /// The other side of the transfer.
+    pub counterparty: Address,
+    pub amount: u64,
+    /// Paid by the watched account on outgoing transfers, zero otherwise.
+    pub fee: u64,
+    pub timestamp: u64,
+}
+
+/// Totals over a watch-only wallet's history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchOnlyReport {
+    pub address: Address,
+    pub balance: u64,
+    pub nonce: u64,
+    pub total_received: u64,
+    pub total_sent: u64,
+    pub total_fees: u64,
+    pub incoming: usize,
+    pub outgoing: usize,
+}
+
+/// Balance and history of an account followed with a [`ViewKey`].
+#[derive(Debug, Clone)]
+pub struct WatchOnlyWallet {
+    address: Address,
+    view_key: ViewKey,
+    balance: u64,
+    nonce: u64,
+    /// Oldest first.
+    history: Vec<WatchedTx>,
+    seen: HashSet<TxHash>,
+}
+
+impl WatchOnlyWallet {
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn view_key(&self) -> &ViewKey {
+        &self.view_key
+    }
+
+    /// Balance as of the last [`sync`](Self::sync), in satoshis.
+    pub fn balance(&self) -> u64 {
+        self.balance
+    }
+
+    /// Transactions sent so far, as of the last sync.
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Confirmed transactions found so far, oldest first.
+    pub fn history(&self) -> &[WatchedTx] {
+        &self.history
+    }
+
+    pub fn incoming(&self) -> impl Iterator<Item = &WatchedTx> {
+        self.history.iter().filter(|t| t.direction == Direction::Incoming)
+    }
+
+    pub fn outgoing(&self) -> impl Iterator<Item = &WatchedTx> {
+        self.history.iter().filter(|t| t.direction != Direction::Incoming)
+    }
+
+    /// Refresh the balance and pull transactions confirmed since the last
+    /// sync. Returns how many new transactions were found.
+    pub async fn sync(&mut self, client: &AxiomClient) -> Result<usize> {
+        let account = client.get_account(&self.address).await?;
+
+        // The index lists newest first; stop at the first page that
+        // reaches something already seen.
+        let mut fresh = Vec::new();
+        let mut offset = 0;
+        'scan: loop {
+            let page = client.get_account_transactions(&self.address, offset, SCAN_PAGE).await?;
+            for entry in &page {
+                let hash = TxHash::from_hex(&entry.hash).map_err(AxiomError::InvalidResponse)?;
+                if self.seen.contains(&hash) {
+                    break 'scan;
+                }
+                let tx = client
+                    .get_transaction(&hash)
+                    .await?
+                    .ok_or_else(|| AxiomError::InvalidResponse(format!("indexed transaction {} not found", hash)))?;
+                fresh.push((entry.clone(), tx));
+            }
+            if page.len() < SCAN_PAGE {
+                break;
+            }
+            offset += page.len();
+        }
+
+        let found = fresh.len();
+        for (entry, tx) in fresh.into_iter().rev() {
+            self.record(&entry, &tx)?;
+        }
+        self.balance = account.balance;
+        self.nonce = account.nonce;
+        Ok(found)
+    }
+
+    /// Add one confirmed transaction to the history. Duplicates are ignored.
+    pub fn record(&mut self, entry: &AccountTx, tx: &Transaction) -> Result<()> {
+        let hash = tx.hash();
+        if !self.seen.insert(hash) {
+            return Ok(());
+        }
+        let (direction, counterparty, fee) = match (tx.from == self.address, tx.to == self.address) {
+            (true, true) => (Direction::SelfTransfer, self.address, tx.fee),
+            (true, false) => (Direction::Outgoing, tx.to, tx.fee),
+            (false, true) => (Direction::Incoming, tx.from, 0),
+            (false, false) => {
+                self.seen.remove(&hash);
+                return Err(AxiomError::InvalidResponse(format!(
+                    "transaction {} does not touch {}",
+                    hash, self.address
+                )));
+            }
+        };
+        self.history.push(WatchedTx {
+            hash,
+            height: entry.height,
+            direction,
+            counterparty,
+            amount: tx.amount,
+            fee,
+            timestamp: tx.timestamp,
+        });
+        Ok(())
+    }
+
+    /// Totals for the history found so far.
+    pub fn report(&self) -> WatchOnlyReport {
+        let mut report = WatchOnlyReport {
+            address: self.address,
+            balance: self.balance,
+            nonce: self.nonce,
+            total_received: 0,
+            total_sent: 0,
+            total_fees: 0,
+            incoming: 0,
+            outgoing: 0,
+        };
+        for tx in &self.history {
+            report.total_fees += tx.fee;
+            match tx.direction {
+                Direction::Incoming => {
+                    report.incoming += 1;
+                    report.total_received += tx.amount;
+                }
+                Direction::Outgoing => {
+                    report.outgoing += 1;
+                    report.total_sent += tx.amount;
+                }
+                Direction::SelfTransfer => report.outgoing += 1,
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmed(from: Address, to: Address, amount: u64, nonce: u64, height: u64) -> (AccountTx, Transaction) {
+        let tx = Transaction { from, to, amount, fee: 10, nonce, timestamp: 1_700_000_000 + height, signature: vec![] };
+        (AccountTx { hash: tx.hash().to_hex(), height, position: 0 }, tx)
+    }
+
+    #[test]
+    fn test_watch_only_classifies_history() {
+        let owner = Wallet::from_secret_key([4u8; 32]).unwrap();
+        let view_key = owner.view_key();
+        assert_eq!(view_key, ViewKey::derive(&[4u8; 32]));
+        let mut books = Wallet::watch_only(owner.address(), view_key.clone()).unwrap();
+
+        let me = owner.address();
+        let other = Address([9u8; 32]);
+        let txs = [
+            confirmed(other, me, 1_000, 0, 1),
+            confirmed(me, other, 300, 0, 2),
+            confirmed(me, me, 50, 1, 3),
+        ];
+        for (entry, tx) in &txs {
+            books.record(entry, tx).unwrap();
+        }
+        // A rescan does not double count.
+        books.record(&txs[0].0, &txs[0].1).unwrap();
+        let (entry, stranger) = confirmed(other, Address([8u8; 32]), 1, 0, 4);
+        assert!(books.record(&entry, &stranger).is_err());
+
+        assert_eq!(books.incoming().count(), 1);
+        assert_eq!(books.history()[1].counterparty, other);
+        let report = books.report();
+        assert_eq!((report.total_received, report.total_sent, report.total_fees), (1_000, 300, 20));
+        assert_eq!((report.incoming, report.outgoing), (1, 2));
+    }
+
+    #[test]
+    fn test_view_key_halves_must_match() {
+        let mut key = ViewKey::derive(&[1u8; 32]);
+        assert!(Wallet::watch_only(Address([0u8; 32]), key.public_only()).is_ok());
+        key.view_public_key[0] ^= 1;
+        assert!(Wallet::watch_only(Address([0u8; 32]), key).is_err());
+    }
+}
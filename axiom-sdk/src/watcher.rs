@@ -0,0 +1,217 @@
+//! Following a broadcast transaction until it is buried deep enough.
+//!
+//! [`AxiomClient::watch_transaction`] returns a [`TxWatcher`] that polls the
+//! node's receipt endpoint and reports each change of state once:
+//!
+//! ```text
+//! Pending -> Included { height } -> Confirmed(2) -> Confirmed(3) ... -> Finalized
+//! ```
+//!
+//! A reorg shows up as a step back to `Pending`, or as `Included` at a new
+//! height. The watcher ends after the requested depth or finality, or with
+//! [`AxiomError::ConfirmationTimeout`] at its deadline.
+//!
+//! ```no_run
+//! use axiom_sdk::{AxiomClient, TxHash};
+//!
+//! # async fn run(client: AxiomClient, hash: TxHash) -> axiom_sdk::Result<()> {
+//! let mut watcher = client.watch_transaction(&hash, 6);
+//! while let Some(progress) = watcher.next().await {
+//!     println!("{:?}", progress?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+
+use crate::client::{AxiomClient, CONFIRMATION_POLL_INTERVAL, TARGET_BLOCK_TIME};
+use crate::error::{AxiomError, Result};
+use crate::runtime::{self, Instant};
+use crate::receipt::TxStatus;
+use crate::types::TxHash;
+
+/// A step in a transaction's progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxProgress {
+    /// Not in a block on the node's best chain.
+    Pending,
+    /// In a block at `height` with one confirmation.
+    Included { height: u64, block_hash: String },
+    /// Buried under further blocks: the total confirmation count.
+    Confirmed(u64),
+    /// In a checkpointed block; it will not be reorganised out.
+    Finalized,
+}
+
+/// Polls a transaction's status and yields [`TxProgress`] transitions.
+pub struct TxWatcher<'a> {
+    client: &'a AxiomClient,
+    hash: TxHash,
+    depth: u64,
+    poll_interval: Duration,
+    deadline: Instant,
+    /// Last reported progress and the block it refers to.
+    last: Option<(TxProgress, Option<String>)>,
+    done: bool,
+}
+
+impl<'a> TxWatcher<'a> {
+    pub(crate) fn new(client: &'a AxiomClient, hash: TxHash, depth: u64) -> Self {
+        let timeout = TARGET_BLOCK_TIME * 2 * (depth.max(1) as u32 + 1);
+        Self {
+            client,
+            hash,
+            depth: depth.max(1),
+            poll_interval: CONFIRMATION_POLL_INTERVAL,
+            deadline: Instant::now() + timeout,
+            last: None,
+            done: false,
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Instant::now() + timeout;
+        self
+    }
+
+    /// Wait for the next transition. Returns `None` once the target depth
+    /// or finality has been reported, or after a timeout error. Failed
+    /// polls are retried until the deadline.
+    pub async fn next(&mut self) -> Option<Result<TxProgress>> {
+        if self.done {
+            return None;
+        }
+        let mut first_poll = self.last.is_none();
+        loop {
+            if !first_poll {
+                let now = Instant::now();
+                if now >= self.deadline {
+                    self.done = true;
+                    let confirmations = match &self.last {
+                        Some((TxProgress::Included { .. }, _)) => 1,
+                        Some((TxProgress::Confirmed(n), _)) => *n,
+                        _ => 0,
+                    };
+                    return Some(Err(AxiomError::ConfirmationTimeout {
+                        tx_hash: self.hash.to_hex(),
+                        confirmations,
+                        depth: self.depth,
+                    }));
+                }
+                runtime::sleep(self.poll_interval.min(self.deadline - now)).await;
+            }
+            first_poll = false;
+            match self.client.get_transaction_status(&self.hash).await {
+                Ok(status) => {
+                    if let Some(progress) = self.advance(&status) {
+                        return Some(Ok(progress));
+                    }
+                }
+                Err(AxiomError::Http(_)) | Err(AxiomError::Network(_)) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+
+    /// Fold a polled status into the watcher, returning the transition it
+    /// represents, if any.
+    fn advance(&mut self, status: &TxStatus) -> Option<TxProgress> {
+        let block = status.receipt().map(|r| r.block_hash.clone());
+        let progress = match status {
+            TxStatus::Pending => TxProgress::Pending,
+            TxStatus::Finalized(_) => TxProgress::Finalized,
+            TxStatus::Confirmed(receipt) => {
+                let moved = self.last.as_ref().is_some_and(|(_, b)| b.is_some() && *b != block);
+                let seen = matches!(self.last, Some((TxProgress::Included { .. } | TxProgress::Confirmed(_), _)));
+                if receipt.confirmations <= 1 || moved || !seen {
+                    TxProgress::Included { height: receipt.block_height, block_hash: receipt.block_hash.clone() }
+                } else {
+                    TxProgress::Confirmed(receipt.confirmations)
+                }
+            }
+        };
+        if status.reached(self.depth) {
+            self.done = true;
+        }
+        if self.last.as_ref().is_some_and(|(p, b)| *p == progress && *b == block) {
+            return None;
+        }
+        self.last = Some((progress.clone(), block));
+        Some(progress)
+    }
+}
+
+impl AxiomClient {
+    /// Follow `hash` until it has `depth` confirmations or is finalized.
+    /// Defaults to [`CONFIRMATION_POLL_INTERVAL`] polling and the same
+    /// deadline as [`wait_for_confirmation`](Self::wait_for_confirmation).
+    pub fn watch_transaction(&self, hash: &TxHash, depth: u64) -> TxWatcher<'_> {
+        TxWatcher::new(self, *hash, depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::receipt::TransactionReceipt;
+
+    fn confirmed(block: u8, height: u64, confirmations: u64) -> TxStatus {
+        let receipt: TransactionReceipt = serde_json::from_value(serde_json::json!({
+            "tx_hash": hex::encode([1u8; 32]),
+            "block_hash": hex::encode([block; 32]),
+            "block_height": height,
+            "timestamp": 0,
+            "position": 0,
+            "fee": 1,
+            "status": "success",
+            "confirmations": confirmations,
+            "tx_root": hex::encode([3u8; 32]),
+            "proof": { "index": 0, "leaf_count": 1, "siblings": [] },
+        }))
+        .unwrap();
+        TxStatus::Confirmed(receipt)
+    }
+
+    #[tokio::test]
+    async fn test_transitions_and_reorgs() {
+        let client = AxiomClient::new("http://127.0.0.1:1").await.unwrap();
+        let mut watcher = client.watch_transaction(&TxHash([1u8; 32]), 3);
+        let included = |block: u8, height| TxProgress::Included { height, block_hash: hex::encode([block; 32]) };
+
+        assert_eq!(watcher.advance(&TxStatus::Pending), Some(TxProgress::Pending));
+        assert_eq!(watcher.advance(&TxStatus::Pending), None);
+        assert_eq!(watcher.advance(&confirmed(7, 10, 1)), Some(included(7, 10)));
+        assert_eq!(watcher.advance(&confirmed(7, 10, 2)), Some(TxProgress::Confirmed(2)));
+        assert_eq!(watcher.advance(&confirmed(7, 10, 2)), None);
+        // Reorganised into a different block: counting starts again.
+        assert_eq!(watcher.advance(&confirmed(8, 11, 1)), Some(included(8, 11)));
+        assert_eq!(watcher.advance(&TxStatus::Pending), Some(TxProgress::Pending));
+        // First sight already buried still reports the block first.
+        assert_eq!(watcher.advance(&confirmed(9, 12, 2)), Some(included(9, 12)));
+        assert!(!watcher.done);
+        assert_eq!(watcher.advance(&confirmed(9, 12, 3)), Some(TxProgress::Confirmed(3)));
+        assert!(watcher.done);
+        assert!(watcher.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_times_out() {
+        let client = AxiomClient::new("http://127.0.0.1:1").await.unwrap();
+        let mut watcher = client
+            .watch_transaction(&TxHash([1u8; 32]), 1)
+            .with_poll_interval(Duration::from_millis(10))
+            .with_timeout(Duration::from_millis(50));
+        assert!(matches!(watcher.next().await, Some(Err(AxiomError::ConfirmationTimeout { confirmations: 0, .. }))));
+        assert!(watcher.next().await.is_none());
+    }
+}
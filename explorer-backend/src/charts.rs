@@ -0,0 +1,191 @@
+//! Pre-aggregated time series for `/api/charts/{metric}`.
+//!
+//! Every ingested block is folded into an hourly and a daily row of the
+//! `rollups` table, so a chart is one indexed range read however long the
+//! chain. A rollback deletes the buckets from the first dropped block on and
+//! rebuilds them from the blocks that remain.
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::Block;
+
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS rollups (
+    interval         INTEGER NOT NULL,
+    bucket           INTEGER NOT NULL,
+    blocks           INTEGER NOT NULL,
+    transactions     INTEGER NOT NULL,
+    block_time_sum   INTEGER NOT NULL,
+    block_time_count INTEGER NOT NULL,
+    difficulty_sum   INTEGER NOT NULL,
+    fee_sum          INTEGER NOT NULL,
+    fee_min          INTEGER,
+    fee_max          INTEGER,
+    PRIMARY KEY (interval, bucket)
+);
+";
+
+/// Default and maximum number of points in one chart.
+pub const DEFAULT_POINTS: u64 = 168;
+pub const MAX_POINTS: u64 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    Hour,
+    Day,
+}
+
+impl Interval {
+    pub const ALL: [Interval; 2] = [Interval::Hour, Interval::Day];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "1h" => Some(Interval::Hour),
+            "1d" => Some(Interval::Day),
+            _ => None,
+        }
+    }
+
+    pub fn secs(self) -> i64 {
+        match self {
+            Interval::Hour => 3_600,
+            Interval::Day => 86_400,
+        }
+    }
+
+    fn bucket(self, timestamp: u64) -> i64 {
+        timestamp as i64 / self.secs() * self.secs()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Transactions per bucket.
+    Transactions,
+    /// Mean seconds between blocks.
+    BlockTime,
+    /// Mean difficulty.
+    Difficulty,
+    /// Hashes per second: expected hashes per block over mean block time.
+    Hashrate,
+    /// Mean fee per transaction, with the bucket's minimum and maximum.
+    Fees,
+}
+
+impl Metric {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "transactions" => Some(Metric::Transactions),
+            "block-time" => Some(Metric::BlockTime),
+            "difficulty" => Some(Metric::Difficulty),
+            "hashrate" => Some(Metric::Hashrate),
+            "fees" => Some(Metric::Fees),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChartPoint {
+    /// Start of the bucket (unix seconds).
+    pub timestamp: u64,
+    pub value: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<u64>,
+}
+
+/// Fold `block`, mined `block_time` seconds after its parent, into its buckets.
+pub fn add_block(conn: &Connection, block: &Block, block_time: Option<u64>) -> rusqlite::Result<()> {
+    let fees = block.transactions.iter().map(|tx| tx.fee);
+    let (fee_min, fee_max, fee_sum) = (fees.clone().min(), fees.clone().max(), fees.sum::<u64>());
+    for interval in Interval::ALL {
+        conn.execute(
+            "INSERT INTO rollups VALUES (?1, ?2, 1, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT (interval, bucket) DO UPDATE SET
+                 blocks = blocks + 1,
+                 transactions = transactions + excluded.transactions,
+                 block_time_sum = block_time_sum + excluded.block_time_sum,
+                 block_time_count = block_time_count + excluded.block_time_count,
+                 difficulty_sum = difficulty_sum + excluded.difficulty_sum,
+                 fee_sum = fee_sum + excluded.fee_sum,
+                 fee_min = COALESCE(MIN(fee_min, excluded.fee_min), fee_min, excluded.fee_min),
+                 fee_max = COALESCE(MAX(fee_max, excluded.fee_max), fee_max, excluded.fee_max)",
+            params![
+                interval.secs(),
+                interval.bucket(block.timestamp),
+                block.transactions.len() as i64,
+                block_time.unwrap_or(0) as i64,
+                block_time.is_some() as i64,
+                block.difficulty as i64,
+                fee_sum as i64,
+                fee_min.map(|f| f as i64),
+                fee_max.map(|f| f as i64),
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Recompute every bucket from the one holding `timestamp` onwards. Block
+/// timestamps never decrease with height, so those buckets hold exactly the
+/// blocks stamped at or after the bucket start.
+pub fn rebuild_from(conn: &Connection, timestamp: u64) -> rusqlite::Result<()> {
+    for interval in Interval::ALL {
+        let start = interval.bucket(timestamp);
+        conn.execute("DELETE FROM rollups WHERE interval = ?1 AND bucket >= ?2", params![interval.secs(), start])?;
+        conn.execute(
+            "INSERT INTO rollups
+             SELECT ?1, b.timestamp / ?1 * ?1 AS bucket, COUNT(*), COALESCE(SUM(t.n), 0),
+                    COALESCE(SUM(b.block_time), 0), COUNT(b.block_time), SUM(b.difficulty),
+                    COALESCE(SUM(t.fees), 0), MIN(t.fee_min), MAX(t.fee_max)
+             FROM blocks b
+             LEFT JOIN (
+                 SELECT block_height, COUNT(*) AS n, SUM(fee) AS fees, MIN(fee) AS fee_min, MAX(fee) AS fee_max
+                 FROM transactions GROUP BY block_height
+             ) t ON t.block_height = b.height
+             WHERE b.timestamp >= ?2
+             GROUP BY bucket",
+            params![interval.secs(), start],
+        )?;
+    }
+    Ok(())
+}
+
+/// The latest `points` buckets of `metric`, oldest first.
+pub fn series(conn: &Connection, metric: Metric, interval: Interval, points: u64) -> rusqlite::Result<Vec<ChartPoint>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT bucket, blocks, transactions, block_time_sum, block_time_count, difficulty_sum, fee_sum, fee_min, fee_max
+         FROM rollups WHERE interval = ?1 ORDER BY bucket DESC LIMIT ?2",
+    )?;
+    let mut series = stmt
+        .query_map(params![interval.secs(), points.clamp(1, MAX_POINTS) as i64], |r| {
+            let blocks = r.get::<_, i64>(1)? as f64;
+            let transactions = r.get::<_, i64>(2)?;
+            let block_time_count = r.get::<_, i64>(4)?;
+            let mean_block_time = match block_time_count {
+                0 => 0.0,
+                n => r.get::<_, i64>(3)? as f64 / n as f64,
+            };
+            let mean_difficulty = r.get::<_, i64>(5)? as f64 / blocks;
+            let value = match metric {
+                Metric::Transactions => transactions as f64,
+                Metric::BlockTime => mean_block_time,
+                Metric::Difficulty => mean_difficulty,
+                Metric::Hashrate if mean_block_time > 0.0 => mean_difficulty / mean_block_time,
+                Metric::Hashrate => 0.0,
+                Metric::Fees if transactions > 0 => r.get::<_, i64>(6)? as f64 / transactions as f64,
+                Metric::Fees => 0.0,
+            };
+            let (min, max) = match metric {
+                Metric::Fees => (r.get::<_, Option<i64>>(7)?.map(|f| f as u64), r.get::<_, Option<i64>>(8)?.map(|f| f as u64)),
+                _ => (None, None),
+            };
+            Ok(ChartPoint { timestamp: r.get::<_, i64>(0)? as u64, value, min, max })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    series.reverse();
+    Ok(series)
+}
@@ -0,0 +1,164 @@
+//! Live push to browsers on `/ws`.
+//!
+//! The sync worker publishes every block it ingests, the transactions in
+//! it, and after each poll whichever headline stats changed. Each socket
+//! gets its own receiver on a broadcast channel; a browser that falls too
+//! far behind skips the missed messages instead of holding up the rest.
+//!
+//! Messages are JSON text frames: `{"type": "block", "data": {...}}`,
+//! `{"type": "transaction", "data": {...}}` and `{"type": "stats", "data": {...}}`,
+//! where stats carry only the fields that changed.
+
+use std::sync::Mutex;
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_ws::Message;
+use serde::Serialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{AppState, Block, BlockSummary, Transaction};
+
+/// Messages buffered per socket before it starts skipping.
+pub const CHANNEL_CAPACITY: usize = 256;
+
+/// Headline figures shown on the explorer's front page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub height: u64,
+    pub total_transactions: u64,
+    pub mempool_size: u64,
+    pub difficulty: u64,
+    pub peers: u32,
+}
+
+/// The fields of [`Stats`] that changed since the last push.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StatsDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_transactions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mempool_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peers: Option<u32>,
+}
+
+impl StatsDelta {
+    fn between(old: &Stats, new: &Stats) -> Option<Self> {
+        let changed = |a, b| (a != b).then_some(b);
+        let delta = Self {
+            height: changed(old.height, new.height),
+            total_transactions: changed(old.total_transactions, new.total_transactions),
+            mempool_size: changed(old.mempool_size, new.mempool_size),
+            difficulty: changed(old.difficulty, new.difficulty),
+            peers: (old.peers != new.peers).then_some(new.peers),
+        };
+        (delta != Self::default()).then_some(delta)
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+enum Event<'a> {
+    Block(BlockSummary),
+    Transaction(&'a Transaction),
+    Stats(StatsDelta),
+}
+
+pub struct Live {
+    sender: broadcast::Sender<String>,
+    last_stats: Mutex<Stats>,
+}
+
+impl Live {
+    pub fn new() -> Self {
+        Self {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            last_stats: Mutex::new(Stats::default()),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&self, event: &Event) {
+        // No receivers just means no browser is connected.
+        if let Ok(json) = serde_json::to_string(event) {
+            let _ = self.sender.send(json);
+        }
+    }
+
+    /// Push a newly ingested block and then each of its transactions.
+    pub fn block_applied(&self, block: &Block) {
+        self.publish(&Event::Block(BlockSummary::from(block)));
+        for tx in &block.transactions {
+            self.publish(&Event::Transaction(tx));
+        }
+    }
+
+    /// Push whatever changed since the last call, if anything.
+    pub fn stats(&self, stats: Stats) {
+        let mut last = self.last_stats.lock().unwrap();
+        if let Some(delta) = StatsDelta::between(&last, &stats) {
+            *last = stats;
+            self.publish(&Event::Stats(delta));
+        }
+    }
+}
+
+/// `GET /ws`: upgrade and forward broadcasts until the browser leaves.
+pub async fn socket(req: HttpRequest, body: web::Payload, data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let (response, mut session, mut incoming) = actix_ws::handle(&req, body)?;
+    let mut updates = data.live.subscribe();
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                message = incoming.recv() => match message {
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+                update = updates.recv() => match update {
+                    Ok(json) => {
+                        if session.text(json).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => log::debug!("WebSocket client skipped {} updates", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+        let _ = session.close(None).await;
+    });
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_push_only_changes() {
+        let live = Live::new();
+        let mut updates = live.subscribe();
+        let stats = Stats { height: 10, total_transactions: 4, mempool_size: 2, difficulty: 1_000, peers: 3 };
+        live.stats(stats);
+        live.stats(stats);
+        live.stats(Stats { mempool_size: 0, ..stats });
+
+        let first: serde_json::Value = serde_json::from_str(&updates.try_recv().unwrap()).unwrap();
+        assert_eq!(first["type"], "stats");
+        assert_eq!(first["data"]["height"], 10);
+        assert_eq!(updates.try_recv().unwrap(), r#"{"type":"stats","data":{"mempool_size":0}}"#);
+        assert!(updates.try_recv().is_err());
+    }
+}
@@ -1,8 +1,24 @@
-use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{middleware, web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use actix_cors::Cors;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 
+mod charts;
+mod live;
+mod pulses;
+mod ratelimit;
+mod search;
+mod store;
+mod sync;
+
+use charts::{Interval, Metric};
+use live::Live;
+use pulses::PulseHead;
+use ratelimit::{Quotas, RateLimiter, Tier};
+use search::SearchError;
+use store::{Counterparty, DailyBalance, MiningRewards, Store};
+use sync::{MempoolSummary, NodeClient, NodeStatus, SupplySnapshot};
+
 /// Block data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Block {
@@ -12,13 +28,17 @@ struct Block {
     timestamp: u64,
     transactions: Vec<Transaction>,
     miner: String,
-    difficulty: u32,
     nonce: u64,
     merkle_root: String,
-    vdf_output: Option<String>,
     vdf_proof: Option<String>,
     size: usize,
     reward: u64,
+    /// Share of this block's fees destroyed
+    fees_burned: u64,
+    /// Share of this block's fees credited to the miner
+    miner_fees: u64,
+    /// Node-reported difficulty when the block was ingested
+    difficulty: u64,
 }
 
 /// Transaction data structure
@@ -34,10 +54,19 @@ struct Transaction {
     block_hash: Option<String>,
     block_index: Option<u64>,
     confirmations: u32,
+    status: TxStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     zk_proof: Option<String>,
 }
 
+/// Whether a transaction is in a block yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TxStatus {
+    Pending,
+    Confirmed,
+}
+
 /// Network statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct NetworkStats {
@@ -45,11 +74,14 @@ struct NetworkStats {
     total_transactions: u64,
     total_supply: u64,
     circulating_supply: u64,
-    difficulty: u32,
+    difficulty: u64,
     hash_rate: f64,
     peers: u32,
     mempool_size: u32,
     average_block_time: f64,
+    /// Stale blocks the node has seen, and their share of recent blocks.
+    stale_blocks: u64,
+    orphan_rate: f64,
     latest_blocks: Vec<BlockSummary>,
 }
 
@@ -64,153 +96,316 @@ struct BlockSummary {
     reward: u64,
 }
 
+impl From<&Block> for BlockSummary {
+    fn from(b: &Block) -> Self {
+        Self {
+            index: b.index,
+            hash: b.hash.clone(),
+            timestamp: b.timestamp,
+            tx_count: b.transactions.len(),
+            miner: b.miner.clone(),
+            reward: b.reward,
+        }
+    }
+}
+
 /// Address information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize)]
 struct AddressInfo {
     address: String,
+    /// Received plus mined, minus sent and fees
     balance: u64,
     total_received: u64,
     total_sent: u64,
     tx_count: u32,
+    first_seen: Option<u64>,
+    last_seen: Option<u64>,
+    mining: MiningRewards,
+    balance_history: Vec<DailyBalance>,
+    counterparties: Vec<Counterparty>,
     recent_transactions: Vec<Transaction>,
 }
 
-/// Search result
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
-enum SearchResult {
-    Block { data: Block },
-    Transaction { data: Transaction },
-    Address { data: AddressInfo },
-    NotFound,
+/// Shared application state, filled by the sync worker
+struct AppState {
+    store: Store,
+    client: NodeClient,
+    /// Latest `/v1/status` from the node
+    node: Mutex<NodeStatus>,
+    /// Latest `/v1/mempool` from the node
+    mempool: Mutex<MempoolSummary>,
+    /// Latest `/v1/supply` from the node, `None` until the first poll
+    supply: Mutex<Option<SupplySnapshot>>,
+    /// Genesis pulse hash and trust pulse from the latest `/v1/pulses`, `None` until the first poll
+    pulse_head: Mutex<Option<PulseHead>>,
+    /// Pushes to `/ws` clients
+    live: Live,
+    /// Per-IP and per-key request quotas
+    limiter: RateLimiter,
+    /// Take client IPs from `X-Forwarded-For`/`Forwarded` (behind a reverse proxy)
+    trust_proxy: bool,
+    /// Bearer token for `/admin/keys`; admin routes are off without one
+    admin_token: Option<String>,
 }
 
-/// Shared application state
-struct AppState {
-    blocks: Mutex<Vec<Block>>,
-    transactions: Mutex<Vec<Transaction>>,
+/// 10^8: AXM has eight decimals
+const SATOSHIS_PER_AXM: u64 = 100_000_000;
+
+/// `?interval=1h|1d&points=` on chart endpoints
+#[derive(Debug, Deserialize)]
+struct ChartQuery {
+    interval: Option<String>,
+    points: Option<u64>,
 }
 
-impl AppState {
-    fn new() -> Self {
-        // Initialize with only the genesis block — real blocks arrive via chain sync
-        let genesis_block = Block {
-            index: 0,
-            hash: "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-            previous_hash: "0".repeat(64),
-            timestamp: 1600000000,
-            transactions: vec![],
-            miner: "Genesis".to_string(),
-            difficulty: 1,
-            nonce: 0,
-            merkle_root: "0".repeat(64),
-            vdf_output: None,
-            vdf_proof: None,
-            size: 285,
-            reward: 5000000000, // 50 AXM
-        };
+/// `?from=&to=` heights on `/api/pulses`, both inclusive
+#[derive(Debug, Deserialize)]
+struct PulsesQuery {
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+/// Body of `POST /admin/keys`
+#[derive(Debug, Deserialize)]
+struct IssueKeyRequest {
+    tier: Tier,
+    #[serde(default)]
+    label: String,
+}
+
+/// Counterparties listed on the address page
+const TOP_COUNTERPARTIES: u64 = 10;
 
+/// `?page=&limit=` on list endpoints
+#[derive(Debug, Deserialize)]
+struct PageQuery {
+    page: Option<u64>,
+    limit: Option<u64>,
+}
+
+impl AppState {
+    fn new(store: Store, client: NodeClient) -> Self {
         Self {
-            blocks: Mutex::new(vec![genesis_block]),
-            transactions: Mutex::new(vec![]),
+            store,
+            client,
+            node: Mutex::new(NodeStatus::default()),
+            mempool: Mutex::new(MempoolSummary::default()),
+            supply: Mutex::new(None),
+            pulse_head: Mutex::new(None),
+            live: Live::new(),
+            limiter: RateLimiter::new(Quotas::default()),
+            trust_proxy: false,
+            admin_token: None,
         }
     }
+
+    /// Height of the stored tip
+    fn tip(&self) -> rusqlite::Result<u64> {
+        Ok(self.store.next_height()?.saturating_sub(1))
+    }
+
+    /// `tx` with its confirmation count as of `tip`
+    fn confirmed(tx: Transaction, tip: u64) -> Transaction {
+        let mut tx = tx;
+        tx.confirmations = tx.block_index.map_or(0, |i| (tip + 1).saturating_sub(i) as u32);
+        tx
+    }
+
+    /// Totals, analytics and latest transactions for `address`, if it has any
+    fn address_info(&self, address: &str) -> rusqlite::Result<Option<AddressInfo>> {
+        let totals = self.store.address_totals(address)?;
+        let analytics = self.store.address_analytics(address, TOP_COUNTERPARTIES)?;
+        if totals.tx_count == 0 && analytics.mining.blocks_mined == 0 {
+            return Ok(None);
+        }
+        let tip = self.tip()?;
+        let recent = self.store.address_transactions(address, 1, 20)?;
+        Ok(Some(AddressInfo {
+            address: address.to_string(),
+            balance: (totals.total_received + analytics.mining.total_rewards + analytics.mining.total_fees)
+                .saturating_sub(totals.total_sent),
+            total_received: totals.total_received,
+            total_sent: totals.total_sent,
+            tx_count: totals.tx_count as u32,
+            first_seen: analytics.first_seen,
+            last_seen: analytics.last_seen,
+            mining: analytics.mining,
+            balance_history: analytics.balance_history,
+            counterparties: analytics.counterparties,
+            recent_transactions: recent.items.into_iter().map(|tx| Self::confirmed(tx, tip)).collect(),
+        }))
+    }
+}
+
+fn storage_error(e: rusqlite::Error) -> HttpResponse {
+    log::error!("Explorer database error: {}", e);
+    HttpResponse::InternalServerError().json(serde_json::json!({
+        "error": "Storage error"
+    }))
 }
 
 /// Get network statistics
 async fn get_stats(data: web::Data<AppState>) -> impl Responder {
-    let blocks = data.blocks.lock().unwrap();
-    let transactions = data.transactions.lock().unwrap();
-    
-    let height = blocks.len() as u64 - 1;
-    let latest_blocks: Vec<BlockSummary> = blocks.iter()
-        .rev()
-        .take(10)
-        .map(|b| BlockSummary {
-            index: b.index,
-            hash: b.hash.clone(),
-            timestamp: b.timestamp,
-            tx_count: b.transactions.len(),
-            miner: b.miner.clone(),
-            reward: b.reward,
-        })
-        .collect();
+    let node = data.node.lock().unwrap().clone();
+    let mempool_size = data.mempool.lock().unwrap().size as u32;
+    let supply = data.supply.lock().unwrap().clone();
+    let (latest, total_transactions, first_ts) = match (
+        data.store.blocks(1, 10),
+        data.store.transaction_count(),
+        data.store.first_timestamp(),
+    ) {
+        (Ok(latest), Ok(count), Ok(first_ts)) => (latest, count, first_ts.unwrap_or(0)),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return storage_error(e),
+    };
+
+    let height = latest.total.saturating_sub(1);
+    let last_ts = latest.items.first().map(|b| b.timestamp).unwrap_or(0);
+    let latest_blocks: Vec<BlockSummary> = latest.items.iter().map(BlockSummary::from).collect();
 
     let stats = NetworkStats {
         height,
-        total_transactions: transactions.len() as u64,
-        total_supply: 124000000_00000000, // 124M AXM in satoshis
-        circulating_supply: height * 5000000000, // 50 AXM per block
-        difficulty: blocks.last().map(|b| b.difficulty).unwrap_or(1000),
+        total_transactions,
+        total_supply: supply.as_ref().map_or(0, |s| s.max_supply),
+        circulating_supply: supply.as_ref().map_or(0, |s| s.circulating),
+        difficulty: node.difficulty,
         hash_rate: 0.0, // Computed when mining telemetry is available
-        peers: 0,       // Updated by P2P layer
-        mempool_size: 0, // Updated by transaction pool
-        average_block_time: if height > 0 {
-            let first_ts = blocks.first().map(|b| b.timestamp).unwrap_or(0);
-            let last_ts = blocks.last().map(|b| b.timestamp).unwrap_or(0);
-            if height > 1 { (last_ts - first_ts) as f64 / (height - 1) as f64 } else { 0.0 }
+        peers: node.connected_peers,
+        mempool_size,
+        average_block_time: if height > 1 {
+            last_ts.saturating_sub(first_ts) as f64 / (height - 1) as f64
         } else { 0.0 },
+        stale_blocks: node.stale_blocks,
+        orphan_rate: node.orphan_rate,
         latest_blocks,
     };
 
     HttpResponse::Ok().json(stats)
 }
 
-/// Get block by hash or index
-async fn get_block(
+/// Get supply and emission figures
+async fn get_supply(data: web::Data<AppState>) -> impl Responder {
+    match &*data.supply.lock().unwrap() {
+        Some(supply) => HttpResponse::Ok().json(supply),
+        None => HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "error": "Supply not synced from the node yet"
+        })),
+    }
+}
+
+/// Circulating supply in AXM as a bare number, for market-data aggregators
+async fn get_circulating_supply(data: web::Data<AppState>) -> impl Responder {
+    match &*data.supply.lock().unwrap() {
+        Some(supply) => HttpResponse::Ok()
+            .content_type("text/plain")
+            .body(format_axm(supply.circulating)),
+        None => HttpResponse::ServiceUnavailable().finish(),
+    }
+}
+
+/// `amount` in satoshis as a decimal AXM string
+fn format_axm(amount: u64) -> String {
+    format!("{}.{:08}", amount / SATOSHIS_PER_AXM, amount % SATOSHIS_PER_AXM)
+}
+
+/// Get a time series: transactions, block-time, difficulty, hashrate or fees
+async fn get_chart(
     path: web::Path<String>,
+    query: web::Query<ChartQuery>,
     data: web::Data<AppState>,
 ) -> impl Responder {
-    let blocks = data.blocks.lock().unwrap();
-    let identifier = path.into_inner();
-
-    // Try parsing as index first
-    if let Ok(index) = identifier.parse::<u64>() {
-        if let Some(block) = blocks.iter().find(|b| b.index == index) {
-            return HttpResponse::Ok().json(block);
-        }
+    let name = path.into_inner();
+    let Some(metric) = Metric::parse(&name) else {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Unknown metric"
+        }));
+    };
+    let Some(interval) = Interval::parse(query.interval.as_deref().unwrap_or("1h")) else {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "interval must be 1h or 1d"
+        }));
+    };
+    match data.store.chart(metric, interval, query.points.unwrap_or(charts::DEFAULT_POINTS)) {
+        Ok(points) => HttpResponse::Ok().json(serde_json::json!({
+            "metric": name,
+            "interval": query.interval.as_deref().unwrap_or("1h"),
+            "points": points,
+        })),
+        Err(e) => storage_error(e),
     }
+}
 
-    // Try as hash
-    if let Some(block) = blocks.iter().find(|b| b.hash == identifier) {
-        return HttpResponse::Ok().json(block);
+/// Get block pulses by height, with each prev_pulse_hash link checked back to genesis
+async fn get_pulses(query: web::Query<PulsesQuery>, data: web::Data<AppState>) -> impl Responder {
+    let Some(head) = data.pulse_head.lock().unwrap().clone() else {
+        return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "error": "Pulses not synced from the node yet"
+        }));
+    };
+    let result = data.store.pulse_tip().and_then(|tip| {
+        let (from, to) = pulses::range_bounds(query.from, query.to, tip.unwrap_or(0));
+        data.store.pulses(from, to, &head)
+    });
+    match result {
+        Ok(range) => HttpResponse::Ok().json(range),
+        Err(e) => storage_error(e),
     }
-
-    HttpResponse::NotFound().json(serde_json::json!({
-        "error": "Block not found"
-    }))
 }
 
-/// Get latest blocks
-async fn get_latest_blocks(
-    query: web::Query<std::collections::HashMap<String, String>>,
+/// Get block by hash or index
+async fn get_block(
+    path: web::Path<String>,
     data: web::Data<AppState>,
 ) -> impl Responder {
-    let blocks = data.blocks.lock().unwrap();
-    let limit = query.get("limit")
-        .and_then(|l| l.parse::<usize>().ok())
-        .unwrap_or(20)
-        .min(100);
+    match data.store.block(&path.into_inner()) {
+        Ok(Some(block)) => HttpResponse::Ok().json(block),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Block not found"
+        })),
+        Err(e) => storage_error(e),
+    }
+}
 
-    let latest: Vec<&Block> = blocks.iter().rev().take(limit).collect();
-    HttpResponse::Ok().json(latest)
+/// Get blocks, newest first, one page at a time
+async fn get_blocks(
+    query: web::Query<PageQuery>,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    let (page, limit) = store::page_bounds(query.page, query.limit, 20);
+    match data.store.blocks(page, limit) {
+        Ok(blocks) => HttpResponse::Ok().json(blocks),
+        Err(e) => storage_error(e),
+    }
 }
 
-/// Get transaction by hash
+/// Get transaction by hash, from the chain or else the node's mempool
 async fn get_transaction(
     path: web::Path<String>,
     data: web::Data<AppState>,
 ) -> impl Responder {
-    let transactions = data.transactions.lock().unwrap();
     let hash = path.into_inner();
-
-    if let Some(tx) = transactions.iter().find(|t| t.hash == hash) {
-        return HttpResponse::Ok().json(tx);
+    let found = data.tip().and_then(|tip| Ok(data.store.transaction(&hash)?.map(|tx| AppState::confirmed(tx, tip))));
+    match found {
+        Ok(Some(tx)) => return HttpResponse::Ok().json(tx),
+        Ok(None) => {}
+        Err(e) => return storage_error(e),
     }
+    match data.client.pending(&hash).await {
+        Ok(Some(pending)) => HttpResponse::Ok().json(pending.into_transaction()),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Transaction not found"
+        })),
+        Err(e) => {
+            log::warn!("Mempool lookup for {} failed: {}", hash, e);
+            HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Transaction not found"
+            }))
+        }
+    }
+}
 
-    HttpResponse::NotFound().json(serde_json::json!({
-        "error": "Transaction not found"
-    }))
+/// Get the node's mempool: size, fee histogram and top pending transactions
+async fn get_mempool(data: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(&*data.mempool.lock().unwrap())
 }
 
 /// Get address information
@@ -218,116 +413,93 @@ async fn get_address(
     path: web::Path<String>,
     data: web::Data<AppState>,
 ) -> impl Responder {
-    let transactions = data.transactions.lock().unwrap();
-    let address = path.into_inner();
-
-    // Find all transactions involving this address
-    let addr_txs: Vec<Transaction> = transactions.iter()
-        .filter(|tx| tx.sender == address || tx.recipient == address)
-        .cloned()
-        .collect();
-
-    if addr_txs.is_empty() {
-        return HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Address not found or has no transactions"
-        }));
+    match data.address_info(&path.into_inner()) {
+        Ok(Some(info)) => HttpResponse::Ok().json(info),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Address not found or has no activity"
+        })),
+        Err(e) => storage_error(e),
     }
+}
 
-    // Calculate balance and stats
-    let mut balance: i64 = 0;
-    let mut total_received: u64 = 0;
-    let mut total_sent: u64 = 0;
-
-    for tx in &addr_txs {
-        if tx.recipient == address {
-            balance += tx.amount as i64;
-            total_received += tx.amount;
-        }
-        if tx.sender == address {
-            balance -= (tx.amount + tx.fee) as i64;
-            total_sent += tx.amount + tx.fee;
-        }
+/// Get an address's transactions, newest first, one page at a time
+async fn get_address_transactions(
+    path: web::Path<String>,
+    query: web::Query<PageQuery>,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    let (page, limit) = store::page_bounds(query.page, query.limit, 20);
+    let found = data.tip().and_then(|tip| {
+        let mut txs = data.store.address_transactions(&path.into_inner(), page, limit)?;
+        txs.items = txs.items.into_iter().map(|tx| AppState::confirmed(tx, tip)).collect();
+        Ok(txs)
+    });
+    match found {
+        Ok(txs) => HttpResponse::Ok().json(txs),
+        Err(e) => storage_error(e),
     }
-
-    let info = AddressInfo {
-        address: address.clone(),
-        balance: balance.max(0) as u64,
-        total_received,
-        total_sent,
-        tx_count: addr_txs.len() as u32,
-        recent_transactions: addr_txs.into_iter().take(20).collect(),
-    };
-
-    HttpResponse::Ok().json(info)
 }
 
-/// Search for block, transaction, or address
+/// Search blocks, transactions and addresses by height, hash prefix or address
 async fn search(
     path: web::Path<String>,
     data: web::Data<AppState>,
 ) -> impl Responder {
     let query = path.into_inner();
-    let blocks = data.blocks.lock().unwrap();
-    let transactions = data.transactions.lock().unwrap();
-
-    // Try as block index
-    if let Ok(index) = query.parse::<u64>() {
-        if let Some(block) = blocks.iter().find(|b| b.index == index) {
-            return HttpResponse::Ok().json(SearchResult::Block {
-                data: block.clone(),
-            });
-        }
+    match search::search(&data, &query) {
+        Ok(results) => HttpResponse::Ok().json(serde_json::json!({
+            "query": query,
+            "results": results,
+        })),
+        Err(SearchError::InvalidAddress(reason)) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": reason
+        })),
+        Err(SearchError::Storage(e)) => storage_error(e),
     }
+}
 
-    // Try as block hash
-    if let Some(block) = blocks.iter().find(|b| b.hash == query) {
-        return HttpResponse::Ok().json(SearchResult::Block {
-            data: block.clone(),
-        });
-    }
+/// Whether `req` carries the admin bearer token
+fn is_admin(req: &HttpRequest, data: &AppState) -> bool {
+    let Some(token) = data.admin_token.as_deref() else {
+        return false;
+    };
+    let presented = req
+        .headers()
+        .get(actix_web::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .unwrap_or("");
+    // Compare digests so the time taken does not depend on the token.
+    ratelimit::hash_key(presented) == ratelimit::hash_key(token)
+}
 
-    // Try as transaction hash
-    if let Some(tx) = transactions.iter().find(|t| t.hash == query) {
-        return HttpResponse::Ok().json(SearchResult::Transaction {
-            data: tx.clone(),
-        });
+/// Issue an API key; the key is only ever shown in this response
+async fn issue_key(req: HttpRequest, body: web::Json<IssueKeyRequest>, data: web::Data<AppState>) -> impl Responder {
+    if !is_admin(&req, &data) {
+        return HttpResponse::Unauthorized().finish();
     }
-
-    // Try as address
-    let addr_txs: Vec<Transaction> = transactions.iter()
-        .filter(|tx| tx.sender == query || tx.recipient == query)
-        .cloned()
-        .collect();
-
-    if !addr_txs.is_empty() {
-        let mut balance: i64 = 0;
-        let mut total_received: u64 = 0;
-        let mut total_sent: u64 = 0;
-
-        for tx in &addr_txs {
-            if tx.recipient == query {
-                balance += tx.amount as i64;
-                total_received += tx.amount;
-            }
-            if tx.sender == query {
-                balance -= (tx.amount + tx.fee) as i64;
-                total_sent += tx.amount + tx.fee;
-            }
-        }
-
-        let info = AddressInfo {
-            address: query.clone(),
-            balance: balance.max(0) as u64,
-            total_received,
-            total_sent,
-            tx_count: addr_txs.len() as u32,
-            recent_transactions: addr_txs.into_iter().take(20).collect(),
-        };
-
-        return HttpResponse::Ok().json(SearchResult::Address { data: info });
+    let now = chrono::Utc::now().timestamp() as u64;
+    match data.store.issue_key(body.tier, &body.label, now) {
+        Ok(key) => HttpResponse::Created().json(serde_json::json!({
+            "key": key,
+            "tier": body.tier,
+            "label": body.label,
+            "requests_per_minute": data.limiter.quotas().of(Some(body.tier)),
+        })),
+        Err(e) => storage_error(e),
     }
+}
 
-    HttpResponse::Ok().json(SearchResult::NotFound)
+/// Revoke an API key
+async fn revoke_key(req: HttpRequest, path: web::Path<String>, data: web::Data<AppState>) -> impl Responder {
+    if !is_admin(&req, &data) {
+        return HttpResponse::Unauthorized().finish();
+    }
+    match data.store.revoke_key(&path.into_inner()) {
+        Ok(true) => HttpResponse::NoContent().finish(),
+        Ok(false) => HttpResponse::NotFound().finish(),
+        Err(e) => storage_error(e),
+    }
 }
 
 /// Health check endpoint
@@ -345,23 +517,60 @@ async fn main() -> std::io::Result<()> {
 
     log::info!("Starting Axiom Explorer Backend...");
 
-    let app_state = web::Data::new(AppState::new());
+    let db_path = std::env::var("EXPLORER_DB").unwrap_or_else(|_| "explorer.db".to_string());
+    let store = Store::open(&db_path).map_err(|e| std::io::Error::other(format!("cannot open {}: {}", db_path, e)))?;
+    let node_url = std::env::var("AXIOM_NODE_URL").unwrap_or_else(|_| "http://127.0.0.1:8080".to_string());
+    let client = NodeClient::new(&node_url).map_err(|e| std::io::Error::other(format!("cannot create HTTP client: {}", e)))?;
+    let mut state = AppState::new(store, client);
+    state.limiter = RateLimiter::new(Quotas::from_env());
+    state.trust_proxy = std::env::var("EXPLORER_TRUST_PROXY").is_ok_and(|v| v == "1" || v == "true");
+    state.admin_token = std::env::var("EXPLORER_ADMIN_TOKEN").ok().filter(|t| !t.is_empty());
+    let quotas = state.limiter.quotas();
+    log::info!(
+        "Rate limits per minute: {} per IP, {} standard key, {} premium key",
+        quotas.anonymous, quotas.standard, quotas.premium
+    );
+    let cors_origins: Vec<String> = std::env::var("EXPLORER_CORS_ORIGINS")
+        .map(|v| v.split(',').map(|o| o.trim().to_string()).filter(|o| !o.is_empty()).collect())
+        .unwrap_or_default();
+    let app_state = web::Data::new(state);
+    tokio::spawn(sync::run(app_state.clone()));
+    let port = std::env::var("EXPLORER_PORT").ok().and_then(|p| p.parse().ok()).unwrap_or(8080);
 
     HttpServer::new(move || {
-        let cors = Cors::permissive(); // Allow all origins for development
+        // Any origin unless EXPLORER_CORS_ORIGINS lists some
+        let cors = if cors_origins.is_empty() {
+            Cors::permissive()
+        } else {
+            cors_origins
+                .iter()
+                .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+                .allowed_methods(["GET"])
+                .allowed_header(ratelimit::API_KEY_HEADER)
+        };
 
         App::new()
+            .wrap(middleware::from_fn(ratelimit::limit))
             .wrap(cors)
             .app_data(app_state.clone())
             .route("/health", web::get().to(health))
+            .route("/ws", web::get().to(live::socket))
             .route("/api/stats", web::get().to(get_stats))
-            .route("/api/blocks", web::get().to(get_latest_blocks))
+            .route("/api/supply", web::get().to(get_supply))
+            .route("/api/supply/circulating", web::get().to(get_circulating_supply))
+            .route("/api/charts/{metric}", web::get().to(get_chart))
+            .route("/api/pulses", web::get().to(get_pulses))
+            .route("/api/blocks", web::get().to(get_blocks))
             .route("/api/block/{id}", web::get().to(get_block))
             .route("/api/transaction/{hash}", web::get().to(get_transaction))
+            .route("/api/mempool", web::get().to(get_mempool))
             .route("/api/address/{address}", web::get().to(get_address))
+            .route("/api/address/{address}/txs", web::get().to(get_address_transactions))
             .route("/api/search/{query}", web::get().to(search))
+            .route("/admin/keys", web::post().to(issue_key))
+            .route("/admin/keys/{key}", web::delete().to(revoke_key))
     })
-    .bind(("0.0.0.0", 8080))?
+    .bind(("0.0.0.0", port))?
     .run()
     .await
 }
@@ -372,9 +581,16 @@ mod tests {
 
     #[test]
     fn test_app_state_initialization() {
-        let state = AppState::new();
-        let blocks = state.blocks.lock().unwrap();
-        assert!(!blocks.is_empty());
-        assert_eq!(blocks[0].index, 0); // Genesis block
+        // Empty until the sync worker ingests genesis from the node
+        let state = AppState::new(Store::in_memory().unwrap(), NodeClient::new("http://127.0.0.1:8080").unwrap());
+        assert_eq!(state.store.next_height().unwrap(), 0);
+        assert_eq!(state.store.tip_hash().unwrap(), None);
+        assert!(state.supply.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_format_axm() {
+        assert_eq!(format_axm(12_345_000_000_001), "123450.00000001");
+        assert_eq!(format_axm(0), "0.00000000");
     }
 }
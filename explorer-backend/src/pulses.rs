@@ -0,0 +1,203 @@
+//! The AxiomPulse chain for `/api/pulses`.
+//!
+//! The node only keeps its recent run of block pulses, so the sync worker
+//! copies each new one into the `pulses` table. Links are checked on read:
+//! every pulse must carry the hash of the stored pulse below it as its
+//! `prev_pulse_hash`, and the lowest one the genesis pulse hash. A pulse
+//! the explorer missed, or one from a node that started unanchored, shows
+//! up as a break at that height, and nothing above it counts as verified
+//! from genesis.
+
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+
+pub const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS pulses (
+    height            INTEGER PRIMARY KEY,
+    pulse_hash        TEXT NOT NULL,
+    prev_pulse_hash   TEXT NOT NULL,
+    block_hash        TEXT NOT NULL,
+    oracle_seal       TEXT NOT NULL,
+    total_mined       INTEGER NOT NULL,
+    remaining         INTEGER NOT NULL,
+    timestamp         INTEGER NOT NULL,
+    has_stark_receipt INTEGER NOT NULL,
+    -- NULL without a receipt
+    stark_verified    INTEGER
+);
+";
+
+/// Largest height range one request returns.
+pub const MAX_PULSES: u64 = 100;
+
+/// A block pulse as the node lists it on `/v1/pulses`, 512-bit hashes in hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pulse {
+    pub height: u64,
+    pub total_mined: u64,
+    pub remaining: u64,
+    pub block_hash: String,
+    pub oracle_seal: String,
+    pub prev_pulse_hash: String,
+    pub pulse_hash: String,
+    pub timestamp: i64,
+    pub has_stark_receipt: bool,
+    pub stark_verified: Option<bool>,
+}
+
+/// A stored pulse and whether it links to the one below it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LinkedPulse {
+    #[serde(flatten)]
+    pub pulse: Pulse,
+    pub linked: bool,
+}
+
+/// The latest Global Trust Pulse commitment, from the node's `/v1/pulses`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustPulse {
+    pub height: u64,
+    pub trust_pulse_hex: String,
+    pub prev_pulse_hex: String,
+    pub timestamp: u64,
+}
+
+/// What the explorer keeps from the node's latest `/v1/pulses` besides the pulses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PulseHead {
+    pub genesis_pulse_hash: String,
+    pub trust_pulse: Option<TrustPulse>,
+}
+
+/// Response body for `/api/pulses`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PulseRange {
+    #[serde(flatten)]
+    pub head: PulseHead,
+    pub pulses: Vec<LinkedPulse>,
+    /// Lowest stored height whose pulse does not link, if any.
+    pub first_break: Option<u64>,
+    /// Whether every stored pulse up to the end of the range links back
+    /// to the genesis pulse hash.
+    pub verified_from_genesis: bool,
+}
+
+/// Inclusive heights to list: `MAX_PULSES` at most, the latest by default.
+pub fn range_bounds(from: Option<u64>, to: Option<u64>, tip: u64) -> (u64, u64) {
+    let span = MAX_PULSES - 1;
+    match (from, to) {
+        (Some(from), Some(to)) => (from, to.min(from.saturating_add(span))),
+        (Some(from), None) => (from, from.saturating_add(span)),
+        (None, Some(to)) => (to.saturating_sub(span), to),
+        (None, None) => (tip.saturating_sub(span), tip),
+    }
+}
+
+/// Store pulses above the highest one held; lower ones are already there.
+pub fn record(conn: &Connection, pulses: &[Pulse]) -> rusqlite::Result<usize> {
+    let mut stmt = conn.prepare_cached("INSERT OR IGNORE INTO pulses VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)")?;
+    let mut added = 0;
+    for p in pulses {
+        added += stmt.execute(params![
+            p.height as i64, p.pulse_hash, p.prev_pulse_hash, p.block_hash, p.oracle_seal,
+            p.total_mined as i64, p.remaining as i64, p.timestamp, p.has_stark_receipt, p.stark_verified,
+        ])?;
+    }
+    Ok(added)
+}
+
+fn linked_from_row(row: &Row) -> rusqlite::Result<LinkedPulse> {
+    Ok(LinkedPulse {
+        pulse: Pulse {
+            height: row.get::<_, i64>(0)? as u64,
+            pulse_hash: row.get(1)?,
+            prev_pulse_hash: row.get(2)?,
+            block_hash: row.get(3)?,
+            oracle_seal: row.get(4)?,
+            total_mined: row.get::<_, i64>(5)? as u64,
+            remaining: row.get::<_, i64>(6)? as u64,
+            timestamp: row.get(7)?,
+            has_stark_receipt: row.get(8)?,
+            stark_verified: row.get(9)?,
+        },
+        linked: row.get(10)?,
+    })
+}
+
+/// Each pulse with the hash it should link to: the stored pulse below it,
+/// or `?1` (the genesis pulse hash) for the lowest.
+const LINKS: &str = "
+SELECT height, pulse_hash, prev_pulse_hash, block_hash, oracle_seal, total_mined, remaining,
+       timestamp, has_stark_receipt, stark_verified,
+       prev_pulse_hash = LAG(pulse_hash, 1, ?1) OVER (ORDER BY height) AS linked
+FROM pulses";
+
+/// Stored pulses in `from..=to`, with the chain checked from genesis.
+pub fn range(conn: &Connection, from: u64, to: u64, head: &PulseHead) -> rusqlite::Result<PulseRange> {
+    let genesis_pulse_hash = head.genesis_pulse_hash.as_str();
+    let first_break: Option<i64> = conn.query_row(
+        &format!("SELECT MIN(height) FROM ({}) WHERE NOT linked", LINKS),
+        [genesis_pulse_hash],
+        |r| r.get(0),
+    )?;
+    let first_break = first_break.map(|h| h as u64);
+    let mut stmt = conn.prepare_cached(&format!(
+        "SELECT * FROM ({}) WHERE height BETWEEN ?2 AND ?3 ORDER BY height",
+        LINKS
+    ))?;
+    let pulses: Vec<LinkedPulse> = stmt
+        .query_map(params![genesis_pulse_hash, from as i64, to.min(i64::MAX as u64) as i64], linked_from_row)?
+        .collect::<rusqlite::Result<_>>()?;
+    let stored = conn.query_row("SELECT COUNT(*) FROM pulses", [], |r| r.get::<_, i64>(0))?;
+    Ok(PulseRange {
+        head: head.clone(),
+        pulses,
+        first_break,
+        verified_from_genesis: stored > 0 && first_break.is_none_or(|h| h > to),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pulse(height: u64, prev: &str) -> Pulse {
+        Pulse {
+            height,
+            total_mined: height * 50,
+            remaining: 1_000 - height * 50,
+            block_hash: format!("b{}", height),
+            oracle_seal: String::new(),
+            prev_pulse_hash: prev.to_string(),
+            pulse_hash: format!("p{}", height),
+            timestamp: height as i64,
+            has_stark_receipt: false,
+            stark_verified: None,
+        }
+    }
+
+    #[test]
+    fn test_links_checked_back_to_genesis() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        let head = PulseHead { genesis_pulse_hash: "g".to_string(), trust_pulse: None };
+        assert_eq!(record(&conn, &[pulse(1, "g"), pulse(2, "p1"), pulse(3, "p2")]).unwrap(), 3);
+        assert_eq!(record(&conn, &[pulse(3, "p2")]).unwrap(), 0);
+
+        let range = range(&conn, 2, 3, &head).unwrap();
+        assert_eq!(range.pulses.iter().map(|p| p.pulse.height).collect::<Vec<_>>(), [2, 3]);
+        assert!(range.pulses.iter().all(|p| p.linked));
+        assert!(range.verified_from_genesis);
+
+        // Pulse 4 was missed, so 5 does not link to 3.
+        record(&conn, &[pulse(5, "p4"), pulse(6, "p5")]).unwrap();
+        let below = super::range(&conn, 1, 3, &head).unwrap();
+        assert_eq!((below.first_break, below.verified_from_genesis), (Some(5), true));
+        let above = super::range(&conn, 4, 6, &head).unwrap();
+        assert_eq!(above.pulses.iter().map(|p| p.linked).collect::<Vec<_>>(), [false, true]);
+        assert!(!above.verified_from_genesis);
+
+        assert_eq!(range_bounds(None, None, 500), (401, 500));
+        assert_eq!(range_bounds(Some(10), Some(1_000), 500), (10, 109));
+    }
+}
@@ -0,0 +1,282 @@
+//! Request quotas for the public API.
+//!
+//! Every request spends a token from a bucket that refills continuously at
+//! the client's per-minute quota and holds at most one minute's worth, so a
+//! client can burst up to its quota and then sustain it. Anonymous clients
+//! get a bucket per IP address. A request carrying an `X-API-Key` header
+//! gets the bucket of that key instead, at its tier's higher quota.
+//!
+//! Keys are issued and revoked on `/admin/keys` with the operator's
+//! `EXPLORER_ADMIN_TOKEN`. Only a SHA-256 of each key is stored, in an
+//! `api_keys` table that survives the resyncs that reset the chain tables.
+//!
+//! A request over quota gets 429 with `Retry-After`; every other response
+//! carries `X-RateLimit-Limit` and `X-RateLimit-Remaining`.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue, RETRY_AFTER};
+use actix_web::middleware::Next;
+use actix_web::{web, HttpResponse};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::AppState;
+
+pub const KEYS_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS api_keys (
+    key_hash TEXT PRIMARY KEY,
+    tier     TEXT NOT NULL,
+    label    TEXT NOT NULL,
+    created  INTEGER NOT NULL
+);
+";
+
+/// Header carrying an API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Buckets tracked before full (idle) ones are dropped.
+const MAX_TRACKED: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    Standard,
+    Premium,
+}
+
+impl Tier {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "standard" => Some(Tier::Standard),
+            "premium" => Some(Tier::Premium),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Tier::Standard => "standard",
+            Tier::Premium => "premium",
+        }
+    }
+}
+
+/// Requests per minute for each kind of client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quotas {
+    /// Per IP address, without a key.
+    pub anonymous: u32,
+    pub standard: u32,
+    pub premium: u32,
+}
+
+impl Default for Quotas {
+    fn default() -> Self {
+        Self { anonymous: 60, standard: 600, premium: 6_000 }
+    }
+}
+
+impl Quotas {
+    /// Defaults overridden by `EXPLORER_RATE_LIMIT`, `EXPLORER_STANDARD_RATE_LIMIT`
+    /// and `EXPLORER_PREMIUM_RATE_LIMIT`.
+    pub fn from_env() -> Self {
+        let var = |name: &str, default: u32| {
+            std::env::var(name).ok().and_then(|v| v.parse().ok()).filter(|&v| v > 0).unwrap_or(default)
+        };
+        let defaults = Self::default();
+        Self {
+            anonymous: var("EXPLORER_RATE_LIMIT", defaults.anonymous),
+            standard: var("EXPLORER_STANDARD_RATE_LIMIT", defaults.standard),
+            premium: var("EXPLORER_PREMIUM_RATE_LIMIT", defaults.premium),
+        }
+    }
+
+    pub fn of(&self, tier: Option<Tier>) -> u32 {
+        match tier {
+            None => self.anonymous,
+            Some(Tier::Standard) => self.standard,
+            Some(Tier::Premium) => self.premium,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Client {
+    Ip(IpAddr),
+    /// SHA-256 of the key.
+    Key(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    per_minute: u32,
+}
+
+impl Bucket {
+    fn rate(&self) -> f64 {
+        self.per_minute as f64 / 60.0
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate()).min(self.per_minute as f64);
+        self.updated = now;
+    }
+}
+
+pub struct RateLimiter {
+    quotas: Quotas,
+    buckets: Mutex<HashMap<Client, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(quotas: Quotas) -> Self {
+        Self { quotas, buckets: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn quotas(&self) -> Quotas {
+        self.quotas
+    }
+
+    /// Spend one of `client`'s tokens at `per_minute`. Returns the whole
+    /// tokens left, or how long until the next one.
+    pub fn check(&self, client: Client, per_minute: u32, now: Instant) -> Result<u32, Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_TRACKED && !buckets.contains_key(&client) {
+            buckets.retain(|_, b| {
+                b.refill(now);
+                b.tokens < b.per_minute as f64
+            });
+        }
+        let bucket = buckets.entry(client).or_insert(Bucket { tokens: per_minute as f64, updated: now, per_minute });
+        bucket.refill(now);
+        // A changed quota applies from now on.
+        bucket.per_minute = per_minute;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(bucket.tokens as u32);
+        }
+        Err(Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.rate()))
+    }
+}
+
+pub fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Create and store a key; the plaintext is returned once and never kept.
+pub fn issue_key(conn: &Connection, tier: Tier, label: &str, now: u64) -> rusqlite::Result<String> {
+    let key = format!("axk_{}", rand::random::<[u8; 24]>().iter().map(|b| format!("{:02x}", b)).collect::<String>());
+    conn.execute(
+        "INSERT INTO api_keys (key_hash, tier, label, created) VALUES (?1, ?2, ?3, ?4)",
+        params![hash_key(&key), tier.as_str(), label, now as i64],
+    )?;
+    Ok(key)
+}
+
+/// Tier of the key with this hash, if it is issued.
+pub fn key_tier(conn: &Connection, key_hash: &str) -> rusqlite::Result<Option<Tier>> {
+    let tier: Option<String> = conn
+        .query_row("SELECT tier FROM api_keys WHERE key_hash = ?1", [key_hash], |r| r.get(0))
+        .optional()?;
+    Ok(tier.as_deref().and_then(Tier::parse))
+}
+
+/// Whether a key was revoked (false if it was never issued).
+pub fn revoke_key(conn: &Connection, key: &str) -> rusqlite::Result<bool> {
+    conn.execute("DELETE FROM api_keys WHERE key_hash = ?1", [hash_key(key)]).map(|n| n > 0)
+}
+
+fn client_ip(req: &ServiceRequest, trust_proxy: bool) -> Option<IpAddr> {
+    if trust_proxy {
+        let info = req.connection_info();
+        if let Some(ip) = info.realip_remote_addr().and_then(|a| a.parse().ok()) {
+            return Some(ip);
+        }
+    }
+    req.peer_addr().map(|a| a.ip())
+}
+
+/// Middleware charging each request to its key or IP.
+pub async fn limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> actix_web::Result<ServiceResponse<impl MessageBody>> {
+    let Some(state) = req.app_data::<web::Data<AppState>>().cloned() else {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    };
+    if req.path() == "/health" {
+        return next.call(req).await.map(ServiceResponse::map_into_left_body);
+    }
+    let key = req.headers().get(API_KEY_HEADER).and_then(|v| v.to_str().ok()).map(hash_key);
+    let (client, tier) = match key {
+        Some(key_hash) => match state.store.key_tier(&key_hash) {
+            Ok(Some(tier)) => (Client::Key(key_hash), Some(tier)),
+            Ok(None) => {
+                let response = HttpResponse::Unauthorized().json(serde_json::json!({ "error": "Unknown API key" }));
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+            Err(e) => return Ok(req.into_response(crate::storage_error(e)).map_into_right_body()),
+        },
+        None => match client_ip(&req, state.trust_proxy) {
+            Some(ip) => (Client::Ip(ip), None),
+            None => return next.call(req).await.map(ServiceResponse::map_into_left_body),
+        },
+    };
+    let per_minute = state.limiter.quotas().of(tier);
+    match state.limiter.check(client, per_minute, Instant::now()) {
+        Ok(remaining) => {
+            let mut response = next.call(req).await?;
+            let headers = response.headers_mut();
+            headers.insert(HeaderName::from_static("x-ratelimit-limit"), HeaderValue::from(per_minute));
+            headers.insert(HeaderName::from_static("x-ratelimit-remaining"), HeaderValue::from(remaining));
+            Ok(response.map_into_left_body())
+        }
+        Err(wait) => {
+            let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+            let response = HttpResponse::TooManyRequests()
+                .insert_header((RETRY_AFTER, retry_after))
+                .insert_header((HeaderName::from_static("x-ratelimit-limit"), per_minute))
+                .json(serde_json::json!({ "error": "Rate limit exceeded", "retry_after": retry_after }));
+            Ok(req.into_response(response).map_into_right_body())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quotas_and_keys() {
+        let limiter = RateLimiter::new(Quotas::default());
+        let ip = Client::Ip("203.0.113.7".parse().unwrap());
+        let start = Instant::now();
+        for left in (0..60).rev() {
+            assert_eq!(limiter.check(ip.clone(), 60, start), Ok(left));
+        }
+        let wait = limiter.check(ip.clone(), 60, start).unwrap_err();
+        assert_eq!(wait.as_secs_f64().ceil(), 1.0);
+        assert_eq!(limiter.check(ip.clone(), 60, start + Duration::from_secs(1)), Ok(0));
+
+        // A key has its own bucket.
+        assert_eq!(limiter.check(Client::Key(hash_key("k")), 600, start), Ok(599));
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(KEYS_SCHEMA).unwrap();
+        let key = issue_key(&conn, Tier::Premium, "indexer", 1_000).unwrap();
+        assert_eq!(key_tier(&conn, &hash_key(&key)).unwrap(), Some(Tier::Premium));
+        assert_eq!(key_tier(&conn, &hash_key("axk_guess")).unwrap(), None);
+        assert!(revoke_key(&conn, &key).unwrap());
+        assert!(!revoke_key(&conn, &key).unwrap());
+    }
+}
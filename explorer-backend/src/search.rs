@@ -0,0 +1,145 @@
+//! Search for `/api/search/{query}`.
+//!
+//! A query can match several things at once: `1234` is a block height and
+//! also the start of some hashes. Every interpretation is tried and the hits
+//! are returned best first. An exact match (a height, a whole hash, a known
+//! address) scores 1; a hex prefix scores the share of the 64 digits it pins
+//! down, so longer prefixes rank higher.
+//!
+//! Addresses are accepted as bech32m (`axm1...`), whose checksum is checked
+//! so that a mistyped address is reported rather than silently not found,
+//! or as hex with or without the legacy `axm1` prefix.
+
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32m, Hrp};
+use serde::Serialize;
+
+use crate::{AppState, BlockSummary, Transaction};
+
+/// Human-readable part of bech32m addresses.
+pub const ADDRESS_HRP: &str = "axm";
+
+/// Shortest hex prefix matched against hashes and addresses.
+pub const MIN_PREFIX_LEN: usize = 4;
+
+/// Hits returned per query, and looked up per kind.
+pub const MAX_RESULTS: usize = 20;
+
+/// Hex digits in a block hash, transaction hash or address.
+const HEX_LEN: usize = 64;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Match {
+    Block { data: BlockSummary },
+    Transaction { data: Transaction },
+    Address { address: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub item: Match,
+    /// 1 for an exact match, less for a prefix.
+    pub score: f64,
+}
+
+#[derive(Debug)]
+pub enum SearchError {
+    /// Looked like an address but failed to decode, e.g. a bad checksum.
+    InvalidAddress(String),
+    Storage(rusqlite::Error),
+}
+
+impl From<rusqlite::Error> for SearchError {
+    fn from(e: rusqlite::Error) -> Self {
+        SearchError::Storage(e)
+    }
+}
+
+/// Decode a bech32m address to the hex form the chain stores.
+pub fn parse_bech32(address: &str) -> Result<String, String> {
+    let checked = CheckedHrpstring::new::<Bech32m>(address).map_err(|e| format!("Invalid address: {}", e))?;
+    if checked.hrp() != Hrp::parse_unchecked(ADDRESS_HRP) {
+        return Err(format!("Invalid address prefix: expected {}1, got {}1", ADDRESS_HRP, checked.hrp()));
+    }
+    let bytes: Vec<u8> = checked.byte_iter().collect();
+    if bytes.len() != HEX_LEN / 2 {
+        return Err(format!("Invalid address length: {}", bytes.len()));
+    }
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn prefix_score(prefix: &str) -> f64 {
+    prefix.len().min(HEX_LEN) as f64 / HEX_LEN as f64
+}
+
+/// Everything `query` could refer to, best first.
+pub fn search(state: &AppState, query: &str) -> Result<Vec<SearchHit>, SearchError> {
+    let query = query.trim().to_ascii_lowercase();
+    let mut hits = Vec::new();
+
+    if let Some(rest) = query.strip_prefix("axm1") {
+        let address = if is_hex(rest) && rest.len() == HEX_LEN {
+            rest.to_string()
+        } else {
+            parse_bech32(&query).map_err(SearchError::InvalidAddress)?
+        };
+        // Well-formed, so it is an address even with no history yet.
+        hits.push(SearchHit { item: Match::Address { address }, score: 1.0 });
+        return Ok(hits);
+    }
+
+    if let Ok(height) = query.parse::<u64>() {
+        if let Some(block) = state.store.block(&height.to_string())? {
+            hits.push(SearchHit { item: Match::Block { data: BlockSummary::from(&block) }, score: 1.0 });
+        }
+    }
+
+    if is_hex(&query) && query.len() >= MIN_PREFIX_LEN && query.len() <= HEX_LEN {
+        let score = prefix_score(&query);
+        let limit = MAX_RESULTS as u64;
+        for block in state.store.blocks_by_hash_prefix(&query, limit)? {
+            hits.push(SearchHit { item: Match::Block { data: BlockSummary::from(&block) }, score });
+        }
+        let tip = state.tip()?;
+        for tx in state.store.transactions_by_hash_prefix(&query, limit)? {
+            hits.push(SearchHit { item: Match::Transaction { data: AppState::confirmed(tx, tip) }, score });
+        }
+        for address in state.store.addresses_by_prefix(&query, limit)? {
+            hits.push(SearchHit { item: Match::Address { address }, score });
+        }
+    }
+
+    // Stable, so equal scores keep block, transaction, address order.
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits.truncate(MAX_RESULTS);
+    Ok(hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bech32_checksum_is_enforced() {
+        let bytes = [7u8; 32];
+        let hrp = Hrp::parse_unchecked(ADDRESS_HRP);
+        let address = bech32::encode::<Bech32m>(hrp, &bytes).unwrap();
+        assert_eq!(parse_bech32(&address).unwrap(), "07".repeat(32));
+
+        // Flip one data character.
+        let mut typo = address.clone().into_bytes();
+        let last = typo.len() - 1;
+        typo[last] = if typo[last] == b'q' { b'p' } else { b'q' };
+        assert!(parse_bech32(std::str::from_utf8(&typo).unwrap()).is_err());
+
+        let foreign = bech32::encode::<Bech32m>(Hrp::parse_unchecked("cosmos"), &bytes).unwrap();
+        assert!(parse_bech32(&foreign).unwrap_err().contains("prefix"));
+        assert_eq!(prefix_score(&"a".repeat(16)), 0.25);
+    }
+}
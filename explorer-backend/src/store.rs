@@ -0,0 +1,595 @@
+//! SQLite storage for ingested blocks and transactions.
+//!
+//! Blocks are keyed by height and indexed by hash; transactions by hash,
+//! with indexes on sender and recipient so an address page is an index
+//! range rather than a scan. Everything the sync worker ingests survives a
+//! restart, and the worker resumes from the stored tip.
+//!
+//! The database is a cache of the node's chain. When the schema changes it
+//! is dropped and synced again rather than migrated; only the issued API
+//! keys are kept.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use crate::charts::{self, ChartPoint, Interval, Metric};
+use crate::pulses::{self, Pulse, PulseHead, PulseRange};
+use crate::ratelimit::{self, Tier};
+use crate::{Block, Transaction, TxStatus};
+
+/// Largest page any list endpoint returns.
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+/// Bumped on every schema change; a database at another version is reset.
+const SCHEMA_VERSION: i64 = 3;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    height        INTEGER PRIMARY KEY,
+    hash          TEXT NOT NULL UNIQUE,
+    previous_hash TEXT NOT NULL,
+    timestamp     INTEGER NOT NULL,
+    miner         TEXT NOT NULL,
+    nonce         INTEGER NOT NULL,
+    merkle_root   TEXT NOT NULL,
+    vdf_proof     TEXT,
+    size          INTEGER NOT NULL,
+    reward        INTEGER NOT NULL,
+    difficulty    INTEGER NOT NULL,
+    fees_burned   INTEGER NOT NULL,
+    miner_fees    INTEGER NOT NULL,
+    -- Seconds since the parent block; NULL for genesis
+    block_time    INTEGER
+);
+CREATE INDEX IF NOT EXISTS blocks_miner ON blocks (miner, height);
+CREATE TABLE IF NOT EXISTS transactions (
+    hash         TEXT PRIMARY KEY,
+    block_height INTEGER NOT NULL,
+    position     INTEGER NOT NULL,
+    sender       TEXT NOT NULL,
+    recipient    TEXT NOT NULL,
+    amount       INTEGER NOT NULL,
+    fee          INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    signature    TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transactions_block ON transactions (block_height, position);
+CREATE INDEX IF NOT EXISTS transactions_sender ON transactions (sender, block_height);
+CREATE INDEX IF NOT EXISTS transactions_recipient ON transactions (recipient, block_height);
+";
+
+const TX_COLUMNS: &str =
+    "t.hash, t.sender, t.recipient, t.amount, t.fee, t.timestamp, t.signature, b.hash, t.block_height";
+
+/// One page of a list, newest first.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Page<T> {
+    pub page: u64,
+    pub limit: u64,
+    pub total: u64,
+    pub items: Vec<T>,
+}
+
+/// 1-based page number and page size, clamped to sane bounds.
+pub fn page_bounds(page: Option<u64>, limit: Option<u64>, default_limit: u64) -> (u64, u64) {
+    (page.unwrap_or(1).max(1), limit.unwrap_or(default_limit).clamp(1, MAX_PAGE_SIZE))
+}
+
+/// Per-address totals over every stored transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AddressTotals {
+    pub tx_count: u64,
+    pub total_received: u64,
+    pub total_sent: u64,
+}
+
+/// Blocks mined by an address and what they paid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct MiningRewards {
+    pub blocks_mined: u64,
+    pub total_rewards: u64,
+    /// Fee shares credited for those blocks.
+    pub total_fees: u64,
+    pub first_block: Option<u64>,
+    pub last_block: Option<u64>,
+}
+
+/// One UTC day of activity and the balance at its close.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct DailyBalance {
+    /// Unix timestamp of the day's midnight (UTC).
+    pub day: u64,
+    pub received: u64,
+    pub sent: u64,
+    pub mined: u64,
+    pub balance: u64,
+}
+
+/// Another address this one has transacted with.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Counterparty {
+    pub address: String,
+    pub tx_count: u64,
+    /// Sent to the counterparty, fees excluded.
+    pub sent: u64,
+    pub received: u64,
+}
+
+/// Everything the address page charts, in one round trip.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressAnalytics {
+    pub first_seen: Option<u64>,
+    pub last_seen: Option<u64>,
+    pub mining: MiningRewards,
+    /// Days with activity only, oldest first.
+    pub balance_history: Vec<DailyBalance>,
+    /// Most frequent counterparties first.
+    pub counterparties: Vec<Counterparty>,
+}
+
+const SECS_PER_DAY: i64 = 86_400;
+
+pub struct Store {
+    conn: Mutex<Connection>,
+}
+
+fn tx_from_row(row: &Row) -> rusqlite::Result<Transaction> {
+    Ok(Transaction {
+        hash: row.get(0)?,
+        sender: row.get(1)?,
+        recipient: row.get(2)?,
+        amount: row.get::<_, i64>(3)? as u64,
+        fee: row.get::<_, i64>(4)? as u64,
+        timestamp: row.get::<_, i64>(5)? as u64,
+        signature: row.get(6)?,
+        block_hash: row.get(7)?,
+        block_index: row.get::<_, Option<i64>>(8)?.map(|h| h as u64),
+        confirmations: 0,
+        status: TxStatus::Confirmed,
+        zk_proof: None,
+    })
+}
+
+fn block_from_row(row: &Row) -> rusqlite::Result<Block> {
+    Ok(Block {
+        index: row.get::<_, i64>(0)? as u64,
+        hash: row.get(1)?,
+        previous_hash: row.get(2)?,
+        timestamp: row.get::<_, i64>(3)? as u64,
+        transactions: vec![],
+        miner: row.get(4)?,
+        nonce: row.get::<_, i64>(5)? as u64,
+        merkle_root: row.get(6)?,
+        vdf_proof: row.get(7)?,
+        size: row.get::<_, i64>(8)? as usize,
+        reward: row.get::<_, i64>(9)? as u64,
+        difficulty: row.get::<_, i64>(10)? as u64,
+        fees_burned: row.get::<_, i64>(11)? as u64,
+        miner_fees: row.get::<_, i64>(12)? as u64,
+    })
+}
+
+const BLOCK_COLUMNS: &str =
+    "height, hash, previous_hash, timestamp, miner, nonce, merkle_root, vdf_proof, size, reward, difficulty, \
+     fees_burned, miner_fees";
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        let version: i64 = conn.pragma_query_value(None, "user_version", |r| r.get(0))?;
+        if version != SCHEMA_VERSION {
+            conn.execute_batch(
+                "DROP TABLE IF EXISTS blocks; DROP TABLE IF EXISTS transactions;
+                 DROP TABLE IF EXISTS rollups; DROP TABLE IF EXISTS pulses;",
+            )?;
+        }
+        conn.execute_batch(SCHEMA)?;
+        conn.execute_batch(charts::SCHEMA)?;
+        conn.execute_batch(pulses::SCHEMA)?;
+        // Not chain data: kept when the tables above are reset.
+        conn.execute_batch(ratelimit::KEYS_SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+
+    /// Height of the next block to ingest.
+    pub fn next_height(&self) -> rusqlite::Result<u64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT COALESCE(MAX(height) + 1, 0) FROM blocks", [], |r| r.get::<_, i64>(0))
+            .map(|h| h as u64)
+    }
+
+    pub fn tip_hash(&self) -> rusqlite::Result<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT hash FROM blocks ORDER BY height DESC LIMIT 1", [], |r| r.get(0))
+            .optional()
+    }
+
+    /// Store a block and its transactions atomically, and fold it into
+    /// the chart rollups.
+    pub fn apply_block(&self, block: &Block) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let db = conn.transaction()?;
+        let parent_timestamp: Option<i64> = match block.index.checked_sub(1) {
+            Some(parent) => db
+                .query_row("SELECT timestamp FROM blocks WHERE height = ?1", [parent as i64], |r| r.get(0))
+                .optional()?,
+            None => None,
+        };
+        let block_time = parent_timestamp.map(|t| block.timestamp.saturating_sub(t as u64));
+        db.execute(
+            &format!(
+                "INSERT INTO blocks ({}, block_time) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                BLOCK_COLUMNS
+            ),
+            params![
+                block.index as i64, block.hash, block.previous_hash, block.timestamp as i64, block.miner,
+                block.nonce as i64, block.merkle_root, block.vdf_proof, block.size as i64, block.reward as i64,
+                block.difficulty as i64, block.fees_burned as i64, block.miner_fees as i64, block_time.map(|t| t as i64),
+            ],
+        )?;
+        for (position, tx) in block.transactions.iter().enumerate() {
+            db.execute(
+                "INSERT OR REPLACE INTO transactions
+                 (hash, block_height, position, sender, recipient, amount, fee, timestamp, signature)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    tx.hash, block.index as i64, position as i64, tx.sender, tx.recipient,
+                    tx.amount as i64, tx.fee as i64, block.timestamp as i64, tx.signature,
+                ],
+            )?;
+        }
+        charts::add_block(&db, block, block_time)?;
+        db.commit()
+    }
+
+    /// Drop every block at or above `height`, with its transactions.
+    pub fn rollback_to(&self, height: u64) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let db = conn.transaction()?;
+        let first_dropped: Option<i64> =
+            db.query_row("SELECT MIN(timestamp) FROM blocks WHERE height >= ?1", [height as i64], |r| r.get(0))?;
+        db.execute("DELETE FROM transactions WHERE block_height >= ?1", [height as i64])?;
+        db.execute("DELETE FROM blocks WHERE height >= ?1", [height as i64])?;
+        if let Some(timestamp) = first_dropped {
+            charts::rebuild_from(&db, timestamp as u64)?;
+        }
+        db.commit()
+    }
+
+    /// The latest `points` buckets of `metric`, oldest first.
+    pub fn chart(&self, metric: Metric, interval: Interval, points: u64) -> rusqlite::Result<Vec<ChartPoint>> {
+        charts::series(&self.conn.lock().unwrap(), metric, interval, points)
+    }
+
+    /// Height of the highest stored pulse.
+    pub fn pulse_tip(&self) -> rusqlite::Result<Option<u64>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT MAX(height) FROM pulses", [], |r| r.get::<_, Option<i64>>(0))
+            .map(|h| h.map(|h| h as u64))
+    }
+
+    pub fn record_pulses(&self, new: &[Pulse]) -> rusqlite::Result<usize> {
+        pulses::record(&self.conn.lock().unwrap(), new)
+    }
+
+    /// Stored pulses in `from..=to`, links checked back to the genesis pulse hash.
+    pub fn pulses(&self, from: u64, to: u64, head: &PulseHead) -> rusqlite::Result<PulseRange> {
+        pulses::range(&self.conn.lock().unwrap(), from, to, head)
+    }
+
+    pub fn issue_key(&self, tier: Tier, label: &str, now: u64) -> rusqlite::Result<String> {
+        ratelimit::issue_key(&self.conn.lock().unwrap(), tier, label, now)
+    }
+
+    pub fn key_tier(&self, key_hash: &str) -> rusqlite::Result<Option<Tier>> {
+        ratelimit::key_tier(&self.conn.lock().unwrap(), key_hash)
+    }
+
+    pub fn revoke_key(&self, key: &str) -> rusqlite::Result<bool> {
+        ratelimit::revoke_key(&self.conn.lock().unwrap(), key)
+    }
+
+    fn with_transactions(conn: &Connection, mut block: Block) -> rusqlite::Result<Block> {
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM transactions t JOIN blocks b ON b.height = t.block_height
+             WHERE t.block_height = ?1 ORDER BY t.position",
+            TX_COLUMNS
+        ))?;
+        block.transactions = stmt
+            .query_map([block.index as i64], tx_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(block)
+    }
+
+    /// A block with its transactions, by height or hash.
+    pub fn block(&self, id: &str) -> rusqlite::Result<Option<Block>> {
+        let conn = self.conn.lock().unwrap();
+        let block = match id.parse::<u64>() {
+            Ok(height) => conn
+                .query_row(&format!("SELECT {} FROM blocks WHERE height = ?1", BLOCK_COLUMNS), [height as i64], block_from_row)
+                .optional()?,
+            Err(_) => None,
+        };
+        let block = match block {
+            Some(block) => Some(block),
+            None => conn
+                .query_row(&format!("SELECT {} FROM blocks WHERE hash = ?1", BLOCK_COLUMNS), [id], block_from_row)
+                .optional()?,
+        };
+        block.map(|b| Self::with_transactions(&conn, b)).transpose()
+    }
+
+    /// Blocks newest first, `limit` per 1-based `page`.
+    pub fn blocks(&self, page: u64, limit: u64) -> rusqlite::Result<Page<Block>> {
+        let conn = self.conn.lock().unwrap();
+        let total = conn.query_row("SELECT COUNT(*) FROM blocks", [], |r| r.get::<_, i64>(0))? as u64;
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM blocks ORDER BY height DESC LIMIT ?1 OFFSET ?2",
+            BLOCK_COLUMNS
+        ))?;
+        let blocks = stmt
+            .query_map(params![limit as i64, ((page - 1) * limit) as i64], block_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        let items = blocks
+            .into_iter()
+            .map(|b| Self::with_transactions(&conn, b))
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Page { page, limit, total, items })
+    }
+
+    /// Timestamp of the lowest stored block.
+    pub fn first_timestamp(&self) -> rusqlite::Result<Option<u64>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT timestamp FROM blocks ORDER BY height LIMIT 1", [], |r| r.get::<_, i64>(0))
+            .optional()
+            .map(|t| t.map(|t| t as u64))
+    }
+
+    pub fn transaction_count(&self) -> rusqlite::Result<u64> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT COUNT(*) FROM transactions", [], |r| r.get::<_, i64>(0)).map(|n| n as u64)
+    }
+
+    pub fn transaction(&self, hash: &str) -> rusqlite::Result<Option<Transaction>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("SELECT {} FROM transactions t JOIN blocks b ON b.height = t.block_height WHERE t.hash = ?1", TX_COLUMNS),
+            [hash],
+            tx_from_row,
+        )
+        .optional()
+    }
+
+    pub fn address_totals(&self, address: &str) -> rusqlite::Result<AddressTotals> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(CASE WHEN recipient = ?1 THEN amount ELSE 0 END), 0),
+                    COALESCE(SUM(CASE WHEN sender = ?1 THEN amount + fee ELSE 0 END), 0)
+             FROM transactions WHERE sender = ?1 OR recipient = ?1",
+            [address],
+            |r| Ok(AddressTotals {
+                tx_count: r.get::<_, i64>(0)? as u64,
+                total_received: r.get::<_, i64>(1)? as u64,
+                total_sent: r.get::<_, i64>(2)? as u64,
+            }),
+        )
+    }
+
+    /// Activity timeline, mining rewards and the `top` counterparties of `address`.
+    pub fn address_analytics(&self, address: &str, top: u64) -> rusqlite::Result<AddressAnalytics> {
+        let conn = self.conn.lock().unwrap();
+        let mining = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(reward), 0), COALESCE(SUM(miner_fees), 0), MIN(height), MAX(height)
+             FROM blocks WHERE miner = ?1",
+            [address],
+            |r| Ok(MiningRewards {
+                blocks_mined: r.get::<_, i64>(0)? as u64,
+                total_rewards: r.get::<_, i64>(1)? as u64,
+                total_fees: r.get::<_, i64>(2)? as u64,
+                first_block: r.get::<_, Option<i64>>(3)?.map(|h| h as u64),
+                last_block: r.get::<_, Option<i64>>(4)?.map(|h| h as u64),
+            }),
+        )?;
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT timestamp / ?2 AS day, MIN(timestamp), MAX(timestamp),
+                    SUM(received), SUM(sent), SUM(mined)
+             FROM (
+                 SELECT timestamp,
+                        CASE WHEN recipient = ?1 THEN amount ELSE 0 END AS received,
+                        CASE WHEN sender = ?1 THEN amount + fee ELSE 0 END AS sent,
+                        0 AS mined
+                 FROM transactions WHERE sender = ?1 OR recipient = ?1
+                 UNION ALL
+                 SELECT timestamp, 0, 0, reward + miner_fees FROM blocks WHERE miner = ?1
+             )
+             GROUP BY day ORDER BY day",
+        )?;
+        let mut analytics = AddressAnalytics { mining, ..Default::default() };
+        let mut balance = 0u64;
+        let mut rows = stmt.query(params![address, SECS_PER_DAY])?;
+        while let Some(row) = rows.next()? {
+            let (day, first, last) = (row.get::<_, i64>(0)?, row.get::<_, i64>(1)? as u64, row.get::<_, i64>(2)? as u64);
+            let (received, sent, mined) = (row.get::<_, i64>(3)? as u64, row.get::<_, i64>(4)? as u64, row.get::<_, i64>(5)? as u64);
+            balance = (balance + received + mined).saturating_sub(sent);
+            analytics.first_seen.get_or_insert(first);
+            analytics.last_seen = Some(last);
+            analytics.balance_history.push(DailyBalance { day: (day * SECS_PER_DAY) as u64, received, sent, mined, balance });
+        }
+
+        let mut stmt = conn.prepare_cached(
+            "SELECT CASE WHEN sender = ?1 THEN recipient ELSE sender END AS other, COUNT(*),
+                    SUM(CASE WHEN sender = ?1 THEN amount ELSE 0 END) AS sent,
+                    SUM(CASE WHEN recipient = ?1 THEN amount ELSE 0 END) AS received
+             FROM transactions WHERE sender = ?1 OR recipient = ?1
+             GROUP BY other ORDER BY COUNT(*) DESC, sent + received DESC, other LIMIT ?2",
+        )?;
+        analytics.counterparties = stmt
+            .query_map(params![address, top as i64], |r| Ok(Counterparty {
+                address: r.get(0)?,
+                tx_count: r.get::<_, i64>(1)? as u64,
+                sent: r.get::<_, i64>(2)? as u64,
+                received: r.get::<_, i64>(3)? as u64,
+            }))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(analytics)
+    }
+
+    // Prefix lookups bind `<prefix>*` to GLOB, which SQLite answers from the
+    // hash, sender, recipient and miner indexes. Callers pass hex only, so
+    // the prefix holds no GLOB metacharacters.
+
+    /// Blocks whose hash starts with `prefix` (lowercase hex), with their transactions.
+    pub fn blocks_by_hash_prefix(&self, prefix: &str, limit: u64) -> rusqlite::Result<Vec<Block>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM blocks WHERE hash GLOB ?1 ORDER BY height DESC LIMIT ?2",
+            BLOCK_COLUMNS
+        ))?;
+        let blocks = stmt
+            .query_map(params![format!("{}*", prefix), limit as i64], block_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        blocks.into_iter().map(|b| Self::with_transactions(&conn, b)).collect()
+    }
+
+    /// Transactions whose hash starts with `prefix` (lowercase hex).
+    pub fn transactions_by_hash_prefix(&self, prefix: &str, limit: u64) -> rusqlite::Result<Vec<Transaction>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM transactions t JOIN blocks b ON b.height = t.block_height
+             WHERE t.hash GLOB ?1 ORDER BY t.block_height DESC LIMIT ?2",
+            TX_COLUMNS
+        ))?;
+        let transactions = stmt.query_map(params![format!("{}*", prefix), limit as i64], tx_from_row)?.collect();
+        transactions
+    }
+
+    /// Addresses that sent, received or mined, starting with `prefix` (lowercase hex).
+    pub fn addresses_by_prefix(&self, prefix: &str, limit: u64) -> rusqlite::Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(
+            "SELECT sender FROM transactions WHERE sender GLOB ?1
+             UNION SELECT recipient FROM transactions WHERE recipient GLOB ?1
+             UNION SELECT miner FROM blocks WHERE miner GLOB ?1
+             ORDER BY 1 LIMIT ?2",
+        )?;
+        let addresses = stmt.query_map(params![format!("{}*", prefix), limit as i64], |r| r.get(0))?.collect();
+        addresses
+    }
+
+    /// Transactions sending to or from `address`, newest first.
+    pub fn address_transactions(&self, address: &str, page: u64, limit: u64) -> rusqlite::Result<Page<Transaction>> {
+        let total = self.address_totals(address)?.tx_count;
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(&format!(
+            "SELECT {} FROM transactions t JOIN blocks b ON b.height = t.block_height
+             WHERE t.sender = ?1 OR t.recipient = ?1
+             ORDER BY t.block_height DESC, t.position DESC LIMIT ?2 OFFSET ?3",
+            TX_COLUMNS
+        ))?;
+        let items = stmt
+            .query_map(params![address, limit as i64, ((page - 1) * limit) as i64], tx_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Page { page, limit, total, items })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(height: u64, hash: &str, parent: &str, txs: &[(&str, &str, &str, u64)]) -> Block {
+        Block {
+            index: height,
+            hash: hash.to_string(),
+            previous_hash: parent.to_string(),
+            timestamp: 1_000 + height,
+            transactions: txs
+                .iter()
+                .map(|(hash, from, to, amount)| Transaction {
+                    hash: hash.to_string(),
+                    sender: from.to_string(),
+                    recipient: to.to_string(),
+                    amount: *amount,
+                    fee: 1,
+                    timestamp: 1_000 + height,
+                    signature: String::new(),
+                    block_hash: Some(hash.to_string()),
+                    block_index: Some(height),
+                    confirmations: 0,
+                    status: TxStatus::Confirmed,
+                    zk_proof: None,
+                })
+                .collect(),
+            miner: "m".to_string(),
+            nonce: 0,
+            merkle_root: String::new(),
+            vdf_proof: None,
+            size: 100,
+            reward: 50,
+            difficulty: 1_000 + height,
+            fees_burned: txs.len() as u64 / 2,
+            miner_fees: txs.len() as u64 - txs.len() as u64 / 2,
+        }
+    }
+
+    #[test]
+    fn test_pages_and_lookups_survive_reopen() {
+        let dir = std::env::temp_dir().join(format!("explorer-store-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("explorer.db");
+        {
+            let store = Store::open(&path).unwrap();
+            store.apply_block(&block(0, "a", "0", &[])).unwrap();
+            store.apply_block(&block(1, "b", "a", &[("t1", "alice", "bob", 10), ("t2", "bob", "carol", 4)])).unwrap();
+            store.apply_block(&block(2, "c", "b", &[("t3", "carol", "alice", 2)])).unwrap();
+        }
+        let store = Store::open(&path).unwrap();
+        assert_eq!(store.next_height().unwrap(), 3);
+        assert_eq!(store.block("1").unwrap().unwrap().transactions.len(), 2);
+        assert_eq!(store.block("c").unwrap().unwrap().index, 2);
+        assert_eq!(store.transaction("t2").unwrap().unwrap().block_hash.as_deref(), Some("b"));
+
+        let page = store.blocks(2, 2).unwrap();
+        assert_eq!((page.total, page.items.len(), page.items[0].index), (3, 1, 0));
+        let txs = store.address_transactions("alice", 1, 1).unwrap();
+        assert_eq!((txs.total, txs.items[0].hash.as_str()), (2, "t3"));
+        assert_eq!(store.address_totals("bob").unwrap(), AddressTotals { tx_count: 2, total_received: 10, total_sent: 5 });
+
+        let carol = store.address_analytics("carol", 10).unwrap();
+        assert_eq!((carol.first_seen, carol.last_seen), (Some(1_001), Some(1_002)));
+        assert_eq!(carol.counterparties.iter().map(|c| c.address.as_str()).collect::<Vec<_>>(), ["bob", "alice"]);
+        let miner = store.address_analytics("m", 10).unwrap();
+        assert_eq!(miner.mining, MiningRewards { blocks_mined: 3, total_rewards: 150, total_fees: 2, first_block: Some(0), last_block: Some(2) });
+        assert_eq!(miner.balance_history.len(), 1);
+        assert_eq!(miner.balance_history[0].balance, 152);
+
+        let fees = store.chart(Metric::Fees, Interval::Hour, 10).unwrap();
+        assert_eq!((fees.len(), fees[0].value, fees[0].min, fees[0].max), (1, 1.0, Some(1), Some(1)));
+        assert_eq!(store.chart(Metric::Transactions, Interval::Day, 10).unwrap()[0].value, 3.0);
+
+        store.rollback_to(2).unwrap();
+        assert_eq!(store.tip_hash().unwrap().as_deref(), Some("b"));
+        assert_eq!(store.chart(Metric::Transactions, Interval::Day, 10).unwrap()[0].value, 2.0);
+        assert_eq!(store.chart(Metric::Difficulty, Interval::Hour, 10).unwrap()[0].value, 1_000.5);
+        assert!(store.transaction("t3").unwrap().is_none());
+
+        assert_eq!(store.transactions_by_hash_prefix("t", 10).unwrap().len(), 2);
+        assert_eq!(store.blocks_by_hash_prefix("b", 10).unwrap()[0].transactions.len(), 2);
+        assert_eq!(store.addresses_by_prefix("b", 10).unwrap(), ["bob"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
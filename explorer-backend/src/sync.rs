@@ -0,0 +1,423 @@
+//! Sync worker: keeps `AppState` in step with an Axiom node.
+//!
+//! The node serves canonical blocks by height on `/v1/blocks`. The worker
+//! asks for the blocks above the last one it holds, checks that the first
+//! one builds on its tip, and appends them. When it does not, the node has
+//! switched branches: the worker drops its tip and asks again, walking back
+//! until the two chains meet. A fresh explorer starts at height 0 and
+//! catches up page by page; a restarted one resumes from its stored tip.
+//!
+//! Each poll also copies the node's `/v1/mempool` and `/v1/supply`, and
+//! stores the block pulses from `/v1/pulses` it has not seen yet; single
+//! pending transactions are looked up on `/v1/mempool/<hash>` when asked for.
+//!
+//! Ingested blocks and changed stats are pushed to `/ws` clients.
+//!
+//! Connection failures are retried with exponential backoff; the worker
+//! never gives up, so a restarted node is picked up again on its own.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::live::{Live, Stats};
+use crate::pulses::{Pulse, PulseHead};
+use crate::store::Store;
+use crate::{AppState, Block, Transaction, TxStatus};
+
+/// Blocks requested per page (the node's maximum).
+pub const PAGE_SIZE: u64 = 100;
+
+/// Pause between polls once caught up.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Longest wait between reconnection attempts.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeTransaction {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeBlock {
+    pub height: u64,
+    pub hash: String,
+    pub parent: String,
+    pub timestamp: u64,
+    pub miner: String,
+    pub nonce: u64,
+    pub reward: u64,
+    #[serde(default)]
+    pub fees_burned: u64,
+    #[serde(default)]
+    pub miner_fees: u64,
+    pub tx_root: String,
+    pub vdf_proof: String,
+    pub size: usize,
+    pub transactions: Vec<NodeTransaction>,
+}
+
+impl NodeBlock {
+    fn into_block(self, difficulty: u64) -> Block {
+        let transactions = self.transactions
+            .into_iter()
+            .map(|tx| Transaction {
+                hash: tx.hash,
+                sender: tx.from,
+                recipient: tx.to,
+                amount: tx.amount,
+                fee: tx.fee,
+                timestamp: self.timestamp,
+                signature: tx.signature,
+                block_hash: Some(self.hash.clone()),
+                block_index: Some(self.height),
+                confirmations: 0,
+                status: TxStatus::Confirmed,
+                zk_proof: None,
+            })
+            .collect();
+        Block {
+            index: self.height,
+            hash: self.hash,
+            previous_hash: self.parent,
+            timestamp: self.timestamp,
+            transactions,
+            miner: self.miner,
+            nonce: self.nonce,
+            merkle_root: self.tx_root,
+            vdf_proof: Some(self.vdf_proof),
+            size: self.size,
+            reward: self.reward,
+            fees_burned: self.fees_burned,
+            miner_fees: self.miner_fees,
+            difficulty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlocksPage {
+    pub tip: Option<u64>,
+    pub blocks: Vec<NodeBlock>,
+}
+
+/// The parts of the node's `/v1/status` the explorer shows.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NodeStatus {
+    #[serde(default)]
+    pub difficulty: u64,
+    #[serde(default)]
+    pub connected_peers: u32,
+    #[serde(default)]
+    pub stale_blocks: u64,
+    #[serde(default)]
+    pub orphan_rate: f64,
+}
+
+/// A transaction waiting in the node's mempool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+    pub size: usize,
+    pub first_seen: u64,
+}
+
+impl PendingTransaction {
+    pub fn into_transaction(self) -> Transaction {
+        Transaction {
+            hash: self.hash,
+            sender: self.from,
+            recipient: self.to,
+            amount: self.amount,
+            fee: self.fee,
+            timestamp: self.first_seen,
+            signature: String::new(),
+            block_hash: None,
+            block_index: None,
+            confirmations: 0,
+            status: TxStatus::Pending,
+            zk_proof: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeBucket {
+    pub min_fee_rate: u64,
+    pub max_fee_rate: Option<u64>,
+    pub count: usize,
+    pub bytes: usize,
+}
+
+/// The node's `/v1/mempool`, served as is on `/api/mempool`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MempoolSummary {
+    pub size: usize,
+    pub bytes: usize,
+    pub total_fees: u64,
+    pub fee_histogram: Vec<FeeBucket>,
+    pub top: Vec<PendingTransaction>,
+}
+
+/// The node's `/v1/supply`, computed there from the emission schedule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplySnapshot {
+    pub height: u64,
+    pub max_supply: u64,
+    pub total_mined: u64,
+    pub remaining: u64,
+    pub circulating: u64,
+    #[serde(default)]
+    pub fees_burned: u64,
+    #[serde(default)]
+    pub fees_to_miners: u64,
+    #[serde(default)]
+    pub fee_burn_percent: u64,
+    pub era: u64,
+    pub block_reward: u64,
+    pub next_halving_height: Option<u64>,
+    pub blocks_until_halving: Option<u64>,
+    pub next_halving_eta: Option<u64>,
+}
+
+/// The node's `/v1/pulses`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodePulses {
+    #[serde(flatten)]
+    pub head: PulseHead,
+    pub pulses: Vec<Pulse>,
+}
+
+/// What one page did to the local chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// Blocks were appended; there may be more.
+    Advanced(usize),
+    /// The local tip was not on the node's chain and was dropped.
+    RolledBack,
+    /// Nothing new.
+    CaughtUp,
+}
+
+/// Apply one `/v1/blocks` page fetched from the local next height. Node
+/// blocks do not carry their difficulty, so new blocks are recorded with
+/// `difficulty`, the node's current one. Each stored block is pushed to `live`.
+pub fn ingest(store: &Store, page: BlocksPage, difficulty: u64, live: &Live) -> rusqlite::Result<Progress> {
+    let next = store.next_height()?;
+    // The node's chain got shorter than ours: drop what it no longer has.
+    if page.tip.map_or(0, |tip| tip + 1) < next {
+        store.rollback_to(page.tip.map_or(0, |tip| tip + 1))?;
+        return Ok(Progress::RolledBack);
+    }
+    let Some(first) = page.blocks.first() else {
+        return Ok(Progress::CaughtUp);
+    };
+    let mut tip = store.tip_hash()?;
+    if tip.as_ref().is_some_and(|tip| *tip != first.parent) {
+        store.rollback_to(next - 1)?;
+        return Ok(Progress::RolledBack);
+    }
+    let mut applied = 0;
+    for block in page.blocks {
+        if block.height != next + applied as u64 || tip.as_ref().is_some_and(|tip| *tip != block.parent) {
+            break;
+        }
+        tip = Some(block.hash.clone());
+        let block = block.into_block(difficulty);
+        store.apply_block(&block)?;
+        live.block_applied(&block);
+        applied += 1;
+    }
+    Ok(Progress::Advanced(applied))
+}
+
+pub struct NodeClient {
+    url: String,
+    http: reqwest::Client,
+}
+
+impl NodeClient {
+    pub fn new(url: &str) -> reqwest::Result<Self> {
+        let http = reqwest::Client::builder().timeout(Duration::from_secs(30)).build()?;
+        Ok(Self { url: url.trim_end_matches('/').to_string(), http })
+    }
+
+    pub async fn blocks(&self, from: u64, limit: u64) -> reqwest::Result<BlocksPage> {
+        self.http
+            .get(format!("{}/v1/blocks", self.url))
+            .query(&[("from", from), ("limit", limit)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    pub async fn mempool(&self) -> reqwest::Result<MempoolSummary> {
+        self.http
+            .get(format!("{}/v1/mempool", self.url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    pub async fn supply(&self) -> reqwest::Result<SupplySnapshot> {
+        self.http
+            .get(format!("{}/v1/supply", self.url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    /// Block pulses from height `from` on; `None` from a node that predates
+    /// `/v1/pulses`.
+    pub async fn pulses(&self, from: u64) -> reqwest::Result<Option<NodePulses>> {
+        let response = self.http.get(format!("{}/v1/pulses", self.url)).query(&[("from", from)]).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        response.error_for_status()?.json().await.map(Some)
+    }
+
+    /// `hash` if it is waiting in the node's mempool.
+    pub async fn pending(&self, hash: &str) -> reqwest::Result<Option<PendingTransaction>> {
+        let response = self.http.get(format!("{}/v1/mempool/{}", self.url, hash)).send().await?;
+        // The node answers 404 for unknown and 400 for malformed hashes.
+        if response.status().is_client_error() {
+            return Ok(None);
+        }
+        response.error_for_status()?.json().await.map(Some)
+    }
+
+    pub async fn status(&self) -> reqwest::Result<NodeStatus> {
+        self.http
+            .get(format!("{}/v1/status", self.url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+/// Fetch and apply pages until caught up with the node.
+async fn sync_to_tip(state: &AppState) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let node = &state.client;
+    let status = node.status().await?;
+    let difficulty = status.difficulty;
+    *state.node.lock().unwrap() = status;
+    *state.mempool.lock().unwrap() = node.mempool().await?;
+    *state.supply.lock().unwrap() = Some(node.supply().await?);
+    if let Some(pulses) = node.pulses(state.store.pulse_tip()?.map_or(0, |h| h + 1)).await? {
+        state.store.record_pulses(&pulses.pulses)?;
+        *state.pulse_head.lock().unwrap() = Some(pulses.head);
+    }
+    loop {
+        let page = node.blocks(state.store.next_height()?, PAGE_SIZE).await?;
+        match ingest(&state.store, page, difficulty, &state.live)? {
+            Progress::Advanced(applied) => log::debug!("Ingested {} blocks, next height {}", applied, state.store.next_height()?),
+            Progress::RolledBack => log::info!("Node switched branches; rolled back to height {}", state.store.next_height()?),
+            Progress::CaughtUp => break,
+        }
+    }
+    let peers = state.node.lock().unwrap().connected_peers;
+    state.live.stats(Stats {
+        height: state.store.next_height()?.saturating_sub(1),
+        total_transactions: state.store.transaction_count()?,
+        mempool_size: state.mempool.lock().unwrap().size as u64,
+        difficulty,
+        peers,
+    });
+    Ok(())
+}
+
+/// Run forever, following the node behind `state.client`.
+pub async fn run(state: actix_web::web::Data<AppState>) {
+    log::info!("Syncing from node at {}", state.client.url);
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        match sync_to_tip(&state).await {
+            Ok(()) => {
+                backoff = Duration::from_secs(1);
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+            Err(e) => {
+                log::warn!("Sync from {} failed ({}); retrying in {:?}", state.client.url, e, backoff);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_block(height: u64, hash: &str, parent: &str) -> NodeBlock {
+        NodeBlock {
+            height,
+            hash: hash.to_string(),
+            parent: parent.to_string(),
+            timestamp: 1_000 + height,
+            miner: "aa".repeat(32),
+            nonce: 0,
+            reward: 5_000_000_000,
+            fees_burned: 0,
+            miner_fees: 1,
+            tx_root: "00".repeat(32),
+            vdf_proof: "00".repeat(32),
+            size: 300,
+            transactions: vec![NodeTransaction {
+                hash: format!("tx{}", hash),
+                from: "bb".repeat(32),
+                to: "cc".repeat(32),
+                amount: 10,
+                fee: 1,
+                signature: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_catch_up_and_reorg() {
+        let store = Store::in_memory().unwrap();
+        let live = Live::new();
+        let mut pushed = live.subscribe();
+        let page = BlocksPage { tip: Some(2), blocks: vec![node_block(0, "a", "0"), node_block(1, "b", "a"), node_block(2, "c", "b")] };
+        assert_eq!(ingest(&store, page, 1_000, &live).unwrap(), Progress::Advanced(3));
+        assert_eq!(ingest(&store, BlocksPage { tip: Some(2), blocks: vec![] }, 1_000, &live).unwrap(), Progress::CaughtUp);
+        assert_eq!(store.transaction_count().unwrap(), 3);
+        // Each block is pushed before its transaction.
+        assert!(pushed.try_recv().unwrap().starts_with(r#"{"type":"block""#));
+        assert!(pushed.try_recv().unwrap().starts_with(r#"{"type":"transaction""#));
+
+        // The node replaced blocks 2.. with a fork off block 1.
+        let fork = BlocksPage { tip: Some(3), blocks: vec![node_block(3, "e", "d")] };
+        assert_eq!(ingest(&store, fork, 1_000, &live).unwrap(), Progress::RolledBack);
+        assert_eq!(store.next_height().unwrap(), 2);
+        let fork = BlocksPage { tip: Some(3), blocks: vec![node_block(2, "d", "b"), node_block(3, "e", "d")] };
+        assert_eq!(ingest(&store, fork, 1_000, &live).unwrap(), Progress::Advanced(2));
+        assert_eq!(store.tip_hash().unwrap().as_deref(), Some("e"));
+        assert!(store.transaction("txc").unwrap().is_none());
+
+        // And then fell back below our tip.
+        assert_eq!(ingest(&store, BlocksPage { tip: Some(1), blocks: vec![] }, 1_000, &live).unwrap(), Progress::RolledBack);
+        assert_eq!(store.next_height().unwrap(), 2);
+    }
+}
@@ -4,6 +4,7 @@ import Dashboard from './pages/Dashboard';
 import BlockDetail from './pages/BlockDetail';
 import TransactionDetail from './pages/TransactionDetail';
 import AddressDetail from './pages/AddressDetail';
+import ContractDetail from './pages/ContractDetail';
 import BlocksList from './pages/BlocksList';
 import Search from './components/Search';
 
@@ -35,6 +36,7 @@ function App() {
             <Route path="/block/:id" element={<BlockDetail />} />
             <Route path="/transaction/:hash" element={<TransactionDetail />} />
             <Route path="/address/:address" element={<AddressDetail />} />
+            <Route path="/contract/:imageId" element={<ContractDetail />} />
           </Routes>
         </div>
       </div>
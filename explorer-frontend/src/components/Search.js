@@ -13,19 +13,24 @@ function Search() {
 
     setSearching(true);
     try {
-      const response = await axios.get(`/api/search/${query.trim()}`);
-      const result = response.data;
+      const response = await axios.get(`/api/search/${encodeURIComponent(query.trim())}`);
+      // Results come best first; open the top one.
+      const [result] = response.data.results;
 
-      if (result.type === 'Block') {
+      if (!result) {
+        alert('Not found');
+      } else if (result.type === 'block') {
         navigate(`/block/${result.data.index}`);
-      } else if (result.type === 'Transaction') {
+      } else if (result.type === 'transaction') {
         navigate(`/transaction/${result.data.hash}`);
-      } else if (result.type === 'Address') {
-        navigate(`/address/${result.data.address}`);
-      } else {
-        alert('Not found');
+      } else if (result.type === 'address') {
+        navigate(`/address/${result.address}`);
       }
     } catch (error) {
+      if (error.response && error.response.status === 400) {
+        alert(error.response.data.error);
+        return;
+      }
       console.error('Search error:', error);
       alert('Search failed. Please try again.');
     } finally {
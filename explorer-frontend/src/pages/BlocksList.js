@@ -14,7 +14,7 @@ function BlocksList() {
   const fetchBlocks = async () => {
     try {
       const response = await axios.get('/api/blocks?limit=50');
-      setBlocks(response.data);
+      setBlocks(response.data.items);
       setLoading(false);
     } catch (error) {
       console.error('Error fetching blocks:', error);
@@ -0,0 +1,110 @@
+import React, { useState, useEffect } from 'react';
+import { useParams, Link } from 'react-router-dom';
+import axios from 'axios';
+import { format } from 'date-fns';
+
+function ContractDetail() {
+  const { imageId } = useParams();
+  const [contract, setContract] = useState(null);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState(null);
+
+  useEffect(() => {
+    fetchContract();
+  }, [imageId]);
+
+  const fetchContract = async () => {
+    try {
+      const response = await axios.get(`/v1/contracts/${imageId}`);
+      setContract(response.data);
+      setLoading(false);
+    } catch (err) {
+      if (err.response && err.response.status === 404) {
+        setContract(null);
+      } else {
+        setError(err.message);
+      }
+      setLoading(false);
+    }
+  };
+
+  const auditBadge = (score) => {
+    if (score >= 80) return 'badge-success';
+    if (score >= 50) return 'badge-warning';
+    return 'badge-danger';
+  };
+
+  if (loading) return <div className="loading">Loading contract...</div>;
+  if (error) return <div className="error">Error: {error}</div>;
+  if (!contract) {
+    return (
+      <div className="card">
+        <h2 style={{ marginBottom: '20px' }}>Unregistered Contract</h2>
+        <div className="detail-value hash">{imageId}</div>
+        <p style={{ marginTop: '10px', color: '#666' }}>
+          No publisher has registered a name for this image ID. Check the source before interacting with it.
+        </p>
+      </div>
+    );
+  }
+
+  return (
+    <div className="card">
+      <h2 style={{ marginBottom: '30px' }}>{contract.name} <small>v{contract.version}</small></h2>
+
+      <div className="detail-row">
+        <div className="detail-label">Image ID:</div>
+        <div className="detail-value hash">{contract.image_id}</div>
+      </div>
+
+      <div className="detail-row">
+        <div className="detail-label">Publisher:</div>
+        <div className="detail-value">
+          <Link to={`/address/${contract.publisher}`} className="hash">
+            {contract.publisher}
+          </Link>
+        </div>
+      </div>
+
+      <div className="detail-row">
+        <div className="detail-label">Source:</div>
+        <div className="detail-value">
+          {contract.source_url ? (
+            <a href={contract.source_url} target="_blank" rel="noopener noreferrer">
+              {contract.source_url}
+            </a>
+          ) : (
+            <span className="badge badge-warning">Not published</span>
+          )}
+        </div>
+      </div>
+
+      <div className="detail-row">
+        <div className="detail-label">Audit:</div>
+        <div className="detail-value">
+          {contract.audit ? (
+            <>
+              <span className={`badge ${auditBadge(contract.audit.score)}`}>
+                Score {contract.audit.score}/100
+              </span>
+              <p style={{ marginTop: '10px', fontSize: '14px', color: '#666' }}>
+                {contract.audit.findings} finding(s), audited {format(new Date(contract.audit.audited_at * 1000), 'PPpp')}
+              </p>
+            </>
+          ) : (
+            <span className="badge badge-warning">Not audited</span>
+          )}
+        </div>
+      </div>
+
+      <div className="detail-row">
+        <div className="detail-label">Registered:</div>
+        <div className="detail-value">
+          {format(new Date(contract.registered_at * 1000), 'PPpp')}
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default ContractDetail;
@@ -10,8 +10,42 @@ function Dashboard() {
 
   useEffect(() => {
     fetchStats();
-    const interval = setInterval(fetchStats, 10000); // Refresh every 10 seconds
-    return () => clearInterval(interval);
+
+    // Live updates pushed by the backend on /ws: stat deltas and new blocks.
+    // Reconnects after a drop and refetches in case anything was missed.
+    let socket;
+    let retry;
+    let closed = false;
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss' : 'ws';
+      socket = new WebSocket(`${protocol}://${window.location.host}/ws`);
+      socket.onmessage = (event) => {
+        const message = JSON.parse(event.data);
+        if (message.type === 'stats') {
+          setStats((current) => current && { ...current, ...message.data });
+        } else if (message.type === 'block') {
+          setStats((current) => current && {
+            ...current,
+            latest_blocks: [message.data, ...current.latest_blocks.filter((b) => b.index < message.data.index)].slice(0, 10),
+          });
+        }
+      };
+      socket.onclose = () => {
+        if (!closed) {
+          retry = setTimeout(() => {
+            fetchStats();
+            connect();
+          }, 5000);
+        }
+      };
+    };
+    connect();
+
+    return () => {
+      closed = true;
+      clearTimeout(retry);
+      socket.close();
+    };
   }, []);
 
   const fetchStats = async () => {
@@ -86,7 +86,7 @@ fn main() {
     // 2. Subscribe to "axiom/realtime/pulse/v1" topic
     // 3. Dial bootstrap nodes
     // 4. On each received Gossipsub message:
-    //    a. Deserialize as AxiomPulse
+    //    a. Decode as AxiomPulse (network::wire envelope)
     //    b. Format as JSON with UTC timestamp
     //    c. Append to pulse_history.jsonl
     //
@@ -104,7 +104,7 @@ fn main() {
     //
     //     loop {
     //         if let SwarmEvent::Behaviour(event) = swarm.select_next_some().await {
-    //             if let Ok(pulse) = bincode::deserialize::<AxiomPulse>(&message.data) {
+    //             if let Ok(pulse) = axiom_core::network::wire::decode::<AxiomPulse>(&message.data) {
     //                 let entry = serde_json::json!({
     //                     "received_utc": chrono::Utc::now().to_rfc3339(),
     //                     "height": pulse.height,
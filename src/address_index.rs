@@ -0,0 +1,346 @@
+// src/address_index.rs - Per-address activity index
+//
+// Maps every address to the chain events that touched it: mining rewards,
+// incoming and outgoing transfers. Built once from the stored chain on
+// startup, then kept current as blocks are connected; on a reorg the index
+// is truncated at the fork point and the new branch is indexed.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::block::Block;
+use crate::economics;
+use crate::transaction::Address;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    /// Block reward credited to the miner.
+    MiningReward,
+    /// Transfer into the address.
+    Received,
+    /// Transfer out of the address (`fee` is paid on top of `amount`).
+    Sent,
+}
+
+/// One event affecting an address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AddressActivity {
+    pub height: u64,
+    pub timestamp: u64,
+    pub kind: ActivityKind,
+    pub amount: u64,
+    pub fee: u64,
+    /// Other side of a transfer; `None` for mining rewards.
+    #[serde(with = "hex_opt")]
+    pub counterparty: Option<Address>,
+    #[serde(with = "hex_opt")]
+    pub tx_hash: Option<[u8; 32]>,
+}
+
+/// Entries per history page, at most.
+pub const MAX_HISTORY_PAGE: usize = 100;
+
+/// Which activity a history page lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryFilter {
+    #[default]
+    All,
+    Sent,
+    /// Incoming transfers and mining rewards.
+    Received,
+}
+
+impl HistoryFilter {
+    fn includes(self, kind: ActivityKind) -> bool {
+        match self {
+            HistoryFilter::All => true,
+            HistoryFilter::Sent => kind == ActivityKind::Sent,
+            HistoryFilter::Received => kind != ActivityKind::Sent,
+        }
+    }
+}
+
+/// Where a history page continues: entries older than the `ordinal`-th
+/// one of the address at `height`. Written `<height>.<ordinal>`. A reorg
+/// only disturbs cursors at or above its fork point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryCursor {
+    pub height: u64,
+    pub ordinal: u32,
+}
+
+impl fmt::Display for HistoryCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.height, self.ordinal)
+    }
+}
+
+impl FromStr for HistoryCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (height, ordinal) = s.split_once('.').ok_or_else(|| format!("bad cursor '{}'", s))?;
+        Ok(Self {
+            height: height.parse().map_err(|_| format!("bad cursor height '{}'", height))?,
+            ordinal: ordinal.parse().map_err(|_| format!("bad cursor ordinal '{}'", ordinal))?,
+        })
+    }
+}
+
+impl Serialize for HistoryCursor {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+/// An activity entry with its depth below the indexed tip.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HistoryEntry {
+    #[serde(flatten)]
+    pub activity: AddressActivity,
+    pub confirmations: u64,
+}
+
+/// One page of an address's history, newest first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HistoryPage {
+    pub entries: Vec<HistoryEntry>,
+    /// Pass back to fetch the next page; `None` on the last one.
+    pub next_cursor: Option<HistoryCursor>,
+    pub tip_height: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct AddressIndex {
+    activity: HashMap<Address, Vec<AddressActivity>>,
+    /// Number of blocks indexed (the next expected height).
+    next_height: u64,
+}
+
+impl AddressIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index a whole chain, genesis first.
+    pub fn from_blocks(blocks: &[Block]) -> Self {
+        let mut index = Self::new();
+        for (height, block) in blocks.iter().enumerate() {
+            index.index_block(height as u64, block);
+        }
+        index
+    }
+
+    /// Number of blocks indexed so far.
+    pub fn indexed_blocks(&self) -> u64 {
+        self.next_height
+    }
+
+    pub fn address_count(&self) -> usize {
+        self.activity.len()
+    }
+
+    /// Record the effects of the block at `height`. Mirrors the reward
+    /// rule in `Timechain::add_block`.
+    pub fn index_block(&mut self, height: u64, block: &Block) {
+        let reward = economics::block_reward(block.slot, 0);
+        if reward > 0 && block.miner != [0u8; 32] {
+            self.push(block.miner, AddressActivity {
+                height,
+                timestamp: block.timestamp,
+                kind: ActivityKind::MiningReward,
+                amount: reward,
+                fee: 0,
+                counterparty: None,
+                tx_hash: None,
+            });
+        }
+
+        for tx in &block.transactions {
+            let tx_hash = Some(tx.legacy_hash());
+            self.push(tx.from, AddressActivity {
+                height,
+                timestamp: block.timestamp,
+                kind: ActivityKind::Sent,
+                amount: tx.amount,
+                fee: tx.fee,
+                counterparty: Some(tx.to),
+                tx_hash,
+            });
+            self.push(tx.to, AddressActivity {
+                height,
+                timestamp: block.timestamp,
+                kind: ActivityKind::Received,
+                amount: tx.amount,
+                fee: 0,
+                counterparty: Some(tx.from),
+                tx_hash,
+            });
+        }
+
+        self.next_height = self.next_height.max(height + 1);
+    }
+
+    /// Forget everything at or above `height` (reorg rollback).
+    pub fn truncate(&mut self, height: u64) {
+        self.activity.retain(|_, events| {
+            events.retain(|e| e.height < height);
+            !events.is_empty()
+        });
+        self.next_height = self.next_height.min(height);
+    }
+
+    /// Re-index after switching from `old` to `new`, touching only the
+    /// blocks above their common prefix.
+    pub fn chain_replaced(&mut self, old: &[Block], new: &[Block]) {
+        let common = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        self.truncate(common as u64);
+        for (height, block) in new.iter().enumerate().skip(common) {
+            self.index_block(height as u64, block);
+        }
+    }
+
+    /// All activity for `address`, oldest first.
+    pub fn history(&self, address: &Address) -> &[AddressActivity] {
+        self.activity.get(address).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Up to `limit` entries of `address` matching `filter`, newest first,
+    /// starting after `cursor` (or at the newest).
+    pub fn page(&self, address: &Address, cursor: Option<HistoryCursor>, filter: HistoryFilter, limit: usize) -> HistoryPage {
+        let history = self.history(address);
+        let first_at = |height: u64| history.partition_point(|e| e.height < height);
+        let end = match cursor {
+            None => history.len(),
+            Some(c) => (first_at(c.height) + c.ordinal as usize).min(history.partition_point(|e| e.height <= c.height)),
+        };
+        let limit = limit.clamp(1, MAX_HISTORY_PAGE);
+
+        let mut entries = Vec::new();
+        let mut next_cursor = None;
+        let mut last = None;
+        for (i, activity) in history[..end].iter().enumerate().rev().filter(|(_, a)| filter.includes(a.kind)) {
+            if entries.len() == limit {
+                next_cursor = last.map(|j: usize| HistoryCursor {
+                    height: history[j].height,
+                    ordinal: (j - first_at(history[j].height)) as u32,
+                });
+                break;
+            }
+            entries.push(HistoryEntry {
+                activity: activity.clone(),
+                confirmations: self.next_height.saturating_sub(activity.height),
+            });
+            last = Some(i);
+        }
+        HistoryPage { entries, next_cursor, tip_height: self.next_height }
+    }
+
+    fn push(&mut self, address: Address, activity: AddressActivity) {
+        self.activity.entry(address).or_default().push(activity);
+    }
+}
+
+mod hex_opt {
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer, const N: usize>(value: &Option<[u8; N]>, s: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(bytes) => s.serialize_some(&hex::encode(bytes)),
+            None => s.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::Transaction;
+
+    fn block(slot: u64, miner: Address, transactions: Vec<Transaction>) -> Block {
+        Block {
+            timestamp: 1_000 + slot * 1800,
+            miner,
+            transactions,
+            nonce: slot,
+            ..Block::test([0u8; 32], slot)
+        }
+    }
+
+    fn transfer(from: Address, to: Address, amount: u64) -> Transaction {
+        Transaction { from, to, amount, fee: 10, nonce: 0, expiry_height: 0, zk_proof: vec![], signature: vec![] }
+    }
+
+    #[test]
+    fn test_index_rewards_and_transfers() {
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        let index = AddressIndex::from_blocks(&[
+            block(0, [0u8; 32], vec![]),
+            block(1, alice, vec![]),
+            block(2, bob, vec![transfer(alice, bob, 500)]),
+        ]);
+
+        let kinds: Vec<ActivityKind> = index.history(&alice).iter().map(|a| a.kind).collect();
+        assert_eq!(kinds, vec![ActivityKind::MiningReward, ActivityKind::Sent]);
+        let bob_history = index.history(&bob);
+        assert_eq!(bob_history.len(), 2);
+        assert_eq!(bob_history[1].kind, ActivityKind::Received);
+        assert_eq!(bob_history[1].counterparty, Some(alice));
+        assert_eq!(index.indexed_blocks(), 3);
+    }
+
+    #[test]
+    fn test_chain_replaced_drops_orphaned_activity() {
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        let genesis = block(0, [0u8; 32], vec![]);
+        let old = vec![genesis.clone(), block(1, alice, vec![])];
+        let new = vec![genesis, block(1, bob, vec![]), block(2, bob, vec![])];
+
+        let mut index = AddressIndex::from_blocks(&old);
+        index.chain_replaced(&old, &new);
+        assert!(index.history(&alice).is_empty());
+        assert_eq!(index.history(&bob).len(), 2);
+        assert_eq!(index.address_count(), 1);
+        assert_eq!(index.indexed_blocks(), 3);
+    }
+
+    #[test]
+    fn test_history_pages_follow_cursor_and_filter() {
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        let index = AddressIndex::from_blocks(&[
+            block(0, [0u8; 32], vec![]),
+            block(1, alice, vec![]),
+            block(2, bob, vec![transfer(alice, bob, 1), transfer(bob, alice, 2), transfer(alice, bob, 3)]),
+            block(3, bob, vec![transfer(alice, bob, 4)]),
+        ]);
+        // Alice: reward@1, sent 1, received 2, sent 3 (all @2), sent 4 @3.
+        let amounts = |page: &HistoryPage| page.entries.iter().map(|e| e.activity.amount).collect::<Vec<_>>();
+
+        let first = index.page(&alice, None, HistoryFilter::All, 2);
+        assert_eq!(amounts(&first), vec![4, 3]);
+        assert_eq!(first.entries[0].confirmations, 1);
+        let cursor = first.next_cursor.unwrap();
+        assert_eq!(cursor, HistoryCursor { height: 2, ordinal: 2 });
+        assert_eq!(cursor.to_string().parse::<HistoryCursor>(), Ok(cursor));
+
+        let second = index.page(&alice, Some(cursor), HistoryFilter::All, 2);
+        assert_eq!(amounts(&second), vec![2, 1]);
+        let third = index.page(&alice, second.next_cursor, HistoryFilter::All, 2);
+        assert_eq!(third.entries.len(), 1);
+        assert_eq!(third.entries[0].activity.kind, ActivityKind::MiningReward);
+        assert_eq!((third.next_cursor, third.tip_height), (None, 4));
+
+        let sent = index.page(&alice, None, HistoryFilter::Sent, 10);
+        assert_eq!(amounts(&sent), vec![4, 3, 1]);
+        let received = index.page(&alice, None, HistoryFilter::Received, 1);
+        assert_eq!(amounts(&received), vec![2]);
+        let rest = index.page(&alice, received.next_cursor, HistoryFilter::Received, 1);
+        assert_eq!(rest.entries[0].activity.kind, ActivityKind::MiningReward);
+        assert!(rest.next_cursor.is_none());
+    }
+}
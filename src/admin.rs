@@ -0,0 +1,336 @@
+// src/admin.rs - Authenticated operator RPC
+//
+// `POST /v1/admin/rpc` takes JSON-RPC 2.0 calls that act on the running
+// node: adding, dropping and banning peers, listing the mempool, and
+// pausing or resuming mining, and halting or resuming the chain. Calls are accepted from loopback only and
+// must carry `Authorization: Bearer <token>`, where the token is read from
+// `admin.token` in the node's working directory. The file is created with
+// owner-only permissions on first start, so only the operator account can
+// drive the node.
+//
+// This module parses and answers calls; the node's event loop executes
+// them, since it owns the swarm and the mempool.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ai_core::ThreatType;
+use crate::guardian::safe_mode::ResumeAuthorization;
+use crate::transaction::Transaction;
+
+/// Token file next to wallet.dat.
+pub const ADMIN_TOKEN_FILE: &str = "admin.token";
+
+/// JSON-RPC error codes used by the admin API.
+pub const PARSE_ERROR: i64 = -32700;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+/// Server-defined: the bearer token is missing or wrong.
+pub const UNAUTHORIZED: i64 = -32001;
+
+/// Shared secret for the admin RPC.
+#[derive(Clone)]
+pub struct AdminToken(String);
+
+impl std::fmt::Debug for AdminToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AdminToken(..)")
+    }
+}
+
+impl AdminToken {
+    /// Read the token at `path`, or generate one and write it there
+    /// readable by the owner only.
+    pub fn load_or_create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        match fs::read_to_string(path) {
+            Ok(token) if !token.trim().is_empty() => return Ok(Self(token.trim().to_string())),
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let mut secret = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut secret);
+        let token = hex::encode(secret);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(path)?.write_all(token.as_bytes())?;
+        Ok(Self(token))
+    }
+
+    /// Whether an `Authorization` header value presents this token. The
+    /// comparison takes the same time wherever the first mismatch is.
+    pub fn authorizes(&self, header: Option<&str>) -> bool {
+        let Some(presented) = header.and_then(|h| h.strip_prefix("Bearer ")) else {
+            return false;
+        };
+        let (expected, presented) = (self.0.as_bytes(), presented.trim().as_bytes());
+        expected.len() == presented.len()
+            && expected.iter().zip(presented).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+}
+
+/// A JSON-RPC call as posted.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A JSON-RPC reply: exactly one of `result` and `error` is set.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcResponse {
+    pub fn result(id: Value, result: Value) -> Self {
+        Self { jsonrpc: "2.0", id, result: Some(result), error: None }
+    }
+
+    pub fn error(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self { jsonrpc: "2.0", id, result: None, error: Some(RpcError { code, message: message.into() }) }
+    }
+}
+
+/// An admin call the node's event loop carries out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    /// Dial a multiaddr, e.g. `/ip4/203.0.113.5/tcp/6000/p2p/12D3...`.
+    AddPeer(String),
+    /// Disconnect a peer by ID. It may reconnect later.
+    RemovePeer(String),
+    /// Disconnect a peer and refuse it from now on, recorded with the
+    /// guardian as an operator ban (lifted by the guardian pardon route).
+    BanPeer(String),
+    MempoolContents,
+    SetMiningEnabled(bool),
+    /// List the consensus optimizer's proposals and their status.
+    ConsensusProposals,
+    /// Approve a consensus proposal; it activates at a later height,
+    /// announced to peers.
+    ApproveProposal(String),
+    RejectProposal { id: String, reason: String },
+    /// Add an address to the threat intelligence as a local discovery,
+    /// published in the node's threat feed with `evidence`.
+    FlagAddress { address: String, threat: ThreatType, evidence: String },
+    /// Whether the chain is halted, and the message a resume must sign.
+    SafeModeStatus,
+    /// Enter safe mode and broadcast a halt notice signed by the node.
+    Halt { reason: String },
+    /// Leave safe mode with operator or guardian manifest signatures.
+    Resume(ResumeAuthorization),
+}
+
+impl AdminCommand {
+    /// Parse a call. Parameters are positional (`["<peer>"]`) or named
+    /// (`{"peer": "<peer>"}`, `{"enabled": true}`). On failure returns
+    /// the JSON-RPC error code and message.
+    pub fn parse(method: &str, params: &Value) -> Result<Self, (i64, String)> {
+        match method {
+            "admin_addPeer" => string_param(params, "addr").map(AdminCommand::AddPeer),
+            "admin_removePeer" => string_param(params, "peer").map(AdminCommand::RemovePeer),
+            "admin_banPeer" => string_param(params, "peer").map(AdminCommand::BanPeer),
+            "admin_mempoolContents" => Ok(AdminCommand::MempoolContents),
+            "admin_setMiningEnabled" => param(params, "enabled")
+                .and_then(Value::as_bool)
+                .map(AdminCommand::SetMiningEnabled)
+                .ok_or((INVALID_PARAMS, "expected a boolean `enabled`".to_string())),
+            "admin_consensusProposals" => Ok(AdminCommand::ConsensusProposals),
+            "admin_approveProposal" => string_param(params, "id").map(AdminCommand::ApproveProposal),
+            "admin_rejectProposal" => {
+                let id = string_param(params, "id")?;
+                let reason = match params {
+                    Value::Array(values) => values.get(1),
+                    Value::Object(fields) => fields.get("reason"),
+                    _ => None,
+                }
+                .and_then(Value::as_str)
+                .unwrap_or("rejected by operator")
+                .to_string();
+                Ok(AdminCommand::RejectProposal { id, reason })
+            }
+            "admin_flagAddress" => {
+                let address = string_param(params, "address")?;
+                let address = address.strip_prefix("axm1").unwrap_or(&address);
+                let address = hex::decode(address)
+                    .ok()
+                    .filter(|b| b.len() == 32)
+                    .map(hex::encode)
+                    .ok_or((INVALID_PARAMS, "expected a 32-byte hex `address`".to_string()))?;
+                let field = |index: usize, name: &str| match params {
+                    Value::Array(values) => values.get(index).cloned(),
+                    Value::Object(fields) => fields.get(name).cloned(),
+                    _ => None,
+                };
+                let threat = field(1, "threat")
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .ok_or((INVALID_PARAMS, "expected a threat type `threat`, e.g. \"MixerService\"".to_string()))?;
+                let evidence = field(2, "evidence")
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .filter(|e| !e.trim().is_empty())
+                    .ok_or((INVALID_PARAMS, "expected a string `evidence`".to_string()))?;
+                Ok(AdminCommand::FlagAddress { address, threat, evidence })
+            }
+            "admin_safeMode" => Ok(AdminCommand::SafeModeStatus),
+            "admin_halt" => string_param(params, "reason").map(|reason| AdminCommand::Halt { reason }),
+            "admin_resume" => {
+                let auth = match params {
+                    Value::Array(values) => serde_json::from_value(serde_json::json!({
+                        "halt_id": values.first(),
+                        "signatures": values.get(1),
+                    })),
+                    other => serde_json::from_value(other.clone()),
+                };
+                auth.map(AdminCommand::Resume).map_err(|_| {
+                    (INVALID_PARAMS, "expected `halt_id` and `signatures` [{signer, signature}]".to_string())
+                })
+            }
+            other => Err((METHOD_NOT_FOUND, format!("unknown method '{}'", other))),
+        }
+    }
+}
+
+fn param<'a>(params: &'a Value, name: &str) -> Option<&'a Value> {
+    match params {
+        Value::Array(values) => values.first(),
+        Value::Object(fields) => fields.get(name),
+        _ => None,
+    }
+}
+
+fn string_param(params: &Value, name: &str) -> Result<String, (i64, String)> {
+    param(params, name)
+        .and_then(Value::as_str)
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| s.trim().to_string())
+        .ok_or((INVALID_PARAMS, format!("expected a string `{}`", name)))
+}
+
+/// One transaction in an `admin_mempoolContents` reply.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MempoolEntry {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+    pub size: usize,
+}
+
+impl From<&Transaction> for MempoolEntry {
+    fn from(tx: &Transaction) -> Self {
+        Self {
+            hash: hex::encode(tx.legacy_hash()),
+            from: hex::encode(tx.from),
+            to: hex::encode(tx.to),
+            amount: tx.amount,
+            fee: tx.fee,
+            nonce: tx.nonce,
+            size: bincode::serialized_size(tx).unwrap_or(0) as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_admin_calls() {
+        assert_eq!(
+            AdminCommand::parse("admin_addPeer", &json!(["/ip4/127.0.0.1/tcp/6000"])),
+            Ok(AdminCommand::AddPeer("/ip4/127.0.0.1/tcp/6000".into()))
+        );
+        assert_eq!(
+            AdminCommand::parse("admin_banPeer", &json!({"peer": "12D3KooW"})),
+            Ok(AdminCommand::BanPeer("12D3KooW".into()))
+        );
+        assert_eq!(AdminCommand::parse("admin_mempoolContents", &Value::Null), Ok(AdminCommand::MempoolContents));
+        assert_eq!(
+            AdminCommand::parse("admin_setMiningEnabled", &json!([false])),
+            Ok(AdminCommand::SetMiningEnabled(false))
+        );
+
+        assert_eq!(
+            AdminCommand::parse("admin_rejectProposal", &json!(["ai_consensus_144", "too soon"])),
+            Ok(AdminCommand::RejectProposal { id: "ai_consensus_144".into(), reason: "too soon".into() })
+        );
+        assert_eq!(
+            AdminCommand::parse("admin_rejectProposal", &json!({"id": "ai_consensus_144"})),
+            Ok(AdminCommand::RejectProposal { id: "ai_consensus_144".into(), reason: "rejected by operator".into() })
+        );
+        let address = "ab".repeat(32);
+        assert_eq!(
+            AdminCommand::parse("admin_flagAddress", &json!([format!("axm1{}", address), "MixerService", "tumbles funds"])),
+            Ok(AdminCommand::FlagAddress { address, threat: ThreatType::MixerService, evidence: "tumbles funds".into() })
+        );
+        assert_eq!(
+            AdminCommand::parse("admin_flagAddress", &json!({"address": "ab", "threat": "MixerService", "evidence": "x"})).unwrap_err().0,
+            INVALID_PARAMS
+        );
+
+        assert_eq!(
+            AdminCommand::parse("admin_halt", &json!({"reason": "bad supply"})),
+            Ok(AdminCommand::Halt { reason: "bad supply".into() })
+        );
+        let signatures = json!([{"signer": "aa", "signature": "bb"}]);
+        let Ok(AdminCommand::Resume(auth)) = AdminCommand::parse("admin_resume", &json!(["h1", signatures])) else {
+            panic!("admin_resume not parsed");
+        };
+        assert_eq!((auth.halt_id.as_str(), auth.signatures.len()), ("h1", 1));
+        assert_eq!(AdminCommand::parse("admin_resume", &json!({"halt_id": "h1"})).unwrap_err().0, INVALID_PARAMS);
+
+        assert_eq!(AdminCommand::parse("admin_removePeer", &json!([])).unwrap_err().0, INVALID_PARAMS);
+        assert_eq!(AdminCommand::parse("admin_setMiningEnabled", &json!(["yes"])).unwrap_err().0, INVALID_PARAMS);
+        assert_eq!(AdminCommand::parse("admin_shutdown", &Value::Null).unwrap_err().0, METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_admin_token() {
+        let path = std::env::temp_dir().join(format!("axiom_admin_token_{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let token = AdminToken::load_or_create(&path).unwrap();
+        // The same token comes back on the next start.
+        let reloaded = AdminToken::load_or_create(&path).unwrap();
+        let header = format!("Bearer {}", fs::read_to_string(&path).unwrap());
+        assert!(token.authorizes(Some(&header)) && reloaded.authorizes(Some(&header)));
+
+        assert!(!token.authorizes(None));
+        assert!(!token.authorizes(Some(header.trim_start_matches("Bearer "))));
+        assert!(!token.authorizes(Some("Bearer 00")));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+}
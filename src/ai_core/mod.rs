@@ -3,6 +3,7 @@
 
 pub mod multi_layer_security;
 pub mod production_ml;
+pub mod threat_feed;
 
 pub use multi_layer_security::{
     MultiLayerSecurityEngine,
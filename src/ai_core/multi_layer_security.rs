@@ -8,11 +8,23 @@ use std::sync::Arc;
 use parking_lot::RwLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 use crate::error::AxiomError;
+use super::production_ml::ProductionMLStack;
+use super::threat_feed::{
+    FeedEntry, FeedImport, SignedThreatFeed, ThreatFeed, DEFAULT_FEED_TTL_SECS, MAX_EVIDENCE_PER_ENTRY, MAX_FEED_TTL_SECS,
+};
+use crate::transaction::Address;
 
 const ANOMALY_MEMORY_SIZE: usize = 10000;
 const BEHAVIORAL_ANALYSIS_WINDOW: usize = 1000;
 const THREAT_INTELLIGENCE_CACHE: usize = 5000;
 
+/// Recent transaction feature vectors the ML layer is fitted on.
+const ML_WINDOW_SIZE: usize = 2048;
+/// Feature vectors needed before the ML layer is first fitted.
+const ML_MIN_FIT_SAMPLES: usize = 64;
+/// Assessments between refits of the ML layer.
+const ML_REFIT_INTERVAL: usize = 256;
+
 // ==================== THREAT CLASSIFICATION ====================
 
 /// Comprehensive transaction risk profile
@@ -259,6 +271,8 @@ struct ThreatIntelligenceSystem {
     known_malicious_addresses: HashMap<String, MaliciousEntity>,
     attack_patterns: Vec<AttackPattern>,
     real_time_alerts: VecDeque<ThreatAlert>,
+    /// `issued_at` of the last feed imported from each publisher.
+    feeds_imported: HashMap<String, u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -269,6 +283,44 @@ struct MaliciousEntity {
     first_identified: u64,
     evidence: Vec<String>,
     blockchain_wide_ban: bool,
+    /// Flagged on this node; never expires and is exported.
+    local: bool,
+    /// Expiry of each feed publisher's entry for the address.
+    feed_expiry: HashMap<String, u64>,
+}
+
+impl MaliciousEntity {
+    fn new(address: String, first_identified: u64) -> Self {
+        Self {
+            address,
+            threat_types: Vec::new(),
+            severity: 0,
+            first_identified,
+            evidence: Vec::new(),
+            blockchain_wide_ban: false,
+            local: false,
+            feed_expiry: HashMap::new(),
+        }
+    }
+
+    fn merge(&mut self, threat_types: &[ThreatType], severity: u8, first_identified: u64, evidence: &[String]) {
+        for threat in threat_types {
+            if !self.threat_types.contains(threat) {
+                self.threat_types.push(threat.clone());
+            }
+        }
+        for item in evidence {
+            if self.evidence.len() < MAX_EVIDENCE_PER_ENTRY && !self.evidence.contains(item) {
+                self.evidence.push(item.clone());
+            }
+        }
+        self.severity = self.severity.max(severity.min(10));
+        self.first_identified = self.first_identified.min(first_identified);
+    }
+
+    fn is_active(&self, now: u64) -> bool {
+        self.local || self.feed_expiry.values().any(|&expires_at| expires_at > now)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -289,44 +341,20 @@ struct ThreatAlert {
 
 // ==================== STATISTICAL MODELS ====================
 
+/// Layer 4: the `ProductionMLStack` ensemble (isolation forest, one-class
+/// SVM, LOF, DBSCAN), fitted online on a rolling window of the feature
+/// vectors of recently assessed transactions and refitted every
+/// `ML_REFIT_INTERVAL` assessments. Features are standardised with the
+/// window's mean and deviation at fitting time.
 struct StatisticalModels {
-    isolation_forest: IsolationForestModel,
-    local_outlier_factor: LOFModel,
-    one_class_svm: OneClassSVMModel,
-    dbscan_clusters: DBSCANModel,
-}
-
-struct IsolationForestModel {
-    trees: Vec<IsolationTree>,
-    n_trees: usize,
-    anomaly_threshold: f64,
-}
-
-struct IsolationTree {
-    max_depth: usize,
-}
-
-struct LOFModel {
-    k_neighbors: usize,
-    distance_cache: HashMap<String, Vec<(String, f64)>>,
-    lof_scores: HashMap<String, f64>,
-}
-
-struct OneClassSVMModel {
-    support_vectors: Vec<Vec<f64>>,
-    nu: f64,
-    gamma: f64,
-}
-
-struct DBSCANModel {
-    epsilon: f64,
-    min_points: usize,
-    clusters: Vec<Cluster>,
-}
-
-struct Cluster {
-    cluster_id: usize,
-    points: Vec<String>,
+    stack: ProductionMLStack,
+    window: VecDeque<Vec<f64>>,
+    mean: Vec<f64>,
+    std_dev: Vec<f64>,
+    /// Assessments since the stack was last fitted.
+    since_fit: usize,
+    /// A refit is running; others keep scoring with the current stack.
+    refitting: bool,
 }
 
 // ==================== IMPLEMENTATION ====================
@@ -435,11 +463,12 @@ impl MultiLayerSecurityEngine {
         // Check against known malicious addresses
         let intel = self.threat_intelligence.read();
 
-        if intel.known_malicious_addresses.contains_key(&profile.sender) {
-            return Ok(0.9);
+        match intel.known_malicious_addresses.get(&profile.sender) {
+            Some(entity) if entity.is_active(profile.timestamp) => {
+                Ok(if entity.blockchain_wide_ban { 0.9 } else { 0.9 * entity.severity as f64 / 10.0 })
+            }
+            _ => Ok(0.0),
         }
-
-        Ok(0.0)
     }
 
     fn run_statistical_models(&self, profile: &TransactionRiskProfile) -> Result<f64, AxiomError> {
@@ -447,11 +476,39 @@ impl MultiLayerSecurityEngine {
             return Ok(0.0);
         }
 
-        // Multi-factor threshold scoring across transaction features
-        let amount_score = if profile.amount > 10_000_00000000 { 0.3 } else { 0.0 };
-        let fee_score = if profile.gas_price < 1000 { 0.2 } else { 0.0 };
+        let features = StatisticalModels::features(profile);
+        let refit_due = {
+            let mut models = self.statistical_models.write();
+            models.observe(features.clone());
+            models.refit_due()
+        };
+        if refit_due {
+            self.refit_statistical_models();
+        }
 
-        Ok((amount_score + fee_score) / 2.0)
+        Ok(self.statistical_models.read().score(&features))
+    }
+
+    /// Fit a fresh ensemble on the current window and swap it in. The
+    /// window is copied out first, so assessments are not held up while
+    /// the models are fitted.
+    fn refit_statistical_models(&self) {
+        let window: Vec<Vec<f64>> = {
+            let mut models = self.statistical_models.write();
+            models.refitting = true;
+            models.window.iter().cloned().collect()
+        };
+        let (mean, std_dev) = StatisticalModels::moments(&window);
+        let standardised: Vec<Vec<f64>> = window.iter().map(|f| StatisticalModels::standardise(f, &mean, &std_dev)).collect();
+        let mut stack = ProductionMLStack::new();
+        stack.fit(&standardised);
+
+        let mut models = self.statistical_models.write();
+        models.stack = stack;
+        models.mean = mean;
+        models.std_dev = std_dev;
+        models.refitting = false;
+        log::debug!("ML layer refitted on {} transactions", window.len());
     }
 
     fn analyze_temporal_patterns(
@@ -612,24 +669,111 @@ impl MultiLayerSecurityEngine {
         evidence: String,
     ) -> Result<(), AxiomError> {
         let mut intel = self.threat_intelligence.write();
-
-        intel.known_malicious_addresses.insert(
-            address.clone(),
-            MaliciousEntity {
-                address,
-                threat_types: vec![threat_type],
-                severity: 8,
-                first_identified: SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs(),
-                evidence: vec![evidence],
-                blockchain_wide_ban: true,
-            },
-        );
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let entity = intel
+            .known_malicious_addresses
+            .entry(address.clone())
+            .or_insert_with(|| MaliciousEntity::new(address, now));
+        entity.merge(&[threat_type], 8, now, &[evidence]);
+        entity.blockchain_wide_ban = true;
+        entity.local = true;
 
         Ok(())
     }
+
+    /// Merge a feed signed by one of the `trusted` publishers into the
+    /// threat intelligence (see `threat_feed` for the rules).
+    pub fn import_threat_feed(
+        &self,
+        signed: &SignedThreatFeed,
+        trusted: &[Address],
+        now: u64,
+    ) -> Result<FeedImport, AxiomError> {
+        let feed = &signed.feed;
+        if !signed.verify() {
+            return Err(AxiomError::InvalidSignature(format!("threat feed from {} does not verify", feed.publisher)));
+        }
+        if !trusted.iter().any(|key| hex::encode(key) == feed.publisher) {
+            return Err(AxiomError::InvalidSignature(format!("threat feed publisher {} is not trusted", feed.publisher)));
+        }
+
+        let mut intel = self.threat_intelligence.write();
+        if intel.feeds_imported.get(&feed.publisher).is_some_and(|&issued| issued >= feed.issued_at) {
+            return Err(AxiomError::InvalidSignature(format!(
+                "threat feed from {} issued at {} is not newer than the last one imported",
+                feed.publisher, feed.issued_at
+            )));
+        }
+        intel.feeds_imported.insert(feed.publisher.clone(), feed.issued_at);
+
+        let latest_expiry = feed.issued_at.saturating_add(MAX_FEED_TTL_SECS);
+        let mut import = FeedImport::default();
+        for entry in &feed.entries {
+            let expires_at = entry.expires_at.min(latest_expiry);
+            if expires_at <= now {
+                import.expired += 1;
+                continue;
+            }
+            let entity = match intel.known_malicious_addresses.get_mut(&entry.address) {
+                Some(entity) => {
+                    import.merged += 1;
+                    entity
+                }
+                None => {
+                    import.added += 1;
+                    intel
+                        .known_malicious_addresses
+                        .entry(entry.address.clone())
+                        .or_insert_with(|| MaliciousEntity::new(entry.address.clone(), entry.first_identified))
+                }
+            };
+            entity.merge(&entry.threat_types, entry.severity, entry.first_identified, &entry.evidence);
+            entity.feed_expiry.insert(feed.publisher.clone(), expires_at);
+        }
+        Ok(import)
+    }
+
+    /// The addresses flagged on this node, with their evidence, as a feed
+    /// for `publisher` to sign. Imported entries are not re-published.
+    pub fn export_threat_feed(&self, publisher: &Address, now: u64) -> ThreatFeed {
+        let intel = self.threat_intelligence.read();
+        let mut entries: Vec<FeedEntry> = intel
+            .known_malicious_addresses
+            .values()
+            .filter(|entity| entity.local)
+            .map(|entity| FeedEntry {
+                address: entity.address.clone(),
+                threat_types: entity.threat_types.clone(),
+                severity: entity.severity,
+                first_identified: entity.first_identified,
+                evidence: entity.evidence.clone(),
+                expires_at: now + DEFAULT_FEED_TTL_SECS,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.address.cmp(&b.address));
+        ThreatFeed::new(publisher, now, entries)
+    }
+
+    /// Drop feed entries that expired by `now`, and the addresses no
+    /// source lists any more. Returns how many addresses were dropped.
+    pub fn expire_threat_intelligence(&self, now: u64) -> usize {
+        let mut intel = self.threat_intelligence.write();
+        let before = intel.known_malicious_addresses.len();
+        intel.known_malicious_addresses.retain(|_, entity| {
+            entity.feed_expiry.retain(|_, expires_at| *expires_at > now);
+            entity.is_active(now)
+        });
+        before - intel.known_malicious_addresses.len()
+    }
+
+    /// Addresses currently listed as malicious.
+    pub fn threat_intelligence_size(&self) -> usize {
+        self.threat_intelligence.read().known_malicious_addresses.len()
+    }
 }
 
 // ==================== CORE IMPLEMENTATIONS ====================
@@ -668,6 +812,7 @@ impl ThreatIntelligenceSystem {
             known_malicious_addresses: HashMap::new(),
             attack_patterns: Vec::new(),
             real_time_alerts: VecDeque::with_capacity(THREAT_INTELLIGENCE_CACHE),
+            feeds_imported: HashMap::new(),
         }
     }
 }
@@ -675,28 +820,77 @@ impl ThreatIntelligenceSystem {
 impl StatisticalModels {
     fn new() -> Self {
         Self {
-            isolation_forest: IsolationForestModel {
-                trees: Vec::new(),
-                n_trees: 100,
-                anomaly_threshold: 0.5,
-            },
-            local_outlier_factor: LOFModel {
-                k_neighbors: 20,
-                distance_cache: HashMap::new(),
-                lof_scores: HashMap::new(),
-            },
-            one_class_svm: OneClassSVMModel {
-                support_vectors: Vec::new(),
-                nu: 0.1,
-                gamma: 0.1,
-            },
-            dbscan_clusters: DBSCANModel {
-                epsilon: 0.5,
-                min_points: 5,
-                clusters: Vec::new(),
-            },
+            stack: ProductionMLStack::new(),
+            window: VecDeque::with_capacity(ML_WINDOW_SIZE),
+            mean: Vec::new(),
+            std_dev: Vec::new(),
+            since_fit: 0,
+            refitting: false,
         }
     }
+
+    /// The profile's features on log scales, so amounts and counts spanning
+    /// orders of magnitude stay comparable. Times are capped at a week.
+    fn features(profile: &TransactionRiskProfile) -> Vec<f64> {
+        const WEEK: u64 = 7 * 24 * 3600;
+        let log = |v: u64| (v as f64).ln_1p();
+        vec![
+            log(profile.amount),
+            log(profile.gas_price),
+            log(profile.zk_proof_size as u64),
+            log(profile.sender_history_count),
+            log(profile.recipient_history_count),
+            log(profile.time_since_last_sender_tx.min(WEEK)),
+            log(profile.time_since_last_recipient_tx.min(WEEK)),
+        ]
+    }
+
+    fn observe(&mut self, features: Vec<f64>) {
+        if self.window.len() >= ML_WINDOW_SIZE {
+            self.window.pop_front();
+        }
+        self.window.push_back(features);
+        self.since_fit += 1;
+    }
+
+    fn refit_due(&mut self) -> bool {
+        let due = !self.refitting
+            && self.window.len() >= ML_MIN_FIT_SAMPLES
+            && (!self.stack.is_trained() || self.since_fit >= ML_REFIT_INTERVAL);
+        if due {
+            self.since_fit = 0;
+        }
+        due
+    }
+
+    fn moments(window: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>) {
+        let dims = window.first().map_or(0, |f| f.len());
+        let n = window.len().max(1) as f64;
+        let mean: Vec<f64> = (0..dims).map(|d| window.iter().map(|f| f[d]).sum::<f64>() / n).collect();
+        let std_dev = (0..dims)
+            .map(|d| (window.iter().map(|f| (f[d] - mean[d]).powi(2)).sum::<f64>() / n).sqrt())
+            .collect();
+        (mean, std_dev)
+    }
+
+    fn standardise(features: &[f64], mean: &[f64], std_dev: &[f64]) -> Vec<f64> {
+        features
+            .iter()
+            .zip(mean.iter().zip(std_dev))
+            .map(|(f, (m, s))| if *s > 1e-9 { (f - m) / s } else { 0.0 })
+            .collect()
+    }
+
+    /// Anomaly score in [0, 1]; 0 until the ensemble is first fitted, as
+    /// there is nothing yet to compare against.
+    fn score(&self, features: &[f64]) -> f64 {
+        if !self.stack.is_trained() {
+            return 0.0;
+        }
+        self.stack
+            .detect_anomaly(&Self::standardise(features, &self.mean, &self.std_dev))
+            .clamp(0.0, 1.0)
+    }
 }
 
 // ==================== TESTS ====================
@@ -745,4 +939,38 @@ mod tests {
         let assessment = engine.assess_transaction_threat(&profile, 1000);
         assert!(assessment.is_ok());
     }
+
+    #[test]
+    fn test_ml_layer_fits_on_recent_transactions() {
+        let engine = MultiLayerSecurityEngine::new(SecurityConfig::default());
+        let profile = |i: u64, amount: u64| TransactionRiskProfile {
+            hash: format!("tx{}", i),
+            timestamp: i,
+            sender: "alice".to_string(),
+            recipient: "bob".to_string(),
+            amount,
+            gas_price: 1000 + i % 50,
+            zk_proof_size: 500,
+            sender_history_count: 20 + i % 10,
+            recipient_history_count: 30,
+            sender_reputation_score: 0.5,
+            time_since_last_sender_tx: 600 + i % 120,
+            time_since_last_recipient_tx: 600,
+            is_contract_deployment: false,
+            contract_bytecode_size: 0,
+            vdf_verification_time_ms: 0,
+        };
+        assert_eq!(engine.run_statistical_models(&profile(0, 100 * 100_000_000)).unwrap(), 0.0, "not fitted yet");
+        for i in 1..ML_MIN_FIT_SAMPLES as u64 * 2 {
+            engine.run_statistical_models(&profile(i, (100 + i % 20) * 100_000_000)).unwrap();
+        }
+        assert!(engine.statistical_models.read().stack.is_trained());
+
+        let usual = engine.run_statistical_models(&profile(500, 105 * 100_000_000)).unwrap();
+        let mut odd = profile(501, 9_000_000 * 100_000_000);
+        odd.sender_history_count = 0;
+        odd.time_since_last_sender_tx = 1;
+        let unusual = engine.run_statistical_models(&odd).unwrap();
+        assert!(unusual > usual, "unusual={} usual={}", unusual, usual);
+    }
 }
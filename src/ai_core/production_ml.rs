@@ -1,8 +1,9 @@
 // src/ai_core/production_ml.rs
 // Production-grade ML anomaly detection stack for Axiom Protocol
 
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use std::collections::BinaryHeap;
 use std::f64::consts::PI;
 
@@ -10,6 +11,25 @@ use std::f64::consts::PI;
 // Utility
 // ---------------------------------------------------------------------------
 
+/// Seed for the randomised models. Fitting is seeded rather than drawn from
+/// the thread RNG so that every node fitting the same data gets the same
+/// model, and so the same scores.
+pub type Seed = [u8; 32];
+
+/// The default seed, derived from the chain's genesis anchor.
+pub fn genesis_seed() -> Seed {
+    *blake3::hash(format!("axiom-ml:{}", crate::chain::GENESIS_ANCHOR).as_bytes()).as_bytes()
+}
+
+/// The seed of one `model` of a stack seeded with `seed`, so the models do
+/// not share a random stream.
+pub fn derive_seed(seed: &Seed, model: &str) -> Seed {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(seed);
+    hasher.update(model.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
 pub fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
     a.iter()
         .zip(b.iter())
@@ -358,6 +378,7 @@ pub struct IsolationForest {
     trees: Vec<IsolationTree>,
     subsample_size: usize,
     num_trees: usize,
+    seed: Seed,
     trained: bool,
 }
 
@@ -367,15 +388,26 @@ impl IsolationForest {
             trees: Vec::new(),
             subsample_size,
             num_trees,
+            seed: derive_seed(&genesis_seed(), "isolation_forest"),
             trained: false,
         }
     }
 
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.seed = seed;
+        self
+    }
+
     pub fn fit(&mut self, data: &[Vec<f64>]) {
+        let mut rng = StdRng::from_seed(self.seed);
+        self.fit_with_rng(data, &mut rng);
+    }
+
+    /// Fit drawing subsamples and splits from `rng` instead of the seed.
+    pub fn fit_with_rng<R: Rng>(&mut self, data: &[Vec<f64>], rng: &mut R) {
         if data.is_empty() {
             return;
         }
-        let mut rng = rand::thread_rng();
         let height_limit = (self.subsample_size as f64).log2().ceil() as usize;
         self.trees.clear();
 
@@ -384,13 +416,13 @@ impl IsolationForest {
                 data.to_vec()
             } else {
                 let mut indices: Vec<usize> = (0..data.len()).collect();
-                indices.shuffle(&mut rng);
+                indices.shuffle(rng);
                 indices[..self.subsample_size]
                     .iter()
                     .map(|&i| data[i].clone())
                     .collect()
             };
-            self.trees.push(IsolationTree::build(&sample, height_limit, &mut rng));
+            self.trees.push(IsolationTree::build(&sample, height_limit, rng));
         }
         self.trained = true;
     }
@@ -420,6 +452,7 @@ pub struct OneClassSVM {
     center: Vec<f64>,
     radius: f64,
     gamma: f64,
+    seed: Seed,
     trained: bool,
 }
 
@@ -432,20 +465,31 @@ impl OneClassSVM {
             center: Vec::new(),
             radius: 1.0,
             gamma,
+            seed: derive_seed(&genesis_seed(), "one_class_svm"),
             trained: false,
         }
     }
 
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.seed = seed;
+        self
+    }
+
     pub fn fit(&mut self, data: &[Vec<f64>]) {
+        let mut rng = StdRng::from_seed(self.seed);
+        self.fit_with_rng(data, &mut rng);
+    }
+
+    /// Fit drawing the random Fourier features from `rng` instead of the seed.
+    pub fn fit_with_rng<R: Rng>(&mut self, data: &[Vec<f64>], rng: &mut R) {
         if data.is_empty() {
             return;
         }
         let dim = data[0].len();
-        let mut rng = rand::thread_rng();
 
         // Generate random weights ~ N(0, 1) using Box-Muller
         self.random_weights = (0..self.rff_dimension)
-            .map(|_| (0..dim).map(|_| box_muller(&mut rng)).collect())
+            .map(|_| (0..dim).map(|_| box_muller(rng)).collect())
             .collect();
 
         // Generate random offsets ~ U(0, 2π)
@@ -759,10 +803,17 @@ pub struct ProductionMLStack {
 }
 
 impl ProductionMLStack {
+    /// A stack seeded from the genesis anchor, as every node's is.
     pub fn new() -> Self {
+        Self::with_seed(genesis_seed())
+    }
+
+    /// A stack whose randomised models are seeded from `seed`. LOF and
+    /// DBSCAN are deterministic already.
+    pub fn with_seed(seed: Seed) -> Self {
         ProductionMLStack {
-            isolation_forest: IsolationForest::new(100, 256),
-            one_class_svm: OneClassSVM::new(200, 0.1),
+            isolation_forest: IsolationForest::new(100, 256).with_seed(derive_seed(&seed, "isolation_forest")),
+            one_class_svm: OneClassSVM::new(200, 0.1).with_seed(derive_seed(&seed, "one_class_svm")),
             lof_detector: LOFDetector::new(20),
             dbscan: DBSCAN::new(0.5, 5),
             trained: false,
@@ -802,6 +853,36 @@ impl ProductionMLStack {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_seeded_stack_scores_identically() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let data: Vec<Vec<f64>> = (0..300)
+            .map(|_| vec![rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)])
+            .collect();
+        let probes = [[0.0, 0.0, 0.0], [0.9, -0.4, 0.2], [6.0, 6.0, -6.0]];
+        let scores = |stack: &mut ProductionMLStack| {
+            stack.fit(&data);
+            probes.iter().map(|p| stack.detect_anomaly(p)).collect::<Vec<f64>>()
+        };
+
+        let first = scores(&mut ProductionMLStack::new());
+        assert_eq!(first, scores(&mut ProductionMLStack::new()), "genesis-seeded stacks must agree");
+        // Refitting the same stack on the same data does not drift either.
+        let mut stack = ProductionMLStack::new();
+        scores(&mut stack);
+        assert_eq!(first, scores(&mut stack));
+
+        let other = scores(&mut ProductionMLStack::with_seed([1u8; 32]));
+        assert_eq!(other, scores(&mut ProductionMLStack::with_seed([1u8; 32])));
+        assert_ne!(first, other);
+
+        let mut a = IsolationForest::new(50, 128);
+        let mut b = IsolationForest::new(50, 128);
+        a.fit_with_rng(&data, &mut StdRng::seed_from_u64(3));
+        b.fit_with_rng(&data, &mut StdRng::seed_from_u64(3));
+        assert_eq!(a.score(&probes[2]), b.score(&probes[2]));
+    }
+
     fn make_cluster(center: &[f64], n: usize, spread: f64) -> Vec<Vec<f64>> {
         let mut rng = rand::thread_rng();
         (0..n)
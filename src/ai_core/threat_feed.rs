@@ -0,0 +1,194 @@
+// src/ai_core/threat_feed.rs - Signed, shareable malicious-address feeds
+//
+// A `ThreatFeed` is a list of malicious addresses with their threat types,
+// severity and evidence, published by one node and signed with its wallet
+// key. The node serves its own discoveries (addresses flagged locally, not
+// ones it imported) at `/v1/threat-intel/feed`, and fetches the feeds at
+// the URLs in `ai.threat_feeds` every `ai.threat_feed_interval` seconds,
+// accepting those signed by a key in `ai.threat_feed_publishers`.
+//
+// Imported entries merge into the engine's threat intelligence:
+//
+//   - a feed no newer than the last one imported from its publisher is
+//     ignored, so an old feed cannot be replayed over a newer one;
+//   - every entry expires: at its own `expires_at`, but never later than
+//     `MAX_FEED_TTL_SECS` after the feed was issued;
+//   - an address listed by several sources keeps the union of their threat
+//     types and evidence and the highest severity, and stays listed until
+//     every source's entry has expired;
+//   - locally flagged addresses never expire.
+
+use serde::{Deserialize, Serialize};
+
+use super::ThreatType;
+use crate::error::AxiomError;
+use crate::transaction::Address;
+use crate::wallet::Wallet;
+
+pub const FEED_VERSION: u32 = 1;
+
+/// How long exported entries stay valid.
+pub const DEFAULT_FEED_TTL_SECS: u64 = 30 * 86_400;
+
+/// Longest an imported entry is kept after its feed was issued.
+pub const MAX_FEED_TTL_SECS: u64 = 180 * 86_400;
+
+/// Largest feed body the node downloads.
+pub const MAX_FEED_BYTES: usize = 16 * 1024 * 1024;
+
+/// Evidence strings kept per address.
+pub const MAX_EVIDENCE_PER_ENTRY: usize = 16;
+
+const FEED_DOMAIN: &[u8] = b"axiom-threat-feed-v1";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedEntry {
+    pub address: String,
+    pub threat_types: Vec<ThreatType>,
+    /// 1 (low) to 10 (ban on sight).
+    pub severity: u8,
+    pub first_identified: u64,
+    pub evidence: Vec<String>,
+    /// Unix time after which the entry no longer applies.
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreatFeed {
+    pub version: u32,
+    /// Hex public key of the publishing node's wallet.
+    pub publisher: String,
+    pub issued_at: u64,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl ThreatFeed {
+    pub fn new(publisher: &Address, issued_at: u64, entries: Vec<FeedEntry>) -> Self {
+        Self { version: FEED_VERSION, publisher: hex::encode(publisher), issued_at, entries }
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = FEED_DOMAIN.to_vec();
+        bytes.extend(serde_json::to_vec(self).unwrap_or_default());
+        bytes
+    }
+
+    pub fn sign(self, signer: &Wallet) -> SignedThreatFeed {
+        let signature = hex::encode(signer.sign_message(&self.signing_bytes()));
+        SignedThreatFeed { feed: self, signature }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedThreatFeed {
+    #[serde(flatten)]
+    pub feed: ThreatFeed,
+    /// Hex ed25519 signature by `publisher` over the feed.
+    pub signature: String,
+}
+
+impl SignedThreatFeed {
+    /// Check the version and the signature against the feed's `publisher`.
+    pub fn verify(&self) -> bool {
+        let key: Option<Address> = hex::decode(&self.feed.publisher).ok().and_then(|b| b.try_into().ok());
+        let signature: Option<[u8; 64]> = hex::decode(&self.signature).ok().and_then(|b| b.try_into().ok());
+        let (Some(key), Some(signature)) = (key, signature) else {
+            return false;
+        };
+        self.feed.version == FEED_VERSION
+            && ed25519_dalek::VerifyingKey::from_bytes(&key)
+                .and_then(|key| key.verify_strict(&self.feed.signing_bytes(), &ed25519_dalek::Signature::from_bytes(&signature)))
+                .is_ok()
+    }
+}
+
+/// Outcome of importing a feed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FeedImport {
+    /// Addresses not listed before.
+    pub added: usize,
+    /// Addresses already listed, by this or another source.
+    pub merged: usize,
+    /// Entries already expired.
+    pub expired: usize,
+}
+
+/// Download the feed at `url`. It is not verified here.
+pub async fn fetch_feed(client: &reqwest::Client, url: &str) -> Result<SignedThreatFeed, AxiomError> {
+    let fail = |e: String| AxiomError::NetworkError(format!("threat feed {}: {}", url, e));
+    let response = client.get(url).send().await.map_err(|e| fail(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(fail(format!("HTTP {}", response.status())));
+    }
+    if response.content_length().is_some_and(|len| len > MAX_FEED_BYTES as u64) {
+        return Err(fail("feed too large".to_string()));
+    }
+    let body = response.bytes().await.map_err(|e| fail(e.to_string()))?;
+    if body.len() > MAX_FEED_BYTES {
+        return Err(fail("feed too large".to_string()));
+    }
+    serde_json::from_slice(&body).map_err(|e| AxiomError::SerializationError(format!("threat feed {}: {}", url, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_core::{MultiLayerSecurityEngine, SecurityConfig};
+
+    fn wallet(seed: u8) -> Wallet {
+        let secret_key = [seed; 32];
+        let address = ed25519_dalek::SigningKey::from_bytes(&secret_key).verifying_key().to_bytes();
+        Wallet { secret_key, address }
+    }
+
+    fn entry(address: &str, threat: ThreatType, severity: u8, expires_at: u64) -> FeedEntry {
+        FeedEntry {
+            address: address.into(),
+            threat_types: vec![threat],
+            severity,
+            first_identified: 100,
+            evidence: vec![format!("seen doing {:?}", address)],
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_feeds_verify_merge_and_expire() {
+        let publisher = wallet(1);
+        let mut feed = ThreatFeed::new(&publisher.address, 1_000, vec![
+            entry("mixer", ThreatType::MixerService, 6, 5_000),
+            entry("stale", ThreatType::SpamFlood, 4, 900),
+        ]);
+        let signed = feed.clone().sign(&publisher);
+        assert!(signed.verify());
+        let mut forged = signed.clone();
+        forged.feed.entries[0].severity = 10;
+        assert!(!forged.verify());
+
+        let engine = MultiLayerSecurityEngine::new(SecurityConfig::default());
+        engine.update_threat_intelligence("mixer".into(), ThreatType::MoneyLaundering, "local finding".into()).unwrap();
+        let trusted = [publisher.address];
+        assert!(engine.import_threat_feed(&forged, &trusted, 1_000).is_err());
+        assert!(engine.import_threat_feed(&signed, &[wallet(2).address], 1_000).is_err());
+
+        let import = engine.import_threat_feed(&signed, &trusted, 1_000).unwrap();
+        assert_eq!(import, FeedImport { added: 0, merged: 1, expired: 1 });
+        assert!(engine.import_threat_feed(&signed, &trusted, 1_000).is_err(), "replayed feed");
+
+        // Only the local discovery is exported, with both sources' evidence.
+        let export = engine.export_threat_feed(&publisher.address, 2_000);
+        assert_eq!(export.entries.len(), 1);
+        assert_eq!(export.entries[0].threat_types, vec![ThreatType::MoneyLaundering, ThreatType::MixerService]);
+        assert_eq!(export.entries[0].evidence.len(), 2);
+        assert_eq!(export.entries[0].expires_at, 2_000 + DEFAULT_FEED_TTL_SECS);
+
+        // A feed-only address lapses with its entry; a local one does not.
+        feed.issued_at = 2_000;
+        feed.entries = vec![entry("spammer", ThreatType::SpamFlood, 5, u64::MAX)];
+        let import = engine.import_threat_feed(&feed.sign(&publisher), &trusted, 2_000).unwrap();
+        assert_eq!(import.added, 1);
+        assert_eq!(engine.expire_threat_intelligence(2_000 + MAX_FEED_TTL_SECS - 1), 0);
+        assert_eq!(engine.expire_threat_intelligence(2_000 + MAX_FEED_TTL_SECS), 1);
+        assert_eq!(engine.threat_intelligence_size(), 1);
+    }
+}
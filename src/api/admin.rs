@@ -0,0 +1,169 @@
+// src/api/admin.rs - Operator routes under /v1/admin
+//
+// Reachable from loopback only, and only with the bearer token from
+// `admin.token`; without a token file every admin route refuses.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use warp::Filter;
+use axiom_core::error::{ErrorBody, ErrorCode};
+use axiom_core::logging::LogControl;
+use axiom_core::admin::{
+    AdminCommand, AdminToken, RpcRequest, RpcResponse, ADMIN_TOKEN_FILE, INTERNAL_ERROR, PARSE_ERROR, UNAUTHORIZED,
+};
+use tracing::info;
+use super::{AdminCall, AdminUnauthorized};
+
+/// Passes loopback callers only; everyone else gets a 404, so admin
+/// routes are invisible from outside the host.
+fn loopback_only() -> impl Filter<Extract = (), Error = warp::Rejection> + Copy {
+    warp::addr::remote()
+        .and_then(|addr: Option<SocketAddr>| async move {
+            match addr {
+                Some(addr) if addr.ip().is_loopback() => Ok(()),
+                _ => Err(warp::reject::not_found()),
+            }
+        })
+        .untuple_one()
+}
+
+/// Passes loopback callers presenting the admin bearer token
+/// (`Authorization: Bearer $(cat admin.token)`), the auth every
+/// `/v1/admin/*` route shares (`/v1/admin/rpc` checks the same token
+/// itself to answer in JSON-RPC). Without a token file nobody passes.
+/// Boxed to keep the route tree's type within the compiler's depth limit.
+pub(crate) fn admin_authorized(token: Option<AdminToken>) -> warp::filters::BoxedFilter<()> {
+    loopback_only()
+        .and(warp::header::optional::<String>("authorization"))
+        .and_then(move |authorization: Option<String>| {
+            let authorized = token.as_ref().is_some_and(|t| t.authorizes(authorization.as_deref()));
+            async move {
+                if authorized {
+                    Ok(())
+                } else {
+                    Err(warp::reject::custom(AdminUnauthorized))
+                }
+            }
+        })
+        .untuple_one()
+        .boxed()
+}
+
+/// `GET`/`POST /v1/admin/log-level`: read or replace the log filter, e.g.
+/// `{"filter": "info,axiom_core::network=debug"}`.
+pub(crate) fn log_level_admin_route(
+    control: Arc<LogControl>,
+    token: Option<AdminToken>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct LogFilter {
+        filter: String,
+    }
+
+    let read = Arc::clone(&control);
+    let get = warp::path!("v1" / "admin" / "log-level")
+        .and(warp::get())
+        .and(admin_authorized(token.clone()))
+        .map(move || warp::reply::json(&LogFilter { filter: read.filter() }));
+
+    let set = warp::path!("v1" / "admin" / "log-level")
+        .and(warp::post())
+        .and(admin_authorized(token))
+        .and(warp::body::content_length_limit(4096))
+        .and(warp::body::json())
+        .map(move |request: LogFilter| match control.set_filter(&request.filter) {
+            Ok(()) => {
+                info!(filter = %request.filter, "📝 Log filter changed");
+                warp::reply::with_status(warp::reply::json(&LogFilter { filter: control.filter() }), warp::http::StatusCode::OK)
+            }
+            Err(e) => warp::reply::with_status(
+                warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, e)),
+                warp::http::StatusCode::BAD_REQUEST,
+            ),
+        });
+
+    get.or(set)
+}
+
+/// `POST /v1/admin/rpc`: JSON-RPC calls from axiom_core::admin, e.g.
+/// `{"jsonrpc": "2.0", "id": 1, "method": "admin_banPeer", "params": ["12D3..."]}`
+/// with `Authorization: Bearer $(cat admin.token)`.
+pub(crate) fn admin_rpc_route(
+    token: Option<AdminToken>,
+    calls: tokio::sync::mpsc::UnboundedSender<AdminCall>,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "admin" / "rpc")
+        .and(warp::post())
+        .and(loopback_only())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::body::content_length_limit(64 * 1024))
+        .and(warp::body::bytes())
+        .and_then(move |authorization: Option<String>, body: warp::hyper::body::Bytes| {
+            let authorized = token.as_ref().is_some_and(|t| t.authorizes(authorization.as_deref()));
+            let calls = calls.clone();
+            async move {
+                if !authorized {
+                    return Ok::<_, warp::Rejection>(warp::reply::with_status(
+                        warp::reply::json(&RpcResponse::error(serde_json::Value::Null, UNAUTHORIZED,
+                            format!("missing or wrong bearer token (see {})", ADMIN_TOKEN_FILE))),
+                        warp::http::StatusCode::UNAUTHORIZED,
+                    ));
+                }
+                let request: RpcRequest = match serde_json::from_slice(&body) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        return Ok(warp::reply::with_status(
+                            warp::reply::json(&RpcResponse::error(serde_json::Value::Null, PARSE_ERROR, e.to_string())),
+                            warp::http::StatusCode::BAD_REQUEST,
+                        ));
+                    }
+                };
+                let response = match AdminCommand::parse(&request.method, &request.params) {
+                    Err((code, message)) => RpcResponse::error(request.id, code, message),
+                    Ok(command) => {
+                        let (reply, result) = tokio::sync::oneshot::channel();
+                        let outcome = match calls.send((command, reply)) {
+                            Ok(()) => result.await.unwrap_or(Err((INTERNAL_ERROR, "node is shutting down".to_string()))),
+                            Err(_) => Err((INTERNAL_ERROR, "node is shutting down".to_string())),
+                        };
+                        match outcome {
+                            Ok(value) => RpcResponse::result(request.id, value),
+                            Err((code, message)) => RpcResponse::error(request.id, code, message),
+                        }
+                    }
+                };
+                Ok(warp::reply::with_status(warp::reply::json(&response), warp::http::StatusCode::OK))
+            }
+        })
+}
+
+/// `GET`/`POST /v1/admin/chaos`: read or replace the injected fault set.
+#[cfg(feature = "chaos")]
+pub(crate) fn chaos_admin_route(token: Option<AdminToken>) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    use axiom_core::chaos::{self, FaultConfig};
+
+    let authorized = admin_authorized(token);
+
+    let get = warp::path!("v1" / "admin" / "chaos")
+        .and(warp::get())
+        .and(authorized.clone())
+        .map(|| warp::reply::json(&chaos::faults()));
+
+    let set = warp::path!("v1" / "admin" / "chaos")
+        .and(warp::post())
+        .and(authorized)
+        .and(warp::body::content_length_limit(4096))
+        .and(warp::body::json())
+        .map(|faults: FaultConfig| match chaos::set_faults(faults.clone()) {
+            Ok(()) => {
+                info!("🧪 CHAOS: Fault set changed: {:?}", faults);
+                warp::reply::with_status(warp::reply::json(&faults), warp::http::StatusCode::OK)
+            }
+            Err(e) => warp::reply::with_status(
+                warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, e)),
+                warp::http::StatusCode::BAD_REQUEST,
+            ),
+        });
+
+    get.or(set)
+}
@@ -0,0 +1,441 @@
+// src/api/chain.rs - Status, probe, chain, archive and network routes
+
+use std::net::SocketAddr;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use warp::Filter;
+use governor::{Quota, RateLimiter};
+use axiom_core::change_feed::MAX_CHANGES_PER_PAGE;
+use axiom_core::block_index::MAX_BLOCKS_PER_PAGE;
+use axiom_core::health::{storage_writable, Readiness};
+use axiom_core::supply_proof::PulseChain;
+use crate::{lock_or_recover, unix_now, build};
+use super::{PulseHistoryEntry, ApiRateLimiter, ApiRoutes, ApiContext, TooManyRequests};
+
+/// Archive chunk downloads allowed per minute per IP (each chunk is a
+/// full `ARCHIVE_CHUNK_BLOCKS` range, so this bounds bulk bandwidth).
+const ARCHIVE_CHUNKS_PER_MINUTE: u32 = 6;
+
+/// Query parameters for `/v1/blocks`.
+#[derive(serde::Deserialize)]
+struct BlocksQuery {
+    from: Option<u64>,
+    limit: Option<usize>,
+}
+
+/// Query parameters for `/v1/pulses`; both bounds are inclusive heights.
+#[derive(serde::Deserialize)]
+struct PulsesQuery {
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+/// Query parameters for `/v1/changes`.
+#[derive(serde::Deserialize)]
+struct ChangesQuery {
+    epoch: Option<u64>,
+    since: Option<u64>,
+    limit: Option<usize>,
+}
+
+/// Response body for `/v1/pulses`.
+#[derive(serde::Serialize)]
+struct PulsesResponse {
+    genesis_pulse_hash: &'static str,
+    #[serde(flatten)]
+    chain: PulseChain,
+    /// Latest Global Trust Pulse commitment, if one has been built yet.
+    trust_pulse: Option<PulseHistoryEntry>,
+}
+
+/// Dashboard status, version and the orchestrator probes.
+pub(crate) fn probe_routes(ctx: &ApiContext) -> ApiRoutes {
+    let api_state_status = Arc::clone(&ctx.api_state);
+    let rate_limiter_status = Arc::clone(&ctx.rate_limiter);
+    let status_route = warp::path!("v1" / "status")
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |addr: Option<SocketAddr>| {
+            let api_state = Arc::clone(&api_state_status);
+            let limiter = Arc::clone(&rate_limiter_status);
+            async move {
+                // Use the client's socket address for rate-limiting; fall back
+                // to loopback when warp can't determine the remote addr.
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let state = lock_or_recover(&api_state).clone();
+                Ok::<_, warp::Rejection>(warp::reply::json(&state))
+            }
+        });
+
+    let version_route = warp::path!("v1" / "version")
+        .and(warp::get())
+        .map(|| {
+            let info = serde_json::json!({
+                "version": build::PKG_VERSION,
+                "build_time_utc": build::BUILD_TIME,
+                "git_commit_hash": build::SHORT_COMMIT,
+                "rustc_version": build::RUST_VERSION,
+            });
+            warp::reply::json(&info)
+        });
+
+    let health_check_route = warp::path!("v1" / "health" / "check")
+        .and(warp::get())
+        .map(|| warp::reply::with_status("ALIVE", warp::http::StatusCode::OK));
+
+    // Orchestrator probes (see axiom_core::health). Not rate-limited:
+    // kubelets poll them every few seconds from a single address.
+    let liveness = Arc::clone(&ctx.heartbeat);
+    let healthz_route = warp::path!("healthz")
+        .and(warp::get())
+        .map(move || {
+            let alive = liveness.alive(unix_now());
+            let status = if alive { warp::http::StatusCode::OK } else { warp::http::StatusCode::SERVICE_UNAVAILABLE };
+            let body = serde_json::json!({"alive": alive, "last_heartbeat": liveness.last()});
+            warp::reply::with_status(warp::reply::json(&body), status)
+        });
+
+    let readyz_api = Arc::clone(&ctx.api_state);
+    let ready_min_peers = ctx.ready_min_peers;
+    let storage_dir = ctx.storage_dir.clone();
+    let readyz_route = warp::path!("readyz")
+        .and(warp::get())
+        .and_then(move || {
+            let api = Arc::clone(&readyz_api);
+            let dir = storage_dir.clone();
+            async move {
+                let writable = tokio::task::spawn_blocking(move || storage_writable(&dir)).await.unwrap_or(false);
+                let state = lock_or_recover(&api).clone();
+                let readiness = Readiness::evaluate(state.current_height, state.network_height, state.is_syncing,
+                    state.connected_peers, ready_min_peers, writable);
+                let status = if readiness.ready { warp::http::StatusCode::OK } else { warp::http::StatusCode::SERVICE_UNAVAILABLE };
+                Ok::<_, warp::Rejection>(warp::reply::with_status(warp::reply::json(&readiness), status))
+            }
+        });
+
+    status_route
+        .or(version_route)
+        .or(health_check_route)
+        .or(healthz_route)
+        .or(readyz_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
+
+/// Pulses, change feed, block bodies, supply, fees and checkpoints.
+pub(crate) fn chain_routes(ctx: &ApiContext) -> ApiRoutes {
+    // Pulse history: returns the last 10 chained pulses so anyone with
+    // a browser can verify the prev_pulse_hash links.
+    let pulse_history_api = Arc::clone(&ctx.pulse_history);
+    let pulse_history_route = warp::path!("v1" / "pulse" / "history")
+        .and(warp::get())
+        .map(move || {
+            let history = lock_or_recover(&pulse_history_api);
+            let entries: Vec<PulseHistoryEntry> = history.iter().cloned().collect();
+            warp::reply::json(&entries)
+        });
+
+    // Change feed: indexers pass the epoch and last cursor they processed
+    // and get only newer events, so each change is applied exactly once.
+    let change_feed_api = Arc::clone(&ctx.change_feed);
+    let rate_limiter_changes = Arc::clone(&ctx.rate_limiter);
+    let changes_route = warp::path!("v1" / "changes")
+        .and(warp::get())
+        .and(warp::query::<ChangesQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |query: ChangesQuery, addr: Option<SocketAddr>| {
+            let feed = Arc::clone(&change_feed_api);
+            let limiter = Arc::clone(&rate_limiter_changes);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let page = lock_or_recover(&feed).since(
+                    query.epoch,
+                    query.since.unwrap_or(0),
+                    query.limit.unwrap_or(MAX_CHANGES_PER_PAGE),
+                );
+                Ok::<_, warp::Rejection>(warp::reply::json(&page))
+            }
+        });
+
+    // Block bodies by height, so an explorer can ingest the chain from
+    // genesis and re-fetch whatever the change feed reports as changed.
+    let block_index_api = Arc::clone(&ctx.block_index);
+    let rate_limiter_blocks = Arc::clone(&ctx.rate_limiter);
+    let blocks_route = warp::path!("v1" / "blocks")
+        .and(warp::get())
+        .and(warp::query::<BlocksQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |query: BlocksQuery, addr: Option<SocketAddr>| {
+            let index = Arc::clone(&block_index_api);
+            let limiter = Arc::clone(&rate_limiter_blocks);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let page = lock_or_recover(&index).page(
+                    query.from.unwrap_or(0),
+                    query.limit.unwrap_or(MAX_BLOCKS_PER_PAGE),
+                );
+                Ok::<_, warp::Rejection>(warp::reply::json(&page))
+            }
+        });
+
+    // Supply statistics for explorers and market-data aggregators.
+    let supply_api = Arc::clone(&ctx.supply);
+    let rate_limiter_supply_stats = Arc::clone(&ctx.rate_limiter);
+    let supply_route = warp::path!("v1" / "supply")
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |addr: Option<SocketAddr>| {
+            let supply = Arc::clone(&supply_api);
+            let limiter = Arc::clone(&rate_limiter_supply_stats);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let snapshot = lock_or_recover(&supply).clone();
+                Ok::<_, warp::Rejection>(warp::reply::json(&snapshot))
+            }
+        });
+
+    // Fee-rate percentiles of recently included transactions, which
+    // the SDK turns into a fee for a requested priority.
+    let fee_stats_api = Arc::clone(&ctx.fee_stats);
+    let rate_limiter_fees = Arc::clone(&ctx.rate_limiter);
+    let fees_route = warp::path!("v1" / "fees")
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |addr: Option<SocketAddr>| {
+            let stats = Arc::clone(&fee_stats_api);
+            let limiter = Arc::clone(&rate_limiter_fees);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let stats = lock_or_recover(&stats).clone();
+                Ok::<_, warp::Rejection>(warp::reply::json(&stats))
+            }
+        });
+
+    // Light supply proof: latest STARK-bearing pulse plus the pulse
+    // chain up to the tip, for SDK "verified supply" checks.
+    let pulse_log_api = Arc::clone(&ctx.pulse_log);
+    let rate_limiter_supply = Arc::clone(&ctx.rate_limiter);
+    let supply_proof_route = warp::path!("v1" / "supply" / "proof")
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |addr: Option<SocketAddr>| {
+            let pulses = Arc::clone(&pulse_log_api);
+            let limiter = Arc::clone(&rate_limiter_supply);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let proof = lock_or_recover(&pulses).supply_proof().ok_or_else(warp::reject::not_found)?;
+                Ok::<_, warp::Rejection>(warp::reply::json(&proof))
+            }
+        });
+
+    // Final checkpoints, lowest first, for light clients that start
+    // from a recent one instead of genesis.
+    let checkpoints_api = Arc::clone(&ctx.checkpoints);
+    let checkpoints_route = warp::path!("v1" / "checkpoints")
+        .and(warp::get())
+        .map(move || warp::reply::json(&*lock_or_recover(&checkpoints_api)));
+
+    // Block pulses by height with every prev_pulse_hash link checked,
+    // plus the latest trust pulse, for explorers auditing the chain.
+    let pulse_log_list = Arc::clone(&ctx.pulse_log);
+    let pulse_history_list = Arc::clone(&ctx.pulse_history);
+    let rate_limiter_pulses = Arc::clone(&ctx.rate_limiter);
+    let pulses_route = warp::path!("v1" / "pulses")
+        .and(warp::get())
+        .and(warp::query::<PulsesQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |query: PulsesQuery, addr: Option<SocketAddr>| {
+            let pulses = Arc::clone(&pulse_log_list);
+            let history = Arc::clone(&pulse_history_list);
+            let limiter = Arc::clone(&rate_limiter_pulses);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let chain = lock_or_recover(&pulses).chain(query.from.unwrap_or(0), query.to.unwrap_or(u64::MAX));
+                let trust_pulse = lock_or_recover(&history).back().cloned();
+                Ok::<_, warp::Rejection>(warp::reply::json(&PulsesResponse {
+                    genesis_pulse_hash: axiom_core::GENESIS_PULSE_HASH,
+                    chain,
+                    trust_pulse,
+                }))
+            }
+        });
+
+    pulse_history_route
+        .or(changes_route)
+        .or(blocks_route)
+        .or(supply_route)
+        .or(fees_route)
+        .or(supply_proof_route)
+        .or(checkpoints_route)
+        .or(pulses_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
+
+/// Block archive and state delta downloads, when `[storage] archive_dir`
+/// is set.
+pub(crate) fn archive_routes(ctx: &ApiContext) -> ApiRoutes {
+    // Block archive: manifest plus resumable (Range) chunk downloads,
+    // behind a tighter per-IP limit than the rest of the API.
+    let archive_manifest_api = ctx.block_archive.clone();
+    let archive_manifest_route = warp::path!("v1" / "archive" / "manifest")
+        .and(warp::get())
+        .and_then(move || {
+            let archive = archive_manifest_api.clone();
+            async move {
+                let archive = archive.ok_or_else(warp::reject::not_found)?;
+                let manifest = lock_or_recover(&archive).manifest().clone();
+                Ok::<_, warp::Rejection>(warp::reply::json(&manifest))
+            }
+        });
+
+    let archive_chunk_api = ctx.block_archive.clone();
+    let archive_limiter: Arc<ApiRateLimiter> =
+        Arc::new(RateLimiter::dashmap(
+            Quota::per_minute(NonZeroU32::new(ARCHIVE_CHUNKS_PER_MINUTE).unwrap()),
+        ));
+    let state_diff_limiter = Arc::clone(&archive_limiter);
+    let archive_chunk_route = warp::path!("v1" / "archive" / "chunks" / u64)
+        .and(warp::get())
+        .and(warp::header::optional::<String>("range"))
+        .and(warp::addr::remote())
+        .and_then(move |index: u64, range: Option<String>, addr: Option<SocketAddr>| {
+            let archive = archive_chunk_api.clone();
+            let limiter = Arc::clone(&archive_limiter);
+            async move {
+                let archive = archive.ok_or_else(warp::reject::not_found)?;
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let (path, etag) = {
+                    let archive = lock_or_recover(&archive);
+                    let path = archive.chunk_path(index).ok_or_else(warp::reject::not_found)?;
+                    (path, archive.manifest().chunks[index as usize].blake3.clone())
+                };
+                let data = tokio::fs::read(&path).await.map_err(|_| warp::reject::not_found())?;
+                let len = data.len() as u64;
+                let response = warp::http::Response::builder()
+                    .header("Content-Type", "application/gzip")
+                    .header("Accept-Ranges", "bytes")
+                    .header("ETag", format!("\"{}\"", etag));
+                let response = match range.as_deref().map(|r| axiom_core::archive::parse_range(r, len)) {
+                    Some(Some((start, end))) => response
+                        .status(warp::http::StatusCode::PARTIAL_CONTENT)
+                        .header("Content-Range", format!("bytes {}-{}/{}", start, end, len))
+                        .body(data[start as usize..=end as usize].to_vec()),
+                    Some(None) => response
+                        .status(warp::http::StatusCode::RANGE_NOT_SATISFIABLE)
+                        .header("Content-Range", format!("bytes */{}", len))
+                        .body(Vec::new()),
+                    None => response.body(data),
+                };
+                response.map_err(|_| warp::reject::not_found())
+            }
+        });
+
+    // State deltas: manifest plus the signed delta files it lists,
+    // sharing the archive's download limit.
+    let state_diff_manifest_api = ctx.state_diffs.clone();
+    let state_diff_manifest_route = warp::path!("v1" / "snapshots" / "manifest")
+        .and(warp::get())
+        .and_then(move || {
+            let store = state_diff_manifest_api.clone();
+            async move {
+                let store = store.ok_or_else(warp::reject::not_found)?;
+                let manifest = lock_or_recover(&store).manifest().clone();
+                Ok::<_, warp::Rejection>(warp::reply::json(&manifest))
+            }
+        });
+
+    let state_diff_file_api = ctx.state_diffs.clone();
+    let state_diff_file_route = warp::path!("v1" / "snapshots" / u64 / u64)
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |from: u64, to: u64, addr: Option<SocketAddr>| {
+            let store = state_diff_file_api.clone();
+            let limiter = Arc::clone(&state_diff_limiter);
+            async move {
+                let store = store.ok_or_else(warp::reject::not_found)?;
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let path = lock_or_recover(&store).file_path(from, to).ok_or_else(warp::reject::not_found)?;
+                let data = tokio::fs::read(&path).await.map_err(|_| warp::reject::not_found())?;
+                warp::http::Response::builder()
+                    .header("Content-Type", "application/gzip")
+                    .body(data)
+                    .map_err(|_| warp::reject::not_found())
+            }
+        });
+
+    archive_manifest_route
+        .or(archive_chunk_route)
+        .or(state_diff_manifest_route)
+        .or(state_diff_file_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
+
+/// Bandwidth per peer and our discv5 node record.
+pub(crate) fn network_routes(ctx: &ApiContext) -> ApiRoutes {
+    // Per-peer bandwidth, refreshed with the dashboard.
+    let node_metrics_api = ctx.node_metrics.clone();
+    let bandwidth_route = warp::path!("v1" / "metrics" / "bandwidth")
+        .and(warp::get())
+        .map(move || {
+            let m = node_metrics_api.snapshot();
+            warp::reply::json(&serde_json::json!({
+                "total_bytes_in": m.total_bytes_in,
+                "total_bytes_out": m.total_bytes_out,
+                "peers": m.peer_bandwidth,
+            }))
+        });
+
+    // Our signed node record, for operators assembling bootstrap lists.
+    let discv5_api = ctx.discv5_service.clone();
+    let enr_route = warp::path!("v1" / "network" / "enr")
+        .and(warp::get())
+        .and_then(move || {
+            let svc = discv5_api.clone();
+            async move {
+                let svc = svc.ok_or_else(warp::reject::not_found)?;
+                let enr = svc.local_enr().await;
+                Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                    "enr": enr.to_base64(),
+                    "node_id": hex::encode(enr.node_id().raw()),
+                    "seq": enr.seq(),
+                    "ip4": enr.ip4(),
+                    "tcp4": enr.tcp4(),
+                    "udp4": enr.udp4(),
+                })))
+            }
+        });
+
+    bandwidth_route
+        .or(enr_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
@@ -0,0 +1,280 @@
+// src/api/guardian.rs - Contract registry and guardian routes
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use warp::Filter;
+use axiom_core::key_audit::{SignedKind, SigningInterface, SigningRecord};
+use axiom_core::error::{ErrorBody, ErrorCode};
+use axiom_core::contract_registry::{decode_hex32, AuditSummary, Registration};
+use tracing::info;
+use axiom_core::guardian::decision_audit::MAX_AUDIT_QUERY;
+use crate::{lock_or_recover, unix_now, audit_signature};
+use super::{ApiRoutes, ApiContext, TooManyRequests, InvalidImageId};
+use super::admin::admin_authorized;
+
+/// Query parameters for `/v1/contracts`.
+#[derive(serde::Deserialize)]
+struct ContractsQuery {
+    name: Option<String>,
+}
+
+/// The parts of a ContractAuditor `AuditReport` the registry keeps; the
+/// rest of the report is accepted and ignored.
+#[derive(serde::Deserialize)]
+struct AuditReportBody {
+    contract_hash: String,
+    overall_score: u8,
+    #[serde(default)]
+    vulnerabilities: Vec<serde_json::Value>,
+}
+
+/// Query parameters for `/v1/guardian/report`: a unix-time range.
+#[derive(serde::Deserialize)]
+struct ThreatReportQuery {
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+/// Query parameters for `/v1/guardian/audit`: a peer and a unix-time range.
+#[derive(serde::Deserialize)]
+struct GuardianAuditQuery {
+    peer: Option<String>,
+    from: Option<u64>,
+    to: Option<u64>,
+    limit: Option<usize>,
+}
+
+/// Range of a threat report when the caller gives no `from`.
+const DEFAULT_THREAT_REPORT_SECS: u64 = 7 * 24 * 3600;
+
+/// Contract registry lookups, registration and audits.
+pub(crate) fn contract_routes(ctx: &ApiContext) -> ApiRoutes {
+    // Contract registry: look up by image ID or name, and accept
+    // publisher-signed registrations. Audits are attached from the host
+    // only, by piping a ContractAuditor report into the admin route.
+    let registry_list_api = Arc::clone(&ctx.contract_registry);
+    let contracts_list_route = warp::path!("v1" / "contracts")
+        .and(warp::get())
+        .and(warp::query::<ContractsQuery>())
+        .and_then(move |query: ContractsQuery| {
+            let registry = Arc::clone(&registry_list_api);
+            async move {
+                let registry = lock_or_recover(&registry);
+                let reply = match query.name {
+                    Some(name) => {
+                        let entry = registry.find_by_name(&name).ok_or_else(warp::reject::not_found)?;
+                        warp::reply::json(entry)
+                    }
+                    None => warp::reply::json(&registry.list()),
+                };
+                Ok::<_, warp::Rejection>(reply)
+            }
+        });
+
+    let registry_get_api = Arc::clone(&ctx.contract_registry);
+    let contract_route = warp::path!("v1" / "contracts" / String)
+        .and(warp::get())
+        .and_then(move |image_id: String| {
+            let registry = Arc::clone(&registry_get_api);
+            async move {
+                let image_id = decode_hex32(&image_id).ok_or_else(|| warp::reject::custom(InvalidImageId))?;
+                let registry = lock_or_recover(&registry);
+                let entry = registry.get(&image_id).ok_or_else(warp::reject::not_found)?;
+                Ok::<_, warp::Rejection>(warp::reply::json(entry))
+            }
+        });
+
+    let registry_register_api = Arc::clone(&ctx.contract_registry);
+    let rate_limiter_register = Arc::clone(&ctx.rate_limiter);
+    let contract_register_route = warp::path!("v1" / "contracts")
+        .and(warp::post())
+        .and(warp::addr::remote())
+        .and(warp::body::content_length_limit(4096))
+        .and(warp::body::json())
+        .and_then(move |addr: Option<SocketAddr>, registration: Registration| {
+            let registry = Arc::clone(&registry_register_api);
+            let limiter = Arc::clone(&rate_limiter_register);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let mut registry = lock_or_recover(&registry);
+                let reply = match registry.register(registration, unix_now()) {
+                    Ok(entry) => {
+                        info!("📇 Contract registered: {} {} ({})",
+                            entry.metadata.name, entry.metadata.version, &entry.image_id[..16]);
+                        warp::reply::with_status(warp::reply::json(entry), warp::http::StatusCode::OK)
+                    }
+                    Err(e) => warp::reply::with_status(
+                        warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, e.to_string())),
+                        warp::http::StatusCode::BAD_REQUEST,
+                    ),
+                };
+                if let Err(e) = registry.save() {
+                    log::warn!("Failed to save contract registry: {}", e);
+                }
+                Ok::<_, warp::Rejection>(reply)
+            }
+        });
+
+    let registry_audit_api = Arc::clone(&ctx.contract_registry);
+    let contract_audit_route = warp::path!("v1" / "admin" / "contracts" / String / "audit")
+        .and(warp::post())
+        .and(admin_authorized(ctx.admin_token.clone()))
+        .and(warp::body::content_length_limit(1024 * 1024))
+        .and(warp::body::json())
+        .map(move |image_id: String, report: AuditReportBody| {
+            let Some(image_id) = decode_hex32(&image_id) else {
+                return warp::reply::with_status(
+                    warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, "invalid image ID")),
+                    warp::http::StatusCode::BAD_REQUEST,
+                );
+            };
+            let audit = AuditSummary {
+                score: report.overall_score,
+                findings: report.vulnerabilities.len() as u32,
+                report_hash: report.contract_hash,
+                audited_at: unix_now(),
+            };
+            let mut registry = lock_or_recover(&registry_audit_api);
+            let result = registry.record_audit(&image_id, audit);
+            if let Err(e) = registry.save() {
+                log::warn!("Failed to save contract registry: {}", e);
+            }
+            match result {
+                Ok(()) => warp::reply::with_status(
+                    warp::reply::json(&registry.get(&image_id)),
+                    warp::http::StatusCode::OK,
+                ),
+                Err(e) => warp::reply::with_status(
+                    warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, e.to_string())),
+                    warp::http::StatusCode::BAD_REQUEST,
+                ),
+            }
+        });
+
+    contracts_list_route
+        .or(contract_route)
+        .or(contract_register_route)
+        .or(contract_audit_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
+
+/// Guardian threat report, threat feed, decision audit and pardons.
+pub(crate) fn guardian_routes(ctx: &ApiContext) -> ApiRoutes {
+    // Threat report: guardian activity over [from, to) (default: the
+    // last week), signed with the node wallet for publication.
+    let guardian_events_api = Arc::clone(&ctx.guardian_events);
+    let rate_limiter_report = Arc::clone(&ctx.rate_limiter);
+    let report_signer = Arc::clone(&ctx.signer);
+    let report_audit = ctx.key_audit.clone();
+    let threat_report_route = warp::path!("v1" / "guardian" / "report")
+        .and(warp::get())
+        .and(warp::query::<ThreatReportQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |query: ThreatReportQuery, addr: Option<SocketAddr>| {
+            let events = Arc::clone(&guardian_events_api);
+            let limiter = Arc::clone(&rate_limiter_report);
+            let signer = Arc::clone(&report_signer);
+            let audit = report_audit.clone();
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let now = unix_now();
+                let to = query.to.unwrap_or(now + 1);
+                let from = query.from.unwrap_or(to.saturating_sub(DEFAULT_THREAT_REPORT_SECS));
+                let report = lock_or_recover(&events).report(&signer.address, from, to, now);
+                let digest = *blake3::hash(&report.signing_bytes()).as_bytes();
+                audit_signature(&audit, SigningRecord::object(
+                    SigningInterface::Rpc, SignedKind::ThreatReport, signer.address, digest));
+                Ok::<_, warp::Rejection>(warp::reply::json(&report.sign(&signer)))
+            }
+        });
+
+    // Threat feed: the addresses flagged on this node with their
+    // evidence, signed with the node wallet for other nodes to import.
+    let feed_engine = Arc::clone(&ctx.security_engine);
+    let rate_limiter_feed = Arc::clone(&ctx.rate_limiter);
+    let feed_signer = Arc::clone(&ctx.signer);
+    let feed_audit = ctx.key_audit.clone();
+    let threat_feed_route = warp::path!("v1" / "threat-intel" / "feed")
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |addr: Option<SocketAddr>| {
+            let engine = Arc::clone(&feed_engine);
+            let limiter = Arc::clone(&rate_limiter_feed);
+            let signer = Arc::clone(&feed_signer);
+            let audit = feed_audit.clone();
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let feed = engine.export_threat_feed(&signer.address, unix_now());
+                let digest = *blake3::hash(&feed.signing_bytes()).as_bytes();
+                audit_signature(&audit, SigningRecord::object(
+                    SigningInterface::Rpc, SignedKind::ThreatFeed, signer.address, digest));
+                Ok::<_, warp::Rejection>(warp::reply::json(&feed.sign(&signer)))
+            }
+        });
+
+    // Decision audit log: the guardian's decisions and enforced
+    // measures with their proofs, by peer and time range.
+    let guardian_audit_api = Arc::clone(&ctx.guardian_audit);
+    let rate_limiter_audit = Arc::clone(&ctx.rate_limiter);
+    let guardian_audit_route = warp::path!("v1" / "guardian" / "audit")
+        .and(warp::get())
+        .and(warp::query::<GuardianAuditQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |query: GuardianAuditQuery, addr: Option<SocketAddr>| {
+            let audit = Arc::clone(&guardian_audit_api);
+            let limiter = Arc::clone(&rate_limiter_audit);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let records = lock_or_recover(&audit).query(
+                    query.peer.as_deref(),
+                    query.from.unwrap_or(0),
+                    query.to.unwrap_or(u64::MAX),
+                    query.limit.unwrap_or(MAX_AUDIT_QUERY),
+                );
+                Ok::<_, warp::Rejection>(warp::reply::json(&records))
+            }
+        });
+
+    // False-positive correction: clear a peer's assessment (lifting a
+    // ban) and count the correction in later reports.
+    let guardian_pardon_api = Arc::clone(&ctx.ai_guardian);
+    let guardian_events_pardon = Arc::clone(&ctx.guardian_events);
+    let guardian_enforcer_pardon = Arc::clone(&ctx.guardian_enforcer);
+    let guardian_pardon_route = warp::path!("v1" / "admin" / "guardian" / "pardon" / String)
+        .and(warp::post())
+        .and(admin_authorized(ctx.admin_token.clone()))
+        .map(move |peer_id: String| {
+            if !lock_or_recover(&guardian_pardon_api).pardon(&peer_id) {
+                return warp::reply::with_status(
+                    warp::reply::json(&ErrorBody::new(ErrorCode::NotFound, "peer has no assessment")),
+                    warp::http::StatusCode::NOT_FOUND,
+                );
+            }
+            lock_or_recover(&guardian_events_pardon).correct_false_positive(&peer_id, None, unix_now());
+            lock_or_recover(&guardian_enforcer_pardon).lift(&peer_id, unix_now());
+            warp::reply::with_status(
+                warp::reply::json(&serde_json::json!({"pardoned": peer_id})),
+                warp::http::StatusCode::OK,
+            )
+        });
+
+    threat_report_route
+        .or(threat_feed_route)
+        .or(guardian_audit_route)
+        .or(guardian_pardon_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
@@ -0,0 +1,219 @@
+// src/api/mod.rs - The node's HTTP API
+//
+// The public Pulse API and the loopback-only admin routes, served by warp.
+// `ApiContext` holds the node state the routes read, shared with the event
+// loop; each submodule builds one group of routes from it, boxed as
+// `ApiRoutes`. Failed requests are answered by `handle_rejection`.
+
+mod admin;
+mod chain;
+mod guardian;
+mod transactions;
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use governor::RateLimiter;
+use governor::clock::DefaultClock;
+use governor::state::keyed::DashMapStateStore;
+use axiom_core::network::Discv5Service;
+use axiom_core::metrics::MetricsCollector;
+use axiom_core::wallet::Wallet;
+use axiom_core::state::State;
+use axiom_core::change_feed::ChangeFeed;
+use axiom_core::block_index::BlockIndex;
+use axiom_core::pending_index::PendingIndex;
+use axiom_core::economics::SupplySnapshot;
+use axiom_core::fee_market::FeeStats;
+use axiom_core::checkpoint::Checkpoints;
+use axiom_core::address_index::AddressIndex;
+use axiom_core::receipt::ReceiptIndex;
+use axiom_core::key_audit::KeyAuditLog;
+use axiom_core::error::{ErrorBody, ErrorCode};
+use axiom_core::health::Heartbeat;
+use axiom_core::contract_registry::ContractRegistry;
+use axiom_core::supply_proof::PulseLog;
+use axiom_core::archive::BlockArchive;
+use axiom_core::state_diff::StateDiffStore;
+use axiom_core::admin::{AdminCommand, AdminToken, ADMIN_TOKEN_FILE};
+use axiom_core::transaction::Transaction;
+use axiom_core::neural_guardian::NeuralGuardian;
+use axiom_core::guardian::threat_report::GuardianEventLog;
+use axiom_core::guardian::enforcement::GuardianEnforcer;
+use axiom_core::guardian::decision_audit::DecisionAuditLog;
+use axiom_core::ai_core::MultiLayerSecurityEngine;
+
+pub(crate) use self::admin::{admin_rpc_route, log_level_admin_route};
+#[cfg(feature = "chaos")]
+pub(crate) use self::admin::chaos_admin_route;
+pub(crate) use self::chain::{archive_routes, chain_routes, network_routes, probe_routes};
+pub(crate) use self::guardian::{contract_routes, guardian_routes};
+pub(crate) use self::transactions::{account_routes, transaction_routes};
+
+/// The Public Pulse API rate limit ([rpc] rate_limit, per IP) is counted
+/// over this window; rejected clients are told to retry after it.
+pub(crate) const API_RATE_LIMIT_WINDOW_SECS: u64 = 60;
+
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct PulseApiState {
+    pub(crate) protocol_phase: String,
+    pub(crate) current_height: u64,
+    /// Current PoW difficulty target.
+    pub(crate) difficulty: u64,
+    pub(crate) supply_remaining_units: u64,
+    pub(crate) supply_remaining_axm: String,
+    pub(crate) trust_pulse: String,
+    pub(crate) zk_verified: bool,
+    pub(crate) is_syncing: bool,
+    /// Best chain height seen on the network.
+    pub(crate) network_height: u64,
+    pub(crate) connected_peers: usize,
+    /// Stale blocks seen since startup, and their share of recent blocks.
+    pub(crate) stale_blocks: u64,
+    pub(crate) orphan_rate: f64,
+}
+
+/// A single entry in the in-memory pulse history ring buffer.
+#[derive(Clone, serde::Serialize)]
+pub(crate) struct PulseHistoryEntry {
+    pub(crate) height: u64,
+    pub(crate) trust_pulse_hex: String,
+    pub(crate) prev_pulse_hex: String,
+    pub(crate) timestamp: u64,
+}
+
+/// An admin RPC call on its way to the event loop, with the channel for
+/// its result.
+pub(crate) type AdminCall = (AdminCommand, tokio::sync::oneshot::Sender<Result<serde_json::Value, (i64, String)>>);
+
+/// A transaction posted to /v1/transactions on its way to the event loop,
+/// with the channel for the verdict.
+pub(crate) type TxSubmission = (Transaction, tokio::sync::oneshot::Sender<Result<SubmittedTx, ErrorBody>>);
+
+/// The reply to an accepted submission.
+#[derive(serde::Serialize)]
+pub(crate) struct SubmittedTx {
+    pub(crate) hash: String,
+    /// Chain length from which a quarantined transaction is pooled and
+    /// relayed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) quarantined_until: Option<u64>,
+}
+
+pub(crate) type ApiRateLimiter = RateLimiter<SocketAddr, DashMapStateStore<SocketAddr>, DefaultClock>;
+
+/// A group of API routes, boxed so the combined route tree stays within
+/// the compiler's type depth limit.
+pub(crate) type ApiRoutes = warp::filters::BoxedFilter<(warp::reply::Response,)>;
+
+/// The node state the public API reads, shared with the event loop.
+pub(crate) struct ApiContext {
+    pub(crate) rate_limiter: Arc<ApiRateLimiter>,
+    pub(crate) api_state: Arc<Mutex<PulseApiState>>,
+    pub(crate) heartbeat: Arc<Heartbeat>,
+    /// Checked for writability by /readyz.
+    pub(crate) storage_dir: std::path::PathBuf,
+    pub(crate) ready_min_peers: usize,
+    pub(crate) pulse_history: Arc<Mutex<VecDeque<PulseHistoryEntry>>>,
+    pub(crate) pulse_log: Arc<Mutex<PulseLog>>,
+    pub(crate) change_feed: Arc<Mutex<ChangeFeed>>,
+    pub(crate) block_index: Arc<Mutex<BlockIndex>>,
+    pub(crate) pending_index: Arc<Mutex<PendingIndex>>,
+    pub(crate) address_index: Arc<Mutex<AddressIndex>>,
+    pub(crate) receipt_index: Arc<Mutex<ReceiptIndex>>,
+    pub(crate) account_state: Arc<Mutex<State>>,
+    pub(crate) supply: Arc<Mutex<SupplySnapshot>>,
+    pub(crate) fee_stats: Arc<Mutex<FeeStats>>,
+    pub(crate) checkpoints: Arc<Mutex<Checkpoints>>,
+    pub(crate) contract_registry: Arc<Mutex<ContractRegistry>>,
+    pub(crate) block_archive: Option<Arc<Mutex<BlockArchive>>>,
+    pub(crate) state_diffs: Option<Arc<Mutex<StateDiffStore>>>,
+    pub(crate) node_metrics: MetricsCollector,
+    pub(crate) discv5_service: Option<Discv5Service>,
+    pub(crate) guardian_events: Arc<Mutex<GuardianEventLog>>,
+    pub(crate) guardian_audit: Arc<Mutex<DecisionAuditLog>>,
+    pub(crate) guardian_enforcer: Arc<Mutex<GuardianEnforcer>>,
+    pub(crate) ai_guardian: Arc<Mutex<NeuralGuardian>>,
+    pub(crate) security_engine: Arc<MultiLayerSecurityEngine>,
+    /// The node wallet, signing threat reports and feeds.
+    pub(crate) signer: Arc<Wallet>,
+    pub(crate) key_audit: Option<Arc<Mutex<KeyAuditLog>>>,
+    /// Submitted transactions, admitted by the event loop.
+    pub(crate) tx_submissions: tokio::sync::mpsc::UnboundedSender<TxSubmission>,
+    /// Gates the `/v1/admin/*` routes; `None` disables them.
+    pub(crate) admin_token: Option<AdminToken>,
+}
+
+/// Parse a hex address as the API accepts it: 64 hex characters, with or
+/// without the SDK's `axm1` prefix.
+pub(crate) fn parse_address(address: &str) -> Option<[u8; 32]> {
+    let address = address.strip_prefix("axm1").unwrap_or(address);
+    hex::decode(address).ok()?.try_into().ok()
+}
+
+// ---------------------------------------------------------------------------
+// Warp rejection handling for rate-limiting
+// ---------------------------------------------------------------------------
+
+#[derive(Debug)]
+pub(crate) struct TooManyRequests;
+impl warp::reject::Reject for TooManyRequests {}
+
+#[derive(Debug)]
+pub(crate) struct InvalidAddress;
+impl warp::reject::Reject for InvalidAddress {}
+
+#[derive(Debug)]
+pub(crate) struct InvalidTxHash;
+impl warp::reject::Reject for InvalidTxHash {}
+
+/// A transaction id that is neither a 512-bit id nor a legacy TXID.
+#[derive(Debug)]
+pub(crate) struct InvalidTxId;
+impl warp::reject::Reject for InvalidTxId {}
+
+#[derive(Debug)]
+pub(crate) struct InvalidImageId;
+impl warp::reject::Reject for InvalidImageId {}
+
+#[derive(Debug)]
+pub(crate) struct InvalidCursor;
+impl warp::reject::Reject for InvalidCursor {}
+
+#[derive(Debug)]
+pub(crate) struct AdminUnauthorized;
+impl warp::reject::Reject for AdminUnauthorized {}
+
+pub(crate) async fn handle_rejection(err: warp::Rejection) -> Result<Box<dyn warp::Reply>, std::convert::Infallible> {
+    if err.find::<TooManyRequests>().is_some() {
+        let body = warp::reply::json(&serde_json::json!({
+            "error": "Too Many Requests",
+            "code": ErrorCode::RateLimited,
+            "retry_after_secs": API_RATE_LIMIT_WINDOW_SECS
+        }));
+        let with_status = warp::reply::with_status(body, warp::http::StatusCode::TOO_MANY_REQUESTS);
+        Ok(Box::new(warp::reply::with_header(with_status, "Retry-After", API_RATE_LIMIT_WINDOW_SECS.to_string())))
+    } else if err.find::<InvalidAddress>().is_some() {
+        let body = warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, "Invalid address: expected 64 hex characters"));
+        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::BAD_REQUEST)))
+    } else if err.find::<InvalidTxHash>().is_some() {
+        let body = warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, "Invalid transaction hash: expected 64 hex characters"));
+        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::BAD_REQUEST)))
+    } else if err.find::<InvalidTxId>().is_some() {
+        let body = warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, "Invalid transaction hash: expected 64 or 128 hex characters"));
+        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::BAD_REQUEST)))
+    } else if err.find::<InvalidImageId>().is_some() {
+        let body = warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, "Invalid image ID: expected 64 hex characters"));
+        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::BAD_REQUEST)))
+    } else if err.find::<AdminUnauthorized>().is_some() {
+        let message = format!("missing or wrong bearer token (see {})", ADMIN_TOKEN_FILE);
+        let body = warp::reply::json(&ErrorBody::new(ErrorCode::Unauthorized, message));
+        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::UNAUTHORIZED)))
+    } else if err.find::<InvalidCursor>().is_some() {
+        let body = warp::reply::json(&ErrorBody::new(ErrorCode::InvalidRequest, "Invalid cursor: pass back a next_cursor from an earlier page"));
+        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::BAD_REQUEST)))
+    } else {
+        let body = warp::reply::json(&ErrorBody::new(ErrorCode::NotFound, "Not Found"));
+        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::NOT_FOUND)))
+    }
+}
@@ -0,0 +1,325 @@
+// src/api/transactions.rs - Transaction and account routes
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use warp::Filter;
+use axiom_core::types::HashRef;
+use axiom_core::pending_index::DEFAULT_TOP_PENDING;
+use axiom_core::address_index::{HistoryCursor, HistoryFilter, MAX_HISTORY_PAGE};
+use axiom_core::bridge::{ChainId, FeeOracle};
+use axiom_core::error::{ErrorBody, ErrorCode};
+use axiom_core::wallet_analytics;
+use axiom_core::transaction::Transaction;
+use crate::{lock_or_recover, unix_now};
+use super::{
+    ApiRoutes, ApiContext, parse_address, TooManyRequests, InvalidAddress, InvalidTxHash, InvalidTxId, InvalidCursor,
+};
+
+/// Query parameters for `/v1/mempool`.
+#[derive(serde::Deserialize)]
+struct MempoolQuery {
+    top: Option<usize>,
+}
+
+/// Query parameters for `/v1/bridge/quote`.
+#[derive(serde::Deserialize)]
+struct BridgeQuoteQuery {
+    amount: u64,
+    from: ChainId,
+    to: ChainId,
+}
+
+/// Query parameters for `/v1/account/<address>/txs`.
+#[derive(serde::Deserialize)]
+struct AccountTxsQuery {
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+/// Query parameters for `/v1/account/<address>/history`.
+#[derive(serde::Deserialize)]
+struct AccountHistoryQuery {
+    cursor: Option<String>,
+    limit: Option<usize>,
+    #[serde(default)]
+    direction: HistoryFilter,
+}
+
+/// Page size cap for `/v1/account/<address>/txs`.
+const MAX_ACCOUNT_TXS_PER_PAGE: usize = 100;
+
+/// Mempool, transaction submission and bridge fee quotes.
+pub(crate) fn transaction_routes(ctx: &ApiContext) -> ApiRoutes {
+    // Mempool: size, fee-rate histogram and best-paying transactions,
+    // plus single lookups so explorers can show a payment as pending.
+    let pending_index_api = Arc::clone(&ctx.pending_index);
+    let rate_limiter_mempool = Arc::clone(&ctx.rate_limiter);
+    let mempool_route = warp::path!("v1" / "mempool")
+        .and(warp::get())
+        .and(warp::query::<MempoolQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |query: MempoolQuery, addr: Option<SocketAddr>| {
+            let index = Arc::clone(&pending_index_api);
+            let limiter = Arc::clone(&rate_limiter_mempool);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let summary = lock_or_recover(&index).summary(query.top.unwrap_or(DEFAULT_TOP_PENDING));
+                Ok::<_, warp::Rejection>(warp::reply::json(&summary))
+            }
+        });
+
+    let pending_index_tx_api = Arc::clone(&ctx.pending_index);
+    let rate_limiter_pending = Arc::clone(&ctx.rate_limiter);
+    let pending_tx_route = warp::path!("v1" / "mempool" / String)
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |tx_hash: String, addr: Option<SocketAddr>| {
+            let index = Arc::clone(&pending_index_tx_api);
+            let limiter = Arc::clone(&rate_limiter_pending);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let tx_hash: [u8; 32] = hex::decode(&tx_hash)
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| warp::reject::custom(InvalidTxHash))?;
+                let pending = lock_or_recover(&index).get(&hex::encode(tx_hash)).cloned().ok_or_else(warp::reject::not_found)?;
+                Ok::<_, warp::Rejection>(warp::reply::json(&pending))
+            }
+        });
+
+    // Transaction submission. Refusals carry the admission error's code:
+    // expired, underpriced replacement, double spend, sender limits...
+    let rate_limiter_submit = Arc::clone(&ctx.rate_limiter);
+    let tx_submissions = ctx.tx_submissions.clone();
+    let submit_tx_route = warp::path!("v1" / "transactions")
+        .and(warp::post())
+        .and(warp::addr::remote())
+        .and(warp::body::content_length_limit(128 * 1024))
+        .and(warp::body::json())
+        .and_then(move |addr: Option<SocketAddr>, tx: Transaction| {
+            let limiter = Arc::clone(&rate_limiter_submit);
+            let submissions = tx_submissions.clone();
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let (reply, verdict) = tokio::sync::oneshot::channel();
+                let shutting_down = || ErrorBody::new(ErrorCode::Internal, "node is shutting down");
+                let verdict = match submissions.send((tx, reply)) {
+                    Ok(()) => verdict.await.unwrap_or_else(|_| Err(shutting_down())),
+                    Err(_) => Err(shutting_down()),
+                };
+                let reply = match verdict {
+                    Ok(accepted) => warp::reply::with_status(
+                        warp::reply::json(&accepted),
+                        warp::http::StatusCode::OK,
+                    ),
+                    Err(body) => {
+                        let status = match body.code {
+                            ErrorCode::DuplicateTransaction | ErrorCode::DoubleSpend
+                            | ErrorCode::ReplacementUnderpriced => warp::http::StatusCode::CONFLICT,
+                            ErrorCode::SenderLimitReached => warp::http::StatusCode::TOO_MANY_REQUESTS,
+                            ErrorCode::OracleFailure | ErrorCode::Internal => warp::http::StatusCode::SERVICE_UNAVAILABLE,
+                            _ => warp::http::StatusCode::BAD_REQUEST,
+                        };
+                        warp::reply::with_status(warp::reply::json(&body), status)
+                    }
+                };
+                Ok::<_, warp::Rejection>(reply)
+            }
+        });
+
+    // Bridge fee quotes, priced from the destination chain's gas now.
+    let fee_oracle = Arc::new(FeeOracle::new());
+    let rate_limiter_quote = Arc::clone(&ctx.rate_limiter);
+    let bridge_quote_route = warp::path!("v1" / "bridge" / "quote")
+        .and(warp::get())
+        .and(warp::query::<BridgeQuoteQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |query: BridgeQuoteQuery, addr: Option<SocketAddr>| {
+            let fees = Arc::clone(&fee_oracle);
+            let limiter = Arc::clone(&rate_limiter_quote);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let reply = match fees.quote(query.amount, &query.from, &query.to, unix_now()).await {
+                    Ok(quote) => warp::reply::with_status(warp::reply::json(&quote), warp::http::StatusCode::OK),
+                    Err(e) => {
+                        let status = match e.code() {
+                            ErrorCode::NetworkError | ErrorCode::InvalidConfig | ErrorCode::NotFound => warp::http::StatusCode::SERVICE_UNAVAILABLE,
+                            _ => warp::http::StatusCode::BAD_REQUEST,
+                        };
+                        warp::reply::with_status(warp::reply::json(&ErrorBody::from(&e)), status)
+                    }
+                };
+                Ok::<_, warp::Rejection>(reply)
+            }
+        });
+
+    mempool_route
+        .or(pending_tx_route)
+        .or(submit_tx_route)
+        .or(bridge_quote_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
+
+/// Balances, histories, wallet analytics and receipts by address or TXID.
+pub(crate) fn account_routes(ctx: &ApiContext) -> ApiRoutes {
+    // Wallet analytics: first/last activity, mining vs transfer income,
+    // per-era rewards and coin age for one address.
+    let address_index_api = Arc::clone(&ctx.address_index);
+    let rate_limiter_wallet = Arc::clone(&ctx.rate_limiter);
+    let wallet_analytics_route = warp::path!("v1" / "wallet" / String / "analytics")
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |address: String, addr: Option<SocketAddr>| {
+            let index = Arc::clone(&address_index_api);
+            let limiter = Arc::clone(&rate_limiter_wallet);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let address: [u8; 32] = hex::decode(&address)
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| warp::reject::custom(InvalidAddress))?;
+                let index = lock_or_recover(&index);
+                let analytics = wallet_analytics::analyze(&address, index.history(&address), unix_now());
+                Ok::<_, warp::Rejection>(warp::reply::json(&analytics))
+            }
+        });
+
+    // Transaction receipts: block position, fee and a Merkle proof
+    // against the block's tx root, checkable with the SDK.
+    let receipt_index_api = Arc::clone(&ctx.receipt_index);
+    let rate_limiter_receipt = Arc::clone(&ctx.rate_limiter);
+    let receipt_route = warp::path!("v1" / "tx" / String / "receipt")
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |tx_hash: String, addr: Option<SocketAddr>| {
+            let index = Arc::clone(&receipt_index_api);
+            let limiter = Arc::clone(&rate_limiter_receipt);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                // The 512-bit id, or the legacy 32-byte TXID.
+                let tx_id: HashRef = tx_hash.parse().map_err(|_| warp::reject::custom(InvalidTxId))?;
+                let receipt = lock_or_recover(&index).lookup(&tx_id).ok_or_else(warp::reject::not_found)?;
+                Ok::<_, warp::Rejection>(warp::reply::json(&receipt))
+            }
+        });
+
+    // Account state: balance and nonce without scanning the chain, and
+    // the account's confirmed transactions, newest first.
+    let account_state_api = Arc::clone(&ctx.account_state);
+    let pending_index_account_api = Arc::clone(&ctx.pending_index);
+    let rate_limiter_account = Arc::clone(&ctx.rate_limiter);
+    let account_route = warp::path!("v1" / "account" / String)
+        .and(warp::get())
+        .and(warp::addr::remote())
+        .and_then(move |address: String, addr: Option<SocketAddr>| {
+            let state = Arc::clone(&account_state_api);
+            let pending = Arc::clone(&pending_index_account_api);
+            let limiter = Arc::clone(&rate_limiter_account);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let address = parse_address(&address).ok_or_else(|| warp::reject::custom(InvalidAddress))?;
+                let account = lock_or_recover(&state).account(&address);
+                let pending_nonce = lock_or_recover(&pending).pending_nonce(&address, account.nonce);
+                Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                    "address": hex::encode(address),
+                    "balance": account.balance,
+                    "nonce": account.nonce,
+                    "pending_nonce": pending_nonce,
+                    "tx_count": account.tx_count,
+                })))
+            }
+        });
+
+    let account_txs_api = Arc::clone(&ctx.account_state);
+    let rate_limiter_account_txs = Arc::clone(&ctx.rate_limiter);
+    let account_txs_route = warp::path!("v1" / "account" / String / "txs")
+        .and(warp::get())
+        .and(warp::query::<AccountTxsQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |address: String, query: AccountTxsQuery, addr: Option<SocketAddr>| {
+            let state = Arc::clone(&account_txs_api);
+            let limiter = Arc::clone(&rate_limiter_account_txs);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let address = parse_address(&address).ok_or_else(|| warp::reject::custom(InvalidAddress))?;
+                let state = lock_or_recover(&state);
+                let history = state.history(&address);
+                let txs: Vec<serde_json::Value> = history
+                    .iter()
+                    .rev()
+                    .skip(query.offset.unwrap_or(0))
+                    .take(query.limit.unwrap_or(MAX_ACCOUNT_TXS_PER_PAGE).clamp(1, MAX_ACCOUNT_TXS_PER_PAGE))
+                    .map(|tx| serde_json::json!({
+                        "hash": hex::encode(tx.hash),
+                        "height": tx.height,
+                        "position": tx.position,
+                    }))
+                    .collect();
+                Ok::<_, warp::Rejection>(warp::reply::json(&serde_json::json!({
+                    "total": history.len(),
+                    "txs": txs,
+                })))
+            }
+        });
+
+    // Address history from the address index, newest first, paged by
+    // cursor and filterable by direction, with confirmation counts.
+    let history_index_api = Arc::clone(&ctx.address_index);
+    let rate_limiter_history = Arc::clone(&ctx.rate_limiter);
+    let account_history_route = warp::path!("v1" / "account" / String / "history")
+        .and(warp::get())
+        .and(warp::query::<AccountHistoryQuery>())
+        .and(warp::addr::remote())
+        .and_then(move |address: String, query: AccountHistoryQuery, addr: Option<SocketAddr>| {
+            let index = Arc::clone(&history_index_api);
+            let limiter = Arc::clone(&rate_limiter_history);
+            async move {
+                let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
+                if limiter.check_key(&peer_addr).is_err() {
+                    return Err(warp::reject::custom(TooManyRequests));
+                }
+                let address = parse_address(&address).ok_or_else(|| warp::reject::custom(InvalidAddress))?;
+                let cursor = query
+                    .cursor
+                    .map(|c| c.parse::<HistoryCursor>())
+                    .transpose()
+                    .map_err(|_| warp::reject::custom(InvalidCursor))?;
+                let limit = query.limit.unwrap_or(MAX_HISTORY_PAGE);
+                let page = lock_or_recover(&index).page(&address, cursor, query.direction, limit);
+                Ok::<_, warp::Rejection>(warp::reply::json(&page))
+            }
+        });
+
+    wallet_analytics_route
+        .or(receipt_route)
+        .or(account_route)
+        .or(account_txs_route)
+        .or(account_history_route)
+        .map(warp::Reply::into_response)
+        .boxed()
+}
@@ -0,0 +1,320 @@
+// src/archive.rs - Bulk block history archive
+//
+// Archive servers cut the chain into fixed-size ranges of
+// `ARCHIVE_CHUNK_BLOCKS` blocks, store each completed range as a
+// gzip-compressed bincode file and describe them in a JSON manifest with
+// the BLAKE3 hash of every file plus the hashes of its first and last
+// block. A new node downloads the chunks over plain HTTP (ranges let an
+// interrupted download resume), checks each one against the manifest and
+// the previous chunk's last hash, and then replays the result through
+// `Timechain::from_saved_blocks`, so the archive server is never trusted
+// for consensus.
+//
+// Only complete ranges are archived; the tip is left to gossip sync.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::block::Block;
+
+/// Blocks per archive chunk.
+pub const ARCHIVE_CHUNK_BLOCKS: u64 = 1_000;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("archive I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("archive is for a different chain (genesis {0})")]
+    WrongGenesis(String),
+    #[error("chunk {0} is not listed in the manifest")]
+    UnknownChunk(u64),
+    #[error("chunk {index} content hash mismatch")]
+    HashMismatch { index: u64 },
+    #[error("chunk {index} is malformed: {reason}")]
+    Malformed { index: u64, reason: String },
+    #[error("chunk {index} does not link to the previous chunk")]
+    BrokenLink { index: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkEntry {
+    pub index: u64,
+    /// First height in the chunk; the chunk covers `from..from + ARCHIVE_CHUNK_BLOCKS`.
+    pub from: u64,
+    pub count: u64,
+    /// Hex hash of the first and last block.
+    pub first_hash: String,
+    pub last_hash: String,
+    /// Size and BLAKE3 hash (hex) of the compressed file.
+    pub size: u64,
+    pub blake3: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub chain_id: u64,
+    /// Hex hash of block 0.
+    pub genesis_hash: String,
+    pub chunk_blocks: u64,
+    pub chunks: Vec<ChunkEntry>,
+}
+
+impl ArchiveManifest {
+    fn new(genesis_hash: String) -> Self {
+        Self {
+            chain_id: crate::AXIOM_CHAIN_ID,
+            genesis_hash,
+            chunk_blocks: ARCHIVE_CHUNK_BLOCKS,
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Number of blocks covered by the archive.
+    pub fn archived_height(&self) -> u64 {
+        self.chunks.last().map_or(0, |c| c.from + c.count)
+    }
+
+    /// Check a downloaded chunk against its manifest entry and the last
+    /// block hash of the chunk before it, returning the decoded blocks.
+    pub fn verify_chunk(&self, index: u64, data: &[u8], prev_last_hash: Option<&str>) -> Result<Vec<Block>, ArchiveError> {
+        let entry = self.chunks.get(index as usize).ok_or(ArchiveError::UnknownChunk(index))?;
+        if data.len() as u64 != entry.size || blake3::hash(data).to_hex().as_str() != entry.blake3 {
+            return Err(ArchiveError::HashMismatch { index });
+        }
+        let blocks = decode_chunk(data).map_err(|e| ArchiveError::Malformed { index, reason: e.to_string() })?;
+        let malformed = |reason: &str| ArchiveError::Malformed { index, reason: reason.to_string() };
+
+        if blocks.len() as u64 != entry.count {
+            return Err(malformed("wrong block count"));
+        }
+        let first = blocks.first().ok_or_else(|| malformed("empty chunk"))?;
+        if hex::encode(first.legacy_hash()) != entry.first_hash {
+            return Err(malformed("first block hash differs from manifest"));
+        }
+        if blocks.windows(2).any(|w| w[1].parent != w[0].legacy_hash()) {
+            return Err(ArchiveError::BrokenLink { index });
+        }
+        if blocks.last().map(|b| hex::encode(b.legacy_hash())).as_deref() != Some(entry.last_hash.as_str()) {
+            return Err(malformed("last block hash differs from manifest"));
+        }
+        match prev_last_hash {
+            Some(prev) if hex::encode(first.parent) != prev => return Err(ArchiveError::BrokenLink { index }),
+            None if index == 0 && entry.first_hash != self.genesis_hash => {
+                return Err(ArchiveError::WrongGenesis(entry.first_hash.clone()))
+            }
+            _ => {}
+        }
+        Ok(blocks)
+    }
+}
+
+pub fn chunk_file_name(index: u64) -> String {
+    format!("chunk-{:06}.bin.gz", index)
+}
+
+fn encode_chunk(blocks: &[Block]) -> io::Result<Vec<u8>> {
+    let raw = bincode::serialize(blocks).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&raw)?;
+    encoder.finish()
+}
+
+fn decode_chunk(data: &[u8]) -> io::Result<Vec<Block>> {
+    let mut raw = Vec::new();
+    // Cap the inflated size so a hostile chunk cannot exhaust memory.
+    GzDecoder::new(data).take(256 * 1024 * 1024).read_to_end(&mut raw)?;
+    bincode::deserialize(&raw).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Server side: the chunk files and manifest in one directory.
+#[derive(Debug)]
+pub struct BlockArchive {
+    dir: PathBuf,
+    manifest: ArchiveManifest,
+}
+
+impl BlockArchive {
+    /// Open the archive in `dir`, creating it if needed. A manifest for a
+    /// different genesis is discarded and the archive rebuilt.
+    pub fn open(dir: impl AsRef<Path>, genesis: &Block) -> Result<Self, ArchiveError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let genesis_hash = hex::encode(genesis.legacy_hash());
+        let manifest = fs::read_to_string(dir.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str::<ArchiveManifest>(&json).ok())
+            .filter(|m| m.genesis_hash == genesis_hash && m.chunk_blocks == ARCHIVE_CHUNK_BLOCKS)
+            .unwrap_or_else(|| ArchiveManifest::new(genesis_hash));
+        Ok(Self { dir, manifest })
+    }
+
+    pub fn manifest(&self) -> &ArchiveManifest {
+        &self.manifest
+    }
+
+    pub fn chunk_path(&self, index: u64) -> Option<PathBuf> {
+        self.manifest
+            .chunks
+            .get(index as usize)
+            .map(|_| self.dir.join(chunk_file_name(index)))
+    }
+
+    /// Archive every complete range of `blocks` not archived yet. Chunks
+    /// whose last block no longer matches the chain (a reorg deeper than
+    /// a chunk) are rewritten. Returns the number of chunks written.
+    pub fn update(&mut self, blocks: &[Block]) -> Result<usize, ArchiveError> {
+        let keep = self
+            .manifest
+            .chunks
+            .iter()
+            .take_while(|c| {
+                blocks
+                    .get((c.from + c.count - 1) as usize)
+                    .is_some_and(|b| hex::encode(b.legacy_hash()) == c.last_hash)
+            })
+            .count();
+        let dropped = self.manifest.chunks.len() - keep;
+        self.manifest.chunks.truncate(keep);
+
+        let mut written = 0;
+        loop {
+            let index = self.manifest.chunks.len() as u64;
+            let from = index * ARCHIVE_CHUNK_BLOCKS;
+            let to = from + ARCHIVE_CHUNK_BLOCKS;
+            if (blocks.len() as u64) < to {
+                break;
+            }
+            let range = &blocks[from as usize..to as usize];
+            let data = encode_chunk(range)?;
+            write_atomic(&self.dir.join(chunk_file_name(index)), &data)?;
+            self.manifest.chunks.push(ChunkEntry {
+                index,
+                from,
+                count: ARCHIVE_CHUNK_BLOCKS,
+                first_hash: hex::encode(range[0].legacy_hash()),
+                last_hash: hex::encode(range[range.len() - 1].legacy_hash()),
+                size: data.len() as u64,
+                blake3: blake3::hash(&data).to_hex().to_string(),
+            });
+            written += 1;
+        }
+
+        if written > 0 || dropped > 0 {
+            let json = serde_json::to_vec_pretty(&self.manifest)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            write_atomic(&self.dir.join(MANIFEST_FILE), &json)?;
+        }
+        Ok(written)
+    }
+}
+
+/// Client side: verify and decode the chunks in `dir` (fetched with any
+/// HTTP client alongside `manifest.json`), stopping at the first missing
+/// chunk. The result still has to pass `Timechain::from_saved_blocks`.
+pub fn import_dir(dir: impl AsRef<Path>, genesis: &Block) -> Result<Vec<Block>, ArchiveError> {
+    let dir = dir.as_ref();
+    let json = fs::read_to_string(dir.join(MANIFEST_FILE))?;
+    let manifest: ArchiveManifest = serde_json::from_str(&json)
+        .map_err(|e| ArchiveError::Malformed { index: 0, reason: format!("manifest: {}", e) })?;
+    if manifest.genesis_hash != hex::encode(genesis.legacy_hash()) {
+        return Err(ArchiveError::WrongGenesis(manifest.genesis_hash));
+    }
+
+    let mut blocks: Vec<Block> = Vec::new();
+    for entry in &manifest.chunks {
+        let data = match fs::read(dir.join(chunk_file_name(entry.index))) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => break,
+            Err(e) => return Err(e.into()),
+        };
+        let prev = blocks.last().map(|b| hex::encode(b.legacy_hash()));
+        blocks.extend(manifest.verify_chunk(entry.index, &data, prev.as_deref())?);
+    }
+    Ok(blocks)
+}
+
+/// Parse a single-range HTTP `Range` header against a body of `len`
+/// bytes, returning the inclusive byte range to serve.
+pub fn parse_range(header: &str, len: u64) -> Option<(u64, u64)> {
+    let spec = header.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') || len == 0 {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let n: u64 = suffix.parse().ok()?;
+            (len.saturating_sub(n), len - 1)
+        }
+        (start, "") => (start.parse().ok()?, len - 1),
+        (start, end) => (start.parse().ok()?, end.parse::<u64>().ok()?.min(len - 1)),
+    };
+    (start <= end && start < len).then_some((start, end))
+}
+
+fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data)?;
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(len: u64) -> Vec<Block> {
+        let mut blocks: Vec<Block> = Vec::new();
+        for slot in 0..len {
+            let parent = blocks.last().map_or([0u8; 32], |b| b.legacy_hash());
+            blocks.push(Block {
+                timestamp: 1_000 + slot,
+                miner: [1u8; 32],
+                nonce: slot,
+                ..Block::test(parent, slot)
+            });
+        }
+        blocks
+    }
+
+    #[test]
+    fn test_archive_roundtrip_and_tamper_detection() {
+        let dir = std::env::temp_dir().join(format!("axiom_test_archive_{}", std::process::id()));
+        let blocks = chain(2 * ARCHIVE_CHUNK_BLOCKS + 5);
+
+        let mut archive = BlockArchive::open(&dir, &blocks[0]).unwrap();
+        assert_eq!(archive.update(&blocks).unwrap(), 2);
+        assert_eq!(archive.update(&blocks).unwrap(), 0);
+        assert_eq!(archive.manifest().archived_height(), 2 * ARCHIVE_CHUNK_BLOCKS);
+
+        let imported = import_dir(&dir, &blocks[0]).unwrap();
+        assert_eq!(imported[..], blocks[..2 * ARCHIVE_CHUNK_BLOCKS as usize]);
+
+        let path = archive.chunk_path(1).unwrap();
+        let mut data = fs::read(&path).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        fs::write(&path, &data).unwrap();
+        let result = import_dir(&dir, &blocks[0]);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(matches!(result, Err(ArchiveError::HashMismatch { index: 1 })));
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=500-", 1000), Some((500, 999)));
+        assert_eq!(parse_range("bytes=-100", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=900-5000", 1000), Some((900, 999)));
+        assert_eq!(parse_range("bytes=1000-", 1000), None);
+        assert_eq!(parse_range("bytes=0-1,5-6", 1000), None);
+    }
+}
@@ -0,0 +1,88 @@
+// axiom-audit: check, export and replay the guardian's decision audit log.
+//
+//   axiom-audit verify [--log axiom_guardian_audit.jsonl] [--model FILE]
+//   axiom-audit export [--log axiom_guardian_audit.jsonl] [--peer ID] [--from T] [--to T]
+//
+// `verify` checks the log's hash chain and, given a model file (the
+// bincode weights of weights.bin or a guardian-model-v*.bin checkpoint),
+// replays every decision made with those weights and compares the proofs.
+// `export` prints the records, oldest first, one JSON object per line.
+
+use axiom_core::guardian::decision_audit::{read_log, replay, GUARDIAN_AUDIT_PATH};
+use axiom_core::neural_guardian::NeuralNetwork;
+
+fn usage() -> ! {
+    eprintln!("Usage: axiom-audit verify [--log FILE] [--model FILE]");
+    eprintln!("       axiom-audit export [--log FILE] [--peer ID] [--from T] [--to T]");
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = args.next().unwrap_or_else(|| usage());
+    let mut log = std::path::PathBuf::from(GUARDIAN_AUDIT_PATH);
+    let mut model = None;
+    let mut peer = None;
+    let mut from = 0u64;
+    let mut to = u64::MAX;
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        let time = |value: &str| value.parse::<u64>().unwrap_or_else(|_| usage());
+        match flag.as_str() {
+            "--log" => log = value.into(),
+            "--model" if command == "verify" => model = Some(std::path::PathBuf::from(value)),
+            "--peer" if command == "export" => peer = Some(value),
+            "--from" if command == "export" => from = time(&value),
+            "--to" if command == "export" => to = time(&value),
+            _ => usage(),
+        }
+    }
+
+    let records = match read_log(&log) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("❌ {}: {}", log.display(), e);
+            std::process::exit(1);
+        }
+    };
+
+    match command.as_str() {
+        "verify" => {
+            println!("✅ {} records, hash chain intact.", records.len());
+            let Some(model_path) = model else { return };
+            let model: NeuralNetwork = match std::fs::read(&model_path)
+                .map_err(|e| e.to_string())
+                .and_then(|data| bincode::deserialize(&data).map_err(|e| e.to_string()))
+            {
+                Ok(model) => model,
+                Err(e) => {
+                    eprintln!("❌ Cannot load model {}: {}", model_path.display(), e);
+                    std::process::exit(1);
+                }
+            };
+            let report = replay(&records, model);
+            println!("   {} decisions replayed to the same proof with {}.", report.verified, model_path.display());
+            println!("   {} decisions were made with other weights.", report.other_model);
+            if !report.mismatched.is_empty() {
+                eprintln!("❌ {} decisions do not replay: records {:?}", report.mismatched.len(), report.mismatched);
+                std::process::exit(1);
+            }
+        }
+        "export" => {
+            for record in records
+                .iter()
+                .filter(|r| r.timestamp >= from && r.timestamp < to)
+                .filter(|r| peer.as_ref().is_none_or(|peer| &r.peer_id == peer))
+            {
+                match serde_json::to_string(record) {
+                    Ok(line) => println!("{}", line),
+                    Err(e) => {
+                        eprintln!("❌ Cannot encode record {}: {}", record.seq, e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        _ => usage(),
+    }
+}
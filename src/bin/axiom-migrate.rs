@@ -0,0 +1,67 @@
+// axiom-migrate: copy a legacy axiom_chain.dat into a database backend.
+//
+//   axiom-migrate <sled|rocksdb|file> [--from axiom_chain.dat] [--data-dir DIR]
+//
+// The data directory defaults to [storage] data_dir from axiom.toml. After
+// migrating, start the node with AXIOM_STORAGE_BACKEND (or [storage]
+// backend) set to the same backend. The legacy file is left untouched.
+
+use axiom_core::config::{AxiomConfig, StorageBackend};
+use axiom_core::storage::{migrate_legacy, open_store};
+
+fn usage() -> ! {
+    eprintln!("Usage: axiom-migrate <sled|rocksdb|file> [--from axiom_chain.dat] [--data-dir DIR]");
+    std::process::exit(2);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let backend: StorageBackend = match args.next().map(|b| b.parse()) {
+        Some(Ok(backend)) => backend,
+        Some(Err(e)) => {
+            eprintln!("❌ {}", e);
+            usage();
+        }
+        None => usage(),
+    };
+
+    let mut config = AxiomConfig::load().map(|cfg| cfg.storage).unwrap_or_default();
+    let mut from = std::path::PathBuf::from("axiom_chain.dat");
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--from" => from = value.into(),
+            "--data-dir" => config.data_dir = value.into(),
+            _ => usage(),
+        }
+    }
+
+    if let Err(e) = std::fs::create_dir_all(&config.data_dir) {
+        eprintln!("❌ Cannot create {}: {}", config.data_dir.display(), e);
+        std::process::exit(1);
+    }
+    let mut store = match open_store(backend, &config) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("❌ Cannot open {:?} store in {}: {}", backend, config.data_dir.display(), e);
+            std::process::exit(1);
+        }
+    };
+    if store.block_count().unwrap_or(0) > 0 {
+        eprintln!("❌ The {:?} store in {} already holds a chain; refusing to overwrite it.",
+            backend, config.data_dir.display());
+        std::process::exit(1);
+    }
+
+    println!("📦 Migrating {} → {:?} store in {}", from.display(), backend, config.data_dir.display());
+    match migrate_legacy(&from, store.as_mut()) {
+        Ok(blocks) => {
+            println!("✅ Migrated and verified {} blocks plus the account state.", blocks);
+            println!("   Start the node with AXIOM_STORAGE_BACKEND={} to use it.", format!("{:?}", backend).to_lowercase());
+        }
+        Err(e) => {
+            eprintln!("❌ Migration failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+//! Axiom Bridge Relayer
+//!
+//! Watches the bridge contracts for lock events, tracks their source-chain
+//! confirmations and mints on the destination chain once they are deep
+//! enough. Jobs and the last scanned block of each chain live in files on
+//! disk (bridge_relayer.json and bridge_cursor.json by default), so a
+//! restarted relayer resumes where it stopped.
+//!
+//! A job is minted only once a quorum of the bridge oracles listed in the
+//! oracle set file (bridge_oracles.json by default) has attested its lock.
+//! With `--oracle-key` the relayer attests ready locks itself. Mints on EVM
+//! chains are sent from the secp256k1 key given with `--evm-key`, on Solana
+//! from the ed25519 key given with `--solana-key`, and on the Cosmos zone
+//! from the secp256k1 key and account given with `--cosmos-key` and
+//! `--cosmos-address`.
+//!
+//! API (loopback by default):
+//!   GET  /v1/relayer/status                 job counts by state
+//!   GET  /v1/relayer/jobs                   every job
+//!   GET  /v1/relayer/jobs/<id>              one job by hex bridge ID
+//!   GET  /v1/relayer/oracles                the current oracle set
+//!   POST /v1/relayer/jobs/<id>/refund       refund a failed job's lock
+//!   POST /v1/relayer/attestations           submit a LockAttestation
+//!   POST /v1/relayer/oracles/updates        apply an approved OracleSetUpdate
+//!
+//! Usage:
+//!   axiom-relayer [--state <file>] [--cursor <file>] [--oracles <file>]
+//!                 [--oracle-key <file>] [--evm-key <file>] [--solana-key <file>]
+//!                 [--cosmos-key <file> --cosmos-address <addr>]
+//!                 [--listen <addr:port>] [--interval <secs>]
+
+use std::net::SocketAddr;
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axiom_core::bridge::attestation::ORACLE_SET_FILE;
+use axiom_core::bridge::cosmos::{CosmosAdapter, CosmosSigner};
+use axiom_core::bridge::evm::EvmSigner;
+use axiom_core::bridge::relayer::{JobQueue, QueueSummary, Relayer, LOCK_CURSOR_FILE, RELAYER_STATE_FILE};
+use axiom_core::bridge::solana::{base58_encode, SolanaAdapter};
+use axiom_core::bridge::{BridgeOracle, LockAttestation, OracleSet, OracleSetUpdate};
+use axiom_core::error::{AxiomError, ErrorBody, ErrorCode};
+use ed25519_dalek::SigningKey;
+use tokio::sync::{mpsc, oneshot};
+use warp::http::StatusCode;
+use warp::Filter;
+
+const DEFAULT_LISTEN: &str = "127.0.0.1:3040";
+const DEFAULT_INTERVAL_SECS: u64 = 15;
+
+struct Options {
+    state: String,
+    cursor: String,
+    oracles: String,
+    oracle_key: Option<String>,
+    evm_key: Option<String>,
+    solana_key: Option<String>,
+    cosmos_key: Option<String>,
+    cosmos_address: Option<String>,
+    listen: SocketAddr,
+    interval: Duration,
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        state: RELAYER_STATE_FILE.to_string(),
+        cursor: LOCK_CURSOR_FILE.to_string(),
+        oracles: ORACLE_SET_FILE.to_string(),
+        oracle_key: None,
+        evm_key: None,
+        solana_key: None,
+        cosmos_key: None,
+        cosmos_address: None,
+        listen: DEFAULT_LISTEN.parse().expect("valid default address"),
+        interval: Duration::from_secs(DEFAULT_INTERVAL_SECS),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+        match flag.as_str() {
+            "--state" => options.state = value()?,
+            "--cursor" => options.cursor = value()?,
+            "--oracles" => options.oracles = value()?,
+            "--oracle-key" => options.oracle_key = Some(value()?),
+            "--evm-key" => options.evm_key = Some(value()?),
+            "--solana-key" => options.solana_key = Some(value()?),
+            "--cosmos-key" => options.cosmos_key = Some(value()?),
+            "--cosmos-address" => options.cosmos_address = Some(value()?),
+            "--listen" => options.listen = value()?.parse().map_err(|e| format!("--listen: {}", e))?,
+            "--interval" => {
+                let secs: u64 = value()?.parse().map_err(|e| format!("--interval: {}", e))?;
+                options.interval = Duration::from_secs(secs.max(1));
+            }
+            "-h" | "--help" => {
+                println!(
+                    "Usage: axiom-relayer [--state <file>] [--cursor <file>] [--oracles <file>] \
+                     [--oracle-key <file>] [--evm-key <file>] [--solana-key <file>] \
+                     [--cosmos-key <file> --cosmos-address <addr>] [--listen <addr:port>] [--interval <secs>]"
+                );
+                process::exit(0);
+            }
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+    if options.cosmos_key.is_some() != options.cosmos_address.is_some() {
+        return Err("--cosmos-key and --cosmos-address go together".into());
+    }
+    Ok(options)
+}
+
+/// Read a hex-encoded 32-byte Ed25519 secret.
+fn read_ed25519_key(path: &str) -> Result<SigningKey, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let bytes: [u8; 32] = hex::decode(text.trim())
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| format!("{}: expected 64 hex characters", path))?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+/// Writes from the API, applied by the main loop between passes.
+enum Command {
+    Attest(LockAttestation, oneshot::Sender<Result<bool, AxiomError>>),
+    Refund(String, oneshot::Sender<Result<String, AxiomError>>),
+    UpdateOracles(OracleSetUpdate, oneshot::Sender<Result<OracleSet, AxiomError>>),
+}
+
+fn error_reply(e: &AxiomError) -> warp::reply::WithStatus<warp::reply::Json> {
+    let status = match e.code() {
+        ErrorCode::NotFound => StatusCode::NOT_FOUND,
+        ErrorCode::Unauthorized => StatusCode::FORBIDDEN,
+        _ => StatusCode::BAD_REQUEST,
+    };
+    warp::reply::with_status(warp::reply::json(&ErrorBody::from(e)), status)
+}
+
+async fn submit<T: serde::Serialize>(
+    commands: mpsc::Sender<Command>,
+    command: impl FnOnce(oneshot::Sender<Result<T, AxiomError>>) -> Command,
+) -> Result<warp::reply::WithStatus<warp::reply::Json>, std::convert::Infallible> {
+    let (reply, answer) = oneshot::channel();
+    if commands.send(command(reply)).await.is_err() {
+        return Ok(error_reply(&AxiomError::ThreadError("relayer is shutting down".into())));
+    }
+    Ok(match answer.await {
+        Ok(Ok(value)) => warp::reply::with_status(warp::reply::json(&value), StatusCode::OK),
+        Ok(Err(e)) => error_reply(&e),
+        Err(_) => error_reply(&AxiomError::ThreadError("relayer dropped the request".into())),
+    })
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let options = parse_args().unwrap_or_else(|e| {
+        eprintln!("❌ {}", e);
+        process::exit(2);
+    });
+    let queue = JobQueue::open(&options.state).unwrap_or_else(|e| {
+        eprintln!("❌ Cannot open relay queue {}: {}", options.state, e);
+        process::exit(1);
+    });
+
+    let oracle_set = OracleSet::load(&options.oracles).unwrap_or_else(|e| {
+        eprintln!("❌ Cannot load oracle set {}: {}", options.oracles, e);
+        process::exit(1);
+    });
+
+    println!("🌉 Axiom Bridge Relayer");
+    println!("   Queue:   {} ({} jobs)", options.state, queue.len());
+    println!(
+        "   Oracles: {} ({}-of-{}, epoch {})",
+        options.oracles,
+        oracle_set.threshold,
+        oracle_set.oracles.len(),
+        oracle_set.epoch
+    );
+    println!("   Status:  http://{}/v1/relayer/status", options.listen);
+    if oracle_set.is_empty() {
+        log::warn!("RELAYER: no bridge oracles registered; nothing will be minted");
+    }
+
+    let mut oracle = BridgeOracle::new().with_oracle_set(oracle_set);
+    match &options.evm_key {
+        Some(path) => {
+            let signer = std::fs::read_to_string(path)
+                .map_err(|e| e.to_string())
+                .and_then(|hex| EvmSigner::from_hex(&hex).map_err(|e| e.to_string()))
+                .unwrap_or_else(|e| {
+                    eprintln!("❌ Cannot read EVM key {}: {}", path, e);
+                    process::exit(1);
+                });
+            println!("   EVM:     0x{}", hex::encode(signer.address()));
+            oracle = oracle.with_evm_signer(signer);
+        }
+        None => log::warn!("RELAYER: no --evm-key; mints on EVM chains will fail"),
+    }
+    if let Some(path) = &options.solana_key {
+        let key = read_ed25519_key(path).unwrap_or_else(|e| {
+            eprintln!("❌ Cannot read Solana key {}", e);
+            process::exit(1);
+        });
+        let adapter = SolanaAdapter::from_env().unwrap_or_else(|e| {
+            eprintln!("❌ Invalid Solana configuration: {}", e);
+            process::exit(1);
+        });
+        println!("   Solana:  {}", base58_encode(&key.verifying_key().to_bytes()));
+        oracle = oracle.with_adapter(Arc::new(adapter.with_signer(key)));
+    }
+    if let (Some(path), Some(address)) = (&options.cosmos_key, &options.cosmos_address) {
+        let signer = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|hex| CosmosSigner::from_hex(&hex, address.clone()).map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                eprintln!("❌ Cannot read Cosmos key {}: {}", path, e);
+                process::exit(1);
+            });
+        let adapter = CosmosAdapter::from_env().unwrap_or_else(|e| {
+            eprintln!("❌ Invalid Cosmos configuration: {}", e);
+            process::exit(1);
+        });
+        println!("   Cosmos:  {}", signer.address);
+        oracle = oracle.with_adapter(Arc::new(adapter.with_signer(signer)));
+    }
+    let mut relayer = Relayer::new(oracle, queue)
+        .with_cursor_file(&options.cursor)
+        .unwrap_or_else(|e| {
+            eprintln!("❌ Cannot open lock cursor {}: {}", options.cursor, e);
+            process::exit(1);
+        });
+    if let Some(path) = &options.oracle_key {
+        let key = read_ed25519_key(path).unwrap_or_else(|e| {
+            eprintln!("❌ Cannot read oracle key {}", e);
+            process::exit(1);
+        });
+        let public = key.verifying_key().to_bytes();
+        if !relayer.oracle_set().contains(&public) {
+            log::warn!("RELAYER: oracle key {} is not in the oracle set", hex::encode(public));
+        }
+        relayer = relayer.with_signing_key(key);
+    }
+
+    // The API reads snapshots so a slow RPC call in a pass never blocks it.
+    let snapshot = Arc::new(Mutex::new(relayer.queue().jobs().cloned().collect::<Vec<_>>()));
+    let oracles_snapshot = Arc::new(Mutex::new(relayer.oracle_set().clone()));
+    let (commands, mut inbox) = mpsc::channel::<Command>(64);
+
+    let status_jobs = Arc::clone(&snapshot);
+    let status = warp::path!("v1" / "relayer" / "status").and(warp::get()).map(move || {
+        let jobs = status_jobs.lock().unwrap_or_else(|e| e.into_inner());
+        let summary = QueueSummary::of(jobs.iter(), unix_now());
+        warp::reply::json(&summary)
+    });
+    let list_jobs = Arc::clone(&snapshot);
+    let jobs = warp::path!("v1" / "relayer" / "jobs").and(warp::get()).map(move || {
+        warp::reply::json(&*list_jobs.lock().unwrap_or_else(|e| e.into_inner()))
+    });
+    let one_jobs = Arc::clone(&snapshot);
+    let job = warp::path!("v1" / "relayer" / "jobs" / String).and(warp::get()).map(move |id: String| {
+        let jobs = one_jobs.lock().unwrap_or_else(|e| e.into_inner());
+        match jobs.iter().find(|j| hex::encode(j.tx.id) == id.to_lowercase()) {
+            Some(job) => warp::reply::with_status(warp::reply::json(job), warp::http::StatusCode::OK),
+            None => warp::reply::with_status(
+                warp::reply::json(&ErrorBody::new(ErrorCode::NotFound, "unknown bridge ID")),
+                warp::http::StatusCode::NOT_FOUND,
+            ),
+        }
+    });
+    let current_oracles = Arc::clone(&oracles_snapshot);
+    let oracles = warp::path!("v1" / "relayer" / "oracles").and(warp::get()).map(move || {
+        warp::reply::json(&*current_oracles.lock().unwrap_or_else(|e| e.into_inner()))
+    });
+    let refund_commands = commands.clone();
+    let refund = warp::path!("v1" / "relayer" / "jobs" / String / "refund")
+        .and(warp::post())
+        .and_then(move |id: String| {
+            submit(refund_commands.clone(), move |reply| Command::Refund(id.to_lowercase(), reply))
+        });
+    let attest_commands = commands.clone();
+    let attest = warp::path!("v1" / "relayer" / "attestations")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(16 * 1024))
+        .and(warp::body::json())
+        .and_then(move |attestation: LockAttestation| {
+            submit(attest_commands.clone(), move |reply| Command::Attest(attestation, reply))
+        });
+    let update = warp::path!("v1" / "relayer" / "oracles" / "updates")
+        .and(warp::post())
+        .and(warp::body::content_length_limit(64 * 1024))
+        .and(warp::body::json())
+        .and_then(move |update: OracleSetUpdate| {
+            submit(commands.clone(), move |reply| Command::UpdateOracles(update, reply))
+        });
+    tokio::spawn(warp::serve(status.or(jobs).or(job).or(oracles).or(refund).or(attest).or(update)).run(options.listen));
+
+    let mut interval = tokio::time::interval(options.interval);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match relayer.tick(unix_now()).await {
+                    Ok(report) if report != Default::default() => log::info!(
+                        "RELAYER: {} new locks, {} minted, {} mint failures, {} expired, {} awaiting attestations",
+                        report.discovered, report.minted, report.mint_failures, report.expired,
+                        report.awaiting_attestations
+                    ),
+                    Ok(_) => {}
+                    Err(e) => log::error!("RELAYER: pass failed: {}", e),
+                }
+                *snapshot.lock().unwrap_or_else(|e| e.into_inner()) = relayer.queue().jobs().cloned().collect();
+            }
+            Some(command) = inbox.recv() => {
+                match command {
+                    Command::Attest(attestation, reply) => {
+                        let _ = reply.send(relayer.attest(attestation, unix_now()));
+                    }
+                    Command::Refund(id, reply) => {
+                        let _ = reply.send(relayer.refund(&id, unix_now()).await);
+                    }
+                    Command::UpdateOracles(update, reply) => {
+                        let result = relayer.update_oracle_set(&update).cloned().and_then(|set| {
+                            set.save(&options.oracles)?;
+                            log::info!("RELAYER: oracle set now at epoch {} ({}-of-{})",
+                                set.epoch, set.threshold, set.oracles.len());
+                            Ok(set)
+                        });
+                        if let Ok(set) = &result {
+                            *oracles_snapshot.lock().unwrap_or_else(|e| e.into_inner()) = set.clone();
+                        }
+                        let _ = reply.send(result);
+                    }
+                }
+                *snapshot.lock().unwrap_or_else(|e| e.into_inner()) = relayer.queue().jobs().cloned().collect();
+            }
+            _ = tokio::signal::ctrl_c() => {
+                log::info!("RELAYER: shutting down; queue is saved after every change");
+                break;
+            }
+        }
+    }
+}
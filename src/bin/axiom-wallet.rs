@@ -5,11 +5,13 @@ fn main() {
     let args: Vec<String> = env::args().collect();
     
     if args.len() < 2 {
-        println!("Usage: axiom-wallet [export|show|send|balance]");
+        println!("Usage: axiom-wallet [export|show|send|balance|payouts|audit]");
         println!("  export     - Show wallet address in hex format");
         println!("  show       - Show full wallet details");
         println!("  balance    - Show current balance");
+        println!("  payouts    - Show balances on rotated mining payout addresses");
         println!("  send <to> <amount> <fee> - Send AXM to address");
+        println!("  audit      - Verify and list everything this wallet has signed");
         return;
     }
 
@@ -86,6 +88,32 @@ fn main() {
             let axm_balance = balance as f64 / 100_000_000.0;
             println!("💰 Balance: {:.8} AXM", axm_balance);
         }
+        "payouts" => {
+            let chain = match axiom_core::storage::load_chain()
+                .map(axiom_core::chain::Timechain::from_saved_blocks)
+            {
+                Some(Ok(chain)) => chain,
+                Some(Err(e)) => {
+                    eprintln!("❌ Could not rebuild chain state: {}", e);
+                    std::process::exit(1);
+                }
+                None => {
+                    println!("No blockchain data found. Payout balance: 0 AXM");
+                    return;
+                }
+            };
+
+            let branch = axiom_core::payout::PayoutBranch::load(axiom_core::payout::PAYOUT_BRANCH_FILE);
+            let summary = axiom_core::payout::discover(&wallet, &chain.state, branch.next_index);
+            println!("🔀 Mining payout addresses");
+            println!("==========================");
+            for entry in &summary.addresses {
+                println!("#{:<5} {}  {:.8} AXM", entry.index, hex::encode(entry.address), entry.balance as f64 / 100_000_000.0);
+            }
+            let main_balance = chain.balance(&wallet.address);
+            println!("Payout total: {:.8} AXM across {} address(es)", summary.total as f64 / 100_000_000.0, summary.addresses.len());
+            println!("💰 Wallet total: {:.8} AXM", (summary.total + main_balance) as f64 / 100_000_000.0);
+        }
         "send" => {
             if args.len() < 5 {
                 eprintln!("Usage: axiom-wallet send <to_address_hex> <amount_axm> <fee_axm>");
@@ -146,9 +174,32 @@ fn main() {
                 }
             };
 
+            // Refuse to sign if the audit log cannot be appended to: an
+            // unrecorded signature is exactly what the log exists to rule out.
+            let mut audit = match axiom_core::key_audit::KeyAuditLog::open_default() {
+                Ok(log) => log,
+                Err(e) => {
+                    eprintln!("❌ Error opening {}: {}", axiom_core::key_audit::AUDIT_LOG_FILE, e);
+                    std::process::exit(1);
+                }
+            };
+
             // Create transaction
             match wallet.create_transaction(to_address, amount, fee, nonce, current_balance) {
                 Ok(tx) => {
+                    let record = axiom_core::key_audit::SigningRecord::transaction(
+                        axiom_core::key_audit::SigningInterface::Cli,
+                        &tx,
+                    );
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs();
+                    if let Err(e) = audit.record(record, now) {
+                        eprintln!("❌ Error recording signature in audit log: {}", e);
+                        std::process::exit(1);
+                    }
+
                     // Save transaction to file for broadcasting
                     let tx_data = match bincode::serialize(&tx) {
                         Ok(data) => data,
@@ -179,9 +230,33 @@ fn main() {
                 }
             }
         }
+        "audit" => {
+            match axiom_core::key_audit::verify(axiom_core::key_audit::AUDIT_LOG_FILE) {
+                Ok(entries) => {
+                    println!("🔏 Signing audit log: {} entries, chain intact", entries.len());
+                    for entry in entries {
+                        let detail = match (entry.amount, &entry.destination) {
+                            (Some(amount), Some(to)) => format!(" {:.8} AXM -> {}", amount as f64 / 100_000_000.0, to),
+                            _ => String::new(),
+                        };
+                        println!(
+                            "#{} {} [{}] {:?} {}{}",
+                            entry.seq, entry.timestamp, entry.interface, entry.kind, entry.digest, detail
+                        );
+                    }
+                }
+                Err(axiom_core::key_audit::AuditError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                    println!("No signing audit log found ({}).", axiom_core::key_audit::AUDIT_LOG_FILE);
+                }
+                Err(e) => {
+                    eprintln!("❌ Audit log verification FAILED: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
         _ => {
             eprintln!("❌ Unknown command: {}", command);
-            eprintln!("Use 'export', 'show', 'balance', or 'send'");
+            eprintln!("Use 'export', 'show', 'balance', 'payouts', 'send', or 'audit'");
             std::process::exit(1);
         }
     }
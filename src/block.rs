@@ -18,11 +18,10 @@ use crate::state::State;
 
 impl Block {
     /// Full block validation: VDF, ZK-STARK, PoW, and transaction checks
-    pub fn validate(&self, parent_hash: [u8; 32], parent_slot: u64, state: &mut State, difficulty: u64, vdf_iterations: u32, vdf_n: &rug::Integer) -> Result<(), &'static str> {
+    pub fn validate(&self, parent_hash: [u8; 32], parent_slot: u64, state: &mut State, difficulty: u64, vdf_iterations: u64, backend: &dyn vdf::Vdf) -> Result<(), &'static str> {
         // 1. VDF verification
         let vdf_seed = vdf::evaluate(parent_hash, parent_slot);
-        let vdf_valid = vdf::wesolowski_verify(&rug::Integer::from_digits(&vdf_seed, rug::integer::Order::Lsf), vdf_iterations, vdf_n, &rug::Integer::from_digits(&self.vdf_proof, rug::integer::Order::Lsf));
-        if !vdf_valid {
+        if !backend.verify(vdf_seed, vdf_iterations, &self.vdf_proof, &self.vdf_witness) {
             return Err("Invalid VDF proof");
         }
 
@@ -48,6 +47,7 @@ impl Block {
 }
 use serde::{Serialize, Deserialize};
 use crate::transaction::{Transaction, Address};
+use crate::types::Hash512;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Block {
@@ -56,18 +56,64 @@ pub struct Block {
     pub timestamp: u64, // UNIX seconds — miner's wall-clock at block creation
     pub miner: Address,
     pub transactions: Vec<Transaction>,
+    /// What the block pays its miner. Must equal `Coinbase::expected`.
+    pub coinbase: Coinbase,
+    /// Stale siblings of recent ancestors this block vouches for (see
+    /// `crate::consensus::uncles`). Never applied to state.
+    pub uncles: Vec<Block>,
     pub vdf_proof: [u8; 32],
+    /// Lets nodes check `vdf_proof` without redoing the delay (see
+    /// `crate::vdf::Vdf`). Left out of `calculate_hash`, but covered by
+    /// `legacy_hash()`, which the proof of work and the child's parent link
+    /// commit to.
+    pub vdf_witness: Vec<u8>,
     pub zk_proof: Vec<u8>,
     pub nonce: u64, // The PoW layer for Hash Power
 }
 
+/// The block's payment to its miner, stated explicitly so that every node
+/// checks issuance block by block instead of trusting a running total.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Coinbase {
+    /// Newly issued coins: the schedule's reward for the block's height.
+    pub reward: u64,
+    /// The miner's share of the block's transaction fees.
+    pub fees: u64,
+}
+
+impl Coinbase {
+    /// The only coinbase a block at `slot` by `miner` carrying
+    /// `transactions` may claim. Blocks without a miner pay nobody; their
+    /// fees are burned whole.
+    pub fn expected(slot: u64, miner: &Address, transactions: &[Transaction]) -> Self {
+        if *miner == [0u8; 32] {
+            return Self::default();
+        }
+        let fees = transactions
+            .iter()
+            .fold(0u64, |sum, tx| sum.saturating_add(crate::economics::FEE_POLICY.split(tx.fee).1));
+        Self { reward: crate::economics::block_reward(slot, 0), fees }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.reward.saturating_add(self.fees)
+    }
+}
+
 impl Block {
-    /// Computes the cryptographic hash of the block using Blake3
-    pub fn hash(&self) -> [u8; 32] {
+    /// Legacy 32-byte BLAKE3 block hash. Parent links, proof of work and
+    /// the genesis anchor commit to it, so it stays until a hard fork;
+    /// use `hash_512` to identify blocks anywhere else.
+    pub fn legacy_hash(&self) -> [u8; 32] {
         let serialized = bincode::serialize(self).expect("Serialization failed");
         blake3::hash(&serialized).into()
     }
 
+    #[deprecated(note = "use `hash_512` to identify blocks, or `legacy_hash` where consensus commits to 32 bytes")]
+    pub fn hash(&self) -> [u8; 32] {
+        self.legacy_hash()
+    }
+
     /// 512-bit BLAKE3 block hash using XOF (Extendable Output Function) mode.
     ///
     /// **Axiom Protocol Standard — 64-byte (512-bit) hash width.**
@@ -89,14 +135,26 @@ impl Block {
     /// 3. **Uniform input size** — Every consumer of a block hash
     ///    (Gossipsub pulses, Kademlia keys, STARK commitment columns) receives
     ///    an identically-sized digest, eliminating mismatched-length bugs.
-    pub fn hash_512(&self) -> [u8; 64] {
+    pub fn hash_512(&self) -> Hash512 {
         let serialized = bincode::serialize(self).expect("Serialization failed");
-        crate::axiom_hash_512(&serialized)
+        Hash512::digest(&serialized)
+    }
+
+    /// TXIDs of the block's transactions, in block order.
+    pub fn txids(&self) -> Vec<[u8; 32]> {
+        self.transactions.iter().map(Transaction::legacy_hash).collect()
+    }
+
+    /// Merkle root of the block's transactions (see `crate::receipt`).
+    /// Derived from `transactions`, so it is bound by `legacy_hash()` without
+    /// being part of the block encoding.
+    pub fn tx_root(&self) -> [u8; 32] {
+        crate::receipt::merkle_root(&self.txids())
     }
 
     /// Checks if the block meets the dynamic network difficulty (Hash Power check)
     pub fn meets_difficulty(&self, difficulty: u64) -> bool {
-        let h = self.hash();
+        let h = self.legacy_hash();
         // Convert first 8 bytes to u64 for numerical comparison
         // Safe conversion with proper error handling
         let val = match <[u8; 8]>::try_from(&h[0..8]) {
@@ -129,10 +187,67 @@ impl Block {
             slot,
             timestamp,
             miner,
+            coinbase: Coinbase::expected(slot, &miner, &transactions),
+            uncles: Vec::new(),
             transactions,
             vdf_proof,
+            vdf_witness: Vec::new(),
             zk_proof,
             nonce,
         }
     }
 }
+
+/// Test fixtures. Build blocks from `Block::test` with struct update syntax
+/// (`Block { miner, ..Block::test(parent, slot) }`) so that new fields need
+/// a default here only, not in every test module.
+#[cfg(test)]
+impl Block {
+    /// An empty, unproven block at `slot` on `parent`, stamped one target
+    /// interval per slot and mined by nobody.
+    pub(crate) fn test(parent: [u8; 32], slot: u64) -> Self {
+        Self {
+            parent,
+            slot,
+            timestamp: slot * crate::chain::TARGET_TIME,
+            miner: [0u8; 32],
+            transactions: Vec::new(),
+            coinbase: Coinbase::default(),
+            uncles: Vec::new(),
+            vdf_proof: [0u8; 32],
+            vdf_witness: Vec::new(),
+            zk_proof: Vec::new(),
+            nonce: 0,
+        }
+    }
+
+    /// The next block on top of this one, one target interval later.
+    pub(crate) fn test_child(&self) -> Self {
+        Self {
+            timestamp: self.timestamp + crate::chain::TARGET_TIME,
+            ..Self::test(self.legacy_hash(), self.slot + 1)
+        }
+    }
+
+    /// This block mined by `miner` carrying `transactions`, claiming the
+    /// coinbase they earn.
+    pub(crate) fn mined_by(self, miner: Address, transactions: Vec<Transaction>) -> Self {
+        Self {
+            coinbase: Coinbase::expected(self.slot, &miner, &transactions),
+            miner,
+            transactions,
+            ..self
+        }
+    }
+
+    /// Genesis followed by `len - 1` empty blocks whose nonce is `tag`, so
+    /// that chains built with different tags fork right after genesis.
+    pub(crate) fn test_chain(len: usize, tag: u64) -> Vec<Self> {
+        let mut blocks = vec![crate::genesis::genesis()];
+        while blocks.len() < len {
+            let next = Self { nonce: tag, ..blocks[blocks.len() - 1].test_child() };
+            blocks.push(next);
+        }
+        blocks
+    }
+}
@@ -0,0 +1,185 @@
+//! Canonical block bodies for explorers.
+//!
+//! The change feed says *that* a block was connected; an explorer also
+//! needs what was in it. This index keeps the canonical chain in the JSON
+//! shape served by `/v1/blocks?from=<height>&limit=<n>`, with hashes and
+//! addresses hex-encoded. It is maintained alongside the address and
+//! receipt indexes, so after a reorg it holds only the new branch.
+
+use serde::{Deserialize, Serialize};
+
+use crate::block::Block;
+use crate::economics;
+use crate::transaction::Transaction;
+
+/// Maximum blocks returned by one `/v1/blocks` query.
+pub const MAX_BLOCKS_PER_PAGE: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedTransaction {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+    pub signature: String,
+}
+
+impl IndexedTransaction {
+    pub fn new(tx: &Transaction) -> Self {
+        Self {
+            hash: hex::encode(tx.legacy_hash()),
+            from: hex::encode(tx.from),
+            to: hex::encode(tx.to),
+            amount: tx.amount,
+            fee: tx.fee,
+            nonce: tx.nonce,
+            signature: hex::encode(&tx.signature),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedBlock {
+    pub height: u64,
+    pub hash: String,
+    pub parent: String,
+    pub slot: u64,
+    pub timestamp: u64,
+    pub miner: String,
+    pub nonce: u64,
+    /// Mining reward paid by this block, from the emission schedule.
+    pub reward: u64,
+    /// This block's transaction fees destroyed under the fee policy.
+    pub fees_burned: u64,
+    /// This block's transaction fees credited to its miner.
+    pub miner_fees: u64,
+    pub tx_root: String,
+    pub vdf_proof: String,
+    /// Encoded size in bytes.
+    pub size: usize,
+    pub transactions: Vec<IndexedTransaction>,
+}
+
+impl IndexedBlock {
+    pub fn new(height: u64, block: &Block) -> Self {
+        let fees = block.transactions.iter().fold(economics::FeeTotals::default(), |mut fees, tx| {
+            let (burned, to_miner) = economics::FEE_POLICY.split(tx.fee);
+            fees.burned += burned;
+            fees.to_miners += to_miner;
+            fees
+        });
+        Self {
+            height,
+            hash: hex::encode(block.legacy_hash()),
+            parent: hex::encode(block.parent),
+            slot: block.slot,
+            timestamp: block.timestamp,
+            miner: hex::encode(block.miner),
+            nonce: block.nonce,
+            reward: economics::block_reward(block.slot, 0),
+            fees_burned: fees.burned,
+            miner_fees: fees.to_miners,
+            tx_root: hex::encode(block.tx_root()),
+            vdf_proof: hex::encode(block.vdf_proof),
+            size: bincode::serialized_size(block).unwrap_or(0) as usize,
+            transactions: block.transactions.iter().map(IndexedTransaction::new).collect(),
+        }
+    }
+}
+
+/// Response body for `/v1/blocks`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlocksPage {
+    /// Height of the canonical tip, or `None` before genesis is indexed.
+    pub tip: Option<u64>,
+    pub blocks: Vec<IndexedBlock>,
+}
+
+#[derive(Debug, Default)]
+pub struct BlockIndex {
+    blocks: Vec<IndexedBlock>,
+}
+
+impl BlockIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index a whole chain, genesis first.
+    pub fn from_blocks(blocks: &[Block]) -> Self {
+        let mut index = Self::new();
+        for (height, block) in blocks.iter().enumerate() {
+            index.index_block(height as u64, block);
+        }
+        index
+    }
+
+    pub fn tip(&self) -> Option<u64> {
+        self.blocks.len().checked_sub(1).map(|tip| tip as u64)
+    }
+
+    /// Record `block` at `height`, replacing anything at or above it.
+    pub fn index_block(&mut self, height: u64, block: &Block) {
+        self.truncate(height);
+        if height as usize == self.blocks.len() {
+            self.blocks.push(IndexedBlock::new(height, block));
+        }
+    }
+
+    /// Forget everything at or above `height` (reorg rollback).
+    pub fn truncate(&mut self, height: u64) {
+        self.blocks.truncate(height as usize);
+    }
+
+    /// Re-index after switching from `old` to `new`, touching only the
+    /// blocks above their common prefix.
+    pub fn chain_replaced(&mut self, old: &[Block], new: &[Block]) {
+        let common = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        self.truncate(common as u64);
+        for (height, block) in new.iter().enumerate().skip(common) {
+            self.index_block(height as u64, block);
+        }
+    }
+
+    /// Up to `limit` blocks from `from` upwards.
+    pub fn page(&self, from: u64, limit: usize) -> BlocksPage {
+        let start = (from as usize).min(self.blocks.len());
+        let end = start.saturating_add(limit.clamp(1, MAX_BLOCKS_PER_PAGE)).min(self.blocks.len());
+        BlocksPage { tip: self.tip(), blocks: self.blocks[start..end].to_vec() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(parent: [u8; 32], slot: u64) -> Block {
+        Block {
+            timestamp: 1_000 + slot,
+            miner: [7u8; 32],
+            nonce: slot,
+            ..Block::test(parent, slot)
+        }
+    }
+
+    #[test]
+    fn test_pages_follow_reorgs() {
+        let b0 = block([0u8; 32], 0);
+        let b1 = block(b0.legacy_hash(), 1);
+        let b2 = block(b1.legacy_hash(), 2);
+        let mut index = BlockIndex::from_blocks(&[b0.clone(), b1.clone(), b2.clone()]);
+        assert_eq!(index.tip(), Some(2));
+        let page = index.page(1, 1);
+        assert_eq!(page.blocks.len(), 1);
+        assert_eq!(page.blocks[0].parent, hex::encode(b0.legacy_hash()));
+        assert!(index.page(5, 10).blocks.is_empty());
+
+        let fork = block(b1.legacy_hash(), 3);
+        let fork_next = block(fork.legacy_hash(), 4);
+        index.chain_replaced(&[b0.clone(), b1.clone(), b2], &[b0, b1, fork.clone(), fork_next]);
+        assert_eq!(index.tip(), Some(3));
+        assert_eq!(index.page(2, 10).blocks[0].hash, hex::encode(fork.legacy_hash()));
+    }
+}
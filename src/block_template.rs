@@ -0,0 +1,323 @@
+// src/block_template.rs - Block template transaction selection
+//
+// Chooses which candidate transactions go into a locally mined block.
+// Selection is miner policy, not consensus: other nodes never see the
+// risk scores or the policy, only the resulting block. The policy and
+// what it excluded are kept as local metadata next to the chain so an
+// operator can audit why a transaction was left out.
+//
+// Candidates are taken best fee rate first (fee per encoded byte), each
+// sender's in nonce order, until the block's byte budget is spent.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::State;
+use crate::transaction::{Address, Transaction};
+
+/// Upper bound on transactions per mined block (matches the gossip
+/// `BlockMessage` limit).
+pub const DEFAULT_MAX_BLOCK_TRANSACTIONS: usize = 1000;
+
+/// Upper bound on the encoded size of a mined block's transactions (the
+/// guardian's `MAX_BLOCK_SIZE_BYTES`).
+pub const DEFAULT_MAX_BLOCK_BYTES: usize = 1_000_000;
+
+/// Encoded size of a transaction in a block: what its fee rate is per.
+pub fn tx_weight(tx: &Transaction) -> usize {
+    bincode::serialized_size(tx).map_or(usize::MAX, |n| n as usize)
+}
+
+/// Fee per byte of `tx_weight`.
+pub fn fee_rate(tx: &Transaction) -> f64 {
+    tx.fee as f64 / tx_weight(tx).max(1) as f64
+}
+
+/// A sender's next transaction, ranked by fee rate.
+struct Head<'a> {
+    rate: f64,
+    /// Position in the pool, so equal rates keep arrival order.
+    arrival: usize,
+    tx: &'a Transaction,
+}
+
+impl PartialEq for Head<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Head<'_> {}
+
+impl PartialOrd for Head<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Head<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rate.total_cmp(&other.rate).then(other.arrival.cmp(&self.arrival))
+    }
+}
+
+/// Order `pool` for a block on top of `state`: the highest fee rate among
+/// each sender's next transaction goes first. A sender's transactions only
+/// qualify as a gapless nonce run from its current nonce, and only while
+/// their total cost fits its balance. Funds received in the same block are
+/// not counted, so leaving out any one sender's transactions never
+/// invalidates another's.
+pub fn fee_ordered<'a>(pool: impl IntoIterator<Item = &'a Transaction>, state: &State) -> Vec<&'a Transaction> {
+    let mut by_sender: HashMap<Address, Vec<(usize, &'a Transaction)>> = HashMap::new();
+    for (arrival, tx) in pool.into_iter().enumerate() {
+        by_sender.entry(tx.from).or_default().push((arrival, tx));
+    }
+
+    let mut runs: HashMap<Address, std::vec::IntoIter<(usize, &'a Transaction)>> = HashMap::new();
+    for (sender, mut txs) in by_sender {
+        txs.sort_by_key(|(arrival, tx)| (tx.nonce, *arrival));
+        let mut nonce = state.nonce(&sender);
+        let mut balance = state.balance(&sender);
+        let run: Vec<_> = txs
+            .into_iter()
+            .filter(|(_, tx)| {
+                let cost = tx.amount.saturating_add(tx.fee);
+                if tx.nonce != nonce || cost > balance {
+                    return false;
+                }
+                nonce += 1;
+                balance -= cost;
+                true
+            })
+            .collect();
+        runs.insert(sender, run.into_iter());
+    }
+
+    let mut heads: BinaryHeap<Head<'a>> = runs
+        .values_mut()
+        .filter_map(Iterator::next)
+        .map(|(arrival, tx)| Head { rate: fee_rate(tx), arrival, tx })
+        .collect();
+    let mut ordered = Vec::with_capacity(heads.len());
+    while let Some(head) = heads.pop() {
+        ordered.push(head.tx);
+        if let Some((arrival, tx)) = runs.get_mut(&head.tx.from).and_then(Iterator::next) {
+            heads.push(Head { rate: fee_rate(tx), arrival, tx });
+        }
+    }
+    ordered
+}
+
+/// Miner policy for AI Guardian risk scores (0.0-100.0, as produced by
+/// `ThreatAssessment::threat_score`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskPolicy {
+    /// Exclude transactions scoring strictly above this. `None` disables
+    /// risk-based filtering entirely.
+    pub max_risk_score: Option<f64>,
+    /// Exclude transactions the guardian never scored (e.g. accepted via
+    /// the rule-based fallback while the AI was unavailable).
+    pub exclude_unscored: bool,
+}
+
+impl Default for RiskPolicy {
+    fn default() -> Self {
+        Self::permissive()
+    }
+}
+
+impl RiskPolicy {
+    /// Include every valid transaction regardless of risk.
+    pub fn permissive() -> Self {
+        Self { max_risk_score: None, exclude_unscored: false }
+    }
+
+    /// Exclude transactions scoring above `threshold`; unscored ones are
+    /// still included.
+    pub fn conservative(threshold: f64) -> Self {
+        Self { max_risk_score: Some(threshold), exclude_unscored: false }
+    }
+
+    /// Whether a transaction with the given score may be included.
+    pub fn admits(&self, risk_score: Option<f64>) -> bool {
+        match (self.max_risk_score, risk_score) {
+            (None, _) => true,
+            (Some(max), Some(score)) => score <= max,
+            (Some(_), None) => !self.exclude_unscored,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExcludedTransaction {
+    pub tx_hash: String,
+    /// `None` when excluded for having no score.
+    pub risk_score: Option<f64>,
+}
+
+/// Local (non-consensus) record of how a block's transactions were chosen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateMetadata {
+    pub policy: RiskPolicy,
+    pub candidates: usize,
+    pub included: usize,
+    pub excluded: Vec<ExcludedTransaction>,
+}
+
+/// Metadata for a mined block, as persisted by
+/// `storage::append_block_metadata`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalBlockMetadata {
+    pub height: u64,
+    pub block_hash: String,
+    pub template: TemplateMetadata,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockTemplate {
+    pub transactions: Vec<Transaction>,
+    pub metadata: TemplateMetadata,
+}
+
+/// Select up to `max_count` transactions totalling at most `max_bytes`
+/// from `candidates` (already in preferred order, e.g. from `fee_ordered`,
+/// and already valid against the current state), dropping those the risk
+/// policy does not admit. Once one of a sender's transactions is left out,
+/// its later ones are too, as their nonces would no longer follow on.
+pub fn build_template<'a>(
+    candidates: impl IntoIterator<Item = &'a Transaction>,
+    max_count: usize,
+    max_bytes: usize,
+    policy: &RiskPolicy,
+    risk_score: impl Fn(&Transaction) -> Option<f64>,
+) -> BlockTemplate {
+    let mut transactions = Vec::new();
+    let mut excluded = Vec::new();
+    let mut considered = 0usize;
+    let mut bytes = 0usize;
+    let mut skipped_senders = HashSet::new();
+
+    for tx in candidates {
+        if transactions.len() >= max_count {
+            break;
+        }
+        if skipped_senders.contains(&tx.from) {
+            continue;
+        }
+        considered += 1;
+        let weight = tx_weight(tx);
+        if bytes.saturating_add(weight) > max_bytes {
+            // Smaller transactions further down may still fit.
+            skipped_senders.insert(tx.from);
+            continue;
+        }
+        let score = risk_score(tx);
+        if policy.admits(score) {
+            bytes += weight;
+            transactions.push(tx.clone());
+        } else {
+            skipped_senders.insert(tx.from);
+            excluded.push(ExcludedTransaction {
+                tx_hash: hex::encode(tx.legacy_hash()),
+                risk_score: score,
+            });
+        }
+    }
+
+    BlockTemplate {
+        metadata: TemplateMetadata {
+            policy: policy.clone(),
+            candidates: considered,
+            included: transactions.len(),
+            excluded,
+        },
+        transactions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(nonce: u64) -> Transaction {
+        Transaction {
+            from: [nonce as u8; 32],
+            to: [0xAA; 32],
+            amount: 100,
+            fee: 1,
+            nonce,
+            expiry_height: 0,
+            zk_proof: vec![],
+            signature: vec![],
+        }
+    }
+
+    #[test]
+    fn test_policy_admits() {
+        assert!(RiskPolicy::permissive().admits(Some(99.0)));
+        let p = RiskPolicy::conservative(50.0);
+        assert!(p.admits(Some(50.0)));
+        assert!(!p.admits(Some(50.1)));
+        assert!(p.admits(None));
+        let strict = RiskPolicy { exclude_unscored: true, ..p };
+        assert!(!strict.admits(None));
+    }
+
+    #[test]
+    fn test_build_template_excludes_risky_and_records_policy() {
+        let txs = vec![tx(1), tx(2), tx(3)];
+        let policy = RiskPolicy::conservative(60.0);
+        let template = build_template(&txs, 10, DEFAULT_MAX_BLOCK_BYTES, &policy, |t| match t.nonce {
+            2 => Some(85.0),
+            3 => None,
+            _ => Some(10.0),
+        });
+
+        assert_eq!(template.transactions.len(), 2);
+        assert_eq!(template.metadata.candidates, 3);
+        assert_eq!(template.metadata.included, 2);
+        assert_eq!(template.metadata.policy, policy);
+        assert_eq!(template.metadata.excluded.len(), 1);
+        assert_eq!(template.metadata.excluded[0].risk_score, Some(85.0));
+        assert_eq!(template.metadata.excluded[0].tx_hash, hex::encode(txs[1].legacy_hash()));
+    }
+
+    #[test]
+    fn test_build_template_respects_max_count() {
+        let txs: Vec<Transaction> = (0..5).map(tx).collect();
+        let template = build_template(&txs, 2, DEFAULT_MAX_BLOCK_BYTES, &RiskPolicy::permissive(), |_| None);
+        assert_eq!(template.transactions.len(), 2);
+        assert_eq!(template.metadata.candidates, 2);
+
+        let template = build_template(&txs, 10, 2 * tx_weight(&txs[0]), &RiskPolicy::permissive(), |_| None);
+        assert_eq!(template.transactions.len(), 2);
+    }
+
+    #[test]
+    fn test_fee_ordered_by_rate_within_sender_nonces() {
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        let mut state = State::new();
+        state.credit(alice, 1_000);
+        state.credit(bob, 1_000);
+        let send = |from, nonce, fee| Transaction { from, to: [0xAA; 32], amount: 100, fee, nonce, expiry_height: 0, zk_proof: vec![], signature: vec![] };
+        let pool = vec![
+            send(alice, 1, 50),
+            send(alice, 0, 1),
+            send(bob, 0, 10),
+            send(bob, 2, 99), // gap: bob has no nonce 1
+            send(alice, 2, 900), // over alice's balance after the first two
+        ];
+        let ordered: Vec<(u8, u64)> = fee_ordered(&pool, &state).iter().map(|tx| (tx.from[0], tx.nonce)).collect();
+        // Bob's 10 beats Alice's 1; Alice's 50 only once her nonce 0 is in.
+        assert_eq!(ordered, [(2, 0), (1, 0), (1, 1)]);
+
+        // Alice's first one risky: her second cannot follow it in.
+        let risky = RiskPolicy::conservative(50.0);
+        let template = build_template(fee_ordered(&pool, &state), 10, DEFAULT_MAX_BLOCK_BYTES, &risky, |tx| {
+            Some(if tx.fee == 1 { 90.0 } else { 0.0 })
+        });
+        assert_eq!(template.transactions.iter().map(|tx| tx.from[0]).collect::<Vec<_>>(), [2]);
+    }
+}
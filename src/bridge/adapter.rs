@@ -0,0 +1,180 @@
+// src/bridge/adapter.rs - Per-chain bridge plumbing behind one trait
+//
+// The oracle and relayer only need four things from an external chain:
+// its current height, the lock events in a height range, how deep a lock
+// must be before it is final, and a way to submit mints and burns. Each
+// family of chains answers those differently (EVM logs, Solana program
+// logs, Cosmos IBC packets), so each gets a `ChainAdapter`. The native
+// Axiom chain has none: its locks are tracked locally.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::cosmos::CosmosAdapter;
+use super::cross_chain::{BridgeContract, BridgeOracle, BridgeTransaction, ChainId, LOCK_SCAN_LAG};
+use super::eth_spv::EthLightClient;
+use super::evm::{self, EvmRpc, EvmSigner};
+use super::solana::SolanaAdapter;
+use crate::error::{AxiomError, Result};
+
+/// A lock discovered on an external chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockEvent {
+    pub sender: String,
+    pub recipient: String,
+    pub amount: u64,
+    /// Block (or slot) that includes the lock.
+    pub block_number: u64,
+    /// Hash or signature of the transaction that emitted it. Locks read
+    /// through the Ethereum light client carry the block hash instead,
+    /// which is what their receipts were verified against.
+    pub tx_hash: String,
+    /// Position of the event within that transaction or block, so that two
+    /// locks in one transaction get distinct bridge IDs.
+    pub log_index: u64,
+}
+
+/// A submitted mint or burn, once final on its chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainReceipt {
+    pub tx_hash: String,
+    /// The account that sent it, in the chain's own address format.
+    pub sender: String,
+    pub block_number: u64,
+}
+
+#[async_trait]
+pub trait ChainAdapter: Send + Sync {
+    fn chain(&self) -> ChainId;
+
+    /// Blocks (or slots) past the lock before the oracle treats it as final.
+    fn required_confirmations(&self) -> u32;
+
+    /// How far behind `head()` a lock scan stops.
+    fn scan_lag(&self) -> u64 {
+        0
+    }
+
+    /// Current height: block number, slot or Tendermint height.
+    async fn head(&self) -> Result<u64>;
+
+    /// Bridge locks included in heights `from..=to`.
+    async fn lock_events(&self, from: u64, to: u64) -> Result<Vec<LockEvent>>;
+
+    /// Mint `bridge_tx.amount` wrapped AXM to `bridge_tx.recipient`.
+    async fn submit_mint(&self, bridge_tx: &BridgeTransaction) -> Result<ChainReceipt>;
+
+    /// Burn `amount` wrapped AXM held by the bridge signer, tagged with
+    /// `bridge_id` so the unlock on Axiom can be matched to it.
+    async fn submit_burn(&self, amount: u64, bridge_id: [u8; 32]) -> Result<ChainReceipt>;
+}
+
+/// Adapter for `chain` using its default endpoints, or `None` for Axiom.
+pub fn default_adapter(chain: &ChainId) -> Result<Option<Arc<dyn ChainAdapter>>> {
+    Ok(match chain {
+        ChainId::Axiom => None,
+        ChainId::Solana => Some(Arc::new(SolanaAdapter::from_env()?)),
+        ChainId::Cosmos => Some(Arc::new(CosmosAdapter::from_env()?)),
+        evm_chain => Some(Arc::new(EvmAdapter::new(evm_chain.clone())?)),
+    })
+}
+
+/// Any EVM chain: `Lock` logs from the bridge contract, calls signed with
+/// an [`EvmSigner`]. On Ethereum, locks are read through an
+/// [`EthLightClient`] rather than trusted from `eth_getLogs`.
+pub struct EvmAdapter {
+    chain: ChainId,
+    contract: String,
+    rpc_url: String,
+    signer: Option<EvmSigner>,
+    light_client: Option<tokio::sync::Mutex<EthLightClient>>,
+}
+
+impl EvmAdapter {
+    pub fn new(chain: ChainId) -> Result<Self> {
+        if !chain.is_evm() {
+            return Err(AxiomError::InvalidConfig(format!("{:?} is not an EVM chain", chain)));
+        }
+        let rpc_url = BridgeOracle::resolve_rpc_url(&chain)?;
+        let light_client = match chain {
+            ChainId::Ethereum => Some(tokio::sync::Mutex::new(EthLightClient::from_env(&rpc_url)?)),
+            _ => None,
+        };
+        Ok(Self {
+            rpc_url,
+            chain,
+            contract: BridgeContract::BRIDGE_ADDRESS.to_string(),
+            signer: None,
+            light_client,
+        })
+    }
+
+    pub fn with_signer(mut self, signer: EvmSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    async fn submit_call(&self, call: Vec<u8>) -> Result<ChainReceipt> {
+        let signer = self.signer.as_ref()
+            .ok_or_else(|| AxiomError::BridgeError(format!("No EVM signer configured for {:?}", self.chain)))?;
+        let receipt = EvmRpc::new(self.rpc_url.as_str())?
+            .submit_call(
+                signer,
+                self.chain.chain_id(),
+                evm::parse_address(&self.contract)?,
+                call,
+                self.required_confirmations(),
+            )
+            .await?;
+        Ok(ChainReceipt {
+            tx_hash: receipt.tx_hash,
+            sender: format!("0x{}", hex::encode(signer.address())),
+            block_number: receipt.block_number,
+        })
+    }
+}
+
+#[async_trait]
+impl ChainAdapter for EvmAdapter {
+    fn chain(&self) -> ChainId {
+        self.chain.clone()
+    }
+
+    fn required_confirmations(&self) -> u32 {
+        match self.chain {
+            ChainId::Ethereum => 12,    // ~3 minutes
+            ChainId::BSC => 15,         // ~45 seconds
+            ChainId::Polygon => 128,    // ~5 minutes
+            _ => 1,                     // Fast finality
+        }
+    }
+
+    fn scan_lag(&self) -> u64 {
+        // The light client's head is already finalized
+        if self.light_client.is_some() { 0 } else { LOCK_SCAN_LAG }
+    }
+
+    async fn head(&self) -> Result<u64> {
+        match &self.light_client {
+            Some(client) => client.lock().await.sync().await,
+            None => EvmRpc::new(self.rpc_url.as_str())?.block_number().await,
+        }
+    }
+
+    async fn lock_events(&self, from: u64, to: u64) -> Result<Vec<LockEvent>> {
+        match &self.light_client {
+            Some(client) => client.lock().await.lock_events(&self.contract, from, to).await,
+            None => BridgeOracle::poll_lock_events(&self.rpc_url, &self.contract, from, to).await,
+        }
+    }
+
+    async fn submit_mint(&self, bridge_tx: &BridgeTransaction) -> Result<ChainReceipt> {
+        let recipient = evm::parse_address(&bridge_tx.recipient)?;
+        self.submit_call(evm::encode_mint_call(recipient, bridge_tx.amount, bridge_tx.id)).await
+    }
+
+    async fn submit_burn(&self, amount: u64, bridge_id: [u8; 32]) -> Result<ChainReceipt> {
+        self.submit_call(evm::encode_burn_call(amount, bridge_id)).await
+    }
+}
@@ -2,6 +2,8 @@ use serde::{Serialize, Deserialize};
 use sha2::{Sha256, Digest};
 use rand::RngCore;
 
+use crate::error::{AxiomError, Result};
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct BridgeLock {
     /// The hash of the secret (H) - Published on the network
@@ -37,4 +39,125 @@ impl BridgeSecret {
         hash.copy_from_slice(&result);
         hash
     }
+
+    /// Whether `preimage` opens `hash_lock`.
+    pub fn opens(preimage: &[u8; 32], hash_lock: &[u8; 32]) -> bool {
+        &Self { secret: *preimage }.to_hash_lock() == hash_lock
+    }
+}
+
+/// Where a hash-time-locked contract stands.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum HtlcState {
+    /// Funds held until claimed with the preimage or refunded at timeout.
+    Locked,
+    /// Paid to the recipient. The preimage is now public, which is what
+    /// lets the other side of a swap claim in turn.
+    Claimed { preimage: [u8; 32] },
+    /// Returned to the sender after the timeout.
+    Refunded,
+}
+
+/// A hash-time-locked contract on the Axiom side of a swap: `lock.amount`
+/// from `sender`, payable to `recipient` with the preimage of
+/// `lock.hash_lock` before `lock.timeout_slot`, and back to `sender` from
+/// that slot on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Htlc {
+    pub lock: BridgeLock,
+    pub sender: String,
+    pub recipient: String,
+    pub state: HtlcState,
+}
+
+impl Htlc {
+    pub fn new(lock: BridgeLock, sender: String, recipient: String) -> Result<Self> {
+        if lock.amount == 0 {
+            return Err(AxiomError::BridgeError("HTLC amount must be positive".into()));
+        }
+        Ok(Self { lock, sender, recipient, state: HtlcState::Locked })
+    }
+
+    pub fn is_expired(&self, slot: u64) -> bool {
+        slot >= self.lock.timeout_slot
+    }
+
+    /// Pay the recipient. Needs the preimage and a slot before the timeout.
+    pub fn claim(&mut self, preimage: [u8; 32], slot: u64) -> Result<()> {
+        if self.state != HtlcState::Locked {
+            return Err(AxiomError::BridgeError(format!("HTLC already settled: {:?}", self.state)));
+        }
+        if self.is_expired(slot) {
+            return Err(AxiomError::BridgeError(format!(
+                "HTLC timed out at slot {}, claim at {}",
+                self.lock.timeout_slot, slot
+            )));
+        }
+        if !BridgeSecret::opens(&preimage, &self.lock.hash_lock) {
+            return Err(AxiomError::BridgeError("preimage does not match hash lock".into()));
+        }
+        self.state = HtlcState::Claimed { preimage };
+        Ok(())
+    }
+
+    /// Return the funds to the sender once the timeout has passed unclaimed.
+    pub fn refund(&mut self, slot: u64) -> Result<()> {
+        if self.state != HtlcState::Locked {
+            return Err(AxiomError::BridgeError(format!("HTLC already settled: {:?}", self.state)));
+        }
+        if !self.is_expired(slot) {
+            return Err(AxiomError::BridgeError(format!(
+                "HTLC refundable from slot {}, now {}",
+                self.lock.timeout_slot, slot
+            )));
+        }
+        self.state = HtlcState::Refunded;
+        Ok(())
+    }
+
+    /// The preimage revealed by a claim.
+    pub fn revealed_preimage(&self) -> Option<[u8; 32]> {
+        match self.state {
+            HtlcState::Claimed { preimage } => Some(preimage),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn htlc(secret: &BridgeSecret) -> Htlc {
+        let lock = BridgeLock {
+            hash_lock: secret.to_hash_lock(),
+            timeout_slot: 100,
+            amount: 5_000,
+            recipient_on_other_chain: "0xabc".into(),
+        };
+        Htlc::new(lock, "alice".into(), "bob".into()).unwrap()
+    }
+
+    #[test]
+    fn test_claim_with_preimage_before_timeout() {
+        let secret = BridgeSecret::generate();
+        let mut contract = htlc(&secret);
+        assert!(contract.claim([0u8; 32], 10).is_err());
+        assert!(contract.refund(99).is_err());
+        contract.claim(secret.secret, 99).unwrap();
+        assert_eq!(contract.revealed_preimage(), Some(secret.secret));
+        // Settled once: no refund after a claim.
+        assert!(contract.refund(200).is_err());
+    }
+
+    #[test]
+    fn test_refund_only_after_timeout() {
+        let secret = BridgeSecret::generate();
+        let mut contract = htlc(&secret);
+        assert!(contract.claim(secret.secret, 100).is_err());
+        contract.refund(100).unwrap();
+        assert_eq!(contract.state, HtlcState::Refunded);
+        assert!(contract.claim(secret.secret, 50).is_err());
+        assert!(contract.revealed_preimage().is_none());
+    }
 }
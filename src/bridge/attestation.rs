@@ -0,0 +1,355 @@
+// src/bridge/attestation.rs - M-of-N oracle attestation of lock events
+//
+// Confirmations alone let any single oracle declare a lock final and mint
+// against it. Minting now also needs `threshold` distinct signatures from
+// the registered bridge oracles over the lock event itself: bridge ID,
+// chains, parties, amount, token and lock block. Attestations reach the
+// relayer over its API; each one is checked against the current oracle set
+// on arrival and again before minting, so a key rotated out stops counting.
+//
+// The oracle set changes only through an `OracleSetUpdate` signed by a
+// quorum of the current set. That includes an oracle rotating its own key,
+// so a stolen key cannot be rotated out of its owner's reach.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::cross_chain::BridgeTransaction;
+use crate::error::{AxiomError, Result};
+
+/// Default oracle set file, next to the relayer queue.
+pub const ORACLE_SET_FILE: &str = "bridge_oracles.json";
+
+const ATTESTATION_DOMAIN: &[u8] = b"axiom-bridge-attestation-v1";
+const SET_UPDATE_DOMAIN: &[u8] = b"axiom-bridge-oracle-set-v1";
+
+/// The lock-event digest an oracle signs. Covers everything a mint acts on
+/// and nothing that changes while the bridge is tracked.
+pub fn lock_digest(tx: &BridgeTransaction) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(ATTESTATION_DOMAIN);
+    hasher.update(tx.id);
+    hasher.update(tx.from_chain.chain_id().to_le_bytes());
+    hasher.update(tx.to_chain.chain_id().to_le_bytes());
+    for field in [&tx.sender, &tx.recipient, &tx.token] {
+        hasher.update((field.len() as u64).to_le_bytes());
+        hasher.update(field.as_bytes());
+    }
+    hasher.update(tx.amount.to_le_bytes());
+    hasher.update(tx.lock_block.to_le_bytes());
+    hasher.finalize().into()
+}
+
+fn verify_signature(key: &[u8; 32], message: &[u8], signature: &[u8]) -> Result<()> {
+    let key = VerifyingKey::from_bytes(key)
+        .map_err(|e| AxiomError::InvalidPublicKey(e.to_string()))?;
+    let signature = Signature::from_slice(signature)
+        .map_err(|e| AxiomError::InvalidSignature(e.to_string()))?;
+    key.verify(message, &signature).map_err(|e| AxiomError::InvalidSignature(e.to_string()))
+}
+
+/// One oracle's signature over a lock event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockAttestation {
+    pub bridge_id: [u8; 32],
+    /// Ed25519 public key of the signing oracle.
+    pub oracle: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl LockAttestation {
+    pub fn sign(tx: &BridgeTransaction, key: &SigningKey) -> Self {
+        Self {
+            bridge_id: tx.id,
+            oracle: key.verifying_key().to_bytes(),
+            signature: key.sign(&lock_digest(tx)).to_bytes().to_vec(),
+        }
+    }
+
+    /// Check the signature against `tx`. Says nothing about membership.
+    pub fn verify(&self, tx: &BridgeTransaction) -> Result<()> {
+        if self.bridge_id != tx.id {
+            return Err(AxiomError::BridgeError(format!(
+                "attestation is for bridge {}, not {}",
+                hex::encode(self.bridge_id),
+                hex::encode(tx.id)
+            )));
+        }
+        verify_signature(&self.oracle, &lock_digest(tx), &self.signature)
+    }
+}
+
+/// A change to the oracle set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleSetChange {
+    Register { key: [u8; 32] },
+    Remove { key: [u8; 32] },
+    Rotate { old: [u8; 32], new: [u8; 32] },
+    SetThreshold { threshold: usize },
+}
+
+/// An oracle set change and the current members' signatures approving it.
+/// `epoch` must match the set it is applied to, so approvals cannot be
+/// replayed against a later set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OracleSetUpdate {
+    pub epoch: u64,
+    pub change: OracleSetChange,
+    /// (oracle public key, signature) pairs.
+    pub approvals: Vec<([u8; 32], Vec<u8>)>,
+}
+
+impl OracleSetUpdate {
+    pub fn new(epoch: u64, change: OracleSetChange) -> Self {
+        Self { epoch, change, approvals: Vec::new() }
+    }
+
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = SET_UPDATE_DOMAIN.to_vec();
+        bytes.extend_from_slice(&self.epoch.to_le_bytes());
+        bytes.extend(bincode::serialize(&self.change).expect("oracle set change serializes"));
+        bytes
+    }
+
+    pub fn approve(&mut self, key: &SigningKey) {
+        let signature = key.sign(&self.signing_bytes()).to_bytes().to_vec();
+        self.approvals.push((key.verifying_key().to_bytes(), signature));
+    }
+}
+
+/// The registered bridge oracles and how many must attest a lock.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OracleSet {
+    /// Bumped by every applied update.
+    pub epoch: u64,
+    pub threshold: usize,
+    pub oracles: Vec<[u8; 32]>,
+}
+
+impl OracleSet {
+    /// A genesis set. Needs 1 <= threshold <= oracles and distinct, valid keys.
+    pub fn new(threshold: usize, oracles: Vec<[u8; 32]>) -> Result<Self> {
+        let set = Self { epoch: 0, threshold, oracles };
+        set.validate()?;
+        Ok(set)
+    }
+
+    /// Load the set at `path`. A missing file is an empty set, under which
+    /// nothing can be minted.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let set: Self = match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| AxiomError::InvalidConfig(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        set.validate()?;
+        Ok(set)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| AxiomError::SerializationError(e.to_string()))?;
+        crate::storage::write_atomic(path.as_ref(), &json)?;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.oracles.is_empty()
+    }
+
+    pub fn contains(&self, key: &[u8; 32]) -> bool {
+        self.oracles.contains(key)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.threshold == 0 || self.threshold > self.oracles.len() {
+            return Err(AxiomError::InvalidConfig(format!(
+                "oracle threshold {} out of range for {} oracles",
+                self.threshold,
+                self.oracles.len()
+            )));
+        }
+        let mut seen = HashSet::new();
+        for key in &self.oracles {
+            VerifyingKey::from_bytes(key).map_err(|e| AxiomError::InvalidPublicKey(e.to_string()))?;
+            if !seen.insert(key) {
+                return Err(AxiomError::InvalidConfig(format!("oracle {} registered twice", hex::encode(key))));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check one attestation: a valid signature over `tx` by a member.
+    pub fn verify(&self, attestation: &LockAttestation, tx: &BridgeTransaction) -> Result<()> {
+        if !self.contains(&attestation.oracle) {
+            return Err(AxiomError::Unauthorized);
+        }
+        attestation.verify(tx)
+    }
+
+    /// Distinct current members with a valid attestation of `tx`.
+    pub fn count_valid(&self, tx: &BridgeTransaction, attestations: &[LockAttestation]) -> usize {
+        attestations
+            .iter()
+            .filter(|a| self.verify(a, tx).is_ok())
+            .map(|a| a.oracle)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Whether `attestations` authorise minting `tx`.
+    pub fn check_quorum(&self, tx: &BridgeTransaction, attestations: &[LockAttestation]) -> Result<()> {
+        if self.is_empty() {
+            return Err(AxiomError::BridgeError("no bridge oracles registered".into()));
+        }
+        let valid = self.count_valid(tx, attestations);
+        if valid < self.threshold {
+            return Err(AxiomError::BridgeError(format!(
+                "bridge {} has {}/{} oracle attestations",
+                hex::encode(tx.id),
+                valid,
+                self.threshold
+            )));
+        }
+        Ok(())
+    }
+
+    /// Whether `signatures` over `message` come from a quorum of distinct
+    /// members.
+    pub fn check_signed(&self, message: &[u8], signatures: &[([u8; 32], Vec<u8>)]) -> Result<()> {
+        let signers: HashSet<_> = signatures
+            .iter()
+            .filter(|(key, sig)| self.contains(key) && verify_signature(key, message, sig).is_ok())
+            .map(|(key, _)| *key)
+            .collect();
+        if self.is_empty() || signers.len() < self.threshold {
+            return Err(AxiomError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Apply an update approved by a quorum of the current set.
+    pub fn apply(&mut self, update: &OracleSetUpdate) -> Result<()> {
+        if update.epoch != self.epoch {
+            return Err(AxiomError::BridgeError(format!(
+                "oracle set update is for epoch {}, set is at {}",
+                update.epoch, self.epoch
+            )));
+        }
+        self.check_signed(&update.signing_bytes(), &update.approvals)?;
+
+        let mut next = self.clone();
+        match &update.change {
+            OracleSetChange::Register { key } => next.oracles.push(*key),
+            OracleSetChange::Remove { key } => {
+                if !next.contains(key) {
+                    return Err(AxiomError::NotFound(format!("oracle {}", hex::encode(key))));
+                }
+                next.oracles.retain(|k| k != key);
+            }
+            OracleSetChange::Rotate { old, new } => {
+                let slot = next
+                    .oracles
+                    .iter_mut()
+                    .find(|k| *k == old)
+                    .ok_or_else(|| AxiomError::NotFound(format!("oracle {}", hex::encode(old))))?;
+                *slot = *new;
+            }
+            OracleSetChange::SetThreshold { threshold } => next.threshold = *threshold,
+        }
+        next.validate()?;
+        next.epoch += 1;
+        *self = next;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::{BridgeStatus, ChainId};
+
+    fn lock() -> BridgeTransaction {
+        BridgeTransaction {
+            id: [5; 32],
+            from_chain: ChainId::Ethereum,
+            to_chain: ChainId::Axiom,
+            sender: "0xabc".into(),
+            recipient: "axm1".into(),
+            amount: 1_000,
+            token: "AXM".into(),
+            status: BridgeStatus::ReadyToMint,
+            timestamp: 0,
+            confirmations: 12,
+            required_confirmations: 12,
+            zk_proof: vec![],
+            lock_block: 100,
+            expires_at: 0,
+        }
+    }
+
+    fn keys() -> Vec<SigningKey> {
+        (1..=3u8).map(|i| SigningKey::from_bytes(&[i; 32])).collect()
+    }
+
+    #[test]
+    fn test_quorum_of_distinct_members() {
+        let keys = keys();
+        let set = OracleSet::new(2, keys.iter().map(|k| k.verifying_key().to_bytes()).collect()).unwrap();
+        let tx = lock();
+
+        let first = LockAttestation::sign(&tx, &keys[0]);
+        // The same oracle twice is still one attestation.
+        assert!(set.check_quorum(&tx, &[first.clone(), first.clone()]).is_err());
+        let outsider = LockAttestation::sign(&tx, &SigningKey::from_bytes(&[9; 32]));
+        assert!(matches!(set.verify(&outsider, &tx), Err(AxiomError::Unauthorized)));
+        assert!(set.check_quorum(&tx, &[first.clone(), outsider]).is_err());
+
+        let second = LockAttestation::sign(&tx, &keys[1]);
+        assert!(set.check_quorum(&tx, &[first.clone(), second.clone()]).is_ok());
+
+        // A signature does not carry over to a different amount.
+        let mut altered = tx.clone();
+        altered.amount += 1;
+        assert_eq!(set.count_valid(&altered, &[first, second]), 0);
+        assert!(OracleSet::default().check_quorum(&tx, &[]).is_err());
+    }
+
+    #[test]
+    fn test_set_updates_need_quorum() {
+        let keys = keys();
+        let mut set = OracleSet::new(2, keys.iter().map(|k| k.verifying_key().to_bytes()).collect()).unwrap();
+        let tx = lock();
+        let replacement = SigningKey::from_bytes(&[7; 32]);
+        let old = keys[0].verifying_key().to_bytes();
+
+        let mut rotate = OracleSetUpdate::new(0, OracleSetChange::Rotate { old, new: replacement.verifying_key().to_bytes() });
+        rotate.approve(&keys[0]);
+        assert!(matches!(set.apply(&rotate), Err(AxiomError::Unauthorized)));
+        rotate.approve(&keys[1]);
+        set.apply(&rotate).unwrap();
+        assert_eq!(set.epoch, 1);
+        // Replaying the approvals against the new epoch fails.
+        assert!(set.apply(&rotate).is_err());
+
+        // The rotated-out key no longer counts.
+        let stale = LockAttestation::sign(&tx, &keys[0]);
+        let fresh = LockAttestation::sign(&tx, &replacement);
+        let other = LockAttestation::sign(&tx, &keys[2]);
+        assert_eq!(set.count_valid(&tx, &[stale, other.clone()]), 1);
+        assert!(set.check_quorum(&tx, &[fresh, other]).is_ok());
+
+        let mut raise = OracleSetUpdate::new(1, OracleSetChange::SetThreshold { threshold: 4 });
+        raise.approve(&replacement);
+        raise.approve(&keys[2]);
+        assert!(matches!(set.apply(&raise), Err(AxiomError::InvalidConfig(_))));
+        assert_eq!(set.epoch, 1);
+    }
+}
@@ -0,0 +1,536 @@
+// src/bridge/cosmos.rs - Cosmos side of the bridge, over an IBC channel
+//
+// Wrapped AXM on a Cosmos zone leaves for Axiom as an ICS-20 transfer on
+// the bridge's IBC channel. Each `send_packet` on that channel carrying the
+// wrapped denom is a lock: the packet data names the sender, the Axiom
+// receiver and the amount. Transfers are found with CometBFT `tx_search`
+// over a height range; Tendermint blocks are final once committed, so no
+// lag or extra depth is needed.
+//
+// Mints and burns execute the bridge's CosmWasm contract. Transactions are
+// protobuf-encoded by hand, signed in SIGN_MODE_DIRECT with the relayer's
+// secp256k1 key and broadcast through the REST (LCD) endpoint.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use discv5::enr::k256;
+use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+use serde_json::{json, Value};
+
+use super::adapter::{ChainAdapter, ChainReceipt, LockEvent};
+use super::cross_chain::{BridgeOracle, BridgeTransaction, ChainId};
+use crate::error::{AxiomError, Result};
+
+/// Gas limit for a bridge contract call. Mints and burns touch a handful
+/// of storage keys; this leaves headroom without an extra simulate call.
+pub const EXECUTE_GAS_LIMIT: u64 = 300_000;
+
+/// Fee per unit of gas, in the fee denom's smallest unit (thousandths).
+const GAS_PRICE_MILLI: u64 = 25;
+
+/// Fee paid for each bridge contract call, in the fee denom.
+pub const EXECUTE_FEE: u64 = EXECUTE_GAS_LIMIT * GAS_PRICE_MILLI / 1000;
+
+/// How long a broadcast transaction may take to land in a block.
+pub const INCLUSION_TIMEOUT: Duration = Duration::from_secs(120);
+
+const INCLUSION_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Results per `tx_search` page (the CometBFT maximum).
+const TX_SEARCH_PAGE: u64 = 100;
+
+const MSG_EXECUTE_CONTRACT: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+const SECP256K1_PUBKEY: &str = "/cosmos.crypto.secp256k1.PubKey";
+const SIGN_MODE_DIRECT: u64 = 1;
+
+fn proto_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Length-delimited field (strings, bytes, embedded messages).
+fn proto_bytes(field: u64, bytes: &[u8], out: &mut Vec<u8>) {
+    proto_varint(field << 3 | 2, out);
+    proto_varint(bytes.len() as u64, out);
+    out.extend_from_slice(bytes);
+}
+
+/// Varint field, omitted at zero as proto3 requires.
+fn proto_uint(field: u64, value: u64, out: &mut Vec<u8>) {
+    if value != 0 {
+        proto_varint(field << 3, out);
+        proto_varint(value, out);
+    }
+}
+
+fn proto_any(type_url: &str, value: &[u8]) -> Vec<u8> {
+    let mut any = Vec::new();
+    proto_bytes(1, type_url.as_bytes(), &mut any);
+    proto_bytes(2, value, &mut any);
+    any
+}
+
+fn proto_coin(denom: &str, amount: u64) -> Vec<u8> {
+    let mut coin = Vec::new();
+    proto_bytes(1, denom.as_bytes(), &mut coin);
+    proto_bytes(2, amount.to_string().as_bytes(), &mut coin);
+    coin
+}
+
+/// `TxBody` with one `MsgExecuteContract` and no funds.
+pub fn execute_body(sender: &str, contract: &str, msg: &Value) -> Vec<u8> {
+    let mut execute = Vec::new();
+    proto_bytes(1, sender.as_bytes(), &mut execute);
+    proto_bytes(2, contract.as_bytes(), &mut execute);
+    proto_bytes(3, msg.to_string().as_bytes(), &mut execute);
+    let mut body = Vec::new();
+    proto_bytes(1, &proto_any(MSG_EXECUTE_CONTRACT, &execute), &mut body);
+    body
+}
+
+/// `AuthInfo` for a single direct-mode secp256k1 signer.
+pub fn auth_info(public_key: &[u8], sequence: u64, fee: u64, fee_denom: &str, gas_limit: u64) -> Vec<u8> {
+    let mut key = Vec::new();
+    proto_bytes(1, public_key, &mut key);
+    let mut single = Vec::new();
+    proto_uint(1, SIGN_MODE_DIRECT, &mut single);
+    let mut mode_info = Vec::new();
+    proto_bytes(1, &single, &mut mode_info);
+    let mut signer_info = Vec::new();
+    proto_bytes(1, &proto_any(SECP256K1_PUBKEY, &key), &mut signer_info);
+    proto_bytes(2, &mode_info, &mut signer_info);
+    proto_uint(3, sequence, &mut signer_info);
+    let mut fee_msg = Vec::new();
+    proto_bytes(1, &proto_coin(fee_denom, fee), &mut fee_msg);
+    proto_uint(2, gas_limit, &mut fee_msg);
+    let mut auth = Vec::new();
+    proto_bytes(1, &signer_info, &mut auth);
+    proto_bytes(2, &fee_msg, &mut auth);
+    auth
+}
+
+pub fn sign_doc(body: &[u8], auth_info: &[u8], chain_id: &str, account_number: u64) -> Vec<u8> {
+    let mut doc = Vec::new();
+    proto_bytes(1, body, &mut doc);
+    proto_bytes(2, auth_info, &mut doc);
+    proto_bytes(3, chain_id.as_bytes(), &mut doc);
+    proto_uint(4, account_number, &mut doc);
+    doc
+}
+
+/// The relayer's account on the Cosmos zone. The address is configured
+/// rather than derived, as it depends on the zone's bech32 prefix.
+#[derive(Clone)]
+pub struct CosmosSigner {
+    key: SigningKey,
+    pub address: String,
+}
+
+impl std::fmt::Debug for CosmosSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CosmosSigner").field("address", &self.address).finish()
+    }
+}
+
+impl CosmosSigner {
+    pub fn from_hex(secret: &str, address: String) -> Result<Self> {
+        let bytes = hex::decode(secret.trim().trim_start_matches("0x")).map_err(|_| AxiomError::InvalidPrivateKey)?;
+        let key = SigningKey::from_slice(&bytes).map_err(|_| AxiomError::InvalidPrivateKey)?;
+        Ok(Self { key, address })
+    }
+
+    /// Compressed SEC1 public key, as Cosmos expects.
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key.verifying_key().to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    /// 64-byte low-S signature over the SHA-256 of `sign_doc`.
+    pub fn sign(&self, sign_doc: &[u8]) -> Vec<u8> {
+        let signature: Signature = self.key.sign(sign_doc);
+        signature.normalize_s().unwrap_or(signature).to_bytes().to_vec()
+    }
+}
+
+/// An event attribute by key. CometBFT before 0.37 base64-encodes keys and
+/// values; later versions send them as plain strings.
+fn event_attribute(event: &Value, key: &str) -> Option<String> {
+    let base64 = |text: &str| {
+        data_encoding::BASE64.decode(text.as_bytes()).ok().and_then(|b| String::from_utf8(b).ok())
+    };
+    event["attributes"].as_array()?.iter().find_map(|attribute| {
+        let raw_key = attribute["key"].as_str()?;
+        let raw_value = attribute["value"].as_str().unwrap_or_default();
+        if raw_key == key {
+            Some(raw_value.to_string())
+        } else if base64(raw_key).as_deref() == Some(key) {
+            base64(raw_value)
+        } else {
+            None
+        }
+    })
+}
+
+/// ICS-20 transfers of `denom` out over `channel` in a `tx_search` result.
+/// The packet sequence serves as the log index.
+pub fn parse_transfer_locks(txs: &[Value], channel: &str, denom: &str) -> Vec<LockEvent> {
+    let mut events = Vec::new();
+    for tx in txs {
+        if tx["tx_result"]["code"].as_u64().unwrap_or(0) != 0 {
+            continue;
+        }
+        let (Some(hash), Some(height)) = (
+            tx["hash"].as_str(),
+            tx["height"].as_str().and_then(|h| h.parse::<u64>().ok()),
+        ) else {
+            continue;
+        };
+        let packets = tx["tx_result"]["events"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|event| event["type"] == "send_packet");
+        for packet in packets {
+            if event_attribute(packet, "packet_src_channel").as_deref() != Some(channel) {
+                continue;
+            }
+            let (Some(sequence), Some(data)) = (
+                event_attribute(packet, "packet_sequence").and_then(|s| s.parse::<u64>().ok()),
+                event_attribute(packet, "packet_data").and_then(|d| serde_json::from_str::<Value>(&d).ok()),
+            ) else {
+                continue;
+            };
+            // A voucher returning to its source carries its trace path.
+            let packet_denom = data["denom"].as_str().unwrap_or_default();
+            if packet_denom != denom && !packet_denom.ends_with(&format!("/{}", denom)) {
+                continue;
+            }
+            let amount = data["amount"].as_str().and_then(|a| a.parse::<u64>().ok()).unwrap_or(0);
+            let (Some(sender), Some(receiver)) = (data["sender"].as_str(), data["receiver"].as_str()) else {
+                continue;
+            };
+            if amount > 0 {
+                events.push(LockEvent {
+                    sender: sender.to_string(),
+                    recipient: receiver.to_string(),
+                    amount,
+                    block_number: height,
+                    tx_hash: hash.to_string(),
+                    log_index: sequence,
+                });
+            }
+        }
+    }
+    events
+}
+
+/// Where the Cosmos side of the bridge lives.
+#[derive(Debug, Clone)]
+pub struct CosmosConfig {
+    /// CometBFT RPC endpoint (`AXIOM_RPC_COSMOS`).
+    pub rpc_url: String,
+    /// Cosmos SDK REST endpoint (`AXIOM_REST_COSMOS`).
+    pub rest_url: String,
+    /// The zone's chain ID, signed into every transaction (`AXIOM_COSMOS_CHAIN_ID`).
+    pub chain_id: String,
+    /// IBC channel wrapped AXM leaves on (`AXIOM_COSMOS_CHANNEL`).
+    pub channel: String,
+    /// Base denom of wrapped AXM (`AXIOM_COSMOS_DENOM`).
+    pub denom: String,
+    /// Bridge CosmWasm contract that mints and burns (`AXIOM_COSMOS_CONTRACT`).
+    pub contract: Option<String>,
+    /// Denom fees are paid in (`AXIOM_COSMOS_FEE_DENOM`).
+    pub fee_denom: String,
+}
+
+impl CosmosConfig {
+    pub fn from_env() -> Result<Self> {
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.is_empty());
+        let or = |key: &str, default: &str| var(key).unwrap_or_else(|| default.to_string());
+        Ok(Self {
+            rpc_url: BridgeOracle::resolve_rpc_url(&ChainId::Cosmos)?,
+            rest_url: or("AXIOM_REST_COSMOS", "https://cosmos-rest.publicnode.com"),
+            chain_id: or("AXIOM_COSMOS_CHAIN_ID", "cosmoshub-4"),
+            channel: or("AXIOM_COSMOS_CHANNEL", "channel-0"),
+            denom: or("AXIOM_COSMOS_DENOM", "uwaxm"),
+            contract: var("AXIOM_COSMOS_CONTRACT"),
+            fee_denom: or("AXIOM_COSMOS_FEE_DENOM", "uatom"),
+        })
+    }
+}
+
+/// A Cosmos zone through CometBFT RPC and the SDK REST API.
+pub struct CosmosAdapter {
+    config: CosmosConfig,
+    signer: Option<CosmosSigner>,
+    http: reqwest::Client,
+}
+
+impl CosmosAdapter {
+    pub fn new(config: CosmosConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| AxiomError::NetworkError(format!("HTTP client error: {}", e)))?;
+        Ok(Self { config, signer: None, http })
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Self::new(CosmosConfig::from_env()?)
+    }
+
+    pub fn with_signer(mut self, signer: CosmosSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+        let resp = self
+            .http
+            .post(&self.config.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("{} to {} failed: {}", method, self.config.rpc_url, e)))?;
+        let mut json: Value = resp
+            .json()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("Failed to parse RPC response: {}", e)))?;
+        if let Some(err) = json.get("error") {
+            return Err(AxiomError::NetworkError(format!("{} error: {}", method, err)));
+        }
+        Ok(json["result"].take())
+    }
+
+    /// GET from the REST API. `None` on 404.
+    async fn rest_get(&self, path: &str) -> Result<Option<Value>> {
+        let url = format!("{}{}", self.config.rest_url.trim_end_matches('/'), path);
+        let resp = self.http.get(&url).send().await
+            .map_err(|e| AxiomError::NetworkError(format!("GET {} failed: {}", url, e)))?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            return Err(AxiomError::NetworkError(format!("GET {} returned HTTP {}", url, resp.status())));
+        }
+        resp.json().await
+            .map(Some)
+            .map_err(|e| AxiomError::NetworkError(format!("Failed to parse {}: {}", url, e)))
+    }
+
+    /// `(account_number, sequence)` of `address`.
+    async fn account(&self, address: &str) -> Result<(u64, u64)> {
+        let json = self.rest_get(&format!("/cosmos/auth/v1beta1/accounts/{}", address)).await?
+            .ok_or_else(|| AxiomError::BridgeError(format!("Cosmos account {} does not exist", address)))?;
+        let number = |key: &str| json["account"][key].as_str().and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+        Ok((number("account_number"), number("sequence")))
+    }
+
+    async fn execute(&self, msg: Value) -> Result<ChainReceipt> {
+        let signer = self.signer.as_ref()
+            .ok_or_else(|| AxiomError::BridgeError("No Cosmos signer configured".into()))?;
+        let contract = self.config.contract.as_deref()
+            .ok_or_else(|| AxiomError::InvalidConfig("AXIOM_COSMOS_CONTRACT is not set".into()))?;
+        let (account_number, sequence) = self.account(&signer.address).await?;
+        let body = execute_body(&signer.address, contract, &msg);
+        let auth = auth_info(&signer.public_key(), sequence, EXECUTE_FEE, &self.config.fee_denom, EXECUTE_GAS_LIMIT);
+        let signature = signer.sign(&sign_doc(&body, &auth, &self.config.chain_id, account_number));
+        let mut raw = Vec::new();
+        proto_bytes(1, &body, &mut raw);
+        proto_bytes(2, &auth, &mut raw);
+        proto_bytes(3, &signature, &mut raw);
+
+        let url = format!("{}/cosmos/tx/v1beta1/txs", self.config.rest_url.trim_end_matches('/'));
+        let resp: Value = self.http
+            .post(&url)
+            .json(&json!({ "tx_bytes": data_encoding::BASE64.encode(&raw), "mode": "BROADCAST_MODE_SYNC" }))
+            .send()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("POST {} failed: {}", url, e)))?
+            .json()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("Failed to parse broadcast response: {}", e)))?;
+        let response = &resp["tx_response"];
+        if response["code"].as_u64().unwrap_or(0) != 0 {
+            return Err(AxiomError::BridgeError(format!("Cosmos broadcast rejected: {}", response["raw_log"])));
+        }
+        let tx_hash = response["txhash"].as_str()
+            .ok_or_else(|| AxiomError::NetworkError(format!("missing txhash in broadcast response: {}", resp)))?
+            .to_string();
+        log::info!("📤 Sent {} on {}", tx_hash, self.config.chain_id);
+
+        let height = self.wait_included(&tx_hash).await?;
+        Ok(ChainReceipt { tx_hash, sender: signer.address.clone(), block_number: height })
+    }
+
+    async fn wait_included(&self, tx_hash: &str) -> Result<u64> {
+        let deadline = tokio::time::Instant::now() + INCLUSION_TIMEOUT;
+        loop {
+            match self.rest_get(&format!("/cosmos/tx/v1beta1/txs/{}", tx_hash)).await {
+                Ok(Some(json)) => {
+                    let response = &json["tx_response"];
+                    if response["code"].as_u64().unwrap_or(0) != 0 {
+                        return Err(AxiomError::BridgeError(format!("{} failed: {}", tx_hash, response["raw_log"])));
+                    }
+                    if let Some(height) = response["height"].as_str().and_then(|h| h.parse::<u64>().ok()) {
+                        return Ok(height);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log::debug!("Inclusion poll for {} failed: {}", tx_hash, e),
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(AxiomError::RpcTimeout);
+            }
+            tokio::time::sleep(INCLUSION_POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[async_trait]
+impl ChainAdapter for CosmosAdapter {
+    fn chain(&self) -> ChainId {
+        ChainId::Cosmos
+    }
+
+    /// A committed Tendermint block is final.
+    fn required_confirmations(&self) -> u32 {
+        1
+    }
+
+    async fn head(&self) -> Result<u64> {
+        let status = self.rpc("status", json!({})).await?;
+        status["sync_info"]["latest_block_height"]
+            .as_str()
+            .and_then(|h| h.parse().ok())
+            .ok_or_else(|| AxiomError::NetworkError("invalid CometBFT status response".into()))
+    }
+
+    async fn lock_events(&self, from: u64, to: u64) -> Result<Vec<LockEvent>> {
+        let query = format!(
+            "send_packet.packet_src_channel='{}' AND tx.height>={} AND tx.height<={}",
+            self.config.channel, from, to
+        );
+        let mut txs = Vec::new();
+        for page in 1.. {
+            let result = self
+                .rpc(
+                    "tx_search",
+                    json!({
+                        "query": query,
+                        "prove": false,
+                        "page": page.to_string(),
+                        "per_page": TX_SEARCH_PAGE.to_string(),
+                        "order_by": "asc",
+                    }),
+                )
+                .await?;
+            let batch = result["txs"].as_array().cloned().unwrap_or_default();
+            let total = result["total_count"].as_str().and_then(|t| t.parse::<u64>().ok()).unwrap_or(0);
+            let done = batch.is_empty() || page * TX_SEARCH_PAGE >= total;
+            txs.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(parse_transfer_locks(&txs, &self.config.channel, &self.config.denom))
+    }
+
+    async fn submit_mint(&self, bridge_tx: &BridgeTransaction) -> Result<ChainReceipt> {
+        self.execute(json!({
+            "mint": {
+                "recipient": bridge_tx.recipient,
+                "amount": bridge_tx.amount.to_string(),
+                "bridge_id": hex::encode(bridge_tx.id),
+            }
+        }))
+        .await
+    }
+
+    async fn submit_burn(&self, amount: u64, bridge_id: [u8; 32]) -> Result<ChainReceipt> {
+        self.execute(json!({
+            "burn": { "amount": amount.to_string(), "bridge_id": hex::encode(bridge_id) }
+        }))
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::signature::Verifier;
+
+    #[test]
+    fn test_direct_sign_encoding() {
+        assert_eq!(proto_coin("uatom", 100), b"\x0a\x05uatom\x12\x03100");
+        let mut varint = Vec::new();
+        proto_varint(300, &mut varint);
+        assert_eq!(varint, [0xac, 0x02]);
+
+        let signer = CosmosSigner::from_hex(&"11".repeat(32), "cosmos1relayer".into()).unwrap();
+        assert_eq!(signer.public_key().len(), 33);
+        assert!(format!("{:?}", signer).contains("cosmos1relayer"));
+        let body = execute_body(&signer.address, "cosmos1contract", &json!({"burn": {"amount": "5"}}));
+        let auth = auth_info(&signer.public_key(), 0, 7_500, "uatom", EXECUTE_GAS_LIMIT);
+        // Zero sequence and account number are left out, as the chain
+        // leaves them out when it rebuilds the sign doc.
+        let doc = sign_doc(&body, &auth, "cosmoshub-4", 0);
+        assert!(doc.ends_with(b"\x1a\x0bcosmoshub-4"));
+        assert_ne!(doc, sign_doc(&body, &auth, "cosmoshub-4", 12));
+
+        let signature = Signature::from_slice(&signer.sign(&doc)).unwrap();
+        assert!(signature.normalize_s().is_none());
+        assert!(signer.key.verifying_key().verify(&doc, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_parse_transfer_locks() {
+        let b64 = |s: &str| data_encoding::BASE64.encode(s.as_bytes());
+        let data = |denom: &str, amount: &str| {
+            json!({ "denom": denom, "amount": amount, "sender": "cosmos1alice", "receiver": "axm1bob" }).to_string()
+        };
+        let txs = vec![
+            // CometBFT 0.37+: plain attributes.
+            json!({ "hash": "AA", "height": "10", "tx_result": { "code": 0, "events": [
+                { "type": "send_packet", "attributes": [
+                    { "key": "packet_src_channel", "value": "channel-7" },
+                    { "key": "packet_sequence", "value": "3" },
+                    { "key": "packet_data", "value": data("transfer/channel-7/uwaxm", "500") },
+                ]},
+                { "type": "send_packet", "attributes": [
+                    { "key": "packet_src_channel", "value": "channel-7" },
+                    { "key": "packet_sequence", "value": "4" },
+                    { "key": "packet_data", "value": data("uatom", "900") },
+                ]},
+            ]}}),
+            // CometBFT 0.34: base64 attributes.
+            json!({ "hash": "BB", "height": "11", "tx_result": { "code": 0, "events": [
+                { "type": "send_packet", "attributes": [
+                    { "key": b64("packet_src_channel"), "value": b64("channel-7") },
+                    { "key": b64("packet_sequence"), "value": b64("5") },
+                    { "key": b64("packet_data"), "value": b64(&data("uwaxm", "25")) },
+                ]},
+            ]}}),
+            // Failed transaction and another channel: not locks.
+            json!({ "hash": "CC", "height": "12", "tx_result": { "code": 5, "events": [] }}),
+            json!({ "hash": "DD", "height": "12", "tx_result": { "code": 0, "events": [
+                { "type": "send_packet", "attributes": [
+                    { "key": "packet_src_channel", "value": "channel-1" },
+                    { "key": "packet_sequence", "value": "6" },
+                    { "key": "packet_data", "value": data("uwaxm", "1") },
+                ]},
+            ]}}),
+        ];
+        let locks = parse_transfer_locks(&txs, "channel-7", "uwaxm");
+        assert_eq!(locks.len(), 2);
+        assert_eq!(
+            (locks[0].amount, locks[0].block_number, locks[0].log_index, locks[0].recipient.as_str()),
+            (500, 10, 3, "axm1bob")
+        );
+        assert_eq!((locks[1].amount, locks[1].tx_hash.as_str(), locks[1].log_index), (25, "BB", 5));
+    }
+}
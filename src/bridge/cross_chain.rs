@@ -1,10 +1,26 @@
 // src/bridge/cross_chain.rs - Axiom Protocol Cross-Chain Bridge
-// Supports: Ethereum, BSC, Polygon, Arbitrum, Optimism
+// Supports: Ethereum, BSC, Polygon, Arbitrum, Optimism, Solana, Cosmos (IBC)
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use sha2::{Sha256, Digest};
 
+use super::attestation::{LockAttestation, OracleSet};
+use super::adapter::{self, ChainAdapter, EvmAdapter, LockEvent};
+use super::evm::EvmSigner;
+use super::fees::{FeeOracle, FeeQuote};
+use super::lock_proof::LockInclusionProof;
+use crate::error::{AxiomError, Result};
+use crate::guardian::bridge_guard::{BridgeGuard, BridgeLimits};
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 /// Cached Ethereum RPC URL from the AXIOM_RPC_ETHEREUM environment variable.
 /// Read once at first access to avoid per-call memory allocation.
 static ETH_RPC_URL: once_cell::sync::Lazy<String> = once_cell::sync::Lazy::new(|| {
@@ -23,12 +39,14 @@ pub enum ChainId {
     Optimism,       // Optimism (Chain ID: 10)
     Avalanche,      // Avalanche C-Chain (Chain ID: 43114)
     Fantom,         // Fantom Opera (Chain ID: 250)
+    Solana,         // Solana mainnet-beta
+    Cosmos,         // Cosmos zone reached over an IBC channel
 }
 
 impl ChainId {
     pub fn chain_id(&self) -> u64 {
         match self {
-            ChainId::Axiom => 84000,        // Custom chain ID for Axiom
+            ChainId::Axiom => crate::AXIOM_CHAIN_ID, // Custom chain ID for Axiom
             ChainId::Ethereum => 1,
             ChainId::BSC => 56,
             ChainId::Polygon => 137,
@@ -36,6 +54,10 @@ impl ChainId {
             ChainId::Optimism => 10,
             ChainId::Avalanche => 43114,
             ChainId::Fantom => 250,
+            // No EIP-155 ID: SLIP-44 coin types, used only to separate
+            // bridge hashes by chain
+            ChainId::Solana => 501,
+            ChainId::Cosmos => 118,
         }
     }
     
@@ -49,6 +71,8 @@ impl ChainId {
             ChainId::Optimism => "https://mainnet.optimism.io",
             ChainId::Avalanche => "https://api.avax.network/ext/bc/C/rpc",
             ChainId::Fantom => "https://rpc.ftm.tools",
+            ChainId::Solana => "https://api.mainnet-beta.solana.com",
+            ChainId::Cosmos => "https://cosmos-rpc.publicnode.com",
         }
     }
     
@@ -62,10 +86,33 @@ impl ChainId {
             ChainId::Optimism => "ETH",
             ChainId::Avalanche => "AVAX",
             ChainId::Fantom => "FTM",
+            ChainId::Solana => "SOL",
+            ChainId::Cosmos => "ATOM",
+        }
+    }
+    
+    /// Decimal places of the native token's smallest unit.
+    pub fn native_decimals(&self) -> u32 {
+        match self {
+            ChainId::Axiom => crate::chain::DECIMALS,
+            ChainId::Solana => 9,       // lamports
+            ChainId::Cosmos => 6,       // uatom
+            _ => 18,                    // wei
         }
     }
+    
+    /// Whether the chain runs the EVM bridge contract.
+    pub fn is_evm(&self) -> bool {
+        !matches!(self, ChainId::Axiom | ChainId::Solana | ChainId::Cosmos)
+    }
 }
 
+/// keccak256("Lock(address,address,uint256)"), topic 0 of a bridge lock log.
+pub const LOCK_TOPIC: &str = "0x625fed9875dada8643f2418b838ae0bc78d9a148a18eee4ee1979ff0f3f5d427";
+
+/// How long a bridge has to be minted before its lock can be refunded.
+pub const BRIDGE_EXPIRY_SECS: u64 = 24 * 60 * 60;
+
 /// Cross-chain bridge transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeTransaction {
@@ -80,11 +127,33 @@ pub struct BridgeTransaction {
     pub timestamp: u64,
     pub confirmations: u32,
     pub required_confirmations: u32,
-    pub zk_proof: Vec<u8>,         // Privacy-preserving bridge proof
+    /// For bridges out of Axiom, a serialized [`LockInclusionProof`]
+    /// checked before minting. Empty until the lock's block is signed.
+    pub zk_proof: Vec<u8>,
     /// Block number on the source chain when the lock was created.
     /// Used by [`BridgeOracle::update_confirmations`] to compute how many
     /// blocks have elapsed since the lock.
     pub lock_block: u64,
+    /// Unix time after which the bridge can no longer be minted and the
+    /// lock may be refunded on the source chain. Zero never expires.
+    #[serde(default)]
+    pub expires_at: u64,
+}
+
+impl BridgeTransaction {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// Whether the sender may take the locked funds back: the bridge failed,
+    /// or expired before it was minted.
+    pub fn is_refundable(&self, now: u64) -> bool {
+        match self.status {
+            BridgeStatus::Failed { .. } => true,
+            BridgeStatus::Minted | BridgeStatus::Refunded => false,
+            _ => self.is_expired(now),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -94,26 +163,117 @@ pub enum BridgeStatus {
     ReadyToMint,
     Minted,
     Failed { reason: String },
+    /// The lock was released back to the sender on the source chain.
+    Refunded,
 }
 
-/// A lock event discovered on an external EVM chain via `eth_getLogs`.
-#[derive(Debug, Clone)]
-struct LockEvent {
-    sender: String,
-    recipient: String,
-    amount: u64,
+/// Blocks behind the source-chain head left unscanned, so a shallow reorg
+/// cannot remove a lock after it has been picked up.
+pub const LOCK_SCAN_LAG: u64 = 6;
+
+/// How far back the first scan of a chain reaches.
+pub const LOCK_SCAN_LOOKBACK: u64 = 100;
+
+/// Largest block range asked for in one `eth_getLogs` call. Halved while
+/// the provider rejects a range as too large.
+pub const LOCK_SCAN_WINDOW: u64 = 2_000;
+
+/// The last source-chain block scanned for lock events, per chain.
+/// Everything up to and including it has been seen.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockCursor {
+    scanned: HashMap<ChainId, u64>,
 }
 
-/// Bridge contract on EVM chains (deployed via CREATE2 for same address)
+impl LockCursor {
+    /// Load the cursor at `path`, or start fresh if there is none.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| AxiomError::DatabaseError(format!("{}: {}", path.display(), e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| AxiomError::SerializationError(e.to_string()))?;
+        crate::storage::write_atomic(path.as_ref(), &json)?;
+        Ok(())
+    }
+
+    pub fn get(&self, chain: &ChainId) -> Option<u64> {
+        self.scanned.get(chain).copied()
+    }
+
+    /// Mark blocks up to `block` as scanned. Never moves backwards.
+    pub fn advance(&mut self, chain: &ChainId, block: u64) {
+        let scanned = self.scanned.entry(chain.clone()).or_insert(block);
+        *scanned = (*scanned).max(block);
+    }
+
+    /// The next inclusive block range to scan on `chain`, given the highest
+    /// block deep enough to scan, or `None` when caught up.
+    pub fn next_range(&self, chain: &ChainId, safe_head: u64, window: u64) -> Option<(u64, u64)> {
+        let from = match self.get(chain) {
+            Some(scanned) => scanned + 1,
+            None => safe_head.saturating_sub(LOCK_SCAN_LOOKBACK),
+        };
+        (from <= safe_head).then(|| (from, safe_head.min(from + window.max(1) - 1)))
+    }
+}
+
+/// Whether an `eth_getLogs` error is the provider refusing the range size
+/// or result count, so a narrower range may succeed.
+fn is_range_limit_error(err: &AxiomError) -> bool {
+    let message = err.to_string().to_lowercase();
+    ["range", "too many", "more than", "limit exceeded", "response size", "-32005"]
+        .iter()
+        .any(|needle| message.contains(needle))
+}
+
+/// Bridge contract on a chain (on EVM chains deployed via CREATE2 for
+/// the same address)
 pub struct BridgeContract {
     pub address: String,            // Same on all EVM chains (CREATE2)
     pub chain: ChainId,
+    /// Lock detection, finality and submission on an external chain.
+    /// `None` on Axiom, where locks are tracked locally.
+    pub adapter: Option<Arc<dyn ChainAdapter>>,
 }
 
 impl BridgeContract {
     /// Canonical bridge address (same on all chains via CREATE2)
     pub const BRIDGE_ADDRESS: &'static str = "0x8400000000000000000000000000000000000001";
     
+    /// Contract on `chain` using the chain's default adapter.
+    pub fn new(chain: ChainId) -> Result<Self> {
+        Ok(Self {
+            address: Self::BRIDGE_ADDRESS.to_string(),
+            adapter: adapter::default_adapter(&chain)?,
+            chain,
+        })
+    }
+    
+    fn adapter(&self) -> Result<&dyn ChainAdapter> {
+        self.adapter.as_deref()
+            .ok_or_else(|| AxiomError::BridgeError(format!("No adapter for {:?}", self.chain)))
+    }
+    
+    /// Current height of this contract's chain.
+    pub async fn head(&self) -> Result<u64> {
+        match &self.adapter {
+            Some(adapter) => adapter.head().await,
+            None => {
+                // Read from local chain storage
+                let blocks = crate::storage::load_chain();
+                Ok(blocks.map(|b| b.len() as u64).unwrap_or(1))
+            }
+        }
+    }
+    
     /// Lock tokens on source chain
     pub async fn lock_tokens(
         &self,
@@ -121,14 +281,12 @@ impl BridgeContract {
         amount: u64,
         destination_chain: ChainId,
         recipient: String,
-    ) -> Result<BridgeTransaction, String> {
+    ) -> Result<BridgeTransaction> {
         log::info!("🔒 Locking {} AXM on {:?} for {:?}", amount, self.chain, destination_chain);
         
-        // Generate ZK proof of lock
-        let zk_proof = self.generate_lock_proof(sender.clone(), amount)?;
-        
         // Record the current block on the source chain so we can track confirmations
-        let lock_block = BridgeOracle::get_block_number_static(&self.chain).await.unwrap_or(0);
+        let lock_block = self.head().await.unwrap_or(0);
+        let now = unix_now();
         
         Ok(BridgeTransaction {
             id: Self::generate_bridge_id(&sender, amount, &destination_chain),
@@ -139,39 +297,76 @@ impl BridgeContract {
             amount,
             token: "AXM".to_string(),
             status: BridgeStatus::Pending,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: now,
             confirmations: 0,
             required_confirmations: self.required_confirmations(),
-            zk_proof,
+            zk_proof: vec![],
             lock_block,
+            expires_at: now + BRIDGE_EXPIRY_SECS,
         })
     }
     
-    /// Mint wrapped tokens on destination chain
+    /// Mint wrapped tokens on destination chain. A lock on Axiom must come
+    /// with an inclusion proof whose header `oracles` signed; locks on EVM
+    /// chains are covered by the attestation quorum checked by the caller.
     pub async fn mint_wrapped(
         &self,
         bridge_tx: &BridgeTransaction,
-    ) -> Result<String, String> {
+        oracles: &OracleSet,
+    ) -> Result<String> {
         if bridge_tx.to_chain != self.chain {
-            return Err("Wrong destination chain".to_string());
+            return Err(AxiomError::BridgeError("Wrong destination chain".to_string()));
         }
         
         if bridge_tx.status != BridgeStatus::ReadyToMint {
-            return Err("Bridge transaction not ready to mint".to_string());
+            return Err(AxiomError::BridgeError("Bridge transaction not ready to mint".to_string()));
         }
         
-        // Verify ZK proof
-        if !self.verify_bridge_proof(&bridge_tx.zk_proof)? {
-            return Err("Invalid bridge proof".to_string());
+        // Past expiry the lock may be refunded; minting too would pay twice
+        if bridge_tx.is_expired(unix_now()) {
+            return Err(AxiomError::BridgeError("Bridge transaction expired".to_string()));
+        }
+        
+        if bridge_tx.from_chain == ChainId::Axiom {
+            if bridge_tx.zk_proof.is_empty() {
+                return Err(AxiomError::ProofVerificationFailed("Missing lock inclusion proof".to_string()));
+            }
+            LockInclusionProof::from_bytes(&bridge_tx.zk_proof)?.verify(bridge_tx, oracles)?;
         }
         
         log::info!("🌉 Minting {} wAXM on {:?} to {}", 
                  bridge_tx.amount, self.chain, bridge_tx.recipient);
         
-        Ok(format!("0x{}", hex::encode(bridge_tx.id)))
+        if self.chain == ChainId::Axiom {
+            return Ok(format!("0x{}", hex::encode(bridge_tx.id)));
+        }
+        
+        // Returns only once the mint is final on this chain
+        let receipt = self.adapter()?.submit_mint(bridge_tx).await?;
+        Ok(receipt.tx_hash)
+    }
+    
+    /// Release a failed or expired lock back to its sender on this (the
+    /// source) chain. Returns the refund transaction hash.
+    pub async fn refund_lock(&self, bridge_tx: &BridgeTransaction, now: u64) -> Result<String> {
+        if bridge_tx.from_chain != self.chain {
+            return Err(AxiomError::BridgeError("Wrong source chain".to_string()));
+        }
+        
+        if !bridge_tx.is_refundable(now) {
+            return Err(AxiomError::BridgeError(format!(
+                "Bridge transaction not refundable in state {:?}",
+                bridge_tx.status
+            )));
+        }
+        
+        log::info!("↩️ Refunding {} {} on {:?} to {}",
+                 bridge_tx.amount, bridge_tx.token, self.chain, bridge_tx.sender);
+        
+        let mut hasher = Sha256::new();
+        hasher.update(b"axiom-bridge-refund");
+        hasher.update(bridge_tx.id);
+        Ok(format!("0x{}", hex::encode(hasher.finalize())))
     }
     
     /// Burn wrapped tokens and unlock on source chain
@@ -180,42 +375,42 @@ impl BridgeContract {
         amount: u64,
         source_chain: ChainId,
         recipient: String,
-    ) -> Result<BridgeTransaction, String> {
+    ) -> Result<BridgeTransaction> {
         log::info!("🔥 Burning {} wAXM on {:?}, unlocking on {:?}", 
                  amount, self.chain, source_chain);
         
-        let lock_block = BridgeOracle::get_block_number_static(&self.chain).await.unwrap_or(0);
+        let id = Self::generate_bridge_id(&recipient, amount, &source_chain);
+        let (sender, lock_block) = match &self.adapter {
+            Some(adapter) => {
+                let receipt = adapter.submit_burn(amount, id).await?;
+                (receipt.sender, receipt.block_number)
+            }
+            None => ("wrapped_contract".to_string(), self.head().await.unwrap_or(0)),
+        };
+        let now = unix_now();
         
         Ok(BridgeTransaction {
-            id: Self::generate_bridge_id(&recipient, amount, &source_chain),
+            id,
             from_chain: self.chain.clone(),
             to_chain: source_chain,
-            sender: "wrapped_contract".to_string(),
+            sender,
             recipient,
             amount,
             token: "wAXM".to_string(),
             status: BridgeStatus::Pending,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: now,
             confirmations: 0,
             required_confirmations: self.required_confirmations(),
             zk_proof: vec![],
             lock_block,
+            expires_at: now + BRIDGE_EXPIRY_SECS,
         })
     }
     
     fn required_confirmations(&self) -> u32 {
-        match self.chain {
-            ChainId::Axiom => 1,        // VDF already provides finality
-            ChainId::Ethereum => 12,    // ~3 minutes
-            ChainId::BSC => 15,         // ~45 seconds
-            ChainId::Polygon => 128,    // ~5 minutes
-            ChainId::Arbitrum => 1,     // Fast finality
-            ChainId::Optimism => 1,     // Fast finality
-            ChainId::Avalanche => 1,    // Fast finality
-            ChainId::Fantom => 1,       // Fast finality
+        match &self.adapter {
+            Some(adapter) => adapter.required_confirmations(),
+            None => 1,                  // VDF already provides finality
         }
     }
     
@@ -233,66 +428,18 @@ impl BridgeContract {
         );
         hasher.finalize().into()
     }
-    
-    fn generate_lock_proof(&self, sender: String, amount: u64) -> Result<Vec<u8>, String> {
-        // Generate a blake3 commitment proving the lock parameters.
-        // The proof commits to (sender, amount, chain_id, timestamp) so that
-        // the destination chain can verify the lock without seeing the source
-        // chain's full state.
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| format!("Clock error: {}", e))?
-            .as_secs();
-
-        let mut hasher = blake3::Hasher::new();
-        hasher.update(b"axiom-bridge-lock-proof-v1");
-        hasher.update(sender.as_bytes());
-        hasher.update(&amount.to_le_bytes());
-        hasher.update(&self.chain.chain_id().to_le_bytes());
-        hasher.update(&timestamp.to_le_bytes());
-        let commitment = hasher.finalize();
-
-        // Proof layout: [commitment: 32B] [amount: 8B] [chain_id: 8B] [timestamp: 8B]
-        let mut proof = Vec::with_capacity(56);
-        proof.extend_from_slice(commitment.as_bytes());
-        proof.extend_from_slice(&amount.to_le_bytes());
-        proof.extend_from_slice(&self.chain.chain_id().to_le_bytes());
-        proof.extend_from_slice(&timestamp.to_le_bytes());
-        Ok(proof)
-    }
-    
-    fn verify_bridge_proof(&self, proof: &[u8]) -> Result<bool, String> {
-        // Verify the bridge lock proof structure and commitment integrity.
-        if proof.len() < 56 {
-            return Ok(false);
-        }
-        let commitment = &proof[0..32];
-        let amount = u64::from_le_bytes(
-            proof[32..40].try_into().map_err(|_| "bad amount bytes")?
-        );
-        let chain_id = u64::from_le_bytes(
-            proof[40..48].try_into().map_err(|_| "bad chain_id bytes")?
-        );
-        // Verify amount is non-zero
-        if amount == 0 {
-            return Ok(false);
-        }
-        // Verify the chain_id in the proof matches this contract's chain
-        if chain_id != self.chain.chain_id() {
-            return Ok(false);
-        }
-        // Verify commitment is non-zero (not an empty proof)
-        if commitment == &[0u8; 32] {
-            return Ok(false);
-        }
-        Ok(true)
-    }
 }
 
 /// Bridge oracle - monitors chains and relays events
 pub struct BridgeOracle {
     pub contracts: HashMap<ChainId, BridgeContract>,
     pub pending_bridges: Vec<BridgeTransaction>,
+    /// How far each source chain has been scanned for lock events.
+    pub lock_cursor: LockCursor,
+    /// Oracles whose attestations authorise minting.
+    pub oracle_set: OracleSet,
+    /// Attestations collected so far, by bridge ID.
+    pub attestations: HashMap<[u8; 32], Vec<LockAttestation>>,
 }
 
 impl Default for BridgeOracle {
@@ -312,84 +459,158 @@ impl BridgeOracle {
             ChainId::Polygon,
             ChainId::Arbitrum,
             ChainId::Optimism,
+            ChainId::Solana,
+            ChainId::Cosmos,
         ] {
-            contracts.insert(
-                chain.clone(),
-                BridgeContract {
-                    address: BridgeContract::BRIDGE_ADDRESS.to_string(),
-                    chain,
+            match BridgeContract::new(chain.clone()) {
+                Ok(contract) => {
+                    contracts.insert(chain, contract);
                 }
-            );
+                Err(e) => log::warn!("Bridge to {:?} disabled: {}", chain, e),
+            }
         }
         
         Self {
             contracts,
             pending_bridges: Vec::new(),
+            lock_cursor: LockCursor::default(),
+            oracle_set: OracleSet::default(),
+            attestations: HashMap::new(),
+        }
+    }
+
+    /// Send mints and burns on every EVM chain from `signer`.
+    pub fn with_evm_signer(mut self, signer: EvmSigner) -> Self {
+        for contract in self.contracts.values_mut().filter(|c| c.chain.is_evm()) {
+            match EvmAdapter::new(contract.chain.clone()) {
+                Ok(evm) => contract.adapter = Some(Arc::new(evm.with_signer(signer.clone()))),
+                Err(e) => log::warn!("No EVM adapter for {:?}: {}", contract.chain, e),
+            }
+        }
+        self
+    }
+
+    /// Use `adapter` for its chain, e.g. one configured with a signer.
+    pub fn with_adapter(mut self, adapter: Arc<dyn ChainAdapter>) -> Self {
+        let chain = adapter.chain();
+        self.contracts
+            .entry(chain.clone())
+            .or_insert_with(|| BridgeContract {
+                address: BridgeContract::BRIDGE_ADDRESS.to_string(),
+                chain,
+                adapter: None,
+            })
+            .adapter = Some(adapter);
+        self
+    }
+
+    pub fn with_oracle_set(mut self, oracle_set: OracleSet) -> Self {
+        self.oracle_set = oracle_set;
+        self
+    }
+
+    /// Record an attestation for a tracked bridge. Returns false if this
+    /// oracle had already attested it.
+    pub fn add_attestation(&mut self, attestation: LockAttestation) -> Result<bool> {
+        let bridge = self.pending_bridges.iter()
+            .find(|b| b.id == attestation.bridge_id)
+            .ok_or_else(|| AxiomError::NotFound(format!("bridge {}", hex::encode(attestation.bridge_id))))?;
+        self.oracle_set.verify(&attestation, bridge)?;
+        let collected = self.attestations.entry(attestation.bridge_id).or_default();
+        if collected.iter().any(|a| a.oracle == attestation.oracle) {
+            return Ok(false);
         }
+        collected.push(attestation);
+        Ok(true)
     }
     
-    /// Monitor source chains for lock events by polling `eth_getLogs`.
+    /// Monitor source chains for lock events through their adapters.
     ///
     /// For the native Axiom chain we scan local storage directly.  For
-    /// external EVM chains we issue an `eth_getLogs` JSON-RPC call filtered
-    /// on the bridge contract address.  Any newly discovered lock events
-    /// are appended to `pending_bridges`.
-    pub async fn monitor_locks(&mut self) -> Result<(), String> {
+    /// external chains we walk each adapter's lock events over height
+    /// ranges from `lock_cursor` up to its scan lag behind the head (for
+    /// EVM chains [`LOCK_SCAN_LAG`] blocks), advancing the cursor after each
+    /// range.  Newly discovered lock events are appended to
+    /// `pending_bridges`; their IDs derive from the event position, so a
+    /// range scanned twice yields the same bridges.
+    pub async fn monitor_locks(&mut self) -> Result<()> {
         for (chain_id, contract) in &self.contracts {
-            match chain_id {
-                ChainId::Axiom => {
-                    // Local chain — locks are added directly via lock_tokens()
-                    log::debug!("Axiom chain: locks tracked locally");
+            let Some(adapter) = &contract.adapter else {
+                // Local chain — locks are added directly via lock_tokens()
+                log::debug!("{:?} chain: locks tracked locally", chain_id);
+                continue;
+            };
+            let head = match adapter.head().await {
+                Ok(head) => head,
+                Err(e) => {
+                    log::warn!("Failed to read head of {:?}: {}", chain_id, e);
+                    continue;
                 }
-                _ => {
-                    // External EVM chain — poll for Lock events via eth_getLogs
-                    let rpc_url = Self::resolve_rpc_url(chain_id)?;
-                    match Self::poll_lock_events(&rpc_url, &contract.address).await {
-                        Ok(events) => {
-                            for event in events {
-                                log::info!(
-                                    "🔒 Lock event on {:?}: sender={} amount={}",
-                                    chain_id, event.sender, event.amount
-                                );
-                                let lock_block = Self::eth_block_number(&rpc_url).await.unwrap_or(0);
-                                let bridge_tx = BridgeTransaction {
-                                    id: BridgeContract::generate_bridge_id(
-                                        &event.sender,
-                                        event.amount,
-                                        &ChainId::Axiom,
-                                    ),
-                                    from_chain: chain_id.clone(),
-                                    to_chain: ChainId::Axiom,
-                                    sender: event.sender.clone(),
-                                    recipient: event.recipient.clone(),
-                                    amount: event.amount,
-                                    token: "AXM".to_string(),
-                                    status: BridgeStatus::Pending,
-                                    timestamp: std::time::SystemTime::now()
-                                        .duration_since(std::time::UNIX_EPOCH)
-                                        .unwrap()
-                                        .as_secs(),
-                                    confirmations: 0,
-                                    required_confirmations: contract.required_confirmations(),
-                                    zk_proof: vec![],
-                                    lock_block,
-                                };
-                                // Avoid duplicates
-                                if !self.pending_bridges.iter().any(|b| b.id == bridge_tx.id) {
-                                    self.pending_bridges.push(bridge_tx);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to poll lock events on {:?}: {}", chain_id, e);
-                        }
+            };
+            let safe_head = head.saturating_sub(adapter.scan_lag());
+            let mut window = LOCK_SCAN_WINDOW;
+            while let Some((from, to)) = self.lock_cursor.next_range(chain_id, safe_head, window) {
+                let events = match adapter.lock_events(from, to).await {
+                    Ok(events) => events,
+                    Err(e) if window > 1 && is_range_limit_error(&e) => {
+                        window /= 2;
+                        log::debug!("Narrowing {:?} scan window to {} blocks: {}", chain_id, window, e);
+                        continue;
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to poll lock events on {:?} in {}..={}: {}", chain_id, from, to, e);
+                        break;
+                    }
+                };
+                for event in events {
+                    log::info!(
+                        "🔒 Lock event on {:?}: sender={} amount={}",
+                        chain_id, event.sender, event.amount
+                    );
+                    let bridge_tx = BridgeTransaction {
+                        id: Self::lock_event_id(chain_id, &event.tx_hash, event.log_index),
+                        from_chain: chain_id.clone(),
+                        to_chain: ChainId::Axiom,
+                        sender: event.sender.clone(),
+                        recipient: event.recipient.clone(),
+                        amount: event.amount,
+                        token: "AXM".to_string(),
+                        status: BridgeStatus::Pending,
+                        timestamp: unix_now(),
+                        confirmations: 0,
+                        required_confirmations: contract.required_confirmations(),
+                        zk_proof: vec![],
+                        lock_block: event.block_number,
+                        expires_at: unix_now() + BRIDGE_EXPIRY_SECS,
+                    };
+                    // Avoid duplicates
+                    if !self.pending_bridges.iter().any(|b| b.id == bridge_tx.id) {
+                        self.pending_bridges.push(bridge_tx);
                     }
                 }
+                self.lock_cursor.advance(chain_id, to);
             }
         }
         
         Ok(())
     }
+
+    /// Bridge ID of an external lock: the chain plus the log's position,
+    /// which no rescan can change.
+    fn lock_event_id(chain: &ChainId, tx_hash: &str, log_index: u64) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"axiom-bridge-lock-event");
+        hasher.update(chain.chain_id().to_le_bytes());
+        // EVM hashes are hex in either case; Solana signatures are
+        // case-sensitive base58
+        if chain.is_evm() {
+            hasher.update(tx_hash.to_lowercase().as_bytes());
+        } else {
+            hasher.update(tx_hash.as_bytes());
+        }
+        hasher.update(log_index.to_le_bytes());
+        hasher.finalize().into()
+    }
     
     /// Update confirmations for pending bridges based on actual block progress.
     ///
@@ -397,12 +618,12 @@ impl BridgeOracle {
     /// chain and compute `confirmations = current_block - lock_block`.  When
     /// the required confirmations are reached the status is promoted to
     /// [`BridgeStatus::ReadyToMint`].
-    pub async fn update_confirmations(&mut self) -> Result<(), String> {
+    pub async fn update_confirmations(&mut self) -> Result<()> {
         // Collect block numbers first to avoid borrow issues
         let mut block_numbers = std::collections::HashMap::new();
         for bridge in self.pending_bridges.iter() {
             if !block_numbers.contains_key(&bridge.from_chain) {
-                let block_num = Self::get_block_number_static(&bridge.from_chain).await?;
+                let block_num = self.get_block_number(&bridge.from_chain).await?;
                 block_numbers.insert(bridge.from_chain.clone(), block_num);
             }
         }
@@ -436,18 +657,30 @@ impl BridgeOracle {
         Ok(())
     }
     
-    /// Execute minting on destination chain
-    pub async fn execute_minting(&mut self) -> Result<(), String> {
+    /// Execute minting on destination chain for ready bridges attested by
+    /// a quorum of the oracle set. The rest stay `ReadyToMint` until
+    /// enough attestations arrive.
+    pub async fn execute_minting(&mut self) -> Result<()> {
         let ready_bridges: Vec<_> = self.pending_bridges.iter()
             .filter(|b| b.status == BridgeStatus::ReadyToMint)
+            .filter(|b| {
+                let attestations = self.attestations.get(&b.id).map(Vec::as_slice).unwrap_or_default();
+                match self.oracle_set.check_quorum(b, attestations) {
+                    Ok(()) => true,
+                    Err(e) => {
+                        log::debug!("⏳ Not minting yet: {}", e);
+                        false
+                    }
+                }
+            })
             .cloned()
             .collect();
         
         for bridge in ready_bridges {
             let dest_contract = self.contracts.get(&bridge.to_chain)
-                .ok_or("Destination chain not supported")?;
+                .ok_or_else(|| AxiomError::BridgeError("Destination chain not supported".into()))?;
             
-            match dest_contract.mint_wrapped(&bridge).await {
+            match dest_contract.mint_wrapped(&bridge, &self.oracle_set).await {
                 Ok(tx_hash) => {
                     log::info!("🎉 Minted on {:?}: {}", bridge.to_chain, tx_hash);
                     // Update status to Minted
@@ -458,7 +691,7 @@ impl BridgeOracle {
                 Err(e) => {
                     log::error!("❌ Minting failed for bridge {}: {}", hex::encode(bridge.id), e);
                     if let Some(b) = self.pending_bridges.iter_mut().find(|b| b.id == bridge.id) {
-                        b.status = BridgeStatus::Failed { reason: e.clone() };
+                        b.status = BridgeStatus::Failed { reason: e.to_string() };
                     }
                 }
             }
@@ -467,24 +700,11 @@ impl BridgeOracle {
         Ok(())
     }
     
-    pub async fn get_block_number(&self, chain: &ChainId) -> Result<u64, String> {
-        Self::get_block_number_static(chain).await
-    }
-    
-    async fn get_block_number_static(chain: &ChainId) -> Result<u64, String> {
-        match chain {
-            ChainId::Axiom => {
-                // Read from local chain storage
-                let blocks = crate::storage::load_chain();
-                Ok(blocks.map(|b| b.len() as u64).unwrap_or(1))
-            }
-            _ => {
-                // External EVM-compatible chain: issue an eth_blockNumber
-                // JSON-RPC call to the configured endpoint.
-                let rpc_url = Self::resolve_rpc_url(chain)?;
-                Self::eth_block_number(&rpc_url).await
-            }
-        }
+    pub async fn get_block_number(&self, chain: &ChainId) -> Result<u64> {
+        self.contracts.get(chain)
+            .ok_or_else(|| AxiomError::BridgeError(format!("Chain not supported: {:?}", chain)))?
+            .head()
+            .await
     }
 
     /// Resolve the RPC URL for an external chain.
@@ -492,7 +712,7 @@ impl BridgeOracle {
     /// Checks for an operator-supplied override in
     /// `AXIOM_RPC_<CHAIN>` (e.g. `AXIOM_RPC_ETHEREUM`) first, then
     /// falls back to the default public endpoint from [`ChainId::rpc_url`].
-    fn resolve_rpc_url(chain: &ChainId) -> Result<String, String> {
+    pub(super) fn resolve_rpc_url(chain: &ChainId) -> Result<String> {
         let chain_name = match chain {
             ChainId::Ethereum => "ETHEREUM",
             ChainId::BSC => "BSC",
@@ -501,6 +721,8 @@ impl BridgeOracle {
             ChainId::Optimism => "OPTIMISM",
             ChainId::Avalanche => "AVALANCHE",
             ChainId::Fantom => "FANTOM",
+            ChainId::Solana => "SOLANA",
+            ChainId::Cosmos => "COSMOS",
             ChainId::Axiom => "AXIOM",
         };
         let env_key = format!("AXIOM_RPC_{}", chain_name);
@@ -510,79 +732,25 @@ impl BridgeOracle {
         }
     }
 
-    /// Issue an `eth_blockNumber` JSON-RPC call and parse the hex response.
-    async fn eth_block_number(rpc_url: &str) -> Result<u64, String> {
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .build()
-            .map_err(|e| format!("HTTP client error: {}", e))?;
-
-        let body = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": "eth_blockNumber",
-            "params": [],
-            "id": 1
-        });
-
-        let resp = client
-            .post(rpc_url)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| format!("RPC request to {} failed: {}", rpc_url, e))?;
-
-        if !resp.status().is_success() {
-            return Err(format!("RPC endpoint {} returned HTTP {}", rpc_url, resp.status()));
-        }
-
-        let json: serde_json::Value = resp
-            .json()
-            .await
-            .map_err(|e| format!("Failed to parse RPC response: {}", e))?;
-
-        // Handle JSON-RPC error
-        if let Some(err) = json.get("error") {
-            return Err(format!("RPC error: {}", err));
-        }
-
-        let hex_str = json
-            .get("result")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| "Missing 'result' in RPC response".to_string())?;
-
-        // Parse hex block number (e.g. "0x1234abc")
-        let hex_trimmed = hex_str.trim_start_matches("0x");
-        u64::from_str_radix(hex_trimmed, 16)
-            .map_err(|e| format!("Invalid block number '{}': {}", hex_str, e))
-    }
-
     /// Poll an external EVM chain for `Lock` events on the bridge contract.
     ///
     /// Issues an `eth_getLogs` JSON-RPC call filtered on the bridge contract
-    /// address and the Lock event topic.  Returns parsed lock events.
-    async fn poll_lock_events(rpc_url: &str, contract_address: &str) -> Result<Vec<LockEvent>, String> {
-        // keccak256("Lock(address,address,uint256)")
-        // Computed via: web3.utils.keccak256("Lock(address,address,uint256)")
-        let lock_topic = "0x625fed9875dada8643f2418b838ae0bc78d9a148a18eee4ee1979ff0f3f5d427";
-
+    /// address and the Lock event topic over blocks `from..=to`.  Returns
+    /// parsed lock events.
+    pub(super) async fn poll_lock_events(rpc_url: &str, contract_address: &str, from: u64, to: u64) -> Result<Vec<LockEvent>> {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(10))
             .build()
-            .map_err(|e| format!("HTTP client error: {}", e))?;
-
-        // Query the last 100 blocks to catch recent events without overwhelming the RPC.
-        // A production deployment should persist the last-processed block number.
-        let current_block = Self::eth_block_number(rpc_url).await.unwrap_or(0);
-        let from_block = current_block.saturating_sub(100);
-        let from_hex = format!("0x{:x}", from_block);
+            .map_err(|e| AxiomError::NetworkError(format!("HTTP client error: {}", e)))?;
 
         let body = serde_json::json!({
             "jsonrpc": "2.0",
             "method": "eth_getLogs",
             "params": [{
                 "address": contract_address,
-                "topics": [lock_topic],
-                "fromBlock": from_hex
+                "topics": [LOCK_TOPIC],
+                "fromBlock": format!("0x{:x}", from),
+                "toBlock": format!("0x{:x}", to)
             }],
             "id": 1
         });
@@ -592,29 +760,49 @@ impl BridgeOracle {
             .json(&body)
             .send()
             .await
-            .map_err(|e| format!("eth_getLogs request to {} failed: {}", rpc_url, e))?;
+            .map_err(|e| AxiomError::NetworkError(format!("eth_getLogs request to {} failed: {}", rpc_url, e)))?;
 
         if !resp.status().is_success() {
-            return Err(format!("RPC endpoint {} returned HTTP {}", rpc_url, resp.status()));
+            return Err(AxiomError::NetworkError(format!("RPC endpoint {} returned HTTP {}", rpc_url, resp.status())));
         }
 
         let json: serde_json::Value = resp
             .json()
             .await
-            .map_err(|e| format!("Failed to parse RPC response: {}", e))?;
+            .map_err(|e| AxiomError::NetworkError(format!("Failed to parse RPC response: {}", e)))?;
 
         if let Some(err) = json.get("error") {
-            return Err(format!("RPC error: {}", err));
+            return Err(AxiomError::NetworkError(format!("RPC error: {}", err)));
         }
 
         let logs = json
             .get("result")
             .and_then(|v| v.as_array())
-            .cloned()
-            .unwrap_or_default();
+            .ok_or_else(|| AxiomError::NetworkError("Missing 'result' in eth_getLogs response".to_string()))?;
 
+        Ok(Self::parse_lock_logs(logs))
+    }
+
+    /// Decode `Lock` logs. Removed (reorged) logs and logs without a
+    /// position are skipped.
+    pub(super) fn parse_lock_logs(logs: &[serde_json::Value]) -> Vec<LockEvent> {
+        let hex_field = |entry: &serde_json::Value, key: &str| {
+            entry.get(key)
+                .and_then(|v| v.as_str())
+                .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        };
         let mut events = Vec::new();
-        for log_entry in &logs {
+        for log_entry in logs {
+            if log_entry.get("removed").and_then(|r| r.as_bool()) == Some(true) {
+                continue;
+            }
+            let (Some(block_number), Some(log_index), Some(tx_hash)) = (
+                hex_field(log_entry, "blockNumber"),
+                hex_field(log_entry, "logIndex"),
+                log_entry.get("transactionHash").and_then(|h| h.as_str()),
+            ) else {
+                continue;
+            };
             let topics = log_entry.get("topics").and_then(|t| t.as_array());
             let data = log_entry.get("data").and_then(|d| d.as_str()).unwrap_or("");
 
@@ -637,18 +825,28 @@ impl BridgeOracle {
                 ).unwrap_or(0);
 
                 if amount > 0 && !sender.is_empty() {
-                    events.push(LockEvent { sender, recipient, amount });
+                    events.push(LockEvent {
+                        sender,
+                        recipient,
+                        amount,
+                        block_number,
+                        tx_hash: tx_hash.to_string(),
+                        log_index,
+                    });
                 }
             }
         }
 
-        Ok(events)
+        events
     }
 }
 
 /// User-facing bridge API
 pub struct AxiomBridge {
     oracle: BridgeOracle,
+    /// Volume caps; refuses transfers and pauses the bridge when hit.
+    guard: BridgeGuard,
+    fees: FeeOracle,
 }
 
 impl Default for AxiomBridge {
@@ -661,18 +859,40 @@ impl AxiomBridge {
     pub fn new() -> Self {
         Self {
             oracle: BridgeOracle::new(),
+            guard: BridgeGuard::default(),
+            fees: FeeOracle::new(),
         }
     }
     
+    pub fn with_limits(mut self, limits: BridgeLimits) -> Self {
+        self.guard = BridgeGuard::new(limits);
+        self
+    }
+    
+    pub fn with_fee_oracle(mut self, fees: FeeOracle) -> Self {
+        self.fees = fees;
+        self
+    }
+    
+    pub fn guard(&self) -> &BridgeGuard {
+        &self.guard
+    }
+    
+    /// Resume a bridge the guardian paused. Returns false if it was not paused.
+    pub fn resume(&mut self) -> bool {
+        self.guard.resume()
+    }
+    
     /// Bridge AXM from Axiom to another chain
     pub async fn bridge_to(
         &mut self,
         amount: u64,
         destination: ChainId,
         recipient: String, // EVM address on destination
-    ) -> Result<BridgeTransaction, String> {
+    ) -> Result<BridgeTransaction> {
         let axiom_contract = self.oracle.contracts.get(&ChainId::Axiom)
-            .ok_or("Axiom bridge not available")?;
+            .ok_or_else(|| AxiomError::BridgeError("Axiom bridge not available".into()))?;
+        self.guard.admit(&destination, amount, unix_now())?;
         
         // Lock tokens on Axiom chain
         let bridge_tx = axiom_contract.lock_tokens(
@@ -687,15 +907,38 @@ impl AxiomBridge {
         Ok(bridge_tx)
     }
     
+    /// Track a bridge out of Axiom whose lock is already in a block signed
+    /// by the oracle set. The lock must pay
+    /// [`deposit_address`](super::lock_proof::deposit_address) for
+    /// `destination` and `recipient`.
+    pub fn bridge_locked(
+        &mut self,
+        proof: &LockInclusionProof,
+        destination: ChainId,
+        recipient: String,
+    ) -> Result<BridgeTransaction> {
+        let bridge_tx = proof.bridge(destination, recipient, unix_now())?;
+        proof.verify(&bridge_tx, &self.oracle.oracle_set)?;
+        if self.get_bridge_status(&bridge_tx.id).is_some() {
+            return Err(AxiomError::BridgeError("Lock already bridged".into()));
+        }
+        self.guard.admit(&bridge_tx.to_chain, bridge_tx.amount, bridge_tx.timestamp)?;
+        
+        self.oracle.pending_bridges.push(bridge_tx.clone());
+        
+        Ok(bridge_tx)
+    }
+    
     /// Bridge from another chain back to Axiom
     pub async fn bridge_from(
         &mut self,
         amount: u64,
         source_chain: ChainId,
         recipient: String, // Axiom address
-    ) -> Result<BridgeTransaction, String> {
+    ) -> Result<BridgeTransaction> {
         let source_contract = self.oracle.contracts.get(&source_chain)
-            .ok_or("Source chain not supported")?;
+            .ok_or_else(|| AxiomError::BridgeError("Source chain not supported".into()))?;
+        self.guard.admit(&source_chain, amount, unix_now())?;
         
         // Burn wrapped tokens on source chain
         let bridge_tx = source_contract.burn_and_unlock(
@@ -715,6 +958,20 @@ impl AxiomBridge {
             .find(|b| &b.id == bridge_id)
     }
     
+    /// Refund a failed or expired bridge on its source chain and mark it
+    /// `Refunded`. Returns the refund transaction hash.
+    pub async fn request_refund(&mut self, bridge_id: [u8; 32]) -> Result<String> {
+        let bridge = self.oracle.pending_bridges.iter_mut()
+            .find(|b| b.id == bridge_id)
+            .ok_or_else(|| AxiomError::NotFound(format!("bridge {}", hex::encode(bridge_id))))?;
+        let source_contract = self.oracle.contracts.get(&bridge.from_chain)
+            .ok_or_else(|| AxiomError::BridgeError("Source chain not supported".into()))?;
+        
+        let refund_tx = source_contract.refund_lock(bridge, unix_now()).await?;
+        bridge.status = BridgeStatus::Refunded;
+        Ok(refund_tx)
+    }
+    
     /// Estimate bridge time
     pub fn estimate_bridge_time(&self, from: &ChainId, _to: &ChainId) -> u64 {
         // Estimate in seconds
@@ -725,11 +982,20 @@ impl AxiomBridge {
             ChainId::Polygon => 300,      // 5 minutes
             ChainId::Arbitrum => 10,      // 10 seconds
             ChainId::Optimism => 10,      // 10 seconds
+            ChainId::Solana => 15,        // 15 seconds (finalized slot)
+            ChainId::Cosmos => 10,        // 10 seconds (instant finality)
             _ => 60,
         }
     }
     
-    /// Calculate bridge fee
+    /// Quote the fee for bridging `amount` from `from` to `to`, priced
+    /// from the destination's current gas and the configured price feed.
+    pub async fn quote(&self, amount: u64, from: &ChainId, to: &ChainId) -> Result<FeeQuote> {
+        self.fees.quote(amount, from, to, unix_now()).await
+    }
+    
+    /// Rough bridge fee from fixed gas figures, for when no live
+    /// [`quote`](Self::quote) can be had.
     pub fn calculate_fee(&self, amount: u64, _from: &ChainId, to: &ChainId) -> u64 {
         // Base fee: 0.1%
         let base_fee = amount / 1000;
@@ -768,6 +1034,20 @@ mod tests {
         assert_eq!(bridge_tx.amount, 100_000_000_000);
     }
     
+    #[tokio::test]
+    async fn test_volume_cap_pauses_bridge() {
+        let limits = BridgeLimits { global_cap: 1_000, default_chain_cap: 1_000, ..BridgeLimits::default() };
+        let mut bridge = AxiomBridge::new().with_limits(limits);
+        let recipient = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb".to_string();
+        bridge.bridge_to(900, ChainId::Ethereum, recipient.clone()).await.unwrap();
+        assert!(bridge.bridge_to(101, ChainId::Ethereum, recipient.clone()).await.is_err());
+        assert!(bridge.guard().paused().is_some());
+        assert!(bridge.bridge_to(1, ChainId::Ethereum, recipient.clone()).await.is_err());
+        assert!(bridge.resume());
+        bridge.bridge_to(100, ChainId::Ethereum, recipient).await.unwrap();
+        assert_eq!(bridge.oracle.pending_bridges.len(), 2);
+    }
+    
     #[test]
     fn test_fee_calculation() {
         let bridge = AxiomBridge::new();
@@ -781,4 +1061,127 @@ mod tests {
         // Should be 0.1% + gas
         assert!(fee > 1_000_000_000); // > 1 AXM
     }
+
+    #[tokio::test]
+    async fn test_refund_after_expiry() {
+        let mut bridge = AxiomBridge::new();
+        let bridge_tx = bridge.bridge_to(
+            1_000,
+            ChainId::Ethereum,
+            "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb".to_string(),
+        ).await.unwrap();
+        assert_eq!(bridge_tx.expires_at, bridge_tx.timestamp + BRIDGE_EXPIRY_SECS);
+        assert!(!bridge_tx.is_refundable(bridge_tx.timestamp));
+        assert!(bridge.request_refund(bridge_tx.id).await.is_err());
+        assert!(matches!(bridge.request_refund([0u8; 32]).await, Err(AxiomError::NotFound(_))));
+
+        // Expired and never minted: the sender gets the lock back, once.
+        bridge.oracle.pending_bridges[0].expires_at = 1;
+        assert!(bridge.request_refund(bridge_tx.id).await.is_ok());
+        assert_eq!(bridge.get_bridge_status(&bridge_tx.id).unwrap().status, BridgeStatus::Refunded);
+        assert!(bridge.request_refund(bridge_tx.id).await.is_err());
+
+        // An expired bridge can no longer be minted.
+        let mut late = bridge_tx.clone();
+        late.status = BridgeStatus::ReadyToMint;
+        late.expires_at = 1;
+        let eth = &bridge.oracle.contracts[&ChainId::Ethereum];
+        assert!(eth.mint_wrapped(&late, &OracleSet::default()).await.is_err());
+        late.status = BridgeStatus::Failed { reason: "mint reverted".into() };
+        late.expires_at = 0;
+        assert!(late.is_refundable(0));
+    }
+
+    #[tokio::test]
+    async fn test_axiom_lock_needs_signed_inclusion_proof() {
+        use super::super::lock_proof::deposit_address;
+        use ed25519_dalek::SigningKey;
+
+        let recipient = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb".to_string();
+        let lock = crate::transaction::Transaction {
+            from: [1u8; 32],
+            to: deposit_address(&ChainId::Ethereum, &recipient),
+            amount: 1_000,
+            fee: 1,
+            nonce: 0,
+            expiry_height: 0,
+            zk_proof: vec![],
+            signature: vec![],
+        };
+        let block = crate::block::Block {
+            timestamp: 0,
+            miner: [9u8; 32],
+            transactions: vec![lock.clone()],
+            ..crate::block::Block::test([0u8; 32], 1)
+        };
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let mut proof = LockInclusionProof::build(&block, 5, &lock.legacy_hash()).unwrap();
+
+        let mut bridge = AxiomBridge::new();
+        bridge.oracle.oracle_set = OracleSet::new(1, vec![key.verifying_key().to_bytes()]).unwrap();
+        assert!(bridge.bridge_locked(&proof, ChainId::Ethereum, recipient.clone()).is_err());
+        proof.sign(&key);
+        let bridge_tx = bridge.bridge_locked(&proof, ChainId::Ethereum, recipient.clone()).unwrap();
+        assert_eq!(bridge_tx.id, lock.legacy_hash());
+        assert!(bridge.bridge_locked(&proof, ChainId::Ethereum, recipient).is_err());
+
+        // Without its proof the lock cannot be minted.
+        let mut bare = bridge_tx;
+        bare.status = BridgeStatus::ReadyToMint;
+        bare.zk_proof.clear();
+        let eth = &bridge.oracle.contracts[&ChainId::Ethereum];
+        let err = eth.mint_wrapped(&bare, &bridge.oracle.oracle_set).await.unwrap_err();
+        assert!(matches!(err, AxiomError::ProofVerificationFailed(_)));
+    }
+
+    #[test]
+    fn test_lock_cursor_walks_ranges() {
+        let chain = ChainId::Ethereum;
+        let mut cursor = LockCursor::default();
+        // First scan reaches back LOCK_SCAN_LOOKBACK blocks.
+        assert_eq!(cursor.next_range(&chain, 1_000, 50), Some((900, 949)));
+        cursor.advance(&chain, 949);
+        assert_eq!(cursor.next_range(&chain, 1_000, 50), Some((950, 999)));
+        cursor.advance(&chain, 1_000);
+        assert_eq!(cursor.next_range(&chain, 1_000, 50), None);
+        // A head that steps back (reorg or lagging provider) is not rescanned.
+        cursor.advance(&chain, 990);
+        assert_eq!(cursor.get(&chain), Some(1_000));
+        assert_eq!(cursor.next_range(&chain, 1_003, 50), Some((1_001, 1_003)));
+        assert_eq!(cursor.next_range(&ChainId::Polygon, 10, 50), Some((0, 10)));
+
+        let path = std::env::temp_dir().join(format!("axiom_lock_cursor_{}.json", std::process::id()));
+        cursor.save(&path).unwrap();
+        assert_eq!(LockCursor::load(&path).unwrap(), cursor);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(is_range_limit_error(&AxiomError::NetworkError(
+            "RPC error: {\"code\":-32005,\"message\":\"query returned more than 10000 results\"}".into()
+        )));
+        assert!(!is_range_limit_error(&AxiomError::NetworkError("connection refused".into())));
+    }
+
+    #[test]
+    fn test_lock_logs_parse_with_stable_ids() {
+        let log = |index: &str, removed: bool| serde_json::json!({
+            "topics": [
+                "0x625fed9875dada8643f2418b838ae0bc78d9a148a18eee4ee1979ff0f3f5d427",
+                "0x000000000000000000000000742d35cc6634c0532925a3b844bc9e7595f0beb0",
+                "0x0000000000000000000000001111111111111111111111111111111111111111",
+            ],
+            "data": "0x00000000000000000000000000000000000000000000000000000000000003e8",
+            "blockNumber": "0x10",
+            "transactionHash": "0xabc",
+            "logIndex": index,
+            "removed": removed,
+        });
+        let events = BridgeOracle::parse_lock_logs(&[log("0x0", false), log("0x1", true), log("0x2", false)]);
+        assert_eq!(events.len(), 2);
+        assert_eq!((events[0].amount, events[0].block_number), (1_000, 16));
+        assert_eq!(events[0].sender, "0x742d35cc6634c0532925a3b844bc9e7595f0beb0");
+
+        let id = |e: &LockEvent| BridgeOracle::lock_event_id(&ChainId::Ethereum, &e.tx_hash, e.log_index);
+        assert_eq!(id(&events[0]), id(&BridgeOracle::parse_lock_logs(&[log("0x0", false)])[0]));
+        assert_ne!(id(&events[0]), id(&events[1]));
+    }
 }
@@ -0,0 +1,624 @@
+// src/bridge/eth_spv.rs - Ethereum light client for lock events
+//
+// Reading locks with `eth_getLogs` trusts the RPC endpoint completely: a
+// compromised or buggy provider can invent a lock and have it minted. The
+// light client instead trusts only what it can check:
+//
+//   * Finalized head: asked of several independent endpoints, and accepted
+//     only if a quorum agree on its hash. Each header's hash is recomputed
+//     from its fields, so a response cannot claim one hash and carry
+//     another header.
+//   * Every other header: linked to an accepted one by parent hash. A new
+//     finalized head must descend from the previous one (or from an
+//     operator checkpoint), so history cannot be swapped between syncs.
+//   * Lock logs: taken from the block's full receipt list, whose trie root
+//     must equal the header's `receiptsRoot`. The header's bloom filter
+//     says which blocks can contain a lock, so no provider can hide one
+//     either.
+//
+// Log positions come from the verified receipts and bridge IDs use the
+// verified block hash, so a provider cannot make one lock look like two.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{json, Value};
+
+use super::adapter::LockEvent;
+use super::cross_chain::{BridgeOracle, LOCK_TOPIC};
+use super::evm::{keccak256, quantity, rlp_bytes, rlp_list, rlp_uint, EvmRpc};
+use crate::error::{AxiomError, Result};
+
+/// Root of an empty Merkle Patricia trie, keccak256(rlp("")).
+pub const EMPTY_TRIE_ROOT: &str = "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421";
+
+/// Header fields in RLP order. Fields from `baseFeePerGas` on were added by
+/// later forks and are present only from those forks' first blocks.
+const HEADER_FIELDS: &[(&str, bool)] = &[
+    ("parentHash", false),
+    ("sha3Uncles", false),
+    ("miner", false),
+    ("stateRoot", false),
+    ("transactionsRoot", false),
+    ("receiptsRoot", false),
+    ("logsBloom", false),
+    ("difficulty", true),
+    ("number", true),
+    ("gasLimit", true),
+    ("gasUsed", true),
+    ("timestamp", true),
+    ("extraData", false),
+    ("mixHash", false),
+    ("nonce", false),
+];
+const FORK_FIELDS: &[(&str, bool)] = &[
+    ("baseFeePerGas", true),
+    ("withdrawalsRoot", false),
+    ("blobGasUsed", true),
+    ("excessBlobGas", true),
+    ("parentBeaconBlockRoot", false),
+    ("requestsHash", false),
+];
+
+fn hex_bytes(value: &Value, what: &str) -> Result<Vec<u8>> {
+    value
+        .as_str()
+        .and_then(|text| hex::decode(text.trim_start_matches("0x")).ok())
+        .ok_or_else(|| AxiomError::NetworkError(format!("invalid {} in RPC response", what)))
+}
+
+fn hash32(value: &Value, what: &str) -> Result<[u8; 32]> {
+    hex_bytes(value, what)?
+        .try_into()
+        .map_err(|_| AxiomError::NetworkError(format!("{} is not 32 bytes", what)))
+}
+
+/// Whether a 2048-bit log bloom may contain `item`.
+pub fn bloom_contains(bloom: &[u8], item: &[u8]) -> bool {
+    if bloom.len() != 256 {
+        return true;
+    }
+    let hash = keccak256(item);
+    (0..3).all(|i| {
+        let bit = (((hash[2 * i] as usize) << 8) | hash[2 * i + 1] as usize) & 2047;
+        bloom[255 - bit / 8] & (1 << (bit % 8)) != 0
+    })
+}
+
+/// The parts of a header the light client relies on, from a header whose
+/// hash was recomputed from its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthHeader {
+    pub hash: [u8; 32],
+    pub parent_hash: [u8; 32],
+    pub number: u64,
+    pub receipts_root: [u8; 32],
+    pub logs_bloom: Vec<u8>,
+}
+
+impl EthHeader {
+    /// Parse `eth_getBlockByNumber` output, checking that its `hash` is
+    /// keccak256 of the RLP-encoded header.
+    pub fn from_json(block: &Value) -> Result<Self> {
+        let mut fields = Vec::with_capacity(HEADER_FIELDS.len() + FORK_FIELDS.len());
+        let fork_fields = FORK_FIELDS.iter().take_while(|(name, _)| !block[*name].is_null());
+        for &(name, is_uint) in HEADER_FIELDS.iter().chain(fork_fields) {
+            fields.push(if is_uint {
+                rlp_uint(quantity(&block[name], name)?)
+            } else {
+                rlp_bytes(&hex_bytes(&block[name], name)?)
+            });
+        }
+        let hash = keccak256(&rlp_list(&fields));
+        if hash32(&block["hash"], "hash")? != hash {
+            return Err(AxiomError::ProofVerificationFailed(format!(
+                "header hash mismatch at block {}",
+                block["number"]
+            )));
+        }
+        Ok(Self {
+            hash,
+            parent_hash: hash32(&block["parentHash"], "parentHash")?,
+            number: quantity(&block["number"], "number")? as u64,
+            receipts_root: hash32(&block["receiptsRoot"], "receiptsRoot")?,
+            logs_bloom: hex_bytes(&block["logsBloom"], "logsBloom")?,
+        })
+    }
+}
+
+// --- Merkle Patricia trie root ---------------------------------------------
+
+fn nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Hex-prefix encoding of a partial path.
+fn hex_prefix(path: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 2 } else { 0 } + (path.len() % 2) as u8;
+    let mut out = Vec::with_capacity(path.len() / 2 + 1);
+    let rest = if path.len() % 2 == 1 {
+        out.push(flag << 4 | path[0]);
+        &path[1..]
+    } else {
+        out.push(flag << 4);
+        path
+    };
+    out.extend(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+    out
+}
+
+/// How a parent refers to a node: inline below 32 bytes, else by hash.
+fn node_ref(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < 32 {
+        node
+    } else {
+        rlp_bytes(&keccak256(&node))
+    }
+}
+
+/// RLP of the node holding `items` (sorted, distinct keys) below `depth`.
+fn trie_node(items: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    if let [(key, value)] = items {
+        return rlp_list(&[rlp_bytes(&hex_prefix(&key[depth..], true)), rlp_bytes(value)]);
+    }
+    let first = &items[0].0;
+    let shared = (depth..first.len())
+        .take_while(|&i| items.iter().all(|(key, _)| key.get(i) == Some(&first[i])))
+        .count();
+    if shared > 0 {
+        let child = trie_node(items, depth + shared);
+        return rlp_list(&[rlp_bytes(&hex_prefix(&first[depth..depth + shared], false)), node_ref(child)]);
+    }
+    let mut branch = Vec::with_capacity(17);
+    for nibble in 0..16u8 {
+        let children: Vec<_> = items.iter().filter(|(key, _)| key.get(depth) == Some(&nibble)).cloned().collect();
+        branch.push(if children.is_empty() { rlp_bytes(&[]) } else { node_ref(trie_node(&children, depth + 1)) });
+    }
+    let value = items.iter().find(|(key, _)| key.len() == depth).map(|(_, v)| v.as_slice()).unwrap_or_default();
+    branch.push(rlp_bytes(value));
+    rlp_list(&branch)
+}
+
+/// Root of the trie mapping each key to its value.
+pub fn trie_root(entries: &[(Vec<u8>, Vec<u8>)]) -> [u8; 32] {
+    let mut items: Vec<(Vec<u8>, Vec<u8>)> = entries.iter().map(|(k, v)| (nibbles(k), v.clone())).collect();
+    items.sort();
+    items.dedup_by(|a, b| a.0 == b.0);
+    if items.is_empty() {
+        return keccak256(&rlp_bytes(&[]));
+    }
+    keccak256(&trie_node(&items, 0))
+}
+
+/// Consensus encoding of an `eth_getBlockReceipts` entry: the RLP of
+/// status, cumulative gas, bloom and logs, behind the type byte for typed
+/// transactions.
+pub fn encode_receipt(receipt: &Value) -> Result<Vec<u8>> {
+    let outcome = match receipt.get("status").filter(|s| !s.is_null()) {
+        Some(status) => rlp_uint(quantity(status, "status")?),
+        // Before Byzantium receipts carried a state root instead.
+        None => rlp_bytes(&hex_bytes(&receipt["root"], "root")?),
+    };
+    let mut logs = Vec::new();
+    for log in receipt["logs"].as_array().into_iter().flatten() {
+        let topics = log["topics"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|t| hex_bytes(t, "topic").map(|t| rlp_bytes(&t)))
+            .collect::<Result<Vec<_>>>()?;
+        logs.push(rlp_list(&[
+            rlp_bytes(&hex_bytes(&log["address"], "log address")?),
+            rlp_list(&topics),
+            rlp_bytes(&hex_bytes(&log["data"], "log data")?),
+        ]));
+    }
+    let body = rlp_list(&[
+        outcome,
+        rlp_uint(quantity(&receipt["cumulativeGasUsed"], "cumulativeGasUsed")?),
+        rlp_bytes(&hex_bytes(&receipt["logsBloom"], "logsBloom")?),
+        rlp_list(&logs),
+    ]);
+    let tx_type = match receipt.get("type").filter(|t| !t.is_null()) {
+        Some(t) => quantity(t, "type")? as u8,
+        None => 0,
+    };
+    Ok(if tx_type == 0 { body } else { [vec![tx_type], body].concat() })
+}
+
+/// Lock events of `contract` in a block's receipts, after checking the
+/// receipts against the header's receipts root.
+pub fn verified_lock_events(header: &EthHeader, receipts: &[Value], contract: &str) -> Result<Vec<LockEvent>> {
+    let entries = receipts
+        .iter()
+        .enumerate()
+        .map(|(index, receipt)| Ok((rlp_uint(index as u128), encode_receipt(receipt)?)))
+        .collect::<Result<Vec<_>>>()?;
+    if trie_root(&entries) != header.receipts_root {
+        return Err(AxiomError::ProofVerificationFailed(format!(
+            "receipts of block {} do not match its receipts root",
+            header.number
+        )));
+    }
+    let contract = contract.to_lowercase();
+    let mut locks = Vec::new();
+    let logs = receipts.iter().flat_map(|r| r["logs"].as_array().into_iter().flatten());
+    for (log_index, log) in logs.enumerate() {
+        let address = log["address"].as_str().unwrap_or_default().to_lowercase();
+        let topic = log["topics"][0].as_str().unwrap_or_default().to_lowercase();
+        if address != contract || topic != LOCK_TOPIC {
+            continue;
+        }
+        locks.push(json!({
+            "blockNumber": format!("0x{:x}", header.number),
+            "logIndex": format!("0x{:x}", log_index),
+            "transactionHash": format!("0x{}", hex::encode(header.hash)),
+            "topics": log["topics"],
+            "data": log["data"],
+        }));
+    }
+    Ok(BridgeOracle::parse_lock_logs(&locks))
+}
+
+// --- Header store ------------------------------------------------------------
+
+/// Verified headers, all linked by parent hash to the anchor.
+#[derive(Debug, Default)]
+pub struct HeaderStore {
+    headers: BTreeMap<u64, EthHeader>,
+    /// The latest finalized head accepted, or an operator checkpoint.
+    anchor: Option<(u64, [u8; 32])>,
+}
+
+impl HeaderStore {
+    pub fn with_checkpoint(number: u64, hash: [u8; 32]) -> Self {
+        Self { headers: BTreeMap::new(), anchor: Some((number, hash)) }
+    }
+
+    pub fn finalized(&self) -> Option<u64> {
+        self.anchor.map(|(number, _)| number)
+    }
+
+    pub fn get(&self, number: u64) -> Option<&EthHeader> {
+        self.headers.get(&number)
+    }
+
+    pub fn lowest(&self) -> Option<&EthHeader> {
+        self.headers.values().next()
+    }
+
+    /// Accept `head` as the new finalized head. `ancestors` are the headers
+    /// from just below `head` down to just above the current anchor,
+    /// newest first; the whole run must hash-link to the anchor.
+    pub fn advance(&mut self, head: EthHeader, ancestors: Vec<EthHeader>) -> Result<()> {
+        let fail = |what: String| Err(AxiomError::ProofVerificationFailed(what));
+        let mut chain = vec![head];
+        chain.extend(ancestors);
+        for pair in chain.windows(2) {
+            if pair[1].number + 1 != pair[0].number || pair[1].hash != pair[0].parent_hash {
+                return fail(format!("header {} does not link to {}", pair[1].number, pair[0].number));
+            }
+        }
+        if let Some((number, hash)) = self.anchor {
+            let oldest = chain.last().expect("chain holds head");
+            let links = if chain[0].number == number {
+                chain[0].hash == hash
+            } else {
+                oldest.number == number + 1 && oldest.parent_hash == hash
+            };
+            if !links {
+                return fail(format!("finalized block {} does not descend from block {}", chain[0].number, number));
+            }
+        }
+        self.anchor = Some((chain[0].number, chain[0].hash));
+        self.headers.extend(chain.into_iter().map(|h| (h.number, h)));
+        Ok(())
+    }
+
+    /// Add the parent of the lowest stored header.
+    pub fn extend_back(&mut self, header: EthHeader) -> Result<()> {
+        let links = match (self.lowest(), self.anchor) {
+            (Some(lowest), _) => header.number + 1 == lowest.number && header.hash == lowest.parent_hash,
+            (None, Some((number, hash))) => header.number == number && header.hash == hash,
+            (None, None) => false,
+        };
+        if !links {
+            return Err(AxiomError::ProofVerificationFailed(format!(
+                "header {} does not link to the verified chain",
+                header.number
+            )));
+        }
+        self.headers.insert(header.number, header);
+        Ok(())
+    }
+
+    /// Drop headers below `number`; they have been scanned.
+    pub fn prune_below(&mut self, number: u64) {
+        self.headers = self.headers.split_off(&number);
+    }
+}
+
+// --- Light client ------------------------------------------------------------
+
+/// Ethereum light client over several untrusted JSON-RPC endpoints.
+pub struct EthLightClient {
+    endpoints: Vec<(String, EvmRpc)>,
+    quorum: usize,
+    store: HeaderStore,
+}
+
+impl EthLightClient {
+    pub fn new(urls: Vec<String>, quorum: usize, checkpoint: Option<(u64, [u8; 32])>) -> Result<Self> {
+        if quorum == 0 || quorum > urls.len() {
+            return Err(AxiomError::InvalidConfig(format!(
+                "light client quorum {} needs 1..={} endpoints",
+                quorum,
+                urls.len()
+            )));
+        }
+        let endpoints = urls
+            .into_iter()
+            .map(|url| EvmRpc::new(url.as_str()).map(|rpc| (url, rpc)))
+            .collect::<Result<_>>()?;
+        let store = match checkpoint {
+            Some((number, hash)) => HeaderStore::with_checkpoint(number, hash),
+            None => HeaderStore::default(),
+        };
+        Ok(Self { endpoints, quorum, store })
+    }
+
+    /// Endpoints from `AXIOM_SPV_ETHEREUM` (comma-separated, else
+    /// `default_url` alone), quorum from `AXIOM_SPV_QUORUM` (else a
+    /// majority) and an optional `AXIOM_ETH_CHECKPOINT` of `number:0xhash`.
+    pub fn from_env(default_url: &str) -> Result<Self> {
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+        let urls: Vec<String> = match var("AXIOM_SPV_ETHEREUM") {
+            Some(list) => list.split(',').map(|u| u.trim().to_string()).filter(|u| !u.is_empty()).collect(),
+            None => vec![default_url.to_string()],
+        };
+        let quorum = match var("AXIOM_SPV_QUORUM") {
+            Some(q) => q.trim().parse().map_err(|_| AxiomError::InvalidConfig(format!("AXIOM_SPV_QUORUM '{}'", q)))?,
+            None => urls.len() / 2 + 1,
+        };
+        let checkpoint = match var("AXIOM_ETH_CHECKPOINT") {
+            Some(text) => {
+                let parsed = text.split_once(':').and_then(|(number, hash)| {
+                    let hash: [u8; 32] = hex::decode(hash.trim().trim_start_matches("0x")).ok()?.try_into().ok()?;
+                    Some((number.trim().parse().ok()?, hash))
+                });
+                Some(parsed.ok_or_else(|| AxiomError::InvalidConfig(format!("AXIOM_ETH_CHECKPOINT '{}'", text)))?)
+            }
+            None => None,
+        };
+        if urls.len() < 2 {
+            log::warn!("Ethereum light client has one endpoint; set AXIOM_SPV_ETHEREUM to cross-check providers");
+        }
+        Self::new(urls, quorum, checkpoint)
+    }
+
+    pub fn finalized(&self) -> Option<u64> {
+        self.store.finalized()
+    }
+
+    /// A header by number from the first endpoint that returns a valid one.
+    async fn fetch_header(&self, number: u64) -> Result<EthHeader> {
+        let mut last_error = AxiomError::NotFound(format!("block {}", number));
+        for (url, rpc) in &self.endpoints {
+            match rpc.header(&format!("0x{:x}", number)).await.and_then(|h| EthHeader::from_json(&h)) {
+                Ok(header) if header.number == number => return Ok(header),
+                Ok(header) => log::warn!("{} returned block {} for {}", url, header.number, number),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Move the finalized head forward to the highest block a quorum of
+    /// endpoints has finalized and agrees on. Returns the finalized number.
+    pub async fn sync(&mut self) -> Result<u64> {
+        let mut finalized = Vec::new();
+        for (url, rpc) in &self.endpoints {
+            match rpc.header("finalized").await.and_then(|h| EthHeader::from_json(&h)) {
+                Ok(header) => finalized.push(header.number),
+                Err(e) => log::warn!("Light client: {} finalized head unavailable: {}", url, e),
+            }
+        }
+        if finalized.len() < self.quorum {
+            return Err(AxiomError::NetworkError(format!(
+                "only {} of {} endpoints answered, quorum is {}",
+                finalized.len(),
+                self.endpoints.len(),
+                self.quorum
+            )));
+        }
+        // The highest block at least `quorum` endpoints have finalized.
+        finalized.sort_unstable();
+        let target = finalized[finalized.len() - self.quorum];
+        if let Some(current) = self.store.finalized().filter(|&current| current >= target) {
+            return Ok(current);
+        }
+
+        let mut votes: HashMap<[u8; 32], (usize, EthHeader)> = HashMap::new();
+        for (url, rpc) in &self.endpoints {
+            match rpc.header(&format!("0x{:x}", target)).await.and_then(|h| EthHeader::from_json(&h)) {
+                Ok(header) if header.number == target => votes.entry(header.hash).or_insert((0, header)).0 += 1,
+                Ok(_) => log::warn!("Light client: {} returned the wrong block for {}", url, target),
+                Err(e) => log::warn!("Light client: {} block {} unavailable: {}", url, target, e),
+            }
+        }
+        let (_, head) = votes
+            .into_values()
+            .find(|(count, _)| *count >= self.quorum)
+            .ok_or_else(|| AxiomError::BridgeError(format!("endpoints disagree on finalized block {}", target)))?;
+
+        let mut ancestors = Vec::new();
+        if let Some(anchor) = self.store.finalized() {
+            for number in (anchor + 1..target).rev() {
+                ancestors.push(self.fetch_header(number).await?);
+            }
+        }
+        self.store.advance(head, ancestors)?;
+        log::debug!("Light client: Ethereum finalized at {}", target);
+        Ok(target)
+    }
+
+    /// The verified header at `number`, extending the chain backwards from
+    /// its lowest header if needed.
+    async fn header_at(&mut self, number: u64) -> Result<EthHeader> {
+        if self.store.finalized().is_none_or(|finalized| number > finalized) {
+            return Err(AxiomError::BridgeError(format!("block {} is not finalized yet", number)));
+        }
+        while self.store.get(number).is_none() {
+            let next = self.store.lowest().map_or_else(|| self.store.finalized().unwrap_or(0), |h| h.number - 1);
+            let header = self.fetch_header(next).await?;
+            self.store.extend_back(header)?;
+        }
+        Ok(self.store.get(number).cloned().expect("header just stored"))
+    }
+
+    /// Verified lock events of `contract` in finalized blocks `from..=to`.
+    pub async fn lock_events(&mut self, contract: &str, from: u64, to: u64) -> Result<Vec<LockEvent>> {
+        let address = hex::decode(contract.trim_start_matches("0x"))
+            .map_err(|_| AxiomError::InvalidConfig(format!("bridge contract '{}'", contract)))?;
+        let topic = hex::decode(LOCK_TOPIC.trim_start_matches("0x")).expect("lock topic is hex");
+        let mut events = Vec::new();
+        for number in from..=to {
+            let header = self.header_at(number).await?;
+            if !bloom_contains(&header.logs_bloom, &address) || !bloom_contains(&header.logs_bloom, &topic) {
+                continue;
+            }
+            events.extend(self.verified_block_locks(&header, contract).await?);
+        }
+        self.store.prune_below(from);
+        Ok(events)
+    }
+
+    /// Receipts of `header`'s block from the first endpoint whose receipts
+    /// match its root.
+    async fn verified_block_locks(&self, header: &EthHeader, contract: &str) -> Result<Vec<LockEvent>> {
+        let mut last_error = AxiomError::NotFound(format!("receipts of block {}", header.number));
+        for (url, rpc) in &self.endpoints {
+            match rpc.block_receipts(header.number).await {
+                Ok(receipts) => match verified_lock_events(header, &receipts, contract) {
+                    Ok(events) => return Ok(events),
+                    Err(e) => {
+                        log::warn!("Light client: {} served bad receipts for block {}: {}", url, header.number, e);
+                        last_error = e;
+                    }
+                },
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trie_root_and_genesis_header() {
+        assert_eq!(hex::encode(trie_root(&[])), EMPTY_TRIE_ROOT);
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = [("doe", "reindeer"), ("dog", "puppy"), ("dogglesworth", "cat")]
+            .iter()
+            .map(|(k, v)| (k.as_bytes().to_vec(), v.as_bytes().to_vec()))
+            .collect();
+        assert_eq!(
+            hex::encode(trie_root(&entries)),
+            "8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+        );
+
+        let zero = format!("0x{}", "00".repeat(32));
+        let mut genesis = json!({
+            "hash": "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "parentHash": zero,
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "miner": format!("0x{}", "00".repeat(20)),
+            "stateRoot": "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544",
+            "transactionsRoot": format!("0x{}", EMPTY_TRIE_ROOT),
+            "receiptsRoot": format!("0x{}", EMPTY_TRIE_ROOT),
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "difficulty": "0x400000000",
+            "number": "0x0",
+            "gasLimit": "0x1388",
+            "gasUsed": "0x0",
+            "timestamp": "0x0",
+            "extraData": "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa",
+            "mixHash": zero,
+            "nonce": "0x0000000000000042",
+        });
+        let header = EthHeader::from_json(&genesis).unwrap();
+        assert_eq!(header.number, 0);
+        genesis["gasLimit"] = json!("0x1389");
+        assert!(EthHeader::from_json(&genesis).is_err());
+    }
+
+    fn header(number: u64, parent: [u8; 32], receipts_root: [u8; 32]) -> EthHeader {
+        let mut hash = keccak256(&[parent.as_slice(), &number.to_be_bytes()].concat());
+        hash[0] = number as u8;
+        EthHeader { hash, parent_hash: parent, number, receipts_root, logs_bloom: vec![0; 256] }
+    }
+
+    #[test]
+    fn test_store_accepts_only_linked_headers() {
+        let h10 = header(10, [1; 32], [0; 32]);
+        let h11 = header(11, h10.hash, [0; 32]);
+        let h12 = header(12, h11.hash, [0; 32]);
+        let h13 = header(13, h12.hash, [0; 32]);
+
+        let mut store = HeaderStore::with_checkpoint(10, h10.hash);
+        // A head that skips a block, or forks off before the checkpoint.
+        assert!(store.advance(h13.clone(), vec![h11.clone()]).is_err());
+        let fork = header(11, [7; 32], [0; 32]);
+        assert!(store.advance(header(12, fork.hash, [0; 32]), vec![fork]).is_err());
+        store.advance(h13.clone(), vec![h12.clone(), h11.clone()]).unwrap();
+        assert_eq!(store.finalized(), Some(13));
+        // Backwards from 11 only the real block 10 fits.
+        assert!(store.extend_back(header(10, [2; 32], [0; 32])).is_err());
+        store.extend_back(h10.clone()).unwrap();
+        assert_eq!(store.lowest(), Some(&h10));
+        // Finality never goes backwards.
+        assert!(store.advance(h12.clone(), vec![]).is_err());
+        store.prune_below(12);
+        assert_eq!(store.lowest(), Some(&h12));
+    }
+
+    #[test]
+    fn test_receipts_checked_against_root() {
+        let contract = "0x8400000000000000000000000000000000000001";
+        let word = |value: u64| format!("{:064x}", value);
+        let receipt = |logs: Value| {
+            json!({
+                "type": "0x2",
+                "status": "0x1",
+                "cumulativeGasUsed": "0x5208",
+                "logsBloom": format!("0x{}", "00".repeat(256)),
+                "logs": logs,
+            })
+        };
+        let lock = json!({
+            "address": contract,
+            "topics": [LOCK_TOPIC, format!("0x{}", word(0xaa)), format!("0x{}", word(0xbb))],
+            "data": format!("0x{}", word(5_000)),
+        });
+        let other = json!({ "address": "0x00000000000000000000000000000000000000ff", "topics": [LOCK_TOPIC], "data": "0x" });
+        let receipts = vec![receipt(json!([other])), receipt(json!([lock]))];
+        let entries: Vec<_> = receipts
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (rlp_uint(i as u128), encode_receipt(r).unwrap()))
+            .collect();
+        let block = header(20, [3; 32], trie_root(&entries));
+
+        let events = verified_lock_events(&block, &receipts, contract).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].amount, events[0].block_number, events[0].log_index), (5_000, 20, 1));
+        assert_eq!(events[0].tx_hash, format!("0x{}", hex::encode(block.hash)));
+
+        // A provider that inflates the amount changes the receipts root.
+        let mut forged = receipts.clone();
+        forged[1]["logs"][0]["data"] = json!(format!("0x{}", word(5_000_000)));
+        assert!(verified_lock_events(&block, &forged, contract).is_err());
+        assert!(verified_lock_events(&block, &receipts[1..], contract).is_err());
+    }
+}
@@ -0,0 +1,495 @@
+// src/bridge/evm.rs - Signing and submitting bridge calls on EVM chains
+//
+// Wrapped AXM is minted and burned by calls to the bridge contract, sent as
+// EIP-1559 (type 2) transactions from the relayer's secp256k1 key. Encoding
+// is done here by hand, RLP and ABI alike, since only two fixed calls are
+// needed:
+//
+//   mint(address recipient, uint256 amount, bytes32 bridgeId)
+//   burn(uint256 amount, bytes32 bridgeId)
+//
+// The contract refuses a bridge ID it has already seen, so re-sending a
+// mint whose first attempt was never confirmed reverts instead of minting
+// twice.
+
+use std::time::Duration;
+
+use discv5::enr::k256;
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use serde_json::{json, Value};
+use sha3::{Digest, Keccak256};
+
+use crate::error::{AxiomError, Result};
+
+/// Added on top of `eth_estimateGas`, in percent.
+pub const GAS_LIMIT_MARGIN_PERCENT: u64 = 20;
+
+/// Priority fee used when the node has no `eth_maxPriorityFeePerGas`.
+pub const DEFAULT_PRIORITY_FEE_WEI: u128 = 1_500_000_000;
+
+/// How long to wait for a sent transaction to be mined and confirmed.
+pub const RECEIPT_TIMEOUT: Duration = Duration::from_secs(600);
+
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+// --- RLP --------------------------------------------------------------------
+
+fn rlp_header(out: &mut Vec<u8>, offset: u8, len: usize) {
+    if len < 56 {
+        out.push(offset + len as u8);
+    } else {
+        let len_bytes = trim_leading_zeros(&(len as u64).to_be_bytes()).to_vec();
+        out.push(offset + 55 + len_bytes.len() as u8);
+        out.extend_from_slice(&len_bytes);
+    }
+}
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+pub fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return vec![bytes[0]];
+    }
+    let mut out = Vec::with_capacity(bytes.len() + 9);
+    rlp_header(&mut out, 0x80, bytes.len());
+    out.extend_from_slice(bytes);
+    out
+}
+
+/// An unsigned integer: big-endian without leading zeros, zero as empty.
+pub fn rlp_uint(value: u128) -> Vec<u8> {
+    rlp_bytes(trim_leading_zeros(&value.to_be_bytes()))
+}
+
+/// A list of already encoded items.
+pub fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload_len = items.iter().map(Vec::len).sum();
+    let mut out = Vec::with_capacity(payload_len + 9);
+    rlp_header(&mut out, 0xc0, payload_len);
+    for item in items {
+        out.extend_from_slice(item);
+    }
+    out
+}
+
+// --- ABI --------------------------------------------------------------------
+
+/// First four bytes of the keccak hash of a function signature.
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+fn abi_word(value: &[u8]) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[32 - value.len()..].copy_from_slice(value);
+    word
+}
+
+pub fn encode_mint_call(recipient: [u8; 20], amount: u64, bridge_id: [u8; 32]) -> Vec<u8> {
+    let mut data = selector("mint(address,uint256,bytes32)").to_vec();
+    data.extend_from_slice(&abi_word(&recipient));
+    data.extend_from_slice(&abi_word(&amount.to_be_bytes()));
+    data.extend_from_slice(&bridge_id);
+    data
+}
+
+pub fn encode_burn_call(amount: u64, bridge_id: [u8; 32]) -> Vec<u8> {
+    let mut data = selector("burn(uint256,bytes32)").to_vec();
+    data.extend_from_slice(&abi_word(&amount.to_be_bytes()));
+    data.extend_from_slice(&bridge_id);
+    data
+}
+
+/// Parse a `0x`-prefixed 20-byte address.
+pub fn parse_address(address: &str) -> Result<[u8; 20]> {
+    hex::decode(address.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| AxiomError::BridgeError(format!("not an EVM address: {}", address)))
+}
+
+// --- Transactions -----------------------------------------------------------
+
+/// An EIP-1559 (type 2) transaction without an access list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip1559Tx {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_gas: u128,
+    pub gas_limit: u64,
+    pub to: [u8; 20],
+    pub value: u128,
+    pub data: Vec<u8>,
+}
+
+impl Eip1559Tx {
+    fn fields(&self) -> Vec<Vec<u8>> {
+        vec![
+            rlp_uint(self.chain_id as u128),
+            rlp_uint(self.nonce as u128),
+            rlp_uint(self.max_priority_fee_per_gas),
+            rlp_uint(self.max_fee_per_gas),
+            rlp_uint(self.gas_limit as u128),
+            rlp_bytes(&self.to),
+            rlp_uint(self.value),
+            rlp_bytes(&self.data),
+            rlp_list(&[]),
+        ]
+    }
+
+    /// The keccak hash of this is what gets signed.
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let mut payload = vec![0x02];
+        payload.extend(rlp_list(&self.fields()));
+        payload
+    }
+
+    fn encode_signed(&self, y_parity: u8, r: &[u8], s: &[u8]) -> Vec<u8> {
+        let mut fields = self.fields();
+        fields.push(rlp_uint(y_parity as u128));
+        fields.push(rlp_bytes(trim_leading_zeros(r)));
+        fields.push(rlp_bytes(trim_leading_zeros(s)));
+        let mut raw = vec![0x02];
+        raw.extend(rlp_list(&fields));
+        raw
+    }
+}
+
+/// The relayer's EVM account key.
+#[derive(Clone)]
+pub struct EvmSigner {
+    key: SigningKey,
+}
+
+impl std::fmt::Debug for EvmSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "EvmSigner(0x{})", hex::encode(self.address()))
+    }
+}
+
+impl EvmSigner {
+    pub fn from_bytes(secret: &[u8; 32]) -> Result<Self> {
+        let key = SigningKey::from_slice(secret).map_err(|_| AxiomError::InvalidPrivateKey)?;
+        Ok(Self { key })
+    }
+
+    /// Parse a hex secret key, with or without `0x`.
+    pub fn from_hex(secret: &str) -> Result<Self> {
+        let bytes: [u8; 32] = hex::decode(secret.trim().trim_start_matches("0x"))
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or(AxiomError::InvalidPrivateKey)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn address(&self) -> [u8; 20] {
+        address_of(self.key.verifying_key())
+    }
+
+    /// Sign `tx` and return the raw transaction for `eth_sendRawTransaction`.
+    pub fn sign(&self, tx: &Eip1559Tx) -> Result<Vec<u8>> {
+        let digest = keccak256(&tx.signing_payload());
+        let (signature, recovery_id) = self
+            .key
+            .sign_prehash_recoverable(&digest)
+            .map_err(|e| AxiomError::InvalidSignature(e.to_string()))?;
+        let (r, s) = signature.split_bytes();
+        Ok(tx.encode_signed(recovery_id.to_byte(), &r, &s))
+    }
+}
+
+fn address_of(key: &VerifyingKey) -> [u8; 20] {
+    let point = key.to_encoded_point(false);
+    let hash = keccak256(&point.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Fee caps for the next block: twice the base fee leaves room for six
+/// full blocks of base-fee growth before the transaction is priced out.
+pub fn eip1559_fees(base_fee: u128, priority_fee: u128) -> (u128, u128) {
+    (base_fee.saturating_mul(2).saturating_add(priority_fee), priority_fee)
+}
+
+// --- JSON-RPC ---------------------------------------------------------------
+
+/// A mined transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmReceipt {
+    pub tx_hash: String,
+    pub block_number: u64,
+    pub gas_used: u64,
+    pub success: bool,
+}
+
+pub(super) fn quantity(value: &Value, what: &str) -> Result<u128> {
+    let text = value
+        .as_str()
+        .ok_or_else(|| AxiomError::NetworkError(format!("missing {} in RPC response", what)))?;
+    u128::from_str_radix(text.trim_start_matches("0x"), 16)
+        .map_err(|e| AxiomError::NetworkError(format!("invalid {} '{}': {}", what, text, e)))
+}
+
+/// Minimal JSON-RPC client for the calls a bridge submission needs.
+pub struct EvmRpc {
+    url: String,
+    http: reqwest::Client,
+}
+
+impl EvmRpc {
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| AxiomError::NetworkError(format!("HTTP client error: {}", e)))?;
+        Ok(Self { url: url.into(), http })
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+        let resp = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("{} to {} failed: {}", method, self.url, e)))?;
+        if !resp.status().is_success() {
+            return Err(AxiomError::NetworkError(format!("RPC endpoint {} returned HTTP {}", self.url, resp.status())));
+        }
+        let mut json: Value = resp
+            .json()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("Failed to parse RPC response: {}", e)))?;
+        if let Some(err) = json.get("error") {
+            return Err(AxiomError::NetworkError(format!("{} error: {}", method, err)));
+        }
+        Ok(json["result"].take())
+    }
+
+    pub async fn block_number(&self) -> Result<u64> {
+        Ok(quantity(&self.call("eth_blockNumber", json!([])).await?, "block number")? as u64)
+    }
+
+    /// Block header JSON for a number or tag (`"finalized"`, `"latest"`).
+    pub async fn header(&self, block: &str) -> Result<Value> {
+        let header = self.call("eth_getBlockByNumber", json!([block, false])).await?;
+        if header.is_null() {
+            return Err(AxiomError::NotFound(format!("block {}", block)));
+        }
+        Ok(header)
+    }
+
+    /// Every receipt of a block, in transaction order.
+    pub async fn block_receipts(&self, number: u64) -> Result<Vec<Value>> {
+        let receipts = self.call("eth_getBlockReceipts", json!([format!("0x{:x}", number)])).await?;
+        receipts
+            .as_array()
+            .cloned()
+            .ok_or_else(|| AxiomError::NotFound(format!("receipts of block {}", number)))
+    }
+
+    pub async fn pending_nonce(&self, address: [u8; 20]) -> Result<u64> {
+        let result = self
+            .call("eth_getTransactionCount", json!([format!("0x{}", hex::encode(address)), "pending"]))
+            .await?;
+        Ok(quantity(&result, "nonce")? as u64)
+    }
+
+    pub async fn estimate_gas(&self, from: [u8; 20], to: [u8; 20], data: &[u8]) -> Result<u64> {
+        let call = json!({
+            "from": format!("0x{}", hex::encode(from)),
+            "to": format!("0x{}", hex::encode(to)),
+            "data": format!("0x{}", hex::encode(data)),
+        });
+        Ok(quantity(&self.call("eth_estimateGas", json!([call])).await?, "gas estimate")? as u64)
+    }
+
+    /// `(max_fee_per_gas, max_priority_fee_per_gas)` for a transaction now.
+    pub async fn fees(&self) -> Result<(u128, u128)> {
+        let block = self.call("eth_getBlockByNumber", json!(["latest", false])).await?;
+        let base_fee = quantity(&block["baseFeePerGas"], "base fee")?;
+        let priority_fee = match self.call("eth_maxPriorityFeePerGas", json!([])).await {
+            Ok(value) => quantity(&value, "priority fee")?,
+            Err(e) => {
+                log::debug!("eth_maxPriorityFeePerGas unavailable, using default: {}", e);
+                DEFAULT_PRIORITY_FEE_WEI
+            }
+        };
+        Ok(eip1559_fees(base_fee, priority_fee))
+    }
+
+    /// Legacy gas price, for chains without EIP-1559 fee history.
+    pub async fn gas_price(&self) -> Result<u128> {
+        quantity(&self.call("eth_gasPrice", json!([])).await?, "gas price")
+    }
+
+    /// `(base fee of the next block, median priority fee)` over the last
+    /// `blocks` blocks, from `eth_feeHistory`.
+    pub async fn fee_history(&self, blocks: u64) -> Result<(u128, u128)> {
+        let history = self
+            .call("eth_feeHistory", json!([format!("0x{:x}", blocks), "latest", [50]]))
+            .await?;
+        let next_base_fee = history["baseFeePerGas"]
+            .as_array()
+            .and_then(|fees| fees.last())
+            .ok_or_else(|| AxiomError::NetworkError("missing baseFeePerGas in eth_feeHistory response".into()))?;
+        let mut rewards = history["reward"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|block| block.get(0))
+            .map(|reward| quantity(reward, "priority fee"))
+            .collect::<Result<Vec<_>>>()?;
+        rewards.sort_unstable();
+        let priority_fee = rewards.get(rewards.len() / 2).copied().unwrap_or(DEFAULT_PRIORITY_FEE_WEI);
+        Ok((quantity(next_base_fee, "base fee")?, priority_fee))
+    }
+
+    /// Broadcast a signed transaction and return its hash.
+    pub async fn send_raw_transaction(&self, raw: &[u8]) -> Result<String> {
+        let result = self
+            .call("eth_sendRawTransaction", json!([format!("0x{}", hex::encode(raw))]))
+            .await?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| AxiomError::NetworkError("missing hash in eth_sendRawTransaction response".into()))
+    }
+
+    pub async fn receipt(&self, tx_hash: &str) -> Result<Option<EvmReceipt>> {
+        let result = self.call("eth_getTransactionReceipt", json!([tx_hash])).await?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(EvmReceipt {
+            tx_hash: tx_hash.to_string(),
+            block_number: quantity(&result["blockNumber"], "receipt block")? as u64,
+            gas_used: quantity(&result["gasUsed"], "gas used")? as u64,
+            success: quantity(&result["status"], "receipt status")? == 1,
+        }))
+    }
+
+    /// Sign and send a call to `to`, then wait until it is mined
+    /// successfully and buried under `confirmations` blocks in total.
+    pub async fn submit_call(
+        &self,
+        signer: &EvmSigner,
+        chain_id: u64,
+        to: [u8; 20],
+        data: Vec<u8>,
+        confirmations: u32,
+    ) -> Result<EvmReceipt> {
+        let from = signer.address();
+        let nonce = self.pending_nonce(from).await?;
+        let estimate = self.estimate_gas(from, to, &data).await?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.fees().await?;
+        let tx = Eip1559Tx {
+            chain_id,
+            nonce,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_limit: estimate + estimate * GAS_LIMIT_MARGIN_PERCENT / 100,
+            to,
+            value: 0,
+            data,
+        };
+        let tx_hash = self.send_raw_transaction(&signer.sign(&tx)?).await?;
+        log::info!("📤 Sent {} on chain {} (nonce {}, gas {})", tx_hash, chain_id, nonce, tx.gas_limit);
+        self.wait_for_receipt(&tx_hash, confirmations).await
+    }
+
+    async fn wait_for_receipt(&self, tx_hash: &str, confirmations: u32) -> Result<EvmReceipt> {
+        let deadline = tokio::time::Instant::now() + RECEIPT_TIMEOUT;
+        loop {
+            match self.receipt(tx_hash).await {
+                Ok(Some(receipt)) if !receipt.success => {
+                    return Err(AxiomError::BridgeError(format!("{} reverted in block {}", tx_hash, receipt.block_number)));
+                }
+                Ok(Some(receipt)) => {
+                    let head = self.block_number().await.unwrap_or(receipt.block_number);
+                    if head + 1 >= receipt.block_number + confirmations.max(1) as u64 {
+                        return Ok(receipt);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log::debug!("Receipt poll for {} failed: {}", tx_hash, e),
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(AxiomError::RpcTimeout);
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::{RecoveryId, Signature};
+
+    #[test]
+    fn test_rlp_and_abi_encoding() {
+        assert_eq!(rlp_bytes(b"dog"), hex::decode("83646f67").unwrap());
+        assert_eq!(rlp_list(&[rlp_bytes(b"cat"), rlp_bytes(b"dog")]), hex::decode("c88363617483646f67").unwrap());
+        assert_eq!(rlp_uint(0), vec![0x80]);
+        assert_eq!(rlp_uint(15), vec![0x0f]);
+        assert_eq!(rlp_uint(1024), vec![0x82, 0x04, 0x00]);
+        assert_eq!(rlp_list(&[]), vec![0xc0]);
+        let long = [b'a'; 56];
+        assert_eq!(&rlp_bytes(&long)[..2], &[0xb8, 56]);
+
+        assert_eq!(selector("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+        let mint = encode_mint_call([0x11; 20], 1_000, [0x22; 32]);
+        assert_eq!(mint.len(), 4 + 3 * 32);
+        assert_eq!(&mint[4..16], &[0u8; 12]);
+        assert_eq!(&mint[4 + 62..4 + 64], &1_000u16.to_be_bytes());
+        assert_eq!(encode_burn_call(5, [0; 32]).len(), 4 + 2 * 32);
+        assert!(parse_address("0x8400000000000000000000000000000000000001").is_ok());
+        assert!(parse_address("0x84").is_err());
+    }
+
+    #[test]
+    fn test_signed_transaction_recovers_to_signer() {
+        let signer = EvmSigner::from_hex("0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        assert_eq!(hex::encode(signer.address()), "2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+
+        let tx = Eip1559Tx {
+            chain_id: 1,
+            nonce: 7,
+            max_priority_fee_per_gas: 2_000_000_000,
+            max_fee_per_gas: 50_000_000_000,
+            gas_limit: 90_000,
+            to: [0x84; 20],
+            value: 0,
+            data: encode_mint_call([0x11; 20], 1_000, [0x22; 32]),
+        };
+        let raw = signer.sign(&tx).unwrap();
+        assert_eq!(raw[0], 0x02);
+
+        // The last three list items are y_parity, r and s (33 bytes each
+        // when r and s have no leading zero byte).
+        let signed_tail = &raw[raw.len() - 67..];
+        assert_eq!((signed_tail[1], signed_tail[34]), (0xa0, 0xa0));
+        let y_parity = signed_tail[0];
+        let mut rs = [0u8; 64];
+        rs[..32].copy_from_slice(&signed_tail[2..34]);
+        rs[32..].copy_from_slice(&signed_tail[35..67]);
+        let signature = Signature::from_slice(&rs).unwrap();
+        let recid = RecoveryId::from_byte(if y_parity == 0x80 { 0 } else { y_parity }).unwrap();
+        let digest = keccak256(&tx.signing_payload());
+        let key = VerifyingKey::recover_from_prehash(&digest, &signature, recid).unwrap();
+        assert_eq!(address_of(&key), signer.address());
+        assert_eq!(eip1559_fees(10, 2), (22, 2));
+    }
+}
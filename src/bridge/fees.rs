@@ -0,0 +1,379 @@
+// src/bridge/fees.rs - Bridge fee quotes from live gas prices
+//
+// The relayer pays gas on the destination chain in that chain's token; the
+// user pays the bridge in AXM. A quote prices the destination's gas as of
+// now, converts it to AXM through a price feed, and adds the protocol fee.
+// Gas and prices move, so a quote carries an expiry after which the
+// caller should ask again.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::cosmos;
+use super::cross_chain::{BridgeOracle, ChainId};
+use super::evm::{self, EvmRpc};
+use super::solana;
+use crate::error::{AxiomError, Result};
+
+/// Protocol fee, in basis points of the bridged amount.
+pub const PROTOCOL_FEE_BPS: u64 = 10;
+
+/// How long a quote is honoured.
+pub const QUOTE_TTL_SECS: u64 = 120;
+
+/// Gas a wrapped-AXM mint uses on an EVM chain, with the relayer's margin.
+pub const EVM_MINT_GAS: u64 = 90_000 + 90_000 * evm::GAS_LIMIT_MARGIN_PERCENT / 100;
+
+/// Blocks of `eth_feeHistory` the EVM oracle takes the median tip over.
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Gas a mint needs and what each unit costs, in the chain's smallest
+/// native unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasCost {
+    pub units: u64,
+    pub price: u128,
+}
+
+impl GasCost {
+    pub fn total(&self) -> u128 {
+        self.units as u128 * self.price
+    }
+}
+
+/// Prices the relayer's mint on one destination chain.
+#[async_trait]
+pub trait GasOracle: Send + Sync {
+    fn chain(&self) -> ChainId;
+
+    async fn mint_cost(&self) -> Result<GasCost>;
+}
+
+/// EVM gas from `eth_feeHistory` (next base fee plus the median tip),
+/// falling back to `eth_gasPrice` on chains without EIP-1559.
+pub struct EvmGasOracle {
+    chain: ChainId,
+    rpc: EvmRpc,
+}
+
+impl EvmGasOracle {
+    pub fn new(chain: ChainId) -> Result<Self> {
+        let rpc = EvmRpc::new(BridgeOracle::resolve_rpc_url(&chain)?)?;
+        Ok(Self { chain, rpc })
+    }
+}
+
+#[async_trait]
+impl GasOracle for EvmGasOracle {
+    fn chain(&self) -> ChainId {
+        self.chain.clone()
+    }
+
+    async fn mint_cost(&self) -> Result<GasCost> {
+        let price = match self.rpc.fee_history(FEE_HISTORY_BLOCKS).await {
+            Ok((base_fee, priority_fee)) => base_fee + priority_fee,
+            Err(e) => {
+                log::debug!("eth_feeHistory unavailable on {:?}, using eth_gasPrice: {}", self.chain, e);
+                self.rpc.gas_price().await?
+            }
+        };
+        Ok(GasCost { units: EVM_MINT_GAS, price })
+    }
+}
+
+/// A chain whose transactions cost a fixed fee: one unit at that price.
+pub struct FixedGasOracle {
+    chain: ChainId,
+    fee: u128,
+}
+
+impl FixedGasOracle {
+    pub fn new(chain: ChainId, fee: u128) -> Self {
+        Self { chain, fee }
+    }
+}
+
+#[async_trait]
+impl GasOracle for FixedGasOracle {
+    fn chain(&self) -> ChainId {
+        self.chain.clone()
+    }
+
+    async fn mint_cost(&self) -> Result<GasCost> {
+        Ok(GasCost { units: 1, price: self.fee })
+    }
+}
+
+/// USD prices of AXM and the destination chains' native tokens.
+#[async_trait]
+pub trait PriceFeed: Send + Sync {
+    /// USD price of one whole `token` (e.g. `"ETH"`, not wei).
+    async fn usd_price(&self, token: &str) -> Result<f64>;
+}
+
+fn parse_price(token: &str, value: &Value) -> Result<f64> {
+    let price = match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    };
+    price
+        .filter(|p| p.is_finite() && *p > 0.0)
+        .ok_or_else(|| AxiomError::BridgeError(format!("no usable {} price in feed: {}", token, value)))
+}
+
+/// Prices set by the operator, e.g. from `AXIOM_PRICES=AXM=0.5,ETH=3000`.
+#[derive(Debug, Clone, Default)]
+pub struct StaticPriceFeed {
+    prices: HashMap<String, f64>,
+}
+
+impl StaticPriceFeed {
+    pub fn new(prices: HashMap<String, f64>) -> Self {
+        Self { prices }
+    }
+
+    /// Parse `TOKEN=price` pairs separated by commas.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut prices = HashMap::new();
+        for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (token, price) = pair
+                .split_once('=')
+                .ok_or_else(|| AxiomError::InvalidConfig(format!("price '{}' is not TOKEN=price", pair)))?;
+            let token = token.trim().to_uppercase();
+            let price = parse_price(&token, &Value::String(price.to_string()))
+                .map_err(|e| AxiomError::InvalidConfig(e.to_string()))?;
+            prices.insert(token, price);
+        }
+        Ok(Self { prices })
+    }
+}
+
+#[async_trait]
+impl PriceFeed for StaticPriceFeed {
+    async fn usd_price(&self, token: &str) -> Result<f64> {
+        self.prices
+            .get(&token.to_uppercase())
+            .copied()
+            .ok_or_else(|| AxiomError::NotFound(format!("{} price", token)))
+    }
+}
+
+/// Prices from an HTTP endpoint returning a JSON object of token to USD
+/// price, e.g. `{"AXM": 0.52, "ETH": "3104.5"}`.
+pub struct HttpPriceFeed {
+    url: String,
+    http: reqwest::Client,
+}
+
+impl HttpPriceFeed {
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .map_err(|e| AxiomError::NetworkError(format!("HTTP client error: {}", e)))?;
+        Ok(Self { url: url.into(), http })
+    }
+}
+
+#[async_trait]
+impl PriceFeed for HttpPriceFeed {
+    async fn usd_price(&self, token: &str) -> Result<f64> {
+        let resp = self.http
+            .get(&self.url)
+            .send()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("price feed {} failed: {}", self.url, e)))?;
+        if !resp.status().is_success() {
+            return Err(AxiomError::NetworkError(format!("price feed {} returned HTTP {}", self.url, resp.status())));
+        }
+        let prices: Value = resp
+            .json()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("Failed to parse price feed: {}", e)))?;
+        let price = prices
+            .get(token.to_uppercase())
+            .or_else(|| prices.get(token))
+            .ok_or_else(|| AxiomError::NotFound(format!("{} price", token)))?;
+        parse_price(token, price)
+    }
+}
+
+/// The feed configured by `AXIOM_PRICE_FEED_URL`, else fixed prices from
+/// `AXIOM_PRICES`, else none.
+pub fn price_feed_from_env() -> Result<Option<Arc<dyn PriceFeed>>> {
+    let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+    if let Some(url) = var("AXIOM_PRICE_FEED_URL") {
+        return Ok(Some(Arc::new(HttpPriceFeed::new(url.trim())?)));
+    }
+    match var("AXIOM_PRICES") {
+        Some(spec) => Ok(Some(Arc::new(StaticPriceFeed::parse(&spec)?))),
+        None => Ok(None),
+    }
+}
+
+/// Fee for one bridge transfer, broken down, valid until `expires_at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeQuote {
+    pub amount: u64,
+    pub from: ChainId,
+    pub to: ChainId,
+    /// [`PROTOCOL_FEE_BPS`] of the amount, in AXM units.
+    pub protocol_fee: u64,
+    /// The destination mint's gas, converted to AXM units.
+    pub gas_fee: u64,
+    pub gas_units: u64,
+    /// Per gas unit, in the destination's smallest native unit.
+    pub gas_price: u128,
+    /// Token the destination's gas is paid in, and the prices used.
+    pub native_token: String,
+    pub native_price_usd: f64,
+    pub axm_price_usd: f64,
+    pub total_fee: u64,
+    pub quoted_at: u64,
+    pub expires_at: u64,
+}
+
+impl FeeQuote {
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+
+    /// What arrives on the destination after fees.
+    pub fn received(&self) -> u64 {
+        self.amount.saturating_sub(self.total_fee)
+    }
+}
+
+/// Gas oracles per destination chain plus a price feed.
+pub struct FeeOracle {
+    gas: HashMap<ChainId, Arc<dyn GasOracle>>,
+    prices: Option<Arc<dyn PriceFeed>>,
+}
+
+impl Default for FeeOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeeOracle {
+    /// Oracles for every supported chain at its default endpoint, and the
+    /// price feed from the environment.
+    pub fn new() -> Self {
+        let mut oracle = Self { gas: HashMap::new(), prices: None };
+        for chain in [
+            ChainId::Ethereum,
+            ChainId::BSC,
+            ChainId::Polygon,
+            ChainId::Arbitrum,
+            ChainId::Optimism,
+        ] {
+            match EvmGasOracle::new(chain.clone()) {
+                Ok(gas) => oracle.gas.insert(chain, Arc::new(gas)),
+                Err(e) => {
+                    log::warn!("No gas oracle for {:?}: {}", chain, e);
+                    continue;
+                }
+            };
+        }
+        oracle.gas.insert(ChainId::Solana, Arc::new(FixedGasOracle::new(ChainId::Solana, solana::SIGNATURE_FEE_LAMPORTS as u128)));
+        oracle.gas.insert(ChainId::Cosmos, Arc::new(FixedGasOracle::new(ChainId::Cosmos, cosmos::EXECUTE_FEE as u128)));
+        match price_feed_from_env() {
+            Ok(prices) => oracle.prices = prices,
+            Err(e) => log::warn!("Bridge price feed misconfigured: {}", e),
+        }
+        oracle
+    }
+
+    pub fn with_gas_oracle(mut self, gas: Arc<dyn GasOracle>) -> Self {
+        self.gas.insert(gas.chain(), gas);
+        self
+    }
+
+    pub fn with_price_feed(mut self, prices: Arc<dyn PriceFeed>) -> Self {
+        self.prices = Some(prices);
+        self
+    }
+
+    /// Quote bridging `amount` AXM units from `from` to `to` at `now`.
+    /// Bridges into Axiom pay no destination gas.
+    pub async fn quote(&self, amount: u64, from: &ChainId, to: &ChainId, now: u64) -> Result<FeeQuote> {
+        if from == to {
+            return Err(AxiomError::BridgeError(format!("cannot bridge from {:?} to itself", from)));
+        }
+        let protocol_fee = (amount as u128 * PROTOCOL_FEE_BPS as u128 / 10_000) as u64;
+        let prices = self.prices.as_ref()
+            .ok_or_else(|| AxiomError::InvalidConfig("no price feed: set AXIOM_PRICE_FEED_URL or AXIOM_PRICES".into()))?;
+        let axm_price_usd = prices.usd_price("AXM").await?;
+
+        let (cost, native_price_usd) = match to {
+            ChainId::Axiom => (GasCost { units: 0, price: 0 }, axm_price_usd),
+            chain => {
+                let oracle = self.gas.get(chain)
+                    .ok_or_else(|| AxiomError::BridgeError(format!("no gas oracle for {:?}", chain)))?;
+                (oracle.mint_cost().await?, prices.usd_price(chain.native_token()).await?)
+            }
+        };
+        // native smallest units -> USD -> AXM smallest units, rounded up
+        let native = cost.total() as f64 / 10f64.powi(to.native_decimals() as i32);
+        let axm = native * native_price_usd / axm_price_usd;
+        let gas_fee = (axm * 10f64.powi(ChainId::Axiom.native_decimals() as i32)).ceil() as u64;
+
+        let total_fee = protocol_fee.saturating_add(gas_fee);
+        if total_fee >= amount {
+            return Err(AxiomError::BridgeError(format!(
+                "amount {} does not cover the bridge fee of {}",
+                amount, total_fee
+            )));
+        }
+        Ok(FeeQuote {
+            amount,
+            from: from.clone(),
+            to: to.clone(),
+            protocol_fee,
+            gas_fee,
+            gas_units: cost.units,
+            gas_price: cost.price,
+            native_token: to.native_token().to_string(),
+            native_price_usd,
+            axm_price_usd,
+            total_fee,
+            quoted_at: now,
+            expires_at: now + QUOTE_TTL_SECS,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_quote_converts_gas_to_axm() {
+        // 100k gas at 20 gwei = 0.002 ETH = $6 = 12 AXM at $0.50
+        let prices = StaticPriceFeed::parse("AXM=0.5, eth=3000").unwrap();
+        let oracle = FeeOracle { gas: HashMap::new(), prices: None }
+            .with_price_feed(Arc::new(prices))
+            .with_gas_oracle(Arc::new(FixedGasOracle::new(ChainId::Ethereum, 100_000 * 20_000_000_000)));
+
+        let amount = 1_000 * 100_000_000;
+        let quote = oracle.quote(amount, &ChainId::Axiom, &ChainId::Ethereum, 1_000).await.unwrap();
+        assert_eq!(quote.protocol_fee, amount / 1_000);
+        assert_eq!(quote.gas_fee, 12 * 100_000_000);
+        assert_eq!(quote.total_fee, quote.protocol_fee + quote.gas_fee);
+        assert_eq!(quote.expires_at, 1_000 + QUOTE_TTL_SECS);
+        assert!(!quote.is_expired(1_000) && quote.is_expired(quote.expires_at));
+
+        // Into Axiom only the protocol fee applies; too small an amount fails.
+        let back = oracle.quote(amount, &ChainId::Ethereum, &ChainId::Axiom, 1_000).await.unwrap();
+        assert_eq!((back.gas_fee, back.total_fee), (0, amount / 1_000));
+        assert!(oracle.quote(12 * 100_000_000, &ChainId::Axiom, &ChainId::Ethereum, 0).await.is_err());
+        assert!(oracle.quote(amount, &ChainId::Axiom, &ChainId::Polygon, 0).await.is_err());
+        assert!(StaticPriceFeed::parse("AXM").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+// src/bridge/lock_proof.rs - Proof that an Axiom lock was included in a block
+//
+// A bridge out of Axiom starts with an ordinary transfer into a deposit
+// address derived from the destination chain and recipient, so the lock
+// transaction itself commits to where the funds go. Once its block is
+// final, the bridge oracles sign the block header (hash, height and
+// transaction root). The destination side mints only after checking:
+//
+//   * the lock transaction pays the deposit address for this bridge's
+//     destination and recipient, with the bridge's amount and sender;
+//   * a Merkle proof places the transaction under the signed root;
+//   * a quorum of the current oracle set signed that header.
+//
+// The bridge ID is the lock's TXID, so a lock can back one mint only.
+
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::attestation::OracleSet;
+use super::cross_chain::{BridgeStatus, BridgeTransaction, ChainId, BRIDGE_EXPIRY_SECS};
+use crate::block::Block;
+use crate::error::{AxiomError, Result};
+use crate::receipt::MerkleProof;
+use crate::transaction::{Address, Transaction};
+
+const DEPOSIT_DOMAIN: &[u8] = b"axiom-bridge-deposit-v1";
+const HEADER_DOMAIN: &[u8] = b"axiom-bridge-header-v1";
+
+/// Address a lock for `recipient` on `destination` must pay. A hash, not a
+/// key, so nothing on Axiom can spend from it.
+pub fn deposit_address(destination: &ChainId, recipient: &str) -> Address {
+    let mut hasher = Sha256::new();
+    hasher.update(DEPOSIT_DOMAIN);
+    hasher.update(destination.chain_id().to_le_bytes());
+    hasher.update(recipient.to_lowercase().as_bytes());
+    hasher.finalize().into()
+}
+
+/// The header digest oracles sign once a block is final.
+pub fn header_digest(block_hash: &[u8; 32], height: u64, tx_root: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(HEADER_DOMAIN);
+    hasher.update(block_hash);
+    hasher.update(height.to_le_bytes());
+    hasher.update(tx_root);
+    hasher.finalize().into()
+}
+
+/// A lock transaction, its place in an Axiom block, and oracle signatures
+/// over that block's header. Carried in `BridgeTransaction::zk_proof`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockInclusionProof {
+    pub lock_tx: Transaction,
+    pub block_hash: [u8; 32],
+    pub block_height: u64,
+    pub tx_root: [u8; 32],
+    pub merkle_proof: MerkleProof,
+    /// (oracle public key, Ed25519 signature over `header_digest`).
+    pub header_signatures: Vec<([u8; 32], Vec<u8>)>,
+}
+
+impl LockInclusionProof {
+    /// Proof for the transaction `txid` in `block` at `height`, not yet
+    /// signed. `None` if the block does not contain it.
+    pub fn build(block: &Block, height: u64, txid: &[u8; 32]) -> Option<Self> {
+        let txids = block.txids();
+        let index = txids.iter().position(|id| id == txid)?;
+        Some(Self {
+            lock_tx: block.transactions[index].clone(),
+            block_hash: block.legacy_hash(),
+            block_height: height,
+            tx_root: block.tx_root(),
+            merkle_proof: MerkleProof::build(&txids, index)?,
+            header_signatures: Vec::new(),
+        })
+    }
+
+    pub fn digest(&self) -> [u8; 32] {
+        header_digest(&self.block_hash, self.block_height, &self.tx_root)
+    }
+
+    /// Add this oracle's header signature, replacing an earlier one.
+    pub fn sign(&mut self, key: &SigningKey) {
+        let oracle = key.verifying_key().to_bytes();
+        let signature = key.sign(&self.digest()).to_bytes().to_vec();
+        self.header_signatures.retain(|(k, _)| *k != oracle);
+        self.header_signatures.push((oracle, signature));
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("lock proof serialization")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        bincode::deserialize(bytes)
+            .map_err(|e| AxiomError::ProofVerificationFailed(format!("malformed lock proof: {}", e)))
+    }
+
+    /// The bridge this lock pays for. Fails if the lock does not pay the
+    /// deposit address for `destination` and `recipient`.
+    pub fn bridge(&self, destination: ChainId, recipient: String, now: u64) -> Result<BridgeTransaction> {
+        if destination == ChainId::Axiom {
+            return Err(AxiomError::BridgeError("Cannot bridge from Axiom to Axiom".into()));
+        }
+        let bridge = BridgeTransaction {
+            id: self.lock_tx.legacy_hash(),
+            from_chain: ChainId::Axiom,
+            to_chain: destination,
+            sender: hex::encode(self.lock_tx.from),
+            recipient,
+            amount: self.lock_tx.amount,
+            token: "AXM".to_string(),
+            status: BridgeStatus::Pending,
+            timestamp: now,
+            confirmations: 0,
+            required_confirmations: 1,
+            zk_proof: self.to_bytes(),
+            lock_block: self.block_height,
+            expires_at: now + BRIDGE_EXPIRY_SECS,
+        };
+        self.check_binding(&bridge)?;
+        Ok(bridge)
+    }
+
+    fn check_binding(&self, bridge: &BridgeTransaction) -> Result<()> {
+        let fail = |what: &str| Err(AxiomError::ProofVerificationFailed(format!("lock proof: {}", what)));
+        if bridge.from_chain != ChainId::Axiom {
+            return fail("bridge does not originate on Axiom");
+        }
+        if bridge.id != self.lock_tx.legacy_hash() {
+            return fail("bridge ID is not the lock TXID");
+        }
+        if self.lock_tx.to != deposit_address(&bridge.to_chain, &bridge.recipient) {
+            return fail("lock does not pay this bridge's deposit address");
+        }
+        if self.lock_tx.amount != bridge.amount || self.lock_tx.amount == 0 {
+            return fail("amount mismatch");
+        }
+        if hex::encode(self.lock_tx.from) != bridge.sender {
+            return fail("sender mismatch");
+        }
+        if self.block_height != bridge.lock_block {
+            return fail("lock block mismatch");
+        }
+        Ok(())
+    }
+
+    /// Everything the destination checks before minting `bridge`.
+    pub fn verify(&self, bridge: &BridgeTransaction, oracles: &OracleSet) -> Result<()> {
+        self.check_binding(bridge)?;
+        if !self.merkle_proof.verify(&bridge.id, &self.tx_root) {
+            return Err(AxiomError::ProofVerificationFailed(
+                "lock proof: transaction not under the block's root".into(),
+            ));
+        }
+        oracles.check_signed(&self.digest(), &self.header_signatures).map_err(|_| {
+            AxiomError::ProofVerificationFailed("lock proof: header not signed by an oracle quorum".into())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECIPIENT: &str = "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb";
+
+    fn transfer(to: Address, amount: u64, nonce: u64) -> Transaction {
+        Transaction { from: [1u8; 32], to, amount, fee: 1, nonce, expiry_height: 0, zk_proof: vec![], signature: vec![] }
+    }
+
+    fn block_with_lock() -> (Block, [u8; 32]) {
+        let lock = transfer(deposit_address(&ChainId::Ethereum, RECIPIENT), 5_000, 2);
+        let txid = lock.legacy_hash();
+        let block = Block {
+            timestamp: 0,
+            miner: [9u8; 32],
+            transactions: vec![transfer([2u8; 32], 10, 0), transfer([3u8; 32], 20, 1), lock],
+            ..Block::test([0u8; 32], 7)
+        };
+        (block, txid)
+    }
+
+    fn oracles() -> (Vec<SigningKey>, OracleSet) {
+        let keys: Vec<_> = (1..=3u8).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let set = OracleSet::new(2, keys.iter().map(|k| k.verifying_key().to_bytes()).collect()).unwrap();
+        (keys, set)
+    }
+
+    #[test]
+    fn test_verifies_signed_inclusion() {
+        let (keys, set) = oracles();
+        let (block, txid) = block_with_lock();
+        let mut proof = LockInclusionProof::build(&block, 42, &txid).unwrap();
+        let bridge = proof.bridge(ChainId::Ethereum, RECIPIENT.into(), 0).unwrap();
+        assert_eq!((bridge.id, bridge.amount, bridge.lock_block), (txid, 5_000, 42));
+
+        proof.sign(&keys[0]);
+        proof.sign(&keys[0]);
+        assert!(proof.verify(&bridge, &set).is_err());
+        proof.sign(&keys[2]);
+        proof.verify(&bridge, &set).unwrap();
+        let decoded = LockInclusionProof::from_bytes(&proof.to_bytes()).unwrap();
+        decoded.verify(&bridge, &set).unwrap();
+        assert!(LockInclusionProof::from_bytes(&[1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_mismatched_lock() {
+        let (keys, set) = oracles();
+        let (block, txid) = block_with_lock();
+        let mut proof = LockInclusionProof::build(&block, 42, &txid).unwrap();
+        keys.iter().for_each(|k| proof.sign(k));
+        let bridge = proof.bridge(ChainId::Ethereum, RECIPIENT.into(), 0).unwrap();
+
+        // The lock pays a deposit address for a different recipient or chain.
+        assert!(proof.bridge(ChainId::Ethereum, "0xattacker".into(), 0).is_err());
+        assert!(proof.bridge(ChainId::Polygon, RECIPIENT.into(), 0).is_err());
+        let mut inflated = bridge.clone();
+        inflated.amount += 1;
+        assert!(proof.verify(&inflated, &set).is_err());
+
+        // Signed header, but the transaction is not under its root.
+        let mut forged = proof.clone();
+        forged.tx_root = [7u8; 32];
+        keys.iter().for_each(|k| forged.sign(k));
+        assert!(forged.verify(&bridge, &set).is_err());
+
+        // Valid inclusion, signed by keys outside the oracle set.
+        let mut unsigned = proof.clone();
+        unsigned.header_signatures.clear();
+        unsigned.sign(&SigningKey::from_bytes(&[8; 32]));
+        unsigned.sign(&SigningKey::from_bytes(&[9; 32]));
+        assert!(unsigned.verify(&bridge, &set).is_err());
+        assert!(LockInclusionProof::build(&block, 42, &[0u8; 32]).is_none());
+    }
+}
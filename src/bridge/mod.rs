@@ -1,10 +1,23 @@
 // Bridge module - Cross-chain bridge functionality
 
+pub mod adapter;
 pub mod cross_chain;
 pub mod atomic_swap;
+pub mod attestation;
+pub mod cosmos;
+pub mod eth_spv;
+pub mod evm;
+pub mod fees;
+pub mod lock_proof;
+pub mod relayer;
+pub mod solana;
 
 pub use cross_chain::{
     AxiomBridge, BridgeContract, BridgeOracle, BridgeStatus, BridgeTransaction, ChainId,
 };
 
-pub use atomic_swap::{BridgeLock, BridgeSecret};
+pub use adapter::{ChainAdapter, ChainReceipt, LockEvent};
+pub use atomic_swap::{BridgeLock, BridgeSecret, Htlc, HtlcState};
+pub use attestation::{LockAttestation, OracleSet, OracleSetChange, OracleSetUpdate};
+pub use fees::{FeeOracle, FeeQuote};
+pub use lock_proof::LockInclusionProof;
@@ -0,0 +1,574 @@
+// src/bridge/relayer.rs - Persistent relay queue for bridge transactions
+//
+// `BridgeOracle` tracks bridges in memory only. The relayer keeps every
+// bridge transaction it has seen in a job queue on disk, written after each
+// change, so a restarted relayer picks up confirmation tracking where it
+// left off (the lock block is part of the job) and never mints twice.
+// Failed mints are retried with exponential backoff and given up after
+// `MAX_MINT_ATTEMPTS`, leaving the job `Failed` for an operator to inspect.
+// The lock-event scan cursor is saved next to the queue, always after the
+// jobs it produced, so a crash between the two rescans a range instead of
+// skipping it; rescanned locks keep their IDs and are not queued twice.
+// A ready job is only minted once a quorum of the oracle set has attested
+// its lock event; attestations are kept with the job. A job not minted by
+// its expiry is failed, after which its lock can be refunded.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+
+use super::attestation::{LockAttestation, OracleSet, OracleSetUpdate};
+use super::cross_chain::{BridgeOracle, BridgeStatus, BridgeTransaction, LockCursor};
+use crate::error::{AxiomError, Result};
+
+/// Default job queue file, in the relayer's working directory.
+pub const RELAYER_STATE_FILE: &str = "bridge_relayer.json";
+
+/// Default lock-event cursor file.
+pub const LOCK_CURSOR_FILE: &str = "bridge_cursor.json";
+
+/// Mint attempts before a job is marked `Failed`.
+pub const MAX_MINT_ATTEMPTS: u32 = 8;
+
+/// First retry delay; doubled on every further failure.
+pub const RETRY_BASE_SECS: u64 = 30;
+
+/// Longest delay between two mint attempts.
+pub const RETRY_MAX_SECS: u64 = 3600;
+
+/// One bridge transaction and its relay bookkeeping.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayJob {
+    pub tx: BridgeTransaction,
+    /// Failed mint attempts so far.
+    pub attempts: u32,
+    /// Unix time before which no mint is attempted.
+    pub next_attempt_at: u64,
+    pub last_error: Option<String>,
+    /// Destination-chain transaction of a successful mint.
+    pub mint_tx: Option<String>,
+    /// Source-chain transaction that returned the lock to its sender.
+    #[serde(default)]
+    pub refund_tx: Option<String>,
+    /// Oracle signatures over the lock event.
+    #[serde(default)]
+    pub attestations: Vec<LockAttestation>,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+impl RelayJob {
+    /// Whether the relayer is done with this job.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self.tx.status, BridgeStatus::Minted | BridgeStatus::Failed { .. } | BridgeStatus::Refunded)
+    }
+}
+
+/// Delay before retry number `attempts` (counting from 1).
+pub fn retry_delay(attempts: u32) -> u64 {
+    RETRY_BASE_SECS
+        .saturating_mul(1u64 << attempts.saturating_sub(1).min(20))
+        .min(RETRY_MAX_SECS)
+}
+
+/// Job counts by state, served by the relayer status API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueSummary {
+    pub total: usize,
+    pub pending: usize,
+    pub confirming: usize,
+    pub ready_to_mint: usize,
+    /// Ready jobs waiting out a retry backoff.
+    pub retrying: usize,
+    pub minted: usize,
+    pub failed: usize,
+    pub refunded: usize,
+}
+
+impl QueueSummary {
+    pub fn of<'a>(jobs: impl IntoIterator<Item = &'a RelayJob>, now: u64) -> Self {
+        let mut summary = QueueSummary::default();
+        for job in jobs {
+            summary.total += 1;
+            match job.tx.status {
+                BridgeStatus::Pending => summary.pending += 1,
+                BridgeStatus::Confirming { .. } => summary.confirming += 1,
+                BridgeStatus::ReadyToMint if job.next_attempt_at > now => summary.retrying += 1,
+                BridgeStatus::ReadyToMint => summary.ready_to_mint += 1,
+                BridgeStatus::Minted => summary.minted += 1,
+                BridgeStatus::Failed { .. } => summary.failed += 1,
+                BridgeStatus::Refunded => summary.refunded += 1,
+            }
+        }
+        summary
+    }
+}
+
+/// Bridge jobs keyed by hex bridge ID, saved as JSON.
+#[derive(Debug)]
+pub struct JobQueue {
+    path: PathBuf,
+    jobs: BTreeMap<String, RelayJob>,
+}
+
+impl JobQueue {
+    /// Load the queue at `path`, or start empty if there is none. A corrupt
+    /// file is an error rather than an empty queue: starting over could
+    /// mint a bridge a second time.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let jobs = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| AxiomError::DatabaseError(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path, jobs })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let json = serde_json::to_vec_pretty(&self.jobs)
+            .map_err(|e| AxiomError::SerializationError(e.to_string()))?;
+        crate::storage::write_atomic(&self.path, &json)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&RelayJob> {
+        self.jobs.get(id)
+    }
+
+    pub fn jobs(&self) -> impl Iterator<Item = &RelayJob> {
+        self.jobs.values()
+    }
+
+    /// Add a bridge transaction not seen before. Returns false for a known
+    /// ID, whose job is left as it is.
+    pub fn insert(&mut self, tx: BridgeTransaction, now: u64) -> bool {
+        let id = hex::encode(tx.id);
+        if self.jobs.contains_key(&id) {
+            return false;
+        }
+        self.jobs.insert(id, RelayJob {
+            tx,
+            attempts: 0,
+            next_attempt_at: 0,
+            last_error: None,
+            mint_tx: None,
+            refund_tx: None,
+            attestations: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        });
+        true
+    }
+
+    /// Bridges still waiting for source-chain confirmations.
+    pub fn confirming(&self) -> Vec<BridgeTransaction> {
+        self.jobs
+            .values()
+            .filter(|j| matches!(j.tx.status, BridgeStatus::Pending | BridgeStatus::Confirming { .. }))
+            .map(|j| j.tx.clone())
+            .collect()
+    }
+
+    /// Take the confirmation counts and status computed by the oracle.
+    /// Returns whether anything changed.
+    pub fn update_tracking(&mut self, tracked: &[BridgeTransaction], now: u64) -> bool {
+        let mut changed = false;
+        for tx in tracked {
+            let Some(job) = self.jobs.get_mut(&hex::encode(tx.id)) else { continue };
+            if job.is_terminal() || (job.tx.status == tx.status && job.tx.confirmations == tx.confirmations) {
+                continue;
+            }
+            job.tx.status = tx.status.clone();
+            job.tx.confirmations = tx.confirmations;
+            job.updated_at = now;
+            changed = true;
+        }
+        changed
+    }
+
+    /// IDs of unexpired ready jobs whose backoff has passed.
+    pub fn due_for_mint(&self, now: u64) -> Vec<String> {
+        self.jobs
+            .iter()
+            .filter(|(_, j)| j.tx.status == BridgeStatus::ReadyToMint && j.next_attempt_at <= now)
+            .filter(|(_, j)| !j.tx.is_expired(now))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Keep an attestation that `oracle_set` accepts for the job's lock.
+    /// Returns false if the oracle had already attested it.
+    pub fn add_attestation(&mut self, attestation: LockAttestation, oracle_set: &OracleSet, now: u64) -> Result<bool> {
+        let id = hex::encode(attestation.bridge_id);
+        let job = self.jobs.get_mut(&id).ok_or_else(|| AxiomError::NotFound(format!("bridge {}", id)))?;
+        oracle_set.verify(&attestation, &job.tx)?;
+        if job.attestations.iter().any(|a| a.oracle == attestation.oracle) {
+            return Ok(false);
+        }
+        job.attestations.push(attestation);
+        job.updated_at = now;
+        Ok(true)
+    }
+
+    pub fn record_minted(&mut self, id: &str, mint_tx: String, now: u64) {
+        if let Some(job) = self.jobs.get_mut(id) {
+            job.tx.status = BridgeStatus::Minted;
+            job.mint_tx = Some(mint_tx);
+            job.last_error = None;
+            job.updated_at = now;
+        }
+    }
+
+    /// Schedule a retry after a failed mint, or fail the job once it has
+    /// used up its attempts.
+    pub fn record_mint_failure(&mut self, id: &str, error: &str, now: u64) {
+        let Some(job) = self.jobs.get_mut(id) else { return };
+        job.attempts += 1;
+        job.last_error = Some(error.to_string());
+        job.updated_at = now;
+        if job.attempts >= MAX_MINT_ATTEMPTS {
+            job.tx.status = BridgeStatus::Failed {
+                reason: format!("mint failed {} times, last: {}", job.attempts, error),
+            };
+        } else {
+            job.next_attempt_at = now + retry_delay(job.attempts);
+        }
+    }
+
+    /// Fail unfinished jobs past their expiry. Returns how many.
+    pub fn expire(&mut self, now: u64) -> usize {
+        let mut expired = 0;
+        for job in self.jobs.values_mut() {
+            if !job.is_terminal() && job.tx.is_expired(now) {
+                job.tx.status = BridgeStatus::Failed { reason: "expired before minting".into() };
+                job.updated_at = now;
+                expired += 1;
+            }
+        }
+        expired
+    }
+
+    pub fn record_refunded(&mut self, id: &str, refund_tx: String, now: u64) {
+        if let Some(job) = self.jobs.get_mut(id) {
+            job.tx.status = BridgeStatus::Refunded;
+            job.refund_tx = Some(refund_tx);
+            job.updated_at = now;
+        }
+    }
+
+    pub fn summary(&self, now: u64) -> QueueSummary {
+        QueueSummary::of(self.jobs.values(), now)
+    }
+}
+
+/// What one relayer pass did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TickReport {
+    pub discovered: usize,
+    pub minted: usize,
+    pub mint_failures: usize,
+    /// Jobs failed for passing their expiry unminted.
+    pub expired: usize,
+    /// Ready jobs still short of an attestation quorum.
+    pub awaiting_attestations: usize,
+}
+
+/// Drives bridge jobs from lock to mint: finds new locks, tracks their
+/// confirmations and mints once they are deep enough and attested.
+pub struct Relayer {
+    oracle: BridgeOracle,
+    queue: JobQueue,
+    /// Set when this relayer is itself a registered oracle.
+    signing_key: Option<SigningKey>,
+    /// Where the lock-event cursor is persisted, if anywhere.
+    cursor_path: Option<PathBuf>,
+}
+
+impl Relayer {
+    pub fn new(oracle: BridgeOracle, queue: JobQueue) -> Self {
+        Self { oracle, queue, signing_key: None, cursor_path: None }
+    }
+
+    /// Resume lock scanning from the cursor at `path` and keep it updated.
+    pub fn with_cursor_file(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        self.oracle.lock_cursor = LockCursor::load(&path)?;
+        self.cursor_path = Some(path);
+        Ok(self)
+    }
+
+    /// Attest every lock that reaches `ReadyToMint` with `key`.
+    pub fn with_signing_key(mut self, key: SigningKey) -> Self {
+        self.signing_key = Some(key);
+        self
+    }
+
+    pub fn queue(&self) -> &JobQueue {
+        &self.queue
+    }
+
+    pub fn oracle_set(&self) -> &OracleSet {
+        &self.oracle.oracle_set
+    }
+
+    /// Take an attestation from another oracle.
+    pub fn attest(&mut self, attestation: LockAttestation, now: u64) -> Result<bool> {
+        let added = self.queue.add_attestation(attestation, &self.oracle.oracle_set, now)?;
+        if added {
+            self.queue.save()?;
+        }
+        Ok(added)
+    }
+
+    /// Apply a quorum-approved oracle set change. The caller persists the
+    /// new set.
+    pub fn update_oracle_set(&mut self, update: &OracleSetUpdate) -> Result<&OracleSet> {
+        self.oracle.oracle_set.apply(update)?;
+        Ok(&self.oracle.oracle_set)
+    }
+
+    /// Queue a bridge created outside the relayer, e.g. an Axiom-side lock
+    /// from [`super::AxiomBridge::bridge_to`].
+    pub fn submit(&mut self, tx: BridgeTransaction, now: u64) -> Result<bool> {
+        let added = self.queue.insert(tx, now);
+        if added {
+            self.queue.save()?;
+        }
+        Ok(added)
+    }
+
+    /// Refund a failed job's lock on its source chain.
+    pub async fn refund(&mut self, id: &str, now: u64) -> Result<String> {
+        let job = self.queue.get(id).ok_or_else(|| AxiomError::NotFound(format!("bridge {}", id)))?;
+        let contract = self.oracle.contracts.get(&job.tx.from_chain)
+            .ok_or_else(|| AxiomError::BridgeError(format!("no bridge contract on {:?}", job.tx.from_chain)))?;
+        let refund_tx = contract.refund_lock(&job.tx, now).await?;
+        log::info!("RELAYER: bridge {} refunded on {:?}: {}", id, job.tx.from_chain, refund_tx);
+        self.queue.record_refunded(id, refund_tx.clone(), now);
+        self.queue.save()?;
+        Ok(refund_tx)
+    }
+
+    /// One pass over the queue. Source-chain RPC failures are logged and
+    /// retried next pass; only failing to save the queue is an error.
+    pub async fn tick(&mut self, now: u64) -> Result<TickReport> {
+        let mut report = TickReport::default();
+
+        self.oracle.pending_bridges.clear();
+        let cursor = self.oracle.lock_cursor.clone();
+        if let Err(e) = self.oracle.monitor_locks().await {
+            log::warn!("RELAYER: lock scan failed: {}", e);
+        }
+        for tx in std::mem::take(&mut self.oracle.pending_bridges) {
+            if self.queue.insert(tx, now) {
+                report.discovered += 1;
+            }
+        }
+        if report.discovered > 0 {
+            self.queue.save()?;
+        }
+        if let Some(path) = &self.cursor_path {
+            if self.oracle.lock_cursor != cursor {
+                self.oracle.lock_cursor.save(path)?;
+            }
+        }
+
+        self.oracle.pending_bridges = self.queue.confirming();
+        if !self.oracle.pending_bridges.is_empty() {
+            match self.oracle.update_confirmations().await {
+                Ok(()) => {
+                    if self.queue.update_tracking(&self.oracle.pending_bridges, now) {
+                        self.queue.save()?;
+                    }
+                }
+                Err(e) => log::warn!("RELAYER: confirmation update failed: {}", e),
+            }
+        }
+
+        report.expired = self.queue.expire(now);
+        if report.expired > 0 {
+            self.queue.save()?;
+        }
+
+        let due = self.queue.due_for_mint(now);
+        if let Some(key) = &self.signing_key {
+            let mut signed = false;
+            for id in &due {
+                let Some(tx) = self.queue.get(id).map(|j| j.tx.clone()) else { continue };
+                let attestation = LockAttestation::sign(&tx, key);
+                match self.queue.add_attestation(attestation, &self.oracle.oracle_set, now) {
+                    Ok(added) => signed |= added,
+                    Err(e) => log::warn!("RELAYER: cannot attest bridge {}: {}", id, e),
+                }
+            }
+            if signed {
+                self.queue.save()?;
+            }
+        }
+
+        for id in due {
+            let Some(job) = self.queue.get(&id) else { continue };
+            if let Err(e) = self.oracle.oracle_set.check_quorum(&job.tx, &job.attestations) {
+                log::debug!("RELAYER: holding mint: {}", e);
+                report.awaiting_attestations += 1;
+                continue;
+            }
+            let tx = job.tx.clone();
+            let result = match self.oracle.contracts.get(&tx.to_chain) {
+                Some(contract) => contract.mint_wrapped(&tx, &self.oracle.oracle_set).await,
+                None => Err(AxiomError::BridgeError(format!("no bridge contract on {:?}", tx.to_chain))),
+            };
+            match result {
+                Ok(mint_tx) => {
+                    log::info!("RELAYER: bridge {} minted on {:?}: {}", id, tx.to_chain, mint_tx);
+                    self.queue.record_minted(&id, mint_tx, now);
+                    report.minted += 1;
+                }
+                Err(e) => {
+                    log::warn!("RELAYER: mint of bridge {} failed: {}", id, e);
+                    self.queue.record_mint_failure(&id, &e.to_string(), now);
+                    report.mint_failures += 1;
+                }
+            }
+            self.queue.save()?;
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::ChainId;
+
+    fn bridge_tx(id: u8, status: BridgeStatus) -> BridgeTransaction {
+        BridgeTransaction {
+            id: [id; 32],
+            from_chain: ChainId::Ethereum,
+            to_chain: ChainId::Axiom,
+            sender: "0xabc".into(),
+            recipient: "axm1".into(),
+            amount: 1_000,
+            token: "AXM".into(),
+            status,
+            timestamp: 0,
+            confirmations: 0,
+            required_confirmations: 12,
+            zk_proof: vec![],
+            lock_block: 100,
+            expires_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_queue_survives_restart() {
+        let path = std::env::temp_dir().join(format!("axiom_relayer_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut queue = JobQueue::open(&path).unwrap();
+        assert!(queue.insert(bridge_tx(1, BridgeStatus::Pending), 10));
+        assert!(queue.insert(bridge_tx(2, BridgeStatus::ReadyToMint), 10));
+        assert!(!queue.insert(bridge_tx(1, BridgeStatus::ReadyToMint), 11));
+
+        let mut tracked = bridge_tx(1, BridgeStatus::Confirming { current: 5, required: 12 });
+        tracked.confirmations = 5;
+        assert!(queue.update_tracking(&[tracked.clone()], 20));
+        assert!(!queue.update_tracking(&[tracked], 21));
+        queue.save().unwrap();
+
+        let reopened = JobQueue::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        let job = reopened.get(&hex::encode([1u8; 32])).unwrap();
+        assert_eq!((job.tx.confirmations, job.tx.lock_block, job.updated_at), (5, 100, 20));
+        assert_eq!(reopened.confirming().len(), 1);
+        assert_eq!(reopened.due_for_mint(30), vec![hex::encode([2u8; 32])]);
+
+        std::fs::write(&path, b"{ not json").unwrap();
+        assert!(JobQueue::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_mint_retries_back_off_then_fail() {
+        let mut queue = JobQueue::open(std::env::temp_dir().join("axiom_relayer_unsaved.json")).unwrap();
+        let id = hex::encode([3u8; 32]);
+        queue.insert(bridge_tx(3, BridgeStatus::ReadyToMint), 0);
+
+        queue.record_mint_failure(&id, "rpc down", 1_000);
+        assert_eq!(queue.get(&id).unwrap().next_attempt_at, 1_000 + RETRY_BASE_SECS);
+        assert!(queue.due_for_mint(1_000).is_empty());
+        assert_eq!(queue.summary(1_000).retrying, 1);
+        assert_eq!(retry_delay(2), 2 * RETRY_BASE_SECS);
+        assert_eq!(retry_delay(30), RETRY_MAX_SECS);
+
+        for _ in 1..MAX_MINT_ATTEMPTS {
+            queue.record_mint_failure(&id, "rpc down", 2_000);
+        }
+        let job = queue.get(&id).unwrap();
+        assert!(job.is_terminal() && matches!(job.tx.status, BridgeStatus::Failed { .. }));
+        assert_eq!(queue.summary(2_000).failed, 1);
+
+        let minted = hex::encode([4u8; 32]);
+        queue.insert(bridge_tx(4, BridgeStatus::ReadyToMint), 0);
+        queue.record_minted(&minted, "0xfeed".into(), 5);
+        assert!(queue.due_for_mint(10).is_empty());
+        assert_eq!(queue.get(&minted).unwrap().mint_tx.as_deref(), Some("0xfeed"));
+
+        let stale = hex::encode([5u8; 32]);
+        let mut expiring = bridge_tx(5, BridgeStatus::ReadyToMint);
+        expiring.expires_at = 3_000;
+        queue.insert(expiring, 0);
+        assert!(queue.due_for_mint(2_999).contains(&stale));
+        assert!(!queue.due_for_mint(3_000).contains(&stale));
+        assert_eq!(queue.expire(3_000), 1);
+        assert!(queue.get(&stale).unwrap().tx.is_refundable(3_000));
+        queue.record_refunded(&stale, "0xback".into(), 3_001);
+        assert_eq!(queue.summary(3_001).refunded, 1);
+        assert_eq!(queue.expire(4_000), 0);
+    }
+
+    #[tokio::test]
+    async fn test_mint_waits_for_attestations() {
+        let keys: Vec<_> = (1..=3u8).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let oracle_set = OracleSet::new(2, keys.iter().map(|k| k.verifying_key().to_bytes()).collect()).unwrap();
+        let mut oracle = BridgeOracle::new().with_oracle_set(oracle_set);
+        // No source chains to poll: this test only exercises the mint gate.
+        oracle.contracts.retain(|chain, _| *chain == ChainId::Axiom);
+        let path = std::env::temp_dir().join(format!("axiom_relayer_attest_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut relayer = Relayer::new(oracle, JobQueue::open(&path).unwrap()).with_signing_key(keys[0].clone());
+        let id = hex::encode([6u8; 32]);
+        let tx = bridge_tx(6, BridgeStatus::ReadyToMint);
+        relayer.submit(tx.clone(), 0).unwrap();
+
+        let report = relayer.tick(10).await.unwrap();
+        assert_eq!((report.minted, report.awaiting_attestations), (0, 1));
+        let job = relayer.queue().get(&id).unwrap();
+        assert_eq!((job.attestations.len(), job.attempts), (1, 0));
+
+        let outsider = LockAttestation::sign(&tx, &SigningKey::from_bytes(&[9; 32]));
+        assert!(relayer.attest(outsider, 11).is_err());
+        assert!(relayer.attest(LockAttestation::sign(&tx, &keys[1]), 11).unwrap());
+        assert!(!relayer.attest(LockAttestation::sign(&tx, &keys[1]), 12).unwrap());
+
+        let report = relayer.tick(20).await.unwrap();
+        assert_eq!((report.minted, report.awaiting_attestations), (1, 0));
+        assert_eq!(relayer.queue().get(&id).unwrap().tx.status, BridgeStatus::Minted);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,435 @@
+// src/bridge/solana.rs - Solana side of the bridge
+//
+// Locks are instructions to the bridge program, which logs
+// `AxiomLock <sender> <recipient> <amount>`. The adapter lists the
+// program's finalized signatures, fetches each transaction and reads the
+// log lines the bridge program itself emitted; the invoke stack is tracked
+// so a lock line printed by any other program is ignored. Heights are
+// finalized slots, so nothing found can be rolled back.
+//
+// Mints and burns are single-instruction legacy transactions signed with
+// the relayer's ed25519 key, using Anchor-style instruction discriminators.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ed25519_dalek::{Signer, SigningKey};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::adapter::{ChainAdapter, ChainReceipt, LockEvent};
+use super::cross_chain::{BridgeOracle, BridgeTransaction, ChainId};
+use crate::error::{AxiomError, Result};
+
+/// Default bridge program ID, overridable with `AXIOM_SOLANA_PROGRAM`.
+pub const SOLANA_BRIDGE_PROGRAM: &str = "AxiomBridge11111111111111111111111111111111";
+
+/// Log line prefix of a lock emitted by the bridge program.
+const LOCK_LOG_PREFIX: &str = "Program log: AxiomLock ";
+
+/// Signatures requested per `getSignaturesForAddress` page.
+const SIGNATURE_PAGE: usize = 1000;
+
+/// How long a submitted transaction may take to finalize. A blockhash
+/// expires after ~150 slots, so a transaction not final by then never will be.
+pub const FINALIZE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Fee per transaction signature, in lamports. Bridge calls carry one.
+pub const SIGNATURE_FEE_LAMPORTS: u64 = 5_000;
+
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+pub fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // Little-endian base-58 digits of the big-endian number `bytes`.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let mut text = "1".repeat(zeros);
+    text.extend(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char));
+    text
+}
+
+pub fn base58_decode(text: &str) -> Result<Vec<u8>> {
+    let zeros = text.bytes().take_while(|&c| c == b'1').count();
+    let mut bytes: Vec<u8> = Vec::with_capacity(text.len());
+    for c in text.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| AxiomError::InvalidConfig(format!("invalid base58 '{}'", text)))? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes.iter().rev());
+    Ok(decoded)
+}
+
+/// A 32-byte public key (account address) in base58.
+pub fn parse_pubkey(text: &str) -> Result<[u8; 32]> {
+    base58_decode(text)?
+        .try_into()
+        .map_err(|_| AxiomError::BridgeError(format!("'{}' is not a Solana address", text)))
+}
+
+/// Solana's compact-u16 length prefix.
+fn short_vec_len(mut len: usize, out: &mut Vec<u8>) {
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Anchor instruction data: `sha256("global:<name>")[..8]`, then the
+/// amount and bridge ID.
+pub fn instruction_data(name: &str, amount: u64, bridge_id: [u8; 32]) -> Vec<u8> {
+    let discriminator = Sha256::digest(format!("global:{}", name).as_bytes());
+    [&discriminator[..8], &amount.to_le_bytes(), &bridge_id].concat()
+}
+
+/// Legacy message with one instruction to `program`. `payer` signs and
+/// pays; `writable` accounts follow it, then the read-only program.
+pub fn bridge_message(
+    payer: [u8; 32],
+    writable: &[[u8; 32]],
+    program: [u8; 32],
+    recent_blockhash: [u8; 32],
+    data: &[u8],
+) -> Vec<u8> {
+    let keys: Vec<[u8; 32]> = std::iter::once(payer)
+        .chain(writable.iter().copied())
+        .chain(std::iter::once(program))
+        .collect();
+    // One signer, no read-only signers, the program as the one read-only
+    // unsigned account.
+    let mut message = vec![1, 0, 1];
+    short_vec_len(keys.len(), &mut message);
+    keys.iter().for_each(|key| message.extend_from_slice(key));
+    message.extend_from_slice(&recent_blockhash);
+    short_vec_len(1, &mut message);
+    message.push((keys.len() - 1) as u8);
+    short_vec_len(keys.len() - 1, &mut message);
+    message.extend(0..(keys.len() - 1) as u8);
+    short_vec_len(data.len(), &mut message);
+    message.extend_from_slice(data);
+    message
+}
+
+/// `(sender, recipient, amount)` of each lock `program` logged, in order.
+/// Lines printed while another program is on top of the invoke stack do
+/// not count.
+pub fn parse_lock_logs(logs: &[String], program: &str) -> Vec<(String, String, u64)> {
+    let mut stack: Vec<&str> = Vec::new();
+    let mut locks = Vec::new();
+    for line in logs {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["Program", id, "invoke", _] => stack.push(*id),
+            ["Program", id, "success"] | ["Program", id, "failed:", ..] if stack.last() == Some(id) => {
+                stack.pop();
+            }
+            _ if stack.last() == Some(&program) => {
+                if let Some(rest) = line.strip_prefix(LOCK_LOG_PREFIX) {
+                    if let [sender, recipient, amount] = rest.split_whitespace().collect::<Vec<_>>()[..] {
+                        match amount.parse::<u64>() {
+                            Ok(amount) if amount > 0 => {
+                                locks.push((sender.to_string(), recipient.to_string(), amount));
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    locks
+}
+
+/// Solana through its JSON-RPC API.
+pub struct SolanaAdapter {
+    rpc_url: String,
+    program: String,
+    program_id: [u8; 32],
+    signer: Option<SigningKey>,
+    http: reqwest::Client,
+}
+
+impl SolanaAdapter {
+    pub fn new(rpc_url: String, program: &str) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| AxiomError::NetworkError(format!("HTTP client error: {}", e)))?;
+        Ok(Self {
+            rpc_url,
+            program: program.to_string(),
+            program_id: parse_pubkey(program)
+                .map_err(|_| AxiomError::InvalidConfig(format!("invalid Solana program ID '{}'", program)))?,
+            signer: None,
+            http,
+        })
+    }
+
+    /// `AXIOM_RPC_SOLANA` and `AXIOM_SOLANA_PROGRAM`, or the defaults.
+    pub fn from_env() -> Result<Self> {
+        let program = std::env::var("AXIOM_SOLANA_PROGRAM")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| SOLANA_BRIDGE_PROGRAM.to_string());
+        Self::new(BridgeOracle::resolve_rpc_url(&ChainId::Solana)?, &program)
+    }
+
+    pub fn with_signer(mut self, signer: SigningKey) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let body = json!({ "jsonrpc": "2.0", "method": method, "params": params, "id": 1 });
+        let resp = self
+            .http
+            .post(&self.rpc_url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("{} to {} failed: {}", method, self.rpc_url, e)))?;
+        if !resp.status().is_success() {
+            return Err(AxiomError::NetworkError(format!("RPC endpoint {} returned HTTP {}", self.rpc_url, resp.status())));
+        }
+        let mut json: Value = resp
+            .json()
+            .await
+            .map_err(|e| AxiomError::NetworkError(format!("Failed to parse RPC response: {}", e)))?;
+        if let Some(err) = json.get("error") {
+            return Err(AxiomError::NetworkError(format!("{} error: {}", method, err)));
+        }
+        Ok(json["result"].take())
+    }
+
+    /// Successful finalized transactions touching the bridge program in
+    /// slots `from..=to`, oldest first.
+    async fn signatures_in(&self, from: u64, to: u64) -> Result<Vec<(String, u64)>> {
+        let mut found = Vec::new();
+        let mut before: Option<String> = None;
+        loop {
+            let mut options = json!({ "limit": SIGNATURE_PAGE, "commitment": "finalized" });
+            if let Some(sig) = &before {
+                options["before"] = json!(sig);
+            }
+            let page = self.call("getSignaturesForAddress", json!([self.program, options])).await?;
+            let page = page.as_array().cloned().unwrap_or_default();
+            let mut reached_start = page.len() < SIGNATURE_PAGE;
+            for entry in &page {
+                let (Some(sig), Some(slot)) = (entry["signature"].as_str(), entry["slot"].as_u64()) else {
+                    continue;
+                };
+                before = Some(sig.to_string());
+                if slot < from {
+                    reached_start = true;
+                    break;
+                }
+                if slot <= to && entry["err"].is_null() {
+                    found.push((sig.to_string(), slot));
+                }
+            }
+            if reached_start {
+                break;
+            }
+        }
+        found.reverse();
+        Ok(found)
+    }
+
+    async fn submit(&self, writable: &[[u8; 32]], data: Vec<u8>) -> Result<ChainReceipt> {
+        let signer = self.signer.as_ref()
+            .ok_or_else(|| AxiomError::BridgeError("No Solana signer configured".into()))?;
+        let latest = self.call("getLatestBlockhash", json!([{ "commitment": "finalized" }])).await?;
+        let blockhash = latest["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| AxiomError::NetworkError("missing blockhash in getLatestBlockhash response".into()))?;
+        let payer = signer.verifying_key().to_bytes();
+        let message = bridge_message(payer, writable, self.program_id, parse_pubkey(blockhash)?, &data);
+
+        let mut raw = Vec::with_capacity(1 + 64 + message.len());
+        short_vec_len(1, &mut raw);
+        raw.extend_from_slice(&signer.sign(&message).to_bytes());
+        raw.extend_from_slice(&message);
+        let encoded = data_encoding::BASE64.encode(&raw);
+        let signature = self
+            .call("sendTransaction", json!([encoded, { "encoding": "base64", "preflightCommitment": "confirmed" }]))
+            .await?
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| AxiomError::NetworkError("missing signature in sendTransaction response".into()))?;
+        log::info!("📤 Sent {} on Solana", signature);
+
+        let slot = self.wait_finalized(&signature).await?;
+        Ok(ChainReceipt { tx_hash: signature, sender: base58_encode(&payer), block_number: slot })
+    }
+
+    async fn wait_finalized(&self, signature: &str) -> Result<u64> {
+        let deadline = tokio::time::Instant::now() + FINALIZE_TIMEOUT;
+        loop {
+            match self.call("getSignatureStatuses", json!([[signature]])).await {
+                Ok(result) => {
+                    let status = &result["value"][0];
+                    if !status.is_null() && !status["err"].is_null() {
+                        return Err(AxiomError::BridgeError(format!("{} failed: {}", signature, status["err"])));
+                    }
+                    if status["confirmationStatus"] == "finalized" {
+                        if let Some(slot) = status["slot"].as_u64() {
+                            return Ok(slot);
+                        }
+                    }
+                }
+                Err(e) => log::debug!("Status poll for {} failed: {}", signature, e),
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(AxiomError::RpcTimeout);
+            }
+            tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[async_trait]
+impl ChainAdapter for SolanaAdapter {
+    fn chain(&self) -> ChainId {
+        ChainId::Solana
+    }
+
+    /// `head()` is already the finalized slot.
+    fn required_confirmations(&self) -> u32 {
+        1
+    }
+
+    async fn head(&self) -> Result<u64> {
+        self.call("getSlot", json!([{ "commitment": "finalized" }]))
+            .await?
+            .as_u64()
+            .ok_or_else(|| AxiomError::NetworkError("invalid getSlot response".into()))
+    }
+
+    async fn lock_events(&self, from: u64, to: u64) -> Result<Vec<LockEvent>> {
+        let mut events = Vec::new();
+        for (signature, slot) in self.signatures_in(from, to).await? {
+            let tx = self
+                .call(
+                    "getTransaction",
+                    json!([signature, { "encoding": "json", "commitment": "finalized", "maxSupportedTransactionVersion": 0 }]),
+                )
+                .await?;
+            if !tx["meta"]["err"].is_null() {
+                continue;
+            }
+            let logs: Vec<String> = tx["meta"]["logMessages"]
+                .as_array()
+                .map(|lines| lines.iter().filter_map(|l| l.as_str().map(str::to_string)).collect())
+                .unwrap_or_default();
+            for (index, (sender, recipient, amount)) in parse_lock_logs(&logs, &self.program).into_iter().enumerate() {
+                events.push(LockEvent {
+                    sender,
+                    recipient,
+                    amount,
+                    block_number: slot,
+                    tx_hash: signature.clone(),
+                    log_index: index as u64,
+                });
+            }
+        }
+        Ok(events)
+    }
+
+    async fn submit_mint(&self, bridge_tx: &BridgeTransaction) -> Result<ChainReceipt> {
+        let recipient = parse_pubkey(&bridge_tx.recipient)?;
+        self.submit(&[recipient], instruction_data("mint", bridge_tx.amount, bridge_tx.id)).await
+    }
+
+    async fn submit_burn(&self, amount: u64, bridge_id: [u8; 32]) -> Result<ChainReceipt> {
+        self.submit(&[], instruction_data("burn", amount, bridge_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+
+    #[test]
+    fn test_base58_and_message_layout() {
+        assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(base58_decode("StV1DL6CwTryKyV").unwrap(), b"hello world");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+        assert_eq!(parse_pubkey("11111111111111111111111111111111").unwrap(), [0u8; 32]);
+        let program = parse_pubkey(SOLANA_BRIDGE_PROGRAM).unwrap();
+        assert_eq!(base58_encode(&program), SOLANA_BRIDGE_PROGRAM);
+        assert!(base58_decode("0OIl").is_err());
+
+        let key = SigningKey::from_bytes(&[5; 32]);
+        let payer = key.verifying_key().to_bytes();
+        let data = instruction_data("mint", 7, [3u8; 32]);
+        assert_eq!(data.len(), 8 + 8 + 32);
+        let message = bridge_message(payer, &[[2u8; 32]], program, [9u8; 32], &data);
+        assert_eq!(&message[..4], &[1, 0, 1, 3]);
+        assert_eq!(&message[4..36], &payer);
+        assert_eq!(&message[68..100], &program);
+        assert_eq!(&message[100..132], &[9u8; 32]);
+        // One instruction: program index 2, accounts [0, 1], then the data.
+        assert_eq!(&message[132..137], &[1, 2, 2, 0, 1]);
+        assert_eq!(message[137] as usize, data.len());
+        assert_eq!(&message[138..], &data[..]);
+        let signature = Signature::from_bytes(&key.sign(&message).to_bytes());
+        assert!(key.verifying_key().verify(&message, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_only_bridge_program_logs_count() {
+        let program = SOLANA_BRIDGE_PROGRAM;
+        let logs: Vec<String> = [
+            format!("Program {} invoke [1]", program),
+            "Program log: AxiomLock alice axm1recipient 500".to_string(),
+            "Program Evil111111111111111111111111111111111 invoke [2]".to_string(),
+            "Program log: AxiomLock mallory axm1thief 1000000".to_string(),
+            "Program Evil111111111111111111111111111111111 success".to_string(),
+            "Program log: AxiomLock bob axm1other 0".to_string(),
+            "Program log: AxiomLock carol axm1third 25".to_string(),
+            format!("Program {} consumed 5000 of 200000 compute units", program),
+            format!("Program {} success", program),
+            "Program log: AxiomLock dave axm1late 9".to_string(),
+        ]
+        .into();
+        assert_eq!(
+            parse_lock_logs(&logs, program),
+            vec![
+                ("alice".to_string(), "axm1recipient".to_string(), 500),
+                ("carol".to_string(), "axm1third".to_string(), 25),
+            ]
+        );
+    }
+}
@@ -1,10 +1,17 @@
-use crate::block::Block;
+use crate::block::{Block, Coinbase};
 use crate::transaction::{Transaction, Address};
-use crate::state::State;
+use crate::state::{State, StateUndo};
 use crate::economics;
-use std::collections::HashSet;
+use crate::consensus::{lwma, uncles};
+use crate::vdf::Vdf;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 pub const TARGET_TIME: u64 = 1800; // 30 Minute Time-Lock (VDF)
+
+/// Difficulty of the block after genesis; each block retargets from it.
+const INITIAL_DIFFICULTY: u64 = 1000;
+
 pub const HALVING_INTERVAL: u64 = 2_100_000;
 pub const INITIAL_REWARD: u64 = 50_000_000_000; // 500 AXM (8 decimals)
 pub const MAX_SUPPLY: u64 = 124_000_000_000_000_000; // 124M AXM in smallest units
@@ -19,6 +26,60 @@ pub struct Timechain {
     pub difficulty: u64,
     seen_hashes: HashSet<[u8; 32]>, // Injection Protection
     pub total_issued: u64,
+    /// One entry per block, for disconnecting blocks on a reorg.
+    undo_log: Vec<BlockUndo>,
+    /// Backend every block's VDF output is checked with.
+    vdf: Arc<dyn Vdf>,
+}
+
+/// What `Timechain::reorganize` did with a competing fork.
+#[derive(Debug, PartialEq)]
+pub enum ForkOutcome {
+    /// The fork is now the chain; these are the blocks it replaced,
+    /// oldest first.
+    Adopted(Vec<Block>),
+    /// The chain is as it was. Carries why a fork block was rejected, or
+    /// None if the fork was valid but not longer.
+    Refused(Option<&'static str>),
+}
+
+/// Everything needed to disconnect one block.
+struct BlockUndo {
+    state: StateUndo,
+    total_issued: u64,
+    difficulty: u64,
+}
+
+/// Apply `block`, the chain's block number `height`, to `state` the way
+/// `rebuild_state` does: the coinbase and uncle rewards, then every
+/// transaction that still applies, with its fee split between burning and
+/// the miner.
+fn replay_block(state: &mut State, total_issued: &mut u64, block: &Block, height: u64, undo: &mut StateUndo) {
+    let reward = block.coinbase.reward;
+    if reward > 0 && block.miner != [0u8; 32] {
+        state.credit_logged(block.miner, reward, undo);
+        *total_issued += reward;
+    }
+    for (miner, reward) in uncles::uncle_payouts(block, economics::UNCLE_POLICY) {
+        state.credit_logged(miner, reward, undo);
+        *total_issued += reward;
+    }
+
+    // Transactions that no longer apply are skipped, fee included
+    for (position, tx) in block.transactions.iter().enumerate() {
+        if state.apply_tx_logged(tx, height, position as u32, undo).is_ok() {
+            state.settle_fee_logged(block.miner, tx.fee, economics::FEE_POLICY, undo);
+        }
+    }
+}
+
+/// Replay `blocks`, the first of which is block number `first_height`,
+/// onto `state` and `total_issued`.
+pub fn replay_blocks(state: &mut State, total_issued: &mut u64, blocks: &[Block], first_height: u64) {
+    let mut undo = StateUndo::default();
+    for (offset, block) in blocks.iter().enumerate() {
+        replay_block(state, total_issued, block, first_height + offset as u64, &mut undo);
+    }
 }
 
 impl Timechain {
@@ -36,9 +97,11 @@ impl Timechain {
         let mut tc = Timechain {
             blocks: vec![genesis],
             state: State::new(),
-            difficulty: 1000,
+            difficulty: INITIAL_DIFFICULTY,
             seen_hashes: HashSet::new(),
             total_issued: 0,
+            undo_log: Vec::new(),
+            vdf: crate::vdf::block_vdf(),
         };
         tc.rebuild_state();
         tc
@@ -65,9 +128,11 @@ impl Timechain {
         let mut tc = Timechain {
             blocks: saved_blocks,
             state: State::new(),
-            difficulty: 1000,
+            difficulty: INITIAL_DIFFICULTY,
             seen_hashes: HashSet::new(),
             total_issued: 0,
+            undo_log: Vec::new(),
+            vdf: crate::vdf::block_vdf(),
         };
         // Populate seen_hashes for injection protection
         for block in &tc.blocks {
@@ -77,24 +142,41 @@ impl Timechain {
         Ok(tc)
     }
 
-    /// Rebuild state from all blocks
+    /// The difficulty the block at `height` was checked against, which is
+    /// also the VDF iteration count it was timed with.
+    pub fn difficulty_at(&self, height: u64) -> Option<u64> {
+        self.undo_log.get(height as usize).map(|undo| undo.difficulty)
+    }
+
+    /// Check new blocks with `vdf` instead of the consensus default.
+    /// Only for devnets and tests: peers on another backend reject our blocks.
+    pub fn with_vdf(mut self, vdf: Arc<dyn Vdf>) -> Self {
+        self.vdf = vdf;
+        self
+    }
+
+    /// The backend new blocks must be timed with.
+    pub fn vdf(&self) -> Arc<dyn Vdf> {
+        self.vdf.clone()
+    }
+
+    /// Rebuild state from all blocks, retargeting the difficulty after
+    /// each one as `add_block` did, so a restarted node checks the next
+    /// block (and every uncle) at the same difficulty as one that stayed up.
     pub fn rebuild_state(&mut self) {
         self.state = State::new();
         self.total_issued = 0;
-
-        for block in &self.blocks {
-            // Process mining reward
-            let reward = economics::block_reward(block.slot, self.total_issued);
-            if reward > 0 && block.miner != [0u8; 32] {
-                self.state.credit(block.miner, reward);
-                self.total_issued += reward;
-            }
-
-            // Process transactions
-            for tx in &block.transactions {
-                if self.state.apply_tx(tx).is_ok() {
-                    // Transaction successful
-                }
+        self.difficulty = INITIAL_DIFFICULTY;
+        self.undo_log.clear();
+
+        for height in 0..self.blocks.len() {
+            let block = &self.blocks[height];
+            let mut undo = BlockUndo { state: StateUndo::default(), total_issued: self.total_issued, difficulty: self.difficulty };
+            replay_block(&mut self.state, &mut self.total_issued, block, height as u64, &mut undo.state);
+            self.undo_log.push(undo);
+            if height > 0 {
+                let elapsed = block.timestamp.saturating_sub(self.blocks[height - 1].timestamp);
+                self.adjust_difficulty(elapsed);
             }
         }
     }
@@ -109,7 +191,7 @@ impl Timechain {
 
         // 2. VALIDATE BLOCK STRUCTURE
         let prev_block = self.blocks.last().unwrap();
-        if block.parent != prev_block.hash() {
+        if block.parent != prev_block.legacy_hash() {
             return Err("Invalid parent hash");
         }
 
@@ -118,26 +200,31 @@ impl Timechain {
         }
 
         // 3. VALIDATE TIMESTAMP
-        // Block timestamp must be ≥ parent's timestamp (no time travel).
+        // After the median of the last MEDIAN_TIME_SPAN blocks, so a lone
+        // miner cannot drag time back, and ≥ the parent's (no time travel).
         // Allow up to 2 minutes in the future to tolerate clock skew.
-        let prev_ts = prev_block.timestamp;
-        if block.timestamp < prev_ts {
-            return Err("Block timestamp before parent");
-        }
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_secs();
-        if block.timestamp > now + 120 {
-            return Err("Block timestamp too far in the future");
+        match lwma::check_timestamp(&self.recent_headers(), block.timestamp, now) {
+            Ok(()) => {}
+            Err(lwma::TimestampError::NotAfterMedianTimePast { .. }) => {
+                return Err("Block timestamp not after median time past");
+            }
+            Err(lwma::TimestampError::TooFarInFuture { .. }) => {
+                return Err("Block timestamp too far in the future");
+            }
+        }
+        let prev_ts = prev_block.timestamp;
+        if block.timestamp < prev_ts {
+            return Err("Block timestamp before parent");
         }
 
         // 4. VALIDATE VDF PROOF
-        let expected_vdf = crate::main_helper::compute_vdf(
-            crate::vdf::evaluate(block.parent, block.slot),
-            self.difficulty as u32
-        );
-        if block.vdf_proof != expected_vdf {
+        // Checked against the witness, without redoing the delay.
+        let vdf_seed = crate::vdf::evaluate(block.parent, block.slot);
+        if !self.vdf.verify(vdf_seed, self.difficulty, &block.vdf_proof, &block.vdf_witness) {
             return Err("Invalid VDF proof");
         }
 
@@ -147,17 +234,52 @@ impl Timechain {
         }
 
         // 6. VALIDATE TRANSACTIONS
+        // Each sender's transactions carry consecutive nonces, starting
+        // from the one its account expects; checked before any proof.
+        let mut next_nonces: HashMap<Address, u64> = HashMap::new();
+        for tx in &block.transactions {
+            if tx.is_expired_at(block.slot) {
+                return Err("Transaction expired");
+            }
+            let next_nonce = next_nonces.entry(tx.from).or_insert_with(|| self.state.nonce(&tx.from));
+            if tx.nonce < *next_nonce {
+                return Err("Transaction nonce already used");
+            }
+            if tx.nonce > *next_nonce {
+                return Err("Transaction nonce out of order");
+            }
+            *next_nonce += 1;
+        }
         for tx in &block.transactions {
             let sender_balance = self.state.balance(&tx.from);
             tx.validate(sender_balance)?;
         }
 
-        // 7. VALIDATE ZK PASS FOR MINER
+        // 7. VALIDATE COINBASE
+        // The miner may claim exactly the scheduled reward plus its share
+        // of the fees, and never issue past the supply cap.
+        let expected = Coinbase::expected(block.slot, &block.miner, &block.transactions);
+        if block.coinbase.total() > expected.total() {
+            return Err("Coinbase overpays the miner");
+        }
+        if block.coinbase != expected {
+            return Err("Coinbase does not match block reward and fees");
+        }
+
+        // 8. VALIDATE UNCLES
+        uncles::check_uncles(&block, self)?;
+        let uncle_payouts = uncles::uncle_payouts(&block, economics::UNCLE_POLICY);
+        let issued = uncle_payouts.iter().fold(block.coinbase.reward, |sum, (_, r)| sum.saturating_add(*r));
+        if self.total_issued.saturating_add(issued) > economics::TOTAL_SUPPLY {
+            return Err("Block rewards exceed the supply cap");
+        }
+
+        // 9. VALIDATE ZK PASS FOR MINER
         if !crate::genesis::verify_zk_pass(&block.miner, &block.parent, &block.zk_proof) {
             return Err("Invalid miner ZK pass");
         }
 
-        // 8. APPLY BLOCK
+        // 10. APPLY BLOCK
         self.seen_hashes.insert(block_hash);
         // Compute elapsed time from block timestamps.  min(1) prevents
         // division by zero in adjust_difficulty when two blocks carry the
@@ -166,25 +288,125 @@ impl Timechain {
         let elapsed = block.timestamp.saturating_sub(prev_ts).max(1);
         self.blocks.push(block.clone());
 
-        // 9. UPDATE STATE
-        let reward = economics::block_reward(block.slot, self.total_issued);
+        // 11. UPDATE STATE
+        let mut undo = BlockUndo { state: StateUndo::default(), total_issued: self.total_issued, difficulty: self.difficulty };
+        let reward = block.coinbase.reward;
         if reward > 0 && block.miner != [0u8; 32] {
-            self.state.credit(block.miner, reward);
+            self.state.credit_logged(block.miner, reward, &mut undo.state);
+            self.total_issued += reward;
+        }
+        for (miner, reward) in uncle_payouts {
+            self.state.credit_logged(miner, reward, &mut undo.state);
             self.total_issued += reward;
         }
 
-        for tx in &block.transactions {
-            if self.state.apply_tx(tx).is_err() {
+        for (position, tx) in block.transactions.iter().enumerate() {
+            if self.state.apply_tx_logged(tx, block.slot, position as u32, &mut undo.state).is_err() {
+                // Leave the chain exactly as it was before this block.
+                self.undo_log.push(undo);
+                self.disconnect_tip();
                 return Err("Transaction application failed");
             }
+            self.state.settle_fee_logged(block.miner, tx.fee, economics::FEE_POLICY, &mut undo.state);
         }
+        self.undo_log.push(undo);
 
-        // 10. ADJUST DIFFICULTY based on actual block time
+        // 12. ADJUST DIFFICULTY based on actual block time
         self.adjust_difficulty(elapsed);
 
         Ok(())
     }
 
+    /// Disconnect the tip block, restoring the state, issuance and
+    /// difficulty from before it. The genesis block cannot be disconnected.
+    pub fn disconnect_tip(&mut self) -> Option<Block> {
+        if self.blocks.len() <= 1 {
+            return None;
+        }
+        let block = self.blocks.pop()?;
+        let undo = self.undo_log.pop().expect("undo log covers every block");
+        self.state.revert(undo.state);
+        self.total_issued = undo.total_issued;
+        self.difficulty = undo.difficulty;
+        self.seen_hashes.remove(&block.calculate_hash());
+        Some(block)
+    }
+
+    /// Disconnect blocks until the chain is `height` blocks long (at least
+    /// the genesis block is kept). Returns the removed blocks, oldest first.
+    pub fn rollback_to(&mut self, height: u64) -> Vec<Block> {
+        let mut removed = Vec::new();
+        while self.blocks.len() as u64 > height.max(1) {
+            match self.disconnect_tip() {
+                Some(block) => removed.push(block),
+                None => break,
+            }
+        }
+        removed.reverse();
+        removed
+    }
+
+    /// Timestamps (and difficulties) of the last `MEDIAN_TIME_SPAN` blocks,
+    /// which the next block's timestamp is checked against.
+    fn recent_headers(&self) -> Vec<lwma::BlockHeader> {
+        let start = self.blocks.len().saturating_sub(lwma::MEDIAN_TIME_SPAN);
+        self.blocks[start..]
+            .iter()
+            .map(|block| lwma::BlockHeader {
+                height: block.slot,
+                timestamp: block.timestamp,
+                difficulty: self.difficulty_at(block.slot).unwrap_or(self.difficulty).into(),
+            })
+            .collect()
+    }
+
+    /// The earliest timestamp the next block may carry: not before its
+    /// parent's, and after the median time past.
+    pub fn min_next_timestamp(&self) -> u64 {
+        let median = lwma::median_time_past(&self.recent_headers()).map_or(0, |median| median + 1);
+        self.blocks.last().map_or(0, |parent| parent.timestamp).max(median)
+    }
+
+    /// Switch to `branch`, a competing fork whose first block is at height
+    /// `from`, if each of its blocks passes `check` and connects through
+    /// full consensus validation, and the result is strictly longer than
+    /// the chain now. Otherwise our own blocks are reconnected; should one
+    /// of them no longer connect, the chain is restored from its blocks
+    /// as they were, so a refused fork never leaves it shorter.
+    pub fn reorganize(
+        &mut self,
+        from: u64,
+        branch: Vec<Block>,
+        mut check: impl FnMut(&Block) -> Result<(), &'static str>,
+    ) -> ForkOutcome {
+        let old_chain = self.blocks.clone();
+        let ours = self.rollback_to(from);
+        let mut rejected = None;
+        for block in branch {
+            if let Err(e) = check(&block).and_then(|_| self.add_block(block)) {
+                rejected = Some(e);
+                break;
+            }
+        }
+        if rejected.is_none() && self.blocks.len() > old_chain.len() {
+            return ForkOutcome::Adopted(ours);
+        }
+
+        self.rollback_to(from);
+        for block in ours {
+            if let Err(e) = self.add_block(block) {
+                log::error!("Failed to reconnect local block after rejected fork: {} — restoring the chain", e);
+                let vdf = Arc::clone(&self.vdf);
+                // Our own blocks, genesis anchor included: restoring cannot fail.
+                *self = Timechain::from_saved_blocks(old_chain)
+                    .expect("the chain's own blocks restore")
+                    .with_vdf(vdf);
+                break;
+            }
+        }
+        ForkOutcome::Refused(rejected)
+    }
+
     /// Adjust difficulty based on block time using proportional adjustment.
     ///
     /// Uses pure integer arithmetic:
@@ -220,6 +442,13 @@ impl Timechain {
         (mined, remaining, percent)
     }
 
+    /// Mined, circulating and halving figures at the current tip.
+    pub fn supply_snapshot(&self) -> economics::SupplySnapshot {
+        let circulating = self.state.balances.values().fold(0u64, |sum, b| sum.saturating_add(*b));
+        let tip_timestamp = self.blocks.last().map_or(0, |b| b.timestamp);
+        economics::SupplySnapshot::new(self.blocks.len() as u64, self.total_issued, circulating, self.state.fees, tip_timestamp)
+    }
+
     /// Format amount to AXM with decimals
     pub fn format_axm(amount: u64) -> String {
         let whole = amount / 10u64.pow(DECIMALS);
@@ -227,9 +456,229 @@ impl Timechain {
         format!("{}.{:08}", whole, fractional)
     }
 
-    /// Validate a transaction against the current chain state.
+    /// Validate a transaction against the current chain state, for
+    /// inclusion in the next block.
     pub fn validate_transaction(&self, tx: &Transaction) -> Result<(), &'static str> {
+        if tx.is_expired_at(self.blocks.len() as u64) {
+            return Err("Transaction expired");
+        }
+        if tx.nonce < self.state.nonce(&tx.from) {
+            return Err("Transaction nonce already used");
+        }
         let sender_balance = self.state.balance(&tx.from);
         tx.validate(sender_balance)
     }
 }
+
+#[cfg(test)]
+impl Timechain {
+    /// `block` sealed as its miner would have on this chain: passed (unless
+    /// it already carries a pass), timed and worked at the difficulty of its
+    /// height.
+    pub(crate) fn seal(&self, mut block: Block) -> Block {
+        if block.zk_proof.is_empty() {
+            let miner = crate::wallet::Wallet { secret_key: block.miner, address: block.miner };
+            block.zk_proof = crate::genesis::generate_zk_pass(&miner, block.parent);
+        }
+        let difficulty = self.difficulty_at(block.slot).unwrap_or(self.difficulty);
+        let vdf = self.vdf.prove(crate::vdf::evaluate(block.parent, block.slot), difficulty);
+        block.vdf_proof = vdf.output;
+        block.vdf_witness = vdf.witness;
+        while !block.meets_difficulty(difficulty) {
+            block.nonce += 1;
+        }
+        block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(parent: &Block, miner: Address, transactions: Vec<Transaction>) -> Block {
+        parent.test_child().mined_by(miner, transactions)
+    }
+
+    #[test]
+    fn test_rollback_restores_account_state() {
+        let (alice, bob) = ([1u8; 32], [2u8; 32]);
+        let genesis = crate::genesis::genesis();
+        let b1 = block(&genesis, alice, vec![]);
+        let transfer = Transaction { from: alice, to: bob, amount: 500, fee: 10, nonce: 0, expiry_height: 0, zk_proof: vec![], signature: vec![] };
+        let b2 = block(&b1, bob, vec![transfer.clone()]);
+
+        let mut tc = Timechain::from_saved_blocks(vec![genesis.clone(), b1.clone(), b2.clone()]).unwrap();
+        assert_eq!(tc.state.account(&alice).nonce, 1);
+        assert_eq!(tc.state.history(&bob), &[crate::state::TxPointer { height: 2, position: 0, hash: transfer.legacy_hash() }]);
+        // Bob mined the block with the transfer: half its fee, the rest burned.
+        assert_eq!(tc.balance(&bob), economics::block_reward(2, 0) + 500 + 5);
+        assert_eq!(tc.state.fees, economics::FeeTotals { burned: 5, to_miners: 5 });
+
+        assert_eq!(tc.rollback_to(2), vec![b2]);
+        let expected = Timechain::from_saved_blocks(vec![genesis, b1]).unwrap();
+        assert_eq!(tc.state.balances, expected.state.balances);
+        assert_eq!(tc.state.nonces, expected.state.nonces);
+        assert!(tc.state.history.is_empty());
+        assert!(!tc.state.balances.contains_key(&bob));
+        assert_eq!(tc.state.fees, economics::FeeTotals::default());
+        assert_eq!(tc.total_issued, expected.total_issued);
+
+        // The genesis block always stays.
+        tc.rollback_to(0);
+        assert_eq!(tc.blocks.len(), 1);
+    }
+
+    #[test]
+    fn test_coinbase_must_match_reward_and_fees() {
+        let genesis = crate::genesis::genesis();
+        let mut tc = Timechain::new(genesis.clone());
+        tc.difficulty = 1;
+        let mut b1 = block(&genesis, [1u8; 32], vec![]);
+        let seed = crate::vdf::evaluate(b1.parent, b1.slot);
+        let vdf = tc.vdf().prove(seed, tc.difficulty);
+        b1.vdf_proof = vdf.output;
+        b1.vdf_witness = vdf.witness;
+        while !b1.meets_difficulty(tc.difficulty) {
+            b1.nonce += 1;
+        }
+        assert_eq!(b1.coinbase.reward, economics::get_mining_reward(1));
+
+        let mut greedy = b1.clone();
+        greedy.coinbase.reward += 1;
+        while !greedy.meets_difficulty(tc.difficulty) {
+            greedy.nonce += 1;
+        }
+        assert_eq!(tc.add_block(greedy), Err("Coinbase overpays the miner"));
+
+        let mut modest = b1.clone();
+        modest.coinbase.fees = 0;
+        modest.coinbase.reward -= 1;
+        while !modest.meets_difficulty(tc.difficulty) {
+            modest.nonce += 1;
+        }
+        assert_eq!(tc.add_block(modest), Err("Coinbase does not match block reward and fees"));
+
+        // An honest coinbase gets as far as the miner's ZK pass.
+        assert_eq!(tc.add_block(b1), Err("Invalid miner ZK pass"));
+        assert_eq!(tc.blocks.len(), 1);
+    }
+
+    #[test]
+    fn test_block_nonces_must_follow_the_account() {
+        let genesis = crate::genesis::genesis();
+        let mut tc = Timechain::new(genesis.clone());
+        tc.difficulty = 1;
+        tc.state.nonces.insert([1u8; 32], 3);
+        let send = |nonce| Transaction::new([1u8; 32], [2u8; 32], 10, 1, nonce, vec![], vec![]);
+        let mine = |transactions: Vec<Transaction>| {
+            let mut b1 = block(&genesis, [0u8; 32], transactions);
+            let seed = crate::vdf::evaluate(b1.parent, b1.slot);
+            let vdf = tc.vdf().prove(seed, 1);
+            b1.vdf_proof = vdf.output;
+            b1.vdf_witness = vdf.witness;
+            while !b1.meets_difficulty(1) {
+                b1.nonce += 1;
+            }
+            b1
+        };
+
+        let reused = mine(vec![send(2)]);
+        let repeated = mine(vec![send(3), send(3)]);
+        let skipping = mine(vec![send(3), send(5)]);
+        let in_order = mine(vec![send(3), send(4)]);
+        assert_eq!(tc.add_block(reused), Err("Transaction nonce already used"));
+        assert_eq!(tc.add_block(repeated), Err("Transaction nonce already used"));
+        assert_eq!(tc.add_block(skipping), Err("Transaction nonce out of order"));
+        // Consecutive nonces get as far as the (empty) sender's balance.
+        assert_eq!(tc.add_block(in_order), Err("Insufficient balance"));
+
+        assert_eq!(tc.validate_transaction(&send(2)), Err("Transaction nonce already used"));
+    }
+
+    #[test]
+    fn test_restart_checks_blocks_at_the_live_difficulty() {
+        let genesis = crate::genesis::genesis();
+        let mut live = Timechain::new(genesis);
+        for (miner, solvetime) in [(1u8, 600), (2, 900), (3, 5_000)] {
+            let parent = live.blocks.last().unwrap();
+            let next = live.seal(Block { timestamp: parent.timestamp + solvetime, ..block(parent, [miner; 32], vec![]) });
+            live.add_block(next).unwrap();
+        }
+        assert_ne!(live.difficulty_at(2), live.difficulty_at(3), "the chain retargeted");
+
+        let restarted = Timechain::from_saved_blocks(live.blocks.clone()).unwrap();
+        assert_eq!(restarted.difficulty, live.difficulty);
+        for height in 0..live.blocks.len() as u64 {
+            assert_eq!(restarted.difficulty_at(height), live.difficulty_at(height));
+        }
+
+        // Both accept the same uncle, checked at the difficulty of its height.
+        let rival = live.seal(block(&live.blocks[2], [9u8; 32], vec![]));
+        let nephew = Block { uncles: vec![rival], ..block(live.blocks.last().unwrap(), [1u8; 32], vec![]) };
+        assert_eq!(uncles::check_uncles(&nephew, &live), Ok(()));
+        assert_eq!(uncles::check_uncles(&nephew, &restarted), Ok(()));
+    }
+
+    #[test]
+    fn test_refused_fork_leaves_the_chain_as_it_was() {
+        let mine = |tc: &mut Timechain, miner: u8, count: usize| {
+            for _ in 0..count {
+                let next = tc.seal(block(tc.blocks.last().unwrap(), [miner; 32], vec![]));
+                tc.add_block(next).unwrap();
+            }
+        };
+        let mut tc = Timechain::new(crate::genesis::genesis());
+        mine(&mut tc, 1, 3);
+        let ours = tc.blocks.clone();
+        let (difficulty, balances) = (tc.difficulty, tc.state.balances.clone());
+        // A rival miner's branch off block 1.
+        let fork = |len: usize| {
+            let mut rival = Timechain::from_saved_blocks(ours[..2].to_vec()).unwrap();
+            mine(&mut rival, 9, len);
+            rival.blocks.split_off(2)
+        };
+
+        // A fork no longer than the chain, or with a bad block, is refused.
+        assert_eq!(tc.reorganize(2, fork(2), |_| Ok(())), ForkOutcome::Refused(None));
+        assert_eq!(tc.reorganize(2, fork(3), |_| Err("Checkpoint mismatch")), ForkOutcome::Refused(Some("Checkpoint mismatch")));
+        let unsealed = vec![block(&ours[1], [9u8; 32], vec![])];
+        assert_eq!(tc.reorganize(2, unsealed, |_| Ok(())), ForkOutcome::Refused(Some("Invalid VDF proof")));
+        assert_eq!(tc.blocks, ours);
+        assert_eq!((tc.difficulty, &tc.state.balances), (difficulty, &balances));
+
+        // Our tip was saved before a rule it breaks: it cannot reconnect,
+        // so the chain comes back whole from its blocks instead.
+        let mut saved = ours.clone();
+        saved.push(block(&ours[3], [4u8; 32], vec![]));
+        let mut restored = Timechain::from_saved_blocks(saved.clone()).unwrap();
+        assert_eq!(restored.reorganize(2, fork(2), |_| Ok(())), ForkOutcome::Refused(None));
+        assert_eq!(restored.blocks, saved);
+        assert_eq!(restored.difficulty, Timechain::from_saved_blocks(saved).unwrap().difficulty);
+
+        // A longer valid fork replaces our blocks above the fork point.
+        let longer = fork(3);
+        assert_eq!(tc.reorganize(2, longer.clone(), |_| Ok(())), ForkOutcome::Adopted(ours[2..].to_vec()));
+        assert_eq!(tc.blocks[2..], longer[..]);
+    }
+
+    #[test]
+    fn test_block_timestamp_must_pass_median_time_past() {
+        let mut tc = Timechain::new(crate::genesis::genesis());
+        // Each block is no earlier than its parent, and after the median.
+        let stamp = tc.blocks[0].timestamp + TARGET_TIME;
+        for (miner, timestamp) in [(1u8, stamp), (2, stamp + 1), (3, stamp + 1)] {
+            let next = tc.seal(Block { timestamp, ..block(tc.blocks.last().unwrap(), [miner; 32], vec![]) });
+            tc.add_block(next).unwrap();
+        }
+        assert_eq!(tc.min_next_timestamp(), stamp + 2);
+
+        // Another in the same second as its parent is now at the median.
+        let tip = tc.blocks.last().unwrap().clone();
+        let stalled = tc.seal(Block { timestamp: stamp + 1, ..block(&tip, [4u8; 32], vec![]) });
+        assert_eq!(tc.add_block(stalled), Err("Block timestamp not after median time past"));
+        let backdated = tc.seal(Block { timestamp: stamp, ..block(&tip, [4u8; 32], vec![]) });
+        assert_eq!(tc.add_block(backdated), Err("Block timestamp not after median time past"));
+        let next = tc.seal(Block { timestamp: tc.min_next_timestamp(), ..block(&tip, [4u8; 32], vec![]) });
+        assert_eq!(tc.add_block(next), Ok(()));
+    }
+}
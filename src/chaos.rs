@@ -0,0 +1,135 @@
+// src/chaos.rs - Fault injection for devnet resilience testing
+//
+// Compiled only with `--features chaos`. Production builds carry none of
+// these hooks. A node built with the feature reads an initial fault set from
+// `AXIOM_CHAOS` (JSON) and exposes it on the admin route `/v1/admin/chaos`
+// (loopback, admin token), so a test harness can switch faults on and off
+// while a devnet is running:
+//
+//   curl -X POST localhost:8080/v1/admin/chaos \
+//        -H "Authorization: Bearer $(cat admin.token)" \
+//        -d '{"gossip_drop_percent": 30, "storage_write_delay_ms": 500}'
+//
+// The fault set is process-global because the storage hooks sit in free
+// functions that are called from everywhere in the node.
+
+use std::sync::RwLock;
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::block::Block;
+
+/// Environment variable holding the fault set applied at startup.
+pub const CHAOS_ENV: &str = "AXIOM_CHAOS";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FaultConfig {
+    /// Share of incoming gossip messages silently dropped, 0-100.
+    pub gossip_drop_percent: u8,
+    /// Delay before every chain write, in milliseconds.
+    pub storage_write_delay_ms: u64,
+    /// Corrupt the block at this height the next time the chain is read
+    /// from disk. One-shot: cleared once applied.
+    pub corrupt_block_on_read: Option<u64>,
+}
+
+impl FaultConfig {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.gossip_drop_percent > 100 {
+            return Err("gossip_drop_percent must be between 0 and 100");
+        }
+        Ok(())
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+static FAULTS: RwLock<FaultConfig> = RwLock::new(FaultConfig {
+    gossip_drop_percent: 0,
+    storage_write_delay_ms: 0,
+    corrupt_block_on_read: None,
+});
+
+/// The fault set currently in force.
+pub fn faults() -> FaultConfig {
+    FAULTS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+pub fn set_faults(config: FaultConfig) -> Result<(), &'static str> {
+    config.validate()?;
+    *FAULTS.write().unwrap_or_else(|e| e.into_inner()) = config;
+    Ok(())
+}
+
+/// Install the fault set from `AXIOM_CHAOS`, if set.
+pub fn load_from_env() -> Result<Option<FaultConfig>, String> {
+    let Ok(raw) = std::env::var(CHAOS_ENV) else {
+        return Ok(None);
+    };
+    let config: FaultConfig = serde_json::from_str(&raw).map_err(|e| format!("{}: {}", CHAOS_ENV, e))?;
+    set_faults(config.clone()).map_err(|e| format!("{}: {}", CHAOS_ENV, e))?;
+    Ok(Some(config))
+}
+
+/// Whether to drop the gossip message just received.
+pub fn drop_gossip() -> bool {
+    let percent = faults().gossip_drop_percent;
+    percent > 0 && rand::thread_rng().gen_range(0..100) < percent
+}
+
+/// Block the calling thread for the configured write delay.
+pub fn delay_storage_write() {
+    let millis = faults().storage_write_delay_ms;
+    if millis > 0 {
+        std::thread::sleep(Duration::from_millis(millis));
+    }
+}
+
+/// Apply a pending read corruption to `blocks`: the target block's VDF
+/// proof is bit-flipped, which breaks its hash and the next block's parent
+/// link. Returns the corrupted height.
+pub fn corrupt_on_read(blocks: &mut [Block]) -> Option<u64> {
+    let height = {
+        let mut faults = FAULTS.write().unwrap_or_else(|e| e.into_inner());
+        let height = faults.corrupt_block_on_read?;
+        if height as usize >= blocks.len() {
+            return None;
+        }
+        faults.corrupt_block_on_read = None;
+        height
+    };
+    for byte in blocks[height as usize].vdf_proof.iter_mut() {
+        *byte ^= 0xff;
+    }
+    Some(height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // One test: the fault set is process-global.
+    #[test]
+    fn test_fault_injection() {
+        assert!(set_faults(FaultConfig { gossip_drop_percent: 101, ..Default::default() }).is_err());
+
+        set_faults(FaultConfig { gossip_drop_percent: 100, ..Default::default() }).unwrap();
+        assert!(drop_gossip());
+        set_faults(FaultConfig::default()).unwrap();
+        assert!(!drop_gossip());
+
+        let genesis = crate::genesis::genesis();
+        let mut blocks = vec![genesis.clone()];
+        set_faults(FaultConfig { corrupt_block_on_read: Some(0), ..Default::default() }).unwrap();
+        assert_eq!(corrupt_on_read(&mut blocks), Some(0));
+        assert_ne!(blocks[0].legacy_hash(), genesis.legacy_hash());
+        // One-shot.
+        assert_eq!(corrupt_on_read(&mut blocks), None);
+        assert!(!faults().is_active());
+    }
+}
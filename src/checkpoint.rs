@@ -0,0 +1,220 @@
+// src/checkpoint.rs - Trust Pulse checkpoints and reorg finality
+//
+// Every `CHECKPOINT_INTERVAL` blocks the block pulse carries a STARK supply
+// receipt: the Global Trust Pulse. When such a pulse verifies and announces
+// the block we hold at its height, that block becomes a pending checkpoint,
+// and once `CHECKPOINT_CONFIRMATIONS` blocks are built on it, a final one.
+// The node never reorganises below its latest final checkpoint, whatever
+// chain a peer offers, and never connects a block that contradicts one.
+//
+// This is weak subjectivity: a node trusts the checkpoints it already holds
+// over a longer chain. The set is kept in `CHECKPOINTS_PATH` as JSON, so a
+// new node can be seeded with checkpoints from a source its operator
+// trusts, and light clients can start verifying from one (`/v1/checkpoints`).
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::block::Block;
+use crate::stark::prover::STARK_PROOF_INTERVAL;
+use crate::supply_proof::{pulse_hash, verify_pulse_receipt};
+use crate::AxiomPulse;
+
+pub const CHECKPOINTS_PATH: &str = "axiom_checkpoints.json";
+
+/// Heights between checkpoints: the Trust Pulse cadence.
+pub const CHECKPOINT_INTERVAL: u64 = STARK_PROOF_INTERVAL;
+
+/// Blocks built on a checkpointed block before reorgs below it are refused.
+pub const CHECKPOINT_CONFIRMATIONS: u64 = 6;
+
+/// A block the node will not reorganise away.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Chain length once the block is connected, as in its pulse.
+    pub height: u64,
+    /// `Block::legacy_hash()` of the block, hex.
+    pub block_hash: String,
+    /// Link hash of the block's Trust Pulse (`supply_proof::pulse_hash`),
+    /// hex: where a light client's pulse chain can start.
+    pub pulse_hash: String,
+    pub total_mined: u64,
+}
+
+impl Checkpoint {
+    fn matches(&self, blocks: &[Block]) -> bool {
+        self.height
+            .checked_sub(1)
+            .and_then(|i| blocks.get(i as usize))
+            .is_some_and(|b| hex::encode(b.legacy_hash()) == self.block_hash)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Checkpoints {
+    /// Final checkpoints, lowest first.
+    checkpoints: Vec<Checkpoint>,
+    /// Verified but not yet buried deep enough.
+    #[serde(skip)]
+    pending: Vec<Checkpoint>,
+}
+
+impl Checkpoints {
+    /// The persisted set, or none if `path` is missing or unreadable.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let encoded = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(path, encoded)
+    }
+
+    pub fn all(&self) -> &[Checkpoint] {
+        &self.checkpoints
+    }
+
+    pub fn latest(&self) -> Option<&Checkpoint> {
+        self.checkpoints.last()
+    }
+
+    /// Chain length below which blocks are final (0 without checkpoints).
+    pub fn finalized_height(&self) -> u64 {
+        self.latest().map_or(0, |cp| cp.height)
+    }
+
+    /// Take `pulse` as a pending checkpoint if it is a Trust Pulse whose
+    /// receipt verifies and whose block is the one `blocks` holds. Returns
+    /// whether it was new.
+    pub fn observe_pulse(&mut self, pulse: &AxiomPulse, blocks: &[Block]) -> bool {
+        if pulse.height == 0 || !pulse.height.is_multiple_of(CHECKPOINT_INTERVAL) || pulse.height <= self.finalized_height() {
+            return false;
+        }
+        if self.pending.iter().any(|cp| cp.height == pulse.height) || verify_pulse_receipt(pulse) != Some(true) {
+            return false;
+        }
+        let Some(block) = blocks.get(pulse.height as usize - 1) else {
+            return false;
+        };
+        if block.hash_512() != pulse.block_hash {
+            return false;
+        }
+        self.pending.push(Checkpoint {
+            height: pulse.height,
+            block_hash: hex::encode(block.legacy_hash()),
+            pulse_hash: hex::encode(pulse_hash(pulse)),
+            total_mined: pulse.total_mined,
+        });
+        true
+    }
+
+    /// Finalise pending checkpoints buried deep enough in `blocks`, and drop
+    /// those whose block was reorganised away. Returns the new final ones.
+    pub fn confirm(&mut self, blocks: &[Block]) -> Vec<Checkpoint> {
+        let length = blocks.len() as u64;
+        self.pending.retain(|cp| cp.matches(blocks));
+        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|cp| length >= cp.height + CHECKPOINT_CONFIRMATIONS);
+        self.pending = waiting;
+        let mut confirmed = Vec::new();
+        for cp in ready {
+            if cp.height > self.finalized_height() {
+                self.checkpoints.push(cp.clone());
+                confirmed.push(cp);
+            }
+        }
+        confirmed
+    }
+
+    /// Whether rolling the chain back to `length` blocks keeps every final
+    /// checkpoint.
+    pub fn permits_rollback_to(&self, length: u64) -> bool {
+        length >= self.finalized_height()
+    }
+
+    /// Refuse a block that takes a checkpointed height with another block.
+    pub fn check_block(&self, block: &Block) -> Result<(), &'static str> {
+        let conflicts = self
+            .checkpoints
+            .iter()
+            .any(|cp| cp.height == block.slot + 1 && cp.block_hash != hex::encode(block.legacy_hash()));
+        if conflicts {
+            return Err("Block conflicts with a checkpoint");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Hash512;
+    use crate::stark::{StarkProver, TransactionData};
+
+    fn chain(len: u64) -> Vec<Block> {
+        let mut blocks = vec![crate::genesis::genesis()];
+        for slot in 1..len {
+            let parent = blocks.last().unwrap();
+            blocks.push(Block::new(parent.legacy_hash(), slot, [1u8; 32], vec![], [0u8; 32], vec![], slot));
+        }
+        blocks
+    }
+
+    fn trust_pulse(blocks: &[Block], height: u64) -> AxiomPulse {
+        let total_mined = crate::economics::calculate_total_supply(height);
+        let remaining = crate::economics::TOTAL_SUPPLY - total_mined;
+        let statement = TransactionData { initial_balance: remaining, amount: total_mined, fee: 0, nonce: height };
+        AxiomPulse {
+            height,
+            total_mined,
+            remaining,
+            block_hash: blocks[height as usize - 1].hash_512(),
+            oracle_seal: Hash512::ZERO,
+            prev_pulse_hash: Hash512::ZERO,
+            timestamp: 0,
+            stark_receipt: Some(StarkProver::generate_proof(&statement).unwrap().seal),
+            signature: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_trust_pulse_becomes_final_checkpoint() {
+        let blocks = chain(CHECKPOINT_INTERVAL + CHECKPOINT_CONFIRMATIONS);
+        let tip = &blocks[..CHECKPOINT_INTERVAL as usize + 1];
+        let mut checkpoints = Checkpoints::default();
+        let pulse = trust_pulse(tip, CHECKPOINT_INTERVAL);
+        assert!(checkpoints.observe_pulse(&pulse, tip));
+        assert!(!checkpoints.observe_pulse(&pulse, tip));
+
+        // Not final until buried.
+        assert!(checkpoints.confirm(tip).is_empty());
+        assert!(checkpoints.permits_rollback_to(1));
+        let confirmed = checkpoints.confirm(&blocks);
+        assert_eq!(confirmed.len(), 1);
+        assert_eq!(checkpoints.finalized_height(), CHECKPOINT_INTERVAL);
+        assert!(checkpoints.permits_rollback_to(CHECKPOINT_INTERVAL));
+        assert!(!checkpoints.permits_rollback_to(CHECKPOINT_INTERVAL - 1));
+
+        let ours = &blocks[CHECKPOINT_INTERVAL as usize - 1];
+        assert_eq!(checkpoints.check_block(ours), Ok(()));
+        let mut rival = ours.clone();
+        rival.nonce += 1;
+        assert_eq!(checkpoints.check_block(&rival), Err("Block conflicts with a checkpoint"));
+
+        // A pulse for another block at that height is not a checkpoint.
+        let mut forged = pulse.clone();
+        forged.block_hash = rival.hash_512();
+        assert!(!Checkpoints::default().observe_pulse(&forged, &blocks));
+
+        let path = std::env::temp_dir().join(format!("axiom_checkpoints_{}.json", std::process::id()));
+        checkpoints.save(&path).unwrap();
+        assert_eq!(Checkpoints::load(&path).all(), checkpoints.all());
+        let _ = fs::remove_file(path);
+    }
+}
@@ -1,13 +1,44 @@
 // src/config.rs - AXIOM Protocol Production Configuration
 // Complete configuration management for mainnet deployment
+//
+// The node reads `axiom.toml` (or `config/axiom.toml`, or
+// `/etc/axiom/axiom.toml`); every section and key is optional and falls
+// back to its default. The AXIOM_* environment variables listed in
+// `ENV_OVERRIDES` override the file, so a one-off run or a container can
+// change a setting without editing it. `load_for_node` applies both and
+// validates the result before the node touches the network or disk.
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use crate::error::{AxiomError, Result};
+use crate::guardian::alerts::{AlertSink, DEFAULT_DEDUP_WINDOW_SECS};
+
+/// Environment variables `AxiomConfig::apply_env` reads, and the key each
+/// one overrides. List values are comma-separated; flags are 0 or 1.
+pub const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("AXIOM_GENESIS_NODE", "node.genesis_node"),
+    ("AXIOM_BOOTSTRAP_PEERS", "network.bootstrap_peers"),
+    ("AXIOM_KNOWN_PEERS", "network.known_peers"),
+    ("AXIOM_RELAY_PEERS", "network.relay_peers"),
+    ("AXIOM_QUIC", "network.enable_quic"),
+    ("AXIOM_EXTERNAL_IP", "network.external_ip"),
+    ("AXIOM_MINING", "mining.enabled"),
+    ("AXIOM_ROTATE_PAYOUT", "mining.rotate_payout"),
+    ("AXIOM_MINING_THREADS", "mining.threads"),
+    ("AXIOM_STORAGE_BACKEND", "storage.backend"),
+    ("AXIOM_DATA_DIR", "storage.data_dir"),
+    ("AXIOM_ARCHIVE_DIR", "storage.archive_dir"),
+    ("AXIOM_MAX_TX_RISK", "ai.max_tx_risk"),
+    ("API_BIND_ADDRESS", "rpc.listen_address"),
+    ("AXIOM_API_BIND", "rpc.listen_address"),
+    ("AXIOM_API_PORT", "rpc.api_port"),
+    ("AXIOM_READY_MIN_PEERS", "rpc.ready_min_peers"),
+];
 
 /// Main configuration structure
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[derive(Default)]
+#[serde(default)]
 pub struct AxiomConfig {
     /// Node identification
     pub node: NodeConfig,
@@ -28,6 +59,7 @@ pub struct AxiomConfig {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct NodeConfig {
     /// Node name/identifier
     pub name: String,
@@ -35,6 +67,9 @@ pub struct NodeConfig {
     pub node_type: NodeType,
     /// Enable metrics collection
     pub metrics_enabled: bool,
+    /// Genesis node: binds only the first P2P ports so the seed addresses
+    /// published for the network stay valid
+    pub genesis_node: bool,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
@@ -47,6 +82,7 @@ pub enum NodeType {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct NetworkConfig {
     /// P2P listen address
     pub listen_address: String,
@@ -68,9 +104,26 @@ pub struct NetworkConfig {
     pub gossip_heartbeat: u64,
     /// Network ID (mainnet=1)
     pub network_id: u8,
+    /// First P2P port to try; the node takes the first free one up to
+    /// `p2p_port_end`. Discv5 listens on UDP at the bound port + 3000.
+    pub p2p_port_start: u16,
+    pub p2p_port_end: u16,
+    /// Also listen on QUIC (UDP, same port as TCP)
+    pub enable_quic: bool,
+    /// Public IP advertised to peers and in the discovery record
+    pub external_ip: Option<std::net::IpAddr>,
+    /// Circuit relays (/.../p2p/<peer-id>) to reserve slots on behind NAT
+    pub relay_peers: Vec<String>,
+    /// Peers to keep in the address book
+    pub known_peers: Vec<String>,
+    /// Directory holding MaxMind GeoLite2-ASN.mmdb and/or
+    /// GeoLite2-Country.mmdb, to tag peers with their AS and country
+    /// (needs a build with the `geoip` feature)
+    pub geoip_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ConsensusConfig {
     /// VDF time steps
     pub vdf_steps: u64,
@@ -91,10 +144,11 @@ pub struct ConsensusConfig {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct MiningConfig {
     /// Enable mining
     pub enabled: bool,
-    /// Number of mining threads
+    /// Nonce-search threads of the background miner
     pub threads: usize,
     /// Miner address (receives block rewards)
     pub miner_address: Option<String>,
@@ -102,10 +156,17 @@ pub struct MiningConfig {
     pub intensity: u8,
     /// Pause mining if <N peers
     pub min_peers_to_mine: usize,
+    /// Pay each coinbase to a fresh address on the wallet's HD payout
+    /// branch instead of the wallet address
+    pub rotate_payout: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct StorageConfig {
+    /// Chain database backend
+    #[serde(default)]
+    pub backend: StorageBackend,
     /// Data directory path
     pub data_dir: PathBuf,
     /// Database cache size (MB)
@@ -116,6 +177,32 @@ pub struct StorageConfig {
     pub pruning: PruningMode,
     /// Maximum database size (GB)
     pub max_db_size_gb: u64,
+    /// Publish completed block ranges from this directory (archive server)
+    pub archive_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// Single `axiom_chain.dat` file (original layout)
+    #[default]
+    File,
+    Sled,
+    /// Requires a build with `--features rocksdb`
+    RocksDb,
+}
+
+impl std::str::FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "file" => Ok(Self::File),
+            "sled" => Ok(Self::Sled),
+            "rocksdb" => Ok(Self::RocksDb),
+            other => Err(format!("unknown storage backend '{}' (expected file, sled or rocksdb)", other)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
@@ -127,6 +214,7 @@ pub enum PruningMode {
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct AIConfig {
     /// Enable Neural Guardian
     pub neural_guardian_enabled: bool,
@@ -140,14 +228,33 @@ pub struct AIConfig {
     pub min_oracle_stake: u64,
     /// Oracle consensus threshold
     pub oracle_consensus_threshold: usize,
+    /// Leave transactions with a guardian risk score above this (0-100)
+    /// out of mined blocks; unset includes every valid transaction
+    pub max_tx_risk: Option<f64>,
+    /// URLs of signed threat intelligence feeds to import
+    pub threat_feeds: Vec<String>,
+    /// Hex wallet keys whose threat feeds are accepted
+    pub threat_feed_publishers: Vec<String>,
+    /// Seconds between threat feed fetches
+    pub threat_feed_interval: u64,
+    /// Where guardian escalations and sentinel failures are sent
+    pub alert_sinks: Vec<AlertSink>,
+    /// Seconds a repeat of an alert is held back
+    pub alert_dedup_window: u64,
+    /// Hex Ed25519 keys whose halt notices this node follows and any one
+    /// of which can resume it
+    pub safe_mode_operators: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct RpcConfig {
     /// Enable RPC server
     pub enabled: bool,
-    /// RPC listen address
+    /// RPC listen address. The node's HTTP API binds to its host part.
     pub listen_address: String,
+    /// Port of the node's HTTP API (/v1/status, receipts, accounts, ...)
+    pub api_port: u16,
     /// CORS allowed origins
     pub cors_allowed_origins: Vec<String>,
     /// Maximum concurrent connections
@@ -158,9 +265,12 @@ pub struct RpcConfig {
     pub websocket_enabled: bool,
     /// Rate limiting (requests per minute)
     pub rate_limit: u64,
+    /// Peers the node needs before `/readyz` reports it ready
+    pub ready_min_peers: usize,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
 pub struct LoggingConfig {
     /// Log level
     pub level: String,
@@ -187,6 +297,7 @@ impl Default for NodeConfig {
             name: format!("axiom-node-{}", rand::random::<u16>()),
             node_type: NodeType::Full,
             metrics_enabled: true,
+            genesis_node: false,
         }
     }
 }
@@ -204,6 +315,13 @@ impl Default for NetworkConfig {
             connection_timeout: 30,
             gossip_heartbeat: 1,
             network_id: 1,
+            p2p_port_start: 6000,
+            p2p_port_end: 6999,
+            enable_quic: true,
+            external_ip: None,
+            relay_peers: vec![],
+            known_peers: vec![],
+            geoip_dir: None,
         }
     }
 }
@@ -226,11 +344,12 @@ impl Default for ConsensusConfig {
 impl Default for MiningConfig {
     fn default() -> Self {
         Self {
-            enabled: false,
+            enabled: true,
             threads: num_cpus::get(),
             miner_address: None,
             intensity: 80,
             min_peers_to_mine: 3,
+            rotate_payout: false,
         }
     }
 }
@@ -238,11 +357,13 @@ impl Default for MiningConfig {
 impl Default for StorageConfig {
     fn default() -> Self {
         Self {
+            backend: StorageBackend::File,
             data_dir: PathBuf::from("./axiom-data"),
             cache_size_mb: 1024,
             compression: true,
             pruning: PruningMode::Full,
             max_db_size_gb: 0,
+            archive_dir: None,
         }
     }
 }
@@ -256,6 +377,13 @@ impl Default for AIConfig {
             oracle_enabled: false,
             min_oracle_stake: 50_000_000_000,
             oracle_consensus_threshold: 3,
+            max_tx_risk: None,
+            threat_feeds: Vec::new(),
+            threat_feed_publishers: Vec::new(),
+            threat_feed_interval: 3600,
+            alert_sinks: Vec::new(),
+            alert_dedup_window: DEFAULT_DEDUP_WINDOW_SECS,
+            safe_mode_operators: Vec::new(),
         }
     }
 }
@@ -265,11 +393,13 @@ impl Default for RpcConfig {
         Self {
             enabled: true,
             listen_address: "0.0.0.0:8546".to_string(),
+            api_port: 8080,
             cors_allowed_origins: vec!["*".to_string()],
             max_connections: 100,
             request_timeout: 30,
             websocket_enabled: true,
             rate_limit: 60,
+            ready_min_peers: 1,
         }
     }
 }
@@ -317,27 +447,188 @@ impl AxiomConfig {
         Ok(())
     }
     
-    /// Validate configuration
+    /// Load the node's configuration: the file (or defaults), then the
+    /// environment overrides, then validation.
+    pub fn load_for_node() -> Result<Self> {
+        let mut config = Self::load()?;
+        config.apply_env(|name| std::env::var(name).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Apply the `ENV_OVERRIDES` variables, read through `var`.
+    pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<()> {
+        fn parse<T: std::str::FromStr>(name: &str, value: &str) -> Result<T> {
+            value.trim().parse().map_err(|_| AxiomError::InvalidConfig(format!("{}: cannot parse '{}'", name, value)))
+        }
+        fn flag(name: &str, value: &str) -> Result<bool> {
+            match value.trim() {
+                "1" | "true" => Ok(true),
+                "0" | "false" => Ok(false),
+                _ => Err(AxiomError::InvalidConfig(format!("{}: expected 0 or 1, got '{}'", name, value))),
+            }
+        }
+        fn list(value: &str) -> Vec<String> {
+            value.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect()
+        }
+
+        if let Some(v) = var("AXIOM_GENESIS_NODE") {
+            self.node.genesis_node = flag("AXIOM_GENESIS_NODE", &v)?;
+        }
+        if let Some(v) = var("AXIOM_BOOTSTRAP_PEERS") {
+            self.network.bootstrap_peers = list(&v);
+        }
+        if let Some(v) = var("AXIOM_KNOWN_PEERS") {
+            self.network.known_peers = list(&v);
+        }
+        if let Some(v) = var("AXIOM_RELAY_PEERS") {
+            self.network.relay_peers = list(&v);
+        }
+        if let Some(v) = var("AXIOM_QUIC") {
+            self.network.enable_quic = flag("AXIOM_QUIC", &v)?;
+        }
+        if let Some(v) = var("AXIOM_EXTERNAL_IP").filter(|v| !v.trim().is_empty()) {
+            self.network.external_ip = Some(parse("AXIOM_EXTERNAL_IP", &v)?);
+        }
+        if let Some(v) = var("AXIOM_MINING") {
+            self.mining.enabled = flag("AXIOM_MINING", &v)?;
+        }
+        if let Some(v) = var("AXIOM_ROTATE_PAYOUT") {
+            self.mining.rotate_payout = flag("AXIOM_ROTATE_PAYOUT", &v)?;
+        }
+        if let Some(v) = var("AXIOM_MINING_THREADS") {
+            self.mining.threads = parse("AXIOM_MINING_THREADS", &v)?;
+        }
+        if let Some(v) = var("AXIOM_STORAGE_BACKEND") {
+            self.storage.backend = v.parse().map_err(AxiomError::InvalidConfig)?;
+        }
+        if let Some(v) = var("AXIOM_DATA_DIR") {
+            self.storage.data_dir = PathBuf::from(v);
+        }
+        if let Some(v) = var("AXIOM_ARCHIVE_DIR") {
+            self.storage.archive_dir = Some(PathBuf::from(v));
+        }
+        if let Some(v) = var("AXIOM_MAX_TX_RISK") {
+            self.ai.max_tx_risk = Some(parse("AXIOM_MAX_TX_RISK", &v)?);
+        }
+        // API_BIND_ADDRESS predates the AXIOM_ prefix and wins over it.
+        if let Some(v) = var("API_BIND_ADDRESS").or_else(|| var("AXIOM_API_BIND")) {
+            self.rpc.listen_address = v;
+        }
+        if let Some(v) = var("AXIOM_API_PORT") {
+            self.rpc.api_port = parse("AXIOM_API_PORT", &v)?;
+        }
+        if let Some(v) = var("AXIOM_READY_MIN_PEERS") {
+            self.rpc.ready_min_peers = parse("AXIOM_READY_MIN_PEERS", &v)?;
+        }
+        Ok(())
+    }
+
+    /// Host the HTTP API binds to: the host part of `rpc.listen_address`,
+    /// which may be given as "ip", "ip:port" or "[ipv6]:port".
+    pub fn api_bind_ip(&self) -> Result<std::net::IpAddr> {
+        let addr = self.rpc.listen_address.trim();
+        addr.parse::<std::net::SocketAddr>()
+            .map(|sa| sa.ip())
+            .or_else(|_| addr.parse())
+            .map_err(|_| AxiomError::InvalidConfig(format!("rpc.listen_address: '{}' is not an IP address", addr)))
+    }
+
+    /// Validate configuration. Every problem is reported, not just the first.
     pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+
         if self.network.max_peers == 0 {
-            return Err(AxiomError::InvalidConfig("max_peers must be > 0".to_string()));
+            problems.push("max_peers must be > 0".to_string());
         }
-        
+        if self.network.p2p_port_start == 0 || self.network.p2p_port_start > self.network.p2p_port_end {
+            problems.push(format!(
+                "network.p2p_port_start..p2p_port_end ({}..{}) is not a valid port range",
+                self.network.p2p_port_start, self.network.p2p_port_end
+            ));
+        }
+        for (key, addrs) in [
+            ("bootstrap_peers", &self.network.bootstrap_peers),
+            ("relay_peers", &self.network.relay_peers),
+            ("known_peers", &self.network.known_peers),
+        ] {
+            for addr in addrs {
+                if addr.parse::<libp2p::Multiaddr>().is_err() {
+                    problems.push(format!(
+                        "network.{}: '{}' is not a multiaddr (expected e.g. /ip4/203.0.113.7/tcp/6000)",
+                        key, addr
+                    ));
+                }
+            }
+        }
+
         if self.consensus.vdf_steps == 0 {
-            return Err(AxiomError::InvalidConfig("vdf_steps must be > 0".to_string()));
+            problems.push("vdf_steps must be > 0".to_string());
         }
-        
-        if self.mining.enabled && self.mining.miner_address.is_none() {
-            return Err(AxiomError::InvalidConfig(
-                "miner_address required when mining enabled".to_string()
-            ));
+
+        if self.mining.threads == 0 {
+            problems.push("mining.threads must be > 0".to_string());
         }
-        
-        if !self.storage.data_dir.exists() {
-            std::fs::create_dir_all(&self.storage.data_dir)?;
+
+        if let Some(address) = &self.mining.miner_address {
+            let hex_part = address.strip_prefix("axm1").unwrap_or(address);
+            if hex::decode(hex_part).map_or(true, |b| b.len() != 32) {
+                problems.push(format!("mining.miner_address: '{}' is not a 32-byte address", address));
+            }
+        }
+
+        if self.storage.data_dir.is_file() {
+            problems.push(format!("storage.data_dir: {} is a file", self.storage.data_dir.display()));
+        }
+
+        if !(0.0..=1.0).contains(&self.ai.threat_threshold) {
+            problems.push("ai.threat_threshold must be between 0 and 1".to_string());
+        }
+        if self.ai.max_tx_risk.is_some_and(|risk| !(0.0..=100.0).contains(&risk)) {
+            problems.push("ai.max_tx_risk must be between 0 and 100".to_string());
+        }
+        for url in &self.ai.threat_feeds {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                problems.push(format!("ai.threat_feeds: '{}' is not an http(s) URL", url));
+            }
+        }
+        for key in &self.ai.threat_feed_publishers {
+            if hex::decode(key).map_or(true, |b| b.len() != 32) {
+                problems.push(format!("ai.threat_feed_publishers: '{}' is not a 32-byte hex key", key));
+            }
+        }
+        if !self.ai.threat_feeds.is_empty() && self.ai.threat_feed_publishers.is_empty() {
+            problems.push("ai.threat_feeds needs at least one key in ai.threat_feed_publishers".to_string());
+        }
+        if self.ai.threat_feed_interval == 0 {
+            problems.push("ai.threat_feed_interval must be > 0".to_string());
+        }
+        for (i, sink) in self.ai.alert_sinks.iter().enumerate() {
+            if let Err(e) = sink.validate() {
+                problems.push(format!("ai.alert_sinks[{}]: {}", i, e));
+            }
+        }
+        for key in &self.ai.safe_mode_operators {
+            if crate::guardian::model_release::parse_key(key).is_err() {
+                problems.push(format!("ai.safe_mode_operators: '{}' is not an Ed25519 public key", key));
+            }
+        }
+
+        if let Err(e) = self.api_bind_ip() {
+            problems.push(e.to_string());
+        }
+        if self.rpc.api_port == 0 {
+            problems.push("rpc.api_port must be > 0".to_string());
+        }
+        if self.rpc.rate_limit == 0 || self.rpc.rate_limit > u32::MAX as u64 {
+            problems.push("rpc.rate_limit must be between 1 and 4294967295 requests per minute".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(AxiomError::InvalidConfig(problems.join("; ")))
         }
-        
-        Ok(())
     }
     
     /// Create validator configuration (mainnet with archive mode)
@@ -387,6 +678,44 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_partial_file_and_env_overrides() {
+        let mut config: AxiomConfig = toml::from_str("[network]\nenable_quic = false\n[rpc]\napi_port = 9090\n").unwrap();
+        assert!(!config.network.enable_quic);
+        assert_eq!(config.rpc.api_port, 9090);
+        assert!(config.mining.enabled, "unset keys keep their defaults");
+
+        let env: std::collections::HashMap<&str, &str> = [
+            ("AXIOM_QUIC", "1"),
+            ("AXIOM_BOOTSTRAP_PEERS", "/ip4/10.0.0.1/tcp/6000, /ip4/10.0.0.2/tcp/6000"),
+            ("AXIOM_API_BIND", "127.0.0.1"),
+        ].into_iter().collect();
+        config.apply_env(|name| env.get(name).map(|v| v.to_string())).unwrap();
+        assert!(config.network.enable_quic);
+        assert_eq!(config.network.bootstrap_peers.len(), 2);
+        assert_eq!(config.api_bind_ip().unwrap(), std::net::IpAddr::from([127, 0, 0, 1]));
+        assert!(config.validate().is_ok());
+
+        assert!(config.apply_env(|name| (name == "AXIOM_API_PORT").then(|| "http".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_validation_reports_every_problem() {
+        let mut config = AxiomConfig::default();
+        config.network.bootstrap_peers = vec!["not-an-address".to_string()];
+        config.ai.max_tx_risk = Some(250.0);
+        config.ai.threat_feeds = vec!["ftp://feeds.example".to_string()];
+        config.ai.alert_sinks = vec![AlertSink::PagerDuty { routing_key: String::new(), min_severity: Default::default() }];
+        config.ai.safe_mode_operators = vec!["00".to_string()];
+        let Err(AxiomError::InvalidConfig(message)) = config.validate() else {
+            panic!("invalid config accepted");
+        };
+        assert!(message.contains("bootstrap_peers") && message.contains("max_tx_risk"), "{}", message);
+        assert!(message.contains("not an http(s) URL") && message.contains("threat_feed_publishers"), "{}", message);
+        assert!(message.contains("ai.alert_sinks[0]: pagerduty sink needs a routing_key"), "{}", message);
+        assert!(message.contains("ai.safe_mode_operators: '00'"), "{}", message);
+    }
+
     #[test]
     fn test_rpc_default_listen_address_is_public() {
         let config = RpcConfig::default();
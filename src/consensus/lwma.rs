@@ -19,6 +19,16 @@ pub const MAX_ADJUSTMENT_FACTOR: f64 = 3.0;
 /// Minimum difficulty adjustment per block (33% = 1/3)
 pub const MIN_ADJUSTMENT_FACTOR: f64 = 0.33;
 
+/// Blocks whose median timestamp a new block must exceed (median-time-past)
+pub const MEDIAN_TIME_SPAN: usize = 11;
+
+/// How far ahead of the local clock a block timestamp may be (2 minutes)
+pub const MAX_FUTURE_DRIFT: u64 = 120;
+
+/// Longest solvetime the average counts, in target block times. Caps how far
+/// one forward-dated block can pull difficulty down.
+pub const MAX_SOLVETIME_FACTOR: u64 = 6;
+
 /// Simple block header for difficulty calculation
 #[derive(Debug, Clone)]
 pub struct BlockHeader {
@@ -27,36 +37,100 @@ pub struct BlockHeader {
     pub difficulty: BigUint,
 }
 
+/// Why a block timestamp is not acceptable after a run of headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimestampError {
+    /// Not after the median of the last `MEDIAN_TIME_SPAN` timestamps
+    NotAfterMedianTimePast { timestamp: u64, median: u64 },
+    /// More than `MAX_FUTURE_DRIFT` seconds ahead of the local clock
+    TooFarInFuture { timestamp: u64, limit: u64 },
+}
+
+impl std::fmt::Display for TimestampError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimestampError::NotAfterMedianTimePast { timestamp, median } => {
+                write!(f, "Block timestamp {} not after median time past {}", timestamp, median)
+            }
+            TimestampError::TooFarInFuture { timestamp, limit } => {
+                write!(f, "Block timestamp {} beyond future limit {}", timestamp, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimestampError {}
+
+/// Median timestamp of the last `MEDIAN_TIME_SPAN` headers, `None` if empty
+pub fn median_time_past(block_headers: &[BlockHeader]) -> Option<u64> {
+    let start = block_headers.len().saturating_sub(MEDIAN_TIME_SPAN);
+    let mut times: Vec<u64> = block_headers[start..].iter().map(|h| h.timestamp).collect();
+    if times.is_empty() {
+        return None;
+    }
+    times.sort_unstable();
+    Some(times[times.len() / 2])
+}
+
+/// Check the timestamp of a block extending `block_headers` against
+/// median-time-past and the local clock `now`.
+///
+/// A lone miner cannot move the median, and cannot date a block far enough
+/// ahead to matter, so timestamp games stay within what the LWMA absorbs.
+pub fn check_timestamp(block_headers: &[BlockHeader], timestamp: u64, now: u64) -> Result<(), TimestampError> {
+    if let Some(median) = median_time_past(block_headers) {
+        if timestamp <= median {
+            return Err(TimestampError::NotAfterMedianTimePast { timestamp, median });
+        }
+    }
+    let limit = now.saturating_add(MAX_FUTURE_DRIFT);
+    if timestamp > limit {
+        return Err(TimestampError::TooFarInFuture { timestamp, limit });
+    }
+    Ok(())
+}
+
 /// Calculate next difficulty using LWMA
+///
+/// Until the chain has `LWMA_WINDOW` solvetimes (warm-up), the average runs
+/// over the ones it has. Out-of-order timestamps count as one second after
+/// the latest seen so far, and each solvetime is capped at
+/// `MAX_SOLVETIME_FACTOR` target times, so neither a backdated nor a
+/// forward-dated block moves difficulty more than an honest one could.
 pub fn calculate_lwma_difficulty(block_headers: &[BlockHeader]) -> BigUint {
-    if block_headers.len() < LWMA_WINDOW + 1 {
-        return BigUint::from(MIN_DIFFICULTY);
+    let min_difficulty = BigUint::from(MIN_DIFFICULTY);
+    let n = block_headers.len().saturating_sub(1).min(LWMA_WINDOW);
+    if n == 0 {
+        return block_headers
+            .last()
+            .map_or(min_difficulty.clone(), |h| h.difficulty.clone())
+            .max(min_difficulty);
     }
-    
-    let start_idx = block_headers.len().saturating_sub(LWMA_WINDOW + 1);
-    let window = &block_headers[start_idx..];
-    
+
+    let window = &block_headers[block_headers.len() - (n + 1)..];
+    let max_solvetime = TARGET_BLOCK_TIME * MAX_SOLVETIME_FACTOR;
+
     let mut weighted_times: u64 = 0;
     let mut sum_difficulties = BigUint::zero();
-    
-    for i in 1..=LWMA_WINDOW {
-        let time_delta = window[i]
-            .timestamp
-            .saturating_sub(window[i - 1].timestamp)
-            .max(1);
-        
+    let mut previous = window[0].timestamp;
+
+    for (i, header) in window.iter().enumerate().skip(1) {
+        let timestamp = header.timestamp.max(previous.saturating_add(1));
+        let time_delta = (timestamp - previous).min(max_solvetime);
+        previous = timestamp;
+
         let weight = i as u64;
         weighted_times = weighted_times.saturating_add(time_delta.saturating_mul(weight));
-        sum_difficulties += &window[i].difficulty;
+        sum_difficulties += &header.difficulty;
     }
     
-    let n = LWMA_WINDOW as u64;
+    let n = n as u64;
     let expected_times = TARGET_BLOCK_TIME
         .saturating_mul(n)
         .saturating_mul(n + 1)
         / 2;
     
-    let avg_difficulty = sum_difficulties / LWMA_WINDOW;
+    let avg_difficulty = sum_difficulties / n;
     
     let new_difficulty = if weighted_times == 0 || expected_times == 0 {
         avg_difficulty
@@ -70,7 +144,7 @@ pub fn calculate_lwma_difficulty(block_headers: &[BlockHeader]) -> BigUint {
         BigUint::from(adjusted as u64)
     };
     
-    new_difficulty.max(BigUint::from(MIN_DIFFICULTY))
+    new_difficulty.max(min_difficulty)
 }
 
 /// Convert difficulty to target
@@ -194,6 +268,104 @@ mod tests {
         assert!(new_diff >= BigUint::from(MIN_DIFFICULTY));
     }
     
+    #[test]
+    fn test_warm_up_and_median_time_past() {
+        assert_eq!(calculate_lwma_difficulty(&[]), BigUint::from(MIN_DIFFICULTY));
+        let headers = create_test_headers(1, TARGET_BLOCK_TIME, 100_000);
+        assert_eq!(calculate_lwma_difficulty(&headers), BigUint::from(100_000u64));
+        // A few blocks at target time keep their difficulty instead of
+        // dropping to the minimum.
+        let headers = create_test_headers(10, TARGET_BLOCK_TIME, 100_000);
+        assert_eq!(calculate_lwma_difficulty(&headers), BigUint::from(100_000u64));
+
+        let mut headers = create_test_headers(20, TARGET_BLOCK_TIME, 100_000);
+        let now = headers.last().unwrap().timestamp + TARGET_BLOCK_TIME;
+        let median = headers[14].timestamp;
+        assert_eq!(median_time_past(&headers), Some(median));
+        assert_eq!(
+            check_timestamp(&headers, median, now),
+            Err(TimestampError::NotAfterMedianTimePast { timestamp: median, median })
+        );
+        assert!(check_timestamp(&headers, median + 1, now).is_ok());
+        assert!(check_timestamp(&headers, now + MAX_FUTURE_DRIFT, now).is_ok());
+        assert!(matches!(
+            check_timestamp(&headers, now + MAX_FUTURE_DRIFT + 1, now),
+            Err(TimestampError::TooFarInFuture { .. })
+        ));
+
+        // One out-of-order timestamp counts as a 1 s solvetime.
+        headers[19].timestamp = headers[17].timestamp;
+        assert!(calculate_lwma_difficulty(&headers) > BigUint::from(100_000u64));
+    }
+
+    /// Mine `blocks` more blocks onto `headers` at `hashrate` (difficulty
+    /// units per second), timestamping each with `stamp(true_time, headers)`.
+    fn mine(
+        headers: &mut Vec<BlockHeader>,
+        blocks: usize,
+        hashrate: f64,
+        mut stamp: impl FnMut(u64, &[BlockHeader]) -> u64,
+        clock: &mut u64,
+    ) {
+        for _ in 0..blocks {
+            let difficulty = calculate_lwma_difficulty(headers);
+            *clock += ((difficulty.to_f64().unwrap() / hashrate) as u64).max(1);
+            let timestamp = stamp(*clock, headers);
+            check_timestamp(headers, timestamp, *clock).expect("simulated timestamp must be valid");
+            headers.push(BlockHeader { height: headers.len() as u64, timestamp, difficulty });
+        }
+    }
+
+    #[test]
+    fn prop_difficulty_follows_hashrate_swings() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(0x1d3a);
+        let base = 100_000.0;
+        for _ in 0..50 {
+            let mut headers = create_test_headers(2 * LWMA_WINDOW, TARGET_BLOCK_TIME, base as u64);
+            let mut clock = headers.last().unwrap().timestamp;
+            for _ in 0..3 {
+                let factor: f64 = rng.gen_range(0.25..4.0);
+                let hashrate = factor * base / TARGET_BLOCK_TIME as f64;
+                mine(&mut headers, 4 * LWMA_WINDOW, hashrate, |t, _| t, &mut clock);
+                let settled = calculate_lwma_difficulty(&headers).to_f64().unwrap();
+                let ratio = settled / (factor * base);
+                assert!((0.85..1.15).contains(&ratio), "factor {:.2}: settled at {:.2}x", factor, ratio);
+            }
+        }
+    }
+
+    #[test]
+    fn prop_timestamp_manipulation_is_bounded() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(0x7157);
+        let base = 100_000.0;
+        let hashrate = base / TARGET_BLOCK_TIME as f64;
+        for _ in 0..50 {
+            let share: f64 = rng.gen_range(0.1..0.5);
+            let forward: bool = rng.gen();
+            let mut headers = create_test_headers(2 * LWMA_WINDOW, TARGET_BLOCK_TIME, base as u64);
+            let mut clock = headers.last().unwrap().timestamp;
+            let mut lowest = f64::MAX;
+            let mut highest = 0.0f64;
+            for _ in 0..4 * LWMA_WINDOW {
+                let attacker = rng.gen_bool(share);
+                mine(&mut headers, 1, hashrate, |t, prior| {
+                    let earliest = median_time_past(prior).unwrap() + 1;
+                    match (attacker, forward) {
+                        (false, _) => t.max(earliest),
+                        (true, true) => t + MAX_FUTURE_DRIFT,
+                        (true, false) => earliest,
+                    }
+                }, &mut clock);
+                let difficulty = headers.last().unwrap().difficulty.to_f64().unwrap() / base;
+                lowest = lowest.min(difficulty);
+                highest = highest.max(difficulty);
+            }
+            assert!(lowest > 0.5 && highest < 2.0, "share {:.2}: ranged {:.2}x..{:.2}x", share, lowest, highest);
+        }
+    }
+
     #[test]
     fn test_flash_mining_detection() {
         let normal = create_test_headers(70, TARGET_BLOCK_TIME, 100_000);
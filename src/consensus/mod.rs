@@ -2,17 +2,24 @@
 pub mod vdf;
 pub mod lwma;
 pub mod validator;
+pub mod uncles;
 
 pub use vdf::{VDF, VDFProof, VDFBlockHeader};
 pub use lwma::{
     calculate_lwma_difficulty,
+    check_timestamp,
+    median_time_past,
     detect_flash_mining,
     estimate_hashrate,
     format_hashrate,
     meets_difficulty,
     difficulty_to_target,
     BlockHeader,
+    TimestampError,
     TARGET_BLOCK_TIME,
     LWMA_WINDOW,
     MIN_DIFFICULTY,
+    MAX_FUTURE_DRIFT,
+    MEDIAN_TIME_SPAN,
 };
+pub use uncles::{check_uncles, StaleBlocks, MAX_UNCLES, MAX_UNCLE_DEPTH};
@@ -0,0 +1,244 @@
+// src/consensus/uncles.rs - Stale blocks and uncle references
+//
+// When two miners find a block at the same height, one of them goes stale.
+// `StaleBlocks` remembers the ones this node sees, to report the orphan
+// rate and to offer them as uncles to the blocks it mines.
+//
+// A block may reference up to `MAX_UNCLES` stale siblings of its last
+// `MAX_UNCLE_DEPTH` ancestors. An uncle travels whole, as its proof of work
+// commits to its whole encoding, but no larger than `MAX_UNCLE_BYTES`.
+// Every node checks it as it checked the sibling that won its height: its
+// parent, its VDF and proof of work at the difficulty of that height, and
+// its miner's ZK pass. Its transactions are not checked, as the uncle is
+// never applied. What its miner earns is `economics::UNCLE_POLICY`.
+
+use std::collections::VecDeque;
+
+use crate::block::Block;
+use crate::chain::Timechain;
+use crate::economics::UnclePolicy;
+use crate::transaction::Address;
+
+/// Uncles one block may reference.
+pub const MAX_UNCLES: usize = 2;
+
+/// How many heights below a block its uncles may be.
+pub const MAX_UNCLE_DEPTH: u64 = 6;
+
+/// Heights the orphan rate is measured over.
+pub const ORPHAN_RATE_WINDOW: u64 = 100;
+
+/// Largest encoded uncle: what a block template may fill.
+pub const MAX_UNCLE_BYTES: u64 = crate::block_template::DEFAULT_MAX_BLOCK_BYTES as u64;
+
+/// Stale blocks kept for the orphan rate and as uncle candidates.
+const MAX_TRACKED: usize = 256;
+
+/// Check `uncle` as a reference from the block at `nephew_slot` on top of
+/// `tc`. Returns the uncle's hash.
+fn check_uncle(uncle: &Block, nephew_slot: u64, tc: &Timechain) -> Result<[u8; 32], &'static str> {
+    if bincode::serialized_size(uncle).map_or(true, |size| size > MAX_UNCLE_BYTES) {
+        return Err("Uncle too large");
+    }
+    if !uncle.uncles.is_empty() {
+        return Err("Uncle references uncles of its own");
+    }
+    if uncle.slot == 0 || uncle.slot >= nephew_slot || nephew_slot - uncle.slot > MAX_UNCLE_DEPTH {
+        return Err("Uncle outside the allowed depth");
+    }
+    let chain = &tc.blocks;
+    let (Some(parent), Some(sibling), Some(difficulty)) =
+        (chain.get(uncle.slot as usize - 1), chain.get(uncle.slot as usize), tc.difficulty_at(uncle.slot))
+    else {
+        return Err("Uncle outside the allowed depth");
+    };
+    if uncle.parent != parent.legacy_hash() {
+        return Err("Uncle does not branch off this chain");
+    }
+    let hash = uncle.legacy_hash();
+    if sibling.legacy_hash() == hash {
+        return Err("Uncle is on the main chain");
+    }
+    let referenced = chain[uncle.slot as usize + 1..]
+        .iter()
+        .flat_map(|b| &b.uncles)
+        .any(|u| u.legacy_hash() == hash);
+    if referenced {
+        return Err("Uncle already referenced");
+    }
+    let vdf_seed = crate::vdf::evaluate(uncle.parent, uncle.slot);
+    if !tc.vdf().verify(vdf_seed, difficulty, &uncle.vdf_proof, &uncle.vdf_witness) {
+        return Err("Invalid uncle VDF proof");
+    }
+    if !uncle.meets_difficulty(difficulty) {
+        return Err("Uncle doesn't meet difficulty requirement");
+    }
+    if !crate::genesis::verify_zk_pass(&uncle.miner, &uncle.parent, &uncle.zk_proof) {
+        return Err("Invalid uncle ZK pass");
+    }
+    Ok(hash)
+}
+
+/// The consensus rule for `block.uncles`, with `block` the next block on
+/// `tc`.
+pub fn check_uncles(block: &Block, tc: &Timechain) -> Result<(), &'static str> {
+    if block.uncles.len() > MAX_UNCLES {
+        return Err("Too many uncles");
+    }
+    let mut seen = Vec::with_capacity(block.uncles.len());
+    for uncle in &block.uncles {
+        let hash = check_uncle(uncle, block.slot, tc)?;
+        if seen.contains(&hash) {
+            return Err("Uncle already referenced");
+        }
+        seen.push(hash);
+    }
+    Ok(())
+}
+
+/// What `block`'s uncles are paid under `policy`, by miner.
+pub fn uncle_payouts(block: &Block, policy: UnclePolicy) -> Vec<(Address, u64)> {
+    block
+        .uncles
+        .iter()
+        .filter(|uncle| uncle.miner != [0u8; 32])
+        .map(|uncle| (uncle.miner, policy.uncle_reward(uncle.slot)))
+        .filter(|(_, reward)| *reward > 0)
+        .collect()
+}
+
+/// Stale blocks this node has seen, newest last.
+#[derive(Debug, Default)]
+pub struct StaleBlocks {
+    recent: VecDeque<Block>,
+    total: u64,
+}
+
+impl StaleBlocks {
+    fn insert(&mut self, block: Block) -> bool {
+        let hash = block.legacy_hash();
+        if self.recent.iter().any(|b| b.legacy_hash() == hash) {
+            return false;
+        }
+        if self.recent.len() >= MAX_TRACKED {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(block);
+        self.total += 1;
+        true
+    }
+
+    /// Record `block` if it lost a race on `chain`: its parent is there, a
+    /// different block holds its height, and it meets `difficulty`.
+    /// Returns whether it was new.
+    pub fn observe(&mut self, block: &Block, chain: &[Block], difficulty: u64) -> bool {
+        let slot = block.slot as usize;
+        let is_sibling = slot > 0
+            && chain.get(slot - 1).is_some_and(|parent| parent.legacy_hash() == block.parent)
+            && chain.get(slot).is_some_and(|ours| ours.legacy_hash() != block.legacy_hash())
+            && block.meets_difficulty(difficulty);
+        is_sibling && self.insert(block.clone())
+    }
+
+    /// Record blocks a reorg disconnected. They were fully validated when
+    /// they were connected.
+    pub fn record_disconnected(&mut self, blocks: impl IntoIterator<Item = Block>) {
+        for block in blocks {
+            self.insert(block);
+        }
+    }
+
+    /// Stale blocks seen since the node started.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Share of the blocks found over the last `ORPHAN_RATE_WINDOW` heights
+    /// of a chain `height` blocks long that went stale.
+    pub fn orphan_rate(&self, height: u64) -> f64 {
+        let from = height.saturating_sub(ORPHAN_RATE_WINDOW);
+        let stale = self.recent.iter().filter(|b| b.slot >= from && b.slot < height).count() as u64;
+        let found = stale + (height - from);
+        if found == 0 {
+            0.0
+        } else {
+            stale as f64 / found as f64
+        }
+    }
+
+    /// Uncles the next block on `tc` can reference, most recent first.
+    pub fn uncle_candidates(&self, tc: &Timechain) -> Vec<Block> {
+        let nephew_slot = tc.blocks.len() as u64;
+        self.recent
+            .iter()
+            .rev()
+            .filter(|uncle| check_uncle(uncle, nephew_slot, tc).is_ok())
+            .take(MAX_UNCLES)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child(parent: &Block, miner: u8) -> Block {
+        parent.test_child().mined_by([miner; 32], vec![])
+    }
+
+    #[test]
+    fn test_stale_sibling_becomes_uncle_once() {
+        let genesis = crate::genesis::genesis();
+        let b1 = child(&genesis, 1);
+        let b2 = child(&b1, 1);
+        let mut tc = Timechain::from_saved_blocks(vec![genesis.clone(), b1.clone(), b2]).unwrap();
+        let rival = tc.seal(child(&genesis, 2));
+        let easy = 1;
+
+        let mut stale = StaleBlocks::default();
+        assert!(!stale.observe(&b1, &tc.blocks, easy), "our own block is not stale");
+        assert!(stale.observe(&rival, &tc.blocks, easy));
+        assert!(!stale.observe(&rival, &tc.blocks, easy));
+        assert_eq!(stale.total(), 1);
+        assert!((stale.orphan_rate(3) - 0.25).abs() < 1e-9);
+
+        let mut b3 = child(&tc.blocks[2], 1);
+        b3.uncles = stale.uncle_candidates(&tc);
+        assert_eq!(b3.uncles, vec![rival.clone()]);
+        assert_eq!(check_uncles(&b3, &tc), Ok(()));
+        assert_eq!(uncle_payouts(&b3, UnclePolicy::NoReward), vec![]);
+        let half = crate::economics::get_mining_reward(1) / 2;
+        assert_eq!(uncle_payouts(&b3, UnclePolicy::Partial { percent: 50 }), vec![([2u8; 32], half)]);
+
+        let mut twice = b3.clone();
+        twice.uncles.push(rival.clone());
+        assert_eq!(check_uncles(&twice, &tc), Err("Uncle already referenced"));
+        let mut main_chain = b3.clone();
+        main_chain.uncles = vec![b1];
+        assert_eq!(check_uncles(&main_chain, &tc), Err("Uncle is on the main chain"));
+
+        // Each uncle is checked as its sibling was.
+        let with_uncle = |uncle: Block| Block { uncles: vec![uncle], ..b3.clone() };
+        let untimed = Block { vdf_proof: [0u8; 32], ..rival.clone() };
+        assert_eq!(check_uncles(&with_uncle(untimed), &tc), Err("Invalid uncle VDF proof"));
+        let mut unworked = rival.clone();
+        while unworked.meets_difficulty(tc.difficulty_at(1).unwrap()) {
+            unworked.nonce += 1;
+        }
+        assert_eq!(check_uncles(&with_uncle(unworked), &tc), Err("Uncle doesn't meet difficulty requirement"));
+        let unpassed = tc.seal(Block { zk_proof: rival.zk_proof.clone(), ..child(&genesis, 3) });
+        assert_eq!(check_uncles(&with_uncle(unpassed), &tc), Err("Invalid uncle ZK pass"));
+        let padded = Block { zk_proof: vec![0u8; MAX_UNCLE_BYTES as usize], ..rival.clone() };
+        assert_eq!(check_uncles(&with_uncle(padded), &tc), Err("Uncle too large"));
+
+        // Once referenced, no later block may claim it again.
+        let mut blocks = tc.blocks.clone();
+        blocks.push(b3.clone());
+        tc = Timechain::from_saved_blocks(blocks).unwrap();
+        assert!(stale.uncle_candidates(&tc).is_empty());
+        let mut b4 = child(&b3, 1);
+        b4.uncles = vec![rival];
+        assert_eq!(check_uncles(&b4, &tc), Err("Uncle already referenced"));
+    }
+}
@@ -74,7 +74,7 @@ impl ConsensusValidator {
             let (is_valid, score, reason) = self.ml_validator.validate_transaction(tx);
             if !is_valid {
                 return Err(ValidationError::MLRejection {
-                    tx_hash: tx.hash(),
+                    tx_hash: tx.legacy_hash(),
                     anomaly_score: score,
                     reason,
                 });
@@ -92,7 +92,7 @@ impl ConsensusValidator {
         let (is_valid, score, reason) = self.ml_validator.validate_transaction(tx);
         if !is_valid {
             return Err(ValidationError::MLRejection {
-                tx_hash: tx.hash(),
+                tx_hash: tx.legacy_hash(),
                 anomaly_score: score,
                 reason,
             });
@@ -118,6 +118,7 @@ mod tests {
             amount: 1_000,
             fee: 10,
             nonce: 1,
+            expiry_height: 0,
             zk_proof: vec![0u8; 256],
             signature: vec![0u8; 64],
         }
@@ -125,14 +126,11 @@ mod tests {
 
     fn sample_block() -> Block {
         Block {
-            parent: [0u8; 32],
-            slot: 1,
             timestamp: crate::genesis::GENESIS_TIMESTAMP + crate::chain::TARGET_TIME,
             miner: [3u8; 32],
             transactions: vec![sample_tx()],
-            vdf_proof: [0u8; 32],
             zk_proof: vec![0u8; 128],
-            nonce: 0,
+            ..Block::test([0u8; 32], 1)
         }
     }
 
@@ -2,7 +2,7 @@
 // Implements Wesolowski VDF for deterministic, sequential proof-of-time
 
 use num_bigint::BigUint;
-use num_traits::One;
+use num_traits::{One, Zero};
 use num_integer::Integer;
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
 use sha2::{Sha256, Digest};
@@ -105,6 +105,12 @@ impl VDF {
         let x = self.hash_to_prime(input);
         let y = &proof.output;
         let pi = &proof.proof;
+
+        // Zero satisfies π^ℓ · x^r ≡ y for any x, so a proof of (0, 0)
+        // would pass without any squaring done.
+        if y.is_zero() || pi.is_zero() || *y >= self.modulus || *pi >= self.modulus {
+            return Ok(false);
+        }
         
         // Wesolowski verification:
         // 1. Compute challenge ℓ = H(x, y)
@@ -331,6 +337,9 @@ mod tests {
         
         let valid = vdf.verify(input, &bad_proof).expect("Verification failed");
         assert!(!valid, "Tampered proof should be invalid");
+
+        let zero = VDFProof { output: BigUint::zero(), proof: BigUint::zero() };
+        assert!(!vdf.verify(input, &zero).expect("Verification failed"));
     }
     
     #[test]
@@ -0,0 +1,369 @@
+// src/contract_registry.rs - Node-indexed contract registry
+//
+// Maps a contract's RISC Zero image ID to a human-readable name, version
+// and source URL, so RPC clients and the explorer can show what a user is
+// actually interacting with instead of a bare 32-byte hash.
+//
+// Entries are published by signed registration: the first valid
+// registration of an image ID makes its signer the publisher, and only
+// that key may update the entry afterwards. Names are unique across the
+// registry. Audit scores are not self-reported: the node operator runs the
+// ContractAuditor (axiom-ai-enhancement) and attaches the report through
+// the loopback-only admin route; any metadata update clears it again.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::transaction::Address;
+use crate::wallet::Wallet;
+
+pub const DEFAULT_REGISTRY_PATH: &str = "axiom_contracts.json";
+
+pub const MAX_NAME_LEN: usize = 64;
+pub const MAX_VERSION_LEN: usize = 32;
+pub const MAX_SOURCE_URL_LEN: usize = 256;
+
+/// Registrations timestamped further than this from the node clock are
+/// refused, which bounds how long a captured registration stays replayable.
+pub const MAX_REGISTRATION_SKEW_SECS: u64 = 300;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RegistryError {
+    #[error("invalid hex in {0}")]
+    InvalidHex(&'static str),
+    #[error("name must be 1-{MAX_NAME_LEN} characters of [A-Za-z0-9._-]")]
+    InvalidName,
+    #[error("version must be 1-{MAX_VERSION_LEN} printable characters")]
+    InvalidVersion,
+    #[error("source URL must be an http(s) URL of at most {MAX_SOURCE_URL_LEN} characters")]
+    InvalidSourceUrl,
+    #[error("registration timestamp is outside the accepted window")]
+    StaleRegistration,
+    #[error("registration signature does not verify")]
+    BadSignature,
+    #[error("contract is registered to another publisher")]
+    NotPublisher,
+    #[error("name '{0}' is already registered to another contract")]
+    NameTaken(String),
+    #[error("contract is not registered")]
+    UnknownContract,
+    #[error("audit score must be between 0 and 100")]
+    InvalidAuditScore,
+}
+
+/// Publisher-supplied description of a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractMetadata {
+    pub name: String,
+    pub version: String,
+    pub source_url: Option<String>,
+}
+
+impl ContractMetadata {
+    pub fn validate(&self) -> Result<(), RegistryError> {
+        let name_ok = !self.name.is_empty()
+            && self.name.len() <= MAX_NAME_LEN
+            && self.name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+        if !name_ok {
+            return Err(RegistryError::InvalidName);
+        }
+        let version_ok = !self.version.is_empty()
+            && self.version.len() <= MAX_VERSION_LEN
+            && self.version.chars().all(|c| c.is_ascii_graphic());
+        if !version_ok {
+            return Err(RegistryError::InvalidVersion);
+        }
+        if let Some(url) = &self.source_url {
+            let url_ok = url.len() <= MAX_SOURCE_URL_LEN
+                && (url.starts_with("https://") || url.starts_with("http://"))
+                && !url.chars().any(char::is_whitespace);
+            if !url_ok {
+                return Err(RegistryError::InvalidSourceUrl);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A signed request to create or update a registry entry, as posted to
+/// `/v1/contracts`. Byte fields are hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Registration {
+    pub image_id: String,
+    #[serde(flatten)]
+    pub metadata: ContractMetadata,
+    pub publisher: String,
+    /// Unix time the registration was signed.
+    pub timestamp: u64,
+    pub signature: String,
+}
+
+impl Registration {
+    /// Build and sign a registration with `publisher`'s key.
+    pub fn signed(image_id: [u8; 32], metadata: ContractMetadata, publisher: &Wallet, timestamp: u64) -> Self {
+        let mut registration = Self {
+            image_id: hex::encode(image_id),
+            metadata,
+            publisher: hex::encode(publisher.address),
+            timestamp,
+            signature: String::new(),
+        };
+        registration.signature = hex::encode(publisher.sign_message(&registration.signing_bytes()));
+        registration
+    }
+
+    /// Domain-separated message covered by `signature`.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut message = b"axiom-contract-v1".to_vec();
+        message.extend(self.image_id.to_ascii_lowercase().as_bytes());
+        message.extend(self.publisher.to_ascii_lowercase().as_bytes());
+        message.extend(self.timestamp.to_le_bytes());
+        message.extend(bincode::serialize(&self.metadata).unwrap_or_default());
+        message
+    }
+
+    fn image_id_bytes(&self) -> Result<[u8; 32], RegistryError> {
+        decode_hex32(&self.image_id).ok_or(RegistryError::InvalidHex("image_id"))
+    }
+
+    fn publisher_bytes(&self) -> Result<Address, RegistryError> {
+        decode_hex32(&self.publisher).ok_or(RegistryError::InvalidHex("publisher"))
+    }
+
+    fn verify_signature(&self) -> Result<(), RegistryError> {
+        let signature: [u8; 64] = hex::decode(&self.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(RegistryError::InvalidHex("signature"))?;
+        let key = ed25519_dalek::VerifyingKey::from_bytes(&self.publisher_bytes()?)
+            .map_err(|_| RegistryError::BadSignature)?;
+        key.verify_strict(&self.signing_bytes(), &ed25519_dalek::Signature::from_bytes(&signature))
+            .map_err(|_| RegistryError::BadSignature)
+    }
+}
+
+/// Summary of a ContractAuditor report, attached by the node operator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditSummary {
+    /// `AuditReport::overall_score`, 0-100.
+    pub score: u8,
+    /// Number of vulnerabilities the report lists.
+    pub findings: u32,
+    /// `AuditReport::contract_hash` of the audited code.
+    pub report_hash: String,
+    pub audited_at: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractEntry {
+    pub image_id: String,
+    #[serde(flatten)]
+    pub metadata: ContractMetadata,
+    pub publisher: String,
+    pub registered_at: u64,
+    /// Timestamp of the registration currently in force.
+    pub updated_at: u64,
+    pub audit: Option<AuditSummary>,
+}
+
+#[derive(Debug, Default)]
+pub struct ContractRegistry {
+    path: PathBuf,
+    /// Keyed by lowercase hex image ID.
+    entries: HashMap<String, ContractEntry>,
+    dirty: bool,
+}
+
+impl ContractRegistry {
+    /// Load the registry at `path`. A missing or unreadable file yields an
+    /// empty registry; it will be recreated on the next save.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let entries = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("CONTRACTS: {} is corrupt, starting empty: {}", path.display(), e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self { path, entries, dirty: false }
+    }
+
+    /// Write the registry if it changed since the last save.
+    pub fn save(&mut self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(&self.entries)?;
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, image_id: &[u8; 32]) -> Option<&ContractEntry> {
+        self.entries.get(&hex::encode(image_id))
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&ContractEntry> {
+        self.entries.values().find(|entry| entry.metadata.name.eq_ignore_ascii_case(name))
+    }
+
+    /// All entries, sorted by name.
+    pub fn list(&self) -> Vec<&ContractEntry> {
+        let mut entries: Vec<_> = self.entries.values().collect();
+        entries.sort_by(|a, b| a.metadata.name.cmp(&b.metadata.name));
+        entries
+    }
+
+    /// Apply a signed registration: create the entry, or update it if the
+    /// signer is its publisher. Updating drops any attached audit, since it
+    /// may describe a different release.
+    pub fn register(&mut self, registration: Registration, now: u64) -> Result<&ContractEntry, RegistryError> {
+        let image_id = hex::encode(registration.image_id_bytes()?);
+        let publisher = hex::encode(registration.publisher_bytes()?);
+        registration.metadata.validate()?;
+        if registration.timestamp.abs_diff(now) > MAX_REGISTRATION_SKEW_SECS {
+            return Err(RegistryError::StaleRegistration);
+        }
+        registration.verify_signature()?;
+
+        if let Some(existing) = self.entries.get(&image_id) {
+            if existing.publisher != publisher {
+                return Err(RegistryError::NotPublisher);
+            }
+            // Also rejects a replay of the registration in force.
+            if registration.timestamp <= existing.updated_at {
+                return Err(RegistryError::StaleRegistration);
+            }
+        }
+        if let Some(holder) = self.find_by_name(&registration.metadata.name) {
+            if holder.image_id != image_id {
+                return Err(RegistryError::NameTaken(registration.metadata.name));
+            }
+        }
+
+        let registered_at = self.entries.get(&image_id).map_or(now, |entry| entry.registered_at);
+        self.dirty = true;
+        self.entries.insert(image_id.clone(), ContractEntry {
+            image_id: image_id.clone(),
+            metadata: registration.metadata,
+            publisher,
+            registered_at,
+            updated_at: registration.timestamp,
+            audit: None,
+        });
+        Ok(&self.entries[&image_id])
+    }
+
+    /// Attach an audit to a registered contract, replacing any earlier one.
+    pub fn record_audit(&mut self, image_id: &[u8; 32], audit: AuditSummary) -> Result<(), RegistryError> {
+        if audit.score > 100 {
+            return Err(RegistryError::InvalidAuditScore);
+        }
+        let entry = self.entries.get_mut(&hex::encode(image_id)).ok_or(RegistryError::UnknownContract)?;
+        entry.audit = Some(audit);
+        self.dirty = true;
+        Ok(())
+    }
+}
+
+/// Parse a 32-byte hex value, with or without a `0x` prefix.
+pub fn decode_hex32(value: &str) -> Option<[u8; 32]> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    hex::decode(value).ok()?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(name: &str) -> ContractMetadata {
+        ContractMetadata {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            source_url: Some("https://github.com/example/escrow".to_string()),
+        }
+    }
+
+    fn wallet(seed: u8) -> Wallet {
+        crate::payout::payout_wallet(&Wallet { secret_key: [seed; 32], address: [0u8; 32] }, 0)
+    }
+
+    fn temp_registry(tag: &str) -> ContractRegistry {
+        let path = std::env::temp_dir().join(format!("axiom_test_contracts_{}_{}.json", tag, std::process::id()));
+        let _ = fs::remove_file(&path);
+        ContractRegistry::load(path)
+    }
+
+    #[test]
+    fn test_register_update_and_reload() {
+        let publisher = wallet(1);
+        let mut registry = temp_registry("reload");
+
+        registry.register(Registration::signed([7u8; 32], metadata("escrow"), &publisher, 1_000), 1_000).unwrap();
+        registry
+            .record_audit(&[7u8; 32], AuditSummary { score: 92, findings: 1, report_hash: "ab".into(), audited_at: 1_010 })
+            .unwrap();
+        assert_eq!(registry.get(&[7u8; 32]).unwrap().audit.as_ref().unwrap().score, 92);
+
+        let mut update = metadata("escrow");
+        update.version = "1.1.0".to_string();
+        let entry = registry.register(Registration::signed([7u8; 32], update, &publisher, 1_100), 1_100).unwrap();
+        assert_eq!(entry.metadata.version, "1.1.0");
+        assert_eq!(entry.registered_at, 1_000);
+        assert!(entry.audit.is_none(), "an update must drop the old audit");
+
+        registry.save().unwrap();
+        let reloaded = ContractRegistry::load(&registry.path);
+        assert_eq!(reloaded.find_by_name("ESCROW").unwrap().metadata.version, "1.1.0");
+        let _ = fs::remove_file(&registry.path);
+    }
+
+    #[test]
+    fn test_registration_rejections() {
+        let publisher = wallet(1);
+        let squatter = wallet(2);
+        let mut registry = temp_registry("reject");
+        let first = Registration::signed([1u8; 32], metadata("escrow"), &publisher, 1_000);
+        registry.register(first.clone(), 1_000).unwrap();
+
+        // Replay of the registration in force.
+        assert_eq!(registry.register(first, 1_000).unwrap_err(), RegistryError::StaleRegistration);
+        // Someone else updating the entry, or taking its name for another image.
+        let hijack = Registration::signed([1u8; 32], metadata("escrow"), &squatter, 1_001);
+        assert_eq!(registry.register(hijack, 1_001).unwrap_err(), RegistryError::NotPublisher);
+        let squat = Registration::signed([2u8; 32], metadata("Escrow"), &squatter, 1_001);
+        assert!(matches!(registry.register(squat, 1_001), Err(RegistryError::NameTaken(_))));
+        // Tampered metadata.
+        let mut forged = Registration::signed([3u8; 32], metadata("vault"), &squatter, 1_001);
+        forged.metadata.source_url = Some("https://evil.example".to_string());
+        assert_eq!(registry.register(forged, 1_001).unwrap_err(), RegistryError::BadSignature);
+        // Old signature.
+        let old = Registration::signed([3u8; 32], metadata("vault"), &squatter, 1);
+        assert_eq!(registry.register(old, 1_001).unwrap_err(), RegistryError::StaleRegistration);
+        // Bad name.
+        let bad = Registration::signed([3u8; 32], metadata("my vault"), &squatter, 1_001);
+        assert_eq!(registry.register(bad, 1_001).unwrap_err(), RegistryError::InvalidName);
+
+        assert_eq!(registry.len(), 1);
+        assert_eq!(
+            registry.record_audit(&[9u8; 32], AuditSummary { score: 50, findings: 0, report_hash: String::new(), audited_at: 0 }),
+            Err(RegistryError::UnknownContract)
+        );
+    }
+}
@@ -54,6 +54,74 @@ pub fn block_reward(slot: u64, _total_issued: u64) -> u64 {
     get_mining_reward(slot)
 }
 
+// ==================== FEE POLICY ====================
+
+/// Share of every transaction fee that is destroyed, in percent. The rest
+/// is credited to the miner of the block that includes the transaction.
+/// Part of consensus: nodes disagreeing on it compute different balances.
+pub const FEE_BURN_PERCENT: u64 = 50;
+
+/// How transaction fees are divided between burning and the miner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FeePolicy {
+    /// 0 to 100; larger values burn everything.
+    pub burn_percent: u64,
+}
+
+/// The policy the chain applies.
+pub const FEE_POLICY: FeePolicy = FeePolicy { burn_percent: FEE_BURN_PERCENT };
+
+impl FeePolicy {
+    /// `(burned, to_miner)` for one fee; the burned share rounds down.
+    pub fn split(&self, fee: u64) -> (u64, u64) {
+        let burned = (fee as u128 * self.burn_percent.min(100) as u128 / 100) as u64;
+        (burned, fee - burned)
+    }
+}
+
+// ==================== UNCLE POLICY ====================
+
+/// What the miner of a stale block gets when a later block references it
+/// as an uncle. Part of consensus, like `FEE_POLICY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UnclePolicy {
+    /// Stale blocks earn nothing; the 124M schedule is paid to the main
+    /// chain only.
+    NoReward,
+    /// The uncle's miner is paid `percent` of the block reward at the
+    /// uncle's height, issued on top of the schedule.
+    Partial { percent: u64 },
+}
+
+/// The policy the chain applies. Anything other than `NoReward` issues
+/// coins beyond the main-chain schedule, which the supply cap still bounds.
+pub const UNCLE_POLICY: UnclePolicy = UnclePolicy::NoReward;
+
+impl UnclePolicy {
+    /// Reward for an uncle mined at `uncle_slot`.
+    pub fn uncle_reward(&self, uncle_slot: u64) -> u64 {
+        match *self {
+            UnclePolicy::NoReward => 0,
+            UnclePolicy::Partial { percent } => {
+                (get_mining_reward(uncle_slot) as u128 * percent.min(100) as u128 / 100) as u64
+            }
+        }
+    }
+
+    /// Whether miners gain anything by referencing uncles.
+    pub fn pays(&self) -> bool {
+        !matches!(self, UnclePolicy::NoReward | UnclePolicy::Partial { percent: 0 })
+    }
+}
+
+/// Fees settled so far, kept in the account state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FeeTotals {
+    pub burned: u64,
+    pub to_miners: u64,
+}
+
 /// Calculate total supply at a given height
 /// 
 /// This accounts for all mined blocks up to the current height,
@@ -135,6 +203,57 @@ impl EraStats {
     }
 }
 
+/// Supply and emission figures for the chain as it stands, served on
+/// `/v1/supply` and by the explorer's `/api/supply`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SupplySnapshot {
+    /// Height the next block will have.
+    pub height: u64,
+    pub max_supply: u64,
+    /// Rewards actually paid so far.
+    pub total_mined: u64,
+    pub remaining: u64,
+    /// Sum of all balances: mined coins less fees burned.
+    pub circulating: u64,
+    /// Fees destroyed so far; they reduce supply for good.
+    pub fees_burned: u64,
+    /// Fees credited to miners so far.
+    pub fees_to_miners: u64,
+    pub fee_burn_percent: u64,
+    pub era: u64,
+    /// Reward for the next block.
+    pub block_reward: u64,
+    /// `None` once the reward has reached zero.
+    pub next_halving_height: Option<u64>,
+    pub blocks_until_halving: Option<u64>,
+    /// Expected unix time of the next halving at the target block time.
+    pub next_halving_eta: Option<u64>,
+}
+
+impl SupplySnapshot {
+    /// Figures for a chain whose next block is at `height`, with the tip
+    /// mined at `tip_timestamp`.
+    pub fn new(height: u64, total_mined: u64, circulating: u64, fees: FeeTotals, tip_timestamp: u64) -> Self {
+        let block_reward = get_mining_reward(height);
+        let blocks_until = (block_reward > 0).then(|| blocks_until_halving(height));
+        Self {
+            height,
+            max_supply: TOTAL_SUPPLY,
+            total_mined,
+            remaining: TOTAL_SUPPLY.saturating_sub(total_mined),
+            circulating,
+            fees_burned: fees.burned,
+            fees_to_miners: fees.to_miners,
+            fee_burn_percent: FEE_POLICY.burn_percent,
+            era: current_era(height),
+            block_reward,
+            next_halving_height: blocks_until.map(|b| height + b),
+            blocks_until_halving: blocks_until,
+            next_halving_eta: blocks_until.map(|b| tip_timestamp + b * BLOCK_TIME_SECONDS),
+        }
+    }
+}
+
 // ==================== 20-YEAR NETWORK SIMULATION ====================
 
 /// Network phase definitions based on the 20-year simulation
@@ -203,7 +322,7 @@ pub fn format_axm(amount: u64) -> String {
 }
 
 /// Format supply statistics for monitoring
-pub fn format_supply_stats(height: u64) -> String {
+pub fn format_supply_stats(height: u64, fees: FeeTotals) -> String {
     let current_supply = calculate_total_supply(height);
     let remaining = remaining_supply(height);
     let percentage = supply_percentage(height);
@@ -220,6 +339,9 @@ pub fn format_supply_stats(height: u64) -> String {
 ║  Total Supply:        124,000,000 AXM (Fixed Forever)       ║
 ║  Current Supply:      {:>12} AXM ({:>5.2}%)             ║
 ║  Remaining:           {:>12} AXM ({:>5.2}%)             ║
+║  Fees Burned:         {:>12} AXM ({:>3}% of fees)       ║
+║  Fees to Miners:      {:>12} AXM                          ║
+║  Effective Supply:    {:>12} AXM                          ║
 ║                                                              ║
 ║  Block Height:        {:>12}                              ║
 ║  Current Reward:      {:>12} AXM                          ║
@@ -234,6 +356,10 @@ pub fn format_supply_stats(height: u64) -> String {
         percentage,
         remaining / SMALLEST_UNIT,
         100.0 - percentage,
+        fees.burned / SMALLEST_UNIT,
+        FEE_POLICY.burn_percent,
+        fees.to_miners / SMALLEST_UNIT,
+        current_supply.saturating_sub(fees.burned) / SMALLEST_UNIT,
         height,
         reward / SMALLEST_UNIT,
         era,
@@ -293,6 +419,11 @@ pub fn validate_economics() -> Result<(), String> {
             TOTAL_SUPPLY, total
         ));
     }
+
+    // Test 4: Fee burn must be a percentage
+    if FEE_POLICY.burn_percent > 100 {
+        return Err(format!("Fee burn percentage over 100: {}", FEE_POLICY.burn_percent));
+    }
     
     Ok(())
 }
@@ -325,6 +456,28 @@ mod tests {
         assert_eq!(get_mining_reward(HALVING_INTERVAL * 3), 6 * SMALLEST_UNIT + 25_000_000);
     }
     
+    #[test]
+    fn test_supply_snapshot() {
+        let fees = FeeTotals { burned: 100, to_miners: 100 };
+        let snapshot = SupplySnapshot::new(HALVING_INTERVAL - 2, 1_000, 900, fees, 10_000);
+        assert_eq!(snapshot.era, 0);
+        assert_eq!(snapshot.next_halving_height, Some(HALVING_INTERVAL));
+        assert_eq!(snapshot.next_halving_eta, Some(10_000 + 2 * BLOCK_TIME_SECONDS));
+        assert_eq!(snapshot.remaining, TOTAL_SUPPLY - 1_000);
+        assert_eq!(snapshot.fees_burned, 100);
+
+        // Emission over: nothing left to halve.
+        let last = SupplySnapshot::new(64 * HALVING_INTERVAL, TOTAL_SUPPLY, TOTAL_SUPPLY, FeeTotals::default(), 0);
+        assert_eq!((last.block_reward, last.next_halving_height), (0, None));
+    }
+
+    #[test]
+    fn test_fee_split() {
+        assert_eq!(FeePolicy { burn_percent: 50 }.split(101), (50, 51));
+        assert_eq!(FeePolicy { burn_percent: 0 }.split(7), (0, 7));
+        assert_eq!(FeePolicy { burn_percent: 250 }.split(u64::MAX), (u64::MAX, 0));
+    }
+
     #[test]
     fn test_supply_cap() {
         // Total supply should never exceed 124M
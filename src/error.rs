@@ -1,6 +1,7 @@
 // src/error.rs - AXIOM Protocol Complete Error Handling
 // Production-ready error types for mainnet deployment
 
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Main error type for AXIOM Protocol
@@ -19,6 +20,12 @@ pub enum AxiomError {
         actual: u64,
     },
     
+    #[error("Nonce gap: the sender's next nonce is {expected}, got {actual}")]
+    NonceGap {
+        expected: u64,
+        actual: u64,
+    },
+    
     #[error("Invalid signature: {0}")]
     InvalidSignature(String),
     
@@ -43,15 +50,43 @@ pub enum AxiomError {
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(String),
     
-    #[error("Transaction expired: submitted at {submitted}, current time {current}")]
+    #[error("Transaction expired at height {expiry_height} (next block: {height})")]
     TransactionExpired {
-        submitted: u64,
-        current: u64,
+        expiry_height: u64,
+        height: u64,
     },
     
     #[error("Nullifier already used (double-spend attempt)")]
     NullifierUsed,
     
+    #[error("Replacement underpriced: pending transaction {pending} has this nonce; a replacement must pay a fee of at least {min_fee} AXM, got {actual} AXM")]
+    ReplacementUnderpriced {
+        pending: String,
+        min_fee: u64,
+        actual: u64,
+    },
+    
+    #[error("Double spend: the sender's {pending} pending transactions and this one need {required} AXM, account has {available} AXM")]
+    DoubleSpend {
+        pending: usize,
+        available: u64,
+        required: u64,
+    },
+    
+    #[error("Mempool sender limit: {sender} has {pending} pending transactions (max: {max})")]
+    SenderTxLimitExceeded {
+        sender: String,
+        pending: usize,
+        max: usize,
+    },
+    
+    #[error("Mempool sender limit: {sender} would have {pending_bytes} pending bytes (max: {max} bytes)")]
+    SenderBytesLimitExceeded {
+        sender: String,
+        pending_bytes: usize,
+        max: usize,
+    },
+    
     // ==================== BLOCK ERRORS ====================
     #[error("Invalid block: {0}")]
     InvalidBlock(String),
@@ -205,6 +240,11 @@ pub enum AxiomError {
         reason: String,
     },
     
+    #[error("Chain halted: {reason} (blocks and transactions are refused until operators resume)")]
+    ChainHalted {
+        reason: String,
+    },
+    
     #[error("Oracle consensus failed: got {responses} responses, need {required}")]
     OracleConsensusFailed {
         responses: usize,
@@ -233,6 +273,10 @@ pub enum AxiomError {
         max_deviation: u64,
     },
     
+    // ==================== BRIDGE ERRORS ====================
+    #[error("Bridge error: {0}")]
+    BridgeError(String),
+    
     // ==================== CONFIGURATION ERRORS ====================
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
@@ -275,6 +319,17 @@ pub enum AxiomError {
     #[error("RPC timeout")]
     RpcTimeout,
     
+    #[error("Not found: {0}")]
+    NotFound(String),
+    
+    #[error("Too many requests: retry after {retry_after_secs} seconds")]
+    RateLimited {
+        retry_after_secs: u64,
+    },
+    
+    #[error("Unauthorized")]
+    Unauthorized,
+    
     // ==================== SYSTEM ERRORS ====================
     #[error("I/O error: {0}")]
     IoError(String),
@@ -314,6 +369,13 @@ impl From<sled::Error> for AxiomError {
     }
 }
 
+#[cfg(feature = "rocksdb")]
+impl From<rocksdb::Error> for AxiomError {
+    fn from(err: rocksdb::Error) -> Self {
+        AxiomError::DatabaseError(err.to_string())
+    }
+}
+
 impl From<bincode::Error> for AxiomError {
     fn from(err: bincode::Error) -> Self {
         AxiomError::SerializationError(err.to_string())
@@ -326,6 +388,249 @@ impl From<toml::de::Error> for AxiomError {
     }
 }
 
+// ==================== ERROR CODES ====================
+
+/// Stable numeric error codes, shared with the SDK.
+///
+/// Codes are part of the public API: a code keeps its number and meaning
+/// once released, new failures get new codes, and the thousands digit names
+/// the subsystem. Several `AxiomError` variants may share a code when
+/// clients have no reason to tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    // 1xxx: transactions
+    InvalidTransaction = 1000,
+    InsufficientFunds = 1001,
+    NonceTooLow = 1002,
+    NonceTooHigh = 1003,
+    InvalidSignature = 1004,
+    ZeroAmount = 1005,
+    FeeTooLow = 1006,
+    DuplicateTransaction = 1007,
+    TransactionTooLarge = 1008,
+    TransactionExpired = 1009,
+    DoubleSpend = 1010,
+    SenderLimitReached = 1011,
+    ReplacementUnderpriced = 1012,
+    /// Ahead of the nonce after the sender's pending transactions; the
+    /// account route's `pending_nonce` is the one to use.
+    NonceGap = 1013,
+    // 2xxx: blocks
+    InvalidBlock = 2000,
+    BlockNotFound = 2001,
+    // 3xxx: consensus
+    ConsensusFailure = 3000,
+    InvalidVdf = 3001,
+    InvalidPow = 3002,
+    // 4xxx: proofs and keys
+    InvalidProof = 4000,
+    ProofGenerationFailed = 4001,
+    InvalidKey = 4002,
+    CryptoError = 4003,
+    // 5xxx: networking
+    NetworkError = 5000,
+    PeerBanned = 5001,
+    MaxPeersReached = 5002,
+    // 6xxx: storage and state
+    StorageError = 6000,
+    StateCorruption = 6001,
+    AccountNotFound = 6002,
+    // 7xxx: guardian and protocol invariants
+    ThreatDetected = 7000,
+    ProposalRejected = 7001,
+    OracleFailure = 7002,
+    InvariantViolation = 7003,
+    ChainHalted = 7004,
+    // 8xxx: bridge
+    BridgeError = 8000,
+    // 9xxx: node configuration and wallet
+    InvalidConfig = 9000,
+    WalletLocked = 9100,
+    InvalidPassword = 9101,
+    WalletError = 9102,
+    // 10xxx: API
+    InvalidRequest = 10000,
+    MethodNotFound = 10001,
+    NotFound = 10002,
+    RateLimited = 10003,
+    Unauthorized = 10004,
+    Timeout = 10005,
+    /// Anything the caller cannot act on.
+    Internal = 99999,
+}
+
+impl ErrorCode {
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_u32(code: u32) -> Option<Self> {
+        Some(match code {
+            1000 => ErrorCode::InvalidTransaction,
+            1001 => ErrorCode::InsufficientFunds,
+            1002 => ErrorCode::NonceTooLow,
+            1003 => ErrorCode::NonceTooHigh,
+            1004 => ErrorCode::InvalidSignature,
+            1005 => ErrorCode::ZeroAmount,
+            1006 => ErrorCode::FeeTooLow,
+            1007 => ErrorCode::DuplicateTransaction,
+            1008 => ErrorCode::TransactionTooLarge,
+            1009 => ErrorCode::TransactionExpired,
+            1010 => ErrorCode::DoubleSpend,
+            1011 => ErrorCode::SenderLimitReached,
+            1012 => ErrorCode::ReplacementUnderpriced,
+            1013 => ErrorCode::NonceGap,
+            2000 => ErrorCode::InvalidBlock,
+            2001 => ErrorCode::BlockNotFound,
+            3000 => ErrorCode::ConsensusFailure,
+            3001 => ErrorCode::InvalidVdf,
+            3002 => ErrorCode::InvalidPow,
+            4000 => ErrorCode::InvalidProof,
+            4001 => ErrorCode::ProofGenerationFailed,
+            4002 => ErrorCode::InvalidKey,
+            4003 => ErrorCode::CryptoError,
+            5000 => ErrorCode::NetworkError,
+            5001 => ErrorCode::PeerBanned,
+            5002 => ErrorCode::MaxPeersReached,
+            6000 => ErrorCode::StorageError,
+            6001 => ErrorCode::StateCorruption,
+            6002 => ErrorCode::AccountNotFound,
+            7000 => ErrorCode::ThreatDetected,
+            7001 => ErrorCode::ProposalRejected,
+            7002 => ErrorCode::OracleFailure,
+            7003 => ErrorCode::InvariantViolation,
+            7004 => ErrorCode::ChainHalted,
+            8000 => ErrorCode::BridgeError,
+            9000 => ErrorCode::InvalidConfig,
+            9100 => ErrorCode::WalletLocked,
+            9101 => ErrorCode::InvalidPassword,
+            9102 => ErrorCode::WalletError,
+            10000 => ErrorCode::InvalidRequest,
+            10001 => ErrorCode::MethodNotFound,
+            10002 => ErrorCode::NotFound,
+            10003 => ErrorCode::RateLimited,
+            10004 => ErrorCode::Unauthorized,
+            10005 => ErrorCode::Timeout,
+            99999 => ErrorCode::Internal,
+            _ => return None,
+        })
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{}", self.as_u32())
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.as_u32())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let code = u32::deserialize(deserializer)?;
+        // Codes from a newer node are not an error, just not actionable.
+        Ok(Self::from_u32(code).unwrap_or(ErrorCode::Internal))
+    }
+}
+
+/// JSON error body returned by the HTTP API:
+/// `{"error": "<message>", "code": 1001}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorBody {
+    pub error: String,
+    pub code: ErrorCode,
+}
+
+impl ErrorBody {
+    pub fn new(code: ErrorCode, error: impl Into<String>) -> Self {
+        Self { error: error.into(), code }
+    }
+}
+
+impl From<&AxiomError> for ErrorBody {
+    fn from(err: &AxiomError) -> Self {
+        Self::new(err.code(), err.to_string())
+    }
+}
+
+impl AxiomError {
+    /// The stable code clients see for this error.
+    pub fn code(&self) -> ErrorCode {
+        use AxiomError::*;
+        match self {
+            InsufficientBalance { .. } => ErrorCode::InsufficientFunds,
+            InvalidNonce { expected, actual } if actual < expected => ErrorCode::NonceTooLow,
+            InvalidNonce { .. } => ErrorCode::NonceTooHigh,
+            NonceGap { .. } => ErrorCode::NonceGap,
+            InvalidSignature(_) => ErrorCode::InvalidSignature,
+            ZeroAmount => ErrorCode::ZeroAmount,
+            FeeTooLow { .. } => ErrorCode::FeeTooLow,
+            DuplicateTransaction => ErrorCode::DuplicateTransaction,
+            TransactionTooLarge { .. } => ErrorCode::TransactionTooLarge,
+            InvalidTransaction(_) => ErrorCode::InvalidTransaction,
+            TransactionExpired { .. } => ErrorCode::TransactionExpired,
+            NullifierUsed | DoubleSpend { .. } => ErrorCode::DoubleSpend,
+            ReplacementUnderpriced { .. } => ErrorCode::ReplacementUnderpriced,
+            SenderTxLimitExceeded { .. } | SenderBytesLimitExceeded { .. } => ErrorCode::SenderLimitReached,
+
+            InvalidBlock(_) | InvalidParent { .. } | InvalidBlockHeight { .. } | InvalidTimestamp { .. }
+            | BlockTooLarge { .. } | InvalidBlockReward { .. } | GenesisExists => ErrorCode::InvalidBlock,
+            BlockNotFound(_) => ErrorCode::BlockNotFound,
+
+            VDFVerificationFailed(_) | VDFComputationFailed(_) => ErrorCode::InvalidVdf,
+            PoWVerificationFailed { .. } => ErrorCode::InvalidPow,
+            ChainReorgRequired { .. } | ForkDetected { .. } | ConsensusFailed(_) => ErrorCode::ConsensusFailure,
+
+            ProofVerificationFailed(_) | InvalidCircuitParams(_) => ErrorCode::InvalidProof,
+            ProofGenerationFailed(_) => ErrorCode::ProofGenerationFailed,
+            InvalidPublicKey(_) | InvalidPrivateKey => ErrorCode::InvalidKey,
+            CryptoError(_) | KeyDerivationFailed(_) => ErrorCode::CryptoError,
+
+            NetworkError(_) | PeerConnectionFailed { .. } | NetworkTimeout { .. } | GossipError(_)
+            | P2PError(_) => ErrorCode::NetworkError,
+            MaxPeersReached { .. } => ErrorCode::MaxPeersReached,
+            PeerBanned { .. } => ErrorCode::PeerBanned,
+
+            DatabaseError(_) | StorageInitFailed(_) | DiskFull { .. } | MigrationFailed(_) => {
+                ErrorCode::StorageError
+            }
+            StateCorruption(_) => ErrorCode::StateCorruption,
+            AccountNotFound(_) => ErrorCode::AccountNotFound,
+
+            ThreatDetected { .. } => ErrorCode::ThreatDetected,
+            AIProposalRejected { .. } => ErrorCode::ProposalRejected,
+            ChainHalted { .. } => ErrorCode::ChainHalted,
+            OracleConsensusFailed { .. } | AIModelError(_) => ErrorCode::OracleFailure,
+            InsufficientStake { .. } | SupplyCapViolation { .. } | BlockTimingViolation { .. } => {
+                ErrorCode::InvariantViolation
+            }
+
+            BridgeError(_) => ErrorCode::BridgeError,
+
+            InvalidConfig(_) | MissingParameter(_) | ConfigNotFound(_) | ConfigParseError(_) => {
+                ErrorCode::InvalidConfig
+            }
+            WalletLocked => ErrorCode::WalletLocked,
+            InvalidPassword => ErrorCode::InvalidPassword,
+            WalletNotFound(_) | WalletExists(_) | KeystoreError(_) => ErrorCode::WalletError,
+
+            InvalidRpcRequest(_) => ErrorCode::InvalidRequest,
+            RpcMethodNotFound(_) => ErrorCode::MethodNotFound,
+            NotFound(_) => ErrorCode::NotFound,
+            RateLimited { .. } => ErrorCode::RateLimited,
+            Unauthorized => ErrorCode::Unauthorized,
+            RpcTimeout => ErrorCode::Timeout,
+
+            RpcError(_) | IoError(_) | SerializationError(_) | DeserializationError(_) | ThreadError(_)
+            | ResourceExhausted(_) | EmergencyShutdown { .. } => ErrorCode::Internal,
+        }
+    }
+}
+
 // ==================== ERROR SEVERITY ====================
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -404,4 +709,27 @@ mod tests {
         
         assert!(matches!(axiom_err, AxiomError::IoError(_)));
     }
+
+    #[test]
+    fn test_error_codes_are_stable() {
+        assert_eq!(AxiomError::InsufficientBalance { available: 1, required: 2 }.code().as_u32(), 1001);
+        assert_eq!(AxiomError::InvalidNonce { expected: 5, actual: 3 }.code(), ErrorCode::NonceTooLow);
+        assert_eq!(AxiomError::InvalidNonce { expected: 5, actual: 9 }.code(), ErrorCode::NonceTooHigh);
+        assert_eq!(AxiomError::ProofVerificationFailed("x".into()).code().as_u32(), 4000);
+        let banned = AxiomError::PeerBanned { peer_id: "p".into(), reason: "spam".into() };
+        assert_eq!(banned.code().as_u32(), 5001);
+
+        let body = ErrorBody::from(&banned);
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json, serde_json::json!({"error": "Peer banned: p (reason: spam)", "code": 5001}));
+        assert_eq!(serde_json::from_value::<ErrorBody>(json).unwrap(), body);
+        // Unknown codes from a newer node still parse.
+        let newer: ErrorBody = serde_json::from_str(r#"{"error": "?", "code": 123456}"#).unwrap();
+        assert_eq!(newer.code, ErrorCode::Internal);
+
+        for code in [1000, 1011, 1012, 1013, 2001, 3002, 4003, 5002, 6002, 7003, 7004, 8000, 9102, 10005, 99999] {
+            assert_eq!(ErrorCode::from_u32(code).map(ErrorCode::as_u32), Some(code));
+        }
+        assert_eq!(ErrorCode::from_u32(1999), None);
+    }
 }
@@ -0,0 +1,114 @@
+// src/fee_market.rs - Fee statistics over recently included transactions
+//
+// Wallets had no way to price a transaction and hardcoded a fee. The node
+// now summarises the fee rates paid by transactions in its last
+// `FEE_WINDOW_BLOCKS` blocks, in the unit miners rank candidates by (fee
+// per encoded byte, `block_template::fee_rate`), and serves them at
+// `/v1/fees`. The SDK turns a percentile into a fee for the priority a
+// wallet asks for.
+//
+// With no transactions in the window every percentile is
+// `MIN_FEE_RATE`, so a quiet chain still quotes a non-zero fee.
+
+use serde::{Deserialize, Serialize};
+
+use crate::block::Block;
+use crate::block_template::{fee_rate, tx_weight};
+
+/// Recent blocks the statistics cover.
+pub const FEE_WINDOW_BLOCKS: usize = 100;
+
+/// Rate reported when the window holds no transactions, in satoshis per byte.
+pub const MIN_FEE_RATE: f64 = 1.0;
+
+/// Fee rates of recently included transactions, in satoshis per byte.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeStats {
+    pub tip_height: u64,
+    /// Blocks the figures were taken from.
+    pub window_blocks: u64,
+    pub tx_count: u64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+    /// Median encoded size of the transactions, to turn a rate into a fee.
+    pub median_tx_bytes: u64,
+}
+
+/// Nearest-rank percentile of sorted `values`.
+fn percentile<T: Copy>(sorted: &[T], p: usize) -> Option<T> {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+impl FeeStats {
+    /// Statistics over the last `FEE_WINDOW_BLOCKS` of `blocks`.
+    pub fn from_blocks(blocks: &[Block]) -> Self {
+        let window = &blocks[blocks.len().saturating_sub(FEE_WINDOW_BLOCKS)..];
+        let txs = window.iter().flat_map(|b| &b.transactions);
+        let mut rates: Vec<f64> = txs.clone().map(fee_rate).collect();
+        let mut sizes: Vec<usize> = txs.map(tx_weight).collect();
+        rates.sort_by(f64::total_cmp);
+        sizes.sort_unstable();
+
+        let rate = |p| percentile(&rates, p).unwrap_or(MIN_FEE_RATE).max(MIN_FEE_RATE);
+        Self {
+            tip_height: blocks.len() as u64,
+            window_blocks: window.len() as u64,
+            tx_count: rates.len() as u64,
+            p10: rate(10),
+            p25: rate(25),
+            p50: rate(50),
+            p75: rate(75),
+            p90: rate(90),
+            median_tx_bytes: percentile(&sizes, 50).unwrap_or(0) as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::Transaction;
+
+    fn block(slot: u64, fees: &[u64]) -> Block {
+        let transactions = fees
+            .iter()
+            .enumerate()
+            .map(|(i, &fee)| Transaction {
+                from: [1u8; 32],
+                to: [2u8; 32],
+                amount: 10,
+                fee,
+                nonce: i as u64,
+                expiry_height: 0,
+                zk_proof: vec![],
+                signature: vec![0u8; 64],
+            })
+            .collect();
+        Block { miner: [7u8; 32], transactions, ..Block::test([0u8; 32], slot) }
+    }
+
+    #[test]
+    fn test_percentiles_over_recent_window() {
+        let empty = FeeStats::from_blocks(&[block(0, &[])]);
+        assert_eq!((empty.tx_count, empty.p90, empty.median_tx_bytes), (0, MIN_FEE_RATE, 0));
+
+        let bytes = tx_weight(&block(0, &[1]).transactions[0]) as u64;
+        let fees: Vec<u64> = (1..=100).map(|i| i * 1_000 * bytes).collect();
+        // The oldest block falls outside the window and its huge fee is ignored.
+        let mut chain = vec![block(0, &[u64::MAX / 2])];
+        chain.extend((1..=FEE_WINDOW_BLOCKS as u64).map(|slot| block(slot, &[])));
+        chain.push(block(101, &fees));
+        let stats = FeeStats::from_blocks(&chain);
+
+        assert_eq!(stats.window_blocks, FEE_WINDOW_BLOCKS as u64);
+        assert_eq!(stats.tip_height, chain.len() as u64);
+        assert_eq!(stats.tx_count, 100);
+        assert_eq!(stats.median_tx_bytes, bytes);
+        assert_eq!((stats.p10, stats.p50, stats.p90), (10_000.0, 50_000.0, 90_000.0));
+        assert!(stats.p25 <= stats.p50 && stats.p50 <= stats.p75);
+    }
+}
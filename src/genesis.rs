@@ -1,6 +1,6 @@
 use crate::zk;
 
-use crate::block::Block;
+use crate::block::{Block, Coinbase};
 use crate::wallet::Wallet;
 use std::sync::Once;
 
@@ -116,7 +116,10 @@ pub fn genesis() -> Block {
         timestamp: GENESIS_TIMESTAMP, // January 20, 2025 00:00:00 UTC
         miner: [0u8; 32],
         transactions: vec![],
+        coinbase: Coinbase::default(),
+        uncles: vec![],
         vdf_proof: [0u8; 32],
+        vdf_witness: Vec::new(),
         zk_proof: vec![0u8; 128],
         nonce: 0,
     };
@@ -0,0 +1,512 @@
+// src/guardian/alerts.rs - Telling a human when the guardian escalates
+//
+// The guardian raises an `Alert` when a transaction assessment calls for
+// `EscalateToGuardian` or `HaltChain`, and the sentinel raises one when a
+// supply, genesis or health check fails. Alerts go to the webhooks,
+// PagerDuty services (Events API v2) and SMTP relays in `ai.alert_sinks`
+// (see config/axiom.toml).
+//
+// Each sink takes the alerts at or above its `min_severity` (default:
+// warning). An alert with the same key as one sent within
+// `ai.alert_dedup_window` seconds is held back and counted, unless it is
+// more severe; the next one sent says how many were held back. PagerDuty
+// also deduplicates on the key, so repeats after the window update the
+// open incident instead of paging again.
+//
+// Raising an alert never blocks: it is queued to a task that delivers it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Default for `ai.alert_dedup_window`.
+pub const DEFAULT_DEDUP_WINDOW_SECS: u64 = 900;
+
+/// Alert keys remembered for deduplication.
+const MAX_TRACKED_KEYS: usize = 1024;
+
+/// Longest one delivery may take.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// SMTP submission over implicit TLS (RFC 8314): TLS from the first byte.
+const SMTPS_PORT: u16 = 465;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertSeverity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl AlertSeverity {
+    fn as_str(self) -> &'static str {
+        match self {
+            AlertSeverity::Info => "info",
+            AlertSeverity::Warning => "warning",
+            AlertSeverity::Critical => "critical",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    /// What the alert is about; alerts with the same key are duplicates.
+    pub key: String,
+    pub severity: AlertSeverity,
+    pub summary: String,
+    pub details: String,
+    /// The component that raised it.
+    pub source: String,
+    pub timestamp: u64,
+    /// Duplicates held back since the last one with this key was sent.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub suppressed: u32,
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+impl Alert {
+    pub fn new(key: impl Into<String>, severity: AlertSeverity, source: &str, summary: impl Into<String>, details: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            severity,
+            summary: summary.into(),
+            details: details.into(),
+            source: source.to_string(),
+            timestamp: unix_now(),
+            suppressed: 0,
+        }
+    }
+
+    fn subject(&self) -> String {
+        format!("[axiom {}] {}", self.severity.as_str(), self.summary)
+    }
+
+    fn text(&self) -> String {
+        let mut text = format!("{}\n\nSource: {}\nKey: {}\nTime: {}\n", self.details, self.source, self.key, self.rfc3339());
+        if self.suppressed > 0 {
+            text.push_str(&format!("Repeats held back since the last alert: {}\n", self.suppressed));
+        }
+        text
+    }
+
+    fn rfc3339(&self) -> String {
+        chrono::DateTime::from_timestamp(self.timestamp as i64, 0).unwrap_or_default().to_rfc3339()
+    }
+
+    /// The Events API v2 trigger for this alert.
+    pub fn pagerduty_event(&self, routing_key: &str) -> serde_json::Value {
+        serde_json::json!({
+            "routing_key": routing_key,
+            "event_action": "trigger",
+            "dedup_key": self.key,
+            "payload": {
+                "summary": self.summary,
+                "source": self.source,
+                "severity": self.severity.as_str(),
+                "timestamp": self.rfc3339(),
+                "custom_details": {
+                    "details": self.details,
+                    "suppressed": self.suppressed,
+                },
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertSink {
+    Webhook {
+        url: String,
+        #[serde(default)]
+        min_severity: AlertSeverity,
+    },
+    #[serde(rename = "pagerduty")]
+    PagerDuty {
+        routing_key: String,
+        #[serde(default)]
+        min_severity: AlertSeverity,
+    },
+    Email {
+        smtp_host: String,
+        #[serde(default = "default_smtp_port")]
+        smtp_port: u16,
+        #[serde(default = "default_starttls")]
+        starttls: bool,
+        #[serde(default)]
+        username: Option<String>,
+        /// Environment variable holding the SMTP password.
+        #[serde(default)]
+        password_env: Option<String>,
+        from: String,
+        to: Vec<String>,
+        #[serde(default)]
+        min_severity: AlertSeverity,
+    },
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_starttls() -> bool {
+    true
+}
+
+impl AlertSink {
+    /// Whether the sink takes `alert`.
+    pub fn accepts(&self, alert: &Alert) -> bool {
+        let min = match self {
+            AlertSink::Webhook { min_severity, .. }
+            | AlertSink::PagerDuty { min_severity, .. }
+            | AlertSink::Email { min_severity, .. } => *min_severity,
+        };
+        alert.severity >= min
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            AlertSink::Webhook { url, .. } if !url.starts_with("https://") && !url.starts_with("http://") => {
+                Err(format!("webhook '{}' is not an http(s) URL", url))
+            }
+            AlertSink::PagerDuty { routing_key, .. } if routing_key.is_empty() => {
+                Err("pagerduty sink needs a routing_key".to_string())
+            }
+            AlertSink::Email { to, .. } if to.is_empty() => Err("email sink needs at least one recipient".to_string()),
+            AlertSink::Email { username: Some(_), password_env: None, .. } => {
+                Err("email sink with a username needs password_env".to_string())
+            }
+            AlertSink::Email { username: Some(_), starttls: false, smtp_port, .. } if *smtp_port != SMTPS_PORT => Err(format!(
+                "email sink with a username needs starttls or smtp_port {} (credentials would go in cleartext)",
+                SMTPS_PORT
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            AlertSink::Webhook { url, .. } => format!("webhook {}", url),
+            AlertSink::PagerDuty { .. } => "pagerduty".to_string(),
+            AlertSink::Email { smtp_host, .. } => format!("email via {}", smtp_host),
+        }
+    }
+
+    async fn deliver(&self, client: &reqwest::Client, alert: &Alert) -> Result<(), String> {
+        match self {
+            AlertSink::Webhook { url, .. } => post_json(client, url, &serde_json::to_value(alert).unwrap_or_default()).await,
+            AlertSink::PagerDuty { routing_key, .. } => {
+                post_json(client, PAGERDUTY_EVENTS_URL, &alert.pagerduty_event(routing_key)).await
+            }
+            AlertSink::Email { smtp_host, smtp_port, starttls, username, password_env, from, to, .. } => {
+                let credentials = match (username, password_env) {
+                    (Some(user), Some(var)) => {
+                        let password = std::env::var(var).map_err(|_| format!("{} is not set", var))?;
+                        Some((user.as_str(), password))
+                    }
+                    _ => None,
+                };
+                let mail = Mail { from, to, subject: alert.subject(), body: alert.text() };
+                tokio::time::timeout(DELIVERY_TIMEOUT, send_mail(smtp_host, *smtp_port, *starttls, credentials, &mail))
+                    .await
+                    .map_err(|_| "SMTP timed out".to_string())?
+            }
+        }
+    }
+}
+
+async fn post_json(client: &reqwest::Client, url: &str, body: &serde_json::Value) -> Result<(), String> {
+    let response = client.post(url).json(body).send().await.map_err(|e| e.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", response.status()))
+    }
+}
+
+/// Holds back alerts whose key was sent within the window.
+pub struct Deduplicator {
+    window: u64,
+    /// Key -> (sent at, severity sent, held back since).
+    sent: HashMap<String, (u64, AlertSeverity, u32)>,
+}
+
+impl Deduplicator {
+    pub fn new(window_secs: u64) -> Self {
+        Self { window: window_secs, sent: HashMap::new() }
+    }
+
+    /// The alert to send, carrying the count held back before it, or None
+    /// if it duplicates one sent within the window.
+    pub fn admit(&mut self, mut alert: Alert, now: u64) -> Option<Alert> {
+        if let Some((sent_at, severity, held)) = self.sent.get_mut(&alert.key) {
+            if now.saturating_sub(*sent_at) < self.window && alert.severity <= *severity {
+                *held += 1;
+                return None;
+            }
+            alert.suppressed = *held;
+        }
+        if self.sent.len() >= MAX_TRACKED_KEYS {
+            let window = self.window;
+            self.sent.retain(|_, (sent_at, _, _)| now.saturating_sub(*sent_at) < window);
+        }
+        self.sent.insert(alert.key.clone(), (now, alert.severity, 0));
+        Some(alert)
+    }
+}
+
+/// Handle for raising alerts. Cheap to clone; a handle without sinks
+/// drops everything.
+#[derive(Clone, Default)]
+pub struct Alerts {
+    queue: Option<mpsc::UnboundedSender<Alert>>,
+}
+
+impl Alerts {
+    /// Start delivering to `sinks`. Must be called inside a Tokio runtime
+    /// when there are sinks.
+    pub fn spawn(sinks: Vec<AlertSink>, dedup_window_secs: u64) -> Self {
+        if sinks.is_empty() {
+            return Self::default();
+        }
+        let (queue, mut alerts) = mpsc::unbounded_channel::<Alert>();
+        tokio::spawn(async move {
+            let client = match reqwest::Client::builder().timeout(DELIVERY_TIMEOUT).build() {
+                Ok(client) => client,
+                Err(e) => {
+                    log::error!("Alert sinks disabled: {}", e);
+                    return;
+                }
+            };
+            let mut dedup = Deduplicator::new(dedup_window_secs);
+            while let Some(alert) = alerts.recv().await {
+                let Some(alert) = dedup.admit(alert, unix_now()) else {
+                    continue;
+                };
+                for sink in sinks.iter().filter(|s| s.accepts(&alert)) {
+                    if let Err(e) = sink.deliver(&client, &alert).await {
+                        log::warn!("🔔 Alert '{}' not delivered to {}: {}", alert.key, sink.name(), e);
+                    }
+                }
+            }
+        });
+        Self { queue: Some(queue) }
+    }
+
+    pub fn raise(&self, alert: Alert) {
+        if let Some(queue) = &self.queue {
+            let _ = queue.send(alert);
+        }
+    }
+}
+
+struct Mail<'a> {
+    from: &'a str,
+    to: &'a [String],
+    subject: String,
+    body: String,
+}
+
+impl Mail<'_> {
+    /// Headers and dot-stuffed body, CRLF line endings, without the
+    /// terminating dot.
+    fn message(&self) -> String {
+        let date = chrono::Utc::now().to_rfc2822();
+        let mut message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n",
+            self.from,
+            self.to.join(", "),
+            self.subject.replace(['\r', '\n'], " "),
+            date
+        );
+        for line in self.body.lines() {
+            if line.starts_with('.') {
+                message.push('.');
+            }
+            message.push_str(line);
+            message.push_str("\r\n");
+        }
+        message
+    }
+}
+
+/// Deliver over implicit TLS on `SMTPS_PORT`, STARTTLS if `starttls`,
+/// else in cleartext (which `AlertSink::validate` allows only without
+/// credentials).
+async fn send_mail(host: &str, port: u16, starttls: bool, credentials: Option<(&str, String)>, mail: &Mail<'_>) -> Result<(), String> {
+    let tcp = tokio::net::TcpStream::connect((host, port)).await.map_err(|e| e.to_string())?;
+    if port == SMTPS_PORT {
+        let mut tls = BufReader::new(tls_connect(host, tcp).await?);
+        expect(&mut tls, 220).await?;
+        command(&mut tls, "EHLO axiom-node", 250).await?;
+        return smtp_session(&mut tls, credentials, mail).await;
+    }
+    let mut plain = BufReader::new(tcp);
+    expect(&mut plain, 220).await?;
+    command(&mut plain, "EHLO axiom-node", 250).await?;
+    if !starttls {
+        if credentials.is_some() {
+            return Err("refusing to send SMTP credentials without TLS".to_string());
+        }
+        return smtp_session(&mut plain, credentials, mail).await;
+    }
+    command(&mut plain, "STARTTLS", 220).await?;
+    let mut tls = BufReader::new(tls_connect(host, plain.into_inner()).await?);
+    command(&mut tls, "EHLO axiom-node", 250).await?;
+    smtp_session(&mut tls, credentials, mail).await
+}
+
+async fn tls_connect(
+    host: &str,
+    tcp: tokio::net::TcpStream,
+) -> Result<tokio_native_tls::TlsStream<tokio::net::TcpStream>, String> {
+    let connector = tokio_native_tls::native_tls::TlsConnector::new().map_err(|e| e.to_string())?;
+    tokio_native_tls::TlsConnector::from(connector)
+        .connect(host, tcp)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// AUTH (if any), then one message, after EHLO.
+async fn smtp_session<S>(stream: &mut S, credentials: Option<(&str, String)>, mail: &Mail<'_>) -> Result<(), String>
+where
+    S: AsyncBufRead + AsyncWrite + Unpin,
+{
+    use base64::Engine;
+
+    if let Some((user, password)) = credentials {
+        let token = base64::engine::general_purpose::STANDARD.encode(format!("\0{}\0{}", user, password));
+        command(stream, &format!("AUTH PLAIN {}", token), 235).await?;
+    }
+    command(stream, &format!("MAIL FROM:<{}>", mail.from), 250).await?;
+    for to in mail.to {
+        command(stream, &format!("RCPT TO:<{}>", to), 250).await?;
+    }
+    command(stream, "DATA", 354).await?;
+    stream.write_all(mail.message().as_bytes()).await.map_err(|e| e.to_string())?;
+    command(stream, ".", 250).await?;
+    let _ = command(stream, "QUIT", 221).await;
+    Ok(())
+}
+
+async fn command<S>(stream: &mut S, line: &str, code: u16) -> Result<(), String>
+where
+    S: AsyncBufRead + AsyncWrite + Unpin,
+{
+    stream.write_all(format!("{}\r\n", line).as_bytes()).await.map_err(|e| e.to_string())?;
+    stream.flush().await.map_err(|e| e.to_string())?;
+    expect(stream, code).await
+}
+
+/// Read one (possibly multi-line) reply and check its code.
+async fn expect<S: AsyncBufRead + Unpin>(stream: &mut S, code: u16) -> Result<(), String> {
+    loop {
+        let mut line = String::new();
+        if stream.read_line(&mut line).await.map_err(|e| e.to_string())? == 0 {
+            return Err("SMTP server closed the connection".to_string());
+        }
+        let reply: u16 = line.get(..3).and_then(|c| c.parse().ok()).ok_or_else(|| format!("bad SMTP reply '{}'", line.trim()))?;
+        if reply != code {
+            return Err(format!("SMTP: {}", line.trim()));
+        }
+        // "250-..." continues, "250 ..." is the last line
+        if line.as_bytes().get(3) != Some(&b'-') {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(key: &str, severity: AlertSeverity, at: u64) -> Alert {
+        Alert { timestamp: at, ..Alert::new(key, severity, "test", "summary", "details") }
+    }
+
+    #[test]
+    fn test_dedup_routing_and_sink_config() {
+        let mut dedup = Deduplicator::new(900);
+        assert!(dedup.admit(alert("escalate", AlertSeverity::Warning, 0), 0).is_some());
+        assert!(dedup.admit(alert("escalate", AlertSeverity::Warning, 10), 10).is_none());
+        assert!(dedup.admit(alert("escalate", AlertSeverity::Info, 20), 20).is_none());
+        assert!(dedup.admit(alert("halt_chain", AlertSeverity::Warning, 20), 20).is_some(), "other keys pass");
+        // A more severe repeat goes out at once, reporting what was held back.
+        let escalated = dedup.admit(alert("escalate", AlertSeverity::Critical, 30), 30).unwrap();
+        assert_eq!(escalated.suppressed, 2);
+        assert!(dedup.admit(alert("escalate", AlertSeverity::Critical, 40), 40).is_none());
+        assert_eq!(dedup.admit(alert("escalate", AlertSeverity::Critical, 930), 930).unwrap().suppressed, 1);
+
+        #[derive(Deserialize)]
+        struct Sinks {
+            sinks: Vec<AlertSink>,
+        }
+        let sinks: Vec<AlertSink> = toml::from_str::<Sinks>(
+            r#"
+            [[sinks]]
+            type = "webhook"
+            url = "https://ops.example/hook"
+
+            [[sinks]]
+            type = "pagerduty"
+            routing_key = "R0UT1NG"
+            min_severity = "critical"
+
+            [[sinks]]
+            type = "email"
+            smtp_host = "smtp.example"
+            username = "axiom"
+            from = "node@example"
+            to = ["oncall@example"]
+            "#,
+        )
+        .unwrap()
+        .sinks;
+        let warning = alert("escalate", AlertSeverity::Warning, 0);
+        assert!(sinks[0].accepts(&warning) && !sinks[1].accepts(&warning));
+        assert!(sinks[1].accepts(&alert("halt_chain", AlertSeverity::Critical, 0)));
+        assert!(sinks[0].validate().is_ok() && sinks[1].validate().is_ok());
+        assert!(sinks[2].validate().unwrap_err().contains("password_env"));
+        assert!(matches!(sinks[2], AlertSink::Email { smtp_port: 587, starttls: true, .. }));
+
+        // Credentials only travel over TLS: STARTTLS or implicit on 465.
+        let email = |smtp_port, starttls, username: Option<&str>| AlertSink::Email {
+            smtp_host: "smtp.example".into(),
+            smtp_port,
+            starttls,
+            username: username.map(String::from),
+            password_env: Some("AXIOM_SMTP_PASSWORD".into()),
+            from: "node@example".into(),
+            to: vec!["oncall@example".into()],
+            min_severity: AlertSeverity::Warning,
+        };
+        assert!(email(587, true, Some("axiom")).validate().is_ok());
+        assert!(email(465, false, Some("axiom")).validate().is_ok());
+        assert!(email(25, false, Some("axiom")).validate().unwrap_err().contains("cleartext"));
+        assert!(email(25, false, None).validate().is_ok());
+
+        let event = warning.pagerduty_event("R0UT1NG");
+        assert_eq!(event["dedup_key"], "escalate");
+        assert_eq!(event["payload"]["severity"], "warning");
+        assert_eq!(event["payload"]["timestamp"], "1970-01-01T00:00:00+00:00");
+
+        let body = Mail { from: "a@x", to: &["b@x".to_string()], subject: "s\r\nBcc: c@x".into(), body: "ok\n.hidden".into() }.message();
+        assert!(body.contains("Subject: s  Bcc: c@x\r\n") && body.ends_with("\r\nok\r\n..hidden\r\n"));
+    }
+}
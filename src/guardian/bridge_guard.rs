@@ -0,0 +1,235 @@
+// src/guardian/bridge_guard.rs - Bridge volume caps and circuit breaker
+//
+// A bridge exploit drains as fast as it can mint or unlock. Capping how
+// much value may cross in any rolling window bounds the loss to that cap:
+// the guardian tracks bridged volume per chain and in total, escalates as
+// a cap is approached, and pauses the whole bridge when a transfer would
+// exceed one. Only an operator can resume a paused bridge.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use super::safety_manifest::SovereignInvariants;
+use crate::bridge::ChainId;
+use crate::error::{AxiomError, Result};
+
+/// Window the caps apply to.
+pub const BRIDGE_CAP_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// Default global cap: 1% of the maximum supply per window.
+pub const DEFAULT_GLOBAL_CAP: u64 = SovereignInvariants::MAX_TOTAL_SUPPLY / 100;
+
+/// Default per-chain cap: a quarter of the global cap, so no single
+/// chain's contract can use up the whole allowance.
+pub const DEFAULT_CHAIN_CAP: u64 = DEFAULT_GLOBAL_CAP / 4;
+
+/// Share of a cap, in percent, at which the guardian starts escalating.
+pub const DEFAULT_ESCALATION_PERCENT: u64 = 80;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeLimits {
+    pub window_secs: u64,
+    /// Most value that may cross the bridge, both ways, per window.
+    pub global_cap: u64,
+    /// Cap for a chain not listed in `chain_caps`.
+    pub default_chain_cap: u64,
+    #[serde(default)]
+    pub chain_caps: HashMap<ChainId, u64>,
+    pub escalation_percent: u64,
+}
+
+impl Default for BridgeLimits {
+    fn default() -> Self {
+        Self {
+            window_secs: BRIDGE_CAP_WINDOW_SECS,
+            global_cap: DEFAULT_GLOBAL_CAP,
+            default_chain_cap: DEFAULT_CHAIN_CAP,
+            chain_caps: HashMap::new(),
+            escalation_percent: DEFAULT_ESCALATION_PERCENT,
+        }
+    }
+}
+
+impl BridgeLimits {
+    pub fn chain_cap(&self, chain: &ChainId) -> u64 {
+        self.chain_caps.get(chain).copied().unwrap_or(self.default_chain_cap)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationLevel {
+    /// Volume passed the escalation share of a cap.
+    Warning,
+    /// A transfer would have exceeded a cap; the bridge is paused.
+    Paused,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BridgeEscalation {
+    pub timestamp: u64,
+    pub level: EscalationLevel,
+    /// The chain whose cap triggered it, or `None` for the global cap.
+    pub chain: Option<ChainId>,
+    /// Window volume including the transfer that triggered it.
+    pub volume: u64,
+    pub cap: u64,
+}
+
+/// Rolling-window volume tracker and circuit breaker for the bridge.
+#[derive(Debug, Clone, Default)]
+pub struct BridgeGuard {
+    limits: BridgeLimits,
+    /// (time, chain, amount) of admitted transfers within the window.
+    flows: VecDeque<(u64, ChainId, u64)>,
+    paused: Option<BridgeEscalation>,
+    escalations: Vec<BridgeEscalation>,
+}
+
+impl BridgeGuard {
+    pub fn new(limits: BridgeLimits) -> Self {
+        Self { limits, ..Self::default() }
+    }
+
+    pub fn limits(&self) -> &BridgeLimits {
+        &self.limits
+    }
+
+    /// The escalation that paused the bridge, if it is paused.
+    pub fn paused(&self) -> Option<&BridgeEscalation> {
+        self.paused.as_ref()
+    }
+
+    pub fn escalations(&self) -> &[BridgeEscalation] {
+        &self.escalations
+    }
+
+    fn in_window(&self, at: u64, now: u64) -> bool {
+        at.saturating_add(self.limits.window_secs) > now
+    }
+
+    /// Volume bridged to or from `chain` (or in total, for `None`) in the
+    /// window ending at `now`.
+    pub fn volume(&self, chain: Option<&ChainId>, now: u64) -> u64 {
+        self.flows
+            .iter()
+            .filter(|(at, c, _)| self.in_window(*at, now) && chain.is_none_or(|chain| c == chain))
+            .map(|(_, _, amount)| *amount)
+            .sum()
+    }
+
+    /// Admit a transfer of `amount` to or from `chain` and count it against
+    /// the caps. A transfer that would exceed a cap is refused and pauses
+    /// the bridge.
+    pub fn admit(&mut self, chain: &ChainId, amount: u64, now: u64) -> Result<()> {
+        if let Some(pause) = &self.paused {
+            return Err(AxiomError::BridgeError(format!(
+                "Bridge paused by guardian since {}: {}",
+                pause.timestamp,
+                Self::describe(pause)
+            )));
+        }
+        while self.flows.front().is_some_and(|(at, _, _)| !self.in_window(*at, now)) {
+            self.flows.pop_front();
+        }
+
+        let checks = [
+            (Some(chain.clone()), self.volume(Some(chain), now), self.limits.chain_cap(chain)),
+            (None, self.volume(None, now), self.limits.global_cap),
+        ];
+        for (cap_chain, current, cap) in checks.iter().cloned() {
+            let volume = current.saturating_add(amount);
+            if volume > cap {
+                let escalation = BridgeEscalation { timestamp: now, level: EscalationLevel::Paused, chain: cap_chain, volume, cap };
+                log::error!("🛑 GUARDIAN: pausing bridge: {}", Self::describe(&escalation));
+                self.escalations.push(escalation.clone());
+                self.paused = Some(escalation);
+                return Err(AxiomError::BridgeError(format!(
+                    "Bridge volume cap exceeded ({} > {}); bridge paused",
+                    volume, cap
+                )));
+            }
+        }
+        for (cap_chain, current, cap) in checks {
+            let threshold = cap / 100 * self.limits.escalation_percent;
+            let volume = current + amount;
+            if current < threshold && volume >= threshold {
+                let escalation = BridgeEscalation { timestamp: now, level: EscalationLevel::Warning, chain: cap_chain, volume, cap };
+                log::warn!("⚠️ GUARDIAN: bridge volume high: {}", Self::describe(&escalation));
+                self.escalations.push(escalation);
+            }
+        }
+        self.flows.push_back((now, chain.clone(), amount));
+        Ok(())
+    }
+
+    /// Resume a paused bridge. Volume already in the window still counts.
+    pub fn resume(&mut self) -> bool {
+        if self.paused.take().is_some() {
+            log::warn!("GUARDIAN: bridge resumed by operator");
+            true
+        } else {
+            false
+        }
+    }
+
+    fn describe(escalation: &BridgeEscalation) -> String {
+        let scope = match &escalation.chain {
+            Some(chain) => format!("{:?}", chain),
+            None => "all chains".to_string(),
+        };
+        format!("{} bridged {} of {} cap", scope, escalation.volume, escalation.cap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> BridgeLimits {
+        BridgeLimits {
+            window_secs: 100,
+            global_cap: 1_000,
+            default_chain_cap: 600,
+            chain_caps: [(ChainId::Polygon, 200)].into(),
+            escalation_percent: 80,
+        }
+    }
+
+    #[test]
+    fn test_cap_pauses_until_resumed() {
+        let mut guard = BridgeGuard::new(limits());
+        guard.admit(&ChainId::Ethereum, 400, 10).unwrap();
+        guard.admit(&ChainId::Ethereum, 100, 20).unwrap();
+        assert_eq!(guard.escalations()[0].level, EscalationLevel::Warning);
+        assert_eq!(guard.escalations()[0].chain, Some(ChainId::Ethereum));
+
+        assert!(guard.admit(&ChainId::Ethereum, 101, 30).is_err());
+        assert_eq!(guard.paused().unwrap().cap, 600);
+        // Paused: even a small transfer on another chain is refused.
+        assert!(guard.admit(&ChainId::BSC, 1, 31).is_err());
+        assert_eq!(guard.volume(None, 31), 500);
+
+        assert!(guard.resume());
+        assert!(!guard.resume());
+        guard.admit(&ChainId::BSC, 1, 32).unwrap();
+    }
+
+    #[test]
+    fn test_window_and_chain_caps() {
+        let mut guard = BridgeGuard::new(limits());
+        assert!(guard.admit(&ChainId::Polygon, 201, 0).is_err());
+        assert!(guard.resume());
+        guard.admit(&ChainId::Polygon, 200, 0).unwrap();
+        guard.admit(&ChainId::Ethereum, 600, 50).unwrap();
+        // Global cap: 800 of 1000 used.
+        assert!(guard.admit(&ChainId::BSC, 201, 60).is_err());
+        assert_eq!(guard.paused().unwrap().chain, None);
+        assert!(guard.resume());
+        // The Polygon transfer leaves the window at t=100.
+        assert_eq!(guard.volume(Some(&ChainId::Polygon), 100), 0);
+        guard.admit(&ChainId::Polygon, 200, 100).unwrap();
+        assert_eq!(guard.volume(None, 100), 800);
+    }
+}
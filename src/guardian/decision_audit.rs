@@ -0,0 +1,303 @@
+// src/guardian/decision_audit.rs - Hash-chained log of guardian decisions
+//
+// `NeuralGuardian::audit_decision` commits an event, the model weights and
+// the resulting trust score into an `AuditProof` that anyone holding the
+// same weights can recompute, but a proof nobody keeps proves nothing.
+// `DecisionAuditLog` appends each distinct trust decision on a peer, and
+// each measure enforced against one, to `axiom_guardian_audit.jsonl`. Every
+// record carries the hash of the one before it, so a record edited or
+// removed afterwards breaks the chain when the log is read back.
+//
+// The node serves the log by peer and time range at `/v1/guardian/audit`;
+// `axiom-audit` checks a copy offline, exports it, and replays the
+// decisions against a model file.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::guardian::enforcement::Enforcement;
+use crate::neural_guardian::{Action, AuditProof, NetworkEvent, NeuralGuardian, NeuralNetwork, ThreatAssessment, ThreatType};
+
+pub const GUARDIAN_AUDIT_PATH: &str = "axiom_guardian_audit.jsonl";
+
+/// Records kept in memory for queries; older ones stay in the file only.
+pub const MAX_AUDIT_RECORDS_IN_MEMORY: usize = 100_000;
+
+/// Most records one query returns.
+pub const MAX_AUDIT_QUERY: usize = 1_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditEntry {
+    /// The guardian's assessment of the peer changed after `event`;
+    /// `proof` is `audit_decision` over that event.
+    Decision {
+        event: Box<NetworkEvent>,
+        proof: AuditProof,
+        trust_score: f32,
+        threats: Vec<ThreatType>,
+        action: Action,
+    },
+    /// `enforcement` was carried out on the peer, for the guardian's
+    /// `action` if it recommended one.
+    Enforced { action: Option<Action>, enforcement: Enforcement },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub seq: u64,
+    pub timestamp: u64,
+    pub peer_id: String,
+    #[serde(flatten)]
+    pub entry: AuditEntry,
+    /// `hash` of the previous record, hex; empty for the first.
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl AuditRecord {
+    fn compute_hash(&self) -> String {
+        let fields = (self.seq, self.timestamp, &self.peer_id, &self.entry, &self.prev_hash);
+        let encoded = bincode::serialize(&fields).unwrap_or_default();
+        hex::encode(Sha256::digest(&encoded))
+    }
+}
+
+/// Read every record at `path`, checking the hash chain. A missing file is
+/// an empty log.
+pub fn read_log(path: impl AsRef<Path>) -> io::Result<Vec<AuditRecord>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut records = Vec::new();
+    let mut prev_hash = String::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let seq = records.len() as u64;
+        let broken = || io::Error::new(io::ErrorKind::InvalidData, format!("guardian audit log broken at record {}", seq));
+        let record: AuditRecord = serde_json::from_str(&line).map_err(|_| broken())?;
+        if record.seq != seq || record.prev_hash != prev_hash || record.hash != record.compute_hash() {
+            return Err(broken());
+        }
+        prev_hash = record.hash.clone();
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Outcome of replaying logged decisions against a model.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Decisions made with the model that replay to the same proof.
+    pub verified: usize,
+    /// Decisions made with the model that do not: their record `seq`s.
+    pub mismatched: Vec<u64>,
+    /// Decisions made with other weights, which the model cannot check.
+    pub other_model: usize,
+}
+
+/// Re-run the decisions among `records` on `model` and compare the proofs.
+pub fn replay(records: &[AuditRecord], model: NeuralNetwork) -> ReplayReport {
+    let mut guardian = NeuralGuardian::new();
+    guardian.install_model(model);
+    let mut report = ReplayReport::default();
+    for record in records {
+        let AuditEntry::Decision { event, proof, .. } = &record.entry else {
+            continue;
+        };
+        let replayed = guardian.audit_decision(event);
+        if replayed.weights_hash != proof.weights_hash {
+            report.other_model += 1;
+        } else if replayed.audit_hash_512 == proof.audit_hash_512 && replayed.trust_score == proof.trust_score {
+            report.verified += 1;
+        } else {
+            report.mismatched.push(record.seq);
+        }
+    }
+    report
+}
+
+#[derive(Debug)]
+pub struct DecisionAuditLog {
+    path: Option<PathBuf>,
+    records: VecDeque<AuditRecord>,
+    next_seq: u64,
+    last_hash: String,
+    /// Last decision logged per peer, so an unchanged one is not logged again.
+    decided: HashMap<String, (Action, Vec<ThreatType>)>,
+}
+
+impl DecisionAuditLog {
+    /// A log that is not written anywhere (tests, tools).
+    pub fn in_memory() -> Self {
+        Self { path: None, records: VecDeque::new(), next_seq: 0, last_hash: String::new(), decided: HashMap::new() }
+    }
+
+    /// Load the log at `path` and append new records to it. Fails if the
+    /// file does not parse or its hash chain is broken.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut log = Self::in_memory();
+        for record in read_log(&path)? {
+            log.remember(record);
+        }
+        log.path = Some(path);
+        Ok(log)
+    }
+
+    fn remember(&mut self, record: AuditRecord) {
+        if let AuditEntry::Decision { threats, action, .. } = &record.entry {
+            self.decided.insert(record.peer_id.clone(), (action.clone(), threats.clone()));
+        }
+        self.next_seq = record.seq + 1;
+        self.last_hash = record.hash.clone();
+        if self.records.len() >= MAX_AUDIT_RECORDS_IN_MEMORY {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    fn append(&mut self, peer_id: &str, entry: AuditEntry, now: u64) {
+        let mut record = AuditRecord {
+            seq: self.next_seq,
+            timestamp: now,
+            peer_id: peer_id.to_string(),
+            entry,
+            prev_hash: self.last_hash.clone(),
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash();
+        if let Some(path) = &self.path {
+            // A record that did not reach the file is dropped, so the chain
+            // on disk has no gap.
+            let written = serde_json::to_string(&record).map_err(io::Error::other).and_then(|line| {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", line)
+            });
+            if let Err(e) = written {
+                log::warn!("Failed to write guardian audit record: {}", e);
+                return;
+            }
+        }
+        self.remember(record);
+    }
+
+    /// Whether `assessment` differs from the last decision logged for its peer.
+    pub fn changed(&self, assessment: &ThreatAssessment) -> bool {
+        self.decided
+            .get(&assessment.peer_id)
+            .is_none_or(|(action, threats)| *action != assessment.recommended_action || *threats != assessment.detected_threats)
+    }
+
+    /// Log `assessment`, reached after `event`, with the guardian's `proof`
+    /// over that event.
+    pub fn record_decision(&mut self, assessment: &ThreatAssessment, event: NetworkEvent, proof: AuditProof, now: u64) {
+        let entry = AuditEntry::Decision {
+            event: Box::new(event),
+            proof,
+            trust_score: assessment.trust_score,
+            threats: assessment.detected_threats.clone(),
+            action: assessment.recommended_action.clone(),
+        };
+        self.append(&assessment.peer_id, entry, now);
+    }
+
+    /// Log the operations carried out on `peer_id` for `action`.
+    pub fn record_enforcement(&mut self, peer_id: &str, action: Option<&Action>, ops: &[Enforcement], now: u64) {
+        for enforcement in ops {
+            let entry = AuditEntry::Enforced { action: action.cloned(), enforcement: enforcement.clone() };
+            self.append(peer_id, entry, now);
+        }
+    }
+
+    /// Records in `[from, to)`, of `peer_id` if given, oldest first, at
+    /// most `limit` (capped at `MAX_AUDIT_QUERY`).
+    pub fn query(&self, peer_id: Option<&str>, from: u64, to: u64, limit: usize) -> Vec<AuditRecord> {
+        self.records
+            .iter()
+            .filter(|r| r.timestamp >= from && r.timestamp < to)
+            .filter(|r| peer_id.is_none_or(|peer| r.peer_id == peer))
+            .take(limit.min(MAX_AUDIT_QUERY))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(peer: &str, churn: f32) -> NetworkEvent {
+        NetworkEvent {
+            peer_id: peer.into(),
+            block_interval: 30.0,
+            block_size: 2.0,
+            tx_count: 4.0,
+            propagation_time: 120.0,
+            peer_count: 8.0,
+            fork_count: 0.0,
+            orphan_rate: 0.0,
+            reorg_depth: 0.0,
+            bandwidth_usage: 2.0,
+            connection_churn: churn,
+            timestamp: 100,
+            country: None,
+            asn: None,
+        }
+    }
+
+    fn assessment(peer: &str, action: Action) -> ThreatAssessment {
+        ThreatAssessment {
+            peer_id: peer.into(),
+            trust_score: 0.2,
+            detected_threats: vec![ThreatType::DoS],
+            confidence: 0.9,
+            recommended_action: action,
+        }
+    }
+
+    #[test]
+    fn test_decisions_chain_query_and_replay() {
+        let path = std::env::temp_dir().join(format!("axiom_guardian_audit_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let guardian = NeuralGuardian::new();
+        let mut log = DecisionAuditLog::load(&path).unwrap();
+
+        let rate_limit = assessment("a", Action::RateLimit);
+        assert!(log.changed(&rate_limit));
+        log.record_decision(&rate_limit, event("a", 1.0), guardian.audit_decision(&event("a", 1.0)), 100);
+        assert!(!log.changed(&rate_limit), "same decision is not logged twice");
+        log.record_enforcement("a", Some(&Action::RateLimit), &[Enforcement::GossipPenalty { points: 10 }], 100);
+        let ban = assessment("b", Action::BanPeer);
+        log.record_decision(&ban, event("b", 9.0), guardian.audit_decision(&event("b", 9.0)), 200);
+        log.record_enforcement("b", None, &[Enforcement::Unban], 300);
+
+        // Reloaded, the chain verifies and the dedup state survives.
+        let reloaded = DecisionAuditLog::load(&path).unwrap();
+        assert!(!reloaded.changed(&rate_limit));
+        assert_eq!(reloaded.query(Some("a"), 0, u64::MAX, 10).len(), 2);
+        assert_eq!(reloaded.query(None, 150, 300, 10).len(), 1);
+        assert_eq!(reloaded.query(None, 0, u64::MAX, 3).len(), 3);
+
+        let records = read_log(&path).unwrap();
+        assert_eq!(replay(&records, NeuralNetwork::new_genesis()), ReplayReport { verified: 2, mismatched: vec![], other_model: 0 });
+        assert_eq!(replay(&records, NeuralNetwork::from_seed([3u8; 32])).other_model, 2);
+
+        // A record edited after the fact breaks the chain.
+        let edited = std::fs::read_to_string(&path).unwrap().replace("\"timestamp\":200", "\"timestamp\":201");
+        std::fs::write(&path, edited).unwrap();
+        assert!(read_log(&path).is_err());
+        assert!(DecisionAuditLog::load(&path).is_err());
+        let _ = std::fs::remove_file(path);
+    }
+}
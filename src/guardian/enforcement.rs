@@ -0,0 +1,277 @@
+// src/guardian/enforcement.rs - Carrying out the guardian's recommendations
+//
+// A `ThreatAssessment` ends in a `recommended_action`; `GuardianEnforcer`
+// turns it into the swarm operations the node performs: disconnecting,
+// banning for a while (refusing reconnects and blacklisting from gossip),
+// charging the peer's gossip message score, capping its messages per
+// minute, or rotating outbound peers. A measure already in force is not
+// issued again, so a peer assessed on every message is not punished on
+// every message.
+//
+// Each operation, and each ban lifted (on expiry or pardon), is appended
+// to `axiom_guardian_enforcement.jsonl`. That log is also where bans and
+// limits in force are restored from after a restart.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::neural_guardian::{Action, ThreatAssessment};
+
+pub const GUARDIAN_ENFORCEMENT_PATH: &str = "axiom_guardian_enforcement.jsonl";
+
+/// How long a `BanPeer` recommendation keeps a peer out.
+pub const BAN_SECS: u64 = 24 * 3600;
+
+/// How long `LimitConnections` refuses a dropped peer's reconnects.
+pub const CONNECTION_COOLDOWN_SECS: u64 = 600;
+
+/// How long a `RateLimit` message cap lasts.
+pub const RATE_LIMIT_SECS: u64 = 600;
+
+/// Gossip messages per minute a rate-limited peer is served.
+pub const RATE_LIMITED_MESSAGES_PER_MINUTE: u32 = 5;
+
+/// Charged to the peer's gossip message score when it is rate limited.
+pub const RATE_LIMIT_PENALTY: u32 = 10;
+
+/// Least time between two peer rotations asked for by one peer's
+/// assessment.
+pub const ROTATION_REQUEST_SECS: u64 = 600;
+
+/// A swarm operation the node performs on a peer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Enforcement {
+    Disconnect,
+    /// Refuse the peer's connections and gossip until `until`.
+    Ban { until: u64 },
+    /// A ban ended, by expiry or pardon.
+    Unban,
+    /// Add `points` to the peer's gossip message count for the minute.
+    GossipPenalty { points: u32 },
+    /// Serve at most `per_minute` of the peer's gossip messages until `until`.
+    MessageLimit { per_minute: u32, until: u64 },
+    /// Drop outbound peers from the most crowded subnets.
+    RotatePeers,
+}
+
+/// One line of the enforcement log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnforcementRecord {
+    pub timestamp: u64,
+    pub peer_id: String,
+    /// The recommendation enforced; `None` for lifted bans.
+    pub action: Option<Action>,
+    #[serde(flatten)]
+    pub enforcement: Enforcement,
+}
+
+/// Measures in force against one peer.
+#[derive(Debug, Default, Clone, Copy)]
+struct Measures {
+    banned_until: u64,
+    limited_until: u64,
+    rotated_at: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct GuardianEnforcer {
+    path: Option<PathBuf>,
+    measures: HashMap<String, Measures>,
+    /// Peers whose ban ended since the last `expire`.
+    lifted: Vec<String>,
+}
+
+impl GuardianEnforcer {
+    /// An enforcer that logs nowhere (tests, tools).
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Restore the bans and limits recorded at `path` and log new
+    /// operations there. Malformed lines are skipped.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut enforcer = Self::in_memory();
+        if let Ok(file) = File::open(&path) {
+            for record in BufReader::new(file)
+                .lines()
+                .map_while(|line| line.ok())
+                .filter_map(|line| serde_json::from_str::<EnforcementRecord>(&line).ok())
+            {
+                enforcer.apply(&record);
+            }
+        }
+        enforcer.path = Some(path);
+        enforcer
+    }
+
+    fn apply(&mut self, record: &EnforcementRecord) {
+        let measures = self.measures.entry(record.peer_id.clone()).or_default();
+        match record.enforcement {
+            Enforcement::Ban { until } => measures.banned_until = until,
+            Enforcement::Unban => measures.banned_until = 0,
+            Enforcement::MessageLimit { until, .. } => measures.limited_until = until,
+            Enforcement::RotatePeers => measures.rotated_at = Some(record.timestamp),
+            Enforcement::Disconnect | Enforcement::GossipPenalty { .. } => {}
+        }
+    }
+
+    fn record(&mut self, peer_id: &str, action: Option<Action>, enforcement: Enforcement, now: u64) -> Enforcement {
+        let record = EnforcementRecord { timestamp: now, peer_id: peer_id.to_string(), action, enforcement };
+        self.apply(&record);
+        if let Some(path) = &self.path {
+            let written = serde_json::to_string(&record).map_err(io::Error::other).and_then(|line| {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                writeln!(file, "{}", line)
+            });
+            if let Err(e) = written {
+                log::warn!("Failed to log guardian enforcement: {}", e);
+            }
+        }
+        record.enforcement
+    }
+
+    /// The operations `assessment` calls for that are not already in force
+    /// at `now`. Each is logged before it is returned.
+    pub fn enforce(&mut self, assessment: &ThreatAssessment, now: u64) -> Vec<Enforcement> {
+        let peer = assessment.peer_id.as_str();
+        let action = assessment.recommended_action.clone();
+        let measures = self.measures.get(peer).copied().unwrap_or_default();
+        let banned = measures.banned_until > now;
+        let planned = match action {
+            Action::BanPeer if !banned => vec![Enforcement::Disconnect, Enforcement::Ban { until: now + BAN_SECS }],
+            Action::LimitConnections if !banned => {
+                vec![Enforcement::Disconnect, Enforcement::Ban { until: now + CONNECTION_COOLDOWN_SECS }]
+            }
+            Action::RateLimit if !banned && measures.limited_until <= now => vec![
+                Enforcement::GossipPenalty { points: RATE_LIMIT_PENALTY },
+                Enforcement::MessageLimit { per_minute: RATE_LIMITED_MESSAGES_PER_MINUTE, until: now + RATE_LIMIT_SECS },
+            ],
+            Action::DiversifyPeers if measures.rotated_at.is_none_or(|t| now >= t + ROTATION_REQUEST_SECS) => {
+                vec![Enforcement::RotatePeers]
+            }
+            // Monitoring is what the guardian does anyway, and every block's
+            // VDF is verified: nothing more to carry out.
+            _ => Vec::new(),
+        };
+        planned
+            .into_iter()
+            .map(|enforcement| self.record(peer, Some(action.clone()), enforcement, now))
+            .collect()
+    }
+
+    pub fn is_banned(&self, peer_id: &str, now: u64) -> bool {
+        self.measures.get(peer_id).is_some_and(|m| m.banned_until > now)
+    }
+
+    /// The per-minute message cap on `peer_id`, if one is in force.
+    pub fn message_limit(&self, peer_id: &str, now: u64) -> Option<u32> {
+        self.measures
+            .get(peer_id)
+            .is_some_and(|m| m.limited_until > now)
+            .then_some(RATE_LIMITED_MESSAGES_PER_MINUTE)
+    }
+
+    /// End `peer_id`'s ban and limits early (operator pardon). Returns
+    /// whether it was banned.
+    pub fn lift(&mut self, peer_id: &str, now: u64) -> bool {
+        let banned = self.is_banned(peer_id, now);
+        if banned {
+            self.record(peer_id, None, Enforcement::Unban, now);
+            self.lifted.push(peer_id.to_string());
+        }
+        if let Some(measures) = self.measures.get_mut(peer_id) {
+            measures.limited_until = 0;
+        }
+        banned
+    }
+
+    /// Log the bans that ran out by `now` and forget spent measures.
+    /// Returns the peers whose ban ended since the last call, to be let
+    /// back into gossip.
+    pub fn expire(&mut self, now: u64) -> Vec<String> {
+        let expired: Vec<String> = self
+            .measures
+            .iter()
+            .filter(|(_, m)| m.banned_until != 0 && m.banned_until <= now)
+            .map(|(peer, _)| peer.clone())
+            .collect();
+        for peer in expired {
+            self.record(&peer, None, Enforcement::Unban, now);
+            self.lifted.push(peer);
+        }
+        self.measures.retain(|_, m| {
+            m.banned_until > now || m.limited_until > now || m.rotated_at.is_some_and(|t| now < t + ROTATION_REQUEST_SECS)
+        });
+        std::mem::take(&mut self.lifted)
+    }
+
+    /// Peers banned at `now`.
+    pub fn banned(&self, now: u64) -> Vec<String> {
+        self.measures
+            .iter()
+            .filter(|(_, m)| m.banned_until > now)
+            .map(|(peer, _)| peer.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::neural_guardian::ThreatType;
+
+    fn assessment(peer: &str, action: Action) -> ThreatAssessment {
+        ThreatAssessment {
+            peer_id: peer.into(),
+            trust_score: 0.0,
+            detected_threats: vec![ThreatType::DoS],
+            confidence: 1.0,
+            recommended_action: action,
+        }
+    }
+
+    #[test]
+    fn test_actions_enforced_once_and_bans_expire() {
+        let path = std::env::temp_dir().join(format!("axiom_guardian_enforcement_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut enforcer = GuardianEnforcer::load(&path);
+
+        let ops = enforcer.enforce(&assessment("a", Action::RateLimit), 100);
+        assert_eq!(ops[0], Enforcement::GossipPenalty { points: RATE_LIMIT_PENALTY });
+        assert_eq!(enforcer.message_limit("a", 100), Some(RATE_LIMITED_MESSAGES_PER_MINUTE));
+        assert!(enforcer.enforce(&assessment("a", Action::RateLimit), 101).is_empty(), "limit already in force");
+
+        let ops = enforcer.enforce(&assessment("b", Action::BanPeer), 100);
+        assert_eq!(ops, vec![Enforcement::Disconnect, Enforcement::Ban { until: 100 + BAN_SECS }]);
+        assert!(enforcer.enforce(&assessment("b", Action::BanPeer), 200).is_empty());
+        assert!(enforcer.is_banned("b", 200));
+        assert!(enforcer.enforce(&assessment("c", Action::IncreaseMonitoring), 100).is_empty());
+
+        // Restored from the log after a restart.
+        let mut restored = GuardianEnforcer::load(&path);
+        assert!(restored.is_banned("b", 200));
+        assert_eq!(restored.message_limit("a", 200), Some(RATE_LIMITED_MESSAGES_PER_MINUTE));
+        assert_eq!(restored.banned(200), vec!["b".to_string()]);
+
+        assert!(restored.expire(100 + RATE_LIMIT_SECS).is_empty());
+        assert_eq!(restored.message_limit("a", 100 + RATE_LIMIT_SECS), None);
+        assert_eq!(restored.expire(100 + BAN_SECS), vec!["b".to_string()]);
+        assert!(!restored.is_banned("b", 100 + BAN_SECS));
+
+        // A pardon lifts a ban early.
+        restored.enforce(&assessment("d", Action::LimitConnections), 1000);
+        assert!(restored.lift("d", 1001));
+        assert!(!restored.is_banned("d", 1001));
+        assert_eq!(restored.expire(1002), vec!["d".to_string()]);
+
+        let unbans = std::fs::read_to_string(&path).unwrap().matches("\"op\":\"unban\"").count();
+        assert_eq!(unbans, 2);
+        let _ = std::fs::remove_file(path);
+    }
+}
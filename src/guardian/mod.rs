@@ -1,6 +1,22 @@
 // src/guardian/mod.rs
 // Guardian module - Immutable safety rules and verification
 
+pub mod alerts;
+pub mod bridge_guard;
+pub mod decision_audit;
+pub mod enforcement;
+pub mod model_checkpoint;
+pub mod model_release;
+pub mod safe_mode;
 pub mod safety_manifest;
+pub mod threat_report;
 
+pub use alerts::{Alert, AlertSeverity, AlertSink, Alerts};
+pub use bridge_guard::{BridgeGuard, BridgeLimits};
+pub use decision_audit::{AuditRecord, DecisionAuditLog};
+pub use enforcement::{Enforcement, GuardianEnforcer};
+pub use model_checkpoint::{ModelCheckpoints, ModelUpdateRecord};
+pub use model_release::{ModelAttestation, ReleasePolicy};
+pub use safe_mode::{SafeMode, SafeModePolicy, SafetyNotice};
 pub use safety_manifest::SovereignInvariants;
+pub use threat_report::{GuardianEventLog, SignedThreatReport, ThreatReport};
@@ -0,0 +1,233 @@
+// src/guardian/model_checkpoint.rs - Neural Guardian model checkpoints
+//
+// The guardian starts from the genesis weights and retrains on what the
+// node confirms (incidents, pardoned false positives), so the weights it runs
+// with drift away from anything on disk. `ModelCheckpoints` saves each
+// retrained model as `guardian-model-v<version>.bin` under
+// `GUARDIAN_MODELS_DIR` and appends a record to `update_log.json`: the
+// model hash, the loss and detection accuracy after training, and the hash
+// of the previous record, so an edited or truncated history is caught when
+// the log is opened.
+//
+// If an update makes detection worse, `--guardian-rollback <version>`
+// reinstalls an earlier checkpoint at startup. The rollback is itself
+// logged, and the node keeps running that model until it retrains again.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::neural_guardian::{NeuralGuardian, NeuralNetwork};
+
+pub const GUARDIAN_MODELS_DIR: &str = "axiom_guardian_models";
+
+const UPDATE_LOG_FILE: &str = "update_log.json";
+
+/// Seconds between retraining rounds, each followed by a checkpoint.
+pub const RETRAIN_INTERVAL_SECS: u64 = 6 * 3600;
+
+/// New labelled samples needed before a round retrains at all.
+pub const MIN_NEW_SAMPLES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ModelUpdateKind {
+    /// Trained on `samples` labelled samples to an average `loss`.
+    Retrained { samples: usize, loss: f32 },
+    /// Reinstated the model of checkpoint `to_version`.
+    RolledBack { to_version: u64 },
+}
+
+/// One entry of the update log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelUpdateRecord {
+    pub version: u64,
+    #[serde(flatten)]
+    pub kind: ModelUpdateKind,
+    /// `NeuralGuardian` weights hash of the model, hex.
+    pub model_hash: String,
+    /// Share of the training samples the model labels correctly.
+    pub accuracy: Option<f32>,
+    pub timestamp: u64,
+    /// Checkpoint file, relative to the models directory.
+    pub file: String,
+    /// `hash` of the previous record, hex; empty for the first.
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl ModelUpdateRecord {
+    fn compute_hash(&self) -> String {
+        let fields = (self.version, &self.kind, &self.model_hash, self.accuracy, self.timestamp, &self.file, &self.prev_hash);
+        let encoded = bincode::serialize(&fields).unwrap_or_default();
+        hex::encode(Sha256::digest(&encoded))
+    }
+}
+
+/// The checkpoints in a models directory and their update log.
+#[derive(Debug)]
+pub struct ModelCheckpoints {
+    dir: PathBuf,
+    log: Vec<ModelUpdateRecord>,
+}
+
+impl ModelCheckpoints {
+    /// Open `dir`, creating it if needed. Fails if the update log does not
+    /// parse or its hash chain is broken.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let log: Vec<ModelUpdateRecord> = match fs::read(dir.join(UPDATE_LOG_FILE)) {
+            Ok(data) => serde_json::from_slice(&data)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        let mut prev_hash = String::new();
+        for (i, record) in log.iter().enumerate() {
+            if record.version != i as u64 + 1 || record.prev_hash != prev_hash || record.hash != record.compute_hash() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("model update log broken at version {}", i + 1),
+                ));
+            }
+            prev_hash = record.hash.clone();
+        }
+        Ok(Self { dir, log })
+    }
+
+    pub fn log(&self) -> &[ModelUpdateRecord] {
+        &self.log
+    }
+
+    pub fn latest(&self) -> Option<&ModelUpdateRecord> {
+        self.log.last()
+    }
+
+    fn append(&mut self, kind: ModelUpdateKind, model_hash: String, accuracy: Option<f32>, file: String, now: u64) -> io::Result<&ModelUpdateRecord> {
+        let mut record = ModelUpdateRecord {
+            version: self.log.len() as u64 + 1,
+            kind,
+            model_hash,
+            accuracy,
+            timestamp: now,
+            file,
+            prev_hash: self.latest().map(|r| r.hash.clone()).unwrap_or_default(),
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash();
+        self.log.push(record);
+        let encoded = serde_json::to_vec_pretty(&self.log).map_err(io::Error::other)?;
+        if let Err(e) = crate::storage::write_atomic(self.dir.join(UPDATE_LOG_FILE), &encoded) {
+            self.log.pop();
+            return Err(e);
+        }
+        Ok(self.log.last().expect("just appended"))
+    }
+
+    /// Save `guardian`'s current model as the next version, retrained on
+    /// `samples` samples to `loss`.
+    pub fn checkpoint(&mut self, guardian: &NeuralGuardian, samples: usize, loss: f32, now: u64) -> io::Result<&ModelUpdateRecord> {
+        let version = self.log.len() as u64 + 1;
+        let file = format!("guardian-model-v{:06}.bin", version);
+        let encoded = bincode::serialize(guardian.model()).map_err(io::Error::other)?;
+        crate::storage::write_atomic(self.dir.join(&file), &encoded)?;
+        let model_hash = NeuralGuardian::hash_model_weights(guardian.model());
+        self.append(ModelUpdateKind::Retrained { samples, loss }, model_hash, guardian.detection_accuracy(), file, now)
+    }
+
+    /// The model of checkpoint `version`, checked against its record.
+    pub fn load(&self, version: u64) -> io::Result<NeuralNetwork> {
+        let record = version
+            .checked_sub(1)
+            .and_then(|i| self.log.get(i as usize))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no model checkpoint v{}", version)))?;
+        let model: NeuralNetwork = bincode::deserialize(&fs::read(self.dir.join(&record.file))?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if NeuralGuardian::hash_model_weights(&model) != record.model_hash {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("model checkpoint v{} does not match its logged hash", version),
+            ));
+        }
+        Ok(model)
+    }
+
+    /// The model the log ends with, if any.
+    pub fn load_latest(&self) -> io::Result<Option<NeuralNetwork>> {
+        self.latest().map(|r| self.load(r.version)).transpose()
+    }
+
+    /// Install checkpoint `version` in `guardian` and log the rollback.
+    pub fn rollback(&mut self, guardian: &mut NeuralGuardian, version: u64, now: u64) -> io::Result<&ModelUpdateRecord> {
+        let model = self.load(version)?;
+        let file = self.log[version as usize - 1].file.clone();
+        guardian.install_model(model);
+        let model_hash = NeuralGuardian::hash_model_weights(guardian.model());
+        self.append(ModelUpdateKind::RolledBack { to_version: version }, model_hash, guardian.detection_accuracy(), file, now)
+    }
+}
+
+/// The checkpoint version given with `--guardian-rollback <version>` (or
+/// `--guardian-rollback=<version>`), if any.
+pub fn rollback_from_args(args: impl IntoIterator<Item = String>) -> Result<Option<u64>, String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--guardian-rollback=") {
+            Some(value) => value.to_string(),
+            None if arg == "--guardian-rollback" => args.next().ok_or("--guardian-rollback needs a version")?,
+            None => continue,
+        };
+        return value
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("--guardian-rollback: '{}' is not a checkpoint version", value));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoints_chain_and_roll_back() {
+        let dir = std::env::temp_dir().join(format!("axiom_guardian_models_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut checkpoints = ModelCheckpoints::open(&dir).unwrap();
+        let mut guardian = NeuralGuardian::new();
+        let genesis_hash = guardian.get_stats().model_hash;
+
+        checkpoints.checkpoint(&guardian, 0, 0.0, 100).unwrap();
+        guardian.install_model(NeuralNetwork::new());
+        let second = checkpoints.checkpoint(&guardian, 5, 0.4, 200).unwrap().clone();
+        assert_eq!(second.file, "guardian-model-v000002.bin");
+        assert_eq!(second.prev_hash, checkpoints.log()[0].hash);
+        assert_ne!(second.model_hash, genesis_hash);
+
+        let rolled = checkpoints.rollback(&mut guardian, 1, 300).unwrap();
+        assert_eq!(rolled.kind, ModelUpdateKind::RolledBack { to_version: 1 });
+        assert_eq!(guardian.get_stats().model_hash, genesis_hash);
+
+        // Reopened, the log verifies and ends with the rolled-back model.
+        let reopened = ModelCheckpoints::open(&dir).unwrap();
+        assert_eq!(reopened.log().len(), 3);
+        let latest = reopened.load_latest().unwrap().unwrap();
+        assert_eq!(NeuralGuardian::hash_model_weights(&latest), genesis_hash);
+
+        // Editing history breaks the chain.
+        let path = dir.join(UPDATE_LOG_FILE);
+        let edited = fs::read_to_string(&path).unwrap().replace("\"loss\": 0.4", "\"loss\": 0.1");
+        fs::write(&path, edited).unwrap();
+        assert!(ModelCheckpoints::open(&dir).is_err());
+        assert!(reopened.load(4).is_err());
+
+        assert_eq!(rollback_from_args(["axiom".into(), "--guardian-rollback".into(), "2".into()]), Ok(Some(2)));
+        assert_eq!(rollback_from_args(["--guardian-rollback=7".to_string()]), Ok(Some(7)));
+        assert_eq!(rollback_from_args(["axiom".to_string()]), Ok(None));
+        assert!(rollback_from_args(["--guardian-rollback=latest".to_string()]).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
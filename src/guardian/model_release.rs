@@ -0,0 +1,220 @@
+// src/guardian/model_release.rs
+// Multi-party attestation of NeuralGuardian weight releases
+//
+// Hash pinning alone trusts whoever ships the constant. A release is
+// therefore accompanied by `<weights>.sig`, a JSON attestation carrying
+// Ed25519 signatures over the weights' SHA-256. Loading requires
+// signatures from at least `MODEL_RELEASE_THRESHOLD` distinct keys listed
+// in the safety manifest, so one compromised mirror or maintainer key is
+// not enough to swap the model.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::SovereignInvariants;
+use crate::error::AxiomError;
+
+/// Domain separator so a release signature cannot be replayed as any
+/// other Ed25519 signature made with the same key.
+const RELEASE_DOMAIN: &[u8] = b"AXIOM-MODEL-RELEASE-V1";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseSignature {
+    /// Hex Ed25519 public key of the signer.
+    pub signer: String,
+    /// Hex Ed25519 signature over `signing_message(weights_sha256)`.
+    pub signature: String,
+}
+
+impl ReleaseSignature {
+    pub fn sign(key: &SigningKey, message: &[u8]) -> Self {
+        Self {
+            signer: hex::encode(key.verifying_key().as_bytes()),
+            signature: hex::encode(key.sign(message).to_bytes()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelAttestation {
+    /// Hex SHA-256 of the weights file.
+    pub weights_sha256: String,
+    pub signatures: Vec<ReleaseSignature>,
+}
+
+/// Attestation file that accompanies `weights`: `weights.bin` -> `weights.bin.sig`.
+pub fn attestation_path(weights: &Path) -> PathBuf {
+    let mut name = weights.as_os_str().to_owned();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+fn signing_message(weights_sha256: &[u8; 32]) -> Vec<u8> {
+    [RELEASE_DOMAIN, weights_sha256.as_slice()].concat()
+}
+
+impl ModelAttestation {
+    /// Start an unsigned attestation for `weights`.
+    pub fn for_weights(weights: &[u8]) -> Self {
+        Self {
+            weights_sha256: hex::encode(Sha256::digest(weights)),
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Add a maintainer signature (release tooling).
+    pub fn sign(&mut self, key: &SigningKey) -> Result<(), AxiomError> {
+        let digest = decode_digest(&self.weights_sha256)?;
+        self.signatures.push(ReleaseSignature::sign(key, &signing_message(&digest)));
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AxiomError> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json)
+            .map_err(|e| AxiomError::SerializationError(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// Which keys may sign a release and how many must.
+#[derive(Debug, Clone)]
+pub struct ReleasePolicy {
+    signers: Vec<VerifyingKey>,
+    threshold: usize,
+}
+
+impl ReleasePolicy {
+    pub fn new(signers: Vec<VerifyingKey>, threshold: usize) -> Self {
+        Self { signers, threshold }
+    }
+
+    /// The policy embedded in the safety manifest. Malformed keys are
+    /// skipped, which can only make the threshold harder to reach.
+    pub fn manifest() -> Self {
+        let signers = SovereignInvariants::MODEL_RELEASE_SIGNERS
+            .iter()
+            .filter_map(|k| parse_key(k).ok())
+            .collect();
+        Self::new(signers, SovereignInvariants::MODEL_RELEASE_THRESHOLD)
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn is_signer(&self, key: &VerifyingKey) -> bool {
+        self.signers.contains(key)
+    }
+
+    /// Number of distinct policy keys with a valid signature over
+    /// `message` in `signatures`. Outsiders and bad entries are ignored.
+    pub fn valid_signers(&self, message: &[u8], signatures: &[ReleaseSignature]) -> usize {
+        let mut valid: HashSet<[u8; 32]> = HashSet::new();
+        for entry in signatures {
+            let Ok(key) = parse_key(&entry.signer) else { continue };
+            if !self.is_signer(&key) {
+                continue;
+            }
+            let Some(signature) = hex::decode(&entry.signature)
+                .ok()
+                .and_then(|b| <[u8; 64]>::try_from(b).ok())
+                .map(|b| Signature::from_bytes(&b))
+            else {
+                continue;
+            };
+            if key.verify(message, &signature).is_ok() {
+                valid.insert(key.to_bytes());
+            }
+        }
+        valid.len()
+    }
+
+    /// Check that `attestation` covers `weights` and carries valid
+    /// signatures from at least `threshold` distinct policy keys. Returns
+    /// the number of valid signers.
+    pub fn verify(&self, weights: &[u8], attestation: &ModelAttestation) -> Result<usize, AxiomError> {
+        let digest: [u8; 32] = Sha256::digest(weights).into();
+        if decode_digest(&attestation.weights_sha256)? != digest {
+            return Err(AxiomError::InvalidSignature(
+                "model attestation is for a different weights file".to_string(),
+            ));
+        }
+        let valid = self.valid_signers(&signing_message(&digest), &attestation.signatures);
+        if valid < self.threshold.max(1) {
+            return Err(AxiomError::InvalidSignature(format!(
+                "model release has {} of {} required maintainer signatures ({} keys in manifest)",
+                valid,
+                self.threshold,
+                self.signers.len()
+            )));
+        }
+        Ok(valid)
+    }
+}
+
+pub(crate) fn parse_key(hex_key: &str) -> Result<VerifyingKey, AxiomError> {
+    let bytes: [u8; 32] = hex::decode(hex_key)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| AxiomError::InvalidSignature(format!("bad release key {}", hex_key)))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| AxiomError::InvalidSignature(e.to_string()))
+}
+
+fn decode_digest(hex_digest: &str) -> Result<[u8; 32], AxiomError> {
+    hex::decode(hex_digest)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| AxiomError::InvalidSignature(format!("bad weights digest {}", hex_digest)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(n: u8) -> Vec<SigningKey> {
+        (1..=n).map(|i| SigningKey::from_bytes(&[i; 32])).collect()
+    }
+
+    fn policy(keys: &[SigningKey], threshold: usize) -> ReleasePolicy {
+        ReleasePolicy::new(keys.iter().map(|k| k.verifying_key()).collect(), threshold)
+    }
+
+    #[test]
+    fn test_threshold_of_distinct_manifest_keys() {
+        let maintainers = keys(5);
+        let policy = policy(&maintainers, 3);
+        let weights = b"weights";
+
+        let mut att = ModelAttestation::for_weights(weights);
+        att.sign(&maintainers[0]).unwrap();
+        att.sign(&maintainers[1]).unwrap();
+        // Duplicates and outsiders do not count towards the threshold.
+        att.sign(&maintainers[1]).unwrap();
+        att.sign(&SigningKey::from_bytes(&[99; 32])).unwrap();
+        assert!(policy.verify(weights, &att).is_err());
+
+        att.sign(&maintainers[4]).unwrap();
+        assert_eq!(policy.verify(weights, &att).unwrap(), 3);
+        assert!(policy.verify(b"tampered", &att).is_err());
+    }
+
+    #[test]
+    fn test_signature_for_other_digest_rejected() {
+        let maintainers = keys(2);
+        let mut att = ModelAttestation::for_weights(b"a");
+        att.sign(&maintainers[0]).unwrap();
+        att.sign(&maintainers[1]).unwrap();
+        // Re-point the attestation at another file: the signatures no longer match.
+        att.weights_sha256 = hex::encode(Sha256::digest(b"b"));
+        assert!(policy(&maintainers, 1).verify(b"b", &att).is_err());
+    }
+
+    #[test]
+    fn test_attestation_path() {
+        assert_eq!(attestation_path(Path::new("/x/weights.bin")), PathBuf::from("/x/weights.bin.sig"));
+    }
+}
@@ -0,0 +1,341 @@
+// src/guardian/safe_mode.rs - Emergency halt and resume
+//
+// When the guardian recommends `HaltChain` (or an operator calls
+// `admin_halt`) the node enters safe mode: it stops accepting and relaying
+// blocks and transactions and stops mining, but keeps serving read RPC so
+// operators and explorers can see what happened. The halt is announced to
+// peers as a `SignedHaltNotice` on `SAFETY_TOPIC`; peers follow it only if
+// the issuer is one of their configured safe-mode operators or a key in the
+// guardian manifest.
+//
+// Nothing resumes on its own. Leaving safe mode takes a
+// `ResumeAuthorization` for that particular halt, signed by one operator
+// key or by a quorum of guardian manifest keys (the same keys and threshold
+// that sign model releases). Authorizations are gossiped too, so one
+// operator can bring the whole network back.
+//
+// The state is saved to `axiom_safe_mode.json`: a restart does not lift a
+// halt, and a resumed halt's notice is not followed again if it is replayed.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::model_release::{parse_key, ReleasePolicy, ReleaseSignature};
+use crate::error::AxiomError;
+use crate::wallet::Wallet;
+
+pub const SAFE_MODE_PATH: &str = "axiom_safe_mode.json";
+
+/// Gossip topic carrying `SafetyNotice`s.
+pub const SAFETY_TOPIC: &str = "axiom/safety/halt/v1";
+
+/// Halt notices older than this are not followed.
+pub const HALT_NOTICE_MAX_AGE_SECS: u64 = 24 * 3600;
+
+/// Allowance for issuers whose clock runs ahead of ours.
+const MAX_CLOCK_SKEW_SECS: u64 = 600;
+
+/// Resumed halts remembered so their notices cannot be replayed.
+const MAX_RESUMED: usize = 64;
+
+const HALT_DOMAIN: &[u8] = b"axiom-halt-notice-v1";
+const RESUME_DOMAIN: &[u8] = b"axiom-resume-v1";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HaltNotice {
+    /// Hex Ed25519 public key of the node or operator halting.
+    pub issuer: String,
+    /// Chain height when the halt was called.
+    pub height: u64,
+    pub reason: String,
+    pub issued_at: u64,
+}
+
+impl HaltNotice {
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = HALT_DOMAIN.to_vec();
+        bytes.extend(serde_json::to_vec(self).unwrap_or_default());
+        bytes
+    }
+
+    /// A notice issued by `signer`'s key.
+    pub fn new(signer: &Wallet, height: u64, reason: impl Into<String>, issued_at: u64) -> Self {
+        Self { issuer: hex::encode(signer.address), height, reason: reason.into(), issued_at }
+    }
+
+    pub fn sign(self, signer: &Wallet) -> SignedHaltNotice {
+        let signature = hex::encode(signer.sign_message(&self.signing_bytes()));
+        SignedHaltNotice { notice: self, signature }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedHaltNotice {
+    pub notice: HaltNotice,
+    /// Hex Ed25519 signature by `issuer` over the notice.
+    pub signature: String,
+}
+
+impl SignedHaltNotice {
+    /// Hex SHA-256 of the signed notice; what a resume authorization names.
+    pub fn id(&self) -> String {
+        hex::encode(Sha256::digest(self.notice.signing_bytes()))
+    }
+
+    fn issuer(&self) -> Option<VerifyingKey> {
+        parse_key(&self.notice.issuer).ok()
+    }
+
+    pub fn verify(&self) -> bool {
+        let signature: Option<[u8; 64]> = hex::decode(&self.signature).ok().and_then(|b| b.try_into().ok());
+        let (Some(key), Some(signature)) = (self.issuer(), signature) else {
+            return false;
+        };
+        key.verify_strict(&self.notice.signing_bytes(), &ed25519_dalek::Signature::from_bytes(&signature))
+            .is_ok()
+    }
+}
+
+/// Message an operator or manifest key signs to lift the halt `halt_id`.
+pub fn resume_message(halt_id: &str) -> Vec<u8> {
+    [RESUME_DOMAIN, halt_id.as_bytes()].concat()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResumeAuthorization {
+    pub halt_id: String,
+    /// Signatures over `resume_message(halt_id)`.
+    pub signatures: Vec<ReleaseSignature>,
+}
+
+impl ResumeAuthorization {
+    pub fn new(halt_id: impl Into<String>) -> Self {
+        Self { halt_id: halt_id.into(), signatures: Vec::new() }
+    }
+
+    /// Add a signature (operator tooling).
+    pub fn sign(&mut self, key: &SigningKey) {
+        self.signatures.push(ReleaseSignature::sign(key, &resume_message(&self.halt_id)));
+    }
+}
+
+/// Payload of `SAFETY_TOPIC` messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SafetyNotice {
+    Halt(SignedHaltNotice),
+    Resume(ResumeAuthorization),
+}
+
+/// Who may halt the network and who may resume it.
+#[derive(Debug, Clone)]
+pub struct SafeModePolicy {
+    /// Any one operator signature resumes.
+    operators: ReleasePolicy,
+    /// Or the manifest's quorum.
+    manifest: ReleasePolicy,
+}
+
+impl SafeModePolicy {
+    pub fn new(operators: Vec<VerifyingKey>, manifest: ReleasePolicy) -> Self {
+        Self { operators: ReleasePolicy::new(operators, 1), manifest }
+    }
+
+    /// Operators from `[ai] safe_mode_operators` (hex keys, checked by
+    /// config validation) and the guardian manifest.
+    pub fn from_config(operators: &[String]) -> Self {
+        Self::new(operators.iter().filter_map(|k| parse_key(k).ok()).collect(), ReleasePolicy::manifest())
+    }
+
+    fn may_halt(&self, issuer: &VerifyingKey) -> bool {
+        self.operators.is_signer(issuer) || self.manifest.is_signer(issuer)
+    }
+
+    fn check_resume(&self, auth: &ResumeAuthorization) -> Result<(), AxiomError> {
+        let message = resume_message(&auth.halt_id);
+        if self.operators.valid_signers(&message, &auth.signatures) >= 1 {
+            return Ok(());
+        }
+        let manifest = self.manifest.valid_signers(&message, &auth.signatures);
+        if manifest >= self.manifest.threshold().max(1) {
+            return Ok(());
+        }
+        Err(AxiomError::InvalidSignature(format!(
+            "resume needs an operator signature or {} guardian manifest signatures, got {}",
+            self.manifest.threshold(),
+            manifest
+        )))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Halt {
+    pub id: String,
+    pub notice: SignedHaltNotice,
+    /// When this node entered safe mode.
+    pub since: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SafeModeState {
+    halt: Option<Halt>,
+    resumed: VecDeque<String>,
+}
+
+pub struct SafeMode {
+    path: Option<PathBuf>,
+    policy: SafeModePolicy,
+    state: SafeModeState,
+}
+
+impl SafeMode {
+    /// Safe mode that is not saved anywhere (tests, tools).
+    pub fn in_memory(policy: SafeModePolicy) -> Self {
+        Self { path: None, policy, state: SafeModeState::default() }
+    }
+
+    /// Restore the state at `path`. An unreadable file is an error rather
+    /// than a silent resume.
+    pub fn load(path: impl AsRef<Path>, policy: SafeModePolicy) -> Result<Self, AxiomError> {
+        let path = path.as_ref().to_path_buf();
+        let state = match std::fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data)
+                .map_err(|e| AxiomError::DeserializationError(format!("{}: {}", path.display(), e)))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => SafeModeState::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self { path: Some(path), policy, state })
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        let written = serde_json::to_vec_pretty(&self.state)
+            .map_err(std::io::Error::other)
+            .and_then(|data| crate::storage::write_atomic(path, &data));
+        if let Err(e) = written {
+            log::warn!("Failed to save safe mode state: {}", e);
+        }
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.state.halt.is_some()
+    }
+
+    pub fn halt(&self) -> Option<&Halt> {
+        self.state.halt.as_ref()
+    }
+
+    /// Halt on this node's own notice. Returns false if already halted.
+    pub fn enter(&mut self, notice: SignedHaltNotice, now: u64) -> bool {
+        if self.is_halted() {
+            return false;
+        }
+        self.state.halt = Some(Halt { id: notice.id(), notice, since: now });
+        self.save();
+        true
+    }
+
+    /// Halt on a peer's notice if it is signed by an operator or manifest
+    /// key, recent, and not for a halt already resumed. Returns false if
+    /// already halted.
+    pub fn observe_halt(&mut self, notice: SignedHaltNotice, now: u64) -> Result<bool, AxiomError> {
+        if !notice.verify() {
+            return Err(AxiomError::InvalidSignature("halt notice signature".to_string()));
+        }
+        if !notice.issuer().is_some_and(|issuer| self.policy.may_halt(&issuer)) {
+            return Err(AxiomError::Unauthorized);
+        }
+        let issued_at = notice.notice.issued_at;
+        if issued_at > now + MAX_CLOCK_SKEW_SECS || now.saturating_sub(issued_at) > HALT_NOTICE_MAX_AGE_SECS {
+            return Err(AxiomError::InvalidSignature(format!("halt notice issued at {} is stale", issued_at)));
+        }
+        if self.state.resumed.contains(&notice.id()) {
+            return Ok(false);
+        }
+        Ok(self.enter(notice, now))
+    }
+
+    /// Lift the current halt. Returns the halt lifted.
+    pub fn resume(&mut self, auth: &ResumeAuthorization) -> Result<Halt, AxiomError> {
+        let Some(halt) = &self.state.halt else {
+            return Err(AxiomError::NotFound("the chain is not halted".to_string()));
+        };
+        if halt.id != auth.halt_id {
+            return Err(AxiomError::NotFound(format!("halt {} is not the current halt", auth.halt_id)));
+        }
+        self.policy.check_resume(auth)?;
+
+        let halt = self.state.halt.take().expect("checked above");
+        if self.state.resumed.len() >= MAX_RESUMED {
+            self.state.resumed.pop_front();
+        }
+        self.state.resumed.push_back(halt.id.clone());
+        self.save();
+        Ok(halt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(i: u8) -> SigningKey {
+        SigningKey::from_bytes(&[i; 32])
+    }
+
+    fn wallet(key: &SigningKey) -> Wallet {
+        Wallet { secret_key: key.to_bytes(), address: key.verifying_key().to_bytes() }
+    }
+
+    fn notice(issuer: &SigningKey, issued_at: u64) -> SignedHaltNotice {
+        let wallet = wallet(issuer);
+        HaltNotice::new(&wallet, 42, "supply invariant", issued_at).sign(&wallet)
+    }
+
+    #[test]
+    fn test_halt_and_resume() {
+        let (operator, stranger) = (key(1), key(9));
+        let manifest = ReleasePolicy::new((2..=4).map(|i| key(i).verifying_key()).collect(), 2);
+        let mut safe_mode = SafeMode::in_memory(SafeModePolicy::new(vec![operator.verifying_key()], manifest));
+        let now = 1_000_000;
+
+        // Only operators and manifest keys halt the node, with fresh notices.
+        assert!(matches!(safe_mode.observe_halt(notice(&stranger, now), now), Err(AxiomError::Unauthorized)));
+        let mut forged = notice(&operator, now);
+        forged.notice.reason = "something else".into();
+        assert!(safe_mode.observe_halt(forged, now).is_err());
+        assert!(safe_mode.observe_halt(notice(&operator, now - HALT_NOTICE_MAX_AGE_SECS - 1), now).is_err());
+        assert!(!safe_mode.is_halted());
+
+        let halt = notice(&key(3), now);
+        assert!(safe_mode.observe_halt(halt.clone(), now).unwrap());
+        assert!(!safe_mode.enter(notice(&operator, now), now));
+        assert_eq!(safe_mode.halt().unwrap().notice, halt);
+
+        // Resuming takes one operator or two manifest keys, for this halt.
+        let mut auth = ResumeAuthorization::new(halt.id());
+        auth.sign(&stranger);
+        auth.sign(&key(2));
+        assert!(safe_mode.resume(&auth).is_err());
+        let mut other = ResumeAuthorization::new(notice(&operator, now).id());
+        other.sign(&operator);
+        assert!(safe_mode.resume(&other).is_err());
+        auth.sign(&key(4));
+        assert_eq!(safe_mode.resume(&auth).unwrap().id, halt.id());
+        assert!(!safe_mode.is_halted());
+        assert!(safe_mode.resume(&auth).is_err());
+
+        // A replayed notice for the resumed halt is ignored.
+        assert!(!safe_mode.observe_halt(halt, now + 60).unwrap());
+
+        let mut operator_auth = ResumeAuthorization::new(String::new());
+        let local = notice(&stranger, now);
+        assert!(safe_mode.enter(local.clone(), now));
+        operator_auth.halt_id = local.id();
+        operator_auth.sign(&operator);
+        assert!(safe_mode.resume(&operator_auth).is_ok());
+    }
+}
@@ -71,6 +71,16 @@ impl SovereignInvariants {
     /// No deprecated algorithms in new transactions
     pub const DEPRECATED_SHA256D: &str = "SHA256d_DISABLED_POST_QUANTUM_ERA";
 
+    // ==================== MODEL RELEASE SIGNING ====================
+    /// Ed25519 public keys (hex) of the maintainers who sign NeuralGuardian
+    /// weight releases. Populated with the release keys when the first
+    /// signed weights are published; while empty, no weights file can be
+    /// attested and `NeuralGuardian::load_model` refuses all of them.
+    pub const MODEL_RELEASE_SIGNERS: &'static [&'static str] = &[];
+
+    /// Distinct maintainer signatures required on a weights release.
+    pub const MODEL_RELEASE_THRESHOLD: usize = 3;
+
     // ==================== SUPPLY VERIFICATION ====================
     /// Verify transaction amount doesn't exceed protocol supply cap
     pub fn verify_supply_integrity(current_supply: u64) -> Result<(), AxiomError> {
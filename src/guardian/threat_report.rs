@@ -0,0 +1,320 @@
+// src/guardian/threat_report.rs - Guardian event history and signed reports
+//
+// The Neural Guardian only keeps its current view of each peer. For
+// transparency updates operators need history: what was detected, when,
+// from which networks, how many peers were banned and how many of those
+// bans were later corrected. `GuardianEventLog` records those events as
+// they happen (one JSON object per line in `axiom_guardian_events.jsonl`)
+// and aggregates any time range into a `ThreatReport`, signed with the
+// node's wallet key so a published report can be attributed to the node.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::neural_guardian::{Action, ThreatAssessment, ThreatType};
+use crate::transaction::Address;
+use crate::wallet::Wallet;
+
+pub const GUARDIAN_EVENTS_PATH: &str = "axiom_guardian_events.jsonl";
+
+/// Events kept in memory for reporting; older ones stay in the file only.
+pub const MAX_EVENTS_IN_MEMORY: usize = 100_000;
+
+/// Subnets listed in a report's `top_subnets`.
+pub const TOP_SUBNETS: usize = 10;
+
+const REPORT_DOMAIN: &[u8] = b"axiom-threat-report-v1";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GuardianEventKind {
+    /// The guardian newly attributed `threat` to the peer.
+    Threat { threat: ThreatType },
+    /// The peer's assessment reached `BanPeer`.
+    Ban,
+    /// An operator cleared the peer's assessment as a false positive.
+    FalsePositiveCorrected,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuardianEvent {
+    pub timestamp: u64,
+    pub peer_id: String,
+    /// Network group of the peer's address (see `network::diversity::Subnet`),
+    /// if the node knew one.
+    pub subnet: Option<String>,
+    #[serde(flatten)]
+    pub kind: GuardianEventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubnetCount {
+    pub subnet: String,
+    pub events: u64,
+}
+
+/// Aggregate of the guardian events in `[from, to)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreatReport {
+    /// Hex public key of the reporting node's wallet.
+    pub node: String,
+    pub from: u64,
+    pub to: u64,
+    pub generated_at: u64,
+    pub total_events: u64,
+    pub threats_by_type: BTreeMap<String, u64>,
+    /// Subnets with the most threat and ban events, most first.
+    pub top_subnets: Vec<SubnetCount>,
+    pub bans_issued: u64,
+    pub false_positive_corrections: u64,
+}
+
+impl ThreatReport {
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = REPORT_DOMAIN.to_vec();
+        // Field order is fixed by the struct and maps are sorted, so the
+        // encoding is canonical.
+        bytes.extend(serde_json::to_vec(self).unwrap_or_default());
+        bytes
+    }
+
+    pub fn sign(self, signer: &Wallet) -> SignedThreatReport {
+        let signature = hex::encode(signer.sign_message(&self.signing_bytes()));
+        SignedThreatReport { report: self, signature }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedThreatReport {
+    #[serde(flatten)]
+    pub report: ThreatReport,
+    /// Hex ed25519 signature by `node` over the report.
+    pub signature: String,
+}
+
+impl SignedThreatReport {
+    /// Check the signature against the report's `node` key.
+    pub fn verify(&self) -> bool {
+        let key: Option<Address> = hex::decode(&self.report.node).ok().and_then(|b| b.try_into().ok());
+        let signature: Option<[u8; 64]> = hex::decode(&self.signature).ok().and_then(|b| b.try_into().ok());
+        let (Some(key), Some(signature)) = (key, signature) else {
+            return false;
+        };
+        ed25519_dalek::VerifyingKey::from_bytes(&key)
+            .and_then(|key| key.verify_strict(&self.report.signing_bytes(), &ed25519_dalek::Signature::from_bytes(&signature)))
+            .is_ok()
+    }
+}
+
+#[derive(Debug)]
+pub struct GuardianEventLog {
+    path: Option<PathBuf>,
+    events: VecDeque<GuardianEvent>,
+    /// Threats already reported per peer and whether a ban was, so repeated
+    /// assessments of the same peer are not counted again.
+    reported: HashMap<String, (HashSet<String>, bool)>,
+}
+
+impl GuardianEventLog {
+    /// A log that is not written anywhere (tests, tools).
+    pub fn in_memory() -> Self {
+        Self { path: None, events: VecDeque::new(), reported: HashMap::new() }
+    }
+
+    /// Load the history at `path` and append new events to it. Malformed
+    /// lines are skipped.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut log = Self::in_memory();
+        if let Ok(file) = File::open(&path) {
+            for event in BufReader::new(file)
+                .lines()
+                .map_while(|line| line.ok())
+                .filter_map(|line| serde_json::from_str::<GuardianEvent>(&line).ok())
+            {
+                log.remember(&event);
+                log.push(event);
+            }
+        }
+        log.path = Some(path);
+        log
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Record whatever is new in `assessment` since the peer was last seen.
+    pub fn observe(&mut self, assessment: &ThreatAssessment, subnet: Option<String>, now: u64) {
+        let (threats, banned) = self.reported.entry(assessment.peer_id.clone()).or_default();
+        let mut new = Vec::new();
+        for threat in &assessment.detected_threats {
+            if *threat != ThreatType::Benign && threats.insert(format!("{:?}", threat)) {
+                new.push(GuardianEventKind::Threat { threat: threat.clone() });
+            }
+        }
+        if assessment.recommended_action == Action::BanPeer && !*banned {
+            *banned = true;
+            new.push(GuardianEventKind::Ban);
+        }
+        for kind in new {
+            self.record(GuardianEvent { timestamp: now, peer_id: assessment.peer_id.clone(), subnet: subnet.clone(), kind });
+        }
+    }
+
+    /// Record that an operator cleared `peer_id`'s assessment. Later
+    /// detections of the same peer count again.
+    pub fn correct_false_positive(&mut self, peer_id: &str, subnet: Option<String>, now: u64) {
+        self.reported.remove(peer_id);
+        self.record(GuardianEvent {
+            timestamp: now,
+            peer_id: peer_id.to_string(),
+            subnet,
+            kind: GuardianEventKind::FalsePositiveCorrected,
+        });
+    }
+
+    fn record(&mut self, event: GuardianEvent) {
+        if let Some(path) = &self.path {
+            if let Err(e) = append(path, &event) {
+                log::warn!("GUARDIAN: Could not append to {}: {}", path.display(), e);
+            }
+        }
+        self.push(event);
+    }
+
+    fn push(&mut self, event: GuardianEvent) {
+        if self.events.len() == MAX_EVENTS_IN_MEMORY {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+
+    fn remember(&mut self, event: &GuardianEvent) {
+        match &event.kind {
+            GuardianEventKind::Threat { threat } => {
+                self.reported.entry(event.peer_id.clone()).or_default().0.insert(format!("{:?}", threat));
+            }
+            GuardianEventKind::Ban => self.reported.entry(event.peer_id.clone()).or_default().1 = true,
+            GuardianEventKind::FalsePositiveCorrected => {
+                self.reported.remove(&event.peer_id);
+            }
+        }
+    }
+
+    /// Aggregate the events in `[from, to)`.
+    pub fn report(&self, node: &Address, from: u64, to: u64, now: u64) -> ThreatReport {
+        let mut report = ThreatReport {
+            node: hex::encode(node),
+            from,
+            to,
+            generated_at: now,
+            total_events: 0,
+            threats_by_type: BTreeMap::new(),
+            top_subnets: Vec::new(),
+            bans_issued: 0,
+            false_positive_corrections: 0,
+        };
+        let mut subnets: HashMap<&str, u64> = HashMap::new();
+        for event in self.events.iter().filter(|e| (from..to).contains(&e.timestamp)) {
+            report.total_events += 1;
+            match &event.kind {
+                GuardianEventKind::Threat { threat } => {
+                    *report.threats_by_type.entry(format!("{:?}", threat)).or_default() += 1;
+                }
+                GuardianEventKind::Ban => report.bans_issued += 1,
+                GuardianEventKind::FalsePositiveCorrected => {
+                    report.false_positive_corrections += 1;
+                    continue;
+                }
+            }
+            if let Some(subnet) = &event.subnet {
+                *subnets.entry(subnet).or_default() += 1;
+            }
+        }
+        let mut subnets: Vec<SubnetCount> = subnets
+            .into_iter()
+            .map(|(subnet, events)| SubnetCount { subnet: subnet.to_string(), events })
+            .collect();
+        subnets.sort_by(|a, b| b.events.cmp(&a.events).then_with(|| a.subnet.cmp(&b.subnet)));
+        subnets.truncate(TOP_SUBNETS);
+        report.top_subnets = subnets;
+        report
+    }
+}
+
+fn append(path: &Path, event: &GuardianEvent) -> io::Result<()> {
+    let line = serde_json::to_string(event)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assessment(peer: &str, threats: Vec<ThreatType>, action: Action) -> ThreatAssessment {
+        ThreatAssessment {
+            peer_id: peer.to_string(),
+            trust_score: 0.0,
+            detected_threats: threats,
+            confidence: 1.0,
+            recommended_action: action,
+        }
+    }
+
+    #[test]
+    fn test_report_aggregates_range() {
+        let mut log = GuardianEventLog::in_memory();
+        let subnet = Some("10.1.0.0/16".to_string());
+        log.observe(&assessment("a", vec![ThreatType::DoS], Action::RateLimit), subnet.clone(), 100);
+        // The same assessment again is not a new event.
+        log.observe(&assessment("a", vec![ThreatType::DoS], Action::RateLimit), subnet.clone(), 110);
+        log.observe(&assessment("b", vec![ThreatType::ForgedMessage], Action::BanPeer), subnet, 120);
+        log.observe(&assessment("c", vec![ThreatType::SybilAttack], Action::BanPeer), Some("192.168.0.0/16".into()), 130);
+        log.correct_false_positive("c", None, 140);
+        log.observe(&assessment("d", vec![ThreatType::DoS], Action::RateLimit), None, 500);
+
+        let report = log.report(&[0u8; 32], 100, 200, 1_000);
+        assert_eq!(report.total_events, 6);
+        assert_eq!(report.threats_by_type.get("DoS"), Some(&1));
+        assert_eq!(report.threats_by_type.get("ForgedMessage"), Some(&1));
+        assert_eq!(report.bans_issued, 2);
+        assert_eq!(report.false_positive_corrections, 1);
+        assert_eq!(report.top_subnets[0], SubnetCount { subnet: "10.1.0.0/16".into(), events: 3 });
+    }
+
+    #[test]
+    fn test_signed_report_verifies() {
+        let signer = crate::payout::payout_wallet(&Wallet { secret_key: [5u8; 32], address: [0u8; 32] }, 0);
+        let log = GuardianEventLog::in_memory();
+        let mut signed = log.report(&signer.address, 0, 10, 10).sign(&signer);
+        assert!(signed.verify());
+
+        signed.report.bans_issued = 1;
+        assert!(!signed.verify());
+    }
+
+    #[test]
+    fn test_history_survives_restart() {
+        let path = std::env::temp_dir().join(format!("axiom_test_guardian_events_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut log = GuardianEventLog::load(&path);
+        log.observe(&assessment("a", vec![ThreatType::DoS], Action::BanPeer), None, 50);
+
+        let mut reloaded = GuardianEventLog::load(&path);
+        assert_eq!(reloaded.len(), 2);
+        // Already reported before the restart.
+        reloaded.observe(&assessment("a", vec![ThreatType::DoS], Action::BanPeer), None, 60);
+        assert_eq!(reloaded.len(), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+}
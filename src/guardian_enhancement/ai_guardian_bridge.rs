@@ -3,10 +3,12 @@
 // CRITICAL: All AI decisions require Guardian verification
 
 use crate::guardian::SovereignInvariants;
+use crate::guardian::alerts::{Alert, AlertSeverity, Alerts};
 use crate::ai_core::{
     MultiLayerSecurityEngine, ThreatAssessment, SecurityAction, RiskLevel, TransactionRiskProfile,
 };
 use crate::error::AxiomError;
+use super::consensus_proposals::{ApprovalSource, ProposalAnnouncement, ProposalQueue, QueuedProposal};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use parking_lot::RwLock;
@@ -17,6 +19,10 @@ pub struct AIGuardianBridge {
     guardian_state: Arc<RwLock<GuardianState>>,
     consensus_ai: Arc<RwLock<ConsensusAIController>>,
     emergency_circuit_breaker: Arc<RwLock<CircuitBreaker>>,
+    /// Optimizer suggestions awaiting approval and activation.
+    proposals: Arc<RwLock<ProposalQueue>>,
+    /// Where escalations and chain halts are reported.
+    alerts: Alerts,
 }
 
 #[derive(Debug, Clone)]
@@ -145,9 +151,23 @@ impl AIGuardianBridge {
                 reason: None,
                 auto_recovery_block: None,
             })),
+            proposals: Arc::new(RwLock::new(ProposalQueue::in_memory())),
+            alerts: Alerts::default(),
         }
     }
 
+    /// Report escalations and chain halts to `alerts`.
+    pub fn with_alerts(mut self, alerts: Alerts) -> Self {
+        self.alerts = alerts;
+        self
+    }
+
+    /// Keep consensus proposals in `queue` (e.g. one loaded from disk).
+    pub fn with_proposal_queue(mut self, queue: ProposalQueue) -> Self {
+        self.proposals = Arc::new(RwLock::new(queue));
+        self
+    }
+
     /// Validate transaction with AI + Guardian oversight
     pub fn validate_transaction_with_guardian(
         &self,
@@ -241,12 +261,32 @@ impl AIGuardianBridge {
             }
             SecurityAction::Reject { reason: _ } => GuardianAction::Reject,
             SecurityAction::EscalateToGuardian { threat_level } => {
+                let severity = if *threat_level >= RiskLevel::Critical { AlertSeverity::Critical } else { AlertSeverity::Warning };
+                self.alerts.raise(Alert::new(
+                    format!("guardian_escalation:{:?}", threat_level),
+                    severity,
+                    "ai_guardian_bridge",
+                    format!("Transaction escalated for manual review ({:?} risk)", threat_level),
+                    escalation_details(ai_assessment, profile),
+                ));
                 GuardianAction::RequireManualReview {
                     threat_level: *threat_level,
                 }
             }
             SecurityAction::HaltChain { emergency_level } => {
-                if *emergency_level >= 9 {
+                let halting = *emergency_level >= 9;
+                self.alerts.raise(Alert::new(
+                    "chain_halt",
+                    AlertSeverity::Critical,
+                    "ai_guardian_bridge",
+                    if halting {
+                        format!("Circuit breaker tripped: chain halt at emergency level {}", emergency_level)
+                    } else {
+                        format!("Chain halt requested at emergency level {}, escalated for review", emergency_level)
+                    },
+                    escalation_details(ai_assessment, profile),
+                ));
+                if halting {
                     self.activate_circuit_breaker(
                         0,
                         "AI detected critical chain-level threat".to_string(),
@@ -345,10 +385,12 @@ impl AIGuardianBridge {
         Ok(proposal)
     }
 
-    /// Apply consensus optimization (Guardian-verified)
-    pub fn apply_consensus_optimization(
+    /// Record an optimizer suggestion for approval. Nothing changes until
+    /// it is approved and its activation height is reached.
+    pub fn submit_consensus_optimization(
         &self,
-        proposal: &ConsensusOptimizationProposal,
+        proposal: ConsensusOptimizationProposal,
+        now: u64,
     ) -> Result<(), AxiomError> {
         if !proposal.guardian_pre_approved {
             return Err(AxiomError::AIProposalRejected {
@@ -362,17 +404,63 @@ impl AIGuardianBridge {
             });
         }
 
-        let mut consensus = self.consensus_ai.write();
-        consensus.current_difficulty = proposal.proposed_difficulty;
-        consensus.current_vdf_iterations = proposal.proposed_vdf;
-        consensus.current_min_gas = proposal.proposed_min_gas;
+        self.proposals.write().record(proposal, None, now)
+    }
 
-        log::info!("🤖 Applied AI consensus optimization:");
-        log::info!("   Difficulty: {} → {} ({:+.2}%)", proposal.current_difficulty, proposal.proposed_difficulty, proposal.difficulty_change_percent);
-        log::info!("   VDF: {} → {} ({:+.2}%)", proposal.current_vdf, proposal.proposed_vdf, proposal.vdf_change_percent);
-        log::info!("   Min Gas: {} → {} ({:+.2}%)", proposal.current_min_gas, proposal.proposed_min_gas, proposal.gas_change_percent);
+    /// Approve proposal `id` on a chain `height` blocks long. Returns the
+    /// announcement to gossip: the change and the height it activates at.
+    pub fn approve_consensus_optimization(
+        &self,
+        id: &str,
+        source: ApprovalSource,
+        height: u64,
+    ) -> Result<ProposalAnnouncement, AxiomError> {
+        self.proposals.write().approve(id, source, height)
+    }
 
-        Ok(())
+    pub fn reject_consensus_optimization(&self, id: &str, reason: String) -> Result<(), AxiomError> {
+        self.proposals.write().reject(id, reason)
+    }
+
+    /// Record a peer's gossiped announcement for the operator to approve.
+    pub fn observe_consensus_announcement(
+        &self,
+        announcement: ProposalAnnouncement,
+        height: u64,
+        now: u64,
+    ) -> Result<(), AxiomError> {
+        self.proposals.write().observe_announcement(announcement, height, now)
+    }
+
+    pub fn consensus_proposals(&self) -> Vec<QueuedProposal> {
+        self.proposals.read().proposals().to_vec()
+    }
+
+    /// Apply the approved changes due on a chain `height` blocks long.
+    /// A change made against parameters that have since moved is skipped.
+    /// Returns the ids of the proposals applied.
+    pub fn activate_consensus_optimizations(&self, height: u64) -> Vec<String> {
+        let due = self.proposals.write().take_due(height);
+        let mut consensus = self.consensus_ai.write();
+        let mut applied = Vec::new();
+        for proposal in due {
+            if (proposal.current_difficulty, proposal.current_vdf, proposal.current_min_gas)
+                != (consensus.current_difficulty, consensus.current_vdf_iterations, consensus.current_min_gas)
+            {
+                log::warn!("🤖 Skipped stale consensus proposal {}: parameters changed since it was made", proposal.proposal_id);
+                continue;
+            }
+            consensus.current_difficulty = proposal.proposed_difficulty;
+            consensus.current_vdf_iterations = proposal.proposed_vdf;
+            consensus.current_min_gas = proposal.proposed_min_gas;
+
+            log::info!("🤖 Activated AI consensus optimization {} at height {}:", proposal.proposal_id, height);
+            log::info!("   Difficulty: {} → {} ({:+.2}%)", proposal.current_difficulty, proposal.proposed_difficulty, proposal.difficulty_change_percent);
+            log::info!("   VDF: {} → {} ({:+.2}%)", proposal.current_vdf, proposal.proposed_vdf, proposal.vdf_change_percent);
+            log::info!("   Min Gas: {} → {} ({:+.2}%)", proposal.current_min_gas, proposal.proposed_min_gas, proposal.gas_change_percent);
+            applied.push(proposal.proposal_id);
+        }
+        applied
     }
 
     fn calculate_change_percent(old: u64, new: u64) -> f64 {
@@ -465,6 +553,21 @@ pub struct GuardianStats {
     pub last_veto_reason: Option<String>,
 }
 
+/// Alert body for a guardian escalation about the transaction in `profile`.
+fn escalation_details(assessment: &ThreatAssessment, profile: &TransactionRiskProfile) -> String {
+    format!(
+        "Transaction {} from {} to {} ({} units)\nThreat score {:.1}, confidence {:.2}, threats {:?}\n{}",
+        profile.hash,
+        profile.sender,
+        profile.recipient,
+        profile.amount,
+        assessment.threat_score,
+        assessment.confidence,
+        assessment.identified_threats,
+        assessment.detailed_analysis
+    )
+}
+
 // ==================== CONSENSUS AI CONTROLLER ====================
 
 impl ConsensusAIController {
@@ -0,0 +1,286 @@
+// src/guardian_enhancement/consensus_proposals.rs - Gated consensus parameter changes
+//
+// The consensus optimizer only suggests. A `ConsensusOptimizationProposal`
+// is recorded here after the Guardian manifest's bounds checks, and waits
+// for approval: by the operator, or by miners signalling for it on chain.
+// Approval does not change anything at once; it schedules the change for a
+// block at least `ACTIVATION_DELAY_BLOCKS` ahead and yields a
+// `ProposalAnnouncement` for gossip, so peers learn of the change before it
+// takes effect. A peer's announcement is recorded as a pending proposal
+// with its announced height, and activates here only if the local operator
+// approves it too.
+//
+// The queue is written to `axiom_consensus_proposals.json` on every change.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::ai_guardian_bridge::ConsensusOptimizationProposal;
+use crate::error::AxiomError;
+use crate::guardian::SovereignInvariants;
+
+pub const CONSENSUS_PROPOSALS_PATH: &str = "axiom_consensus_proposals.json";
+
+/// Least notice, in blocks, between approving a change and its activation.
+pub const ACTIVATION_DELAY_BLOCKS: u64 = 144;
+
+/// Share of the signalling window's blocks that must signal for a proposal
+/// for on-chain approval.
+pub const SIGNAL_THRESHOLD_PERCENT: u64 = 75;
+
+/// Proposals kept; the oldest settled ones are dropped beyond this.
+pub const MAX_PROPOSALS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "by", rename_all = "snake_case")]
+pub enum ApprovalSource {
+    Operator,
+    /// `signalling` of the last `window` blocks signalled for the proposal.
+    OnChainSignal { signalling: u64, window: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending {
+        /// Height a peer announced for the change, if it came over gossip.
+        announced_height: Option<u64>,
+    },
+    Approved { source: ApprovalSource, activation_height: u64 },
+    Rejected { reason: String },
+    Activated { height: u64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedProposal {
+    pub proposal: ConsensusOptimizationProposal,
+    pub status: ProposalStatus,
+    pub recorded_at: u64,
+}
+
+/// An approved change and the height it activates at, as gossiped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalAnnouncement {
+    pub proposal: ConsensusOptimizationProposal,
+    pub activation_height: u64,
+}
+
+fn rejected(reason: impl Into<String>) -> AxiomError {
+    AxiomError::AIProposalRejected { reason: reason.into() }
+}
+
+/// Check `proposal`'s changes against the Guardian manifest's bounds.
+pub fn check_bounds(proposal: &ConsensusOptimizationProposal) -> Result<(), AxiomError> {
+    SovereignInvariants::verify_ai_difficulty_proposal(proposal.current_difficulty, proposal.proposed_difficulty)?;
+    SovereignInvariants::verify_ai_vdf_proposal(proposal.current_vdf, proposal.proposed_vdf)?;
+    SovereignInvariants::verify_ai_gas_proposal(proposal.current_min_gas, proposal.proposed_min_gas)
+}
+
+#[derive(Debug, Default)]
+pub struct ProposalQueue {
+    path: Option<PathBuf>,
+    proposals: Vec<QueuedProposal>,
+}
+
+impl ProposalQueue {
+    /// A queue that is not written anywhere (tests, tools).
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Load the queue at `path`, or start an empty one there. An
+    /// unreadable file is logged and ignored.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let proposals = match std::fs::read(&path) {
+            Ok(data) => serde_json::from_slice(&data).unwrap_or_else(|e| {
+                log::warn!("Ignoring unreadable consensus proposals {}: {}", path.display(), e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { path: Some(path), proposals }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        let written = serde_json::to_vec_pretty(&self.proposals)
+            .map_err(std::io::Error::other)
+            .and_then(|data| crate::storage::write_atomic(path, &data));
+        if let Err(e) = written {
+            log::warn!("Failed to save consensus proposals: {}", e);
+        }
+    }
+
+    pub fn proposals(&self) -> &[QueuedProposal] {
+        &self.proposals
+    }
+
+    pub fn get(&self, id: &str) -> Option<&QueuedProposal> {
+        self.proposals.iter().find(|q| q.proposal.proposal_id == id)
+    }
+
+    fn get_mut(&mut self, id: &str) -> Result<&mut QueuedProposal, AxiomError> {
+        self.proposals
+            .iter_mut()
+            .find(|q| q.proposal.proposal_id == id)
+            .ok_or_else(|| rejected(format!("no consensus proposal '{}'", id)))
+    }
+
+    fn push(&mut self, queued: QueuedProposal) {
+        if self.proposals.len() >= MAX_PROPOSALS {
+            if let Some(i) = self
+                .proposals
+                .iter()
+                .position(|q| matches!(q.status, ProposalStatus::Rejected { .. } | ProposalStatus::Activated { .. }))
+            {
+                self.proposals.remove(i);
+            }
+        }
+        self.proposals.push(queued);
+        self.save();
+    }
+
+    /// Record a suggestion. One outside the manifest's bounds is recorded
+    /// as rejected and the bounds error returned.
+    pub fn record(&mut self, proposal: ConsensusOptimizationProposal, announced_height: Option<u64>, now: u64) -> Result<(), AxiomError> {
+        if self.get(&proposal.proposal_id).is_some() {
+            return Err(rejected(format!("consensus proposal '{}' already recorded", proposal.proposal_id)));
+        }
+        let checked = check_bounds(&proposal);
+        let status = match &checked {
+            Ok(()) => ProposalStatus::Pending { announced_height },
+            Err(e) => ProposalStatus::Rejected { reason: e.to_string() },
+        };
+        self.push(QueuedProposal { proposal, status, recorded_at: now });
+        checked
+    }
+
+    /// Approve pending proposal `id` on a chain `height` blocks long. It
+    /// activates at the height a peer announced for it, if that still gives
+    /// `ACTIVATION_DELAY_BLOCKS` notice, or that many blocks from now.
+    pub fn approve(&mut self, id: &str, source: ApprovalSource, height: u64) -> Result<ProposalAnnouncement, AxiomError> {
+        if let ApprovalSource::OnChainSignal { signalling, window } = source {
+            if window == 0 || signalling * 100 < window * SIGNAL_THRESHOLD_PERCENT {
+                return Err(rejected(format!(
+                    "{} of {} blocks signalled, {}% needed",
+                    signalling, window, SIGNAL_THRESHOLD_PERCENT
+                )));
+            }
+        }
+        let queued = self.get_mut(id)?;
+        let ProposalStatus::Pending { announced_height } = queued.status else {
+            return Err(rejected(format!("consensus proposal '{}' is not pending", id)));
+        };
+        let earliest = height + ACTIVATION_DELAY_BLOCKS;
+        let activation_height = announced_height.filter(|h| *h >= earliest).unwrap_or(earliest);
+        queued.status = ProposalStatus::Approved { source, activation_height };
+        let announcement = ProposalAnnouncement { proposal: queued.proposal.clone(), activation_height };
+        self.save();
+        Ok(announcement)
+    }
+
+    pub fn reject(&mut self, id: &str, reason: String) -> Result<(), AxiomError> {
+        let queued = self.get_mut(id)?;
+        if matches!(queued.status, ProposalStatus::Activated { .. }) {
+            return Err(rejected(format!("consensus proposal '{}' already activated", id)));
+        }
+        queued.status = ProposalStatus::Rejected { reason };
+        self.save();
+        Ok(())
+    }
+
+    /// Record a peer's announcement, on a chain `height` blocks long, for
+    /// the operator to approve. Late or out-of-bounds announcements are
+    /// refused; one for a proposal already known changes nothing.
+    pub fn observe_announcement(&mut self, announcement: ProposalAnnouncement, height: u64, now: u64) -> Result<(), AxiomError> {
+        if announcement.activation_height <= height {
+            return Err(rejected(format!(
+                "announced activation height {} already passed",
+                announcement.activation_height
+            )));
+        }
+        if self.get(&announcement.proposal.proposal_id).is_some() {
+            return Ok(());
+        }
+        self.record(announcement.proposal, Some(announcement.activation_height), now)
+    }
+
+    /// The approved proposals due on a chain `height` blocks long, marked
+    /// activated.
+    pub fn take_due(&mut self, height: u64) -> Vec<ConsensusOptimizationProposal> {
+        let mut due = Vec::new();
+        for queued in &mut self.proposals {
+            if let ProposalStatus::Approved { activation_height, .. } = queued.status {
+                if activation_height <= height {
+                    queued.status = ProposalStatus::Activated { height };
+                    due.push(queued.proposal.clone());
+                }
+            }
+        }
+        if !due.is_empty() {
+            self.save();
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(id: &str, difficulty: u64) -> ConsensusOptimizationProposal {
+        ConsensusOptimizationProposal {
+            proposal_id: id.to_string(),
+            block_height: 1_000,
+            timestamp: 0,
+            current_difficulty: 1_000_000,
+            proposed_difficulty: difficulty,
+            difficulty_change_percent: 0.0,
+            current_vdf: SovereignInvariants::MINIMUM_VDF_ITERATIONS,
+            proposed_vdf: SovereignInvariants::MINIMUM_VDF_ITERATIONS,
+            vdf_change_percent: 0.0,
+            current_min_gas: 1_000,
+            proposed_min_gas: 1_000,
+            gas_change_percent: 0.0,
+            avg_block_time_last_144: 1_800.0,
+            hashrate_trend: 0.0,
+            mempool_congestion: 0.0,
+            network_health_score: 1.0,
+            ai_confidence: 0.9,
+            expected_improvement: 0.1,
+            guardian_pre_approved: true,
+            requires_voting: false,
+        }
+    }
+
+    #[test]
+    fn test_proposals_activate_only_after_approval_and_notice() {
+        let mut queue = ProposalQueue::in_memory();
+        assert!(queue.record(proposal("wild", 2_000_000), None, 0).is_err());
+        assert!(matches!(queue.get("wild").unwrap().status, ProposalStatus::Rejected { .. }));
+        assert!(queue.approve("wild", ApprovalSource::Operator, 1_000).is_err());
+
+        queue.record(proposal("p1", 1_040_000), None, 0).unwrap();
+        assert!(queue.take_due(u64::MAX).is_empty(), "pending proposals never activate");
+        let weak = ApprovalSource::OnChainSignal { signalling: 70, window: 100 };
+        assert!(queue.approve("p1", weak, 1_000).is_err());
+        let announcement = queue.approve("p1", ApprovalSource::OnChainSignal { signalling: 75, window: 100 }, 1_000).unwrap();
+        assert_eq!(announcement.activation_height, 1_000 + ACTIVATION_DELAY_BLOCKS);
+        assert!(queue.take_due(1_000 + ACTIVATION_DELAY_BLOCKS - 1).is_empty());
+        assert_eq!(queue.take_due(1_000 + ACTIVATION_DELAY_BLOCKS).len(), 1);
+        assert!(queue.take_due(u64::MAX).is_empty(), "activated once");
+
+        // A peer's announcement waits for the operator, then keeps its height.
+        let mut peer = ProposalQueue::in_memory();
+        let late = ProposalAnnouncement { proposal: proposal("p2", 990_000), activation_height: 900 };
+        assert!(peer.observe_announcement(late, 1_000, 0).is_err());
+        let announced = ProposalAnnouncement { proposal: proposal("p2", 990_000), activation_height: 1_500 };
+        peer.observe_announcement(announced, 1_000, 0).unwrap();
+        assert!(peer.take_due(2_000).is_empty());
+        assert_eq!(peer.approve("p2", ApprovalSource::Operator, 1_100).unwrap().activation_height, 1_500);
+        peer.reject("p2", "operator changed their mind".into()).unwrap();
+        assert!(peer.take_due(2_000).is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 // Guardian Enhancement module - AI-Guardian integration for consensus optimization
 
 pub mod ai_guardian_bridge;
+pub mod consensus_proposals;
 
 pub use ai_guardian_bridge::{
     AIGuardianBridge,
@@ -11,3 +12,4 @@ pub use ai_guardian_bridge::{
     GuardianAction,
     GuardianStats,
 };
+pub use consensus_proposals::{ApprovalSource, ProposalAnnouncement, ProposalQueue, ProposalStatus};
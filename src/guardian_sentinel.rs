@@ -10,6 +10,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use log;
 use chrono::Local;
 
+use crate::guardian::alerts::{Alert, AlertSeverity, Alerts};
+
 /// Sentinel operating modes
 #[derive(Clone, Debug, PartialEq)]
 pub enum SentinelMode {
@@ -42,6 +44,9 @@ pub struct SovereignGuardian {
     
     /// Guardian start time for session logging
     session_start: std::time::Instant,
+
+    /// Where failed checks are reported
+    alerts: Alerts,
 }
 
 impl SovereignGuardian {
@@ -54,8 +59,15 @@ impl SovereignGuardian {
             deep_sleep_threshold: Duration::from_secs(3600),
             last_activity: std::time::Instant::now(),
             session_start: std::time::Instant::now(),
+            alerts: Alerts::default(),
         }
     }
+
+    /// Report failed checks to `alerts`
+    pub fn with_alerts(mut self, alerts: Alerts) -> Self {
+        self.alerts = alerts;
+        self
+    }
     
     /// The eternal watch - this function never returns unless explicitly shut down
     /// 
@@ -87,7 +99,7 @@ impl SovereignGuardian {
                     // Determine mode based on idle time
                     if idle_duration < self.deep_sleep_threshold {
                         self.mode = SentinelMode::Active;
-                        self.emit_active_heartbeat(&idle_duration)?;
+                        self.emit_active_heartbeat(&idle_duration).inspect_err(|e| self.raise_alert(e))?;
                     } else {
                         self.mode = SentinelMode::DeepSleep;
                     }
@@ -101,7 +113,7 @@ impl SovereignGuardian {
                         self.emit_deep_sleep_heartbeat(&idle_duration).await?;
                         
                         // Even in deep sleep, verify critical invariants
-                        self.verify_sovereign_guarantees().await?;
+                        self.verify_sovereign_guarantees().await.inspect_err(|e| self.raise_alert(e))?;
                     }
                 }
                 
@@ -242,6 +254,22 @@ impl SovereignGuardian {
         Ok(())
     }
     
+    /// A failed check stops the sentinel; make sure someone hears of it
+    fn raise_alert(&self, error: &GuardianError) {
+        let key = match error {
+            GuardianError::Shutdown => return,
+            GuardianError::VerificationFailed(_) => "sentinel_verification",
+            GuardianError::ChainIntegrityError(_) => "sentinel_chain_integrity",
+        };
+        self.alerts.raise(Alert::new(
+            key,
+            AlertSeverity::Critical,
+            "guardian_sentinel",
+            format!("Sentinel stopped: {}", error),
+            format!("Mode {:?}, session uptime {:?}", self.mode, self.session_start.elapsed()),
+        ));
+    }
+
     /// Wait for shutdown signal
     async fn wait_for_shutdown(&self) {
         loop {
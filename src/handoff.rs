@@ -0,0 +1,189 @@
+// src/handoff.rs - Upgrade handoff between node processes
+//
+// Restarting a node for an upgrade normally costs a whole VDF window: the
+// new process starts its mining timer from zero, forgets its mempool and
+// has to rediscover peers. Instead the old process can hand over:
+//
+//   1. the new binary starts with `AXIOM_TAKEOVER=1`, reads the running
+//      node's PID from `axiom_node.pid` and sends it SIGUSR2 (operators can
+//      also send the signal themselves);
+//   2. the old node stops mining, flushes the chain and peerstore, writes
+//      `axiom_handoff.dat` (mempool, connected peer addresses, VDF timer)
+//      and exits, releasing its ports;
+//   3. the new node binds, consumes the handoff, redials the peers,
+//      re-admits the mempool and continues the VDF window where it was.
+//
+// Handoffs older than `HANDOFF_MAX_AGE_SECS` are ignored: by then the
+// mempool and timer are too stale to be worth restoring.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::transaction::Transaction;
+
+pub const HANDOFF_FILE: &str = "axiom_handoff.dat";
+pub const PID_FILE: &str = "axiom_node.pid";
+
+/// Handoff file layout version.
+pub const HANDOFF_FORMAT: u32 = 1;
+
+pub const HANDOFF_MAX_AGE_SECS: u64 = 120;
+
+/// How long a taking-over node waits for the old one to exit.
+pub const TAKEOVER_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum HandoffError {
+    #[error("handoff I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("handoff file is malformed: {0}")]
+    Malformed(String),
+    #[error("handoff format {0} is not supported")]
+    UnsupportedFormat(u32),
+    #[error("handoff is {age}s old")]
+    Stale { age: u64 },
+    #[error("no running node to take over from")]
+    NoRunningNode,
+    #[error("node {pid} did not hand off within {secs}s")]
+    TimedOut { pid: u32, secs: u64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Handoff {
+    pub format: u32,
+    /// Version of the binary that wrote the handoff.
+    pub from_version: String,
+    /// Unix time the handoff was written.
+    pub written_at: u64,
+    /// Chain length at handoff, for logging; the chain itself is on disk.
+    pub chain_height: u64,
+    pub mempool: Vec<Transaction>,
+    /// Dialable addresses of the peers connected at handoff.
+    pub peers: Vec<String>,
+    /// Unix time the current VDF window started.
+    pub vdf_started_at: u64,
+}
+
+impl Handoff {
+    pub fn new(chain_height: u64, mempool: Vec<Transaction>, peers: Vec<String>, vdf_started_at: u64, now: u64) -> Self {
+        Self {
+            format: HANDOFF_FORMAT,
+            from_version: env!("CARGO_PKG_VERSION").to_string(),
+            written_at: now,
+            chain_height,
+            mempool,
+            peers,
+            vdf_started_at,
+        }
+    }
+
+    /// Write atomically, so a taker never reads a half-written file.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), HandoffError> {
+        let path = path.as_ref();
+        let encoded = bincode::serialize(self).map_err(|e| HandoffError::Malformed(e.to_string()))?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, encoded)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read and delete the handoff at `path`. `Ok(None)` if there is none.
+    /// The file is removed even when it is rejected, so a bad handoff is
+    /// only ever reported once.
+    pub fn take(path: impl AsRef<Path>, now: u64) -> Result<Option<Self>, HandoffError> {
+        let path = path.as_ref();
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        fs::remove_file(path)?;
+        let handoff: Handoff = bincode::deserialize(&data).map_err(|e| HandoffError::Malformed(e.to_string()))?;
+        if handoff.format != HANDOFF_FORMAT {
+            return Err(HandoffError::UnsupportedFormat(handoff.format));
+        }
+        let age = now.saturating_sub(handoff.written_at);
+        if age > HANDOFF_MAX_AGE_SECS {
+            return Err(HandoffError::Stale { age });
+        }
+        Ok(Some(handoff))
+    }
+
+    /// Time already spent in the VDF window, as of `now`.
+    pub fn vdf_elapsed(&self, now: u64) -> Duration {
+        Duration::from_secs(now.saturating_sub(self.vdf_started_at))
+    }
+}
+
+/// Record this process as the running node.
+pub fn write_pid_file(path: impl AsRef<Path>) -> io::Result<()> {
+    fs::write(path, std::process::id().to_string())
+}
+
+pub fn read_pid_file(path: impl AsRef<Path>) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Ask the node recorded in `pid_path` to hand off, then wait until it has
+/// written `handoff_path` and exited. Returns the old node's PID.
+#[cfg(unix)]
+pub fn request_takeover(pid_path: impl AsRef<Path>, handoff_path: impl AsRef<Path>, timeout: Duration) -> Result<u32, HandoffError> {
+    let pid = read_pid_file(&pid_path).ok_or(HandoffError::NoRunningNode)?;
+    if pid == std::process::id() || !send_signal("-0", pid) {
+        return Err(HandoffError::NoRunningNode);
+    }
+    if !send_signal("-USR2", pid) {
+        return Err(HandoffError::NoRunningNode);
+    }
+    let deadline = std::time::Instant::now() + timeout;
+    while std::time::Instant::now() < deadline {
+        if handoff_path.as_ref().exists() && !send_signal("-0", pid) {
+            return Ok(pid);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Err(HandoffError::TimedOut { pid, secs: timeout.as_secs() })
+}
+
+#[cfg(unix)]
+fn send_signal(signal: &str, pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .arg(signal)
+        .arg(pid.to_string())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handoff_round_trip_is_consumed() {
+        let path = std::env::temp_dir().join(format!("axiom_test_handoff_{}.dat", std::process::id()));
+        let handoff = Handoff::new(42, Vec::new(), vec!["/ip4/10.0.0.1/tcp/6000".into()], 1_000, 1_500);
+        handoff.write(&path).unwrap();
+
+        let taken = Handoff::take(&path, 1_510).unwrap().unwrap();
+        assert_eq!(taken, handoff);
+        assert_eq!(taken.vdf_elapsed(1_510), Duration::from_secs(510));
+        assert!(Handoff::take(&path, 1_510).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stale_handoff_rejected() {
+        let path = std::env::temp_dir().join(format!("axiom_test_handoff_stale_{}.dat", std::process::id()));
+        Handoff::new(1, Vec::new(), Vec::new(), 0, 100).write(&path).unwrap();
+        assert!(matches!(
+            Handoff::take(&path, 100 + HANDOFF_MAX_AGE_SECS + 1),
+            Err(HandoffError::Stale { .. })
+        ));
+        assert!(!path.exists());
+    }
+}
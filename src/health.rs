@@ -0,0 +1,108 @@
+// src/health.rs - Liveness and readiness for orchestrators
+//
+// `/healthz` answers whether the process should be restarted: it fails
+// only when the main event loop has stopped making progress. `/readyz`
+// answers whether the node should receive traffic: it fails while the
+// node is syncing, has fewer than `rpc.ready_min_peers` peers, or cannot
+// write to its storage directory. Both are cheap enough to poll every few
+// seconds and are exempt from API rate limiting.
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::Serialize;
+
+/// The event loop is considered stuck after this long without a beat.
+pub const LIVENESS_STALL_SECS: u64 = 120;
+
+/// A node this close to the best known height counts as synced.
+pub const SYNC_TOLERANCE_BLOCKS: u64 = 2;
+
+/// Last time (unix seconds) the main loop reported progress.
+#[derive(Debug, Default)]
+pub struct Heartbeat(AtomicU64);
+
+impl Heartbeat {
+    pub fn new(now: u64) -> Self {
+        Self(AtomicU64::new(now))
+    }
+
+    pub fn beat(&self, now: u64) {
+        self.0.store(now, Ordering::Relaxed);
+    }
+
+    pub fn last(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn alive(&self, now: u64) -> bool {
+        now.saturating_sub(self.last()) < LIVENESS_STALL_SECS
+    }
+}
+
+/// Outcome of a readiness probe, returned as the `/readyz` body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub synced: bool,
+    pub height: u64,
+    pub network_height: u64,
+    pub peers: usize,
+    pub min_peers: usize,
+    pub storage_writable: bool,
+}
+
+impl Readiness {
+    pub fn evaluate(height: u64, network_height: u64, syncing: bool, peers: usize, min_peers: usize, storage_writable: bool) -> Self {
+        let synced = !syncing && height + SYNC_TOLERANCE_BLOCKS >= network_height;
+        Self {
+            ready: synced && peers >= min_peers && storage_writable,
+            synced,
+            height,
+            network_height,
+            peers,
+            min_peers,
+            storage_writable,
+        }
+    }
+}
+
+/// Whether a file can be created, written and removed in `dir`.
+pub fn storage_writable(dir: &Path) -> bool {
+    let probe = dir.join(".axiom_ready_probe");
+    let written = fs::write(&probe, b"ok").is_ok();
+    let _ = fs::remove_file(&probe);
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readiness() {
+        let ready = Readiness::evaluate(100, 101, false, 3, 1, true);
+        assert!(ready.ready && ready.synced);
+
+        // Each failing check is enough on its own.
+        assert!(!Readiness::evaluate(100, 101, true, 3, 1, true).ready);
+        assert!(!Readiness::evaluate(90, 101, false, 3, 1, true).synced);
+        assert!(!Readiness::evaluate(100, 101, false, 0, 1, true).ready);
+        assert!(!Readiness::evaluate(100, 101, false, 3, 1, false).ready);
+        // A bootstrap node that is alone on the network can be ready.
+        assert!(Readiness::evaluate(100, 100, false, 0, 0, true).ready);
+    }
+
+    #[test]
+    fn test_liveness_and_storage_probe() {
+        let heartbeat = Heartbeat::new(1_000);
+        assert!(heartbeat.alive(1_000 + LIVENESS_STALL_SECS - 1));
+        assert!(!heartbeat.alive(1_000 + LIVENESS_STALL_SECS));
+        heartbeat.beat(2_000);
+        assert!(heartbeat.alive(2_000 + LIVENESS_STALL_SECS - 1));
+
+        assert!(storage_writable(&std::env::temp_dir()));
+        assert!(!storage_writable(Path::new("/nonexistent/axiom")));
+    }
+}
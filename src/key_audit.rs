@@ -0,0 +1,315 @@
+// src/key_audit.rs - Append-only record of what the wallet key signed
+//
+// Every signature made with the wallet key is appended to
+// `wallet_audit.log` next to wallet.dat: when, through which interface,
+// what kind of object, its hash, and for transactions the amount and
+// destination. Each entry commits to the one before it, so after a
+// suspected host compromise the log shows what was signed and when, and
+// `verify` shows whether anyone edited or truncated it in between.
+//
+// The log is one JSON object per line. It never holds key material.
+
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::transaction::{Address, Transaction};
+
+/// Audit log kept next to wallet.dat.
+pub const AUDIT_LOG_FILE: &str = "wallet_audit.log";
+
+/// `prev` of the first entry.
+pub const AUDIT_CHAIN_START: [u8; 32] = [0u8; 32];
+
+/// Where a signing request came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningInterface {
+    /// The `axiom-wallet` command line.
+    Cli,
+    /// The node itself, e.g. block pulses and published state deltas.
+    Node,
+    /// An HTTP API request served by the node.
+    Rpc,
+}
+
+impl fmt::Display for SigningInterface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SigningInterface::Cli => "cli",
+            SigningInterface::Node => "node",
+            SigningInterface::Rpc => "rpc",
+        })
+    }
+}
+
+/// What was signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedKind {
+    Transaction,
+    BlockPulse,
+    StateDelta,
+    ThreatReport,
+    ThreatFeed,
+    HaltNotice,
+}
+
+/// One signing operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 0.
+    pub seq: u64,
+    pub timestamp: u64,
+    pub interface: SigningInterface,
+    pub kind: SignedKind,
+    /// Hex address of the signing key.
+    pub signer: String,
+    /// Hex hash identifying what was signed: the transaction hash for
+    /// transactions, the block hash for pulses, the content hash otherwise.
+    pub digest: String,
+    /// Transactions only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    /// Hex `hash` of the previous entry.
+    pub prev: String,
+    /// Hex BLAKE3 over `prev` and every field above.
+    pub hash: String,
+}
+
+impl AuditEntry {
+    fn compute_hash(&self) -> [u8; 32] {
+        let body = (
+            self.seq,
+            self.timestamp,
+            self.interface,
+            self.kind,
+            &self.signer,
+            &self.digest,
+            self.amount,
+            &self.destination,
+            &self.prev,
+        );
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"axiom-key-audit-v1");
+        hasher.update(&bincode::serialize(&body).unwrap_or_default());
+        *hasher.finalize().as_bytes()
+    }
+}
+
+/// What is about to be appended, before it is chained.
+#[derive(Debug, Clone)]
+pub struct SigningRecord {
+    pub interface: SigningInterface,
+    pub kind: SignedKind,
+    pub signer: Address,
+    pub digest: [u8; 32],
+    pub amount: Option<u64>,
+    pub destination: Option<Address>,
+}
+
+impl SigningRecord {
+    /// A signed transaction.
+    pub fn transaction(interface: SigningInterface, tx: &Transaction) -> Self {
+        Self {
+            interface,
+            kind: SignedKind::Transaction,
+            signer: tx.from,
+            digest: tx.legacy_hash(),
+            amount: Some(tx.amount),
+            destination: Some(tx.to),
+        }
+    }
+
+    /// Any other signed object, identified by `digest`.
+    pub fn object(interface: SigningInterface, kind: SignedKind, signer: Address, digest: [u8; 32]) -> Self {
+        Self { interface, kind, signer, digest, amount: None, destination: None }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("audit log I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("audit log line {line} is not a valid entry: {reason}")]
+    Malformed { line: usize, reason: String },
+    #[error("audit log broken at entry {seq}: {reason}")]
+    Broken { seq: u64, reason: String },
+}
+
+/// The wallet's audit log, open for appending.
+#[derive(Debug)]
+pub struct KeyAuditLog {
+    path: PathBuf,
+    next_seq: u64,
+    last_hash: [u8; 32],
+}
+
+impl KeyAuditLog {
+    /// Open the log at `path`, creating it if missing. The existing chain
+    /// is verified first; a log that fails verification is not appended to.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, AuditError> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match fs::File::open(&path) {
+            Ok(_) => verify(&path)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let (next_seq, last_hash) = match entries.last() {
+            Some(last) => (last.seq + 1, decode_hash(&last.hash).unwrap_or(AUDIT_CHAIN_START)),
+            None => (0, AUDIT_CHAIN_START),
+        };
+        Ok(Self { path, next_seq, last_hash })
+    }
+
+    /// The log next to wallet.dat in the working directory.
+    pub fn open_default() -> Result<Self, AuditError> {
+        Self::open(AUDIT_LOG_FILE)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Entries written so far.
+    pub fn len(&self) -> u64 {
+        self.next_seq
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next_seq == 0
+    }
+
+    /// Append `record` at time `now` and flush it to disk.
+    pub fn record(&mut self, record: SigningRecord, now: u64) -> Result<AuditEntry, AuditError> {
+        let mut entry = AuditEntry {
+            seq: self.next_seq,
+            timestamp: now,
+            interface: record.interface,
+            kind: record.kind,
+            signer: hex::encode(record.signer),
+            digest: hex::encode(record.digest),
+            amount: record.amount,
+            destination: record.destination.map(hex::encode),
+            prev: hex::encode(self.last_hash),
+            hash: String::new(),
+        };
+        let hash = entry.compute_hash();
+        entry.hash = hex::encode(hash);
+
+        let mut line = serde_json::to_vec(&entry).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        line.push(b'\n');
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&line)?;
+        file.sync_data()?;
+
+        self.next_seq += 1;
+        self.last_hash = hash;
+        Ok(entry)
+    }
+}
+
+fn decode_hash(hex_hash: &str) -> Option<[u8; 32]> {
+    hex::decode(hex_hash).ok()?.try_into().ok()
+}
+
+/// Read the log at `path` and check that every entry is in sequence, hashes
+/// to its `hash` and links to the one before it. Returns the entries.
+pub fn verify(path: impl AsRef<Path>) -> Result<Vec<AuditEntry>, AuditError> {
+    let reader = BufReader::new(fs::File::open(path)?);
+    let mut entries: Vec<AuditEntry> = Vec::new();
+    let mut prev = AUDIT_CHAIN_START;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: AuditEntry = serde_json::from_str(&line)
+            .map_err(|e| AuditError::Malformed { line: index + 1, reason: e.to_string() })?;
+        let seq = entries.len() as u64;
+        let broken = |reason: &str| AuditError::Broken { seq, reason: reason.to_string() };
+        if entry.seq != seq {
+            return Err(broken(&format!("expected sequence {}, found {}", seq, entry.seq)));
+        }
+        if entry.prev != hex::encode(prev) {
+            return Err(broken("does not link to the previous entry"));
+        }
+        let hash = entry.compute_hash();
+        if entry.hash != hex::encode(hash) {
+            return Err(broken("contents do not match its hash"));
+        }
+        if entries.last().is_some_and(|last| entry.timestamp < last.timestamp) {
+            return Err(broken("timestamp earlier than the previous entry"));
+        }
+        prev = hash;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("axiom_key_audit_{}_{}.log", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn sample_tx(nonce: u64) -> Transaction {
+        Transaction::new([1u8; 32], [2u8; 32], 5_000, 10, nonce, vec![], vec![0u8; 64])
+    }
+
+    #[test]
+    fn test_audit_log_chains_across_reopen() {
+        let path = temp_log("chain");
+        let mut log = KeyAuditLog::open(&path).unwrap();
+        let first = log.record(SigningRecord::transaction(SigningInterface::Cli, &sample_tx(0)), 100).unwrap();
+        assert_eq!(first.prev, hex::encode(AUDIT_CHAIN_START));
+        assert_eq!(first.amount, Some(5_000));
+        assert_eq!(first.destination, Some(hex::encode([2u8; 32])));
+
+        // A reopened log carries on from the last entry.
+        let mut log = KeyAuditLog::open(&path).unwrap();
+        assert_eq!(log.len(), 1);
+        let pulse = SigningRecord::object(SigningInterface::Node, SignedKind::BlockPulse, [1u8; 32], [7u8; 32]);
+        let second = log.record(pulse, 101).unwrap();
+        assert_eq!(second.seq, 1);
+        assert_eq!(second.prev, first.hash);
+
+        let entries = verify(&path).unwrap();
+        assert_eq!(entries, vec![first, second]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_audit_log_detects_tampering() {
+        let path = temp_log("tamper");
+        let mut log = KeyAuditLog::open(&path).unwrap();
+        for nonce in 0..3 {
+            log.record(SigningRecord::transaction(SigningInterface::Cli, &sample_tx(nonce)), 100 + nonce).unwrap();
+        }
+        let original = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = original.lines().collect();
+
+        // Rewriting an amount breaks that entry's hash.
+        fs::write(&path, original.replacen("\"amount\":5000", "\"amount\":50", 1)).unwrap();
+        assert!(matches!(verify(&path), Err(AuditError::Broken { seq: 0, .. })));
+        assert!(KeyAuditLog::open(&path).is_err());
+
+        // Dropping an entry from the middle breaks the sequence.
+        fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(matches!(verify(&path), Err(AuditError::Broken { seq: 1, .. })));
+
+        fs::write(&path, format!("{}\nnot json\n", lines[0])).unwrap();
+        assert!(matches!(verify(&path), Err(AuditError::Malformed { line: 2, .. })));
+        fs::remove_file(&path).unwrap();
+    }
+}
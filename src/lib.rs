@@ -67,14 +67,11 @@ pub struct AxiomPulse {
     /// Remaining supply: TOTAL_SUPPLY - total_mined
     pub remaining: u64,
     /// 512-bit BLAKE3 hash of the latest block
-    #[serde(with = "serde_bytes_64")]
-    pub block_hash: [u8; 64],
+    pub block_hash: types::Hash512,
     /// 512-bit Deterministic AI Oracle seal
-    #[serde(with = "serde_bytes_64")]
-    pub oracle_seal: [u8; 64],
+    pub oracle_seal: types::Hash512,
     /// 512-bit BLAKE3 hash of the previous pulse (tamper-evident chain)
-    #[serde(with = "serde_bytes_64")]
-    pub prev_pulse_hash: [u8; 64],
+    pub prev_pulse_hash: types::Hash512,
     /// Unix timestamp (seconds) for freshness check
     pub timestamp: i64,
     /// Optional RISC-V STARK receipt proving 124M supply integrity.
@@ -82,11 +79,43 @@ pub struct AxiomPulse {
     /// Populated every 100 blocks with a serialised RISC Zero receipt so
     /// that any node (or the Ethereum bridge) can verify the supply law
     /// without re-running the Guardian logic.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub stark_receipt: Option<Vec<u8>>,
+    /// Ed25519 signature over [`AxiomPulse::signing_bytes`] by the key of
+    /// the announced block's `miner`, so only the block's producer can
+    /// vouch for its height. Empty until [`AxiomPulse::sign`] is called.
+    #[serde(default)]
+    pub signature: Vec<u8>,
 }
 
 impl AxiomPulse {
+    /// Message covered by `signature`: the pulse with the signature
+    /// cleared, bincode-encoded behind a domain tag.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.signature = Vec::new();
+        let mut message = b"axiom-pulse-v1".to_vec();
+        message.extend(bincode::serialize(&unsigned).unwrap_or_default());
+        message
+    }
+
+    /// Sign as the block's miner. `miner` must be the wallet whose address
+    /// the block pays.
+    pub fn sign(&mut self, miner: &wallet::Wallet) {
+        self.signature = miner.sign_message(&self.signing_bytes());
+    }
+
+    /// Check `signature` against the announced block's `miner` address.
+    pub fn verify_signature(&self, miner: &transaction::Address) -> bool {
+        let Ok(signature) = <[u8; 64]>::try_from(self.signature.as_slice()) else {
+            return false;
+        };
+        let Ok(key) = ed25519_dalek::VerifyingKey::from_bytes(miner) else {
+            return false;
+        };
+        key.verify_strict(&self.signing_bytes(), &ed25519_dalek::Signature::from_bytes(&signature)).is_ok()
+    }
+
     /// Verify the genesis block hash against the hardcoded
     /// `VERIFIED_GENESIS_ANCHOR_512` constant.
     ///
@@ -99,6 +128,10 @@ impl AxiomPulse {
     }
 }
 
+/// Chain ID of the Axiom network. Bound into peer handshakes (see
+/// `network::handshake`) and used by the bridge for `ChainId::Axiom`.
+pub const AXIOM_CHAIN_ID: u64 = 84000;
+
 // Core modules
 pub mod zk;
 pub mod stark; // RISC Zero zkVM STARK proving (124M supply integrity)
@@ -113,19 +146,44 @@ pub mod validation; // ML-powered transaction validation
 // Re-export modules and wallet so they can be used by bin crates
 pub mod transaction;
 pub mod main_helper;
+pub mod types; // Hash512 and legacy-compatible hash identifiers
 pub mod block;
+pub mod block_template; // Miner-side transaction selection with AI risk policy
+pub mod fee_market; // Fee-rate percentiles over recent blocks for wallet fee estimates
+pub mod miner; // VDF and nonce search on worker threads, cancelled on a new tip
 pub mod genesis;
 pub mod chain;
 pub mod state;
 pub mod economics;
 pub mod wallet;
+pub mod payout; // Per-block mining payout addresses (HD branch)
+pub mod key_audit; // Hash-chained log of every signature made with the wallet key
+pub mod multisig; // m-of-n Ed25519 accounts and partial-signature exchange
+pub mod address_index; // Per-address activity index
+pub mod wallet_analytics; // Coin age and reward accounting over the address index
+pub mod receipt; // Transaction receipts with Merkle inclusion proofs
+pub mod supply_proof; // Pulse-chain supply proofs for light clients
+pub mod checkpoint; // Trust Pulse checkpoints: reorg finality and light-client anchors
+pub mod pulse_validator; // Freshness, supply-law and continuity checks on gossiped pulses
+pub mod contract_registry; // Names, versions and audit scores for contract image IDs
 pub mod vdf;
 pub mod ai_engine;
 pub mod bridge;
 pub mod time;
 pub mod storage;
+pub mod archive; // Compressed block-range archive for bulk history download
+pub mod state_diff; // Signed state deltas between checkpoints for metered links
+pub mod handoff; // State handoff between node processes for zero-downtime upgrades
+pub mod shutdown; // Signal handling and mempool persistence across restarts
+pub mod logging; // Text/JSON log output with a runtime-adjustable filter
+pub mod health; // Liveness and readiness probes for orchestrators
+pub mod admin; // Token-authenticated operator RPC: peers, mempool, mining
 pub mod network;
 pub mod network_legacy; // Legacy network implementation with TimechainBehaviour
+pub mod change_feed; // Cursor-based chain change feed for indexers
+pub mod block_index; // Canonical block bodies served to explorers
+pub mod pending_index; // Mempool snapshot served to explorers
+pub mod tx_screening; // Security-engine screening and quarantine of mempool candidates
 pub mod network_config; // NEW: Network configuration and peer discovery
 pub mod guardian_sentinel; // NEW: Sovereign Guardian sentinel with eternal monitoring
 pub mod neural_guardian; // NEW: AI-powered security with federated learning
@@ -135,6 +193,8 @@ pub mod openclaw_integration; // NEW: OpenClaw automation integration
 pub mod privacy; // View keys & selective disclosure
 pub mod sustainability; // Energy benchmarking & reporting
 pub mod metrics; // Node metrics collection & monitoring
+#[cfg(feature = "chaos")]
+pub mod chaos; // Fault injection for devnet resilience testing
 
 pub use wallet::Wallet;
 pub use block::Block;
@@ -202,6 +262,11 @@ pub use economics::{
     blocks_until_halving,
     format_supply_stats,
     validate_economics,
+    FeePolicy,
+    FeeTotals,
+    FEE_BURN_PERCENT,
+    UnclePolicy,
+    UNCLE_POLICY,
     NetworkPhase,
 };
 
@@ -0,0 +1,142 @@
+// src/logging.rs - Node log output and runtime level control
+//
+// The node logs through `tracing`: text for a terminal, or one JSON object
+// per event with `--log-format json` (or AXIOM_LOG_FORMAT=json) for log
+// shippers. Events carry block heights, transaction hashes and peer IDs as
+// fields rather than only inside the message.
+//
+// Levels use `RUST_LOG` directive syntax, e.g.
+// `info,axiom_core::network=debug,libp2p_gossipsub=warn`, and start from
+// RUST_LOG (default `info`). The filter can be replaced while the node runs
+// through `LogControl`, which the admin API exposes at
+// `/v1/admin/log-level` to loopback callers holding the admin token.
+
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+pub const DEFAULT_LOG_FILTER: &str = "info";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" | "pretty" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!("unknown log format '{}' (expected text or json)", other)),
+        }
+    }
+}
+
+impl LogFormat {
+    /// The format chosen by `--log-format <fmt>` / `--log-format=<fmt>` in
+    /// `args`, else by AXIOM_LOG_FORMAT, else text.
+    pub fn from_args(args: impl IntoIterator<Item = String>, env: Option<String>) -> Result<Self, String> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if let Some(value) = arg.strip_prefix("--log-format=") {
+                return value.parse();
+            }
+            if arg == "--log-format" {
+                return args.next().ok_or("--log-format needs a value")?.parse();
+            }
+        }
+        env.map_or(Ok(LogFormat::Text), |value| value.parse())
+    }
+}
+
+/// Handle on the installed filter.
+pub struct LogControl {
+    handle: reload::Handle<EnvFilter, Registry>,
+    current: Mutex<String>,
+}
+
+impl LogControl {
+    /// The directives in force.
+    pub fn filter(&self) -> String {
+        self.current.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the filter. Invalid directives are rejected and the old
+    /// filter stays.
+    pub fn set_filter(&self, directives: &str) -> Result<(), String> {
+        let filter = parse_filter(directives)?;
+        self.handle.reload(filter).map_err(|e| e.to_string())?;
+        *self.current.lock().unwrap_or_else(|e| e.into_inner()) = directives.trim().to_string();
+        Ok(())
+    }
+}
+
+fn parse_filter(directives: &str) -> Result<EnvFilter, String> {
+    let directives = directives.trim();
+    if directives.is_empty() {
+        return Err("empty log filter".to_string());
+    }
+    EnvFilter::try_new(directives).map_err(|e| format!("invalid log filter '{}': {}", directives, e))
+}
+
+/// Install the global subscriber. `log` records from dependencies and
+/// older modules are routed through it too.
+pub fn init(format: LogFormat) -> LogControl {
+    let directives = std::env::var("RUST_LOG")
+        .ok()
+        .filter(|d| parse_filter(d).is_ok())
+        .unwrap_or_else(|| DEFAULT_LOG_FILTER.to_string());
+    let filter = parse_filter(&directives).unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let registry = tracing_subscriber::registry().with(filter);
+    match format {
+        LogFormat::Text => registry.with(tracing_subscriber::fmt::layer()).init(),
+        LogFormat::Json => registry
+            .with(tracing_subscriber::fmt::layer().json().flatten_event(true).with_current_span(true))
+            .init(),
+    }
+
+    LogControl { handle, current: Mutex::new(directives) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_log_format_selection() {
+        assert_eq!(LogFormat::from_args(args(&["axiom"]), None), Ok(LogFormat::Text));
+        assert_eq!(LogFormat::from_args(args(&["axiom", "--log-format", "json"]), None), Ok(LogFormat::Json));
+        assert_eq!(LogFormat::from_args(args(&["axiom", "--log-format=JSON"]), None), Ok(LogFormat::Json));
+        // The flag wins over the environment.
+        assert_eq!(LogFormat::from_args(args(&["axiom", "--log-format=text"]), Some("json".into())), Ok(LogFormat::Text));
+        assert_eq!(LogFormat::from_args(args(&["axiom"]), Some("json".into())), Ok(LogFormat::Json));
+        assert!(LogFormat::from_args(args(&["axiom", "--log-format"]), None).is_err());
+        assert!(LogFormat::from_args(args(&["axiom", "--log-format", "xml"]), None).is_err());
+    }
+
+    #[test]
+    fn test_filter_reload() {
+        let (layer, handle) = reload::Layer::<EnvFilter, Registry>::new(EnvFilter::new(DEFAULT_LOG_FILTER));
+        let control = LogControl { handle, current: Mutex::new(DEFAULT_LOG_FILTER.to_string()) };
+
+        control.set_filter("warn,axiom_core::network=debug").unwrap();
+        assert_eq!(control.filter(), "warn,axiom_core::network=debug");
+        assert!(control.set_filter("axiom_core=loud").is_err());
+        assert!(control.set_filter("  ").is_err());
+        assert_eq!(control.filter(), "warn,axiom_core::network=debug");
+        drop(layer);
+    }
+}
@@ -6,40 +6,125 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::error::Error;
 use std::net::{IpAddr, SocketAddr};
 use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::time;
-use libp2p::{gossipsub, Multiaddr, PeerId, Swarm};
+use libp2p::{autonat, dcutr, gossipsub, identify, relay, request_response, upnp, Multiaddr, PeerId, Swarm};
+use libp2p::multiaddr::Protocol;
+use libp2p::swarm::DialError;
 use libp2p::swarm::SwarmEvent;
 use futures::StreamExt;
 use warp::Filter;
 use governor::{Quota, RateLimiter};
-use governor::clock::DefaultClock;
-use governor::state::keyed::DashMapStateStore;
 
 // Import production modules from the axiom_core library
-use axiom_core::network_legacy::{TimechainBehaviourEvent, init_network, init_network_with_bootstrap};
+use axiom_core::network_legacy::{TimechainBehaviourEvent, default_bootstrap_peers, init_network_with_config};
+use axiom_core::network_legacy::{
+    BlockRequest, ChainRequest, ChainResponse, serve_chain_request, header_request_for, first_divergence, SYNC_LOOKBACK, Ping, PING_INTERVAL_SECS,
+};
 use axiom_core::network::Discv5Service;
 use axiom_core::network::discv5_service::default_bootstrap_enrs;
-use axiom_core::network::config::{NetworkConfig, DiscoveryStrategy};
+use axiom_core::network::config::{NetworkConfig, DiscoveryStrategy, quic_multiaddr};
+use axiom_core::network::handshake::verify_identify;
+use axiom_core::network::peerstore::{PeerStore, DEFAULT_PEERSTORE_PATH};
+use axiom_core::network::encoded_blocks::{EncodedBlockCache, ENCODED_BLOCK_CACHE_BYTES};
+use axiom_core::network::diversity::{ip_of, PeerDiversity, Subnet};
+use axiom_core::network::geoip::{open_geoip, PeerGeo, GEO_HOP_PENALTY};
+use axiom_core::network::gossip_tuning::{load_tuning, save_tuning, GossipTuner, NetworkSample, GOSSIP_TUNING_PATH};
+use axiom_core::network::discv5_service::ExternalIpVotes;
+use axiom_core::network::peer_manager::{fastest_peer, PeerMetrics, PeerRateLimiter, RateLimitVerdict};
+use axiom_core::network::config::{SYNC_RATE_TOPIC, TX_PULL_RATE_TOPIC};
+use axiom_core::network::wire;
+use axiom_core::network::tx_relay::{serve_tx_request, TxAnnouncement, TxRelay, TX_ANNOUNCE_TOPIC};
+use axiom_core::network::sync_manager::{SyncEvent, SyncManager, SYNC_STALL_TIMEOUT};
+use axiom_core::metrics::{MetricsCollector, PeerBandwidth};
+use axiom_core::network::gossip_handler::{
+    isolate_panics, GossipError, HANDLER_PANIC_PENALTY, INVALID_MESSAGE_PENALTY,
+};
+use axiom_core::types::Hash512;
 use axiom_core::{AxiomPulse, GENESIS_PULSE_HASH};
 use axiom_core::wallet::Wallet;
-use axiom_core::chain::Timechain;
+use axiom_core::chain::{ForkOutcome, Timechain};
+use axiom_core::state::State;
+use axiom_core::change_feed::ChangeFeed;
+use axiom_core::block_index::BlockIndex;
+use axiom_core::pending_index::PendingIndex;
+use axiom_core::economics::SupplySnapshot;
+use axiom_core::fee_market::FeeStats;
+use axiom_core::consensus::StaleBlocks;
+use axiom_core::checkpoint::{Checkpoints, CHECKPOINTS_PATH};
+use axiom_core::address_index::AddressIndex;
+use axiom_core::receipt::ReceiptIndex;
+use axiom_core::payout::{payout_wallet, PayoutBranch, PAYOUT_BRANCH_FILE};
+use axiom_core::key_audit::{KeyAuditLog, SignedKind, SigningInterface, SigningRecord, AUDIT_LOG_FILE};
+use axiom_core::config::{AxiomConfig, StorageBackend};
+use axiom_core::error::AxiomError;
+use axiom_core::health::Heartbeat;
+use axiom_core::storage::{ChainFileRecovery, NodeStorage};
+use axiom_core::handoff::{write_pid_file, Handoff, HANDOFF_FILE, PID_FILE};
+#[cfg(unix)]
+use axiom_core::handoff::{request_takeover, TAKEOVER_TIMEOUT};
+use axiom_core::contract_registry::{ContractRegistry, DEFAULT_REGISTRY_PATH};
+use axiom_core::supply_proof::PulseLog;
+use axiom_core::pulse_validator::{PulseRejection, PulseValidator, PulseVerdict};
+use axiom_core::archive::BlockArchive;
+use axiom_core::state_diff::StateDiffStore;
+use axiom_core::logging::LogFormat;
+use axiom_core::admin::{
+    AdminCommand, AdminToken, MempoolEntry, ADMIN_TOKEN_FILE, INTERNAL_ERROR, INVALID_PARAMS,
+};
+use tracing::{error, info, warn};
+use axiom_core::block_template::{LocalBlockMetadata, RiskPolicy};
+use axiom_core::miner::{Miner, MinerEvent};
 use axiom_core::block::Block;
 use axiom_core::transaction::Transaction;
-use axiom_core::neural_guardian::NeuralGuardian;
+use axiom_core::mempool::SenderLimits;
+use axiom_core::neural_guardian::{
+    Action, Incident, ModelUpdate, NeuralGuardian, ThreatType, PEER_SCORES_PATH, TRAINING_DATA_PATH,
+};
+use axiom_core::guardian::alerts::Alerts;
+use axiom_core::guardian::safe_mode::{
+    resume_message, SafeMode, SafeModePolicy, SafetyNotice, SAFETY_TOPIC, SAFE_MODE_PATH,
+};
+use axiom_core::guardian::threat_report::{GuardianEventLog, GUARDIAN_EVENTS_PATH};
+use axiom_core::guardian::enforcement::{Enforcement, GuardianEnforcer, GUARDIAN_ENFORCEMENT_PATH};
+use axiom_core::guardian::decision_audit::{DecisionAuditLog, GUARDIAN_AUDIT_PATH};
+use axiom_core::guardian::model_checkpoint::{
+    rollback_from_args, ModelCheckpoints, GUARDIAN_MODELS_DIR, MIN_NEW_SAMPLES, RETRAIN_INTERVAL_SECS,
+};
+use axiom_core::shutdown::{listen_for_signals, save_mempool, take_mempool, StopRequest, MEMPOOL_FILE};
 use axiom_core::main_helper::{get_network_health, format_axm_supply};
-use axiom_core::stark::prover::{StarkProver, TransactionData, STARK_PROOF_INTERVAL};
 use axiom_core::guardian_sentinel::SovereignGuardian;
-use axiom_core::guardian_enhancement::AIGuardianBridge;
-use axiom_core::ai_core::{MultiLayerSecurityEngine, TransactionRiskProfile, SecurityConfig};
+use axiom_core::guardian_enhancement::{AIGuardianBridge, GuardianAction, GuardianDecision};
+use axiom_core::guardian_enhancement::consensus_proposals::{
+    ApprovalSource, ProposalAnnouncement, ProposalQueue, CONSENSUS_PROPOSALS_PATH,
+};
+use axiom_core::tx_screening::Screening;
+use axiom_core::ai_core::{MultiLayerSecurityEngine, SecurityConfig};
+use axiom_core::ai_core::threat_feed::fetch_feed;
+
+mod api;
+mod mining;
+mod safe_mode;
+mod screening;
+
+use api::{
+    account_routes, admin_rpc_route, archive_routes, chain_routes, contract_routes, guardian_routes, handle_rejection,
+    log_level_admin_route, network_routes, parse_address, probe_routes, transaction_routes, AdminCall, ApiContext,
+    ApiRateLimiter, PulseApiState, PulseHistoryEntry, TxSubmission,
+};
+#[cfg(feature = "chaos")]
+use api::chaos_admin_route;
+use mining::{mined_block_pulse, next_mining_job, PendingMiningJob};
+use safe_mode::{enter_safe_mode, halt_node, resume_node, sign_halt_notice};
+use screening::{
+    answer_screened, check_transaction, finish_screening, pool_transaction, screen_transaction, ScreenedTx, TxOrigin,
+};
 
 // Build metadata generated by shadow-rs
 shadow_rs::shadow!(build);
 
-/// Rate limit: maximum requests per minute per IP on the Public Pulse API.
-const API_RATE_LIMIT_PER_MINUTE: u32 = 60;
-
 /// Maximum number of chained pulses kept in memory for the `/v1/pulse/history` endpoint.
 const PULSE_HISTORY_CAPACITY: usize = 10;
 
@@ -48,36 +133,88 @@ const PULSE_HISTORY_CAPACITY: usize = 10;
 /// launch phase of the Axiom Protocol.
 const PROTOCOL_PHASE: &str = "Axiom-124M-Genesis";
 
-#[derive(Clone, serde::Serialize)]
-struct PulseApiState {
-    protocol_phase: String,
-    current_height: u64,
-    supply_remaining_units: u64,
-    supply_remaining_axm: String,
-    trust_pulse: String,
-    zk_verified: bool,
+/// Lock a shared API/guardian mutex, recovering the data if a previous
+/// holder panicked. Gossip decoding runs under panic isolation (and reads
+/// the pulse log), so a poisoned lock must not turn one dropped message
+/// into a node crash.
+fn lock_or_recover<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
 }
 
-/// A single entry in the in-memory pulse history ring buffer.
-#[derive(Clone, serde::Serialize)]
-struct PulseHistoryEntry {
-    height: u64,
-    trust_pulse_hex: String,
-    prev_pulse_hex: String,
-    timestamp: u64,
+/// Seconds since the Unix epoch, for records persisted across restarts.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Append a signature made with the wallet key to the audit log. A failed
+/// write is reported but does not stop the node from signing.
+fn audit_signature(audit: &Option<Arc<Mutex<KeyAuditLog>>>, record: SigningRecord) {
+    if let Some(audit) = audit {
+        if let Err(e) = lock_or_recover(audit).record(record, unix_now()) {
+            error!("🔏 KEY AUDIT: failed to record signature: {}", e);
+        }
+    }
+}
+
+/// Record the `written` deltas `StateDiffStore::update` just signed, plus
+/// the snapshot it re-signed alongside them.
+fn audit_state_deltas(audit: &Option<Arc<Mutex<KeyAuditLog>>>, store: &StateDiffStore, written: usize, signer: &Wallet) {
+    if written == 0 {
+        return;
+    }
+    let manifest = store.manifest();
+    let fresh = &manifest.diffs[manifest.diffs.len().saturating_sub(written)..];
+    for entry in fresh.iter().chain(manifest.snapshot.as_ref()) {
+        let digest = hex::decode(&entry.blake3).ok().and_then(|d| d.try_into().ok()).unwrap_or([0u8; 32]);
+        audit_signature(audit, SigningRecord::object(SigningInterface::Node, SignedKind::StateDelta, signer.address, digest));
+    }
+}
+
+/// A gossip message decoded and, for pulses, validated; what the event
+/// loop applies.
+enum GossipPayload {
+    Block(Block),
+    Transaction(Transaction),
+    TxAnnouncement(TxAnnouncement),
+    Pulse(AxiomPulse, PulseVerdict),
+    Proposal(ProposalAnnouncement),
+    Safety(SafetyNotice),
+    /// A topic nothing is taken from here (e.g. network health).
+    Other,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    // Initialize logging
-    tracing_subscriber::fmt::init();
-
-    println!("--------------------------------------------------");
-    println!("🏛️  AXIOM CORE | PRIVACY-FIRST BLOCKCHAIN");
-    println!("🛡️  VDF: 1800sec (30min) | PoW Hybrid | 124M Fixed Supply");
-    println!("🤖 AI NEURAL GUARDIAN: ATTACK DETECTION ACTIVE");
-    println!("🔐 MANDATORY ZK-STARK PRIVACY | ED25519 SIGNATURES");
-    println!("--------------------------------------------------");
+    // Logging: text by default, JSON with --log-format json. The filter
+    // starts from RUST_LOG and can be changed at runtime through the
+    // loopback admin API.
+    let log_format = match LogFormat::from_args(std::env::args(), std::env::var("AXIOM_LOG_FORMAT").ok()) {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("🚨 {}", e);
+            std::process::exit(2);
+        }
+    };
+    let log_control = Arc::new(axiom_core::logging::init(log_format));
+
+    info!(version = env!("CARGO_PKG_VERSION"), "🏛️  AXIOM CORE | PRIVACY-FIRST BLOCKCHAIN");
+    info!("🛡️  VDF: 1800sec (30min) | PoW Hybrid | 124M Fixed Supply");
+    info!("🤖 AI NEURAL GUARDIAN: ATTACK DETECTION ACTIVE");
+    info!("🔐 MANDATORY ZK-STARK PRIVACY | ED25519 SIGNATURES");
+
+    // Node configuration: axiom.toml (see axiom_core::config) with AXIOM_*
+    // environment overrides. A bad value stops the node here, before it
+    // touches the network or its data.
+    let node_config = match AxiomConfig::load_for_node() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("🚨 CONFIG: {}", e);
+            std::process::exit(1);
+        }
+    };
 
     // 0. FATAL INTEGRITY ENFORCEMENT (Zero-Tolerance AI)
     // The SHA-256 of the model weights MUST match the genesis anchor.
@@ -89,18 +226,28 @@ async fn main() -> Result<(), Box<dyn Error>> {
         let weights_path = base_dir.join("weights.bin");
         if weights_path.exists() {
             let mut guardian_check = NeuralGuardian::new();
-            if let Err(e) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 guardian_check.load_model(weights_path)
             })) {
-                eprintln!("🚨 CRITICAL: AI MODEL INTEGRITY FAILURE");
-                eprintln!("🚨 The SHA-256 hash of weights.bin does not match GENESIS_WEIGHTS_HASH.");
-                eprintln!("🚨 This node CANNOT start with tampered model weights.");
-                eprintln!("🚨 Detail: {:?}", e);
-                std::process::exit(1);
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    error!("🚨 CRITICAL: AI MODEL WEIGHTS REJECTED");
+                    error!("🚨 weights.bin must ship with weights.bin.sig signed by {} maintainer keys.",
+                        axiom_core::guardian::SovereignInvariants::MODEL_RELEASE_THRESHOLD);
+                    error!("🚨 Detail: {}", e);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    error!("🚨 CRITICAL: AI MODEL INTEGRITY FAILURE");
+                    error!("🚨 The SHA-256 hash of weights.bin does not match GENESIS_WEIGHTS_HASH.");
+                    error!("🚨 This node CANNOT start with tampered model weights.");
+                    error!("🚨 Detail: {:?}", e);
+                    std::process::exit(1);
+                }
             }
-            println!("✅ AI Model Integrity: weights.bin matches Genesis Anchor");
+            info!("✅ AI Model Integrity: weights.bin matches Genesis Anchor and is maintainer-signed");
         } else {
-            println!("ℹ️  No weights.bin found — using default NeuralGuardian model");
+            info!("ℹ️  No weights.bin found — using default NeuralGuardian model");
         }
     }
 
@@ -108,124 +255,431 @@ async fn main() -> Result<(), Box<dyn Error>> {
     // If config/genesis_pulse.json exists, verify its 512-bit hash against
     // GENESIS_PULSE_HASH and use it as the starting prev_pulse_hash. This
     // anchors the tamper-evident pulse chain to the absolute origin.
-    let genesis_pulse_anchor: [u8; 64] = {
+    let genesis_pulse_anchor: Hash512 = {
         let genesis_pulse_path = base_dir.join("config").join("genesis_pulse.json");
         if genesis_pulse_path.exists() {
             let pulse_bytes = std::fs::read(&genesis_pulse_path)
                 .expect("Failed to read config/genesis_pulse.json");
-            let pulse_hash = axiom_core::axiom_hash_512(&pulse_bytes);
+            let pulse_hash = Hash512::digest(&pulse_bytes);
             let pulse_hash_hex = hex::encode(pulse_hash);
             if pulse_hash_hex != GENESIS_PULSE_HASH {
-                eprintln!("🚨 GENESIS PULSE INTEGRITY FAILURE");
-                eprintln!("🚨 Expected: {}", GENESIS_PULSE_HASH);
-                eprintln!("🚨 Got:      {}", pulse_hash_hex);
-                eprintln!("🚨 The genesis pulse file has been tampered with.");
+                error!("🚨 GENESIS PULSE INTEGRITY FAILURE");
+                error!("🚨 Expected: {}", GENESIS_PULSE_HASH);
+                error!("🚨 Got:      {}", pulse_hash_hex);
+                error!("🚨 The genesis pulse file has been tampered with.");
                 std::process::exit(1);
             }
-            println!("✅ Genesis Pulse Anchor: config/genesis_pulse.json verified");
+            info!("✅ Genesis Pulse Anchor: config/genesis_pulse.json verified");
             pulse_hash
         } else {
-            println!("ℹ️  No config/genesis_pulse.json found — using unanchored start");
-            [0u8; 64]
+            info!("ℹ️  No config/genesis_pulse.json found — using unanchored start");
+            Hash512::ZERO
         }
     };
 
     // 1. IDENTITY & STATE INITIALIZATION
     let wallet = Wallet::load_or_create();
-    println!("💳 Wallet Address: {:?}", hex::encode(wallet.address));
-    println!("📁 Wallet file: ./wallet.dat (keep safe!)");
+    info!("💳 Wallet Address: {:?}", hex::encode(wallet.address));
+    info!("📁 Wallet file: ./wallet.dat (keep safe!)");
+
+    // Every signature the node makes with the wallet key is appended to a
+    // hash-chained audit log; `axiom-wallet audit` verifies and lists it.
+    let key_audit: Option<Arc<Mutex<KeyAuditLog>>> = match KeyAuditLog::open_default() {
+        Ok(log) => {
+            info!(entries = log.len(), "🔏 KEY AUDIT: Recording signatures in {}", AUDIT_LOG_FILE);
+            Some(Arc::new(Mutex::new(log)))
+        }
+        Err(e) => {
+            error!("🔏 KEY AUDIT: {} unusable: {} — signatures will NOT be recorded; investigate before trusting this host", AUDIT_LOG_FILE, e);
+            None
+        }
+    };
+
+    // Payout rotation: AXIOM_ROTATE_PAYOUT=1 pays each block we mine to a
+    // fresh address on the wallet's HD payout branch instead of reusing
+    // wallet.address. `axiom-wallet payouts` aggregates the balances.
+    let rotate_payout = node_config.mining.rotate_payout;
+    let mut payout_branch = PayoutBranch::load(PAYOUT_BRANCH_FILE);
+    if rotate_payout {
+        info!("🔀 Payout rotation enabled: next coinbase index {}", payout_branch.next_index);
+    }
 
     let ai_guardian = Arc::new(Mutex::new(NeuralGuardian::new()));
+    match lock_or_recover(&ai_guardian).load_assessments(PEER_SCORES_PATH) {
+        Ok(0) => {}
+        Ok(n) => info!("🛡️  Restored {} peer assessments from {}", n, PEER_SCORES_PATH),
+        Err(e) => log::warn!("Ignoring unreadable {}: {}", PEER_SCORES_PATH, e),
+    }
+    // Labelled samples from confirmed incidents, for retraining.
+    match lock_or_recover(&ai_guardian).load_training_data(TRAINING_DATA_PATH) {
+        Ok(0) => {}
+        Ok(n) => info!("🛡️  Restored {} guardian training samples from {}", n, TRAINING_DATA_PATH),
+        Err(e) => log::warn!("Ignoring unreadable {}: {}", TRAINING_DATA_PATH, e),
+    }
+    // Retrained guardian models: resume from the latest checkpoint, or the
+    // one --guardian-rollback names if an update made detection worse.
+    let guardian_rollback = match rollback_from_args(std::env::args()) {
+        Ok(version) => version,
+        Err(e) => {
+            error!("🚨 {}", e);
+            std::process::exit(2);
+        }
+    };
+    let mut guardian_models = match ModelCheckpoints::open(GUARDIAN_MODELS_DIR) {
+        Ok(checkpoints) => checkpoints,
+        Err(e) => {
+            error!("🚨 GUARDIAN: cannot open model checkpoints in {}: {}", GUARDIAN_MODELS_DIR, e);
+            std::process::exit(1);
+        }
+    };
+    {
+        let mut ai = lock_or_recover(&ai_guardian);
+        if let Some(version) = guardian_rollback {
+            match guardian_models.rollback(&mut ai, version, unix_now()) {
+                Ok(record) => info!("🛡️  GUARDIAN: rolled back to model v{} ({})", version, record.model_hash),
+                Err(e) => {
+                    error!("🚨 GUARDIAN: rollback to model v{} failed: {}", version, e);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            match guardian_models.load_latest() {
+                Ok(Some(model)) => {
+                    ai.install_model(model);
+                    info!("🛡️  GUARDIAN: resumed model checkpoint v{}", guardian_models.log().len());
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    error!("🚨 GUARDIAN: latest model checkpoint unusable: {} (use --guardian-rollback)", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+    // Guardian history for threat reports: detections, bans, corrections.
+    let guardian_events = Arc::new(Mutex::new(GuardianEventLog::load(GUARDIAN_EVENTS_PATH)));
+    // What the guardian's recommendations are turned into, and the bans
+    // and limits still in force from the last run.
+    let guardian_enforcer = Arc::new(Mutex::new(GuardianEnforcer::load(GUARDIAN_ENFORCEMENT_PATH)));
+    // Hash-chained record of the guardian's decisions and what was done
+    // about them, for operators and third parties to audit.
+    let guardian_audit = match DecisionAuditLog::load(GUARDIAN_AUDIT_PATH) {
+        Ok(log) => Arc::new(Mutex::new(log)),
+        Err(e) => {
+            error!("🚨 GUARDIAN: cannot load the decision audit log {}: {}", GUARDIAN_AUDIT_PATH, e);
+            std::process::exit(1);
+        }
+    };
     let mut peer_message_counts: HashMap<PeerId, (u32, Instant)> = HashMap::new();
 
+    // Alert sinks: escalations, chain halts and sentinel failures reach a human
+    let alerts = Alerts::spawn(node_config.ai.alert_sinks.clone(), node_config.ai.alert_dedup_window);
+    if !node_config.ai.alert_sinks.is_empty() {
+        info!("🔔 Guardian alerts: {} sinks", node_config.ai.alert_sinks.len());
+    }
+
     // AI Guardian Bridge: transaction-level threat assessment with Guardian veto layer
     let security_engine = Arc::new(MultiLayerSecurityEngine::new(SecurityConfig::default()));
-    let ai_bridge = Arc::new(AIGuardianBridge::new(security_engine));
+    let ai_bridge = Arc::new(AIGuardianBridge::new(Arc::clone(&security_engine))
+        .with_proposal_queue(ProposalQueue::load(CONSENSUS_PROPOSALS_PATH))
+        .with_alerts(alerts.clone()));
+
+    // Threat feeds: import the signed malicious-address lists at
+    // [ai] threat_feeds on an interval, and drop entries as they expire.
+    if !node_config.ai.threat_feeds.is_empty() {
+        let engine = Arc::clone(&security_engine);
+        let urls = node_config.ai.threat_feeds.clone();
+        let publishers: Vec<[u8; 32]> = node_config.ai.threat_feed_publishers.iter().filter_map(|k| parse_address(k)).collect();
+        let interval = Duration::from_secs(node_config.ai.threat_feed_interval);
+        tokio::spawn(async move {
+            let client = match reqwest::Client::builder().timeout(Duration::from_secs(30)).build() {
+                Ok(client) => client,
+                Err(e) => {
+                    log::error!("Threat feeds disabled: {}", e);
+                    return;
+                }
+            };
+            let mut timer = time::interval(interval);
+            loop {
+                timer.tick().await;
+                let now = unix_now();
+                for url in &urls {
+                    match fetch_feed(&client, url).await.and_then(|feed| engine.import_threat_feed(&feed, &publishers, now)) {
+                        Ok(import) => info!("🛡️  THREAT FEED: {} — {} added, {} merged, {} expired",
+                            url, import.added, import.merged, import.expired),
+                        Err(e) => warn!("⚠️  THREAT FEED: {} not imported: {}", url, e),
+                    }
+                }
+                let dropped = engine.expire_threat_intelligence(now);
+                if dropped > 0 {
+                    info!("🛡️  THREAT FEED: {} expired addresses dropped", dropped);
+                }
+            }
+        });
+        info!("🛡️  Threat feeds: {} sources, every {}s", node_config.ai.threat_feeds.len(), interval.as_secs());
+    }
 
     // Sovereign Guardian: background sentinel for supply-cap and chain-integrity monitoring
     let _sentinel_handle = {
-        let mut sentinel = SovereignGuardian::new();
+        let mut sentinel = SovereignGuardian::new().with_alerts(alerts.clone());
         tokio::spawn(async move {
             if let Err(e) = sentinel.run_sentinel().await {
                 log::error!("SovereignGuardian exited: {}", e);
             }
         })
     };
-    println!("🛡️  SovereignGuardian: background sentinel started");
+    info!("🛡️  SovereignGuardian: background sentinel started");
+
+    // Safe mode: a chain halt (the guardian's HaltChain, admin_halt or an
+    // operator's notice) holds until an operator or manifest quorum resumes.
+    let mut safe_mode = match SafeMode::load(SAFE_MODE_PATH,
+        SafeModePolicy::from_config(&node_config.ai.safe_mode_operators)) {
+        Ok(safe_mode) => safe_mode,
+        Err(e) => {
+            error!("🚨 SAFE MODE: cannot load {}: {}", SAFE_MODE_PATH, e);
+            std::process::exit(1);
+        }
+    };
+    if let Some(halt) = safe_mode.halt() {
+        warn!("🛑 SAFE MODE: chain halted at H-{} since {}: {} — blocks and transactions refused until admin_resume",
+            halt.notice.notice.height, halt.since, halt.notice.notice.reason);
+        let _ = ai_bridge.activate_circuit_breaker(halt.notice.notice.height, halt.notice.notice.reason.clone());
+    }
 
     // Transaction mempool
     let mut mempool: VecDeque<Transaction> = VecDeque::new();
+    let sender_limits = SenderLimits::default();
+
+    // UPGRADE TAKEOVER: with AXIOM_TAKEOVER=1 this process asks the running
+    // node to hand off (see axiom_core::handoff) and waits for it to exit
+    // before touching the chain files and ports it holds.
+    if std::env::var("AXIOM_TAKEOVER").unwrap_or_default() == "1" {
+        #[cfg(unix)]
+        match tokio::task::spawn_blocking(|| request_takeover(PID_FILE, HANDOFF_FILE, TAKEOVER_TIMEOUT)).await {
+            Ok(Ok(pid)) => info!("🔁 HANDOFF: Node {} handed off, taking over", pid),
+            Ok(Err(e)) => warn!("⚠️  HANDOFF: Takeover failed ({}) — starting normally", e),
+            Err(e) => warn!("⚠️  HANDOFF: Takeover task failed ({}) — starting normally", e),
+        }
+    }
+    if let Err(e) = write_pid_file(PID_FILE) {
+        log::warn!("Failed to write {}: {}", PID_FILE, e);
+    }
+
+    // Devnet fault injection: initial faults from AXIOM_CHAOS, changed at
+    // runtime through /v1/admin/chaos. Must run before the chain is read.
+    #[cfg(feature = "chaos")]
+    match axiom_core::chaos::load_from_env() {
+        Ok(Some(faults)) => info!("🧪 CHAOS: Fault injection active: {:?}", faults),
+        Ok(None) => info!("🧪 CHAOS: Fault injection compiled in, no faults set"),
+        Err(e) => {
+            error!("🚨 CHAOS: Invalid fault set: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Chain storage backend: [storage] backend in axiom.toml or
+    // AXIOM_STORAGE_BACKEND (file, sled, rocksdb). Default: axiom_chain.dat.
+    let storage_config = &node_config.storage;
+    let storage_dir = match storage_config.backend {
+        StorageBackend::File => std::path::PathBuf::from("."),
+        _ => storage_config.data_dir.clone(),
+    };
+    let mut storage = match NodeStorage::open(storage_config) {
+        Ok(storage) => storage,
+        Err(e) => {
+            error!("🚨 STORAGE: Cannot open {:?} backend in {}: {}",
+                storage_config.backend, storage_config.data_dir.display(), e);
+            std::process::exit(1);
+        }
+    };
+    info!("💾 STORAGE: {:?} backend", storage_config.backend);
+
+    // Repair what a crash in the middle of a save left behind.
+    match storage.recover() {
+        ChainFileRecovery::Clean => {}
+        ChainFileRecovery::DiscardedPartialWrite =>
+            info!("🩹 STORAGE: Discarded an interrupted chain write; keeping the last complete save"),
+        ChainFileRecovery::CompletedPendingWrite =>
+            info!("🩹 STORAGE: Completed a chain write interrupted before its rename"),
+        ChainFileRecovery::RestoredBackup =>
+            info!("🩹 STORAGE: Chain file was damaged; restored the previous save"),
+        ChainFileRecovery::Unrecoverable =>
+            warn!("⚠️  STORAGE: Chain file is damaged and no backup is usable"),
+    }
 
     // Load or create blockchain
     let genesis_block = axiom_core::genesis::genesis();
-    let mut tc = if let Some(saved_blocks) = axiom_core::storage::load_chain() {
-        println!("✅ STORAGE: Loaded {} blocks. Integrity verified.", saved_blocks.len());
+    // A chain that cannot be read or restored is left for the operator:
+    // starting fresh would overwrite it with the first save.
+    let saved_blocks = match storage.load_chain() {
+        Ok(saved_blocks) => saved_blocks,
+        Err(e) => {
+            error!("🚨 STORAGE: Cannot load the chain: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let mut tc = if let Some(saved_blocks) = saved_blocks {
+        info!("✅ STORAGE: Loaded {} blocks. Integrity verified.", saved_blocks.len());
         match Timechain::from_saved_blocks(saved_blocks) {
             Ok(chain) => chain,
             Err(e) => {
-                println!("⚠️  STORAGE: Failed to restore chain: {} — starting fresh", e);
-                Timechain::new(genesis_block)
+                error!("🚨 STORAGE: Failed to restore chain: {} — move it aside to resync", e);
+                std::process::exit(1);
             }
         }
     } else {
         Timechain::new(genesis_block)
     };
 
-    println!("\n--- AXIOM GENESIS ANCHOR ---");
+    // 1b. ARCHIVE IMPORT: bulk history downloaded out-of-band from an
+    // archive server. Chunks are checked against the manifest, then the
+    // whole chain is replayed through consensus validation.
+    if let Ok(import_dir) = std::env::var("AXIOM_ARCHIVE_IMPORT") {
+        match axiom_core::archive::import_dir(&import_dir, &tc.blocks[0]) {
+            Ok(blocks) if blocks.len() > tc.blocks.len() => match Timechain::from_saved_blocks(blocks) {
+                Ok(chain) => {
+                    info!("📦 ARCHIVE: Imported {} blocks from {}", chain.blocks.len(), import_dir);
+                    storage.save_chain(&chain.blocks, &chain.state);
+                    tc = chain;
+                }
+                Err(e) => warn!("⚠️  ARCHIVE: Imported history failed validation: {}", e),
+            },
+            Ok(_) => info!("📦 ARCHIVE: {} holds nothing newer than the local chain", import_dir),
+            Err(e) => warn!("⚠️  ARCHIVE: Import from {} failed: {}", import_dir, e),
+        }
+    }
+
+    // Archive server mode: publish completed block ranges over HTTP.
+    let block_archive: Option<Arc<Mutex<BlockArchive>>> = node_config.storage.archive_dir.as_ref().and_then(|dir| {
+        match BlockArchive::open(dir, &tc.blocks[0]) {
+            Ok(mut archive) => {
+                if let Err(e) = archive.update(&tc.blocks) {
+                    log::warn!("ARCHIVE: initial update failed: {}", e);
+                }
+                info!("📦 ARCHIVE: Serving {} blocks from {}", archive.manifest().archived_height(), dir.display());
+                Some(Arc::new(Mutex::new(archive)))
+            }
+            Err(e) => {
+                warn!("⚠️  ARCHIVE: Cannot open {}: {} — archive server disabled", dir.display(), e);
+                None
+            }
+        }
+    });
+
+    // Archive servers also publish signed state deltas between weekly
+    // checkpoints, for nodes that catch up over metered links.
+    let state_diffs: Option<Arc<Mutex<StateDiffStore>>> = node_config.storage.archive_dir.as_ref().and_then(|dir| {
+        let dir = dir.join("state");
+        match StateDiffStore::open(&dir, &tc.blocks[0], &wallet.address) {
+            Ok(mut store) => {
+                match store.update(&tc.blocks, &wallet) {
+                    Ok(n) => audit_state_deltas(&key_audit, &store, n, &wallet),
+                    Err(e) => log::warn!("STATE DIFF: initial update failed: {}", e),
+                }
+                info!("📦 STATE DIFF: Serving deltas up to checkpoint H-{} from {}",
+                    store.manifest().checkpoint_height(), dir.display());
+                Some(Arc::new(Mutex::new(store)))
+            }
+            Err(e) => {
+                warn!("⚠️  STATE DIFF: Cannot open {}: {} — state deltas disabled", dir.display(), e);
+                None
+            }
+        }
+    });
+
     if let Some(first) = tc.blocks.first() {
-        println!("HASH: {:?}", hex::encode(first.hash()));
+        info!(genesis = %hex::encode(first.legacy_hash()), height = tc.blocks.len(), "⚓ Genesis anchor");
     }
-    println!("----------------------------\n");
 
     // 2. NETWORK SETUP
-    let bootstrap_peers: Vec<String> = std::env::var("AXIOM_BOOTSTRAP_PEERS")
-        .unwrap_or_default()
-        .split(',')
-        .filter(|s| !s.trim().is_empty())
-        .map(|s| s.trim().to_string())
-        .collect();
-
-    let mut swarm: Swarm<axiom_core::network_legacy::TimechainBehaviour> = if !bootstrap_peers.is_empty() {
-        init_network_with_bootstrap(bootstrap_peers).await
-            .map_err(|e| -> Box<dyn Error> { e })?
+    let bootstrap_peers = if node_config.network.bootstrap_peers.is_empty() {
+        default_bootstrap_peers()
     } else {
-        init_network().await
-            .map_err(|e| -> Box<dyn Error> { e })?
+        node_config.network.bootstrap_peers.clone()
+    };
+
+    // Per-node transport settings. QUIC is on by default; enable_quic =
+    // false (AXIOM_QUIC=0) restricts the node to TCP. Relays are
+    // /.../p2p/<peer-id> multiaddrs from relay_peers (AXIOM_RELAY_PEERS).
+    let mut node_net_config = NetworkConfig {
+        enable_quic: node_config.network.enable_quic,
+        ..NetworkConfig::default()
     };
+    node_net_config.nat.relay_addrs.extend(node_config.network.relay_peers.iter().cloned());
+    let relay_addrs = node_net_config.nat.relay_multiaddrs()?;
+    // Mesh profile chosen from the network size seen on the last run.
+    if let Some(tuning) = load_tuning(GOSSIP_TUNING_PATH) {
+        info!("📶 Gossip mesh: D={} ({}-{}), heartbeat {} ms (tuned on last run)",
+            tuning.mesh_n, tuning.mesh_n_low, tuning.mesh_n_high, tuning.heartbeat_interval.as_millis());
+        node_net_config.gossip_config = tuning;
+    }
+    let mut gossip_tuner = GossipTuner::new(node_net_config.gossip_config.clone());
+    let mut last_traffic_sample = (0u64, Instant::now());
+    // Per-peer, per-topic token buckets with byte accounting
+    let mut peer_limiter = PeerRateLimiter::new(node_net_config.rate_limits.clone());
+    // Peer country/AS tags from the GeoLite2 databases, if configured
+    let geoip = node_config.network.geoip_dir.as_deref().and_then(|dir| match open_geoip(dir) {
+        Ok(lookup) => {
+            info!("🌍 GeoIP: tagging peers from {}", dir.display());
+            Some(lookup)
+        }
+        Err(e) => {
+            warn!("🌍 GeoIP disabled: {}", e);
+            None
+        }
+    });
+    // Outbound subnet, AS and country limits and anchors against eclipse attacks
+    let mut peer_diversity = PeerDiversity::new(node_net_config.diversity.clone());
+    if let Some(lookup) = &geoip {
+        peer_diversity = peer_diversity.with_asn_lookup(Box::new(Arc::clone(lookup)));
+    }
+    let mut peer_geo = PeerGeo::new(geoip);
+    let node_metrics = MetricsCollector::new();
+
+    let mut swarm: Swarm<axiom_core::network_legacy::TimechainBehaviour> =
+        init_network_with_config(bootstrap_peers, &node_net_config).await
+            .map_err(|e| -> Box<dyn Error> { e })?;
+    for peer in lock_or_recover(&guardian_enforcer).banned(unix_now()) {
+        if let Ok(peer_id) = peer.parse::<PeerId>() {
+            swarm.behaviour_mut().gossipsub.blacklist_peer(&peer_id);
+        }
+    }
 
     // Port Binding Logic
-    let is_genesis = std::env::var("AXIOM_GENESIS_NODE").unwrap_or_default() == "1";
-    let (port_start, port_end) = if is_genesis { (6000, 6003) } else { (6000, 6999) };
+    let is_genesis = node_config.node.genesis_node;
+    let port_start = node_config.network.p2p_port_start;
+    let port_end = if is_genesis { port_start.saturating_add(3).min(node_config.network.p2p_port_end) } else { node_config.network.p2p_port_end };
 
     let mut current_port = port_start;
     loop {
         let addr: Multiaddr = format!("/ip4/0.0.0.0/tcp/{}", current_port).parse()?;
         match swarm.listen_on(addr.clone()) {
             Ok(_) => {
-                println!("🌐 Node successfully bound to port: {}", current_port);
-                println!("🆔 PeerId: {}", swarm.local_peer_id());
-                println!("🔊 Listening on: {}", addr);
+                info!("🌐 Node successfully bound to port: {}", current_port);
+                info!("🆔 PeerId: {}", swarm.local_peer_id());
+                info!("🔊 Listening on: {}", addr);
+                if node_net_config.enable_quic {
+                    match swarm.listen_on(quic_multiaddr(current_port)) {
+                        Ok(_) => info!("🔊 Listening on: {} (QUIC)", quic_multiaddr(current_port)),
+                        Err(e) => warn!("⚠️  QUIC listen failed on UDP {}: {:?} — continuing with TCP only", current_port, e),
+                    }
+                }
 
                 // Display usable connection strings for other nodes.
-                // Prefer AXIOM_EXTERNAL_IP env var (operator-set), otherwise
-                // guide the operator to set it.
-                let external_ip = std::env::var("AXIOM_EXTERNAL_IP").unwrap_or_default();
-                if !external_ip.is_empty() {
-                    println!("🌍 Public address: /ip4/{}/tcp/{}/p2p/{}", external_ip, current_port, swarm.local_peer_id());
-                    println!("[DIAG] To connect another node, set:");
-                    println!("   AXIOM_BOOTSTRAP_PEERS=/ip4/{}/tcp/{}/p2p/{}", external_ip, current_port, swarm.local_peer_id());
+                // Prefer the operator-set external_ip (AXIOM_EXTERNAL_IP),
+                // otherwise guide the operator to set it.
+                if let Some(external_ip) = node_config.network.external_ip {
+                    info!("🌍 Public address: /ip4/{}/tcp/{}/p2p/{} — other nodes can set AXIOM_BOOTSTRAP_PEERS to it",
+                        external_ip, current_port, swarm.local_peer_id());
                 } else {
-                    println!("⚠️  AXIOM_EXTERNAL_IP not set — other nodes cannot find you.");
-                    println!("   Set your public IP so peers can connect:");
-                    println!("   export AXIOM_EXTERNAL_IP=<YOUR_PUBLIC_IP>");
-                    println!("   Then share this with peers:");
-                    println!("   AXIOM_BOOTSTRAP_PEERS=/ip4/<YOUR_PUBLIC_IP>/tcp/{}/p2p/{}", current_port, swarm.local_peer_id());
+                    warn!("⚠️  AXIOM_EXTERNAL_IP not set — other nodes cannot find you. Export your public IP, \
+                        then share AXIOM_BOOTSTRAP_PEERS=/ip4/<YOUR_PUBLIC_IP>/tcp/{}/p2p/{}", current_port, swarm.local_peer_id());
                 }
                 break;
             }
             Err(_e) => {
                 if current_port < port_end {
-                    println!("⚠️  Port {} busy. Trying {}...", current_port, current_port + 1);
+                    warn!("⚠️  Port {} busy. Trying {}...", current_port, current_port + 1);
                     current_port += 1;
                 } else {
                     return Err(format!("No available ports in range {}-{}", port_start, port_end).into());
@@ -235,28 +689,22 @@ async fn main() -> Result<(), Box<dyn Error>> {
     }
 
     // 3. MULTI-VECTOR BOOTSTRAP (DiscoveryStrategy)
-    println!("🌍 Bootstrap Configuration (Multi-Vector):");
+    info!("🌍 Bootstrap Configuration (Multi-Vector):");
     let mut bootstrap_connected = 0;
     let mut bootstrap_addrs: Vec<(String, Multiaddr)> = Vec::new();
 
-    // Environment variable overrides all strategies
-    let env_bootstrap_peers: Vec<String> = std::env::var("AXIOM_BOOTSTRAP_PEERS")
-        .unwrap_or_default()
-        .split(',')
-        .filter(|s| !s.trim().is_empty())
-        .map(|s| s.trim().to_string())
-        .collect();
-
-    let resolved_addrs = if !env_bootstrap_peers.is_empty() {
-        println!("   📌 Using AXIOM_BOOTSTRAP_PEERS environment variable");
-        env_bootstrap_peers
+    // Configured peers (bootstrap_peers / AXIOM_BOOTSTRAP_PEERS) override
+    // all strategies
+    let resolved_addrs = if !node_config.network.bootstrap_peers.is_empty() {
+        info!("📌 Using configured bootstrap_peers");
+        node_config.network.bootstrap_peers.clone()
     } else if let Ok(bootstrap_content) = std::fs::read_to_string("config/bootstrap.toml") {
         // Fallback to config file
         let mut addrs = Vec::new();
         if let Ok(bootstrap_config) = toml::from_str::<toml::Value>(&bootstrap_content) {
             if let Some(bootnodes) = bootstrap_config.get("bootnodes").and_then(|v| v.as_array()) {
                 if !bootnodes.is_empty() {
-                    println!("   📌 Using config/bootstrap.toml addresses");
+                    info!("📌 Using config/bootstrap.toml addresses");
                     for bootnode in bootnodes {
                         if let Some(s) = bootnode.as_str() {
                             addrs.push(s.to_string());
@@ -269,15 +717,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
     } else {
         // Use multi-vector discovery strategies from NetworkConfig
         let net_config = NetworkConfig::default();
-        println!("   📌 Using {} discovery strategies:", net_config.discovery_strategies.len());
-        for (i, strat) in net_config.discovery_strategies.iter().enumerate() {
-            let label = match strat {
-                DiscoveryStrategy::StaticList(v) => format!("StaticList ({} addrs)", v.len()),
-                DiscoveryStrategy::KademliaDHT { protocol } => format!("KademliaDHT ({})", protocol),
-                DiscoveryStrategy::DnsDiscovery { domain } => format!("DnsDiscovery ({})", domain),
-            };
-            println!("      {}. {}", i + 1, label);
-        }
+        let labels: Vec<String> = net_config.discovery_strategies.iter().map(|strat| match strat {
+            DiscoveryStrategy::StaticList(v) => format!("StaticList ({} addrs)", v.len()),
+            DiscoveryStrategy::KademliaDHT { protocol } => format!("KademliaDHT ({})", protocol),
+            DiscoveryStrategy::DnsDiscovery { domain } => format!("DnsDiscovery ({})", domain),
+        }).collect();
+        info!("📌 Using {} discovery strategies: {}", labels.len(), labels.join(", "));
         net_config.resolve_all_bootstrap_addrs()
     };
 
@@ -286,75 +731,119 @@ async fn main() -> Result<(), Box<dyn Error>> {
             bootstrap_addrs.push((addr_str.clone(), addr.clone()));
             match swarm.dial(addr.clone()) {
                 Ok(_) => {
-                    println!("   ✅ Dialing bootstrap node: {}", addr_str);
+                    info!("✅ Dialing bootstrap node: {}", addr_str);
                     bootstrap_connected += 1;
                 }
-                Err(e) => println!("   ⚠️  Failed to dial {}: {:?}", addr_str, e),
+                Err(e) => warn!("⚠️  Failed to dial {}: {:?}", addr_str, e),
             }
         }
     }
 
     if bootstrap_connected == 0 {
-        println!("   🌐 Using mDNS and Discv5 for peer discovery");
+        info!("🌐 Using mDNS and Discv5 for peer discovery");
     } else {
-        println!("   ✅ {} bootstrap nodes queued for connection", bootstrap_connected);
+        info!("✅ {} bootstrap nodes queued for connection", bootstrap_connected);
+    }
+
+    // 3b. PEERSTORE: redial peers remembered from previous runs
+    let mut peerstore = PeerStore::load(DEFAULT_PEERSTORE_PATH);
+    let mut remembered = 0;
+    for addr in peerstore.dialable(unix_now()) {
+        if bootstrap_addrs.iter().any(|(_, b)| *b == addr) {
+            continue;
+        }
+        if swarm.dial(addr).is_ok() {
+            remembered += 1;
+        }
+    }
+    if remembered > 0 {
+        info!("📒 Redialing {} remembered peers ({} addresses known)", remembered, peerstore.len());
+    }
+
+    // 3a. CIRCUIT RELAYS
+    // With AutoNAT enabled we wait until the node is confirmed private
+    // before reserving relay slots; without it we reserve up front.
+    let mut relay_listening = false;
+    if !node_net_config.nat.enable_autonat && !relay_addrs.is_empty() {
+        for addr in &relay_addrs {
+            if let Err(e) = swarm.listen_on(addr.clone().with(Protocol::P2pCircuit)) {
+                warn!("⚠️  Relay listen failed on {}: {:?}", addr, e);
+            }
+        }
+        relay_listening = true;
     }
 
     // 3b. DISCV5 PEER DISCOVERY (UDP Radar)
     // Discv5 runs externally alongside the libp2p Swarm, not inside NetworkBehaviour.
     // It scans the network (UDP) and discovered peers are manually dialed by the Swarm (TCP).
     let discv5_udp_port = current_port as u32 + 3000;
-    let discv5_service = if discv5_udp_port <= 65535 {
+    let discv5_service = if !node_config.network.enable_discv5 {
+        info!("ℹ️  Discv5 disabled (network.enable_discv5 = false)");
+        None
+    } else if discv5_udp_port <= 65535 {
         let discv5_listen_addr: std::net::SocketAddr = format!("0.0.0.0:{}", discv5_udp_port)
             .parse()
             .expect("valid socket addr");
         let discv5_key = discv5::enr::CombinedKey::generate_secp256k1();
         let boot_enrs = default_bootstrap_enrs();
 
-        match Discv5Service::new(discv5_listen_addr, discv5_key, boot_enrs).await {
+        let external_ip: Option<IpAddr> = node_config.network.external_ip;
+        match Discv5Service::new(discv5_listen_addr, discv5_key, boot_enrs, current_port, external_ip).await {
             Ok(svc) => {
-                println!("🔍 Discv5 discovery active on UDP port {}", discv5_udp_port);
+                info!("🔍 Discv5 discovery active on UDP port {}", discv5_udp_port);
+                info!("ENR: {}", svc.export_enr().await);
                 Some(svc)
             }
             Err(e) => {
-                println!("⚠️  Discv5 init warning (falling back to mDNS only): {}", e);
+                warn!("⚠️  Discv5 init warning (falling back to mDNS only): {}", e);
                 None
             }
         }
     } else {
-        println!("⚠️  Discv5 UDP port {} exceeds valid range, falling back to mDNS only", discv5_udp_port);
+        warn!("⚠️  Discv5 UDP port {} exceeds valid range, falling back to mDNS only", discv5_udp_port);
         None
     };
     let mut discv5_lookup_timer = time::interval(Duration::from_secs(30));
+    let mut external_ip_votes = ExternalIpVotes::default();
 
     // 4. TOPICS
-    let req_topic = gossipsub::IdentTopic::new("timechain-request");
-    let chain_topic = gossipsub::IdentTopic::new("timechain-chain");
     let blocks_topic = gossipsub::IdentTopic::new("timechain-blocks");
     let tx_topic = gossipsub::IdentTopic::new("timechain-transactions");
+    let tx_announce_topic = gossipsub::IdentTopic::new(TX_ANNOUNCE_TOPIC);
     let pulse_topic = gossipsub::IdentTopic::new("axiom/realtime/pulse/v1");
     let health_topic = gossipsub::IdentTopic::new("axiom/health/trust-pulse/v1");
-
-    // Subscribe to topics
-    swarm.behaviour_mut().gossipsub.subscribe(&req_topic)?;
-    swarm.behaviour_mut().gossipsub.subscribe(&chain_topic)?;
-    swarm.behaviour_mut().gossipsub.subscribe(&blocks_topic)?;
-    swarm.behaviour_mut().gossipsub.subscribe(&tx_topic)?;
+    // Approved consensus parameter changes and their activation heights.
+    let proposals_topic = gossipsub::IdentTopic::new("axiom/consensus/proposals/v1");
+    // Halt notices and resume authorizations.
+    let safety_topic = gossipsub::IdentTopic::new(SAFETY_TOPIC);
+
+    // Subscribe to topics. Full-body tx gossip is kept for nodes that
+    // predate announcements; our own and relayed transactions go out as
+    // announcements only. A halted node stays off the intake topics.
+    let intake_topics = [blocks_topic.clone(), tx_topic.clone(), tx_announce_topic.clone()];
+    if !safe_mode.is_halted() {
+        for topic in &intake_topics {
+            swarm.behaviour_mut().gossipsub.subscribe(topic)?;
+        }
+    }
     swarm.behaviour_mut().gossipsub.subscribe(&pulse_topic)?;
     swarm.behaviour_mut().gossipsub.subscribe(&health_topic)?;
+    swarm.behaviour_mut().gossipsub.subscribe(&proposals_topic)?;
+    swarm.behaviour_mut().gossipsub.subscribe(&safety_topic)?;
 
-    // Request chains from network
-    let _ = swarm.behaviour_mut().gossipsub.publish(req_topic.clone(), b"REQ_CHAIN".to_vec());
+    // Chain sync runs over the request-response protocol (see
+    // network_legacy::ChainRequest); peers are asked for headers as soon
+    // as they connect.
 
     // 5. START OPENCLAW
-    println!("🤖 Initializing OpenClaw automation...");
+    info!("🤖 Initializing OpenClaw automation...");
     let _openclaw_handle = match axiom_core::openclaw_integration::start_openclaw_background().await {
         Ok(handle) => {
-            println!("✅ OpenClaw started in background");
+            info!("✅ OpenClaw started in background");
             Some(handle)
         }
         Err(e) => {
-            println!("⚠️  OpenClaw startup warning: {}", e);
+            warn!("⚠️  OpenClaw startup warning: {}", e);
             None
         }
     };
@@ -368,123 +857,190 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let api_state = Arc::new(Mutex::new(PulseApiState {
         protocol_phase: PROTOCOL_PHASE.to_string(),
         current_height: tc.blocks.len() as u64,
+        difficulty: tc.difficulty,
         supply_remaining_units: initial_remaining,
         supply_remaining_axm: format_axm_supply(initial_remaining),
         trust_pulse: String::new(),
         zk_verified: false,
+        is_syncing: false,
+        network_height: tc.blocks.len() as u64,
+        connected_peers: 0,
+        stale_blocks: 0,
+        orphan_rate: 0.0,
     }));
+    // Liveness: the event loop beats on every dashboard tick and while
+    // mining, so /healthz fails only if the loop is wedged.
+    let heartbeat = Arc::new(Heartbeat::new(unix_now()));
 
     // Pulse history: ring buffer of the last N chained pulses (for /v1/pulse/history)
     let pulse_history: Arc<Mutex<VecDeque<PulseHistoryEntry>>> =
         Arc::new(Mutex::new(VecDeque::with_capacity(PULSE_HISTORY_CAPACITY)));
 
-    // Rate limiter: 60 requests per minute per IP (DoS protection)
-    let rate_limiter: Arc<RateLimiter<SocketAddr, DashMapStateStore<SocketAddr>, DefaultClock>> =
+    // Pulse log: the recent unbroken run of block pulses, from which light
+    // clients get a supply proof (/v1/supply/proof)
+    let pulse_log: Arc<Mutex<PulseLog>> = Arc::new(Mutex::new(PulseLog::new(genesis_pulse_anchor)));
+    let pulse_validator = PulseValidator::new();
+
+    // Change feed: ordered block/tx connect and disconnect events for
+    // indexers (served on /v1/changes?since=<cursor>)
+    let change_feed: Arc<Mutex<ChangeFeed>> = Arc::new(Mutex::new(ChangeFeed::default()));
+
+    // Address index: per-address rewards and transfers, backing the wallet
+    // analytics endpoint (/v1/wallet/<address>/analytics)
+    let address_index: Arc<Mutex<AddressIndex>> = Arc::new(Mutex::new(AddressIndex::from_blocks(&tc.blocks)));
+
+    // Receipt index: TXID -> receipt with Merkle inclusion proof, backing
+    // /v1/tx/<hash>/receipt
+    let receipt_index: Arc<Mutex<ReceiptIndex>> = Arc::new(Mutex::new(ReceiptIndex::from_blocks(&tc.blocks)));
+
+    // Block index: canonical block bodies for explorers, paged by height
+    // on /v1/blocks
+    let block_index: Arc<Mutex<BlockIndex>> = Arc::new(Mutex::new(BlockIndex::from_blocks(&tc.blocks)));
+
+    // Pending index: copy of the mempool refreshed by the event loop,
+    // served on /v1/mempool and /v1/mempool/<hash>
+    let pending_index: Arc<Mutex<PendingIndex>> = Arc::new(Mutex::new(PendingIndex::new()));
+
+    // Account state: balance, nonce and tx pointers per address, copied
+    // from the chain whenever it changes (/v1/account/<address>)
+    let account_state: Arc<Mutex<State>> = Arc::new(Mutex::new(tc.state.clone()));
+
+    // Supply: mined, circulating and halving figures from the economics
+    // schedule, refreshed with the account state (/v1/supply)
+    let supply: Arc<Mutex<SupplySnapshot>> = Arc::new(Mutex::new(tc.supply_snapshot()));
+
+    // Fee-rate percentiles over recent blocks, refreshed with the supply
+    // figures, for wallet fee estimates (/v1/fees)
+    let fee_stats: Arc<Mutex<FeeStats>> = Arc::new(Mutex::new(FeeStats::from_blocks(&tc.blocks)));
+
+    // Trust Pulse checkpoints: reorgs below the latest are refused
+    // (/v1/checkpoints, persisted so operators can seed new nodes)
+    let checkpoints: Arc<Mutex<Checkpoints>> = Arc::new(Mutex::new(Checkpoints::load(CHECKPOINTS_PATH)));
+    if let Some(latest) = lock_or_recover(&checkpoints).latest() {
+        info!(height = latest.height, block = %latest.block_hash, "🏁 Latest checkpoint: H-{}", latest.height);
+    }
+
+    // Contract registry: image ID -> name, version, source and audit,
+    // served on /v1/contracts for wallets and the explorer
+    let contract_registry: Arc<Mutex<ContractRegistry>> =
+        Arc::new(Mutex::new(ContractRegistry::load(DEFAULT_REGISTRY_PATH)));
+    if !lock_or_recover(&contract_registry).is_empty() {
+        info!("📇 Contract registry: {} contracts", lock_or_recover(&contract_registry).len());
+    }
+
+    // Submitted transactions go through the same admission as gossiped ones,
+    // in the event loop, and the sender gets the verdict back.
+    let (tx_submissions, mut tx_submission_rx) = tokio::sync::mpsc::unbounded_channel::<TxSubmission>();
+    // Mempool candidates go to the security engine on blocking threads;
+    // its decisions come back here. Until then, and while quarantined,
+    // they are held in `screening`.
+    let (screened_sender, mut screened_rx) = tokio::sync::mpsc::unbounded_channel::<ScreenedTx>();
+    let mut screening = Screening::new();
+
+    // Admin RPC: token-authenticated calls posted to /v1/admin/rpc are
+    // carried out by the event loop, which owns the swarm and mempool.
+    let (admin_calls, mut admin_rx) = tokio::sync::mpsc::unbounded_channel::<AdminCall>();
+    let admin_token = match AdminToken::load_or_create(ADMIN_TOKEN_FILE) {
+        Ok(token) => Some(token),
+        Err(e) => {
+            warn!("⚠️  ADMIN: Cannot read or create {}: {} — admin RPC disabled", ADMIN_TOKEN_FILE, e);
+            None
+        }
+    };
+
+    // Rate limiter: [rpc] rate_limit requests per minute per IP (default
+    // 60; DoS protection). validate() keeps it within 1..=u32::MAX.
+    let api_rate_limit = node_config.rpc.rate_limit as u32;
+    let rate_limiter: Arc<ApiRateLimiter> =
         Arc::new(RateLimiter::dashmap(
-            Quota::per_minute(NonZeroU32::new(API_RATE_LIMIT_PER_MINUTE).unwrap()),
+            Quota::per_minute(NonZeroU32::new(api_rate_limit).unwrap()),
         ));
 
     {
-        let api_state_status = Arc::clone(&api_state);
-        let rate_limiter_status = Arc::clone(&rate_limiter);
-
-        let status_route = warp::path!("v1" / "status")
-            .and(warp::get())
-            .and(warp::addr::remote())
-            .and_then(move |addr: Option<SocketAddr>| {
-                let api_state = Arc::clone(&api_state_status);
-                let limiter = Arc::clone(&rate_limiter_status);
-                async move {
-                    // Use the client's socket address for rate-limiting; fall back
-                    // to loopback when warp can't determine the remote addr.
-                    let peer_addr = addr.unwrap_or(SocketAddr::from(([127, 0, 0, 1], 0)));
-                    if limiter.check_key(&peer_addr).is_err() {
-                        return Err(warp::reject::custom(TooManyRequests));
-                    }
-                    let state = api_state.lock().unwrap().clone();
-                    Ok::<_, warp::Rejection>(warp::reply::json(&state))
-                }
-            });
-
-        let version_route = warp::path!("v1" / "version")
-            .and(warp::get())
-            .map(|| {
-                let info = serde_json::json!({
-                    "version": build::PKG_VERSION,
-                    "build_time_utc": build::BUILD_TIME,
-                    "git_commit_hash": build::SHORT_COMMIT,
-                    "rustc_version": build::RUST_VERSION,
-                });
-                warp::reply::json(&info)
-            });
-
-        let health_check_route = warp::path!("v1" / "health" / "check")
-            .and(warp::get())
-            .map(|| warp::reply::with_status("ALIVE", warp::http::StatusCode::OK));
-
-        // Pulse history: returns the last 10 chained pulses so anyone with
-        // a browser can verify the prev_pulse_hash links.
-        let pulse_history_api = Arc::clone(&pulse_history);
-        let pulse_history_route = warp::path!("v1" / "pulse" / "history")
-            .and(warp::get())
-            .map(move || {
-                let history = pulse_history_api.lock().unwrap();
-                let entries: Vec<PulseHistoryEntry> = history.iter().cloned().collect();
-                warp::reply::json(&entries)
-            });
-
-        let routes = status_route
-            .or(version_route)
-            .or(health_check_route)
-            .or(pulse_history_route)
+        let api = ApiContext {
+            rate_limiter: Arc::clone(&rate_limiter),
+            api_state: Arc::clone(&api_state),
+            heartbeat: Arc::clone(&heartbeat),
+            storage_dir,
+            ready_min_peers: node_config.rpc.ready_min_peers,
+            pulse_history: Arc::clone(&pulse_history),
+            pulse_log: Arc::clone(&pulse_log),
+            change_feed: Arc::clone(&change_feed),
+            block_index: Arc::clone(&block_index),
+            pending_index: Arc::clone(&pending_index),
+            address_index: Arc::clone(&address_index),
+            receipt_index: Arc::clone(&receipt_index),
+            account_state: Arc::clone(&account_state),
+            supply: Arc::clone(&supply),
+            fee_stats: Arc::clone(&fee_stats),
+            checkpoints: Arc::clone(&checkpoints),
+            contract_registry: Arc::clone(&contract_registry),
+            block_archive: block_archive.clone(),
+            state_diffs: state_diffs.clone(),
+            node_metrics: node_metrics.clone(),
+            discv5_service: discv5_service.clone(),
+            guardian_events: Arc::clone(&guardian_events),
+            guardian_audit: Arc::clone(&guardian_audit),
+            guardian_enforcer: Arc::clone(&guardian_enforcer),
+            ai_guardian: Arc::clone(&ai_guardian),
+            security_engine: Arc::clone(&security_engine),
+            signer: Arc::new(Wallet { secret_key: wallet.secret_key, address: wallet.address }),
+            key_audit: key_audit.clone(),
+            tx_submissions,
+            admin_token: admin_token.clone(),
+        };
+
+        let routes = probe_routes(&api)
+            .or(chain_routes(&api))
+            .or(archive_routes(&api))
+            .or(network_routes(&api))
+            .or(transaction_routes(&api))
+            .or(account_routes(&api))
+            .or(contract_routes(&api))
+            .or(guardian_routes(&api))
+            .or(log_level_admin_route(Arc::clone(&log_control), admin_token.clone()))
+            .or(admin_rpc_route(admin_token.clone(), admin_calls));
+
+        #[cfg(feature = "chaos")]
+        let routes = routes.or(chaos_admin_route(admin_token));
+
+        let routes = routes
             // CORS: allow any origin with GET-only methods. This is safe because
-            // the API is read-only, unauthenticated, and carries no credentials.
-            // It enables external dashboard websites to query pulse history.
+            // reads are unauthenticated and carry no credentials; the public
+            // writes (contract registration, transaction submission) are signed
+            // and posted by tools, not cross-origin pages. It enables external dashboards to query pulse history.
             .with(warp::cors()
                 .allow_any_origin()
                 .allow_methods(vec!["GET"])
                 .allow_headers(vec!["Content-Type"]))
             .recover(handle_rejection);
 
-        // Configurable API bind address: read from API_BIND_ADDRESS or
-        // AXIOM_API_BIND env var, then fall back to the config file
-        // (config/axiom.toml → [rpc] listen_address). Default: 127.0.0.1
-        // (localhost only). Set to 0.0.0.0 for public-facing nodes.
-        let api_bind: IpAddr = std::env::var("API_BIND_ADDRESS")
-            .or_else(|_| std::env::var("AXIOM_API_BIND"))
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .or_else(|| {
-                match axiom_core::config::AxiomConfig::load() {
-                    Ok(cfg) => {
-                        // listen_address may be "ip:port" or just "ip"
-                        let addr_str = &cfg.rpc.listen_address;
-                        addr_str.split(':').next()
-                            .map(|ip| ip.trim())
-                            .and_then(|ip| ip.parse().ok())
-                    }
-                    Err(e) => {
-                        eprintln!("⚠️  Config file load warning: {} — using default bind address", e);
-                        None
-                    }
-                }
-            })
-            .unwrap_or_else(|| [0, 0, 0, 0].into());
-        let api_port: u16 = 8080;
+        // API bind address and port: [rpc] listen_address / api_port in
+        // axiom.toml, or API_BIND_ADDRESS / AXIOM_API_BIND and AXIOM_API_PORT.
+        // Default 0.0.0.0:8080; set 127.0.0.1 for a local-only API.
+        let api_bind: IpAddr = node_config.api_bind_ip()?;
+        let api_port: u16 = node_config.rpc.api_port;
 
         // Contextual API logging: inform the operator about the bind mode.
         if api_bind.is_loopback() {
-            println!("💡 TIP: API is local-only (127.0.0.1). To enable public observability, set API_BIND_ADDRESS=0.0.0.0:{}.", api_port);
+            info!("💡 TIP: API is local-only (127.0.0.1). To enable public observability, set API_BIND_ADDRESS=0.0.0.0:{}.", api_port);
         } else {
-            println!("⚠️  WARNING: API is publicly accessible at {}:{}. Rate-limiting is active ({} req/min).", api_bind, api_port, API_RATE_LIMIT_PER_MINUTE);
+            warn!("⚠️  WARNING: API is publicly accessible at {}:{}. Rate-limiting is active ({} req/min).", api_bind, api_port, api_rate_limit);
         }
 
         tokio::spawn(async move {
-            println!("🌐 Public Pulse API: http://{}:{}/v1/status", api_bind, api_port);
-            println!("🌐 Version endpoint: http://{}:{}/v1/version", api_bind, api_port);
-            println!("🌐 Health check:     http://{}:{}/v1/health/check", api_bind, api_port);
-            println!("🌐 Pulse history:    http://{}:{}/v1/pulse/history", api_bind, api_port);
-            println!("🌐 CORS:             enabled (any origin, GET only)");
+            info!("🌐 Public Pulse API: http://{}:{}/v1/status", api_bind, api_port);
+            info!("🌐 Version endpoint: http://{}:{}/v1/version", api_bind, api_port);
+            info!("🌐 Health check:     http://{}:{}/v1/health/check", api_bind, api_port);
+            info!("🌐 Probes:           http://{}:{}/healthz, /readyz", api_bind, api_port);
+            info!("🌐 Pulse history:    http://{}:{}/v1/pulse/history", api_bind, api_port);
+            info!("🌐 Pulse chain:      http://{}:{}/v1/pulses?from=0", api_bind, api_port);
+            info!("🌐 Change feed:      http://{}:{}/v1/changes?since=0", api_bind, api_port);
+            info!("🌐 Block bodies:     http://{}:{}/v1/blocks?from=0", api_bind, api_port);
+            info!("🌐 Mempool:          http://{}:{}/v1/mempool", api_bind, api_port);
+            info!("🌐 Threat feed:      http://{}:{}/v1/threat-intel/feed", api_bind, api_port);
+            info!("🌐 Admin RPC:        http://127.0.0.1:{}/v1/admin/rpc (bearer token in {})", api_port, ADMIN_TOKEN_FILE);
+            info!("🌐 CORS:             enabled (any origin, GET only)");
             warp::serve(routes)
                 .run(SocketAddr::new(api_bind, api_port))
                 .await;
@@ -493,9 +1049,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     // 7. TIMERS AND STATE
     let mut last_vdf = Instant::now();
+    let mut mining_enabled = node_config.mining.enabled;
+    let (mut miner, mut miner_events) = Miner::new(node_config.mining.threads);
+    let max_block_bytes = node_config.consensus.max_block_size;
+    let mut mining_job: Option<PendingMiningJob> = None;
+    let mut stale_blocks = StaleBlocks::default();
     let mut last_diff = tc.difficulty;
     let mut last_bootstrap_retry = Instant::now();
-    let mut last_pulse_hash: [u8; 64] = genesis_pulse_anchor;
+    let mut last_pulse_hash: [u8; 64] = genesis_pulse_anchor.0;
     let mut last_block_received = Instant::now(); // For NN block_interval feature
     let mut ai_fallback_count: u32 = 0; // Track AI-bypassed transactions
 
@@ -503,17 +1064,79 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mut dashboard_timer = time::interval(Duration::from_secs(30));
     let mut throttle_reset = time::interval(Duration::from_secs(60));
     let mut tx_broadcast_timer = time::interval(Duration::from_secs(30));
+    let mut tx_announce_timer = time::interval(Duration::from_secs(2));
     let mut chain_sync_timer = time::interval(Duration::from_secs(300));
     let mut bootstrap_retry_timer = time::interval(Duration::from_secs(120));
+    let mut ping_timer = time::interval(Duration::from_secs(PING_INTERVAL_SECS));
+    let mut guardian_retrain_timer = time::interval(Duration::from_secs(RETRAIN_INTERVAL_SECS));
+    // Retraining runs on a blocking thread against a copy of the guardian;
+    // the trained copy comes back here to be installed and checkpointed.
+    let (guardian_trained, mut guardian_trained_rx) = tokio::sync::mpsc::unbounded_channel::<(NeuralGuardian, ModelUpdate, usize)>();
+    let mut guardian_training = false;
     let _cross_network_discovery = time::interval(Duration::from_secs(30));
 
     let mut connected_peers: HashSet<PeerId> = HashSet::new();
-    let _known_peers: Vec<String> = std::env::var("AXIOM_KNOWN_PEERS")
-        .unwrap_or_default()
-        .split(',')
-        .filter(|s| !s.trim().is_empty())
-        .map(|s| s.trim().to_string())
-        .collect();
+    let mut tx_relay = TxRelay::new();
+
+    // Guardian risk scores for admitted transactions, consulted by the
+    // block template. AXIOM_MAX_TX_RISK (0-100) makes the miner leave out
+    // transactions scoring above it; unset means include everything valid.
+    let mut tx_risk_scores: HashMap<[u8; 32], f64> = HashMap::new();
+    let risk_policy = match node_config.ai.max_tx_risk {
+        Some(threshold) => {
+            info!("🛡️  Block template risk policy: exclude transactions scoring above {:.1}", threshold);
+            RiskPolicy::conservative(threshold)
+        }
+        None => RiskPolicy::permissive(),
+    };
+    let mut nat_status = autonat::NatStatus::Unknown;
+    let mut peer_sync_tips: HashMap<PeerId, u64> = HashMap::new();
+    // Sync state: mining pauses while we are catching up to the network.
+    let (mut sync_manager, mut sync_events) = SyncManager::new(tc.blocks.len() as u64);
+    let mut block_request_in_flight: Option<BlockRequest> = None;
+    // Warned once: peers are on a breaking wire version we cannot read.
+    let mut newer_wire_seen = false;
+    // Blocks served to syncing peers, encoded once and reused.
+    let mut encoded_blocks = EncodedBlockCache::new(ENCODED_BLOCK_CACHE_BYTES);
+    // Latency pings: RTT samples per peer feed block-download peer
+    // selection and the guardian's propagation_time feature.
+    let mut peer_latency: HashMap<PeerId, PeerMetrics> = HashMap::new();
+    let mut pings_in_flight: HashMap<request_response::OutboundRequestId, (PeerId, u64, Instant)> = HashMap::new();
+    let _known_peers: Vec<String> = node_config.network.known_peers.clone();
+
+    // Resume from an upgrade handoff left by the previous process: redial
+    // its peers, re-admit its mempool and keep its place in the VDF window.
+    match Handoff::take(HANDOFF_FILE, unix_now()) {
+        Ok(Some(handoff)) => {
+            for addr in handoff.peers.iter().filter_map(|a| a.parse::<Multiaddr>().ok()) {
+                let _ = swarm.dial(addr);
+            }
+            let restored = handoff.mempool.iter().filter(|tx| screen_transaction((*tx).clone(), TxOrigin::Restored,
+                &tc, &mempool, &mut screening, &sender_limits, &ai_bridge, &address_index, &screened_sender).is_ok()).count();
+            let vdf_elapsed = handoff.vdf_elapsed(unix_now());
+            last_vdf = Instant::now().checked_sub(vdf_elapsed).unwrap_or(last_vdf);
+            info!("🔁 HANDOFF: Resumed from v{} at H-{} | {} peers | {}/{} txs | VDF window {}s in",
+                handoff.from_version, handoff.chain_height, handoff.peers.len(),
+                restored, handoff.mempool.len(), vdf_elapsed.as_secs());
+        }
+        Ok(None) => {}
+        Err(e) => warn!("⚠️  HANDOFF: Ignoring previous handoff: {}", e),
+    }
+    // Transactions that were pending when the node last shut down.
+    match take_mempool(MEMPOOL_FILE) {
+        Ok(saved) if !saved.is_empty() => {
+            let restored = saved.iter().filter(|tx| screen_transaction((*tx).clone(), TxOrigin::Restored,
+                &tc, &mempool, &mut screening, &sender_limits, &ai_bridge, &address_index, &screened_sender).is_ok()).count();
+            info!("📥 Restored {}/{} pending transactions from {} for screening", restored, saved.len(), MEMPOOL_FILE);
+        }
+        Ok(_) => {}
+        Err(e) => warn!("⚠️  Ignoring saved mempool: {}", e),
+    }
+
+    // SIGINT/SIGTERM shut the node down, SIGUSR2 hands off to a new
+    // binary. Either one cancels the block being mined.
+    let stopping = Arc::new(AtomicBool::new(false));
+    let mut stop_rx = listen_for_signals(stopping.clone());
 
     // 8. MAIN EVENT LOOP
     loop {
@@ -523,6 +1146,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 SwarmEvent::Behaviour(TimechainBehaviourEvent::Gossipsub(gossipsub::Event::Message {
                     propagation_source, message, ..
                 })) => {
+                    #[cfg(feature = "chaos")]
+                    if axiom_core::chaos::drop_gossip() {
+                        continue;
+                    }
+
                     // Rate limiting
                     let now = Instant::now();
                     let entry = peer_message_counts.entry(propagation_source).or_insert((0, now));
@@ -531,24 +1159,34 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         entry.1 = now;
                     }
                     entry.0 += 1;
+                    let limit = lock_or_recover(&guardian_enforcer).message_limit(&propagation_source.to_string(), unix_now());
+                    if limit.is_some_and(|limit| entry.0 > limit) {
+                        continue;
+                    }
 
-                    if entry.0 > 100 {
-                        println!("🚨 DoS protection: Peer {} exceeded rate limit", propagation_source);
+                    let verdict = peer_limiter.check_inbound(propagation_source, message.topic.as_str(), message.data.len());
+                    if verdict != RateLimitVerdict::Allowed {
+                        warn!("🚨 DoS protection: Peer {} exceeded its {} quota ({:?})",
+                            propagation_source, message.topic, verdict);
+                        lock_or_recover(&ai_guardian).confirm_incident(&propagation_source.to_string(), Incident::RateLimitViolation);
                         continue;
                     }
 
-                    let mut ai = ai_guardian.lock().unwrap();
+                    let mut ai = lock_or_recover(&ai_guardian);
 
                     // Record network event for EVERY peer message so the NN
                     // builds peer behavior profiles for threat detection.
                     let peer_str = propagation_source.to_string();
                     let block_interval_secs = last_block_received.elapsed().as_secs_f32();
-                    ai.record_event(peer_str.clone(), axiom_core::neural_guardian::NetworkEvent {
+                    let location = peer_geo.location(&propagation_source).cloned().unwrap_or_default();
+                    let event = axiom_core::neural_guardian::NetworkEvent {
                         peer_id: peer_str.clone(),
                         block_interval: block_interval_secs,
                         block_size: message.data.len() as f32 / 1024.0,
                         tx_count: entry.0 as f32,
-                        propagation_time: now.elapsed().as_millis() as f32,
+                        propagation_time: peer_latency.get(&propagation_source)
+                            .and_then(|m| m.median_rtt())
+                            .map_or(0.0, |rtt| rtt.as_secs_f32() * 1000.0),
                         peer_count: connected_peers.len() as f32,
                         fork_count: 0.0,
                         orphan_rate: 0.0,
@@ -559,10 +1197,30 @@ async fn main() -> Result<(), Box<dyn Error>> {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
-                    });
+                        country: location.country,
+                        asn: location.asn,
+                    };
+                    ai.record_event(peer_str.clone(), event.clone());
 
                     // Use the real NeuralGuardian threat detection
                     let assessment = ai.analyze_peer(&peer_str);
+                    if let Some(assessment) = &assessment {
+                        lock_or_recover(&guardian_events).observe(assessment,
+                            peer_subnet(&peerstore, &propagation_source), unix_now());
+                        let mut audit = lock_or_recover(&guardian_audit);
+                        if audit.changed(assessment) {
+                            let proof = ai.audit_decision(&event);
+                            audit.record_decision(assessment, event, proof, unix_now());
+                        }
+                    }
+                    // Carry out what the assessment recommends: bans, message
+                    // limits, peer rotation on eclipse suspicion.
+                    if let Some(assessment) = &assessment {
+                        let ops = lock_or_recover(&guardian_enforcer).enforce(assessment, unix_now());
+                        let penalty = apply_enforcement(&mut swarm, &mut peer_diversity, &guardian_audit,
+                            propagation_source, Some(&assessment.recommended_action), &ops);
+                        entry.0 = entry.0.saturating_add(penalty);
+                    }
                     // Unknown peers get limited trust (rate-limited below);
                     // peers with detected threats are blocked entirely.
                     let is_trustworthy = assessment
@@ -570,179 +1228,732 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         .unwrap_or(entry.0 <= 5); // Unknown peers: trust only if low message count
 
                     if is_trustworthy && entry.0 <= 15 {
-                        // Handle chain request
-                        if message.data == b"REQ_CHAIN" {
-                            if let Ok(encoded) = bincode::serialize(&tc.blocks) {
-                                let _ = swarm.behaviour_mut().gossipsub.publish(chain_topic.clone(), encoded);
-                            }
+                        let topic = message.topic.clone();
+                        // Safe mode: intake still in flight from before we
+                        // left the topics is dropped.
+                        if safe_mode.is_halted() && intake_topics.iter().any(|t| topic == t.hash()) {
+                            continue;
                         }
-                        // Handle block from peer
-                        else if message.topic == blocks_topic.hash() {
-                            if let Ok(block) = bincode::deserialize::<Block>(&message.data) {
+                        // Panic isolation covers decoding and validation only:
+                        // a payload that trips a bug there is dropped and the
+                        // sender charged. Applying a message to the chain,
+                        // mempool and indexes below returns errors instead,
+                        // as a panic there would leave them half updated.
+                        let decoded = isolate_panics(|| {
+                            let decode_failed = |e: bincode::Error| GossipError::DecodeFailed(e.to_string());
+                            Ok(if topic == blocks_topic.hash() {
+                                GossipPayload::Block(wire::decode(&message.data)?)
+                            } else if topic == tx_topic.hash() {
+                                GossipPayload::Transaction(wire::decode(&message.data)?)
+                            } else if topic == tx_announce_topic.hash() {
+                                GossipPayload::TxAnnouncement(bincode::deserialize(&message.data).map_err(decode_failed)?)
+                            } else if topic == pulse_topic.hash() {
+                                let pulse = wire::decode::<AxiomPulse>(&message.data)?;
+                                // Bogus pulses (skewed clock, broken supply law, bad
+                                // receipt, wrong signer) are rejected and charged
+                                // to the sender.
+                                let verdict = pulse_validator.validate(
+                                    &pulse, &lock_or_recover(&pulse_log), &tc.blocks, unix_now() as i64,
+                                )?;
+                                GossipPayload::Pulse(pulse, verdict)
+                            } else if topic == proposals_topic.hash() {
+                                GossipPayload::Proposal(bincode::deserialize(&message.data).map_err(decode_failed)?)
+                            } else if topic == safety_topic.hash() {
+                                GossipPayload::Safety(bincode::deserialize(&message.data).map_err(decode_failed)?)
+                            } else {
+                                GossipPayload::Other
+                            })
+                        });
+
+                        match decoded {
+                            // Handle block from peer
+                            Ok(GossipPayload::Block(block)) => {
                                 // add_block() validates the block's embedded
                                 // timestamp and computes elapsed time from the
                                 // previous block — no external timing needed.
-                                if tc.add_block(block).is_ok() {
-                                    println!("✅ Block accepted from peer. Height: {}", tc.blocks.len());
-                                    axiom_core::storage::save_chain(&tc.blocks);
-                                    // Reset VDF timer: the chain just advanced, so
-                                    // start our next mining round from now.
-                                    last_vdf = Instant::now();
-                                    last_block_received = Instant::now();
-                                }
-                            }
-                        }
-                        // Handle transaction
-                        else if message.topic == tx_topic.hash() {
-                            if let Ok(tx) = bincode::deserialize::<Transaction>(&message.data) {
-                                if tc.validate_transaction(&tx).is_ok() && !mempool.contains(&tx) {
-                                    // AI Guardian Bridge: run threat assessment before accepting
-                                    let profile = TransactionRiskProfile {
-                                        hash: hex::encode(tx.hash()),
-                                        timestamp: std::time::SystemTime::now()
-                                            .duration_since(std::time::UNIX_EPOCH)
-                                            .unwrap_or_default()
-                                            .as_secs(),
-                                        sender: hex::encode(&tx.from),
-                                        recipient: hex::encode(&tx.to),
-                                        amount: tx.amount,
-                                        gas_price: tx.fee,
-                                        zk_proof_size: tx.zk_proof.len(),
-                                        sender_history_count: 0,
-                                        recipient_history_count: 0,
-                                        sender_reputation_score: 0.5,
-                                        time_since_last_sender_tx: 0,
-                                        time_since_last_recipient_tx: 0,
-                                        is_contract_deployment: false,
-                                        contract_bytecode_size: 0,
-                                        vdf_verification_time_ms: 0,
-                                    };
-                                    let block_height = tc.blocks.len() as u64;
-                                    match ai_bridge.validate_transaction_with_guardian(profile, block_height) {
-                                        Ok(decision) if decision.approved => {
-                                            mempool.push_back(tx);
-                                            println!("✅ Transaction accepted (AI: approved)");
-                                        }
-                                        Ok(decision) => {
-                                            println!("🛡️  Transaction rejected by AI Guardian: {:?}",
-                                                decision.veto_reason.unwrap_or_else(|| "threat detected".into()));
-                                        }
-                                        Err(e) => {
-                                            // Circuit breaker or engine error — rate-limited fallback
-                                            ai_fallback_count += 1;
-                                            if ai_fallback_count <= 10 {
-                                                log::warn!("AI Guardian unavailable ({}), accepting by rule-based validation ({}/10)", e, ai_fallback_count);
-                                                mempool.push_back(tx);
-                                            } else {
-                                                log::error!("AI Guardian offline, fallback limit reached — rejecting transaction");
-                                            }
+                                match tc.add_block(block.clone()) {
+                                    Ok(_) => {
+                                        let height = tc.blocks.len() as u64 - 1;
+                                        info!(height = tc.blocks.len(), block = %hex::encode(block.legacy_hash()),
+                                            peer_id = %propagation_source,
+                                            "✅ Block accepted from peer. Height: {}", tc.blocks.len());
+                                        lock_or_recover(&change_feed).block_connected(height, &block);
+                                        lock_or_recover(&address_index).index_block(height, &block);
+                                        lock_or_recover(&receipt_index).index_block(height, &block);
+                                        lock_or_recover(&block_index).index_block(height, &block);
+                                        storage.save_chain(&tc.blocks, &tc.state);
+                                        *lock_or_recover(&account_state) = tc.state.clone();
+                                        *lock_or_recover(&supply) = tc.supply_snapshot();
+                                        *lock_or_recover(&fee_stats) = FeeStats::from_blocks(&tc.blocks);
+                                        // Reset VDF timer: the chain just advanced, so
+                                        // drop the block we were mining on the old tip
+                                        // and start our next round from now.
+                                        miner.cancel();
+                                        mining_job = None;
+                                        last_vdf = Instant::now();
+                                        last_block_received = Instant::now();
+                                        sync_manager.local_height_changed(tc.blocks.len() as u64, Instant::now());
+                                    }
+                                    Err(_) if stale_blocks.observe(&block, &tc.blocks, tc.difficulty) => {
+                                        info!(height = block.slot, block = %hex::encode(block.legacy_hash()), peer_id = %propagation_source,
+                                            "🪦 Stale block at H-{} from peer", block.slot);
+                                    }
+                                    Err(e) => {
+                                        if let Some(incident) = Incident::from_block_rejection(e) {
+                                            ai.confirm_incident(&peer_str, incident);
                                         }
                                     }
                                 }
                             }
-                        }
-                        // Handle full chain sync from peer
-                        else if message.topic == chain_topic.hash() {
-                            if let Ok(peer_blocks) = bincode::deserialize::<Vec<Block>>(&message.data) {
-                                if peer_blocks.len() > tc.blocks.len() {
-                                    // Validate the peer chain: rebuild a fresh
-                                    // Timechain from genesis and replay every block
-                                    // through full consensus validation (VDF + PoW +
-                                    // ZK + timestamp).  Each block carries its own
-                                    // timestamp; add_block() uses the delta between
-                                    // consecutive timestamps for difficulty adjustment.
-                                    let genesis_block = axiom_core::genesis::genesis();
-                                    let mut candidate_chain = Timechain::new(genesis_block);
-                                    let mut valid = true;
-                                    for b in peer_blocks.iter().skip(1) {
-                                        if candidate_chain.add_block(b.clone()).is_err() {
-                                            println!("⚠️  Peer chain rejected: invalid block at slot {}", b.slot);
-                                            valid = false;
-                                            break;
-                                        }
-                                    }
-                                    if valid && candidate_chain.blocks.len() > tc.blocks.len() {
-                                        println!("🔁 Validated & synced chain from peer. New height: {}", candidate_chain.blocks.len());
-                                        tc = candidate_chain;
-                                        axiom_core::storage::save_chain(&tc.blocks);
-                                        last_vdf = Instant::now();
-                                    }
+                            // Handle transaction
+                            Ok(GossipPayload::Transaction(tx)) => {
+                                let _ = screen_transaction(tx, TxOrigin::Peer, &tc, &mempool, &mut screening,
+                                    &sender_limits, &ai_bridge, &address_index, &screened_sender);
+                            }
+                            // Transaction announcements: pull bodies we lack
+                            Ok(GossipPayload::TxAnnouncement(announcement)) => {
+                                let ids = tx_relay.on_announcement(propagation_source, &announcement.ids, Instant::now());
+                                if !ids.is_empty() {
+                                    swarm.behaviour_mut().request_response.send_request(
+                                        &propagation_source, ChainRequest::GetTransactions { ids },
+                                    );
                                 }
                             }
-                        }
-                        // Handle real-time pulse (push-based sync)
-                        else if message.topic == pulse_topic.hash() {
-                            if let Ok(pulse) = bincode::deserialize::<AxiomPulse>(&message.data) {
+                            // Handle real-time pulse (push-based sync)
+                            Ok(GossipPayload::Pulse(pulse, verdict)) => {
+                                // Only a pulse that extends our pulse chain may
+                                // move the sync target.
+                                if verdict == PulseVerdict::Linked {
+                                    sync_manager.observe_network_height(pulse.height, Instant::now());
+                                }
+                                if lock_or_recover(&checkpoints).observe_pulse(&pulse, &tc.blocks) {
+                                    info!(height = pulse.height, "🏁 Trust Pulse checkpoint pending at H-{}", pulse.height);
+                                }
                                 if pulse.height > tc.blocks.len() as u64 {
-                                    println!("🔥 Real-time Pulse: Height {} | Mined: {} AXM | Remaining: {} AXM",
+                                    info!(height = pulse.height, peer_id = %propagation_source,
+                                        "🔥 Real-time Pulse: Height {} | Mined: {} AXM | Remaining: {} AXM{}",
                                         pulse.height,
                                         Timechain::format_axm(pulse.total_mined),
-                                        Timechain::format_axm(pulse.remaining));
+                                        Timechain::format_axm(pulse.remaining),
+                                        if verdict == PulseVerdict::Linked { "" } else { " (unlinked)" });
+                                }
+                                lock_or_recover(&pulse_log).record(pulse);
+                            }
+                            // A peer approved a consensus change: recorded for
+                            // our operator to approve, never applied as is.
+                            Ok(GossipPayload::Proposal(announcement)) => {
+                                let id = announcement.proposal.proposal_id.clone();
+                                let height = announcement.activation_height;
+                                match ai_bridge.observe_consensus_announcement(announcement, tc.blocks.len() as u64, unix_now()) {
+                                    Ok(()) => info!(peer_id = %propagation_source,
+                                        "🗳️  Consensus proposal {} announced for H-{}, awaiting operator approval", id, height),
+                                    Err(e) => warn!(peer_id = %propagation_source, "Ignoring consensus proposal {}: {}", id, e),
                                 }
                             }
+                            // Halt notices are followed only from operator and
+                            // manifest keys; anyone else's is just logged.
+                            Ok(GossipPayload::Safety(SafetyNotice::Halt(notice))) => {
+                                let (issuer, reason) = (notice.notice.issuer.clone(), notice.notice.reason.clone());
+                                match safe_mode.observe_halt(notice, unix_now()) {
+                                    Ok(true) => {
+                                        if let Some(halt) = safe_mode.halt() {
+                                            halt_node(&mut swarm, &intake_topics, &mut miner, &mut mining_job, &ai_bridge, &alerts, halt);
+                                        }
+                                    }
+                                    Ok(false) => {}
+                                    Err(e) => info!(peer_id = %propagation_source,
+                                        "🛑 Halt notice from {} not followed ({}): {}", issuer, e, reason),
+                                }
+                            }
+                            Ok(GossipPayload::Safety(SafetyNotice::Resume(auth))) => match safe_mode.resume(&auth) {
+                                Ok(halt) => resume_node(&mut swarm, &intake_topics, &ai_bridge, &halt),
+                                Err(e) => tracing::debug!(peer_id = %propagation_source, "Resume for halt {} ignored: {}", auth.halt_id, e),
+                            },
+                            Ok(GossipPayload::Other) => {}
+                            Err(GossipError::HandlerPanicked(reason)) => {
+                                log::error!("Decoding panicked on {} message from {}: {} — message dropped",
+                                    topic, peer_str, reason);
+                                entry.0 = entry.0.saturating_add(HANDLER_PANIC_PENALTY as u32);
+                            }
+                            Err(GossipError::UnsupportedWireVersion(version)) => {
+                                if !newer_wire_seen {
+                                    warn!("⬆️  Peer {} sends {} messages in wire version {}, which this node cannot read — upgrade",
+                                        peer_str, topic, version);
+                                    newer_wire_seen = true;
+                                }
+                            }
+                            Err(GossipError::InvalidPulse(rejection @ PulseRejection::ForgedSignature { .. })) => {
+                                // Provable forgery: ban rather than just charge.
+                                let assessment = ai.report_threat(&peer_str, ThreatType::ForgedMessage);
+                                ai.confirm_incident(&peer_str, Incident::ForgedPulse);
+                                lock_or_recover(&guardian_events).observe(&assessment,
+                                    peer_subnet(&peerstore, &propagation_source), unix_now());
+                                warn!("🚨 Peer {} banned: forged pulse ({})", peer_str, rejection);
+                                let ops = lock_or_recover(&guardian_enforcer).enforce(&assessment, unix_now());
+                                apply_enforcement(&mut swarm, &mut peer_diversity, &guardian_audit,
+                                    propagation_source, Some(&assessment.recommended_action), &ops);
+                            }
+                            Err(e) => {
+                                log::warn!("Invalid {} message from {}: {}", topic, peer_str, e);
+                                entry.0 = entry.0.saturating_add(INVALID_MESSAGE_PENALTY as u32);
+                            }
                         }
                     } else if entry.0 > 20 {
                         // Already recorded above with all-message tracking;
                         // just log the rate-limit breach.
-                        println!("🚨 Peer {} rate-limited ({} msgs/min, trust too low)", peer_str, entry.0);
+                        warn!("🚨 Peer {} rate-limited ({} msgs/min, trust too low)", peer_str, entry.0);
+                    }
+                }
+
+                // CHAIN SYNC: serve ranged requests from peers
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::RequestResponse(request_response::Event::Message {
+                    peer, message: request_response::Message::Request { request, channel, .. }, ..
+                })) => {
+                    let request_size = serde_json::to_vec(&request).map_or(0, |b| b.len());
+                    let quota = match request {
+                        ChainRequest::GetTransactions { .. } => TX_PULL_RATE_TOPIC,
+                        _ => SYNC_RATE_TOPIC,
+                    };
+                    let verdict = peer_limiter.check_inbound(peer, quota, request_size);
+                    if verdict != RateLimitVerdict::Allowed {
+                        warn!("🚨 DoS protection: Peer {} exceeded its {} quota ({:?}), request dropped", peer, quota, verdict);
+                        lock_or_recover(&ai_guardian).confirm_incident(&peer.to_string(), Incident::RateLimitViolation);
+                        continue;
+                    }
+                    let response = match &request {
+                        // A halted node relays no transactions.
+                        ChainRequest::GetTransactions { .. } if safe_mode.is_halted() => {
+                            ChainResponse::Transactions { txs: Vec::new() }
+                        }
+                        ChainRequest::GetTransactions { ids } => ChainResponse::Transactions {
+                            txs: serve_tx_request(&mempool, ids),
+                        },
+                        _ => serve_chain_request(&tc.blocks, &request, &mut encoded_blocks),
+                    };
+                    peer_limiter.record_outbound(peer, response.encoded_len());
+                    let _ = swarm.behaviour_mut().request_response.send_response(channel, response);
+                }
+
+                // TX RELAY: bodies we pulled after an announcement
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::RequestResponse(request_response::Event::Message {
+                    peer, message: request_response::Message::Response {
+                        response: ChainResponse::Transactions { txs }, ..
+                    }, ..
+                })) => {
+                    if safe_mode.is_halted() {
+                        continue;
+                    }
+                    for tx in txs {
+                        if !tx_relay.was_requested(&peer, &tx) {
+                            continue;
+                        }
+                        let _ = screen_transaction(tx, TxOrigin::Peer, &tc, &mempool, &mut screening,
+                            &sender_limits, &ai_bridge, &address_index, &screened_sender);
+                    }
+                }
+
+                // CHAIN SYNC: headers tell us what is missing
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::RequestResponse(request_response::Event::Message {
+                    peer, message: request_response::Message::Response {
+                        response: ChainResponse::Headers { tip_height, headers }, ..
+                    }, ..
+                })) => {
+                    peer_sync_tips.insert(peer, tip_height);
+                    sync_manager.observe_network_height(tip_height, Instant::now());
+                    let local_height = tc.blocks.len() as u64;
+                    if tip_height <= local_height || safe_mode.is_halted() {
+                        continue;
+                    }
+                    let busy = block_request_in_flight.is_some_and(|request| !request.expired(Instant::now()));
+                    if busy {
+                        continue;
+                    }
+                    match first_divergence(&tc.blocks, &headers) {
+                        Some(from) => {
+                            // Download from the lowest-latency peer that has
+                            // reached at least this tip; the announcing peer
+                            // is always a candidate.
+                            let source = fastest_peer(
+                                peer_sync_tips.iter().filter(|(_, tip)| **tip >= tip_height).map(|(p, _)| p),
+                                &peer_latency,
+                            ).unwrap_or(peer);
+                            info!(peer_id = %peer, height = tip_height, local_height, source = %source,
+                                "🔄 Peer {} is at height {} (local {}), fetching blocks from {} via {}",
+                                peer, tip_height, local_height, from, source);
+                            let request = BlockRequest::new(source, from, Instant::now());
+                            swarm.behaviour_mut().request_response.send_request(&source, request.request());
+                            block_request_in_flight = Some(request);
+                        }
+                        None => {
+                            log::warn!("Headers from {} do not connect to the local chain (fork deeper than {} blocks?)",
+                                peer, SYNC_LOOKBACK);
+                        }
+                    }
+                }
+
+                // CHAIN SYNC: apply a page of blocks
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::RequestResponse(request_response::Event::Message {
+                    peer, message: request_response::Message::Response {
+                        response: ChainResponse::Blocks { tip_height, from, blocks }, ..
+                    }, ..
+                })) => {
+                    // Only the page we asked for, from the peer we asked:
+                    // `from` decides how far we roll back, so a peer must
+                    // not pick it.
+                    if !block_request_in_flight.is_some_and(|request| request.answered_by(&peer, from, blocks.len())) {
+                        warn!(peer_id = %peer, from, "Unrequested blocks from {} at H-{} ignored", peer, from);
+                        continue;
+                    }
+                    block_request_in_flight = None;
+                    peer_sync_tips.insert(peer, tip_height);
+                    sync_manager.observe_network_height(tip_height, Instant::now());
+                    let local_height = tc.blocks.len() as u64;
+                    if blocks.is_empty() || from == 0 || from > local_height || safe_mode.is_halted() {
+                        continue;
+                    }
+                    // Why the peer's blocks did not connect, if they did not.
+                    let mut rejected: Option<&'static str> = None;
+
+                    let mut applied = 0usize;
+                    if from == local_height {
+                        // Straight extension of our tip.
+                        for b in blocks {
+                            if let Err(e) = lock_or_recover(&checkpoints).check_block(&b).and_then(|_| tc.add_block(b)) {
+                                warn!("⚠️  Synced block rejected: {}", e);
+                                rejected = Some(e);
+                                break;
+                            }
+                            let height = tc.blocks.len() as u64 - 1;
+                            lock_or_recover(&change_feed).block_connected(height, &tc.blocks[height as usize]);
+                            lock_or_recover(&address_index).index_block(height, &tc.blocks[height as usize]);
+                            lock_or_recover(&receipt_index).index_block(height, &tc.blocks[height as usize]);
+                            lock_or_recover(&block_index).index_block(height, &tc.blocks[height as usize]);
+                            applied += 1;
+                        }
+                    } else if !lock_or_recover(&checkpoints).permits_rollback_to(from) {
+                        warn!(from, finalized = lock_or_recover(&checkpoints).finalized_height(),
+                            "🏁 Peer fork branches below the latest checkpoint — refused");
+                    } else {
+                        // Peer is on a competing fork that branches at `from`:
+                        // keep it only if it connects and is strictly longer.
+                        // A refused fork leaves our chain as it was, so the
+                        // indexes only hear about an adopted one.
+                        let old_chain = tc.blocks.clone();
+                        let outcome = tc.reorganize(from, blocks, |b| lock_or_recover(&checkpoints).check_block(b));
+                        match outcome {
+                            ForkOutcome::Adopted(ours) => {
+                                applied = tc.blocks.len() - from as usize;
+                                stale_blocks.record_disconnected(ours);
+                                lock_or_recover(&change_feed).chain_replaced(&old_chain, &tc.blocks);
+                                lock_or_recover(&address_index).chain_replaced(&old_chain, &tc.blocks);
+                                lock_or_recover(&receipt_index).chain_replaced(&old_chain, &tc.blocks);
+                                lock_or_recover(&block_index).chain_replaced(&old_chain, &tc.blocks);
+                            }
+                            ForkOutcome::Refused(reason) => {
+                                if let Some(e) = reason {
+                                    warn!("⚠️  Peer fork rejected: {}", e);
+                                }
+                                rejected = reason;
+                            }
+                        }
+                    }
+
+                    // A peer whose blocks fail to connect is charged like an
+                    // invalid gossip message and no longer synced from.
+                    if let Some(reason) = rejected {
+                        let peer_str = peer.to_string();
+                        if let Some(incident) = Incident::from_block_rejection(reason) {
+                            lock_or_recover(&ai_guardian).confirm_incident(&peer_str, incident);
+                        }
+                        let count = peer_message_counts.entry(peer).or_insert((0, Instant::now()));
+                        count.0 = count.0.saturating_add(INVALID_MESSAGE_PENALTY as u32);
+                        peer_sync_tips.remove(&peer);
+                    }
+
+                    if applied > 0 {
+                        info!(height = tc.blocks.len(), applied, "🔁 Synced {} block(s) from peer. New height: {}", applied, tc.blocks.len());
+                        storage.save_chain(&tc.blocks, &tc.state);
+                        *lock_or_recover(&account_state) = tc.state.clone();
+                        *lock_or_recover(&supply) = tc.supply_snapshot();
+                        *lock_or_recover(&fee_stats) = FeeStats::from_blocks(&tc.blocks);
+                        miner.cancel();
+                        mining_job = None;
+                        last_vdf = Instant::now();
+                        sync_manager.local_height_changed(tc.blocks.len() as u64, Instant::now());
+
+                        // Keep paging from the same peer until we reach its tip.
+                        let local_height = tc.blocks.len() as u64;
+                        if tip_height > local_height && rejected.is_none() {
+                            let request = BlockRequest::new(peer, local_height, Instant::now());
+                            swarm.behaviour_mut().request_response.send_request(&peer, request.request());
+                            block_request_in_flight = Some(request);
+                        }
+                    }
+                }
+
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::RequestResponse(request_response::Event::OutboundFailure {
+                    peer, error, ..
+                })) => {
+                    log::warn!("Chain sync request to {} failed: {}", peer, error);
+                    if block_request_in_flight.is_some_and(|request| request.peer == peer) {
+                        block_request_in_flight = None;
+                    }
+                }
+
+                // LATENCY: echo pings, record RTTs of our own
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::Latency(request_response::Event::Message {
+                    message: request_response::Message::Request { request, channel, .. }, ..
+                })) => {
+                    let _ = swarm.behaviour_mut().latency.send_response(channel, request);
+                }
+
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::Latency(request_response::Event::Message {
+                    peer, message: request_response::Message::Response { request_id, response }, ..
+                })) => {
+                    if let Some((_, nonce, sent_at)) = pings_in_flight.remove(&request_id) {
+                        let metrics = peer_latency.entry(peer).or_default();
+                        if response.nonce == nonce {
+                            metrics.record_rtt(sent_at.elapsed());
+                        } else {
+                            metrics.record_ping_failure();
+                        }
+                    }
+                }
+
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::Latency(request_response::Event::OutboundFailure {
+                    peer, request_id, ..
+                })) => {
+                    // Only pings we sent count; a stale id was already settled.
+                    let in_flight = pings_in_flight.remove(&request_id);
+                    if in_flight.is_some() {
+                        peer_latency.entry(peer).or_default().record_ping_failure();
+                    }
+                }
+
+                // CHAIN BINDING: drop peers from another chain or genesis
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::Identify(identify::Event::Received { peer_id, info, .. })) => {
+                    if let Err(e) = verify_identify(&info.protocol_version) {
+                        warn!("⛔ Disconnecting peer {} from another network: {}", peer_id, e);
+                        swarm.behaviour_mut().gossipsub.blacklist_peer(&peer_id);
+                        let _ = swarm.disconnect_peer_id(peer_id);
+                        continue;
+                    }
+                    // Peers tell us the IP they see; once enough agree,
+                    // advertise it (with our TCP port) in the discv5 ENR.
+                    if let (Some(svc), Some(ip)) = (&discv5_service, ip_of(&info.observed_addr)) {
+                        if let Some(ip) = external_ip_votes.record(peer_id, ip) {
+                            svc.update_external_ip(ip).await;
+                        }
+                    }
+                }
+
+                SwarmEvent::ExternalAddrConfirmed { address } => {
+                    if let (Some(svc), Some(ip)) = (&discv5_service, ip_of(&address)) {
+                        if external_ip_votes.confirm(ip) {
+                            svc.update_external_ip(ip).await;
+                        }
+                    }
+                }
+
+                // NAT TRAVERSAL
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::Autonat(autonat::Event::StatusChanged { new, .. })) => {
+                    info!("🧭 NAT status: {:?}", new);
+                    if let (autonat::NatStatus::Public(address), Some(svc)) = (&new, &discv5_service) {
+                        if let Some(ip) = ip_of(address).filter(|ip| external_ip_votes.confirm(*ip)) {
+                            svc.update_external_ip(ip).await;
+                        }
+                    }
+                    if new == autonat::NatStatus::Private && !relay_listening && !relay_addrs.is_empty() {
+                        info!("📡 Behind NAT — reserving circuits on {} relay(s)", relay_addrs.len());
+                        for addr in &relay_addrs {
+                            if let Err(e) = swarm.listen_on(addr.clone().with(Protocol::P2pCircuit)) {
+                                warn!("⚠️  Relay listen failed on {}: {:?}", addr, e);
+                            }
+                        }
+                        relay_listening = true;
+                    }
+                    nat_status = new;
+                }
+
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::Upnp(event)) => match event {
+                    upnp::Event::NewExternalAddr(addr) => info!("🔓 UPnP port mapping active: {}", addr),
+                    upnp::Event::ExpiredExternalAddr(addr) => log::warn!("UPnP port mapping expired: {}", addr),
+                    upnp::Event::GatewayNotFound => log::info!("No UPnP gateway found"),
+                    upnp::Event::NonRoutableGateway => log::info!("UPnP gateway is not publicly routable"),
+                },
+
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::RelayClient(
+                    relay::client::Event::ReservationReqAccepted { relay_peer_id, .. },
+                )) => {
+                    info!("📡 Relay reservation accepted by {}", relay_peer_id);
+                }
+
+                SwarmEvent::Behaviour(TimechainBehaviourEvent::Dcutr(dcutr::Event { remote_peer_id, result })) => {
+                    match result {
+                        Ok(_) => info!("🕳️  Hole punch succeeded with {}", remote_peer_id),
+                        Err(e) => log::debug!("Hole punch with {} failed: {}", remote_peer_id, e),
                     }
                 }
 
                 SwarmEvent::NewListenAddr { address, .. } => {
-                    println!("🌐 Node active on: {}", address);
+                    info!("🌐 Node active on: {}", address);
                 }
 
-                SwarmEvent::ConnectionEstablished { peer_id, endpoint: _, .. } => {
+                SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
+                    // Only addresses we dialed are worth remembering; the
+                    // remote side of an inbound connection is an ephemeral port.
+                    if endpoint.is_dialer() {
+                        let addr = endpoint.get_remote_address();
+                        if let Err(violation) = peer_diversity.outbound_connected(peer_id, addr, Instant::now()) {
+                            info!("🧭 Dropping outbound peer {}: {}", peer_id, violation);
+                            let _ = swarm.disconnect_peer_id(peer_id);
+                            continue;
+                        }
+                        peerstore.record_success(addr, peer_id, unix_now());
+                    }
+                    // A ban the guardian recommends but that is not in force
+                    // yet (assessments restored at startup) starts now.
+                    let peer = peer_id.to_string();
+                    let unenforced = lock_or_recover(&ai_guardian)
+                        .assessment(&peer)
+                        .filter(|a| a.recommended_action == Action::BanPeer)
+                        .cloned();
+                    if let Some(assessment) = unenforced {
+                        let ops = lock_or_recover(&guardian_enforcer).enforce(&assessment, unix_now());
+                        apply_enforcement(&mut swarm, &mut peer_diversity, &guardian_audit,
+                            peer_id, Some(&assessment.recommended_action), &ops);
+                    }
+                    if lock_or_recover(&guardian_enforcer).is_banned(&peer, unix_now()) {
+                        warn!("🚫 Refusing banned peer {}", peer_id);
+                        let _ = swarm.disconnect_peer_id(peer_id);
+                        continue;
+                    }
+                    // The same node key reappearing from another country
+                    // within the hour: stolen, shared or relayed.
+                    let hop = ip_of(endpoint.get_remote_address())
+                        .and_then(|ip| peer_geo.connected(peer_id, ip, unix_now()));
+                    if let Some(hop) = hop {
+                        warn!("🌍 Peer {} moved from {} to {} in {}s", peer_id, hop.from, hop.to, hop.after_secs);
+                        let assessment = lock_or_recover(&ai_guardian)
+                            .flag_threat(&peer, ThreatType::GeographicAnomaly, GEO_HOP_PENALTY);
+                        lock_or_recover(&guardian_events).observe(&assessment,
+                            peer_subnet(&peerstore, &peer_id), unix_now());
+                        let ops = lock_or_recover(&guardian_enforcer).enforce(&assessment, unix_now());
+                        apply_enforcement(&mut swarm, &mut peer_diversity, &guardian_audit,
+                            peer_id, Some(&assessment.recommended_action), &ops);
+                    }
                     connected_peers.insert(peer_id);
-                    println!("🔗 Peer connected: {} | Total: {}", peer_id, connected_peers.len());
-
-                    // When the first peer connects, immediately request
-                    // chain sync so the node doesn't have to wait for the
-                    // 5-minute periodic sync timer.
-                    if connected_peers.len() == 1 {
-                        println!("🔄 First peer connected — requesting chain sync...");
-                        let _ = swarm.behaviour_mut().gossipsub.publish(
-                            req_topic.clone(), b"REQ_CHAIN".to_vec(),
-                        );
+                    lock_or_recover(&api_state).connected_peers = connected_peers.len();
+                    info!(peer_id = %peer_id, peers = connected_peers.len(), "🔗 Peer connected: {} | Total: {}", peer_id, connected_peers.len());
+
+                    // Ask every new peer for headers straight away so the
+                    // node doesn't have to wait for the 5-minute periodic
+                    // sync timer. Headers are small; bodies are only fetched
+                    // for the range we are actually missing.
+                    let request = header_request_for(tc.blocks.len() as u64);
+                    swarm.behaviour_mut().request_response.send_request(&peer_id, request);
+                }
+
+                SwarmEvent::OutgoingConnectionError { error, .. } => {
+                    let now = unix_now();
+                    match error {
+                        DialError::Transport(attempts) => {
+                            for (addr, _) in attempts {
+                                peerstore.record_failure(&addr, now);
+                            }
+                        }
+                        DialError::WrongPeerId { endpoint, .. } => {
+                            peerstore.record_failure(endpoint.get_remote_address(), now);
+                        }
+                        _ => {}
                     }
                 }
 
                 SwarmEvent::ConnectionClosed { peer_id, .. } => {
                     connected_peers.remove(&peer_id);
-                    println!("🔌 Peer disconnected: {} | Total: {}", peer_id, connected_peers.len());
+                    lock_or_recover(&api_state).connected_peers = connected_peers.len();
+                    peer_diversity.disconnected(&peer_id);
+                    peer_geo.disconnected(&peer_id, unix_now());
+                    peer_sync_tips.remove(&peer_id);
+                    peer_latency.remove(&peer_id);
+                    peer_limiter.remove_peer(&peer_id);
+                    tx_relay.peer_disconnected(&peer_id);
+                    pings_in_flight.retain(|_, (p, _, _)| *p != peer_id);
+                    info!(peer_id = %peer_id, peers = connected_peers.len(), "🔌 Peer disconnected: {} | Total: {}", peer_id, connected_peers.len());
                 }
 
                 _ => {}
             },
 
+            // GUARDIAN RETRAINING: keep the labelled samples, and once enough
+            // are new, train a copy of the guardian off the event loop. The
+            // result is installed and checkpointed when it comes back.
+            _ = guardian_retrain_timer.tick() => {
+                let ai = lock_or_recover(&ai_guardian);
+                if let Err(e) = ai.save_training_data(TRAINING_DATA_PATH) {
+                    log::warn!("Failed to save guardian training samples: {}", e);
+                }
+                if !guardian_training && ai.untrained_samples() >= MIN_NEW_SAMPLES {
+                    guardian_training = true;
+                    let mut job = ai.training_job();
+                    let pending = ai.untrained_samples();
+                    let done = guardian_trained.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let update = job.train_local(10, 0.01);
+                        let _ = done.send((job, update, pending));
+                    });
+                }
+            }
+            Some((job, update, trained)) = guardian_trained_rx.recv() => {
+                guardian_training = false;
+                let mut ai = lock_or_recover(&ai_guardian);
+                ai.finish_training(job, trained);
+                match guardian_models.checkpoint(&ai, update.num_samples, update.loss, unix_now()) {
+                    Ok(record) => info!(
+                        "🛡️  GUARDIAN: model v{} on {} samples, loss {:.4}, accuracy {} ({})",
+                        record.version,
+                        update.num_samples,
+                        update.loss,
+                        record.accuracy.map_or("n/a".to_string(), |a| format!("{:.1}%", a * 100.0)),
+                        record.model_hash
+                    ),
+                    Err(e) => log::warn!("GUARDIAN: failed to checkpoint model: {}", e),
+                }
+            }
+
             // THROTTLE RESET
             _ = throttle_reset.tick() => {
                 peer_message_counts.clear();
                 ai_fallback_count = 0; // Reset AI fallback rate limit each minute
+                // Guardian bans that ran out or were pardoned: back into
+                // gossip, judged afresh.
+                for peer in lock_or_recover(&guardian_enforcer).expire(unix_now()) {
+                    lock_or_recover(&ai_guardian).forget_assessment(&peer);
+                    if let Ok(peer_id) = peer.parse::<PeerId>() {
+                        apply_enforcement(&mut swarm, &mut peer_diversity, &guardian_audit, peer_id, None, &[Enforcement::Unban]);
+                    }
+                    info!("🛡️  Guardian ban on {} lifted", peer);
+                }
+                if let Some(archive) = &block_archive {
+                    match lock_or_recover(archive).update(&tc.blocks) {
+                        Ok(0) => {}
+                        Ok(n) => info!("📦 ARCHIVE: {} new chunk(s) published", n),
+                        Err(e) => log::warn!("ARCHIVE: update failed: {}", e),
+                    }
+                }
+                if let Some(store) = &state_diffs {
+                    let mut store = lock_or_recover(store);
+                    match store.update(&tc.blocks, &wallet) {
+                        Ok(0) => {}
+                        Ok(n) => {
+                            audit_state_deltas(&key_audit, &store, n, &wallet);
+                            info!("📦 STATE DIFF: {} new checkpoint delta(s) published", n);
+                        }
+                        Err(e) => log::warn!("STATE DIFF: update failed: {}", e),
+                    }
+                }
+                if let Err(e) = peerstore.save() {
+                    log::warn!("Failed to save peerstore: {}", e);
+                }
             }
 
             // TX BROADCAST
-            _ = tx_broadcast_timer.tick() => {
+            // Held while halted; the wallet's transaction stays on disk.
+            _ = tx_broadcast_timer.tick(), if !safe_mode.is_halted() => {
                 if let Ok(tx_data) = std::fs::read("pending_tx.dat") {
                     if let Ok(tx) = bincode::deserialize::<Transaction>(&tx_data) {
-                        if tc.validate_transaction(&tx).is_ok() {
-                            let encoded = bincode::serialize(&tx).unwrap();
-                            let _ = swarm.behaviour_mut().gossipsub.publish(tx_topic.clone(), encoded);
-                            println!("📤 Transaction broadcasted");
+                        // Admit locally and announce; peers pull the body
+                        // from our mempool instead of receiving it unasked.
+                        if mempool.contains(&tx) {
+                            tx_relay.accepted(&tx, Instant::now());
                             let _ = std::fs::remove_file("pending_tx.dat");
+                        } else {
+                            let _ = screen_transaction(tx, TxOrigin::Wallet, &tc, &mempool, &mut screening,
+                                &sender_limits, &ai_bridge, &address_index, &screened_sender);
                         }
                     }
                 }
             }
 
+            // TX ANNOUNCEMENTS (and the pending index explorers read)
+            _ = tx_announce_timer.tick() => {
+                let next_height = tc.blocks.len() as u64;
+                mempool.retain(|tx| !tx.is_expired_at(next_height) && tx.nonce >= tc.state.nonce(&tx.from));
+                // Quarantine over: pooled and announced if still valid. A
+                // halted node holds them in quarantine and announces nothing.
+                let halted = safe_mode.is_halted();
+                let released = if halted { Vec::new() } else { screening.release(next_height) };
+                for (tx, risk) in released {
+                    match check_transaction(&tx, &tc, &mempool, &screening, &sender_limits) {
+                        Ok(replaces) => {
+                            info!(tx = %hex::encode(tx.legacy_hash()), "🛡️  Transaction released from quarantine");
+                            tx_relay.accepted(&tx, Instant::now());
+                            pool_transaction(tx, replaces, Some(risk), &mut mempool, &mut tx_risk_scores);
+                        }
+                        Err(e) => info!(tx = %hex::encode(tx.legacy_hash()), "🛡️  Quarantined transaction dropped: {}", e),
+                    }
+                }
+                // Approved consensus changes whose announced height arrived.
+                for id in ai_bridge.activate_consensus_optimizations(next_height) {
+                    info!(height = next_height, "🗳️  Consensus proposal {} activated", id);
+                }
+                lock_or_recover(&pending_index).refresh(&mempool, unix_now());
+                tx_relay.expire_requests(Instant::now());
+                let announcements = if halted { Vec::new() } else { tx_relay.take_announcements() };
+                for announcement in announcements {
+                    match bincode::serialize(&announcement) {
+                        Ok(encoded) => {
+                            let _ = swarm.behaviour_mut().gossipsub.publish(tx_announce_topic.clone(), encoded);
+                        }
+                        Err(e) => log::error!("Failed to encode tx announcement: {}", e),
+                    }
+                }
+            }
+
+            // LATENCY PINGS
+            _ = ping_timer.tick() => {
+                for peer in connected_peers.iter() {
+                    let ping = Ping { nonce: rand::random() };
+                    let request_id = swarm.behaviour_mut().latency.send_request(peer, ping);
+                    pings_in_flight.insert(request_id, (*peer, ping.nonce, Instant::now()));
+                    peer_latency.entry(*peer).or_default().record_ping_sent();
+                }
+            }
+
             // CHAIN SYNC
             _ = chain_sync_timer.tick() => {
-                println!("🔄 Performing periodic chain synchronization...");
-                let _ = swarm.behaviour_mut().gossipsub.publish(req_topic.clone(), b"REQ_CHAIN".to_vec());
+                info!("🔄 Performing periodic chain synchronization...");
+                for peer in connected_peers.iter() {
+                    let request = header_request_for(tc.blocks.len() as u64);
+                    swarm.behaviour_mut().request_response.send_request(peer, request);
+                }
+            }
+
+            // SYNC PROGRESS
+            Some(event) = sync_events.recv() => {
+                match &event {
+                    SyncEvent::Started { local_height, target_height } => {
+                        info!("⏬ SYNC: Started — local height {}, network height {} (mining paused)",
+                            local_height, target_height);
+                    }
+                    SyncEvent::Progress { local_height, target_height, .. } => {
+                        info!("⏬ SYNC: {}/{} ({:.1}%)", local_height, target_height,
+                            sync_manager.progress() * 100.0);
+                    }
+                    SyncEvent::Completed { height, downloaded, elapsed_secs } => {
+                        info!("✅ SYNC: Completed at height {} ({} blocks in {}s) — mining resumed",
+                            height, downloaded, elapsed_secs);
+                    }
+                }
+                node_metrics.set_syncing(sync_manager.is_syncing());
+                let mut api = lock_or_recover(&api_state);
+                api.is_syncing = sync_manager.is_syncing();
+                api.network_height = sync_manager.network_height();
             }
 
             // DASHBOARD
             _ = dashboard_timer.tick() => {
+                heartbeat.beat(unix_now());
                 let elapsed = last_vdf.elapsed().as_secs();
                 let remaining = 1800u64.saturating_sub(elapsed);
                 let trend = if tc.difficulty > last_diff { "UP ⬆️" }
@@ -753,32 +1964,87 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 let mined_axm = Timechain::format_axm(mined);
                 let remaining_axm = Timechain::format_axm(remaining_supply);
 
-                println!("\n--- 🏛️  AXIOM STATUS ---");
-                println!("⛓️  Height: {} | Diff: {} | Trend: {}", tc.blocks.len(), tc.difficulty, trend);
-                println!("⏳ Time-Lock: {}m remaining | 🤖 AI Shield: ACTIVE", remaining / 60);
-                println!("💰 Mined: {} AXM | Remaining: {} AXM | {:.2}% of max supply",
+                info!(height = tc.blocks.len(), difficulty = tc.difficulty, trend, timelock_remaining_secs = remaining,
+                    "⛓️  Height: {} | Diff: {} | Trend: {} | Time-Lock: {}m remaining",
+                    tc.blocks.len(), tc.difficulty, trend, remaining / 60);
+                info!(mined, remaining_supply, "💰 Mined: {} AXM | Remaining: {} AXM | {:.2}% of max supply",
                     mined_axm, remaining_axm, percent);
+                if mining_job.is_some() {
+                    let progress = miner.progress();
+                    info!(height = progress.height, phase = ?progress.phase, attempts = progress.attempts,
+                        max_attempts = progress.max_attempts, threads = progress.threads,
+                        "⛏️  Mining H-{}: {:?} | {}/{} nonces on {} thread(s)", progress.height, progress.phase,
+                        progress.attempts, progress.max_attempts, progress.threads);
+                }
 
-                println!("🌐 Network Status:");
-                println!("   ├─ PeerId: {}", swarm.local_peer_id());
-                println!("   ├─ Connected Peers: {}", connected_peers.len());
+                node_metrics.update_peer_count(connected_peers.len());
+                {
+                    let mut checkpoints = lock_or_recover(&checkpoints);
+                    let confirmed = checkpoints.confirm(&tc.blocks);
+                    if let Some(latest) = confirmed.last() {
+                        info!(height = latest.height, block = %latest.block_hash,
+                            "🏁 Checkpoint final at H-{}: no reorgs below it", latest.height);
+                        if let Err(e) = checkpoints.save(CHECKPOINTS_PATH) {
+                            log::warn!("Failed to save checkpoints: {}", e);
+                        }
+                    }
+                }
+                let orphan_rate = stale_blocks.orphan_rate(tc.blocks.len() as u64);
+                node_metrics.set_stale_blocks(stale_blocks.total(), orphan_rate);
+                node_metrics.set_peer_bandwidth(peer_limiter.all_stats()
+                    .map(|(peer, stats)| PeerBandwidth { peer_id: peer.to_string(), stats: stats.clone() })
+                    .collect());
+                let traffic = node_metrics.snapshot();
+                let mut medians: Vec<Duration> = peer_latency.values().filter_map(|m| m.median_rtt()).collect();
+                medians.sort();
+                let median_rtt_ms = medians.get(medians.len() / 2).map(|rtt| rtt.as_millis() as u64);
+                let listen_addrs: Vec<String> = libp2p::Swarm::listeners(&swarm).map(|a| a.to_string()).collect();
+                info!(
+                    peer_id = %swarm.local_peer_id(),
+                    peers = connected_peers.len(),
+                    nat = ?nat_status,
+                    best_peer_height = peer_sync_tips.values().max().copied(),
+                    bytes_in = traffic.total_bytes_in,
+                    bytes_out = traffic.total_bytes_out,
+                    median_rtt_ms,
+                    stale_blocks = traffic.stale_blocks,
+                    orphan_rate = traffic.orphan_rate,
+                    listen = %listen_addrs.join(" "),
+                    "🌐 Network: {} peers | NAT {:?} | {} KiB in / {} KiB out",
+                    connected_peers.len(), nat_status, traffic.total_bytes_in / 1024, traffic.total_bytes_out / 1024,
+                );
                 if connected_peers.is_empty() {
-                    println!("   │  └─ No peers connected (check firewall/NAT)");
+                    warn!("No peers connected (check firewall/NAT)");
                 }
-                println!("   └─ Listen Addresses:");
-                for addr in libp2p::Swarm::listeners(&swarm) {
-                    println!("      └─ {}", addr);
+                if sync_manager.is_syncing() {
+                    info!(network_height = sync_manager.network_height(), "⏬ Syncing: {}/{} ({:.1}%)",
+                        sync_manager.local_height(), sync_manager.network_height(), sync_manager.progress() * 100.0);
                 }
-
-                let ai = ai_guardian.lock().unwrap();
+                let total_bytes = traffic.total_bytes_in + traffic.total_bytes_out;
+                let discovered = match &discv5_service {
+                    Some(svc) => svc.table_entries().await.len(),
+                    None => 0,
+                };
+                let sample = NetworkSample {
+                    peer_count: connected_peers.len().max(discovered),
+                    bytes_per_sec: total_bytes.saturating_sub(last_traffic_sample.0) as f64
+                        / last_traffic_sample.1.elapsed().as_secs_f64().max(1.0),
+                };
+                last_traffic_sample = (total_bytes, Instant::now());
+                if let Some(tuning) = gossip_tuner.observe(sample).cloned() {
+                    info!("📶 Gossip mesh retuned for ~{:.0} nodes: D={} ({}-{}), heartbeat {} ms; applies on next start",
+                        gossip_tuner.estimated_network_size(), tuning.mesh_n, tuning.mesh_n_low,
+                        tuning.mesh_n_high, tuning.heartbeat_interval.as_millis());
+                    if let Err(e) = save_tuning(GOSSIP_TUNING_PATH, &tuning) {
+                        log::warn!("Failed to save gossip tuning: {}", e);
+                    }
+                }
+                let ai = lock_or_recover(&ai_guardian);
                 let stats = ai.get_stats();
-                println!("🤖 AI Guardian: {} events | {} peers | {} cached | model: {}…",
+                info!(events = stats.total_events, peers = stats.unique_peers, cached = stats.cached_assessments,
+                    "🤖 AI Guardian: {} events | {} peers | {} cached | model: {}…",
                     stats.total_events, stats.unique_peers, stats.cached_assessments,
                     if stats.model_hash.len() >= 12 { &stats.model_hash[..12] } else { &stats.model_hash });
-                if stats.total_events == 0 && connected_peers.is_empty() {
-                    println!("   └─ No peer messages yet — stats populate when peers connect");
-                }
-                println!("------------------------\n");
 
                 // Update Public Pulse API state
                 {
@@ -796,12 +2062,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     // Chain the pulse: store this hash for next iteration
                     match health.trust_pulse_512.as_slice().try_into() {
                         Ok(arr) => last_pulse_hash = arr,
-                        Err(_) => eprintln!("⚠️  PULSE CHAIN INTEGRITY: trust_pulse_512 is not 64 bytes — chain link skipped"),
+                        Err(_) => warn!("⚠️  PULSE CHAIN INTEGRITY: trust_pulse_512 is not 64 bytes — chain link skipped"),
                     }
 
                     // Record to pulse history ring buffer
                     {
-                        let mut history = pulse_history.lock().unwrap();
+                        let mut history = lock_or_recover(&pulse_history);
                         if history.len() >= PULSE_HISTORY_CAPACITY {
                             history.pop_front();
                         }
@@ -813,8 +2079,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
                         });
                     }
 
-                    let mut api = api_state.lock().unwrap();
+                    let mut api = lock_or_recover(&api_state);
                     api.current_height = tc.blocks.len() as u64;
+                    api.difficulty = tc.difficulty;
+                    api.stale_blocks = stale_blocks.total();
+                    api.orphan_rate = orphan_rate;
                     api.supply_remaining_units = remaining_supply;
                     api.supply_remaining_axm = format_axm_supply(remaining_supply);
                     api.trust_pulse = trust_pulse_hex;
@@ -826,8 +2095,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
             // BOOTSTRAP RETRY
             _ = bootstrap_retry_timer.tick() => {
                 if connected_peers.len() < 2 && last_bootstrap_retry.elapsed().as_secs() > 120 {
+                    // Failing addresses back off exponentially instead of
+                    // being hammered every retry tick.
+                    let now = unix_now();
                     for (_, addr) in &bootstrap_addrs {
-                        let _ = swarm.dial(addr.clone());
+                        if peerstore.is_ready(addr, now) {
+                            let _ = swarm.dial(addr.clone());
+                        }
+                    }
+                    for addr in peerstore.dialable(now) {
+                        if !bootstrap_addrs.iter().any(|(_, b)| *b == addr) && peer_diversity.allows_dial(&addr) {
+                            let _ = swarm.dial(addr);
+                        }
                     }
                     last_bootstrap_retry = Instant::now();
                 }
@@ -850,8 +2129,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
                                 }
                                 if let Some(tcp_port) = enr.tcp4() {
                                     if let Ok(addr) = format!("/ip4/{}/tcp/{}", ip, tcp_port).parse::<Multiaddr>() {
+                                        if !peer_diversity.allows_dial(&addr) {
+                                            continue;
+                                        }
                                         if let Err(e) = swarm.dial(addr.clone()) {
-                                            println!("⚠️  Discv5 bridge: failed to dial {}: {}", addr, e);
+                                            warn!("⚠️  Discv5 bridge: failed to dial {}: {}", addr, e);
                                         }
                                     }
                                 }
@@ -862,176 +2144,348 @@ async fn main() -> Result<(), Box<dyn Error>> {
             }
 
             // MINING
-            _ = vdf_loop.tick() => {
-                let elapsed = last_vdf.elapsed().as_secs();
-                if elapsed >= 1800 {
-                    let parent_hash = tc.blocks.last().map(|b| b.hash())
-                        .unwrap_or_else(|| axiom_core::genesis::genesis().hash());
-                    let current_slot = tc.blocks.len() as u64;
-                    let vdf_seed = axiom_core::vdf::evaluate(parent_hash, current_slot);
-                    let vdf_proof = axiom_core::main_helper::compute_vdf(vdf_seed, tc.difficulty as u32);
-                    let zk_pass = axiom_core::genesis::generate_zk_pass(&wallet, parent_hash);
-
-                    // Current wall-clock timestamp for the new block
-                    let block_timestamp = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs();
-
-                    // Randomize nonce start so competing miners don't all
-                    // search the same nonce space — essential for real multi-node mining.
-                    let nonce_start: u64 = rand::random();
-                    let mut nonce = nonce_start;
-                    // Scale nonce search budget to difficulty.  At difficulty D the
-                    // expected number of hashes to find a valid nonce is D, so we
-                    // budget NONCE_SEARCH_MULTIPLIER × D (minimum MIN_NONCE_ATTEMPTS)
-                    // to give a >99.99% success probability per VDF round.
-                    const NONCE_SEARCH_MULTIPLIER: u64 = 10;
-                    const MIN_NONCE_ATTEMPTS: u64 = 100_000;
-                    let max_attempts = tc.difficulty.saturating_mul(NONCE_SEARCH_MULTIPLIER).max(MIN_NONCE_ATTEMPTS);
-
-                    let mut attempts = 0u64;
-                    while attempts < max_attempts {
-                        let candidate = Block {
-                            parent: parent_hash,
-                            slot: current_slot,
-                            timestamp: block_timestamp,
-                            miner: wallet.address,
-                            transactions: vec![],
-                            vdf_proof,
-                            zk_proof: zk_pass.clone(),
-                            nonce,
-                        };
-
-                        if candidate.meets_difficulty(tc.difficulty) && tc.add_block(candidate.clone()).is_ok() {
-                            println!("✨ MINED: H-{} | Nonce: {}", tc.blocks.len(), nonce);
-                            let encoded = bincode::serialize(&candidate).unwrap();
-                            let _ = swarm.behaviour_mut().gossipsub.publish(blocks_topic.clone(), encoded);
-                            axiom_core::storage::save_chain(&tc.blocks);
-
-                            // Broadcast real-time pulse to all peers
-                            let height = tc.blocks.len() as u64;
-                            let (total_mined, remaining, _percent) = tc.supply_info();
-                            // Generate deterministic AI oracle seal for this block
-                            let oracle_query = format!(
-                                "Axiom block {} mined with hash {}",
-                                tc.blocks.len(),
-                                hex::encode(candidate.hash())
-                            );
-                            let oracle_seal = axiom_core::ai::query_oracle(&oracle_query).await;
-
-                            let mut pulse = AxiomPulse {
-                                height,
-                                total_mined,
-                                remaining,
-                                block_hash: candidate.hash_512(),
-                                oracle_seal,
-                                prev_pulse_hash: last_pulse_hash,
-                                timestamp: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_secs() as i64,
-                                stark_receipt: None,
-                            };
-
-                            // Generate mandatory STARK receipt every STARK_PROOF_INTERVAL blocks
-                            if height % STARK_PROOF_INTERVAL == 0 {
-                                let stark_tx = TransactionData {
-                                    initial_balance: remaining,
-                                    amount: total_mined,
-                                    fee: 0,
-                                    nonce: height,
-                                };
-                                match StarkProver::generate_proof(&stark_tx) {
-                                    Ok(receipt) => {
-                                        pulse.stark_receipt = Some(receipt.seal.clone());
-
-                                        // Verify the receipt against the expected anchor
-                                        let anchor = match StarkProver::compute_512_anchor(&stark_tx) {
-                                            Ok(a) => a,
-                                            Err(e) => {
-                                                println!("⚠️  STARK anchor computation failed @ H-{}: {}", height, e);
-                                                [0u8; 64]
-                                            }
-                                        };
-                                        let verified = match StarkProver::verify_receipt(&receipt, &anchor) {
-                                            Ok(v) => v,
-                                            Err(e) => {
-                                                println!("⚠️  STARK receipt verification failed @ H-{}: {}", height, e);
-                                                false
-                                            }
-                                        };
-
-                                        let mut api = api_state.lock().unwrap();
-                                        api.zk_verified = verified;
-                                        drop(api);
-
-                                        println!("🔐 STARK receipt generated @ H-{}: zk_verified={}", height, verified);
-                                    }
-                                    Err(e) => {
-                                        println!("⚠️  STARK receipt generation failed @ H-{}: {}", height, e);
-                                        let mut api = api_state.lock().unwrap();
-                                        api.zk_verified = false;
-                                    }
-                                }
-                            }
+            // SHUTDOWN / UPGRADE HANDOFF: stop mining, persist the chain,
+            // mempool, peerstore and peer scores, then leave the loop so
+            // our ports are released.
+            Some(request) = stop_rx.recv() => {
+                miner.cancel();
+                mining_job = None;
+                storage.save_chain(&tc.blocks, &tc.state);
+                if let Err(e) = peerstore.save() {
+                    log::warn!("Failed to save peerstore: {}", e);
+                }
+                if let Err(e) = lock_or_recover(&ai_guardian).save_assessments(PEER_SCORES_PATH) {
+                    log::warn!("Failed to save peer scores: {}", e);
+                }
+                if let Err(e) = lock_or_recover(&ai_guardian).save_training_data(TRAINING_DATA_PATH) {
+                    log::warn!("Failed to save guardian training samples: {}", e);
+                }
+                if request == StopRequest::Shutdown {
+                    if let Err(e) = save_mempool(MEMPOOL_FILE, &mempool) {
+                        log::warn!("Failed to save mempool: {}", e);
+                    }
+                    info!("🛑 SHUTDOWN: Chain saved at H-{} | {} pending txs kept", tc.blocks.len(), mempool.len());
+                    let _ = std::fs::remove_file(PID_FILE);
+                    break;
+                }
+                let now = unix_now();
+                let peers: Vec<String> = connected_peers.iter()
+                    .flat_map(|peer| peerstore.addrs_of(peer))
+                    .map(|addr| addr.to_string())
+                    .collect();
+                let vdf_started_at = now.saturating_sub(last_vdf.elapsed().as_secs());
+                let handoff = Handoff::new(tc.blocks.len() as u64, mempool.iter().cloned().collect(),
+                    peers, vdf_started_at, now);
+                match handoff.write(HANDOFF_FILE) {
+                    Ok(()) => {
+                        info!("🔁 HANDOFF: State written ({} txs, {} peers) — exiting for upgrade",
+                            handoff.mempool.len(), handoff.peers.len());
+                        let _ = std::fs::remove_file(PID_FILE);
+                        break;
+                    }
+                    Err(e) => {
+                        warn!("⚠️  HANDOFF: Could not write handoff ({}) — still running", e);
+                        stopping.store(false, Ordering::SeqCst);
+                    }
+                }
+            }
+
+            Some((tx, reply)) = tx_submission_rx.recv() => {
+                if let Some(halt) = safe_mode.halt() {
+                    let halted = AxiomError::ChainHalted { reason: halt.notice.notice.reason.clone() };
+                    TxOrigin::Submitted(reply).refused(&tx, &halted);
+                    continue;
+                }
+                let _ = screen_transaction(tx, TxOrigin::Submitted(reply), &tc, &mempool, &mut screening,
+                    &sender_limits, &ai_bridge, &address_index, &screened_sender);
+            }
 
-                            // Chain the pulse hash for tamper-evident history
-                            last_pulse_hash = axiom_core::axiom_hash_512(
-                                &bincode::serialize(&pulse).unwrap_or_default(),
-                            );
+            // SCREENED TRANSACTIONS: pool, quarantine or refuse, and only
+            // then relay.
+            Some((tx, origin, decision)) = screened_rx.recv() => {
+                screening.finish(&tx.legacy_hash());
+                // The guardian's HaltChain: this node halts and tells peers.
+                if let Ok(GuardianDecision { action: GuardianAction::ChainHalt, threat_assessment, .. }) = &decision {
+                    let reason = format!("guardian halted the chain on transaction {} (threat score {:.1})",
+                        hex::encode(tx.legacy_hash()), threat_assessment.threat_score);
+                    let notice = sign_halt_notice(&wallet, &key_audit, tc.blocks.len() as u64, reason);
+                    enter_safe_mode(&mut safe_mode, notice, &mut swarm, &safety_topic, &intake_topics,
+                        &mut miner, &mut mining_job, &ai_bridge, &alerts);
+                }
+                if let Some(halt) = safe_mode.halt() {
+                    origin.refused(&tx, &AxiomError::ChainHalted { reason: halt.notice.notice.reason.clone() });
+                    continue;
+                }
+                let outcome = finish_screening(tx.clone(), decision, &tc, &mut mempool, &mut screening,
+                    &sender_limits, &mut tx_risk_scores, &mut ai_fallback_count);
+                answer_screened(tx, origin, outcome, &mempool, &mut tx_relay, &pending_index);
+            }
 
-                            if let Ok(pulse_data) = bincode::serialize(&pulse) {
-                                let _ = swarm.behaviour_mut().gossipsub.publish(pulse_topic.clone(), pulse_data);
+            Some((command, reply)) = admin_rx.recv() => {
+                let outcome = match command {
+                    AdminCommand::AddPeer(addr) => match addr.parse::<Multiaddr>() {
+                        Ok(addr) => match swarm.dial(addr.clone()) {
+                            Ok(()) => {
+                                info!(addr = %addr, "🛠️  ADMIN: Dialing {}", addr);
+                                Ok(serde_json::json!({"dialing": addr.to_string()}))
                             }
-
-                            // Broadcast Global Trust Pulse every 100 blocks
-                            if height % 100 == 0 {
-                                let stats = ai_guardian.lock().unwrap().get_stats();
-                                let health = get_network_health(
-                                    height,
-                                    total_mined,
-                                    remaining,
-                                    connected_peers.len(),
-                                    stats,
-                                    &last_pulse_hash,
-                                );
-                                println!("💎 Global Trust Pulse @ H-{}: 512-bit commitment broadcast", height);
-                                if let Ok(health_data) = bincode::serialize(&health) {
-                                    let _ = swarm.behaviour_mut().gossipsub.publish(health_topic.clone(), health_data);
+                            Err(e) => Err((INTERNAL_ERROR, format!("dial failed: {}", e))),
+                        },
+                        Err(e) => Err((INVALID_PARAMS, format!("invalid multiaddr: {}", e))),
+                    },
+                    AdminCommand::RemovePeer(peer) => match peer.parse::<PeerId>() {
+                        Ok(peer_id) => {
+                            let connected = swarm.disconnect_peer_id(peer_id).is_ok();
+                            info!(peer_id = %peer_id, connected, "🛠️  ADMIN: Removing peer {}", peer_id);
+                            Ok(serde_json::json!({"peer": peer, "disconnected": connected}))
+                        }
+                        Err(e) => Err((INVALID_PARAMS, format!("invalid peer ID: {}", e))),
+                    },
+                    AdminCommand::BanPeer(peer) => match peer.parse::<PeerId>() {
+                        Ok(peer_id) => {
+                            // Banned through the guardian, so reconnects are
+                            // refused and the pardon route lifts it again.
+                            let assessment = lock_or_recover(&ai_guardian).report_threat(&peer, ThreatType::OperatorBan);
+                            lock_or_recover(&guardian_events).observe(&assessment,
+                                peer_subnet(&peerstore, &peer_id), unix_now());
+                            let connected = swarm.is_connected(&peer_id);
+                            let ops = lock_or_recover(&guardian_enforcer).enforce(&assessment, unix_now());
+                            apply_enforcement(&mut swarm, &mut peer_diversity, &guardian_audit,
+                                peer_id, Some(&assessment.recommended_action), &ops);
+                            warn!(peer_id = %peer_id, "🛠️  ADMIN: Peer {} banned by operator", peer_id);
+                            Ok(serde_json::json!({"peer": peer, "banned": true, "disconnected": connected}))
+                        }
+                        Err(e) => Err((INVALID_PARAMS, format!("invalid peer ID: {}", e))),
+                    },
+                    AdminCommand::MempoolContents => {
+                        let transactions: Vec<MempoolEntry> = mempool.iter().map(MempoolEntry::from).collect();
+                        Ok(serde_json::json!({"count": transactions.len(), "transactions": transactions}))
+                    }
+                    AdminCommand::SetMiningEnabled(enabled) => {
+                        if enabled != mining_enabled {
+                            info!(enabled, "🛠️  ADMIN: Mining {}", if enabled { "resumed" } else { "paused" });
+                        }
+                        mining_enabled = enabled;
+                        if !enabled {
+                            miner.cancel();
+                            mining_job = None;
+                        }
+                        Ok(serde_json::json!({"mining": mining_enabled}))
+                    }
+                    AdminCommand::ConsensusProposals => {
+                        serde_json::to_value(ai_bridge.consensus_proposals()).map_err(|e| (INTERNAL_ERROR, e.to_string()))
+                    }
+                    AdminCommand::ApproveProposal(id) => {
+                        match ai_bridge.approve_consensus_optimization(&id, ApprovalSource::Operator, tc.blocks.len() as u64) {
+                            Ok(announcement) => {
+                                info!("🛠️  ADMIN: Consensus proposal {} approved, activating at H-{}", id, announcement.activation_height);
+                                if let Ok(data) = bincode::serialize(&announcement) {
+                                    let _ = swarm.behaviour_mut().gossipsub.publish(proposals_topic.clone(), data);
                                 }
+                                Ok(serde_json::json!({"id": id, "activation_height": announcement.activation_height}))
                             }
-
-                            last_vdf = Instant::now();
-                            break;
+                            Err(e) => Err((INVALID_PARAMS, e.to_string())),
+                        }
+                    }
+                    AdminCommand::RejectProposal { id, reason } => ai_bridge
+                        .reject_consensus_optimization(&id, reason)
+                        .map(|()| serde_json::json!({"id": id, "rejected": true}))
+                        .map_err(|e| (INVALID_PARAMS, e.to_string())),
+                    AdminCommand::FlagAddress { address, threat, evidence } => security_engine
+                        .update_threat_intelligence(address.clone(), threat, evidence)
+                        .map(|()| serde_json::json!({"address": address, "flagged": true}))
+                        .map_err(|e| (INTERNAL_ERROR, e.to_string())),
+                    AdminCommand::SafeModeStatus => Ok(match safe_mode.halt() {
+                        Some(halt) => serde_json::json!({
+                            "halted": true,
+                            "halt_id": halt.id,
+                            "since": halt.since,
+                            "notice": halt.notice,
+                            "resume_message": hex::encode(resume_message(&halt.id)),
+                        }),
+                        None => serde_json::json!({"halted": false}),
+                    }),
+                    AdminCommand::Halt { reason } => {
+                        let notice = sign_halt_notice(&wallet, &key_audit, tc.blocks.len() as u64, reason);
+                        if enter_safe_mode(&mut safe_mode, notice, &mut swarm, &safety_topic, &intake_topics,
+                            &mut miner, &mut mining_job, &ai_bridge, &alerts) {
+                            warn!("🛠️  ADMIN: Chain halted by operator");
                         }
-                        nonce = nonce.wrapping_add(1);
-                        attempts += 1;
+                        let halt_id = safe_mode.halt().map(|halt| halt.id.clone()).unwrap_or_default();
+                        Ok(serde_json::json!({"halted": true, "halt_id": halt_id,
+                            "resume_message": hex::encode(resume_message(&halt_id))}))
                     }
+                    AdminCommand::Resume(auth) => match safe_mode.resume(&auth) {
+                        Ok(halt) => {
+                            info!("🛠️  ADMIN: Halt {} resumed", halt.id);
+                            resume_node(&mut swarm, &intake_topics, &ai_bridge, &halt);
+                            if let Ok(data) = bincode::serialize(&SafetyNotice::Resume(auth)) {
+                                let _ = swarm.behaviour_mut().gossipsub.publish(safety_topic.clone(), data);
+                            }
+                            Ok(serde_json::json!({"halted": false, "resumed": halt.id}))
+                        }
+                        Err(e) => Err((INVALID_PARAMS, e.to_string())),
+                    },
+                };
+                let _ = reply.send(outcome);
+            }
+
+            _ = vdf_loop.tick() => {
+                if sync_manager.check_stalled(Instant::now()) {
+                    warn!("⚠️  SYNC: No blocks towards the advertised height for {}s — resuming mining",
+                        SYNC_STALL_TIMEOUT.as_secs());
+                }
+                let elapsed = last_vdf.elapsed().as_secs();
+                if mining_enabled && elapsed >= 1800 && !sync_manager.is_syncing() && mining_job.is_none()
+                    && !safe_mode.is_halted() {
+                    // The key the coinbase pays also signs the block's pulse.
+                    let payout_signer = rotate_payout.then(|| payout_wallet(&wallet, payout_branch.next_index));
+                    let (job, metadata) = next_mining_job(&tc, &mempool, &wallet, payout_signer.as_ref().unwrap_or(&wallet),
+                        &stale_blocks, &risk_policy, &tx_risk_scores, max_block_bytes);
+                    let height = job.candidate.slot;
+                    let id = miner.start(job);
+                    info!(height, difficulty = tc.difficulty, threads = miner.threads(),
+                        "⛏️  Mining H-{} on {} thread(s)", height, miner.threads());
+                    mining_job = Some(PendingMiningJob { id, metadata, payout_signer });
                 }
             }
+
+            // BLOCKS FROM THE BACKGROUND MINER
+            Some(event) = miner_events.recv() => {
+                let (id, found) = match event {
+                    MinerEvent::Found { job, block } => (job, Some(*block)),
+                    MinerEvent::Exhausted { job } => (job, None),
+                };
+                // A job replaced since is stale.
+                let Some(job) = mining_job.take_if(|job| job.id == id) else {
+                    continue;
+                };
+                let Some(candidate) = found else {
+                    warn!("⛏️  No nonce met difficulty {} at H-{} — retrying", tc.difficulty, tc.blocks.len());
+                    continue;
+                };
+                if let Err(e) = tc.add_block(candidate.clone()) {
+                    warn!("⚠️  Mined block rejected: {}", e);
+                    continue;
+                }
+                let nonce = candidate.nonce;
+                let block_signer = job.payout_signer.as_ref().unwrap_or(&wallet);
+                info!(height = tc.blocks.len(), block = %hex::encode(candidate.legacy_hash()), nonce,
+                    txs = candidate.transactions.len(),
+                    "✨ MINED: H-{} | Nonce: {} | Txs: {}", tc.blocks.len(), nonce, candidate.transactions.len());
+                sync_manager.local_height_changed(tc.blocks.len() as u64, Instant::now());
+                if rotate_payout {
+                    payout_branch.advance();
+                    if let Err(e) = payout_branch.save(PAYOUT_BRANCH_FILE) {
+                        log::warn!("Failed to persist payout index: {}", e);
+                    }
+                }
+                let included: HashSet<[u8; 32]> = candidate.transactions.iter().map(|tx| tx.legacy_hash()).collect();
+                mempool.retain(|tx| !included.contains(&tx.legacy_hash()));
+                tx_risk_scores.retain(|hash, _| !included.contains(hash));
+                axiom_core::storage::append_block_metadata(&LocalBlockMetadata {
+                    height: tc.blocks.len() as u64 - 1,
+                    block_hash: hex::encode(candidate.legacy_hash()),
+                    template: job.metadata.clone(),
+                });
+                lock_or_recover(&change_feed).block_connected(tc.blocks.len() as u64 - 1, &candidate);
+                lock_or_recover(&address_index).index_block(tc.blocks.len() as u64 - 1, &candidate);
+                lock_or_recover(&receipt_index).index_block(tc.blocks.len() as u64 - 1, &candidate);
+                lock_or_recover(&block_index).index_block(tc.blocks.len() as u64 - 1, &candidate);
+                match wire::encode(&candidate) {
+                    Ok(encoded) => {
+                        let _ = swarm.behaviour_mut().gossipsub.publish(blocks_topic.clone(), encoded);
+                    }
+                    Err(e) => log::error!("Failed to encode mined block for broadcast: {}", e),
+                }
+                storage.save_chain(&tc.blocks, &tc.state);
+                *lock_or_recover(&account_state) = tc.state.clone();
+                *lock_or_recover(&supply) = tc.supply_snapshot();
+                *lock_or_recover(&fee_stats) = FeeStats::from_blocks(&tc.blocks);
+
+                // Broadcast real-time pulse to all peers
+                let pulse = mined_block_pulse(&candidate, &tc, &pulse_log, &api_state, block_signer, &key_audit).await;
+
+                // Chain the pulse hash for tamper-evident history
+                last_pulse_hash = axiom_core::axiom_hash_512(
+                    &bincode::serialize(&pulse).unwrap_or_default(),
+                );
+
+                lock_or_recover(&checkpoints).observe_pulse(&pulse, &tc.blocks);
+                lock_or_recover(&pulse_log).record(pulse.clone());
+                if let Ok(pulse_data) = wire::encode(&pulse) {
+                    let _ = swarm.behaviour_mut().gossipsub.publish(pulse_topic.clone(), pulse_data);
+                }
+
+                // Broadcast Global Trust Pulse every 100 blocks
+                let height = pulse.height;
+                if height % 100 == 0 {
+                    let stats = lock_or_recover(&ai_guardian).get_stats();
+                    let health = get_network_health(
+                        height,
+                        pulse.total_mined,
+                        pulse.remaining,
+                        connected_peers.len(),
+                        stats,
+                        &last_pulse_hash,
+                    );
+                    info!("💎 Global Trust Pulse @ H-{}: 512-bit commitment broadcast", height);
+                    if let Ok(health_data) = bincode::serialize(&health) {
+                        let _ = swarm.behaviour_mut().gossipsub.publish(health_topic.clone(), health_data);
+                    }
+                }
+
+                last_vdf = Instant::now();
+            }
         }
     }
+
+    info!("👋 Node stopped");
+    Ok(())
 }
 
-// ---------------------------------------------------------------------------
-// Warp rejection handling for rate-limiting
-// ---------------------------------------------------------------------------
-
-#[derive(Debug)]
-struct TooManyRequests;
-impl warp::reject::Reject for TooManyRequests {}
-
-async fn handle_rejection(err: warp::Rejection) -> Result<Box<dyn warp::Reply>, std::convert::Infallible> {
-    if err.find::<TooManyRequests>().is_some() {
-        let body = warp::reply::json(&serde_json::json!({
-            "error": "Too Many Requests",
-            "retry_after_secs": API_RATE_LIMIT_PER_MINUTE
-        }));
-        let with_status = warp::reply::with_status(body, warp::http::StatusCode::TOO_MANY_REQUESTS);
-        Ok(Box::new(warp::reply::with_header(with_status, "Retry-After", API_RATE_LIMIT_PER_MINUTE.to_string())))
-    } else {
-        let body = warp::reply::json(&serde_json::json!({"error": "Not Found"}));
-        Ok(Box::new(warp::reply::with_status(body, warp::http::StatusCode::NOT_FOUND)))
+/// Perform the operations the guardian enforcer issued against `peer` for
+/// `action`, and log them for audit. Returns the gossip penalty among them,
+/// for the caller to charge to the peer's message count.
+fn apply_enforcement(
+    swarm: &mut Swarm<axiom_core::network_legacy::TimechainBehaviour>,
+    peer_diversity: &mut PeerDiversity,
+    audit: &Mutex<DecisionAuditLog>,
+    peer: PeerId,
+    action: Option<&Action>,
+    ops: &[Enforcement],
+) -> u32 {
+    lock_or_recover(audit).record_enforcement(&peer.to_string(), action, ops, unix_now());
+    let mut penalty = 0;
+    for op in ops {
+        match op {
+            Enforcement::Disconnect => {
+                let _ = swarm.disconnect_peer_id(peer);
+            }
+            Enforcement::Ban { until } => {
+                swarm.behaviour_mut().gossipsub.blacklist_peer(&peer);
+                warn!("🚫 Guardian banned peer {} until {}", peer, until);
+            }
+            Enforcement::Unban => swarm.behaviour_mut().gossipsub.remove_blacklisted_peer(&peer),
+            Enforcement::GossipPenalty { points } => penalty += points,
+            Enforcement::MessageLimit { per_minute, .. } => {
+                warn!("🚨 Guardian limited peer {} to {} msgs/min", peer, per_minute);
+            }
+            Enforcement::RotatePeers => {
+                // Eclipse suspicion: shed the newest peers from the most
+                // crowded subnets; discovery refills the slots elsewhere.
+                for victim in peer_diversity.rotate(Instant::now()) {
+                    info!("🧭 Guardian requested peer rotation, dropping {}", victim);
+                    let _ = swarm.disconnect_peer_id(victim);
+                }
+            }
+        }
     }
+    penalty
 }
+
+/// Network group of a peer's first known address, for guardian reports.
+fn peer_subnet(peerstore: &PeerStore, peer: &PeerId) -> Option<String> {
+    peerstore.addrs_of(peer).iter().find_map(ip_of).map(|ip| Subnet::of(ip).to_string())
+}
+
@@ -2,12 +2,129 @@
 
 use std::collections::{HashMap, HashSet, BTreeMap};
 use sha2::Digest;
-use crate::{transaction::Transaction, error::{AxiomError, Result}};
+use crate::{transaction::Transaction, state::AccountState, error::{AxiomError, Result}};
 
 pub type Address = [u8; 32];
 
 const DEFAULT_MAX_SIZE: usize = 100_000;
 const DEFAULT_MAX_TX_SIZE: usize = 100_000;
+/// Pending transactions a single sender may hold in the pool.
+pub const DEFAULT_MAX_TXS_PER_SENDER: usize = 64;
+/// Serialized bytes a single sender may hold in the pool.
+pub const DEFAULT_MAX_BYTES_PER_SENDER: usize = 1_000_000;
+
+/// Fee increase, in percent, a transaction must offer to replace a pending
+/// one with the same sender and nonce.
+pub const MIN_REPLACEMENT_BUMP_PERCENT: u64 = 10;
+
+/// The lowest fee that replaces a pending transaction paying `fee`: at
+/// least one unit more, however small the fee.
+pub fn min_replacement_fee(fee: u64) -> u64 {
+    fee.saturating_add((fee.saturating_mul(MIN_REPLACEMENT_BUMP_PERCENT) / 100).max(1))
+}
+
+/// The nonce `sender`'s next transaction must carry: the first one after
+/// the run of consecutive nonces it has pending in `pool`, starting from
+/// `account_nonce`, the one its account expects.
+pub fn pending_nonce<'a>(pool: impl IntoIterator<Item = &'a Transaction>, sender: &Address, account_nonce: u64) -> u64 {
+    let nonces: HashSet<u64> = pool.into_iter().filter(|tx| tx.from == *sender).map(|tx| tx.nonce).collect();
+    (account_nonce..).find(|n| !nonces.contains(n)).unwrap_or(account_nonce)
+}
+
+/// Check `tx` against the transactions already in `pool`, for the block at
+/// `height` and the sender's `account`. On success returns the position in
+/// `pool` of the transaction it replaces, if any.
+///
+/// A transaction is refused past its expiry height, with a nonce its
+/// account already used, or with one beyond `pending_nonce`, which would
+/// leave a gap. It is also refused when it conflicts with the pool: it
+/// takes the nonce of a pending transaction without paying
+/// `min_replacement_fee` for it, or together with the sender's other
+/// pending transactions it spends more than the balance.
+pub fn check_admission<'a>(
+    tx: &Transaction,
+    pool: impl IntoIterator<Item = &'a Transaction> + Clone,
+    height: u64,
+    account: &AccountState,
+) -> Result<Option<usize>> {
+    if tx.is_expired_at(height) {
+        return Err(AxiomError::TransactionExpired { expiry_height: tx.expiry_height, height });
+    }
+    if tx.nonce < account.nonce {
+        return Err(AxiomError::InvalidNonce { expected: account.nonce, actual: tx.nonce });
+    }
+    let next_nonce = pending_nonce(pool.clone(), &tx.from, account.nonce);
+    if tx.nonce > next_nonce {
+        return Err(AxiomError::NonceGap { expected: next_nonce, actual: tx.nonce });
+    }
+    let hash = tx.legacy_hash();
+    let mut replaces = None;
+    let (mut pending, mut committed) = (0usize, 0u64);
+    let senders = pool.into_iter().enumerate().filter(|(_, p)| p.from == tx.from && p.nonce >= account.nonce);
+    for (i, other) in senders {
+        if other.legacy_hash() == hash {
+            return Err(AxiomError::DuplicateTransaction);
+        }
+        if other.nonce == tx.nonce {
+            let min_fee = min_replacement_fee(other.fee);
+            if tx.fee < min_fee {
+                return Err(AxiomError::ReplacementUnderpriced {
+                    pending: hex::encode(other.legacy_hash()),
+                    min_fee,
+                    actual: tx.fee,
+                });
+            }
+            replaces = Some(i);
+            continue;
+        }
+        pending += 1;
+        committed = committed.saturating_add(other.amount.saturating_add(other.fee));
+    }
+    let required = committed.saturating_add(tx.amount.saturating_add(tx.fee));
+    if pending > 0 && required > account.balance {
+        return Err(AxiomError::DoubleSpend { pending, available: account.balance, required });
+    }
+    Ok(replaces)
+}
+
+/// Per-sender quotas, so one key cannot fill the whole pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SenderLimits {
+    pub max_txs: usize,
+    pub max_bytes: usize,
+}
+
+impl Default for SenderLimits {
+    fn default() -> Self {
+        Self {
+            max_txs: DEFAULT_MAX_TXS_PER_SENDER,
+            max_bytes: DEFAULT_MAX_BYTES_PER_SENDER,
+        }
+    }
+}
+
+impl SenderLimits {
+    /// Check whether `sender`, currently holding `pending_txs` transactions
+    /// totalling `pending_bytes`, may add one more of `tx_size` bytes.
+    pub fn check(&self, sender: &Address, pending_txs: usize, pending_bytes: usize, tx_size: usize) -> Result<()> {
+        if pending_txs >= self.max_txs {
+            return Err(AxiomError::SenderTxLimitExceeded {
+                sender: hex::encode(sender),
+                pending: pending_txs,
+                max: self.max_txs,
+            });
+        }
+        let total = pending_bytes.saturating_add(tx_size);
+        if total > self.max_bytes {
+            return Err(AxiomError::SenderBytesLimitExceeded {
+                sender: hex::encode(sender),
+                pending_bytes: total,
+                max: self.max_bytes,
+            });
+        }
+        Ok(())
+    }
+}
 
 /// Production-grade transaction mempool
 pub struct Mempool {
@@ -17,6 +134,10 @@ pub struct Mempool {
     by_fee: BTreeMap<u64, HashSet<[u8; 32]>>,
     /// Transactions grouped by sender
     by_sender: HashMap<Address, Vec<[u8; 32]>>,
+    /// Serialized bytes held by each sender
+    sender_bytes: HashMap<Address, usize>,
+    /// Per-sender count and byte quotas
+    sender_limits: SenderLimits,
     /// Nullifiers to prevent double-spend
     nullifiers: HashSet<[u8; 32]>,
     /// Maximum mempool size
@@ -31,6 +152,8 @@ impl Mempool {
             transactions: HashMap::new(),
             by_fee: BTreeMap::new(),
             by_sender: HashMap::new(),
+            sender_bytes: HashMap::new(),
+            sender_limits: SenderLimits::default(),
             nullifiers: HashSet::new(),
             max_size: DEFAULT_MAX_SIZE,
             max_tx_size: DEFAULT_MAX_TX_SIZE,
@@ -42,15 +165,28 @@ impl Mempool {
             transactions: HashMap::with_capacity(max_size),
             by_fee: BTreeMap::new(),
             by_sender: HashMap::new(),
+            sender_bytes: HashMap::new(),
+            sender_limits: SenderLimits::default(),
             nullifiers: HashSet::new(),
             max_size,
             max_tx_size,
         }
     }
     
+    /// Override the default per-sender quotas
+    pub fn with_sender_limits(mut self, limits: SenderLimits) -> Self {
+        self.sender_limits = limits;
+        self
+    }
+    
+    /// Current per-sender quotas
+    pub fn sender_limits(&self) -> SenderLimits {
+        self.sender_limits
+    }
+    
     /// Add transaction to mempool
     pub fn add(&mut self, tx: Transaction) -> Result<()> {
-        let hash = tx.hash();
+        let hash = tx.legacy_hash();
         
         // Calculate size
         let tx_size = bincode::serialize(&tx)
@@ -81,14 +217,39 @@ impl Mempool {
             n
         };
         
-        // Check nullifier (double-spend protection)
+        // Check nullifier (double-spend protection): the nonce is taken
+        // unless this pays enough to replace the pending transaction. The
+        // pending one stays pooled until every other check has passed.
+        let mut replaced = None;
         if self.nullifiers.contains(&nullifier) {
-            return Err(AxiomError::NullifierUsed);
+            let pending = self.get_by_sender(&tx.from).into_iter().find(|p| p.nonce == tx.nonce);
+            let Some(pending) = pending else {
+                return Err(AxiomError::NullifierUsed);
+            };
+            let min_fee = min_replacement_fee(pending.fee);
+            if tx.fee < min_fee {
+                return Err(AxiomError::ReplacementUnderpriced {
+                    pending: hex::encode(pending.legacy_hash()),
+                    min_fee,
+                    actual: tx.fee,
+                });
+            }
+            let pending_size = bincode::serialized_size(&pending).unwrap_or(0) as usize;
+            replaced = Some((pending.legacy_hash(), pending_size));
         }
+        let (freed_txs, freed_bytes) = replaced.map_or((0, 0), |(_, size)| (1, size));
+        
+        // Check per-sender quotas before touching anyone else's transactions
+        self.sender_limits.check(
+            &tx.from,
+            self.by_sender.get(&tx.from).map_or(0, |h| h.len()).saturating_sub(freed_txs),
+            self.sender_bytes.get(&tx.from).copied().unwrap_or(0).saturating_sub(freed_bytes),
+            tx_size,
+        )?;
         
         // Check mempool capacity
-        if self.transactions.len() >= self.max_size {
-            // Try to evict lowest fee transaction
+        let full = self.transactions.len() - freed_txs >= self.max_size;
+        if full {
             if let Some((&lowest_fee, _)) = self.by_fee.iter().next() {
                 if tx.fee <= lowest_fee {
                     return Err(AxiomError::FeeTooLow {
@@ -96,10 +257,17 @@ impl Mempool {
                         actual: tx.fee,
                     });
                 }
-                self.evict_lowest_fee();
             }
         }
         
+        // Admitted: make room for it
+        if let Some((pending_hash, _)) = replaced {
+            self.remove(&pending_hash);
+        } else if full {
+            // Evict the lowest fee transaction
+            self.evict_lowest_fee();
+        }
+        
         // Add to indexes
         self.by_fee
             .entry(tx.fee)
@@ -110,6 +278,7 @@ impl Mempool {
             .entry(tx.from)
             .or_default()
             .push(hash);
+        *self.sender_bytes.entry(tx.from).or_default() += tx_size;
         
         self.nullifiers.insert(nullifier);
         self.transactions.insert(hash, tx);
@@ -164,6 +333,13 @@ impl Mempool {
                     self.by_sender.remove(&tx.from);
                 }
             }
+            let tx_size = bincode::serialized_size(&tx).map_or(0, |s| s as usize);
+            if let Some(bytes) = self.sender_bytes.get_mut(&tx.from) {
+                *bytes = bytes.saturating_sub(tx_size);
+                if !self.by_sender.contains_key(&tx.from) {
+                    self.sender_bytes.remove(&tx.from);
+                }
+            }
             
             // Remove nullifier
             let nullifier = {
@@ -190,6 +366,11 @@ impl Mempool {
         }
     }
     
+    /// Serialized bytes currently held by a sender
+    pub fn pending_bytes(&self, sender: &Address) -> usize {
+        self.sender_bytes.get(sender).copied().unwrap_or(0)
+    }
+    
     /// Get all transactions from a sender
     pub fn get_by_sender(&self, sender: &Address) -> Vec<Transaction> {
         self.by_sender
@@ -231,6 +412,7 @@ impl Mempool {
         self.transactions.clear();
         self.by_fee.clear();
         self.by_sender.clear();
+        self.sender_bytes.clear();
         self.nullifiers.clear();
     }
     
@@ -273,6 +455,7 @@ mod tests {
             amount,
             fee,
             nonce,
+            expiry_height: 0,
             zk_proof: vec![],
             signature: vec![],
         }
@@ -324,4 +507,103 @@ mod tests {
         let stats = mempool.stats();
         assert_eq!(stats.lowest_fee, 10);
     }
+    
+    #[test]
+    fn test_mempool_sender_limits() {
+        let tx_size = bincode::serialized_size(&create_test_transaction(100, 1, 0)).unwrap() as usize;
+        let mut mempool = Mempool::new().with_sender_limits(SenderLimits { max_txs: 2, max_bytes: usize::MAX });
+        
+        assert!(mempool.add(create_test_transaction(100, 1, 0)).is_ok());
+        assert!(mempool.add(create_test_transaction(100, 1, 1)).is_ok());
+        assert!(matches!(
+            mempool.add(create_test_transaction(100, 1, 2)),
+            Err(AxiomError::SenderTxLimitExceeded { pending: 2, max: 2, .. })
+        ));
+        
+        // Another sender is unaffected
+        let mut other = create_test_transaction(100, 1, 0);
+        other.from = [9u8; 32];
+        assert!(mempool.add(other).is_ok());
+        
+        // Removing frees the quota again
+        let first = create_test_transaction(100, 1, 0).legacy_hash();
+        mempool.remove(&first);
+        assert_eq!(mempool.pending_bytes(&[1u8; 32]), tx_size);
+        assert!(mempool.add(create_test_transaction(100, 1, 2)).is_ok());
+        
+        let mut by_bytes = Mempool::new().with_sender_limits(SenderLimits { max_txs: 10, max_bytes: tx_size * 2 });
+        assert!(by_bytes.add(create_test_transaction(100, 1, 0)).is_ok());
+        assert!(by_bytes.add(create_test_transaction(100, 1, 1)).is_ok());
+        let err = by_bytes.add(create_test_transaction(100, 1, 2)).unwrap_err();
+        assert!(matches!(err, AxiomError::SenderBytesLimitExceeded { .. }));
+        assert!(err.to_string().starts_with("Mempool sender limit"));
+    }
+    #[test]
+    fn test_admission_expiry_replacement_and_double_spend() {
+        let pool = vec![create_test_transaction(100, 10, 0), create_test_transaction(100, 10, 1)];
+        let account = AccountState { balance: 1_000, nonce: 0, tx_count: 0 };
+
+        let expiring = create_test_transaction(100, 10, 2).with_expiry(5);
+        assert!(check_admission(&expiring, &pool, 5, &account).unwrap().is_none());
+        assert!(matches!(
+            check_admission(&expiring, &pool, 6, &account),
+            Err(AxiomError::TransactionExpired { expiry_height: 5, height: 6 })
+        ));
+        assert!(matches!(check_admission(&pool[1], &pool, 1, &account), Err(AxiomError::DuplicateTransaction)));
+
+        // Nonces: none the account used, none past the pending run.
+        assert_eq!(pending_nonce(&pool, &[1u8; 32], 0), 2);
+        assert_eq!(pending_nonce(&pool, &[9u8; 32], 7), 7);
+        let confirmed = AccountState { nonce: 1, ..account };
+        let err = check_admission(&create_test_transaction(100, 20, 0), &pool, 1, &confirmed).unwrap_err();
+        assert_eq!(err.code(), crate::error::ErrorCode::NonceTooLow);
+        let err = check_admission(&create_test_transaction(100, 10, 3), &pool, 1, &account).unwrap_err();
+        assert!(matches!(err, AxiomError::NonceGap { expected: 2, actual: 3 }));
+        assert_eq!(err.code(), crate::error::ErrorCode::NonceGap);
+
+        // Same nonce: a double spend unless the fee rises by the bump ratio.
+        assert_eq!(min_replacement_fee(10), 11);
+        assert_eq!(min_replacement_fee(1_000), 1_100);
+        let err = check_admission(&create_test_transaction(200, 10, 1), &pool, 1, &account).unwrap_err();
+        assert!(matches!(err, AxiomError::ReplacementUnderpriced { min_fee: 11, actual: 10, .. }));
+        assert_eq!(err.code(), crate::error::ErrorCode::ReplacementUnderpriced);
+        assert_eq!(check_admission(&create_test_transaction(200, 11, 1), &pool, 1, &account).unwrap(), Some(1));
+
+        // Spending what the pending transactions already committed.
+        let err = check_admission(&create_test_transaction(800, 10, 2), &pool, 1, &account).unwrap_err();
+        assert!(matches!(err, AxiomError::DoubleSpend { pending: 2, available: 1_000, required: 1_030 }));
+        assert!(check_admission(&create_test_transaction(800, 10, 1), &pool[..1], 1, &account).is_ok());
+
+        let mut mempool = Mempool::new();
+        assert!(mempool.add(pool[0].clone()).is_ok());
+        assert!(matches!(
+            mempool.add(create_test_transaction(200, 10, 0)),
+            Err(AxiomError::ReplacementUnderpriced { .. })
+        ));
+        let replacement = create_test_transaction(200, 11, 0);
+        assert!(mempool.add(replacement.clone()).is_ok());
+        assert_eq!(mempool.get_by_sender(&[1u8; 32]), vec![replacement]);
+    }
+
+    #[test]
+    fn test_rejected_replacement_keeps_the_original() {
+        let original = create_test_transaction(100, 10, 0);
+        let tx_size = bincode::serialized_size(&original).unwrap() as usize;
+        let mut mempool = Mempool::new().with_sender_limits(SenderLimits { max_txs: 1, max_bytes: tx_size });
+        assert!(mempool.add(original.clone()).is_ok());
+
+        // Pays enough to replace, but is over the sender's byte quota.
+        let mut bloated = create_test_transaction(100, 20, 0);
+        bloated.zk_proof = vec![0u8; 16];
+        assert!(matches!(mempool.add(bloated), Err(AxiomError::SenderBytesLimitExceeded { .. })));
+        assert_eq!(mempool.get_by_sender(&[1u8; 32]), vec![original.clone()]);
+        assert_eq!(mempool.pending_bytes(&[1u8; 32]), tx_size);
+
+        // The replaced slot counts as free against the quota and capacity.
+        let mut full = Mempool::with_capacity(1, DEFAULT_MAX_TX_SIZE).with_sender_limits(SenderLimits { max_txs: 1, max_bytes: tx_size });
+        assert!(full.add(original).is_ok());
+        let replacement = create_test_transaction(200, 11, 0);
+        assert!(full.add(replacement.clone()).is_ok());
+        assert_eq!(full.get_by_sender(&[1u8; 32]), vec![replacement]);
+    }
 }
@@ -2,6 +2,17 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 use std::time::Instant;
 use std::collections::VecDeque;
+use serde::Serialize;
+
+use crate::network::peer_manager::BandwidthStats;
+
+/// Traffic counters for one connected peer.
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerBandwidth {
+    pub peer_id: String,
+    #[serde(flatten)]
+    pub stats: BandwidthStats,
+}
 
 /// Node metrics collector — tracks operational telemetry for production
 /// monitoring dashboards and alerting.
@@ -28,6 +39,16 @@ pub struct NodeMetrics {
     pub mempool_size: usize,
     /// Blocks mined by this node
     pub blocks_mined: u64,
+    /// Bytes received from all peers (accepted messages only)
+    pub total_bytes_in: u64,
+    /// Bytes sent to individual peers
+    pub total_bytes_out: u64,
+    /// Per-peer traffic, busiest first
+    pub peer_bandwidth: Vec<PeerBandwidth>,
+    /// Stale blocks seen on the network since startup
+    pub stale_blocks: u64,
+    /// Share of recently found blocks that went stale (0.0 to 1.0)
+    pub orphan_rate: f64,
 }
 
 impl Default for NodeMetrics {
@@ -46,6 +67,11 @@ impl Default for NodeMetrics {
             memory_usage_bytes: 0,
             mempool_size: 0,
             blocks_mined: 0,
+            total_bytes_in: 0,
+            total_bytes_out: 0,
+            peer_bandwidth: Vec::new(),
+            stale_blocks: 0,
+            orphan_rate: 0.0,
         }
     }
 }
@@ -145,6 +171,22 @@ impl MetricsCollector {
         self.metrics.write().mempool_size = size;
     }
     
+    /// Update stale block count and orphan rate (from `StaleBlocks`)
+    pub fn set_stale_blocks(&self, total: u64, orphan_rate: f64) {
+        let mut m = self.metrics.write();
+        m.stale_blocks = total;
+        m.orphan_rate = orphan_rate;
+    }
+
+    /// Replace the per-peer bandwidth table (from `PeerRateLimiter::all_stats`)
+    pub fn set_peer_bandwidth(&self, mut peers: Vec<PeerBandwidth>) {
+        peers.sort_by_key(|p| std::cmp::Reverse(p.stats.bytes_in + p.stats.bytes_out));
+        let mut m = self.metrics.write();
+        m.total_bytes_in = peers.iter().map(|p| p.stats.bytes_in).sum();
+        m.total_bytes_out = peers.iter().map(|p| p.stats.bytes_out).sum();
+        m.peer_bandwidth = peers;
+    }
+    
     /// Take a consistent snapshot of all metrics
     pub fn snapshot(&self) -> NodeMetrics {
         let mut metrics = self.metrics.read().clone();
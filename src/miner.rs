@@ -0,0 +1,277 @@
+// src/miner.rs - Block mining off the node's event loop
+//
+// Running the VDF and grinding nonces for a block takes as long as the
+// delay and difficulty demand, during which the event loop would stop
+// handling gossip, sync and RPC. `Miner` runs each job on its own threads
+// instead: one evaluates the VDF, then `threads` workers search interleaved
+// nonces. The result comes back on a channel the event loop selects on.
+//
+// A job is cancelled when the tip moves (a peer's block arrived first) or
+// the node stops. The VDF backend cannot be interrupted, so a job
+// cancelled during the delay ends when the delay does; nothing it finds
+// afterwards is reported. `progress()` tells the dashboard how far the
+// current job is.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::block::Block;
+use crate::vdf::Vdf;
+
+/// Nonces a worker tries between checks for cancellation.
+const CHECK_INTERVAL: u64 = 4096;
+
+/// A block to mine. `candidate` is complete except for `vdf_proof`,
+/// `vdf_witness` and `nonce`.
+pub struct MiningJob {
+    pub candidate: Block,
+    pub difficulty: u64,
+    /// First nonce tried; workers spread out from here.
+    pub nonce_start: u64,
+    /// Nonces tried before giving up on this job.
+    pub max_attempts: u64,
+    pub vdf: Arc<dyn Vdf>,
+}
+
+#[derive(Debug)]
+pub enum MinerEvent {
+    /// A block meeting the job's difficulty.
+    Found { job: u64, block: Box<Block> },
+    /// Every nonce in the budget failed.
+    Exhausted { job: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MiningPhase {
+    #[default]
+    Idle,
+    Vdf,
+    Nonces,
+}
+
+/// Where the current job stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct MiningProgress {
+    pub job: u64,
+    /// Height of the block being mined.
+    pub height: u64,
+    pub phase: MiningPhase,
+    /// Nonces tried so far, rounded down to `CHECK_INTERVAL` per worker.
+    pub attempts: u64,
+    pub max_attempts: u64,
+    pub threads: usize,
+}
+
+pub struct Miner {
+    threads: usize,
+    events: mpsc::UnboundedSender<MinerEvent>,
+    progress: Arc<Mutex<MiningProgress>>,
+    /// Cancel flag of the running job.
+    cancel: Option<Arc<AtomicBool>>,
+    next_job: u64,
+}
+
+impl Miner {
+    /// A miner using `threads` nonce workers (at least one), and the
+    /// channel its results arrive on.
+    pub fn new(threads: usize) -> (Self, mpsc::UnboundedReceiver<MinerEvent>) {
+        let (events, rx) = mpsc::unbounded_channel();
+        let miner = Self {
+            threads: threads.max(1),
+            events,
+            progress: Arc::new(Mutex::new(MiningProgress::default())),
+            cancel: None,
+            next_job: 1,
+        };
+        (miner, rx)
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    pub fn progress(&self) -> MiningProgress {
+        *self.progress.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether a job is running and not cancelled.
+    pub fn is_busy(&self) -> bool {
+        self.progress().phase != MiningPhase::Idle
+    }
+
+    /// Stop the running job, if any. Its result will not be reported.
+    pub fn cancel(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        let mut progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        progress.phase = MiningPhase::Idle;
+    }
+
+    /// Start `job` in the background, cancelling any running one.
+    /// Returns its id, which its `MinerEvent` carries.
+    pub fn start(&mut self, job: MiningJob) -> u64 {
+        self.cancel();
+        let id = self.next_job;
+        self.next_job += 1;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.cancel = Some(cancel.clone());
+        *self.progress.lock().unwrap_or_else(|e| e.into_inner()) = MiningProgress {
+            job: id,
+            height: job.candidate.slot,
+            phase: MiningPhase::Vdf,
+            attempts: 0,
+            max_attempts: job.max_attempts,
+            threads: self.threads,
+        };
+
+        let threads = self.threads;
+        let events = self.events.clone();
+        let progress = self.progress.clone();
+        thread::spawn(move || {
+            let outcome = run(id, job, threads, &cancel, &progress);
+            let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+            if progress.job == id {
+                progress.phase = MiningPhase::Idle;
+            }
+            drop(progress);
+            if let Some(event) = outcome.filter(|_| !cancel.load(Ordering::Relaxed)) {
+                let _ = events.send(event);
+            }
+        });
+        id
+    }
+}
+
+fn set_progress(progress: &Mutex<MiningProgress>, job: u64, update: impl FnOnce(&mut MiningProgress)) {
+    let mut progress = progress.lock().unwrap_or_else(|e| e.into_inner());
+    if progress.job == job {
+        update(&mut progress);
+    }
+}
+
+/// Mine one job; `None` if it was cancelled.
+fn run(
+    id: u64,
+    job: MiningJob,
+    threads: usize,
+    cancel: &AtomicBool,
+    progress: &Mutex<MiningProgress>,
+) -> Option<MinerEvent> {
+    let mut candidate = job.candidate;
+    let seed = crate::vdf::evaluate(candidate.parent, candidate.slot);
+    let vdf = job.vdf.prove(seed, job.difficulty);
+    if cancel.load(Ordering::Relaxed) {
+        return None;
+    }
+    candidate.vdf_proof = vdf.output;
+    candidate.vdf_witness = vdf.witness;
+    set_progress(progress, id, |p| p.phase = MiningPhase::Nonces);
+
+    let found: Mutex<Option<Block>> = Mutex::new(None);
+    let done = AtomicBool::new(false);
+    let attempts = AtomicU64::new(0);
+    thread::scope(|scope| {
+        for worker in 0..threads as u64 {
+            let mut block = candidate.clone();
+            let (found, done, attempts) = (&found, &done, &attempts);
+            scope.spawn(move || {
+                let mut i = worker;
+                let mut tried = 0u64;
+                while i < job.max_attempts {
+                    block.nonce = job.nonce_start.wrapping_add(i);
+                    if block.meets_difficulty(job.difficulty) {
+                        if !done.swap(true, Ordering::Relaxed) {
+                            *found.lock().unwrap_or_else(|e| e.into_inner()) = Some(block);
+                        }
+                        return;
+                    }
+                    i = i.saturating_add(threads as u64);
+                    tried += 1;
+                    if tried.is_multiple_of(CHECK_INTERVAL) {
+                        if done.load(Ordering::Relaxed) || cancel.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        let total = attempts.fetch_add(CHECK_INTERVAL, Ordering::Relaxed) + CHECK_INTERVAL;
+                        set_progress(progress, id, |p| p.attempts = total);
+                    }
+                }
+            });
+        }
+    });
+
+    if cancel.load(Ordering::Relaxed) {
+        return None;
+    }
+    match found.into_inner().unwrap_or_else(|e| e.into_inner()) {
+        Some(block) => Some(MinerEvent::Found { job: id, block: Box::new(block) }),
+        None => Some(MinerEvent::Exhausted { job: id }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vdf::{HashChainVdf, VdfOutput};
+
+    /// Lets a job at a huge difficulty skip straight to the nonce search.
+    struct NoDelay;
+
+    impl Vdf for NoDelay {
+        fn name(&self) -> &'static str {
+            "none"
+        }
+
+        fn prove(&self, _seed: [u8; 32], _iterations: u64) -> VdfOutput {
+            VdfOutput { output: [0u8; 32], witness: Vec::new() }
+        }
+
+        fn verify(&self, _seed: [u8; 32], _iterations: u64, _output: &[u8; 32], _witness: &[u8]) -> bool {
+            true
+        }
+    }
+
+    fn job(difficulty: u64, max_attempts: u64, vdf: Arc<dyn Vdf>) -> MiningJob {
+        let genesis = crate::genesis::genesis();
+        MiningJob {
+            candidate: Block { miner: [1u8; 32], ..genesis.test_child() },
+            difficulty,
+            nonce_start: 0,
+            max_attempts,
+            vdf,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_background_job_reports_and_cancels() {
+        let (mut miner, mut events) = Miner::new(4);
+        let id = miner.start(job(16, 1_000_000, Arc::new(HashChainVdf)));
+        match events.recv().await {
+            Some(MinerEvent::Found { job, block }) => {
+                assert_eq!(job, id);
+                assert!(block.meets_difficulty(16));
+                let seed = crate::vdf::evaluate(block.parent, block.slot);
+                assert!(HashChainVdf.verify(seed, 16, &block.vdf_proof, &block.vdf_witness));
+            }
+            other => panic!("expected a block, got {:?}", other),
+        }
+
+        // Unreachable difficulty with a small budget runs out.
+        let id = miner.start(job(u64::MAX, 10_000, Arc::new(NoDelay)));
+        assert!(matches!(events.recv().await, Some(MinerEvent::Exhausted { job }) if job == id));
+        assert!(!miner.is_busy());
+
+        // A cancelled job reports nothing.
+        miner.start(job(u64::MAX, u64::MAX, Arc::new(NoDelay)));
+        assert!(miner.is_busy());
+        miner.cancel();
+        assert!(!miner.is_busy());
+        let id = miner.start(job(16, 1_000_000, Arc::new(HashChainVdf)));
+        assert!(matches!(events.recv().await, Some(MinerEvent::Found { job, .. }) if job == id));
+    }
+}
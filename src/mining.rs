@@ -0,0 +1,198 @@
+// src/mining.rs - Block templates and pulses for the background miner
+//
+// The event loop hands `Miner` a job built by `next_mining_job` on the
+// current tip, and announces each block it finds with the pulse from
+// `mined_block_pulse`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use axiom_core::types::Hash512;
+use axiom_core::AxiomPulse;
+use axiom_core::wallet::Wallet;
+use axiom_core::chain::Timechain;
+use axiom_core::economics::UNCLE_POLICY;
+use axiom_core::consensus::StaleBlocks;
+use axiom_core::key_audit::{KeyAuditLog, SignedKind, SigningInterface, SigningRecord};
+use axiom_core::supply_proof::PulseLog;
+use tracing::{info, warn};
+use axiom_core::block_template::{
+    build_template, fee_ordered, RiskPolicy, TemplateMetadata, DEFAULT_MAX_BLOCK_TRANSACTIONS,
+};
+use axiom_core::miner::MiningJob;
+use axiom_core::block::{Block, Coinbase};
+use axiom_core::transaction::Transaction;
+use axiom_core::stark::prover::{StarkProver, TransactionData, STARK_PROOF_INTERVAL};
+use crate::{lock_or_recover, audit_signature};
+use crate::api::PulseApiState;
+
+/// The block the background miner is working on, as far as the event
+/// loop needs it once the block is found.
+pub(crate) struct PendingMiningJob {
+    pub(crate) id: u64,
+    pub(crate) metadata: TemplateMetadata,
+    /// Fresh payout key the block pays, when payouts rotate.
+    pub(crate) payout_signer: Option<Wallet>,
+}
+
+/// Nonce search budget per mining job. At difficulty D a valid nonce takes
+/// D hashes on average, so NONCE_SEARCH_MULTIPLIER × D (minimum
+/// MIN_NONCE_ATTEMPTS) succeeds with >99.99% probability per VDF round.
+const NONCE_SEARCH_MULTIPLIER: u64 = 10;
+const MIN_NONCE_ATTEMPTS: u64 = 100_000;
+
+/// The next block to mine on `tc`'s tip, paying `block_signer`, and the
+/// template's record of what was left out.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn next_mining_job(
+    tc: &Timechain,
+    mempool: &VecDeque<Transaction>,
+    wallet: &Wallet,
+    block_signer: &Wallet,
+    stale_blocks: &StaleBlocks,
+    risk_policy: &RiskPolicy,
+    tx_risk_scores: &HashMap<[u8; 32], f64>,
+    max_block_bytes: usize,
+) -> (MiningJob, TemplateMetadata) {
+    let parent_hash = tc.blocks.last().map(|b| b.legacy_hash())
+        .unwrap_or_else(|| axiom_core::genesis::genesis().legacy_hash());
+    let current_slot = tc.blocks.len() as u64;
+    let zk_pass = axiom_core::genesis::generate_zk_pass(wallet, parent_hash);
+
+    // Current wall-clock timestamp for the new block, unless the chain
+    // needs a later one (median time past)
+    let block_timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .max(tc.min_next_timestamp());
+
+    // Randomize nonce start so competing miners don't all
+    // search the same nonce space — essential for real multi-node mining.
+    let nonce_start: u64 = rand::random();
+    let max_attempts = tc.difficulty.saturating_mul(NONCE_SEARCH_MULTIPLIER).max(MIN_NONCE_ATTEMPTS);
+
+    // Block template: valid transactions, best fee rate
+    // first, that apply in sequence against the current
+    // state, filtered by the miner's risk policy.
+    let template = build_template(
+        fee_ordered(mempool.iter().filter(|tx| tc.validate_transaction(tx).is_ok()), &tc.state),
+        DEFAULT_MAX_BLOCK_TRANSACTIONS,
+        max_block_bytes,
+        risk_policy,
+        |tx| tx_risk_scores.get(&tx.legacy_hash()).copied(),
+    );
+    if !template.metadata.excluded.is_empty() {
+        info!("🛡️  Risk policy excluded {} transaction(s) from block template",
+            template.metadata.excluded.len());
+    }
+
+    let coinbase = Coinbase::expected(current_slot, &block_signer.address, &template.transactions);
+    // Uncles cost block space, so only reference them when they pay.
+    let uncles = if UNCLE_POLICY.pays() {
+        stale_blocks.uncle_candidates(tc)
+    } else {
+        Vec::new()
+    };
+    let candidate = Block {
+        parent: parent_hash,
+        slot: current_slot,
+        timestamp: block_timestamp,
+        miner: block_signer.address,
+        transactions: template.transactions,
+        coinbase,
+        uncles,
+        vdf_proof: [0u8; 32],
+        vdf_witness: Vec::new(),
+        zk_proof: zk_pass,
+        nonce: nonce_start,
+    };
+    let job = MiningJob {
+        candidate,
+        difficulty: tc.difficulty,
+        nonce_start,
+        max_attempts,
+        vdf: tc.vdf(),
+    };
+    (job, template.metadata)
+}
+
+/// The signed pulse announcing `block`, just mined onto `tc`: supply
+/// figures, oracle seal, link to the previous pulse and, every
+/// STARK_PROOF_INTERVAL blocks, a STARK receipt whose check is shown as
+/// `zk_verified` on /v1/status.
+pub(crate) async fn mined_block_pulse(
+    block: &Block,
+    tc: &Timechain,
+    pulse_log: &Mutex<PulseLog>,
+    api_state: &Mutex<PulseApiState>,
+    block_signer: &Wallet,
+    key_audit: &Option<Arc<Mutex<KeyAuditLog>>>,
+) -> AxiomPulse {
+    let height = tc.blocks.len() as u64;
+    let (total_mined, remaining, _percent) = tc.supply_info();
+    // Generate deterministic AI oracle seal for this block
+    let oracle_query = format!(
+        "Axiom block {} mined with hash {}",
+        tc.blocks.len(),
+        hex::encode(block.legacy_hash())
+    );
+    let oracle_seal = axiom_core::ai::query_oracle(&oracle_query).await;
+
+    let mut pulse = AxiomPulse {
+        height,
+        total_mined,
+        remaining,
+        block_hash: block.hash_512(),
+        oracle_seal: Hash512(oracle_seal),
+        prev_pulse_hash: lock_or_recover(pulse_log).tip_hash(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64,
+        stark_receipt: None,
+        signature: Vec::new(),
+    };
+
+    // Generate mandatory STARK receipt every STARK_PROOF_INTERVAL blocks
+    if height % STARK_PROOF_INTERVAL == 0 {
+        let stark_tx = TransactionData {
+            initial_balance: remaining,
+            amount: total_mined,
+            fee: 0,
+            nonce: height,
+        };
+        match StarkProver::generate_proof(&stark_tx) {
+            Ok(receipt) => {
+                pulse.stark_receipt = Some(receipt.seal.clone());
+
+                // Verify the receipt against the expected anchor
+                let anchor = match StarkProver::compute_512_anchor(&stark_tx) {
+                    Ok(a) => a,
+                    Err(e) => {
+                        warn!("⚠️  STARK anchor computation failed @ H-{}: {}", height, e);
+                        [0u8; 64]
+                    }
+                };
+                let verified = match StarkProver::verify_receipt(&receipt, &anchor) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        warn!("⚠️  STARK receipt verification failed @ H-{}: {}", height, e);
+                        false
+                    }
+                };
+
+                lock_or_recover(api_state).zk_verified = verified;
+                info!("🔐 STARK receipt generated @ H-{}: zk_verified={}", height, verified);
+            }
+            Err(e) => {
+                warn!("⚠️  STARK receipt generation failed @ H-{}: {}", height, e);
+                lock_or_recover(api_state).zk_verified = false;
+            }
+        }
+    }
+
+    pulse.sign(block_signer);
+    audit_signature(key_audit, SigningRecord::object(
+        SigningInterface::Node, SignedKind::BlockPulse, block_signer.address, block.legacy_hash()));
+    pulse
+}
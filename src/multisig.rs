@@ -0,0 +1,408 @@
+// src/multisig.rs - m-of-n Ed25519 multisig accounts
+//
+// A multisig account is an address derived from a policy: a threshold m
+// and n cosigner public keys. It holds AXM like any other address; a
+// transaction spending from it carries, in place of the usual 64-byte
+// signature, the policy and at least m cosigner signatures over the
+// unsigned transaction. The chain checks that the policy hashes to the
+// sender address and that enough distinct cosigners signed.
+//
+// Cosigners do not need to be online together. One proposes the unsigned
+// transaction, each signs it separately and hands back a
+// `PartialSignature` (a short hex string, easy to pass over any channel),
+// and whoever collects m of them combines them into the final transaction.
+//
+// Spend encoding, shared with the SDK:
+//
+//   "AXMS" | m: u8 | n: u8 | n × 32-byte key | k: u8 | k × (index: u8, 64-byte signature)
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::transaction::{Address, Transaction};
+use crate::wallet::Wallet;
+
+/// Marks a transaction signature as a multisig spend.
+pub const MULTISIG_TAG: &[u8; 4] = b"AXMS";
+
+/// Most cosigners a policy may name.
+pub const MAX_MULTISIG_KEYS: usize = 15;
+
+const ADDRESS_DOMAIN: &[u8] = b"axiom-multisig-v1";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MultisigError {
+    #[error("threshold {threshold} is not between 1 and {keys} cosigners")]
+    InvalidThreshold { threshold: usize, keys: usize },
+    #[error("a policy names between 1 and {} cosigners, got {0}", MAX_MULTISIG_KEYS)]
+    InvalidKeyCount(usize),
+    #[error("cosigner {} appears twice", hex::encode(.0))]
+    DuplicateKey(Address),
+    #[error("{} is not an Ed25519 public key", hex::encode(.0))]
+    InvalidKey(Address),
+    #[error("{} is not a cosigner of this account", hex::encode(.0))]
+    NotACosigner(Address),
+    #[error("partial signature is for a different transaction")]
+    WrongTransaction,
+    #[error("transaction is not from this multisig account")]
+    AddressMismatch,
+    #[error("signature by {} does not verify", hex::encode(.0))]
+    BadSignature(Address),
+    #[error("{have} of {need} required signatures")]
+    NotEnoughSignatures { have: usize, need: usize },
+    #[error("malformed multisig data: {0}")]
+    Malformed(&'static str),
+}
+
+/// Threshold and cosigner keys of a multisig account. Keys are kept
+/// sorted, so the same set in any order gives the same address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    threshold: u8,
+    keys: Vec<Address>,
+}
+
+impl MultisigPolicy {
+    pub fn new(threshold: usize, mut keys: Vec<Address>) -> Result<Self, MultisigError> {
+        if keys.is_empty() || keys.len() > MAX_MULTISIG_KEYS {
+            return Err(MultisigError::InvalidKeyCount(keys.len()));
+        }
+        if threshold == 0 || threshold > keys.len() {
+            return Err(MultisigError::InvalidThreshold { threshold, keys: keys.len() });
+        }
+        keys.sort();
+        if let Some(pair) = keys.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(MultisigError::DuplicateKey(pair[0]));
+        }
+        if let Some(key) = keys.iter().find(|key| VerifyingKey::from_bytes(key).is_err()) {
+            return Err(MultisigError::InvalidKey(*key));
+        }
+        Ok(Self { threshold: threshold as u8, keys })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold as usize
+    }
+
+    pub fn keys(&self) -> &[Address] {
+        &self.keys
+    }
+
+    /// The account address: BLAKE3 over the threshold and sorted keys.
+    pub fn address(&self) -> Address {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(ADDRESS_DOMAIN);
+        hasher.update(&[self.threshold, self.keys.len() as u8]);
+        for key in &self.keys {
+            hasher.update(key);
+        }
+        *hasher.finalize().as_bytes()
+    }
+
+    fn index_of(&self, key: &Address) -> Option<u8> {
+        self.keys.binary_search(key).ok().map(|i| i as u8)
+    }
+}
+
+/// One cosigner's signature over an unsigned multisig transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature {
+    pub signer: Address,
+    /// Hash of the unsigned transaction, so a partial cannot be applied
+    /// to a different proposal by mistake.
+    pub tx_hash: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl PartialSignature {
+    /// Hex of signer, transaction hash and signature (256 characters).
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(128);
+        bytes.extend_from_slice(&self.signer);
+        bytes.extend_from_slice(&self.tx_hash);
+        bytes.extend_from_slice(&self.signature);
+        hex::encode(bytes)
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self, MultisigError> {
+        let bytes = hex::decode(encoded.trim()).map_err(|_| MultisigError::Malformed("partial signature is not hex"))?;
+        if bytes.len() != 128 {
+            return Err(MultisigError::Malformed("partial signature must be 128 bytes"));
+        }
+        Ok(Self {
+            signer: bytes[..32].try_into().expect("length checked"),
+            tx_hash: bytes[32..64].try_into().expect("length checked"),
+            signature: bytes[64..].to_vec(),
+        })
+    }
+}
+
+/// The contents of a multisig transaction's signature field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigSpend {
+    pub policy: MultisigPolicy,
+    /// Cosigner index in `policy.keys()` and signature, by ascending index.
+    pub signatures: Vec<(u8, [u8; 64])>,
+}
+
+impl MultisigSpend {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = MULTISIG_TAG.to_vec();
+        bytes.push(self.policy.threshold);
+        bytes.push(self.policy.keys.len() as u8);
+        for key in &self.policy.keys {
+            bytes.extend_from_slice(key);
+        }
+        bytes.push(self.signatures.len() as u8);
+        for (index, signature) in &self.signatures {
+            bytes.push(*index);
+            bytes.extend_from_slice(signature);
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, MultisigError> {
+        let mut rest = bytes.strip_prefix(MULTISIG_TAG.as_slice()).ok_or(MultisigError::Malformed("missing tag"))?;
+        let mut take = |len: usize| -> Result<&[u8], MultisigError> {
+            if rest.len() < len {
+                return Err(MultisigError::Malformed("truncated"));
+            }
+            let (head, tail) = rest.split_at(len);
+            rest = tail;
+            Ok(head)
+        };
+        let threshold = take(1)?[0] as usize;
+        let key_count = take(1)?[0] as usize;
+        let keys = (0..key_count)
+            .map(|_| take(32).map(|key| key.try_into().expect("32 bytes")))
+            .collect::<Result<Vec<Address>, _>>()?;
+        let policy = MultisigPolicy::new(threshold, keys.clone())?;
+        if policy.keys != keys {
+            return Err(MultisigError::Malformed("keys not in canonical order"));
+        }
+        let count = take(1)?[0] as usize;
+        let signatures = (0..count)
+            .map(|_| {
+                let index = take(1)?[0];
+                Ok((index, take(64)?.try_into().expect("64 bytes")))
+            })
+            .collect::<Result<Vec<_>, MultisigError>>()?;
+        if !rest.is_empty() {
+            return Err(MultisigError::Malformed("trailing bytes"));
+        }
+        Ok(Self { policy, signatures })
+    }
+}
+
+/// Whether a transaction signature field holds a multisig spend. A spend
+/// is always longer than a plain signature, so a 64-byte signature that
+/// happens to start with the tag is still a plain one.
+pub fn is_multisig_signature(signature: &[u8]) -> bool {
+    signature.len() > 64 && signature.starts_with(MULTISIG_TAG)
+}
+
+/// The message every cosigner signs: the transaction without signature,
+/// the same bytes a single-key wallet signs.
+fn signing_message(tx: &Transaction) -> Vec<u8> {
+    let mut unsigned = tx.clone();
+    unsigned.signature = vec![];
+    bincode::serialize(&unsigned).unwrap_or_default()
+}
+
+fn unsigned_hash(tx: &Transaction) -> [u8; 32] {
+    let mut unsigned = tx.clone();
+    unsigned.signature = vec![];
+    unsigned.legacy_hash()
+}
+
+/// Chain-side check of a multisig spend: the embedded policy must hash to
+/// `tx.from` and at least `threshold` distinct cosigners must have signed.
+pub fn verify_spend(tx: &Transaction) -> Result<(), MultisigError> {
+    let spend = MultisigSpend::decode(&tx.signature)?;
+    if spend.policy.address() != tx.from {
+        return Err(MultisigError::AddressMismatch);
+    }
+    let message = signing_message(tx);
+    let mut previous: Option<u8> = None;
+    for (index, signature) in &spend.signatures {
+        if previous.is_some_and(|p| *index <= p) {
+            return Err(MultisigError::Malformed("signatures not in ascending cosigner order"));
+        }
+        previous = Some(*index);
+        let key = spend.policy.keys.get(*index as usize).ok_or(MultisigError::Malformed("cosigner index out of range"))?;
+        let verifying_key = VerifyingKey::from_bytes(key).map_err(|_| MultisigError::InvalidKey(*key))?;
+        verifying_key
+            .verify(&message, &Signature::from_bytes(signature))
+            .map_err(|_| MultisigError::BadSignature(*key))?;
+    }
+    if spend.signatures.len() < spend.policy.threshold() {
+        return Err(MultisigError::NotEnoughSignatures {
+            have: spend.signatures.len(),
+            need: spend.policy.threshold(),
+        });
+    }
+    Ok(())
+}
+
+/// A multisig account as seen by one of its cosigners.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultisigWallet {
+    policy: MultisigPolicy,
+}
+
+impl MultisigWallet {
+    pub fn new(policy: MultisigPolicy) -> Self {
+        Self { policy }
+    }
+
+    pub fn policy(&self) -> &MultisigPolicy {
+        &self.policy
+    }
+
+    pub fn address(&self) -> Address {
+        self.policy.address()
+    }
+
+    /// Propose an unsigned spend from the account. The ZK proof is made
+    /// with the proposing cosigner's key, as for a single-key transaction.
+    pub fn propose_transaction(
+        &self,
+        proposer: &Wallet,
+        to: Address,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+        current_balance: u64,
+    ) -> Result<Transaction, Box<dyn std::error::Error>> {
+        if self.policy.index_of(&proposer.address).is_none() {
+            return Err(Box::new(MultisigError::NotACosigner(proposer.address)));
+        }
+        let zk_proof = crate::genesis::generate_transaction_proof(&proposer.secret_key, current_balance, amount, fee)?;
+        Ok(Transaction::new(self.address(), to, amount, fee, nonce, zk_proof, vec![]))
+    }
+
+    /// Sign a proposed spend as `cosigner`.
+    pub fn sign_partial(&self, tx: &Transaction, cosigner: &Wallet) -> Result<PartialSignature, MultisigError> {
+        if tx.from != self.address() {
+            return Err(MultisigError::AddressMismatch);
+        }
+        if self.policy.index_of(&cosigner.address).is_none() {
+            return Err(MultisigError::NotACosigner(cosigner.address));
+        }
+        let signing_key = SigningKey::from_bytes(&cosigner.secret_key);
+        Ok(PartialSignature {
+            signer: cosigner.address,
+            tx_hash: unsigned_hash(tx),
+            signature: signing_key.sign(&signing_message(tx)).to_bytes().to_vec(),
+        })
+    }
+
+    /// Check the partial signatures against `tx` and, once at least the
+    /// threshold are valid, return `tx` carrying the multisig spend. Extra
+    /// signatures beyond the threshold are dropped; duplicates count once.
+    pub fn combine(&self, tx: &Transaction, partials: &[PartialSignature]) -> Result<Transaction, MultisigError> {
+        if tx.from != self.address() {
+            return Err(MultisigError::AddressMismatch);
+        }
+        let (tx_hash, message) = (unsigned_hash(tx), signing_message(tx));
+        let mut signatures: Vec<(u8, [u8; 64])> = Vec::new();
+        for partial in partials {
+            if partial.tx_hash != tx_hash {
+                return Err(MultisigError::WrongTransaction);
+            }
+            let index = self.policy.index_of(&partial.signer).ok_or(MultisigError::NotACosigner(partial.signer))?;
+            let signature: [u8; 64] = partial
+                .signature
+                .as_slice()
+                .try_into()
+                .map_err(|_| MultisigError::Malformed("signature must be 64 bytes"))?;
+            VerifyingKey::from_bytes(&partial.signer)
+                .map_err(|_| MultisigError::InvalidKey(partial.signer))?
+                .verify(&message, &Signature::from_bytes(&signature))
+                .map_err(|_| MultisigError::BadSignature(partial.signer))?;
+            if signatures.iter().all(|(i, _)| *i != index) {
+                signatures.push((index, signature));
+            }
+        }
+        if signatures.len() < self.policy.threshold() {
+            return Err(MultisigError::NotEnoughSignatures { have: signatures.len(), need: self.policy.threshold() });
+        }
+        signatures.sort_by_key(|(index, _)| *index);
+        signatures.truncate(self.policy.threshold());
+
+        let mut signed = tx.clone();
+        signed.signature = MultisigSpend { policy: self.policy.clone(), signatures }.encode();
+        Ok(signed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cosigner(seed: u8) -> Wallet {
+        crate::payout::payout_wallet(&Wallet { secret_key: [seed; 32], address: [0u8; 32] }, 0)
+    }
+
+    fn proposal(account: &MultisigWallet) -> Transaction {
+        Transaction::new(account.address(), [9u8; 32], 1_000, 10, 0, vec![], vec![])
+    }
+
+    #[test]
+    fn test_two_of_three_spend() {
+        let (a, b, c) = (cosigner(1), cosigner(2), cosigner(3));
+        let policy = MultisigPolicy::new(2, vec![c.address, a.address, b.address]).unwrap();
+        // Key order does not change the account.
+        assert_eq!(policy.address(), MultisigPolicy::new(2, vec![a.address, b.address, c.address]).unwrap().address());
+        let account = MultisigWallet::new(policy);
+        let tx = proposal(&account);
+
+        // Partials travel as hex strings.
+        let from_c = PartialSignature::decode(&account.sign_partial(&tx, &c).unwrap().encode()).unwrap();
+        let from_a = account.sign_partial(&tx, &a).unwrap();
+        assert_eq!(
+            account.combine(&tx, std::slice::from_ref(&from_a)),
+            Err(MultisigError::NotEnoughSignatures { have: 1, need: 2 })
+        );
+        assert_eq!(
+            account.combine(&tx, &[from_a.clone(), from_a.clone()]),
+            Err(MultisigError::NotEnoughSignatures { have: 1, need: 2 })
+        );
+
+        let signed = account.combine(&tx, &[from_c, from_a]).unwrap();
+        assert!(is_multisig_signature(&signed.signature));
+        assert_eq!(verify_spend(&signed), Ok(()));
+        assert!(Wallet::verify_transaction_signature(&signed).unwrap());
+
+        // Any change to the signed transaction invalidates it.
+        let mut tampered = signed.clone();
+        tampered.amount += 1;
+        assert!(matches!(verify_spend(&tampered), Err(MultisigError::BadSignature(_))));
+        assert!(!Wallet::verify_transaction_signature(&tampered).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_foreign_policies_and_signers() {
+        let (a, b, outsider) = (cosigner(1), cosigner(2), cosigner(4));
+        assert!(matches!(MultisigPolicy::new(3, vec![a.address, b.address]), Err(MultisigError::InvalidThreshold { .. })));
+        assert!(matches!(MultisigPolicy::new(1, vec![a.address, a.address]), Err(MultisigError::DuplicateKey(_))));
+
+        let account = MultisigWallet::new(MultisigPolicy::new(1, vec![a.address, b.address]).unwrap());
+        let tx = proposal(&account);
+        assert_eq!(account.sign_partial(&tx, &outsider), Err(MultisigError::NotACosigner(outsider.address)));
+
+        // A spend whose policy does not hash to the sender is refused, even
+        // if its signatures are valid for that other policy.
+        let other = MultisigWallet::new(MultisigPolicy::new(1, vec![outsider.address]).unwrap());
+        let mut forged = other.combine(&proposal(&other), &[other.sign_partial(&proposal(&other), &outsider).unwrap()]).unwrap();
+        forged.from = account.address();
+        assert_eq!(verify_spend(&forged), Err(MultisigError::AddressMismatch));
+
+        let partial = account.sign_partial(&tx, &a).unwrap();
+        let mut next = tx.clone();
+        next.nonce = 1;
+        assert_eq!(account.combine(&next, &[partial]), Err(MultisigError::WrongTransaction));
+        assert!(MultisigSpend::decode(b"AXMS\x01").is_err());
+    }
+}
@@ -4,9 +4,10 @@ use libp2p::kad::{self, store::MemoryStore, Event as KademliaEvent};
 use libp2p::mdns;
 use libp2p::swarm::NetworkBehaviour;
 use libp2p::identity::Keypair;
-use libp2p::PeerId;
+use libp2p::{PeerId, Swarm};
 
 use crate::network::config::NetworkConfig;
+use crate::network::handshake::{identify_protocol_version, noise_config};
 
 /// Compute a 512-bit BLAKE3 node identity from a PeerId.
 ///
@@ -96,7 +97,7 @@ impl AxiomHybridBehaviour {
         let mut kademlia = kad::Behaviour::new(peer_id, kad_store);
         kademlia.set_mode(Some(kad::Mode::Server));
 
-        let identify = Identify::new(IdentifyConfig::new("/axiom/1.0.0".to_string(), keypair.public()));
+        let identify = Identify::new(IdentifyConfig::new(identify_protocol_version(), keypair.public()));
 
         let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), peer_id)?;
 
@@ -114,7 +115,7 @@ impl AxiomHybridBehaviour {
         let mut kademlia = kad::Behaviour::new(peer_id, kad_store);
         kademlia.set_mode(Some(kad::Mode::Server));
 
-        let identify = Identify::new(IdentifyConfig::new("/axiom/1.0.0".to_string(), keypair.public()));
+        let identify = Identify::new(IdentifyConfig::new(identify_protocol_version(), keypair.public()));
 
         // Note: mDNS cannot be fully disabled via config; when `enable_mdns`
         // is false we disable IPv6 to reduce chatter but IPv4 mDNS still runs.
@@ -147,6 +148,39 @@ impl AxiomHybridBehaviour {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Swarm Construction
+// ---------------------------------------------------------------------------
+
+/// Build a swarm running the hybrid behaviour over TCP (chain-bound Noise +
+/// Yamux) and QUIC, and start listening on `config.listen_multiaddrs()`.
+///
+/// The QUIC transport is always installed so `/quic-v1` peers can be
+/// dialed; `config.enable_quic` only controls whether we listen on it.
+pub fn build_swarm(
+    keypair: Keypair,
+    config: &NetworkConfig,
+) -> Result<Swarm<AxiomHybridBehaviour>, Box<dyn std::error::Error + Send + Sync>> {
+    let mut swarm = libp2p::SwarmBuilder::with_existing_identity(keypair)
+        .with_tokio()
+        .with_tcp(
+            libp2p::tcp::Config::default(),
+            noise_config,
+            libp2p::yamux::Config::default,
+        )?
+        .with_quic()
+        .with_behaviour(|key| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(AxiomHybridBehaviour::new_with_config(key, config).map_err(|e| e.to_string())?)
+        })?
+        .with_swarm_config(|cfg| cfg.with_idle_connection_timeout(config.connection_timeout))
+        .build();
+
+    for addr in config.listen_multiaddrs() {
+        swarm.listen_on(addr)?;
+    }
+    Ok(swarm)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -193,6 +227,13 @@ mod tests {
         assert!(behaviour.is_ok(), "Hybrid behaviour with config must construct without error");
     }
 
+    #[tokio::test]
+    async fn test_build_swarm_listens_on_tcp_and_quic() {
+        let config = NetworkConfig { tcp_port: 0, ..NetworkConfig::default() };
+        let swarm = build_swarm(Keypair::generate_ed25519(), &config);
+        assert!(swarm.is_ok(), "Swarm with TCP + QUIC must build and listen");
+    }
+
     #[tokio::test]
     async fn test_topic_subscription() {
         let keypair = Keypair::generate_ed25519();
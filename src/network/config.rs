@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::net::IpAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 use std::net::SocketAddr;
+use super::diversity::DiversityConfig;
+use super::tx_relay::TX_ANNOUNCE_TOPIC;
 
 /// Peer discovery strategies for the AXIOM network.
 ///
@@ -21,10 +24,11 @@ pub enum DiscoveryStrategy {
         protocol: String,
     },
 
-    /// DNS-based seed discovery via SRV records.
-    /// Resolves `_axiom-seed._tcp.<domain>` to obtain bootstrap addresses.
+    /// DNS-based seed discovery via TXT records (multiaddresses or a
+    /// signed EIP-1459 ENR tree).
     DnsDiscovery {
-        /// The domain to query, e.g. `"axiom-protocol.org"`.
+        /// The domain to query, e.g. `"axiom-protocol.org"`, or an
+        /// `enrtree://<base32 pubkey>@<domain>` link to a signed node list.
         domain: String,
     },
 }
@@ -47,9 +51,9 @@ impl DiscoveryStrategy {
     /// * `StaticList` returns the addresses as-is.
     /// * `KademliaDHT` returns an empty list (peers are discovered via
     ///   the DHT protocol itself once connected to any single node).
-    /// * `DnsDiscovery` performs a DNS SRV lookup for
-    ///   `_axiom-seed._tcp.<domain>` and converts the results to
-    ///   multiaddresses.
+    /// * `DnsDiscovery` walks a signed EIP-1459 tree when the domain is an
+    ///   `enrtree://` link, otherwise reads multiaddress TXT records at
+    ///   `_axiom-seed.<domain>` (see `network::dns_seeds`).
     pub fn resolve(&self) -> Vec<String> {
         match self {
             DiscoveryStrategy::StaticList(addrs) => addrs.clone(),
@@ -59,37 +63,12 @@ impl DiscoveryStrategy {
                 vec![]
             }
             DiscoveryStrategy::DnsDiscovery { domain } => {
-                resolve_dns_seeds(domain)
+                crate::network::dns_seeds::resolve_seeds(domain)
             }
         }
     }
 }
 
-/// Attempt to resolve `_axiom-seed._tcp.<domain>` via the system DNS
-/// resolver and convert the results into libp2p multiaddresses.
-///
-/// Falls back to an empty list if resolution fails (non-fatal: the node
-/// can still bootstrap via static seeds or mDNS).
-fn resolve_dns_seeds(domain: &str) -> Vec<String> {
-    let srv_name = format!("_axiom-seed._tcp.{}", domain);
-
-    // Use the standard library DNS resolution (blocking) for SRV-like
-    // lookup. `std::net::ToSocketAddrs` resolves A/AAAA records; true
-    // SRV record support requires a dedicated DNS library which we
-    // avoid for minimal dependency. Instead we resolve the SRV hostname
-    // directly and use the default AXIOM TCP port.
-    match std::net::ToSocketAddrs::to_socket_addrs(&(srv_name.as_str(), 7000)) {
-        Ok(addrs) => addrs
-            .map(|sa| format!("/ip4/{}/tcp/{}", sa.ip(), sa.port()))
-            .collect(),
-        Err(_) => {
-            // DNS resolution failed — not fatal, other strategies can
-            // still provide bootstrap peers.
-            vec![]
-        }
-    }
-}
-
 /// Production network configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfig {
@@ -150,9 +129,137 @@ pub struct NetworkConfig {
     /// DNS Discovery). Strategies are tried in order; addresses from all
     /// successful strategies are merged.
     pub discovery_strategies: Vec<DiscoveryStrategy>,
+
+    /// NAT traversal (AutoNAT, UPnP, hole punching, circuit relay).
+    #[serde(default)]
+    pub nat: NatConfig,
+
+    /// Listen for QUIC connections alongside TCP. QUIC uses the UDP port
+    /// with the same number as `tcp_port` and saves the separate Noise and
+    /// Yamux round trips on connection setup.
+    #[serde(default = "default_enable_quic")]
+    pub enable_quic: bool,
+
+    /// Per-peer inbound quotas, enforced by `peer_manager::PeerRateLimiter`.
+    #[serde(default)]
+    pub rate_limits: RateLimitConfig,
+
+    /// Outbound subnet/ASN limits and anchor peers, enforced by
+    /// `diversity::PeerDiversity`.
+    #[serde(default)]
+    pub diversity: DiversityConfig,
+}
+
+fn default_enable_quic() -> bool {
+    true
+}
+
+/// Quota key for chain-sync requests, which have no gossip topic.
+pub const SYNC_RATE_TOPIC: &str = "chain-sync";
+
+/// Quota key for transaction body requests (`ChainRequest::GetTransactions`).
+pub const TX_PULL_RATE_TOPIC: &str = "tx-pull";
+
+/// Inbound allowance for one peer on one topic. Both limits refill
+/// continuously; a peer may burst up to a full minute's allowance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicQuota {
+    pub messages_per_minute: u32,
+    pub bytes_per_minute: u64,
+}
+
+/// Per-topic quotas; topics not listed use `default_quota`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub default_quota: TopicQuota,
+    pub topics: HashMap<String, TopicQuota>,
 }
 
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        const MIB: u64 = 1024 * 1024;
+        let topics = HashMap::from([
+            // Blocks are large but rare (one per 30 minutes plus relays).
+            ("timechain-blocks".to_string(), TopicQuota { messages_per_minute: 30, bytes_per_minute: 64 * MIB }),
+            ("timechain-transactions".to_string(), TopicQuota { messages_per_minute: 120, bytes_per_minute: 4 * MIB }),
+            (SYNC_RATE_TOPIC.to_string(), TopicQuota { messages_per_minute: 30, bytes_per_minute: MIB }),
+            // Announcements are just IDs; pulls are small batches of bodies.
+            (TX_ANNOUNCE_TOPIC.to_string(), TopicQuota { messages_per_minute: 240, bytes_per_minute: 4 * MIB }),
+            (TX_PULL_RATE_TOPIC.to_string(), TopicQuota { messages_per_minute: 240, bytes_per_minute: MIB }),
+        ]);
+        Self {
+            default_quota: TopicQuota { messages_per_minute: 100, bytes_per_minute: 4 * MIB },
+            topics,
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub fn quota_for(&self, topic: &str) -> TopicQuota {
+        self.topics.get(topic).copied().unwrap_or(self.default_quota)
+    }
+}
+
+/// NAT traversal settings.
+///
+/// Nodes behind home routers cannot accept inbound TCP connections, so
+/// on their own they only ever see the peers they dial out to. AutoNAT
+/// tells the node whether it is publicly reachable; when it is not, the
+/// node asks the gateway for a port mapping (UPnP IGD), reserves a slot on
+/// one of the configured circuit relays, and upgrades relayed connections
+/// to direct ones via DCUtR hole punching where the NATs allow it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatConfig {
+    /// Probe our own reachability with AutoNAT.
+    pub enable_autonat: bool,
+
+    /// Request a TCP port mapping from the local gateway via UPnP IGD.
+    pub enable_upnp: bool,
+
+    /// Attempt DCUtR hole punching over relayed connections.
+    pub enable_hole_punching: bool,
+
+    /// Serve as a circuit relay (v2) for other peers. Only useful on
+    /// publicly reachable nodes.
+    pub enable_relay_server: bool,
+
+    /// Relay multiaddresses (must end in `/p2p/<peer-id>`) to reserve a
+    /// circuit on once AutoNAT reports that we are behind a NAT.
+    pub relay_addrs: Vec<String>,
+}
+
+impl Default for NatConfig {
+    fn default() -> Self {
+        Self {
+            enable_autonat: true,
+            enable_upnp: true,
+            enable_hole_punching: true,
+            enable_relay_server: false,
+            relay_addrs: vec![],
+        }
+    }
+}
+
+impl NatConfig {
+    /// Parse `relay_addrs` into multiaddresses, rejecting any that do not
+    /// name the relay's peer ID (a reservation cannot be made without it).
+    pub fn relay_multiaddrs(&self) -> Result<Vec<libp2p::Multiaddr>, ConfigError> {
+        self.relay_addrs
+            .iter()
+            .map(|s| {
+                let addr: libp2p::Multiaddr = s
+                    .parse()
+                    .map_err(|_| ConfigError::InvalidRelayAddr(s.clone()))?;
+                match addr.iter().last() {
+                    Some(libp2p::multiaddr::Protocol::P2p(_)) => Ok(addr),
+                    _ => Err(ConfigError::InvalidRelayAddr(s.clone())),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GossipConfig {
     /// Heartbeat interval
     pub heartbeat_interval: Duration,
@@ -194,6 +301,10 @@ impl Default for NetworkConfig {
             history_length: 6,
             history_gossip: 3,
             enable_mdns: true,
+            nat: NatConfig::default(),
+            enable_quic: default_enable_quic(),
+            rate_limits: RateLimitConfig::default(),
+            diversity: DiversityConfig::default(),
             discovery_strategies: vec![
                 DiscoveryStrategy::default(), // Static list of 4 diverse IPs
                 DiscoveryStrategy::KademliaDHT {
@@ -220,6 +331,23 @@ impl Default for GossipConfig {
     }
 }
 
+impl GossipConfig {
+    /// Gossipsub behaviour config with these mesh parameters; everything
+    /// else keeps the libp2p defaults. The outbound quota is derived from
+    /// `mesh_n` so that small meshes still pass libp2p's validation.
+    pub fn to_gossipsub(&self) -> Result<libp2p::gossipsub::Config, libp2p::gossipsub::ConfigBuilderError> {
+        libp2p::gossipsub::ConfigBuilder::default()
+            .heartbeat_interval(self.heartbeat_interval)
+            .mesh_n(self.mesh_n)
+            .mesh_n_low(self.mesh_n_low)
+            .mesh_n_high(self.mesh_n_high)
+            .mesh_outbound_min((self.mesh_n / 2).min(self.mesh_n_low).min(2))
+            .history_length(self.history_length)
+            .history_gossip(self.history_gossip)
+            .build()
+    }
+}
+
 impl NetworkConfig {
     /// Create mainnet configuration
     pub fn mainnet() -> Self {
@@ -275,6 +403,8 @@ impl NetworkConfig {
                 "target_peers must be >= min_peers_for_sync".into()
             ));
         }
+
+        self.nat.relay_multiaddrs()?;
         
         Ok(())
     }
@@ -287,6 +417,15 @@ impl NetworkConfig {
         format!("0.0.0.0:{}", self.udp_port).parse().unwrap()
     }
 
+    /// libp2p listen addresses: TCP always, plus QUIC when enabled.
+    pub fn listen_multiaddrs(&self) -> Vec<libp2p::Multiaddr> {
+        let mut addrs = vec![tcp_multiaddr(self.tcp_port)];
+        if self.enable_quic {
+            addrs.push(quic_multiaddr(self.tcp_port));
+        }
+        addrs
+    }
+
     /// Resolve all configured discovery strategies into a flat list of
     /// bootstrap multiaddresses suitable for `Swarm::dial`.
     pub fn resolve_all_bootstrap_addrs(&self) -> Vec<String> {
@@ -300,6 +439,21 @@ impl NetworkConfig {
     }
 }
 
+/// `/ip4/0.0.0.0/tcp/<port>`
+pub fn tcp_multiaddr(port: u16) -> libp2p::Multiaddr {
+    libp2p::Multiaddr::empty()
+        .with(libp2p::multiaddr::Protocol::Ip4(std::net::Ipv4Addr::UNSPECIFIED))
+        .with(libp2p::multiaddr::Protocol::Tcp(port))
+}
+
+/// `/ip4/0.0.0.0/udp/<port>/quic-v1`
+pub fn quic_multiaddr(port: u16) -> libp2p::Multiaddr {
+    libp2p::Multiaddr::empty()
+        .with(libp2p::multiaddr::Protocol::Ip4(std::net::Ipv4Addr::UNSPECIFIED))
+        .with(libp2p::multiaddr::Protocol::Udp(port))
+        .with(libp2p::multiaddr::Protocol::QuicV1)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ConfigError {
     #[error("Invalid port configuration: {0}")]
@@ -308,6 +462,9 @@ pub enum ConfigError {
     #[error("Invalid peer configuration: {0}")]
     InvalidPeerConfig(String),
     
+    #[error("Invalid relay address (expected /.../p2p/<peer-id>): {0}")]
+    InvalidRelayAddr(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -371,6 +528,29 @@ mod tests {
         assert_eq!(addrs.len(), 3, "Duplicates should be removed");
     }
 
+    #[test]
+    fn test_listen_multiaddrs_quic_toggle() {
+        let mut config = NetworkConfig::default();
+        let addrs = config.listen_multiaddrs();
+        assert_eq!(addrs.len(), 2);
+        assert_eq!(addrs[1].to_string(), format!("/ip4/0.0.0.0/udp/{}/quic-v1", config.tcp_port));
+
+        config.enable_quic = false;
+        assert_eq!(config.listen_multiaddrs(), vec![tcp_multiaddr(config.tcp_port)]);
+    }
+
+    #[test]
+    fn test_relay_addrs_require_peer_id() {
+        let mut config = NetworkConfig::default();
+        config.nat.relay_addrs = vec![
+            "/ip4/1.2.3.4/tcp/7000/p2p/12D3KooWDpJ7As7BWAwRMfu1VU2WCqNjvq387JEYKDBj4kx6nXTN".to_string(),
+        ];
+        assert!(config.validate().is_ok());
+
+        config.nat.relay_addrs.push("/ip4/1.2.3.4/tcp/7000".to_string());
+        assert!(matches!(config.validate(), Err(ConfigError::InvalidRelayAddr(_))));
+    }
+
     #[test]
     fn test_default_static_list_has_at_least_3() {
         let strategy = DiscoveryStrategy::default();
@@ -1,23 +1,40 @@
 use discv5::{Discv5, enr::{CombinedKey, Enr, NodeId}, ConfigBuilder as Discv5ConfigBuilder, ListenConfig};
-use std::net::SocketAddr;
+use libp2p::PeerId;
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::time::Duration;
 
+/// Distinct peers that must report the same external IP via identify
+/// before it is published in our ENR.
+pub const EXTERNAL_IP_VOTES: usize = 3;
+
+#[derive(Clone)]
 pub struct Discv5Service {
     discv5: Arc<RwLock<Discv5>>,
-    local_enr: Enr<CombinedKey>,
+    tcp_port: u16,
 }
 
 impl Discv5Service {
+    /// Start discovery on `listen_addr` (UDP). The local ENR advertises
+    /// `tcp_port`, our libp2p listener, so peers that find us can dial
+    /// in. An unspecified listen IP is left out of the record until the
+    /// external address is learned (`update_external_ip`) or discv5's own
+    /// PONG votes fill it in.
     pub async fn new(
         listen_addr: SocketAddr,
         enr_key: CombinedKey,
         boot_nodes: Vec<Enr<CombinedKey>>,
+        tcp_port: u16,
+        external_ip: Option<IpAddr>,
     ) -> Result<Self, String> {
         let mut builder = Enr::builder();
-        builder.ip(listen_addr.ip());
+        if let Some(ip) = external_ip.or(Some(listen_addr.ip()).filter(|ip| !ip.is_unspecified())) {
+            builder.ip(ip);
+        }
         builder.udp4(listen_addr.port());
+        builder.tcp4(tcp_port);
         let local_enr = builder.build(&enr_key).map_err(|e| e.to_string())?;
 
         let listen_config = ListenConfig::from_ip(listen_addr.ip(), listen_addr.port());
@@ -42,7 +59,7 @@ impl Discv5Service {
         
         Ok(Self {
             discv5: Arc::new(RwLock::new(discv5)),
-            local_enr,
+            tcp_port,
         })
     }
     
@@ -57,8 +74,25 @@ impl Discv5Service {
         }
     }
     
-    pub fn local_enr(&self) -> Enr<CombinedKey> {
-        self.local_enr.clone()
+    /// Our current signed node record, including any updates since startup.
+    pub async fn local_enr(&self) -> Enr<CombinedKey> {
+        self.discv5.read().await.local_enr()
+    }
+
+    /// `enr:` text form of our record, for bootstrap lists.
+    pub async fn export_enr(&self) -> String {
+        self.local_enr().await.to_base64()
+    }
+
+    /// Publish `ip` with our libp2p TCP port. Returns `true` if the
+    /// record changed (and its sequence number was bumped).
+    pub async fn update_external_ip(&self, ip: IpAddr) -> bool {
+        let discv5 = self.discv5.read().await;
+        let changed = discv5.update_local_enr_socket(SocketAddr::new(ip, self.tcp_port), true);
+        if changed {
+            log::info!("Discv5 ENR now advertises {}:{} (seq {})", ip, self.tcp_port, discv5.local_enr().seq());
+        }
+        changed
     }
     
     pub async fn connected_peers(&self) -> usize {
@@ -81,4 +115,57 @@ pub fn default_bootstrap_enrs() -> Vec<Enr<CombinedKey>> {
     .into_iter()
     .flatten()
     .collect()
-}
\ No newline at end of file
+}
+
+/// Tallies the external IP that peers observe for us (identify's
+/// `observed_addr`). A single peer cannot move our ENR; an address is
+/// adopted once `EXTERNAL_IP_VOTES` distinct peers agree.
+#[derive(Debug, Default)]
+pub struct ExternalIpVotes {
+    votes: HashMap<IpAddr, HashSet<PeerId>>,
+    adopted: Option<IpAddr>,
+}
+
+impl ExternalIpVotes {
+    /// Record `peer`'s observation; returns the IP when it newly reaches
+    /// the vote threshold.
+    pub fn record(&mut self, peer: PeerId, ip: IpAddr) -> Option<IpAddr> {
+        if ip.is_loopback() || ip.is_unspecified() || self.adopted == Some(ip) {
+            return None;
+        }
+        let voters = self.votes.entry(ip).or_default();
+        voters.insert(peer);
+        if voters.len() >= EXTERNAL_IP_VOTES {
+            self.adopted = Some(ip);
+            self.votes.clear();
+            return Some(ip);
+        }
+        None
+    }
+
+    /// Adopt an address confirmed by other means (AutoNAT).
+    pub fn confirm(&mut self, ip: IpAddr) -> bool {
+        let changed = self.adopted != Some(ip);
+        self.adopted = Some(ip);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_ip_needs_distinct_voters() {
+        let mut votes = ExternalIpVotes::default();
+        let ip: IpAddr = "203.0.113.9".parse().unwrap();
+        let peer = PeerId::random();
+        assert_eq!(votes.record(peer, ip), None);
+        assert_eq!(votes.record(peer, ip), None);
+        assert_eq!(votes.record(PeerId::random(), ip), None);
+        assert_eq!(votes.record(PeerId::random(), ip), Some(ip));
+        // Already adopted: further votes are ignored.
+        assert_eq!(votes.record(PeerId::random(), ip), None);
+        assert!(!votes.confirm(ip));
+    }
+}
@@ -0,0 +1,340 @@
+// src/network/diversity.rs - Outbound peer diversity (eclipse resistance)
+//
+// An eclipse attacker needs to own every one of our outbound connections.
+// Cheap addresses tend to come in blocks (one /16, one hosting provider),
+// so we cap how many outbound peers may share a /16 subnet, an autonomous
+// system or (with GeoIP data, see `geoip`) a country. The oldest few
+// outbound connections are kept as anchors: they are never rotated or
+// evicted, so an attacker who floods us with fresh addresses still cannot
+// displace peers we already trust.
+// When the NeuralGuardian recommends `Action::DiversifyPeers`, the
+// non-anchor peers from the most crowded subnets are dropped and replaced
+// through the normal discovery paths.
+
+use libp2p::multiaddr::Protocol;
+use libp2p::{Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two guardian-triggered rotations.
+pub const ROTATION_COOLDOWN: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiversityConfig {
+    /// Outbound peers allowed in one IPv4 /16 (or IPv6 /32).
+    pub max_per_subnet: usize,
+    /// Outbound peers allowed in one ASN, when an ASN lookup is available.
+    pub max_per_asn: usize,
+    /// Outbound peers allowed in one country, when a country lookup is available.
+    pub max_per_country: usize,
+    /// Oldest outbound connections protected from rotation.
+    pub anchor_count: usize,
+    /// Peers dropped per guardian-triggered rotation.
+    pub rotate_count: usize,
+}
+
+impl Default for DiversityConfig {
+    fn default() -> Self {
+        Self {
+            max_per_subnet: 2,
+            max_per_asn: 4,
+            max_per_country: 6,
+            anchor_count: 2,
+            rotate_count: 2,
+        }
+    }
+}
+
+/// Maps an IP to its autonomous system number and, optionally, its
+/// country (e.g. from a GeoIP database).
+pub trait AsnLookup: Send + Sync {
+    fn asn(&self, ip: IpAddr) -> Option<u32>;
+
+    /// ISO 3166 country code of `ip`.
+    fn country(&self, _ip: IpAddr) -> Option<String> {
+        None
+    }
+}
+
+impl<T: AsnLookup + ?Sized> AsnLookup for Arc<T> {
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        (**self).asn(ip)
+    }
+
+    fn country(&self, ip: IpAddr) -> Option<String> {
+        (**self).country(ip)
+    }
+}
+
+/// Network group used for the subnet limit: the /16 for IPv4, the /32 for IPv6.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subnet {
+    V4([u8; 2]),
+    V6([u8; 4]),
+}
+
+impl Subnet {
+    pub fn of(ip: IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(v4) => {
+                let o = v4.octets();
+                Subnet::V4([o[0], o[1]])
+            }
+            IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+                Some(v4) => Subnet::of(IpAddr::V4(v4)),
+                None => {
+                    let o = v6.octets();
+                    Subnet::V6([o[0], o[1], o[2], o[3]])
+                }
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Subnet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subnet::V4([a, b]) => write!(f, "{}.{}.0.0/16", a, b),
+            Subnet::V6([a, b, c, d]) => write!(f, "{:02x}{:02x}:{:02x}{:02x}::/32", a, b, c, d),
+        }
+    }
+}
+
+/// First IP component of a multiaddress, if it has one.
+pub fn ip_of(addr: &Multiaddr) -> Option<IpAddr> {
+    addr.iter().find_map(|p| match p {
+        Protocol::Ip4(ip) => Some(IpAddr::V4(ip)),
+        Protocol::Ip6(ip) => Some(IpAddr::V6(ip)),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiversityViolation {
+    SubnetFull(Subnet),
+    AsnFull(u32),
+    CountryFull(String),
+}
+
+impl std::fmt::Display for DiversityViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiversityViolation::SubnetFull(subnet) => write!(f, "subnet {:?} already at its outbound limit", subnet),
+            DiversityViolation::AsnFull(asn) => write!(f, "AS{} already at its outbound limit", asn),
+            DiversityViolation::CountryFull(country) => write!(f, "country {} already at its outbound limit", country),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct OutboundPeer {
+    subnet: Subnet,
+    asn: Option<u32>,
+    country: Option<String>,
+    connected_at: Instant,
+}
+
+pub struct PeerDiversity {
+    config: DiversityConfig,
+    asn_lookup: Option<Box<dyn AsnLookup>>,
+    outbound: HashMap<PeerId, OutboundPeer>,
+    last_rotation: Option<Instant>,
+}
+
+impl PeerDiversity {
+    pub fn new(config: DiversityConfig) -> Self {
+        Self { config, asn_lookup: None, outbound: HashMap::new(), last_rotation: None }
+    }
+
+    pub fn with_asn_lookup(mut self, lookup: Box<dyn AsnLookup>) -> Self {
+        self.asn_lookup = Some(lookup);
+        self
+    }
+
+    pub fn outbound_count(&self) -> usize {
+        self.outbound.len()
+    }
+
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        self.asn_lookup.as_ref().and_then(|l| l.asn(ip))
+    }
+
+    fn country(&self, ip: IpAddr) -> Option<String> {
+        self.asn_lookup.as_ref().and_then(|l| l.country(ip))
+    }
+
+    /// Whether a new outbound connection to `ip` would stay within limits.
+    pub fn check(&self, ip: IpAddr) -> Result<(), DiversityViolation> {
+        // Local and private addresses (mDNS, devnets) are not a diversity
+        // concern and would otherwise all share one subnet.
+        if !is_routable(ip) {
+            return Ok(());
+        }
+        let subnet = Subnet::of(ip);
+        if self.outbound.values().filter(|p| p.subnet == subnet).count() >= self.config.max_per_subnet {
+            return Err(DiversityViolation::SubnetFull(subnet));
+        }
+        if let Some(asn) = self.asn(ip) {
+            if self.outbound.values().filter(|p| p.asn == Some(asn)).count() >= self.config.max_per_asn {
+                return Err(DiversityViolation::AsnFull(asn));
+            }
+        }
+        if let Some(country) = self.country(ip) {
+            if self.outbound.values().filter(|p| p.country.as_ref() == Some(&country)).count() >= self.config.max_per_country {
+                return Err(DiversityViolation::CountryFull(country));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `addr` is worth dialing. Addresses without an IP (DNS,
+    /// relayed circuits) are always allowed and checked once connected.
+    pub fn allows_dial(&self, addr: &Multiaddr) -> bool {
+        ip_of(addr).is_none_or(|ip| self.check(ip).is_ok())
+    }
+
+    /// Register an outbound connection. On a violation the peer is not
+    /// recorded and the caller should disconnect it.
+    pub fn outbound_connected(&mut self, peer: PeerId, addr: &Multiaddr, now: Instant) -> Result<(), DiversityViolation> {
+        if self.outbound.contains_key(&peer) {
+            return Ok(());
+        }
+        let Some(ip) = ip_of(addr).filter(|ip| is_routable(*ip)) else {
+            return Ok(());
+        };
+        self.check(ip)?;
+        let outbound = OutboundPeer { subnet: Subnet::of(ip), asn: self.asn(ip), country: self.country(ip), connected_at: now };
+        self.outbound.insert(peer, outbound);
+        Ok(())
+    }
+
+    pub fn disconnected(&mut self, peer: &PeerId) {
+        self.outbound.remove(peer);
+    }
+
+    /// The `anchor_count` longest-held outbound peers.
+    pub fn anchors(&self) -> Vec<PeerId> {
+        let mut by_age: Vec<(&PeerId, &OutboundPeer)> = self.outbound.iter().collect();
+        by_age.sort_by_key(|(_, p)| p.connected_at);
+        by_age.into_iter().take(self.config.anchor_count).map(|(id, _)| *id).collect()
+    }
+
+    pub fn is_anchor(&self, peer: &PeerId) -> bool {
+        self.anchors().contains(peer)
+    }
+
+    /// Pick up to `rotate_count` non-anchor outbound peers to drop, most
+    /// crowded subnet first and newest connection first within a subnet.
+    /// Returns nothing while the rotation cooldown is running.
+    pub fn rotate(&mut self, now: Instant) -> Vec<PeerId> {
+        if self.last_rotation.is_some_and(|t| now.duration_since(t) < ROTATION_COOLDOWN) {
+            return Vec::new();
+        }
+        let anchors = self.anchors();
+        let mut crowding: HashMap<Subnet, usize> = HashMap::new();
+        for p in self.outbound.values() {
+            *crowding.entry(p.subnet).or_default() += 1;
+        }
+        let mut candidates: Vec<(&PeerId, &OutboundPeer)> = self
+            .outbound
+            .iter()
+            .filter(|(id, _)| !anchors.contains(id))
+            .collect();
+        candidates.sort_by_key(|(_, p)| (std::cmp::Reverse(crowding[&p.subnet]), std::cmp::Reverse(p.connected_at)));
+        let victims: Vec<PeerId> = candidates
+            .into_iter()
+            .take(self.config.rotate_count)
+            .map(|(id, _)| *id)
+            .collect();
+        if !victims.is_empty() {
+            self.last_rotation = Some(now);
+        }
+        victims
+    }
+}
+
+pub(crate) fn is_routable(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => !(v4.is_private() || v4.is_loopback() || v4.is_link_local() || v4.is_unspecified()),
+        IpAddr::V6(v6) => !(v6.is_loopback() || v6.is_unspecified() || (v6.segments()[0] & 0xfe00) == 0xfc00),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(ip: &str) -> Multiaddr {
+        format!("/ip4/{}/tcp/7000", ip).parse().unwrap()
+    }
+
+    struct FixedAsn;
+    impl AsnLookup for FixedAsn {
+        fn asn(&self, ip: IpAddr) -> Option<u32> {
+            match ip {
+                IpAddr::V4(v4) if v4.octets()[0] == 34 => Some(15169),
+                _ => None,
+            }
+        }
+
+        fn country(&self, ip: IpAddr) -> Option<String> {
+            match ip {
+                IpAddr::V4(v4) if matches!(v4.octets()[0], 3 | 34) => Some("US".into()),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_subnet_asn_and_country_limits() {
+        let mut d = PeerDiversity::new(DiversityConfig::default()).with_asn_lookup(Box::new(FixedAsn));
+        let now = Instant::now();
+        d.outbound_connected(PeerId::random(), &addr("51.15.1.1"), now).unwrap();
+        d.outbound_connected(PeerId::random(), &addr("51.15.2.2"), now).unwrap();
+        assert!(matches!(
+            d.outbound_connected(PeerId::random(), &addr("51.15.3.3"), now),
+            Err(DiversityViolation::SubnetFull(_))
+        ));
+        assert!(!d.allows_dial(&addr("51.15.9.9")));
+        assert!(d.allows_dial(&addr("192.168.1.5")));
+
+        for i in 0..4 {
+            d.outbound_connected(PeerId::random(), &addr(&format!("34.{}.0.1", i)), now).unwrap();
+        }
+        assert_eq!(
+            d.outbound_connected(PeerId::random(), &addr("34.200.0.1"), now),
+            Err(DiversityViolation::AsnFull(15169))
+        );
+
+        // Different subnets and no ASN, but the country is full.
+        d.outbound_connected(PeerId::random(), &addr("3.1.0.1"), now).unwrap();
+        d.outbound_connected(PeerId::random(), &addr("3.2.0.1"), now).unwrap();
+        assert_eq!(
+            d.outbound_connected(PeerId::random(), &addr("3.3.0.1"), now),
+            Err(DiversityViolation::CountryFull("US".into()))
+        );
+        assert_eq!(d.outbound_count(), 8);
+    }
+
+    #[test]
+    fn test_rotation_spares_anchors_and_respects_cooldown() {
+        let mut d = PeerDiversity::new(DiversityConfig::default());
+        let start = Instant::now();
+        let peers: Vec<PeerId> = (0..5).map(|_| PeerId::random()).collect();
+        let ips = ["3.8.0.1", "51.15.0.1", "51.15.0.2", "3.8.0.2", "34.10.0.1"];
+        for (i, (peer, ip)) in peers.iter().zip(ips).enumerate() {
+            d.outbound_connected(*peer, &addr(ip), start + Duration::from_secs(i as u64)).unwrap();
+        }
+        assert_eq!(d.anchors(), vec![peers[0], peers[1]]);
+
+        let dropped = d.rotate(start + Duration::from_secs(10));
+        // Crowded subnets go first, newest first; anchors are never chosen.
+        assert_eq!(dropped, vec![peers[3], peers[2]]);
+        assert!(d.rotate(start + Duration::from_secs(20)).is_empty());
+        assert!(!d.rotate(start + ROTATION_COOLDOWN + Duration::from_secs(10)).is_empty());
+    }
+}
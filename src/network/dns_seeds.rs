@@ -0,0 +1,428 @@
+// src/network/dns_seeds.rs - DNS seed discovery
+//
+// Two seed formats are supported for `DiscoveryStrategy::DnsDiscovery`:
+//
+// * `enrtree://<base32 pubkey>@<domain>` - an EIP-1459 node list. The
+//   tree root TXT record is signed by the seed operator's secp256k1 key;
+//   every branch and leaf is content-addressed by its keccak256 hash, so
+//   a verified root pins the whole tree.
+// * a plain domain - TXT records at `_axiom-seed.<domain>`, each holding
+//   one multiaddress. Unsigned; it is only as trustworthy as the DNS zone.
+//   If there are no such records we fall back to resolving the A records
+//   of `_axiom-seed._tcp.<domain>` on the default port.
+//
+// Results are cached per domain for `DNS_SEED_CACHE_TTL` so repeated
+// bootstrap retries do not hammer the resolver.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use data_encoding::{BASE32_NOPAD, BASE64URL_NOPAD};
+use discv5::enr::{k256, CombinedKey, Enr};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{Signature, VerifyingKey};
+use once_cell::sync::Lazy;
+use sha3::{Digest, Keccak256};
+
+/// How long a successful resolution is reused.
+pub const DNS_SEED_CACHE_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Upper bound on TXT lookups while walking one ENR tree.
+pub const MAX_TREE_LOOKUPS: usize = 512;
+
+/// Port assumed for A-record seeds.
+const DEFAULT_SEED_PORT: u16 = 7000;
+
+const ROOT_PREFIX: &str = "enrtree-root:v1";
+const BRANCH_PREFIX: &str = "enrtree-branch:";
+const LINK_PREFIX: &str = "enrtree://";
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DnsSeedError {
+    #[error("DNS lookup for {0} failed: {1}")]
+    Lookup(String, String),
+
+    #[error("Invalid enrtree link: {0}")]
+    InvalidLink(String),
+
+    #[error("Invalid tree root: {0}")]
+    InvalidRoot(String),
+
+    #[error("Tree root signature does not match the enrtree public key")]
+    BadRootSignature,
+
+    #[error("Record at {0} does not match its hash")]
+    HashMismatch(String),
+
+    #[error("Invalid tree entry at {0}: {1}")]
+    InvalidEntry(String, String),
+}
+
+/// Source of TXT records. Split out so tree walking can be tested without
+/// a network.
+pub trait TxtResolver {
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DnsSeedError>;
+}
+
+/// Resolver using the system DNS configuration.
+pub struct SystemTxtResolver;
+
+impl TxtResolver for SystemTxtResolver {
+    fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DnsSeedError> {
+        use hickory_resolver::config::{ResolverConfig, ResolverOpts};
+        use hickory_resolver::Resolver;
+
+        // The blocking resolver runs its own runtime, which cannot be
+        // started from inside the node's tokio runtime, so do the lookup
+        // on a plain thread.
+        let name = name.to_string();
+        std::thread::spawn(move || {
+            let (config, mut opts): (ResolverConfig, ResolverOpts) =
+                hickory_resolver::system_conf::read_system_conf().unwrap_or_default();
+            opts.timeout = Duration::from_secs(3);
+            opts.attempts = 1;
+            let resolver = Resolver::new(config, opts)
+                .map_err(|e| DnsSeedError::Lookup(name.clone(), e.to_string()))?;
+            let lookup = resolver
+                .txt_lookup(name.as_str())
+                .map_err(|e| DnsSeedError::Lookup(name.clone(), e.to_string()))?;
+            Ok(lookup
+                .iter()
+                .map(|txt| {
+                    // Long records are split into 255-byte character strings.
+                    txt.txt_data()
+                        .iter()
+                        .map(|part| String::from_utf8_lossy(part).into_owned())
+                        .collect::<String>()
+                })
+                .collect())
+        })
+        .join()
+        .unwrap_or_else(|_| Err(DnsSeedError::Lookup("resolver thread".into(), "panicked".into())))
+    }
+}
+
+/// Parsed `enrtree://<pubkey>@<domain>` link.
+#[derive(Debug, Clone)]
+pub struct EnrTreeLink {
+    pub public_key: VerifyingKey,
+    pub domain: String,
+}
+
+impl EnrTreeLink {
+    pub fn parse(link: &str) -> Result<Self, DnsSeedError> {
+        let rest = link
+            .strip_prefix(LINK_PREFIX)
+            .ok_or_else(|| DnsSeedError::InvalidLink(link.to_string()))?;
+        let (key, domain) = rest
+            .split_once('@')
+            .ok_or_else(|| DnsSeedError::InvalidLink(link.to_string()))?;
+        let key_bytes = BASE32_NOPAD
+            .decode(key.as_bytes())
+            .map_err(|e| DnsSeedError::InvalidLink(format!("{}: {}", link, e)))?;
+        let public_key = VerifyingKey::from_sec1_bytes(&key_bytes)
+            .map_err(|e| DnsSeedError::InvalidLink(format!("{}: {}", link, e)))?;
+        if domain.is_empty() {
+            return Err(DnsSeedError::InvalidLink(link.to_string()));
+        }
+        Ok(Self { public_key, domain: domain.to_string() })
+    }
+}
+
+/// Verified `enrtree-root:v1` record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeRoot {
+    pub enr_root: String,
+    pub link_root: String,
+    pub seq: u64,
+}
+
+impl TreeRoot {
+    /// Parse a root record and check its signature against `public_key`.
+    pub fn parse_and_verify(record: &str, public_key: &VerifyingKey) -> Result<Self, DnsSeedError> {
+        let invalid = || DnsSeedError::InvalidRoot(record.to_string());
+        let (signed, sig) = record.rsplit_once(" sig=").ok_or_else(invalid)?;
+
+        let mut fields = signed.split(' ');
+        if fields.next() != Some(ROOT_PREFIX) {
+            return Err(invalid());
+        }
+        let mut enr_root = None;
+        let mut link_root = None;
+        let mut seq = None;
+        for field in fields {
+            match field.split_once('=') {
+                Some(("e", v)) => enr_root = Some(v.to_string()),
+                Some(("l", v)) => link_root = Some(v.to_string()),
+                Some(("seq", v)) => seq = v.parse().ok(),
+                _ => return Err(invalid()),
+            }
+        }
+
+        // 65-byte r || s || v; the recovery id is not needed since we
+        // already know the key.
+        let sig_bytes = BASE64URL_NOPAD
+            .decode(sig.trim_end_matches('=').as_bytes())
+            .map_err(|_| invalid())?;
+        if sig_bytes.len() != 65 {
+            return Err(invalid());
+        }
+        let signature = Signature::from_slice(&sig_bytes[..64]).map_err(|_| invalid())?;
+        let digest = Keccak256::digest(signed.as_bytes());
+        public_key
+            .verify_prehash(&digest, &signature)
+            .map_err(|_| DnsSeedError::BadRootSignature)?;
+
+        Ok(Self {
+            enr_root: enr_root.ok_or_else(invalid)?,
+            link_root: link_root.ok_or_else(invalid)?,
+            seq: seq.ok_or_else(invalid)?,
+        })
+    }
+}
+
+/// Subdomain label under which a tree entry is published.
+pub fn entry_hash(record: &str) -> String {
+    let digest = Keccak256::digest(record.as_bytes());
+    BASE32_NOPAD.encode(&digest[..16])
+}
+
+/// Walk the ENR subtree of a signed tree and return its node records.
+///
+/// Links to other trees (`l=`) are not followed: each seed operator is
+/// configured explicitly, so we only trust trees whose key we were given.
+pub fn resolve_enr_tree(
+    resolver: &dyn TxtResolver,
+    link: &EnrTreeLink,
+) -> Result<Vec<Enr<CombinedKey>>, DnsSeedError> {
+    let root_record = first_record(resolver, &link.domain)?;
+    let root = TreeRoot::parse_and_verify(&root_record, &link.public_key)?;
+
+    let mut enrs = Vec::new();
+    let mut pending = VecDeque::from([root.enr_root]);
+    let mut lookups = 0;
+    while let Some(hash) = pending.pop_front() {
+        if lookups >= MAX_TREE_LOOKUPS {
+            log::warn!("DNS seeds: {} exceeds {} entries, truncating", link.domain, MAX_TREE_LOOKUPS);
+            break;
+        }
+        lookups += 1;
+
+        let name = format!("{}.{}", hash, link.domain);
+        let record = first_record(resolver, &name)?;
+        if entry_hash(&record) != hash {
+            return Err(DnsSeedError::HashMismatch(name));
+        }
+
+        if let Some(children) = record.strip_prefix(BRANCH_PREFIX) {
+            pending.extend(children.split(',').filter(|c| !c.is_empty()).map(str::to_string));
+        } else if record.starts_with("enr:") {
+            let enr = record
+                .parse::<Enr<CombinedKey>>()
+                .map_err(|e| DnsSeedError::InvalidEntry(name.clone(), e.to_string()))?;
+            enrs.push(enr);
+        } else {
+            return Err(DnsSeedError::InvalidEntry(name, "not a branch or ENR".into()));
+        }
+    }
+    Ok(enrs)
+}
+
+/// Multiaddresses published as TXT records at `_axiom-seed.<domain>`.
+pub fn resolve_txt_multiaddrs(resolver: &dyn TxtResolver, domain: &str) -> Result<Vec<String>, DnsSeedError> {
+    let records = resolver.lookup_txt(&format!("_axiom-seed.{}", domain))?;
+    Ok(records
+        .into_iter()
+        .map(|r| r.trim().to_string())
+        .filter(|r| r.parse::<libp2p::Multiaddr>().is_ok())
+        .collect())
+}
+
+/// Dialable TCP multiaddress for an ENR, if it advertises one.
+pub fn enr_to_multiaddr(enr: &Enr<CombinedKey>) -> Option<String> {
+    let ip = enr.ip4()?;
+    let port = enr.tcp4()?;
+    if ip.is_loopback() || ip.is_unspecified() {
+        return None;
+    }
+    Some(format!("/ip4/{}/tcp/{}", ip, port))
+}
+
+fn first_record(resolver: &dyn TxtResolver, name: &str) -> Result<String, DnsSeedError> {
+    resolver
+        .lookup_txt(name)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| DnsSeedError::Lookup(name.to_string(), "no TXT record".into()))
+}
+
+/// Resolved addresses per domain, with the time they were fetched.
+type SeedCache = HashMap<String, (Instant, Vec<String>)>;
+
+static SEED_CACHE: Lazy<Mutex<SeedCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Resolve a `DnsDiscovery` domain (plain or `enrtree://`) into bootstrap
+/// multiaddresses. Failures are logged and yield an empty list; other
+/// discovery strategies can still provide peers.
+pub fn resolve_seeds(domain: &str) -> Vec<String> {
+    resolve_seeds_with(&SystemTxtResolver, domain)
+}
+
+pub fn resolve_seeds_with(resolver: &dyn TxtResolver, domain: &str) -> Vec<String> {
+    if let Some((at, addrs)) = SEED_CACHE
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .get(domain)
+    {
+        if at.elapsed() < DNS_SEED_CACHE_TTL {
+            return addrs.clone();
+        }
+    }
+
+    let result = if domain.starts_with(LINK_PREFIX) {
+        EnrTreeLink::parse(domain)
+            .and_then(|link| resolve_enr_tree(resolver, &link))
+            .map(|enrs| enrs.iter().filter_map(enr_to_multiaddr).collect::<Vec<_>>())
+    } else {
+        resolve_txt_multiaddrs(resolver, domain)
+            .map(|addrs| if addrs.is_empty() { resolve_a_records(domain) } else { addrs })
+            .or_else(|_| Ok(resolve_a_records(domain)))
+    };
+
+    match result {
+        Ok(addrs) => {
+            if !addrs.is_empty() {
+                SEED_CACHE
+                    .lock()
+                    .unwrap_or_else(|p| p.into_inner())
+                    .insert(domain.to_string(), (Instant::now(), addrs.clone()));
+            }
+            addrs
+        }
+        Err(e) => {
+            log::warn!("DNS seeds: {}", e);
+            vec![]
+        }
+    }
+}
+
+/// Legacy seeds: A records of `_axiom-seed._tcp.<domain>` on the default port.
+fn resolve_a_records(domain: &str) -> Vec<String> {
+    let name = format!("_axiom-seed._tcp.{}", domain);
+    match std::net::ToSocketAddrs::to_socket_addrs(&(name.as_str(), DEFAULT_SEED_PORT)) {
+        Ok(addrs) => addrs
+            .filter(|sa| sa.is_ipv4())
+            .map(|sa| format!("/ip4/{}/tcp/{}", sa.ip(), sa.port()))
+            .collect(),
+        Err(_) => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use discv5::enr::CombinedKey;
+    use k256::ecdsa::SigningKey;
+
+    #[derive(Default)]
+    struct MapResolver(HashMap<String, String>);
+
+    impl TxtResolver for MapResolver {
+        fn lookup_txt(&self, name: &str) -> Result<Vec<String>, DnsSeedError> {
+            self.0
+                .get(name)
+                .map(|r| vec![r.clone()])
+                .ok_or_else(|| DnsSeedError::Lookup(name.into(), "NXDOMAIN".into()))
+        }
+    }
+
+    fn signed_root(key: &SigningKey, enr_root: &str, seq: u64) -> String {
+        let content = format!("{} e={} l={} seq={}", ROOT_PREFIX, enr_root, entry_hash("enrtree-branch:"), seq);
+        let (sig, recid) = key.sign_prehash_recoverable(&Keccak256::digest(content.as_bytes())).unwrap();
+        let mut bytes = sig.to_bytes().to_vec();
+        bytes.push(recid.to_byte());
+        format!("{} sig={}", content, BASE64URL_NOPAD.encode(&bytes))
+    }
+
+    fn test_enr(port: u16) -> String {
+        let key = CombinedKey::generate_secp256k1();
+        Enr::builder()
+            .ip4("203.0.113.9".parse().unwrap())
+            .tcp4(port)
+            .build(&key)
+            .unwrap()
+            .to_base64()
+    }
+
+    fn publish_tree(key: &SigningKey, domain: &str) -> (MapResolver, EnrTreeLink) {
+        let mut zone = MapResolver::default();
+        let leaves: Vec<String> = [6000, 6001, 6002].into_iter().map(test_enr).collect();
+        let leaf_hashes: Vec<String> = leaves.iter().map(|l| entry_hash(l)).collect();
+        for (leaf, hash) in leaves.iter().zip(&leaf_hashes) {
+            zone.0.insert(format!("{}.{}", hash, domain), leaf.clone());
+        }
+        let branch = format!("{}{}", BRANCH_PREFIX, leaf_hashes.join(","));
+        let branch_hash = entry_hash(&branch);
+        zone.0.insert(format!("{}.{}", branch_hash, domain), branch);
+        zone.0.insert(domain.to_string(), signed_root(key, &branch_hash, 1));
+
+        let pubkey = BASE32_NOPAD.encode(&key.verifying_key().to_sec1_bytes());
+        let link = EnrTreeLink::parse(&format!("enrtree://{}@{}", pubkey, domain)).unwrap();
+        (zone, link)
+    }
+
+    #[test]
+    fn test_resolve_signed_enr_tree() {
+        let key = SigningKey::random(&mut rand::thread_rng());
+        let (zone, link) = publish_tree(&key, "nodes.example.org");
+        let enrs = resolve_enr_tree(&zone, &link).unwrap();
+        let mut addrs: Vec<String> = enrs.iter().filter_map(enr_to_multiaddr).collect();
+        addrs.sort();
+        assert_eq!(addrs, vec![
+            "/ip4/203.0.113.9/tcp/6000",
+            "/ip4/203.0.113.9/tcp/6001",
+            "/ip4/203.0.113.9/tcp/6002",
+        ]);
+    }
+
+    #[test]
+    fn test_root_signed_by_other_key_is_rejected() {
+        let key = SigningKey::random(&mut rand::thread_rng());
+        let (zone, _) = publish_tree(&key, "nodes.example.org");
+        let other = SigningKey::random(&mut rand::thread_rng());
+        let forged_link = EnrTreeLink {
+            public_key: *other.verifying_key(),
+            domain: "nodes.example.org".into(),
+        };
+        assert_eq!(resolve_enr_tree(&zone, &forged_link).unwrap_err(), DnsSeedError::BadRootSignature);
+    }
+
+    #[test]
+    fn test_tampered_leaf_is_rejected() {
+        let key = SigningKey::random(&mut rand::thread_rng());
+        let (mut zone, link) = publish_tree(&key, "nodes.example.org");
+        let leaf = zone.0.keys().find(|k| zone.0[*k].starts_with("enr:")).unwrap().clone();
+        zone.0.insert(leaf, test_enr(9999));
+        assert!(matches!(resolve_enr_tree(&zone, &link), Err(DnsSeedError::HashMismatch(_))));
+    }
+
+    #[test]
+    fn test_txt_multiaddrs_skip_garbage() {
+        let mut zone = MapResolver::default();
+        zone.0.insert("_axiom-seed.example.org".into(), "/ip4/198.51.100.1/tcp/7000".into());
+        assert_eq!(resolve_txt_multiaddrs(&zone, "example.org").unwrap(), vec!["/ip4/198.51.100.1/tcp/7000"]);
+
+        zone.0.insert("_axiom-seed.example.org".into(), "v=spf1 -all".into());
+        assert!(resolve_txt_multiaddrs(&zone, "example.org").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resolved_seeds_are_cached() {
+        let mut zone = MapResolver::default();
+        zone.0.insert("_axiom-seed.cache.example.org".into(), "/ip4/198.51.100.2/tcp/7000".into());
+        assert_eq!(resolve_seeds_with(&zone, "cache.example.org").len(), 1);
+        // Served from cache even after the record disappears.
+        assert_eq!(resolve_seeds_with(&MapResolver::default(), "cache.example.org").len(), 1);
+    }
+}
@@ -0,0 +1,156 @@
+// src/network/encoded_blocks.rs - Serving sync blocks without re-encoding them
+//
+// A `GetBlocks` reply used to copy up to `MAX_BLOCKS_PER_REQUEST` blocks
+// out of the chain and encode the whole response into one buffer, for
+// every peer that asked; a node bootstrapping several peers held several
+// such copies at once. Blocks are now encoded once, cached by hash, and
+// the reply is written to the stream one block at a time, splicing the
+// cached bytes. What goes on the wire is byte for byte what
+// `serde_json::to_vec(&ChainResponse::Blocks { .. })` produced, so peers
+// see no difference.
+//
+// The cache is keyed by block hash, so blocks dropped by a reorg are
+// never served stale; they just age out.
+
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::Arc;
+
+use futures::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::block::Block;
+
+/// Encoded bytes kept for serving, at most.
+pub const ENCODED_BLOCK_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+/// A block's JSON encoding, shared between the cache and replies in flight.
+pub type EncodedBlock = Arc<[u8]>;
+
+pub struct EncodedBlockCache {
+    max_bytes: usize,
+    bytes: usize,
+    entries: HashMap<[u8; 32], EncodedBlock>,
+    /// Insertion order, oldest first, for eviction.
+    order: VecDeque<[u8; 32]>,
+    hits: u64,
+    misses: u64,
+}
+
+impl EncodedBlockCache {
+    pub fn new(max_bytes: usize) -> Self {
+        Self { max_bytes, bytes: 0, entries: HashMap::new(), order: VecDeque::new(), hits: 0, misses: 0 }
+    }
+
+    /// `block`'s encoding, from the cache or encoded now and cached.
+    pub fn encode(&mut self, block: &Block) -> EncodedBlock {
+        let hash = block.legacy_hash();
+        if let Some(encoded) = self.entries.get(&hash) {
+            self.hits += 1;
+            return Arc::clone(encoded);
+        }
+        self.misses += 1;
+        let encoded: EncodedBlock = serde_json::to_vec(block).unwrap_or_default().into();
+        if encoded.len() > self.max_bytes {
+            return encoded;
+        }
+        while self.bytes + encoded.len() > self.max_bytes {
+            let Some(oldest) = self.order.pop_front() else { break };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.bytes -= evicted.len();
+            }
+        }
+        self.bytes += encoded.len();
+        self.order.push_back(hash);
+        self.entries.insert(hash, Arc::clone(&encoded));
+        encoded
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Encoded bytes held.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Lookups served from the cache and lookups that had to encode.
+    pub fn hit_counts(&self) -> (u64, u64) {
+        (self.hits, self.misses)
+    }
+}
+
+fn blocks_prefix(tip_height: u64, from: u64) -> String {
+    format!("{{\"Blocks\":{{\"tip_height\":{},\"from\":{},\"blocks\":[", tip_height, from)
+}
+
+const BLOCKS_SUFFIX: &[u8] = b"]}}";
+
+/// Length of the `Blocks` reply `write_blocks_response` writes.
+pub fn blocks_response_len(tip_height: u64, from: u64, blocks: &[EncodedBlock]) -> usize {
+    blocks_prefix(tip_height, from).len()
+        + blocks.iter().map(|b| b.len()).sum::<usize>()
+        + blocks.len().saturating_sub(1)
+        + BLOCKS_SUFFIX.len()
+}
+
+/// Write a `ChainResponse::Blocks` reply made of already encoded blocks.
+pub async fn write_blocks_response<T>(io: &mut T, tip_height: u64, from: u64, blocks: &[EncodedBlock]) -> io::Result<()>
+where
+    T: AsyncWrite + Unpin,
+{
+    io.write_all(blocks_prefix(tip_height, from).as_bytes()).await?;
+    for (i, block) in blocks.iter().enumerate() {
+        if i > 0 {
+            io.write_all(b",").await?;
+        }
+        io.write_all(block).await?;
+    }
+    io.write_all(BLOCKS_SUFFIX).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network_legacy::ChainResponse;
+
+    fn chain(n: u64) -> Vec<Block> {
+        (0..n)
+            .map(|slot| Block { miner: [7u8; 32], nonce: slot, ..Block::test([slot as u8; 32], slot) })
+            .collect()
+    }
+
+    #[test]
+    fn test_reply_matches_plain_encoding_and_reuses_blocks() {
+        let blocks = chain(4);
+        let mut cache = EncodedBlockCache::new(ENCODED_BLOCK_CACHE_BYTES);
+        let encoded: Vec<EncodedBlock> = blocks[1..].iter().map(|b| cache.encode(b)).collect();
+        cache.encode(&blocks[2]);
+        assert_eq!(cache.hit_counts(), (1, 3));
+
+        let mut wire = Vec::new();
+        futures::executor::block_on(write_blocks_response(&mut wire, 4, 1, &encoded)).unwrap();
+        let plain = serde_json::to_vec(&ChainResponse::Blocks { tip_height: 4, from: 1, blocks: blocks[1..].to_vec() }).unwrap();
+        assert_eq!(wire, plain);
+        assert_eq!(blocks_response_len(4, 1, &encoded), plain.len());
+
+        let mut empty = Vec::new();
+        futures::executor::block_on(write_blocks_response(&mut empty, 4, 9, &[])).unwrap();
+        assert_eq!(empty, serde_json::to_vec(&ChainResponse::Blocks { tip_height: 4, from: 9, blocks: vec![] }).unwrap());
+        assert_eq!(blocks_response_len(4, 9, &[]), empty.len());
+
+        // Room for two blocks: the oldest go first.
+        let mut small = EncodedBlockCache::new(encoded[0].len() * 2 + 1);
+        for block in &blocks {
+            small.encode(block);
+        }
+        assert_eq!(small.len(), 2);
+        assert!(small.bytes() <= encoded[0].len() * 2 + 1);
+        small.encode(&blocks[3]);
+        assert_eq!(small.hit_counts(), (1, 4));
+    }
+}
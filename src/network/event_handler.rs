@@ -1,8 +1,17 @@
 use libp2p::swarm::SwarmEvent;
 use libp2p::PeerId;
 use crate::network::{AxiomEvent, PeerManager};
+use crate::network::handshake::verify_identify;
 use tracing::{info, warn, debug};
 
+/// Follow-up the caller must apply to the swarm after an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerAction {
+    /// Close all connections to the peer (it has already been banned in
+    /// the peer manager).
+    Disconnect { peer_id: PeerId, reason: String },
+}
+
 pub struct EventHandler {
     peer_manager: PeerManager,
 }
@@ -14,7 +23,7 @@ impl EventHandler {
         }
     }
     
-    pub async fn handle_swarm_event(&mut self, event: SwarmEvent<AxiomEvent>) {
+    pub async fn handle_swarm_event(&mut self, event: SwarmEvent<AxiomEvent>) -> Option<PeerAction> {
         match event {
             SwarmEvent::ConnectionEstablished { peer_id, endpoint, .. } => {
                 info!("✅ Connected to peer: {} at {:?}", peer_id, endpoint);
@@ -31,7 +40,7 @@ impl EventHandler {
             }
             
             SwarmEvent::Behaviour(axiom_event) => {
-                self.handle_axiom_event(axiom_event).await;
+                return self.handle_axiom_event(axiom_event).await;
             }
             
             SwarmEvent::IncomingConnection { .. } => {
@@ -46,9 +55,10 @@ impl EventHandler {
             
             _ => {}
         }
+        None
     }
     
-    async fn handle_axiom_event(&mut self, event: AxiomEvent) {
+    async fn handle_axiom_event(&mut self, event: AxiomEvent) -> Option<PeerAction> {
         match event {
             AxiomEvent::Gossipsub(gossip_event) => {
                 use libp2p::gossipsub::Event;
@@ -95,6 +105,11 @@ impl EventHandler {
                         info!("🔍 Identified peer: {}", peer_id);
                         info!("   Protocol: {}", info.protocol_version);
                         info!("   Agent: {}", info.agent_version);
+                        if let Err(e) = verify_identify(&info.protocol_version) {
+                            warn!("⛔ Rejecting peer {} from another network: {}", peer_id, e);
+                            self.peer_manager.ban_peer(peer_id);
+                            return Some(PeerAction::Disconnect { peer_id, reason: e.to_string() });
+                        }
                     }
                     Event::Sent { peer_id, .. } => {
                         debug!("🔍 Sent identify info to {}", peer_id);
@@ -124,6 +139,7 @@ impl EventHandler {
                 }
             }
         }
+        None
     }
     
     pub fn peer_count(&self) -> usize {
@@ -134,3 +150,41 @@ impl EventHandler {
         self.peer_manager.all_peers()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libp2p::identify;
+    use libp2p::identity::Keypair;
+    use libp2p::swarm::ConnectionId;
+
+    fn identify_received(peer_id: PeerId, protocol_version: String) -> SwarmEvent<AxiomEvent> {
+        let key = Keypair::generate_ed25519();
+        SwarmEvent::Behaviour(AxiomEvent::Identify(identify::Event::Received {
+            connection_id: ConnectionId::new_unchecked(0),
+            peer_id,
+            info: identify::Info {
+                public_key: key.public(),
+                protocol_version,
+                agent_version: "test".into(),
+                listen_addrs: vec![],
+                protocols: vec![],
+                observed_addr: "/ip4/127.0.0.1/tcp/1".parse().unwrap(),
+            },
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_foreign_genesis_peer_is_disconnected() {
+        let mut handler = EventHandler::new(10);
+        let peer_id = PeerId::random();
+
+        let action = handler
+            .handle_swarm_event(identify_received(peer_id, "/axiom/1.0.0/chain/84000/genesis/00".into()))
+            .await;
+        assert!(matches!(action, Some(PeerAction::Disconnect { peer_id: p, .. }) if p == peer_id));
+
+        let ours = crate::network::handshake::identify_protocol_version();
+        assert_eq!(handler.handle_swarm_event(identify_received(PeerId::random(), ours)).await, None);
+    }
+}
@@ -0,0 +1,238 @@
+// src/network/geoip.rs - Peer location from MaxMind GeoLite2 databases
+//
+// With the `geoip` feature and `network.geoip_dir` pointing at a directory
+// holding GeoLite2-ASN.mmdb and/or GeoLite2-Country.mmdb, every connected
+// peer is tagged with the country and autonomous system of its address.
+// The tags go three places:
+//
+//   - `PeerDiversity` caps outbound peers per AS and per country;
+//   - each `NetworkEvent` recorded for the peer carries its location, so
+//     guardian decisions are audited with it;
+//   - a peer ID that reconnects from a different country within
+//     `GEO_HOP_WINDOW_SECS` is reported as a `GeographicAnomaly`. A node
+//     identity is a key on one machine; the same key turning up on two
+//     continents within the hour is a stolen or shared key, or a relay
+//     masking where the node really is.
+//
+// Without the feature (or the databases) peers are simply untagged and
+// every rule that needs a location is skipped.
+
+use libp2p::PeerId;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::diversity::{is_routable, AsnLookup};
+
+pub const ASN_DATABASE: &str = "GeoLite2-ASN.mmdb";
+pub const COUNTRY_DATABASE: &str = "GeoLite2-Country.mmdb";
+
+/// A reconnect from another country within this long is a hop.
+pub const GEO_HOP_WINDOW_SECS: u64 = 3600;
+
+/// Trust taken off a peer for each hop.
+pub const GEO_HOP_PENALTY: f32 = 0.3;
+
+/// Disconnected peers whose last location is remembered.
+const MAX_REMEMBERED_PEERS: usize = 4096;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PeerLocation {
+    /// ISO 3166 country code.
+    pub country: Option<String>,
+    pub asn: Option<u32>,
+}
+
+impl PeerLocation {
+    pub fn is_known(&self) -> bool {
+        self.country.is_some() || self.asn.is_some()
+    }
+}
+
+impl std::fmt::Display for PeerLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.country.as_deref().unwrap_or("??"))?;
+        match self.asn {
+            Some(asn) => write!(f, "/AS{}", asn),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A peer that reappeared from another country.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeoHop {
+    pub from: PeerLocation,
+    pub to: PeerLocation,
+    /// Seconds since it was last seen at `from`.
+    pub after_secs: u64,
+}
+
+/// Lookups against the GeoLite2 databases.
+#[cfg(feature = "geoip")]
+pub struct MaxMindGeoIp {
+    asn: Option<maxminddb::Reader<Vec<u8>>>,
+    country: Option<maxminddb::Reader<Vec<u8>>>,
+}
+
+#[cfg(feature = "geoip")]
+impl MaxMindGeoIp {
+    /// Open whichever of the two databases are in `dir`; at least one must be.
+    pub fn open(dir: &Path) -> Result<Self, String> {
+        let open = |name: &str| -> Result<Option<maxminddb::Reader<Vec<u8>>>, String> {
+            let path = dir.join(name);
+            if !path.exists() {
+                return Ok(None);
+            }
+            maxminddb::Reader::open_readfile(&path)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", path.display(), e))
+        };
+        let geoip = Self { asn: open(ASN_DATABASE)?, country: open(COUNTRY_DATABASE)? };
+        if geoip.asn.is_none() && geoip.country.is_none() {
+            return Err(format!("neither {} nor {} in {}", ASN_DATABASE, COUNTRY_DATABASE, dir.display()));
+        }
+        Ok(geoip)
+    }
+}
+
+#[cfg(feature = "geoip")]
+impl AsnLookup for MaxMindGeoIp {
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        let reader = self.asn.as_ref()?;
+        reader.lookup::<maxminddb::geoip2::Asn>(ip).ok()?.autonomous_system_number
+    }
+
+    fn country(&self, ip: IpAddr) -> Option<String> {
+        let reader = self.country.as_ref()?;
+        let record = reader.lookup::<maxminddb::geoip2::Country>(ip).ok()?;
+        record.country?.iso_code.map(str::to_string)
+    }
+}
+
+/// Open the GeoLite2 databases in `dir`.
+pub fn open_geoip(dir: &Path) -> Result<Arc<dyn AsnLookup>, String> {
+    #[cfg(feature = "geoip")]
+    {
+        Ok(Arc::new(MaxMindGeoIp::open(dir)?))
+    }
+    #[cfg(not(feature = "geoip"))]
+    {
+        Err(format!("{} is set, but the node was built without the geoip feature", dir.display()))
+    }
+}
+
+/// Locations of connected peers, and where recently disconnected ones
+/// were last seen.
+pub struct PeerGeo {
+    lookup: Option<Arc<dyn AsnLookup>>,
+    connected: HashMap<PeerId, PeerLocation>,
+    last_seen: HashMap<PeerId, (PeerLocation, u64)>,
+}
+
+impl PeerGeo {
+    pub fn new(lookup: Option<Arc<dyn AsnLookup>>) -> Self {
+        Self { lookup, connected: HashMap::new(), last_seen: HashMap::new() }
+    }
+
+    /// Tag `peer`, connected from `ip`. Returns the hop if it was seen in
+    /// another country within `GEO_HOP_WINDOW_SECS`.
+    pub fn connected(&mut self, peer: PeerId, ip: IpAddr, now: u64) -> Option<GeoHop> {
+        let lookup = self.lookup.as_ref()?;
+        if !is_routable(ip) {
+            return None;
+        }
+        let location = PeerLocation { country: lookup.country(ip), asn: lookup.asn(ip) };
+        if !location.is_known() {
+            return None;
+        }
+
+        let hop = self.last_seen.get(&peer).and_then(|(previous, seen)| {
+            let after_secs = now.saturating_sub(*seen);
+            let moved = matches!((&previous.country, &location.country), (Some(a), Some(b)) if a != b);
+            (moved && after_secs < GEO_HOP_WINDOW_SECS).then(|| GeoHop {
+                from: previous.clone(),
+                to: location.clone(),
+                after_secs,
+            })
+        });
+
+        if self.last_seen.len() >= MAX_REMEMBERED_PEERS && !self.last_seen.contains_key(&peer) {
+            self.last_seen.retain(|_, (_, seen)| now.saturating_sub(*seen) < GEO_HOP_WINDOW_SECS);
+            if self.last_seen.len() >= MAX_REMEMBERED_PEERS {
+                let oldest = self.last_seen.iter().min_by_key(|(_, (_, seen))| *seen).map(|(p, _)| *p);
+                if let Some(oldest) = oldest {
+                    self.last_seen.remove(&oldest);
+                }
+            }
+        }
+        self.last_seen.insert(peer, (location.clone(), now));
+        self.connected.insert(peer, location);
+        hop
+    }
+
+    /// Untag `peer`. Its location is remembered for hop detection.
+    pub fn disconnected(&mut self, peer: &PeerId, now: u64) {
+        if let Some(location) = self.connected.remove(peer) {
+            self.last_seen.insert(*peer, (location, now));
+        }
+    }
+
+    pub fn location(&self, peer: &PeerId) -> Option<&PeerLocation> {
+        self.connected.get(peer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ByFirstOctet;
+    impl AsnLookup for ByFirstOctet {
+        fn asn(&self, ip: IpAddr) -> Option<u32> {
+            match ip {
+                IpAddr::V4(v4) => Some(64_000 + v4.octets()[0] as u32),
+                IpAddr::V6(_) => None,
+            }
+        }
+
+        fn country(&self, ip: IpAddr) -> Option<String> {
+            match ip {
+                IpAddr::V4(v4) if v4.octets()[0] < 100 => Some("DE".into()),
+                IpAddr::V4(_) => Some("BR".into()),
+                IpAddr::V6(_) => None,
+            }
+        }
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_tags_peers_and_detects_country_hops() {
+        assert!(PeerGeo::new(None).connected(PeerId::random(), ip("3.3.3.3"), 0).is_none());
+
+        let mut geo = PeerGeo::new(Some(Arc::new(ByFirstOctet)));
+        let peer = PeerId::random();
+        assert_eq!(geo.connected(peer, ip("3.3.3.3"), 1_000), None);
+        assert_eq!(geo.location(&peer).unwrap().to_string(), "DE/AS64003");
+        assert!(geo.connected(PeerId::random(), ip("10.0.0.1"), 1_000).is_none());
+
+        // Another AS in the same country is not a hop.
+        geo.disconnected(&peer, 1_100);
+        assert!(geo.location(&peer).is_none());
+        assert_eq!(geo.connected(peer, ip("4.4.4.4"), 1_200), None);
+
+        geo.disconnected(&peer, 1_300);
+        let hop = geo.connected(peer, ip("200.1.1.1"), 1_500).unwrap();
+        assert_eq!((hop.from.country.as_deref(), hop.to.country.as_deref()), (Some("DE"), Some("BR")));
+        assert_eq!(hop.after_secs, 200);
+
+        // Back home, but long after.
+        geo.disconnected(&peer, 2_000);
+        assert_eq!(geo.connected(peer, ip("3.3.3.3"), 2_000 + GEO_HOP_WINDOW_SECS), None);
+    }
+}
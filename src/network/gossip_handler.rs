@@ -1,15 +1,19 @@
 use bincode::{deserialize, serialize};
 use libp2p::gossipsub::{IdentTopic, TopicHash};
+use libp2p::PeerId;
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Sha3_256};
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::{mpsc, RwLock};
-use tracing::{debug, trace, warn};
+use tracing::{debug, error, trace, warn};
 
-use crate::network::peer_manager::PeerManager;
+use crate::network::peer_manager::{PeerManager, RateLimitVerdict};
+use crate::network::wire::WireError;
+use crate::pulse_validator::PulseRejection;
 
 /// Maximum message size (2MB)
 const MAX_MESSAGE_SIZE: usize = 2 * 1024 * 1024;
@@ -17,6 +21,37 @@ const MAX_MESSAGE_SIZE: usize = 2 * 1024 * 1024;
 /// Message deduplication cache TTL
 const MESSAGE_CACHE_TTL: Duration = Duration::from_secs(300); // 5 minutes
 
+/// Reputation penalty for a message that fails to decode or validate.
+pub const INVALID_MESSAGE_PENALTY: i32 = 10;
+
+/// Reputation penalty for a message whose handling panicked.
+pub const HANDLER_PANIC_PENALTY: i32 = 50;
+
+/// Run message decoding or validation, converting a panic into
+/// [`GossipError::HandlerPanicked`] so one bad message is dropped instead
+/// of taking the whole node down.
+///
+/// Only for code that reads its input and changes nothing: a panic halfway
+/// through applying a message would leave the chain or mempool half
+/// updated, so that code returns errors instead. Release builds unwind
+/// (see `[profile.release]`), so this works there too.
+pub fn isolate_panics<T>(f: impl FnOnce() -> Result<T, GossipError>) -> Result<T, GossipError> {
+    match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(result) => result,
+        Err(payload) => Err(GossipError::HandlerPanicked(panic_message(payload.as_ref()))),
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
 /// Block message
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockMessage {
@@ -40,7 +75,7 @@ impl BlockMessage {
         
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .unwrap()
+            .unwrap_or_default()
             .as_secs();
         
         if self.timestamp > now + 3600 {
@@ -164,6 +199,20 @@ impl GossipMessage {
         
         deserialize(data).map_err(|e| GossipError::DecodeFailed(e.to_string()))
     }
+
+    /// Decode `data` and check the message it carries.
+    pub fn decode_valid(data: &[u8]) -> Result<Self, GossipError> {
+        let message = Self::decode(data)?;
+        match &message {
+            GossipMessage::Block(block) => block.validate().map_err(GossipError::Validation)?,
+            GossipMessage::Transaction(tx) => tx.validate().map_err(GossipError::Validation)?,
+            GossipMessage::Sync(sync) if sync.end_height < sync.start_height => {
+                return Err(GossipError::InvalidSyncRange);
+            }
+            GossipMessage::Sync(_) | GossipMessage::Heartbeat(_) => {}
+        }
+        Ok(message)
+    }
     
     pub fn message_type(&self) -> &str {
         match self {
@@ -186,7 +235,7 @@ pub struct ProcessedMessage {
 /// Gossip message handler
 pub struct GossipHandler {
     message_cache: Arc<RwLock<HashMap<[u8; 32], Instant>>>,
-    peer_manager: Arc<PeerManager>,
+    peer_manager: Arc<RwLock<PeerManager>>,
     block_tx: mpsc::UnboundedSender<ProcessedMessage>,
     transaction_tx: mpsc::UnboundedSender<ProcessedMessage>,
     sync_tx: mpsc::UnboundedSender<ProcessedMessage>,
@@ -207,7 +256,7 @@ pub struct GossipMetrics {
 
 impl GossipHandler {
     pub fn new(
-        peer_manager: Arc<PeerManager>,
+        peer_manager: Arc<RwLock<PeerManager>>,
     ) -> (
         Self,
         mpsc::UnboundedReceiver<ProcessedMessage>,
@@ -251,90 +300,90 @@ impl GossipHandler {
         self.processed_messages.contains(message_id)
     }
     
+    /// Lower the reputation of the peer behind `source` (a `PeerId`
+    /// string). Sources that are not valid peer IDs are only logged.
+    async fn penalize(&self, source: &str, amount: i32) {
+        match source.parse::<PeerId>() {
+            Ok(peer_id) => {
+                if self.peer_manager.write().await.penalize_peer(&peer_id, amount) {
+                    warn!("Banned peer {} after repeated invalid messages", peer_id);
+                }
+            }
+            Err(_) => debug!("Cannot penalize unknown source {}", source),
+        }
+    }
+
     pub async fn handle_message(
         &self,
         source: String,
         data: Vec<u8>,
-        _topic: String,
+        topic: String,
     ) -> Result<(), GossipError> {
         let mut m = self.metrics.write().await;
         m.total_messages_received += 1;
         drop(m);
         
+        if let Ok(peer_id) = source.parse::<PeerId>() {
+            let verdict = self.peer_manager.write().await.check_inbound(peer_id, &topic, data.len());
+            if verdict != RateLimitVerdict::Allowed {
+                debug!("Dropping message from {} on {}: {:?}", source, topic, verdict);
+                return Err(GossipError::RateLimited(verdict));
+            }
+        }
+        
         if data.len() > MAX_MESSAGE_SIZE {
             warn!("Oversized message from {}: {} bytes", source, data.len());
+            self.penalize(&source, INVALID_MESSAGE_PENALTY).await;
             return Err(GossipError::MessageTooLarge);
         }
         
-        let message = match GossipMessage::decode(&data) {
+        // Decoding and validation are isolated; sending the message on is
+        // not, and reports failures as errors.
+        let message = match isolate_panics(|| GossipMessage::decode_valid(&data)) {
             Ok(msg) => msg,
             Err(e) => {
-                warn!("Failed to decode message from {}: {}", source, e);
-                let mut m = self.metrics.write().await;
-                m.invalid_messages += 1;
+                let penalty = match &e {
+                    GossipError::HandlerPanicked(reason) => {
+                        error!("Gossip decoding panicked on message from {}: {}", source, reason);
+                        HANDLER_PANIC_PENALTY
+                    }
+                    e => {
+                        warn!("Invalid message from {} on {}: {}", source, topic, e);
+                        INVALID_MESSAGE_PENALTY
+                    }
+                };
+                self.metrics.write().await.invalid_messages += 1;
+                self.penalize(&source, penalty).await;
                 return Err(e);
             }
         };
         
         debug!("📨 Processing {} message from {}", message.message_type(), source);
         
-        match &message {
-            GossipMessage::Block(block) => {
-                if let Err(e) = block.validate() {
-                    warn!("Invalid block from {}: {:?}", source, e);
-                    return Err(GossipError::Validation(e));
-                }
-                
-                let mut m = self.metrics.write().await;
-                m.blocks_received += 1;
-                drop(m);
-                
-                self.block_tx.send(ProcessedMessage {
-                    message: GossipMessage::Block(block.clone()),
-                    source,
-                    received_at: Instant::now(),
-                }).map_err(|_| GossipError::ChannelClosed)?;
+        let sender = match &message {
+            GossipMessage::Block(_) => {
+                self.metrics.write().await.blocks_received += 1;
+                &self.block_tx
             }
-            
-            GossipMessage::Transaction(tx) => {
-                if let Err(e) = tx.validate() {
-                    warn!("Invalid transaction from {}: {:?}", source, e);
-                    return Err(GossipError::Validation(e));
-                }
-                
-                let mut m = self.metrics.write().await;
-                m.transactions_received += 1;
-                drop(m);
-                
-                self.transaction_tx.send(ProcessedMessage {
-                    message: GossipMessage::Transaction(tx.clone()),
-                    source,
-                    received_at: Instant::now(),
-                }).map_err(|_| GossipError::ChannelClosed)?;
+            GossipMessage::Transaction(_) => {
+                self.metrics.write().await.transactions_received += 1;
+                &self.transaction_tx
             }
-            
-            GossipMessage::Sync(sync) => {
-                if sync.end_height < sync.start_height {
-                    warn!("Invalid sync range from {}", source);
-                    return Err(GossipError::InvalidSyncRange);
-                }
-                
-                let mut m = self.metrics.write().await;
-                m.sync_requests_received += 1;
-                drop(m);
-                
-                self.sync_tx.send(ProcessedMessage {
-                    message: GossipMessage::Sync(sync.clone()),
-                    source,
-                    received_at: Instant::now(),
-                }).map_err(|_| GossipError::ChannelClosed)?;
+            GossipMessage::Sync(_) => {
+                self.metrics.write().await.sync_requests_received += 1;
+                &self.sync_tx
             }
-            
             GossipMessage::Heartbeat(_) => {
                 // Just log it
                 trace!("Heartbeat received from {}", source);
+                return Ok(());
             }
-        }
+        };
+        sender.send(ProcessedMessage {
+            message,
+            source,
+            received_at: Instant::now(),
+        }).map_err(|_| GossipError::ChannelClosed)?;
         
         Ok(())
     }
@@ -390,11 +439,35 @@ pub enum GossipError {
     #[error("Validation error: {0:?}")]
     Validation(ValidationError),
     
+    #[error("Invalid pulse: {0}")]
+    InvalidPulse(#[from] PulseRejection),
+
     #[error("Invalid sync range")]
     InvalidSyncRange,
     
     #[error("Channel closed")]
     ChannelClosed,
+
+    #[error("Message handler panicked: {0}")]
+    HandlerPanicked(String),
+
+    #[error("Peer rate limit exceeded: {0:?}")]
+    RateLimited(RateLimitVerdict),
+
+    /// The message uses a breaking wire version newer than ours. Not the
+    /// sender's fault.
+    #[error("Unsupported wire version {0}")]
+    UnsupportedWireVersion(u8),
+}
+
+impl From<WireError> for GossipError {
+    fn from(e: WireError) -> Self {
+        match e {
+            WireError::UnsupportedVersion(version) => GossipError::UnsupportedWireVersion(version),
+            WireError::Encode(e) => GossipError::EncodeFailed(e),
+            WireError::Malformed(e) => GossipError::DecodeFailed(e),
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -426,3 +499,51 @@ pub enum ValidationError {
     #[error("Missing commitment")]
     MissingCommitment,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_isolate_panics_converts_panic_to_error() {
+        let ok = isolate_panics(|| Ok::<_, GossipError>(7));
+        assert_eq!(ok.unwrap(), 7);
+
+        let result: Result<(), GossipError> = isolate_panics(|| panic!("boom"));
+        assert!(matches!(result, Err(GossipError::HandlerPanicked(ref m)) if m == "boom"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_message_penalizes_peer() {
+        let peer_id = PeerId::random();
+        let mut pm = PeerManager::new(10);
+        pm.add_peer(peer_id);
+        let pm = Arc::new(RwLock::new(pm));
+        let (handler, _b, _t, mut sync_rx) = GossipHandler::new(pm.clone());
+
+        let result = handler
+            .handle_message(peer_id.to_string(), vec![0xff; 3], "blocks".into())
+            .await;
+        assert!(matches!(result, Err(GossipError::DecodeFailed(_))));
+
+        let reputation = pm.read().await.get_peer(&peer_id).unwrap().reputation;
+        assert_eq!(reputation, 100 - INVALID_MESSAGE_PENALTY);
+        assert_eq!(handler.metrics().await.invalid_messages, 1);
+
+        // Well-formed but invalid: charged the same, and never passed on.
+        let sync = |start_height, end_height| GossipMessage::Sync(SyncMessage {
+            start_height,
+            end_height,
+            known_hashes: Vec::new(),
+            request_type: SyncRequestType::Headers,
+        });
+        let result = handler.handle_message(peer_id.to_string(), sync(9, 3).encode().unwrap(), "sync".into()).await;
+        assert!(matches!(result, Err(GossipError::InvalidSyncRange)));
+        let reputation = pm.read().await.get_peer(&peer_id).unwrap().reputation;
+        assert_eq!(reputation, 100 - 2 * INVALID_MESSAGE_PENALTY);
+        assert!(sync_rx.try_recv().is_err());
+
+        handler.handle_message(peer_id.to_string(), sync(3, 9).encode().unwrap(), "sync".into()).await.unwrap();
+        assert_eq!(sync_rx.try_recv().unwrap().message.message_type(), "Sync");
+    }
+}
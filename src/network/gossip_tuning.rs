@@ -0,0 +1,185 @@
+// src/network/gossip_tuning.rs - Gossipsub mesh parameters sized to the network
+//
+// One fixed mesh is wrong at both ends of our range. On a five-node devnet
+// a mesh degree of 8 can never fill, so every heartbeat goes looking for
+// peers that do not exist; on a mainnet of thousands a short heartbeat and
+// a tight D_hi waste control traffic and churn the mesh on every inbound
+// GRAFT. The tuner keeps smoothed estimates of the network size and of our
+// gossip bandwidth, sampled on the dashboard tick, and maps them to a
+// `GossipConfig` profile.
+//
+// libp2p cannot reconfigure a running gossipsub behaviour, so a new
+// profile is persisted to `axiom_gossip_tuning.json` and applied when the
+// swarm is next built. An upgrade handoff (SIGUSR2) restarts the node
+// without losing its mempool, peers or VDF window.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use super::config::GossipConfig;
+
+pub const GOSSIP_TUNING_PATH: &str = "axiom_gossip_tuning.json";
+
+/// Weight of a new sample in the moving averages.
+pub const SMOOTHING: f64 = 0.2;
+
+/// Consecutive samples a new profile must be recommended for before the
+/// tuner switches to it. At the 30 s dashboard tick this is five minutes,
+/// so a burst of connections or a short outage does not flip the profile.
+pub const STABLE_SAMPLES: u32 = 10;
+
+/// Sustained gossip bandwidth above which the mesh is trimmed to D_lo.
+pub const HIGH_BANDWIDTH_BYTES_PER_SEC: f64 = 512.0 * 1024.0;
+
+/// One observation of the network, as the consensus optimizer's
+/// `NetworkMetrics` sees it: how many peers exist and what gossip costs us.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkSample {
+    /// Estimated network size: the larger of our connected peers and the
+    /// nodes in the discovery table.
+    pub peer_count: usize,
+    /// Gossip bytes in plus out per second since the previous sample.
+    pub bytes_per_sec: f64,
+}
+
+/// The mesh profile for a network of `network_size` nodes at the given
+/// bandwidth.
+pub fn tuning_for(network_size: f64, bytes_per_sec: f64) -> GossipConfig {
+    let (mesh_n_low, mesh_n, mesh_n_high, heartbeat_ms) = if network_size < 12.0 {
+        // Devnet: a mesh the network can actually fill, and a fast
+        // heartbeat so the few links form quickly.
+        (2, 3, 5, 700)
+    } else if network_size < 100.0 {
+        (4, 6, 10, 1_000)
+    } else if network_size < 1_000.0 {
+        (6, 8, 12, 1_000)
+    } else {
+        // Large mesh: room for inbound grafts and fewer control rounds.
+        (6, 8, 16, 1_500)
+    };
+    let mesh_n = if bytes_per_sec > HIGH_BANDWIDTH_BYTES_PER_SEC { mesh_n_low } else { mesh_n };
+    GossipConfig {
+        heartbeat_interval: Duration::from_millis(heartbeat_ms),
+        mesh_n,
+        mesh_n_low,
+        mesh_n_high,
+        ..GossipConfig::default()
+    }
+}
+
+#[derive(Debug)]
+pub struct GossipTuner {
+    active: GossipConfig,
+    network_size: Option<f64>,
+    bytes_per_sec: f64,
+    /// Profile recommended by recent samples and for how many in a row.
+    candidate: Option<(GossipConfig, u32)>,
+}
+
+impl GossipTuner {
+    /// Start from the profile the swarm was built with.
+    pub fn new(active: GossipConfig) -> Self {
+        Self { active, network_size: None, bytes_per_sec: 0.0, candidate: None }
+    }
+
+    /// Profile currently recommended for the next start.
+    pub fn active(&self) -> &GossipConfig {
+        &self.active
+    }
+
+    pub fn estimated_network_size(&self) -> f64 {
+        self.network_size.unwrap_or(0.0)
+    }
+
+    /// Feed one sample. Returns the new profile once a different one has
+    /// been recommended for `STABLE_SAMPLES` samples in a row.
+    pub fn observe(&mut self, sample: NetworkSample) -> Option<&GossipConfig> {
+        let size = sample.peer_count as f64;
+        self.network_size = Some(match self.network_size {
+            Some(avg) => avg + SMOOTHING * (size - avg),
+            None => size,
+        });
+        self.bytes_per_sec += SMOOTHING * (sample.bytes_per_sec - self.bytes_per_sec);
+
+        let recommended = tuning_for(self.estimated_network_size(), self.bytes_per_sec);
+        if recommended == self.active {
+            self.candidate = None;
+            return None;
+        }
+        let streak = match &self.candidate {
+            Some((candidate, streak)) if *candidate == recommended => streak + 1,
+            _ => 1,
+        };
+        if streak < STABLE_SAMPLES {
+            self.candidate = Some((recommended, streak));
+            return None;
+        }
+        self.candidate = None;
+        self.active = recommended;
+        Some(&self.active)
+    }
+}
+
+/// The persisted profile, if there is a valid one at `path`.
+pub fn load_tuning(path: impl AsRef<Path>) -> Option<GossipConfig> {
+    let content = fs::read_to_string(path.as_ref()).ok()?;
+    let tuning: GossipConfig = serde_json::from_str(&content)
+        .map_err(|e| log::warn!("GOSSIP: ignoring corrupt {}: {}", path.as_ref().display(), e))
+        .ok()?;
+    tuning.to_gossipsub().is_ok().then_some(tuning)
+}
+
+pub fn save_tuning(path: impl AsRef<Path>, tuning: &GossipConfig) -> io::Result<()> {
+    let path = path.as_ref();
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(tuning)?)?;
+    fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_are_valid_gossipsub_configs() {
+        for size in [1.0, 5.0, 50.0, 500.0, 5_000.0] {
+            for bandwidth in [0.0, 2.0 * HIGH_BANDWIDTH_BYTES_PER_SEC] {
+                let tuning = tuning_for(size, bandwidth);
+                assert!(tuning.to_gossipsub().is_ok(), "invalid profile for {} nodes: {:?}", size, tuning);
+            }
+        }
+        assert!(tuning_for(5.0, 0.0).mesh_n < tuning_for(5_000.0, 0.0).mesh_n);
+        assert!(tuning_for(5.0, 0.0).heartbeat_interval < tuning_for(5_000.0, 0.0).heartbeat_interval);
+    }
+
+    #[test]
+    fn test_tuner_switches_only_after_stable_samples() {
+        let mut tuner = GossipTuner::new(tuning_for(500.0, 0.0));
+        let devnet = NetworkSample { peer_count: 4, bytes_per_sec: 100.0 };
+
+        // One sample must never be enough on its own.
+        let mut switched_after = None;
+        for i in 1..=40 {
+            if tuner.observe(devnet).is_some() {
+                switched_after = Some(i);
+                break;
+            }
+        }
+        assert!(switched_after.is_some_and(|n| n >= STABLE_SAMPLES));
+        assert_eq!(tuner.active(), &tuning_for(4.0, 100.0));
+
+        // A single large sample does not flip it back.
+        assert!(tuner.observe(NetworkSample { peer_count: 5_000, bytes_per_sec: 0.0 }).is_none());
+    }
+
+    #[test]
+    fn test_tuning_round_trip() {
+        let path = std::env::temp_dir().join(format!("axiom_test_gossip_tuning_{}.json", std::process::id()));
+        let tuning = tuning_for(5_000.0, 0.0);
+        save_tuning(&path, &tuning).unwrap();
+        assert_eq!(load_tuning(&path), Some(tuning));
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,106 @@
+//! Chain binding for peer handshakes.
+//!
+//! Two independent checks keep peers from other networks out of our
+//! gossip mesh:
+//!
+//! 1. **Noise prologue** — the chain ID and genesis anchor are mixed into
+//!    the Noise handshake as its prologue. Both sides must supply the same
+//!    prologue or the handshake fails, so a TCP peer from another chain
+//!    never completes a connection.
+//! 2. **Identify payload** — the same binding is advertised in the
+//!    identify `protocol_version`. This also covers transports that do not
+//!    use Noise (QUIC runs TLS) and gives a readable reason when a peer is
+//!    dropped; see `EventHandler` and the node event loop.
+
+use libp2p::identity::Keypair;
+use libp2p::noise;
+
+use crate::chain::GENESIS_ANCHOR;
+use crate::AXIOM_CHAIN_ID;
+
+/// Base identify protocol; the chain binding is appended to it.
+pub const IDENTIFY_PROTOCOL_BASE: &str = "/axiom/1.0.0";
+
+/// Noise prologue binding the handshake to this chain.
+pub fn noise_prologue() -> Vec<u8> {
+    format!("axiom-chain:{}:{}", AXIOM_CHAIN_ID, GENESIS_ANCHOR).into_bytes()
+}
+
+/// Noise security upgrade with the chain prologue, for use in place of
+/// `noise::Config::new` when building a swarm.
+pub fn noise_config(keypair: &Keypair) -> Result<noise::Config, noise::Error> {
+    Ok(noise::Config::new(keypair)?.with_prologue(noise_prologue()))
+}
+
+/// Identify `protocol_version` advertised by this node:
+/// `/axiom/1.0.0/chain/84000/genesis/<anchor>`.
+pub fn identify_protocol_version() -> String {
+    format!("{}/chain/{}/genesis/{}", IDENTIFY_PROTOCOL_BASE, AXIOM_CHAIN_ID, GENESIS_ANCHOR)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HandshakeError {
+    #[error("not an Axiom peer (protocol {0})")]
+    NotAxiom(String),
+
+    #[error("chain ID mismatch: expected {expected}, peer advertises {found}")]
+    ChainIdMismatch { expected: u64, found: String },
+
+    #[error("genesis mismatch: expected {expected}, peer advertises {found}")]
+    GenesisMismatch { expected: String, found: String },
+}
+
+/// Check a peer's identify `protocol_version` against our chain binding.
+pub fn verify_identify(protocol_version: &str) -> Result<(), HandshakeError> {
+    let rest = protocol_version
+        .strip_prefix(IDENTIFY_PROTOCOL_BASE)
+        .and_then(|r| r.strip_prefix("/chain/"))
+        .ok_or_else(|| HandshakeError::NotAxiom(protocol_version.to_string()))?;
+
+    let (chain_id, genesis) = rest
+        .split_once("/genesis/")
+        .ok_or_else(|| HandshakeError::NotAxiom(protocol_version.to_string()))?;
+
+    if chain_id.parse::<u64>().ok() != Some(AXIOM_CHAIN_ID) {
+        return Err(HandshakeError::ChainIdMismatch {
+            expected: AXIOM_CHAIN_ID,
+            found: chain_id.to_string(),
+        });
+    }
+    if genesis != GENESIS_ANCHOR {
+        return Err(HandshakeError::GenesisMismatch {
+            expected: GENESIS_ANCHOR.to_string(),
+            found: genesis.to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_own_identify_version_verifies() {
+        assert_eq!(verify_identify(&identify_protocol_version()), Ok(()));
+    }
+
+    #[test]
+    fn test_foreign_peers_rejected() {
+        assert!(matches!(verify_identify("/ipfs/0.1.0"), Err(HandshakeError::NotAxiom(_))));
+        // Pre-binding Axiom nodes advertised the bare base protocol.
+        assert!(matches!(verify_identify("/axiom/1.0.0"), Err(HandshakeError::NotAxiom(_))));
+
+        let other_chain = format!("/axiom/1.0.0/chain/1/genesis/{}", GENESIS_ANCHOR);
+        assert!(matches!(verify_identify(&other_chain), Err(HandshakeError::ChainIdMismatch { .. })));
+
+        let other_genesis = format!("/axiom/1.0.0/chain/{}/genesis/deadbeef", AXIOM_CHAIN_ID);
+        assert!(matches!(verify_identify(&other_genesis), Err(HandshakeError::GenesisMismatch { .. })));
+    }
+
+    #[test]
+    fn test_noise_config_builds_with_prologue() {
+        assert!(noise_prologue().ends_with(GENESIS_ANCHOR.as_bytes()));
+        assert!(noise_config(&Keypair::generate_ed25519()).is_ok());
+    }
+}
@@ -1,14 +1,29 @@
 pub mod behaviour;
 pub mod config;
 pub mod discv5_service;
+pub mod diversity;
+pub mod dns_seeds;
+pub mod encoded_blocks;
 pub mod event_handler;
+pub mod geoip;
 pub mod gossip_handler;
+pub mod gossip_tuning;
+pub mod handshake;
 pub mod peer_manager;
+pub mod peerstore;
+pub mod sync_manager;
+pub mod tx_relay;
+pub mod wire;
 
 pub use behaviour::{AxiomHybridBehaviour, AxiomBehaviour, AxiomEvent, node_identity_512};
 pub use config::NetworkConfig;
 pub use discv5_service::Discv5Service;
-pub use event_handler::EventHandler;
+pub use diversity::PeerDiversity;
+pub use event_handler::{EventHandler, PeerAction};
+pub use geoip::PeerGeo;
 pub use gossip_handler::{GossipHandler, GossipMessage};
 pub use peer_manager::{PeerManager, PeerInfo};
+pub use peerstore::PeerStore;
+pub use sync_manager::{SyncEvent, SyncManager};
+pub use tx_relay::{TxId, TxRelay};
 
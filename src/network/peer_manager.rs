@@ -1,7 +1,219 @@
 use libp2p::PeerId;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
+use crate::network::config::{RateLimitConfig, TopicQuota};
+
+/// Round-trip samples kept per peer.
+pub const MAX_RTT_SAMPLES: usize = 32;
+
+/// Application-level ping statistics for one peer.
+#[derive(Debug, Clone, Default)]
+pub struct PeerMetrics {
+    /// Most recent round-trip times, oldest first.
+    rtt_samples: VecDeque<Duration>,
+    pub pings_sent: u64,
+    pub ping_failures: u64,
+}
+
+impl PeerMetrics {
+    pub fn record_ping_sent(&mut self) {
+        self.pings_sent += 1;
+    }
+
+    pub fn record_rtt(&mut self, rtt: Duration) {
+        if self.rtt_samples.len() == MAX_RTT_SAMPLES {
+            self.rtt_samples.pop_front();
+        }
+        self.rtt_samples.push_back(rtt);
+    }
+
+    pub fn record_ping_failure(&mut self) {
+        self.ping_failures += 1;
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.rtt_samples.len()
+    }
+
+    /// RTT at percentile `p` (0.0-1.0) of the retained samples.
+    pub fn rtt_percentile(&self, p: f64) -> Option<Duration> {
+        if self.rtt_samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.rtt_samples.iter().copied().collect();
+        sorted.sort();
+        let idx = ((sorted.len() - 1) as f64 * p.clamp(0.0, 1.0)).round() as usize;
+        Some(sorted[idx])
+    }
+
+    pub fn median_rtt(&self) -> Option<Duration> {
+        self.rtt_percentile(0.5)
+    }
+
+    pub fn p90_rtt(&self) -> Option<Duration> {
+        self.rtt_percentile(0.9)
+    }
+
+    /// Fraction of pings that failed or timed out.
+    pub fn loss_rate(&self) -> f64 {
+        if self.pings_sent == 0 {
+            0.0
+        } else {
+            self.ping_failures as f64 / self.pings_sent as f64
+        }
+    }
+}
+
+/// Pick the candidate with the lowest median RTT. Peers without samples
+/// rank after every measured peer, so a fresh connection is still chosen
+/// when nothing better is known.
+pub fn fastest_peer<'a>(
+    candidates: impl IntoIterator<Item = &'a PeerId>,
+    metrics: &HashMap<PeerId, PeerMetrics>,
+) -> Option<PeerId> {
+    candidates
+        .into_iter()
+        .min_by_key(|peer| {
+            metrics
+                .get(*peer)
+                .and_then(|m| m.median_rtt())
+                .unwrap_or(Duration::MAX)
+        })
+        .copied()
+}
+
+/// Continuously refilling allowance of `capacity` units per minute.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn per_minute(capacity: u64, now: Instant) -> Self {
+        let capacity = capacity as f64;
+        Self { capacity, tokens: capacity, refill_per_sec: capacity / 60.0, last_refill: now }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    pub fn available(&mut self, now: Instant) -> f64 {
+        self.refill(now);
+        self.tokens
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.tokens -= amount;
+    }
+}
+
+/// Per-peer traffic counters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BandwidthStats {
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub messages_in: u64,
+    pub messages_out: u64,
+    /// Inbound messages dropped for exceeding a quota.
+    pub dropped_messages: u64,
+    pub dropped_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitVerdict {
+    Allowed,
+    MessageQuotaExceeded,
+    ByteQuotaExceeded,
+}
+
+/// Token-bucket rate limiting and byte accounting per (peer, topic).
+#[derive(Debug)]
+pub struct PeerRateLimiter {
+    config: RateLimitConfig,
+    buckets: HashMap<(PeerId, String), (TokenBucket, TokenBucket)>,
+    stats: HashMap<PeerId, BandwidthStats>,
+}
+
+impl Default for PeerRateLimiter {
+    fn default() -> Self {
+        Self::new(RateLimitConfig::default())
+    }
+}
+
+impl PeerRateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config, buckets: HashMap::new(), stats: HashMap::new() }
+    }
+
+    pub fn quota_for(&self, topic: &str) -> TopicQuota {
+        self.config.quota_for(topic)
+    }
+
+    /// Charge an inbound message of `bytes` on `topic` to `peer`. Nothing is
+    /// deducted from the buckets when the message is refused.
+    pub fn check_inbound(&mut self, peer: PeerId, topic: &str, bytes: usize) -> RateLimitVerdict {
+        self.check_inbound_at(peer, topic, bytes, Instant::now())
+    }
+
+    pub fn check_inbound_at(&mut self, peer: PeerId, topic: &str, bytes: usize, now: Instant) -> RateLimitVerdict {
+        let quota = self.config.quota_for(topic);
+        let (messages, volume) = self.buckets.entry((peer, topic.to_string())).or_insert_with(|| {
+            (
+                TokenBucket::per_minute(quota.messages_per_minute as u64, now),
+                TokenBucket::per_minute(quota.bytes_per_minute, now),
+            )
+        });
+
+        let verdict = if messages.available(now) < 1.0 {
+            RateLimitVerdict::MessageQuotaExceeded
+        } else if volume.available(now) < bytes as f64 {
+            RateLimitVerdict::ByteQuotaExceeded
+        } else {
+            messages.take(1.0);
+            volume.take(bytes as f64);
+            RateLimitVerdict::Allowed
+        };
+
+        let stats = self.stats.entry(peer).or_default();
+        if verdict == RateLimitVerdict::Allowed {
+            stats.messages_in += 1;
+            stats.bytes_in += bytes as u64;
+        } else {
+            stats.dropped_messages += 1;
+            stats.dropped_bytes += bytes as u64;
+        }
+        verdict
+    }
+
+    pub fn record_outbound(&mut self, peer: PeerId, bytes: usize) {
+        let stats = self.stats.entry(peer).or_default();
+        stats.messages_out += 1;
+        stats.bytes_out += bytes as u64;
+    }
+
+    pub fn stats(&self, peer: &PeerId) -> Option<&BandwidthStats> {
+        self.stats.get(peer)
+    }
+
+    pub fn all_stats(&self) -> impl Iterator<Item = (&PeerId, &BandwidthStats)> {
+        self.stats.iter()
+    }
+
+    /// Drop buckets and counters for a disconnected peer.
+    pub fn remove_peer(&mut self, peer: &PeerId) {
+        self.buckets.retain(|(p, _), _| p != peer);
+        self.stats.remove(peer);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PeerInfo {
     pub peer_id: PeerId,
@@ -10,6 +222,7 @@ pub struct PeerInfo {
     pub reputation: i32,
     pub messages_received: u64,
     pub messages_sent: u64,
+    pub metrics: PeerMetrics,
 }
 
 impl PeerInfo {
@@ -22,6 +235,7 @@ impl PeerInfo {
             reputation: 100,
             messages_received: 0,
             messages_sent: 0,
+            metrics: PeerMetrics::default(),
         }
     }
     
@@ -51,6 +265,7 @@ pub struct PeerManager {
     max_peers: usize,
     banned_peers: HashMap<PeerId, Instant>,
     ban_duration: Duration,
+    rate_limiter: PeerRateLimiter,
 }
 
 impl PeerManager {
@@ -60,6 +275,14 @@ impl PeerManager {
             max_peers,
             banned_peers: HashMap::new(),
             ban_duration: Duration::from_secs(3600),
+            rate_limiter: PeerRateLimiter::default(),
+        }
+    }
+    
+    pub fn with_rate_limits(max_peers: usize, config: RateLimitConfig) -> Self {
+        Self {
+            rate_limiter: PeerRateLimiter::new(config),
+            ..Self::new(max_peers)
         }
     }
     
@@ -81,9 +304,19 @@ impl PeerManager {
     }
     
     pub fn remove_peer(&mut self, peer_id: &PeerId) -> Option<PeerInfo> {
+        self.rate_limiter.remove_peer(peer_id);
         self.peers.remove(peer_id)
     }
     
+    /// Apply the inbound quota for `topic` to a message from `peer`.
+    pub fn check_inbound(&mut self, peer_id: PeerId, topic: &str, bytes: usize) -> RateLimitVerdict {
+        self.rate_limiter.check_inbound(peer_id, topic, bytes)
+    }
+    
+    pub fn bandwidth_stats(&self, peer_id: &PeerId) -> Option<&BandwidthStats> {
+        self.rate_limiter.stats(peer_id)
+    }
+    
     pub fn get_peer(&self, peer_id: &PeerId) -> Option<&PeerInfo> {
         self.peers.get(peer_id)
     }
@@ -129,9 +362,101 @@ impl PeerManager {
         }
     }
     
+    pub fn record_rtt(&mut self, peer_id: &PeerId, rtt: Duration) {
+        if let Some(peer) = self.get_peer_mut(peer_id) {
+            peer.metrics.record_rtt(rtt);
+            peer.update_last_seen();
+        }
+    }
+    
+    /// Connected peers ordered by median RTT, unmeasured peers last.
+    pub fn peers_by_latency(&self) -> Vec<PeerId> {
+        let mut peers: Vec<&PeerInfo> = self.peers.values().collect();
+        peers.sort_by_key(|p| p.metrics.median_rtt().unwrap_or(Duration::MAX));
+        peers.into_iter().map(|p| p.peer_id).collect()
+    }
+    
     pub fn record_message_received(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.get_peer_mut(peer_id) {
             peer.messages_received += 1;
         }
     }
+
+    /// Lower a peer's reputation for misbehaviour, banning it once the
+    /// reputation reaches zero. Returns `true` if the peer was banned.
+    pub fn penalize_peer(&mut self, peer_id: &PeerId, amount: i32) -> bool {
+        let exhausted = match self.get_peer_mut(peer_id) {
+            Some(peer) => {
+                peer.decrement_reputation(amount);
+                peer.reputation == 0
+            }
+            None => false,
+        };
+        if exhausted {
+            self.ban_peer(*peer_id);
+        }
+        exhausted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtt_percentiles_and_window() {
+        let mut m = PeerMetrics::default();
+        assert_eq!(m.median_rtt(), None);
+        for ms in [40, 10, 30, 20, 500] {
+            m.record_rtt(Duration::from_millis(ms));
+        }
+        assert_eq!(m.median_rtt(), Some(Duration::from_millis(30)));
+        assert_eq!(m.p90_rtt(), Some(Duration::from_millis(500)));
+
+        for _ in 0..MAX_RTT_SAMPLES {
+            m.record_rtt(Duration::from_millis(5));
+        }
+        assert_eq!(m.sample_count(), MAX_RTT_SAMPLES);
+        assert_eq!(m.p90_rtt(), Some(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn test_token_bucket_limits_messages_and_bytes() {
+        let mut config = RateLimitConfig::default();
+        config.topics.insert("t".into(), TopicQuota { messages_per_minute: 2, bytes_per_minute: 1_000 });
+        let mut limiter = PeerRateLimiter::new(config);
+        let peer = PeerId::random();
+        let start = Instant::now();
+
+        assert_eq!(limiter.check_inbound_at(peer, "t", 100, start), RateLimitVerdict::Allowed);
+        assert_eq!(limiter.check_inbound_at(peer, "t", 2_000, start), RateLimitVerdict::ByteQuotaExceeded);
+        assert_eq!(limiter.check_inbound_at(peer, "t", 100, start), RateLimitVerdict::Allowed);
+        assert_eq!(limiter.check_inbound_at(peer, "t", 100, start), RateLimitVerdict::MessageQuotaExceeded);
+
+        // Another topic has its own bucket; half a minute refills one message.
+        assert_eq!(limiter.check_inbound_at(peer, "other", 100, start), RateLimitVerdict::Allowed);
+        let later = start + Duration::from_secs(30);
+        assert_eq!(limiter.check_inbound_at(peer, "t", 100, later), RateLimitVerdict::Allowed);
+
+        let stats = limiter.stats(&peer).unwrap();
+        assert_eq!(stats.messages_in, 4);
+        assert_eq!(stats.bytes_in, 400);
+        assert_eq!(stats.dropped_messages, 2);
+        assert_eq!(stats.dropped_bytes, 2_100);
+
+        limiter.remove_peer(&peer);
+        assert!(limiter.stats(&peer).is_none());
+    }
+
+    #[test]
+    fn test_fastest_peer_prefers_measured() {
+        let (slow, fast, unknown) = (PeerId::random(), PeerId::random(), PeerId::random());
+        let mut metrics = HashMap::new();
+        metrics.entry(slow).or_insert_with(PeerMetrics::default).record_rtt(Duration::from_millis(300));
+        metrics.entry(fast).or_insert_with(PeerMetrics::default).record_rtt(Duration::from_millis(20));
+
+        assert_eq!(fastest_peer([&unknown, &slow, &fast], &metrics), Some(fast));
+        assert_eq!(fastest_peer([&unknown], &metrics), Some(unknown));
+        assert_eq!(fastest_peer([], &metrics), None);
+    }
 }
@@ -0,0 +1,240 @@
+// src/network/peerstore.rs - Persistent peer address book
+//
+// Remembers every address we have successfully dialed, when we last saw
+// it and how often it has failed since. On startup the node redials the
+// remembered addresses straight away instead of rediscovering the network
+// from the bootstrap list, and addresses that keep failing are retried on
+// an exponential backoff instead of every retry tick.
+
+use libp2p::{Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PEERSTORE_PATH: &str = "axiom_peers.json";
+
+/// Maximum addresses kept on disk.
+pub const MAX_PEERSTORE_ENTRIES: usize = 1_000;
+
+/// Delay after the first failure; doubles with each further failure.
+pub const BASE_BACKOFF_SECS: u64 = 30;
+
+/// Upper bound on the retry delay.
+pub const MAX_BACKOFF_SECS: u64 = 3_600;
+
+/// Addresses that never connected are forgotten after this many failures.
+pub const MAX_FAILURES_WITHOUT_SUCCESS: u32 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressRecord {
+    /// Peer last seen at this address, if known.
+    pub peer_id: Option<String>,
+    /// Unix time of the last successful connection.
+    pub last_seen: Option<u64>,
+    /// Consecutive failed dials since the last success.
+    pub failures: u32,
+    /// Unix time before which the address should not be redialed.
+    pub next_attempt: u64,
+}
+
+/// Exponential backoff for an address that has failed `failures` times in a row.
+pub fn backoff_secs(failures: u32) -> u64 {
+    if failures == 0 {
+        return 0;
+    }
+    BASE_BACKOFF_SECS
+        .saturating_mul(1u64 << (failures - 1).min(16))
+        .min(MAX_BACKOFF_SECS)
+}
+
+#[derive(Debug, Default)]
+pub struct PeerStore {
+    path: PathBuf,
+    addrs: HashMap<String, AddressRecord>,
+    dirty: bool,
+}
+
+impl PeerStore {
+    /// Load the address book at `path`. A missing or unreadable file yields
+    /// an empty store; it will be recreated on the next save.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let addrs = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("PEERSTORE: {} is corrupt, starting empty: {}", path.display(), e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self { path, addrs, dirty: false }
+    }
+
+    /// Write the address book if it changed since the last save.
+    pub fn save(&mut self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(&self.addrs)?;
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.addrs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addrs.is_empty()
+    }
+
+    pub fn get(&self, addr: &Multiaddr) -> Option<&AddressRecord> {
+        self.addrs.get(&addr.to_string())
+    }
+
+    /// Record a successful connection to `addr`, clearing its backoff.
+    pub fn record_success(&mut self, addr: &Multiaddr, peer_id: PeerId, now: u64) {
+        let record = self.addrs.entry(addr.to_string()).or_insert(AddressRecord {
+            peer_id: None,
+            last_seen: None,
+            failures: 0,
+            next_attempt: 0,
+        });
+        record.peer_id = Some(peer_id.to_string());
+        record.last_seen = Some(now);
+        record.failures = 0;
+        record.next_attempt = 0;
+        self.dirty = true;
+        self.prune();
+    }
+
+    /// Record a failed dial. Unknown addresses are tracked too, so that a
+    /// dead bootstrap node backs off like any other.
+    pub fn record_failure(&mut self, addr: &Multiaddr, now: u64) {
+        let record = self.addrs.entry(addr.to_string()).or_insert(AddressRecord {
+            peer_id: None,
+            last_seen: None,
+            failures: 0,
+            next_attempt: 0,
+        });
+        record.failures = record.failures.saturating_add(1);
+        record.next_attempt = now.saturating_add(backoff_secs(record.failures));
+        self.dirty = true;
+        self.prune();
+    }
+
+    /// Whether `addr` may be dialed now. Addresses we know nothing about
+    /// are always ready.
+    pub fn is_ready(&self, addr: &Multiaddr, now: u64) -> bool {
+        self.get(addr).is_none_or(|r| r.next_attempt <= now)
+    }
+
+    /// Addresses at which `peer` was last reached.
+    pub fn addrs_of(&self, peer: &PeerId) -> Vec<Multiaddr> {
+        let peer = peer.to_string();
+        self.addrs
+            .iter()
+            .filter(|(_, r)| r.peer_id.as_deref() == Some(peer.as_str()))
+            .filter_map(|(addr, _)| addr.parse().ok())
+            .collect()
+    }
+
+    /// Known-good addresses ready to dial, most recently seen first.
+    pub fn dialable(&self, now: u64) -> Vec<Multiaddr> {
+        let mut ready: Vec<(&String, &AddressRecord)> = self
+            .addrs
+            .iter()
+            .filter(|(_, r)| r.last_seen.is_some() && r.next_attempt <= now)
+            .collect();
+        ready.sort_by_key(|(_, r)| std::cmp::Reverse(r.last_seen));
+        ready
+            .into_iter()
+            .filter_map(|(addr, _)| addr.parse().ok())
+            .collect()
+    }
+
+    /// Drop addresses that never worked and keep failing, then cap the
+    /// store at `MAX_PEERSTORE_ENTRIES` by evicting the stalest entries.
+    fn prune(&mut self) {
+        self.addrs
+            .retain(|_, r| r.last_seen.is_some() || r.failures < MAX_FAILURES_WITHOUT_SUCCESS);
+        if self.addrs.len() > MAX_PEERSTORE_ENTRIES {
+            let mut by_age: Vec<(String, Option<u64>)> = self
+                .addrs
+                .iter()
+                .map(|(a, r)| (a.clone(), r.last_seen))
+                .collect();
+            by_age.sort_by_key(|(_, seen)| *seen);
+            let excess = self.addrs.len() - MAX_PEERSTORE_ENTRIES;
+            for (addr, _) in by_age.into_iter().take(excess) {
+                self.addrs.remove(&addr);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(port: u16) -> Multiaddr {
+        format!("/ip4/203.0.113.7/tcp/{}", port).parse().unwrap()
+    }
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        assert_eq!(backoff_secs(0), 0);
+        assert_eq!(backoff_secs(1), 30);
+        assert_eq!(backoff_secs(2), 60);
+        assert_eq!(backoff_secs(4), 240);
+        assert_eq!(backoff_secs(20), MAX_BACKOFF_SECS);
+    }
+
+    #[test]
+    fn test_failing_address_backs_off_until_success() {
+        let mut store = PeerStore::default();
+        let a = addr(6000);
+        store.record_success(&a, PeerId::random(), 1_000);
+        assert_eq!(store.dialable(1_000), vec![a.clone()]);
+
+        store.record_failure(&a, 2_000);
+        store.record_failure(&a, 2_000);
+        assert!(!store.is_ready(&a, 2_059));
+        assert!(store.is_ready(&a, 2_060));
+        assert!(store.dialable(2_000).is_empty());
+
+        store.record_success(&a, PeerId::random(), 2_100);
+        assert_eq!(store.get(&a).unwrap().failures, 0);
+        assert!(store.is_ready(&a, 2_100));
+    }
+
+    #[test]
+    fn test_never_connected_address_is_forgotten() {
+        let mut store = PeerStore::default();
+        let a = addr(6001);
+        for _ in 0..MAX_FAILURES_WITHOUT_SUCCESS {
+            store.record_failure(&a, 0);
+        }
+        assert!(store.get(&a).is_none());
+    }
+
+    #[test]
+    fn test_save_and_reload() {
+        let path = std::env::temp_dir().join(format!("axiom_test_peers_{}.json", std::process::id()));
+        let a = addr(6002);
+        let b = addr(6003);
+        {
+            let mut store = PeerStore::load(&path);
+            store.record_success(&a, PeerId::random(), 10);
+            store.record_success(&b, PeerId::random(), 20);
+            store.save().unwrap();
+        }
+        let reloaded = PeerStore::load(&path);
+        let _ = fs::remove_file(&path);
+        assert_eq!(reloaded.dialable(30), vec![b, a]);
+    }
+}
@@ -0,0 +1,186 @@
+// src/network/sync_manager.rs - Block sync state machine
+//
+// Tracks the best chain height the network has shown us (from real-time
+// pulses and sync response tips) against our own, and reports the
+// transitions as `SyncEvent`s on a channel. While syncing, the node does
+// not mine: a block built on a stale tip only becomes an orphan.
+//
+// Heights claimed by peers are unauthenticated. If a claimed height stops
+// being reachable (no local progress for `SYNC_STALL_TIMEOUT`), the claim
+// is dropped so a lying peer cannot keep mining switched off.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+/// How far behind the network we must fall before sync starts. A single
+/// block of lag is normal while a new block is still propagating.
+pub const SYNC_START_LAG: u64 = 2;
+
+/// Longest time without local progress before the target is abandoned.
+pub const SYNC_STALL_TIMEOUT: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum SyncEvent {
+    Started { local_height: u64, target_height: u64 },
+    Progress { local_height: u64, target_height: u64, downloaded: u64 },
+    Completed { height: u64, downloaded: u64, elapsed_secs: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncState {
+    Synced,
+    Syncing { started_at: Instant, start_height: u64, last_progress: Instant },
+}
+
+#[derive(Debug)]
+pub struct SyncManager {
+    state: SyncState,
+    local_height: u64,
+    network_height: u64,
+    events: mpsc::UnboundedSender<SyncEvent>,
+}
+
+impl SyncManager {
+    /// A manager for a node whose chain is `local_height` blocks long,
+    /// plus the receiving end of its event channel.
+    pub fn new(local_height: u64) -> (Self, mpsc::UnboundedReceiver<SyncEvent>) {
+        let (events, rx) = mpsc::unbounded_channel();
+        let manager = Self { state: SyncState::Synced, local_height, network_height: local_height, events };
+        (manager, rx)
+    }
+
+    pub fn is_syncing(&self) -> bool {
+        matches!(self.state, SyncState::Syncing { .. })
+    }
+
+    pub fn local_height(&self) -> u64 {
+        self.local_height
+    }
+
+    /// Best height seen on the network (never below our own).
+    pub fn network_height(&self) -> u64 {
+        self.network_height.max(self.local_height)
+    }
+
+    /// Fraction of the current sync done, `1.0` when synced.
+    pub fn progress(&self) -> f64 {
+        match self.state {
+            SyncState::Synced => 1.0,
+            SyncState::Syncing { start_height, .. } => {
+                let total = self.network_height.saturating_sub(start_height).max(1);
+                let done = self.local_height.saturating_sub(start_height);
+                (done as f64 / total as f64).min(1.0)
+            }
+        }
+    }
+
+    /// A peer or pulse reported a chain of `height` blocks.
+    pub fn observe_network_height(&mut self, height: u64, now: Instant) {
+        if height <= self.network_height {
+            return;
+        }
+        self.network_height = height;
+        if !self.is_syncing() && height >= self.local_height + SYNC_START_LAG {
+            self.state = SyncState::Syncing { started_at: now, start_height: self.local_height, last_progress: now };
+            self.emit(SyncEvent::Started { local_height: self.local_height, target_height: height });
+        }
+    }
+
+    /// Our chain is now `height` blocks long (after connecting blocks or a reorg).
+    pub fn local_height_changed(&mut self, height: u64, now: Instant) {
+        let advanced = height > self.local_height;
+        self.local_height = height;
+        let SyncState::Syncing { start_height, ref mut last_progress, .. } = self.state else {
+            return;
+        };
+        if advanced {
+            *last_progress = now;
+        }
+        if height >= self.network_height {
+            self.complete(now);
+        } else if advanced {
+            self.emit(SyncEvent::Progress {
+                local_height: height,
+                target_height: self.network_height,
+                downloaded: height.saturating_sub(start_height),
+            });
+        }
+    }
+
+    /// Abandon a target nobody has delivered blocks towards for
+    /// `SYNC_STALL_TIMEOUT`. Call periodically.
+    pub fn check_stalled(&mut self, now: Instant) -> bool {
+        let SyncState::Syncing { last_progress, .. } = self.state else {
+            return false;
+        };
+        if now.duration_since(last_progress) < SYNC_STALL_TIMEOUT {
+            return false;
+        }
+        self.network_height = self.local_height;
+        self.complete(now);
+        true
+    }
+
+    fn complete(&mut self, now: Instant) {
+        if let SyncState::Syncing { started_at, start_height, .. } = self.state {
+            self.state = SyncState::Synced;
+            self.emit(SyncEvent::Completed {
+                height: self.local_height,
+                downloaded: self.local_height.saturating_sub(start_height),
+                elapsed_secs: now.duration_since(started_at).as_secs(),
+            });
+        }
+    }
+
+    fn emit(&self, event: SyncEvent) {
+        // The receiver only goes away at shutdown.
+        let _ = self.events.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(rx: &mut mpsc::UnboundedReceiver<SyncEvent>) -> Vec<SyncEvent> {
+        std::iter::from_fn(|| rx.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn test_started_progress_completed() {
+        let now = Instant::now();
+        let (mut sync, mut rx) = SyncManager::new(10);
+
+        // One block of lag is propagation, not a sync.
+        sync.observe_network_height(11, now);
+        assert!(!sync.is_syncing());
+
+        sync.observe_network_height(110, now);
+        assert!(sync.is_syncing());
+        sync.local_height_changed(60, now);
+        assert_eq!(sync.progress(), 0.5);
+        sync.local_height_changed(110, now + Duration::from_secs(5));
+        assert!(!sync.is_syncing());
+
+        assert_eq!(drain(&mut rx), vec![
+            SyncEvent::Started { local_height: 10, target_height: 110 },
+            SyncEvent::Progress { local_height: 60, target_height: 110, downloaded: 50 },
+            SyncEvent::Completed { height: 110, downloaded: 100, elapsed_secs: 5 },
+        ]);
+    }
+
+    #[test]
+    fn test_unreachable_target_is_abandoned() {
+        let now = Instant::now();
+        let (mut sync, mut rx) = SyncManager::new(10);
+        sync.observe_network_height(1_000_000, now);
+        assert!(!sync.check_stalled(now + Duration::from_secs(60)));
+        assert!(sync.check_stalled(now + SYNC_STALL_TIMEOUT));
+        assert!(!sync.is_syncing());
+        assert_eq!(sync.network_height(), 10);
+        assert_eq!(drain(&mut rx).len(), 2);
+    }
+}
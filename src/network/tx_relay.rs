@@ -0,0 +1,229 @@
+// src/network/tx_relay.rs - Announce/pull transaction relay
+//
+// Flooding every transaction body to every mesh peer means each node
+// receives each body several times over. Instead, nodes gossip batches of
+// 64-byte transaction IDs on `TX_ANNOUNCE_TOPIC` and fetch only the bodies
+// they have not seen, directly from one announcer, over the chain-sync
+// request-response protocol. A bounded, time-limited seen filter keeps
+// repeated announcements from turning into repeated downloads.
+
+use libp2p::PeerId;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::transaction::Transaction;
+use crate::types::Hash512;
+
+pub const TX_ANNOUNCE_TOPIC: &str = "timechain-tx-announce";
+
+/// IDs per announcement message and per body request.
+pub const MAX_TX_IDS_PER_MESSAGE: usize = 256;
+
+/// How long a body request may stay unanswered before another announcer
+/// is asked.
+pub const TX_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// IDs remembered by the seen filter.
+pub const SEEN_FILTER_CAPACITY: usize = 50_000;
+
+/// How long a seen ID suppresses further downloads.
+pub const SEEN_FILTER_TTL: Duration = Duration::from_secs(600);
+
+/// 512-bit announcement ID: BLAKE3-512 of the bincode-encoded transaction.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TxId(pub Hash512);
+
+impl TxId {
+    pub fn of(tx: &Transaction) -> Self {
+        TxId(tx.hash_512())
+    }
+}
+
+impl std::fmt::Debug for TxId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TxId({}…)", hex::encode(&self.0.as_bytes()[..8]))
+    }
+}
+
+/// Payload of a `TX_ANNOUNCE_TOPIC` message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxAnnouncement {
+    pub ids: Vec<TxId>,
+}
+
+/// Recently seen IDs, evicted oldest-first by age or when full.
+#[derive(Debug, Default)]
+pub struct SeenFilter {
+    seen: HashSet<TxId>,
+    order: VecDeque<(TxId, Instant)>,
+}
+
+impl SeenFilter {
+    /// Record `id`; returns `true` if it was not already present.
+    pub fn insert(&mut self, id: TxId, now: Instant) -> bool {
+        self.expire(now);
+        if !self.seen.insert(id) {
+            return false;
+        }
+        self.order.push_back((id, now));
+        if self.order.len() > SEEN_FILTER_CAPACITY {
+            if let Some((old, _)) = self.order.pop_front() {
+                self.seen.remove(&old);
+            }
+        }
+        true
+    }
+
+    pub fn contains(&self, id: &TxId) -> bool {
+        self.seen.contains(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some((id, at)) = self.order.front() {
+            if now.duration_since(*at) < SEEN_FILTER_TTL {
+                break;
+            }
+            self.seen.remove(id);
+            self.order.pop_front();
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct TxRelay {
+    /// IDs we hold (or held) a body for.
+    seen: SeenFilter,
+    /// Bodies requested but not yet received.
+    in_flight: HashMap<TxId, (PeerId, Instant)>,
+    /// Newly accepted IDs waiting for the next announcement batch.
+    outgoing: Vec<TxId>,
+}
+
+impl TxRelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note a transaction accepted into the mempool and queue its
+    /// announcement. Returns `false` if it was already known.
+    pub fn accepted(&mut self, tx: &Transaction, now: Instant) -> bool {
+        let id = TxId::of(tx);
+        self.in_flight.remove(&id);
+        if !self.seen.insert(id, now) {
+            return false;
+        }
+        self.outgoing.push(id);
+        true
+    }
+
+    /// Handle an announcement from `peer` and return the IDs to fetch from
+    /// it: those neither seen nor already being fetched.
+    pub fn on_announcement(&mut self, peer: PeerId, ids: &[TxId], now: Instant) -> Vec<TxId> {
+        let wanted: Vec<TxId> = ids
+            .iter()
+            .take(MAX_TX_IDS_PER_MESSAGE)
+            .filter(|id| !self.seen.contains(id) && !self.in_flight.contains_key(id))
+            .copied()
+            .collect();
+        for id in &wanted {
+            self.in_flight.insert(*id, (peer, now));
+        }
+        wanted
+    }
+
+    /// Whether `tx` answers one of our requests to `peer`. Unsolicited
+    /// bodies are dropped by the caller.
+    pub fn was_requested(&self, peer: &PeerId, tx: &Transaction) -> bool {
+        self.in_flight.get(&TxId::of(tx)).is_some_and(|(p, _)| p == peer)
+    }
+
+    /// Forget requests that timed out so the next announcer is asked.
+    pub fn expire_requests(&mut self, now: Instant) -> usize {
+        let before = self.in_flight.len();
+        self.in_flight
+            .retain(|_, (_, asked)| now.duration_since(*asked) < TX_REQUEST_TIMEOUT);
+        before - self.in_flight.len()
+    }
+
+    /// Forget requests outstanding to a disconnected peer.
+    pub fn peer_disconnected(&mut self, peer: &PeerId) {
+        self.in_flight.retain(|_, (p, _)| p != peer);
+    }
+
+    /// Drain queued IDs into announcement batches.
+    pub fn take_announcements(&mut self) -> Vec<TxAnnouncement> {
+        std::mem::take(&mut self.outgoing)
+            .chunks(MAX_TX_IDS_PER_MESSAGE)
+            .map(|ids| TxAnnouncement { ids: ids.to_vec() })
+            .collect()
+    }
+}
+
+/// Bodies for the requested IDs that are in `mempool`.
+pub fn serve_tx_request<'a>(mempool: impl IntoIterator<Item = &'a Transaction>, ids: &[TxId]) -> Vec<Transaction> {
+    let wanted: HashSet<&TxId> = ids.iter().take(MAX_TX_IDS_PER_MESSAGE).collect();
+    mempool
+        .into_iter()
+        .filter(|tx| wanted.contains(&TxId::of(tx)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(nonce: u64) -> Transaction {
+        Transaction { from: [1u8; 32], to: [2u8; 32], amount: 10, fee: 1, nonce, expiry_height: 0, zk_proof: vec![], signature: vec![] }
+    }
+
+    #[test]
+    fn test_announce_pull_dedup() {
+        let now = Instant::now();
+        let (alice, bob) = (PeerId::random(), PeerId::random());
+        let mut relay = TxRelay::new();
+        let (a, b) = (TxId::of(&tx(0)), TxId::of(&tx(1)));
+
+        assert_eq!(relay.on_announcement(alice, &[a, b], now), vec![a, b]);
+        // Bob announces the same IDs while Alice's bodies are in flight.
+        assert!(relay.on_announcement(bob, &[a, b], now).is_empty());
+        assert!(relay.was_requested(&alice, &tx(0)));
+        assert!(!relay.was_requested(&bob, &tx(0)));
+
+        assert!(relay.accepted(&tx(0), now));
+        assert!(!relay.accepted(&tx(0), now));
+        // B timed out at Alice, so Bob is asked for it; A is already known.
+        assert_eq!(relay.expire_requests(now + TX_REQUEST_TIMEOUT), 1);
+        assert_eq!(relay.on_announcement(bob, &[a, b], now + TX_REQUEST_TIMEOUT), vec![b]);
+
+        let batches = relay.take_announcements();
+        assert_eq!(batches, vec![TxAnnouncement { ids: vec![a] }]);
+        assert!(relay.take_announcements().is_empty());
+    }
+
+    #[test]
+    fn test_seen_filter_expires() {
+        let now = Instant::now();
+        let mut seen = SeenFilter::default();
+        let id = TxId::of(&tx(0));
+        assert!(seen.insert(id, now));
+        assert!(!seen.insert(id, now + Duration::from_secs(1)));
+        assert!(seen.insert(id, now + SEEN_FILTER_TTL));
+    }
+
+    #[test]
+    fn test_serve_tx_request_returns_only_known() {
+        let mempool = [tx(0), tx(1)];
+        let served = serve_tx_request(&mempool, &[TxId::of(&tx(1)), TxId::of(&tx(7))]);
+        assert_eq!(served, vec![tx(1)]);
+    }
+}
@@ -0,0 +1,255 @@
+// src/network/wire.rs - Versioned envelopes for blocks, transactions and pulses
+//
+// Gossip used to carry these as bare bincode. Bincode is positional, so a
+// field added to `Block` made every older node fail to decode every block
+// (`#[serde(default)]` does not help it), and an upgrade had to be a flag
+// day. Each message now travels in an envelope:
+//
+//   magic (1) | version (1) | payload length (u32 LE) | payload | extensions
+//
+// The payload is the type's version 1 encoding (`WireMessage::encode_payload`,
+// bincode of the type as it was then), frozen: `test_payloads_are_frozen`
+// fails if a field is added to a type's serde encoding. A later wire
+// version that adds fields keeps them out of it (`#[serde(skip)]`) and
+// writes them with `encode_extensions` instead, which older nodes skip:
+// they decode the payload and go on. A version with the high bit set
+// (`BREAKING_VERSION`) changes the payload itself; a node that does not
+// know it reports `WireError::UnsupportedVersion` instead of guessing, and
+// the sender is not charged for it, since the node that is out of date is
+// ours.
+//
+// Bare bincode from nodes that predate envelopes is still accepted.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::block::Block;
+use crate::transaction::Transaction;
+use crate::AxiomPulse;
+
+/// First byte of every envelope.
+pub const WIRE_MAGIC: u8 = 0xA7;
+
+/// The version this node writes.
+pub const WIRE_VERSION: u8 = 1;
+
+/// Set in versions whose payload older nodes cannot read.
+pub const BREAKING_VERSION: u8 = 0x80;
+
+const HEADER_LEN: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WireError {
+    #[error("encoding failed: {0}")]
+    Encode(String),
+    #[error("malformed message: {0}")]
+    Malformed(String),
+    #[error("wire version {0} is newer than this node understands (upgrade to read it)")]
+    UnsupportedVersion(u8),
+}
+
+/// Types sent in envelopes.
+pub trait WireMessage: Serialize + DeserializeOwned {
+    /// The version 1 payload: bincode of the type as version 1 defined it.
+    fn encode_payload(&self) -> bincode::Result<Vec<u8>> {
+        bincode::serialize(self)
+    }
+
+    fn decode_payload(payload: &[u8]) -> bincode::Result<Self> {
+        bincode::deserialize(payload)
+    }
+
+    /// Fields added after version 1, written after the payload.
+    fn encode_extensions(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    /// Read back the fields `encode_extensions` wrote, skipping any from
+    /// versions this node does not know.
+    fn read_extensions(&mut self, _extensions: &[u8]) -> Result<(), WireError> {
+        Ok(())
+    }
+}
+
+impl WireMessage for Block {}
+impl WireMessage for Transaction {}
+impl WireMessage for AxiomPulse {}
+
+/// An envelope split into its parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Envelope<'a> {
+    pub version: u8,
+    pub payload: &'a [u8],
+    /// Fields added by versions after the payload's; empty at ours.
+    pub extensions: &'a [u8],
+}
+
+impl<'a> Envelope<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, WireError> {
+        if bytes.len() < HEADER_LEN || bytes[0] != WIRE_MAGIC {
+            return Err(WireError::Malformed("no envelope header".to_string()));
+        }
+        let version = bytes[1];
+        if version == 0 {
+            return Err(WireError::Malformed("version 0".to_string()));
+        }
+        let len = u32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]) as usize;
+        let body = &bytes[HEADER_LEN..];
+        if len > body.len() {
+            return Err(WireError::Malformed(format!("payload of {} bytes, {} present", len, body.len())));
+        }
+        let (payload, extensions) = body.split_at(len);
+        Ok(Self { version, payload, extensions })
+    }
+
+    /// Decode the payload, skipping extensions this node does not know.
+    pub fn open<T: WireMessage>(&self) -> Result<T, WireError> {
+        if self.version > WIRE_VERSION && self.version & BREAKING_VERSION != 0 {
+            return Err(WireError::UnsupportedVersion(self.version));
+        }
+        let mut value = T::decode_payload(self.payload).map_err(|e| WireError::Malformed(e.to_string()))?;
+        value.read_extensions(self.extensions)?;
+        Ok(value)
+    }
+}
+
+/// Encode `value` in a current-version envelope.
+pub fn encode<T: WireMessage>(value: &T) -> Result<Vec<u8>, WireError> {
+    let payload = value.encode_payload().map_err(|e| WireError::Encode(e.to_string()))?;
+    let extensions = value.encode_extensions();
+    let len = u32::try_from(payload.len()).map_err(|_| WireError::Encode("payload over 4 GiB".to_string()))?;
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len() + extensions.len());
+    bytes.extend([WIRE_MAGIC, WIRE_VERSION]);
+    bytes.extend(len.to_le_bytes());
+    bytes.extend(payload);
+    bytes.extend(extensions);
+    Ok(bytes)
+}
+
+/// Decode an enveloped message, or bare bincode from an older node.
+pub fn decode<T: WireMessage>(bytes: &[u8]) -> Result<T, WireError> {
+    let enveloped = Envelope::parse(bytes).and_then(|envelope| envelope.open());
+    match enveloped {
+        Ok(value) => Ok(value),
+        // A newer node's envelope, not bare bincode that happens to parse.
+        Err(e @ WireError::UnsupportedVersion(_)) => Err(e),
+        // Bare bincode can start with the magic byte by chance.
+        Err(e) => T::decode_payload(bytes).map_err(|_| e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx() -> Transaction {
+        Transaction {
+            from: [1u8; 32],
+            to: [2u8; 32],
+            amount: 500,
+            fee: 10,
+            nonce: 3,
+            expiry_height: 0,
+            zk_proof: vec![9; 4],
+            signature: vec![7; 64],
+        }
+    }
+
+    fn envelope(version: u8, payload: &[u8], extensions: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![WIRE_MAGIC, version];
+        bytes.extend((payload.len() as u32).to_le_bytes());
+        bytes.extend(payload);
+        bytes.extend(extensions);
+        bytes
+    }
+
+    #[test]
+    fn test_envelopes_old_and_future_versions() {
+        let tx = tx();
+        let bytes = encode(&tx).unwrap();
+        assert_eq!(&bytes[..2], &[WIRE_MAGIC, WIRE_VERSION]);
+        assert_eq!(decode::<Transaction>(&bytes).unwrap(), tx);
+
+        // Nodes from before envelopes send bare bincode.
+        let bare = bincode::serialize(&tx).unwrap();
+        assert_eq!(decode::<Transaction>(&bare).unwrap(), tx);
+
+        // A newer additive version: its extra fields are skipped.
+        let newer = envelope(WIRE_VERSION + 1, &bare, b"fields added later");
+        assert_eq!(Envelope::parse(&newer).unwrap().extensions, b"fields added later");
+        assert_eq!(decode::<Transaction>(&newer).unwrap(), tx);
+
+        // A breaking version is refused as such, not misread.
+        let breaking = envelope(BREAKING_VERSION | 2, &bare, &[]);
+        assert_eq!(decode::<Transaction>(&breaking), Err(WireError::UnsupportedVersion(BREAKING_VERSION | 2)));
+
+        let mut truncated = bytes.clone();
+        truncated.truncate(bytes.len() - 1);
+        assert!(matches!(decode::<Transaction>(&truncated), Err(WireError::Malformed(_))));
+        assert!(decode::<Transaction>(&[WIRE_MAGIC]).is_err());
+    }
+
+    /// A block as a later version might send it, with a field version 1
+    /// does not have.
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct BlockV2 {
+        block: Block,
+        #[serde(skip)]
+        weight: u64,
+    }
+
+    impl WireMessage for BlockV2 {
+        fn encode_payload(&self) -> bincode::Result<Vec<u8>> {
+            self.block.encode_payload()
+        }
+
+        fn decode_payload(payload: &[u8]) -> bincode::Result<Self> {
+            Ok(Self { block: Block::decode_payload(payload)?, weight: 0 })
+        }
+
+        fn encode_extensions(&self) -> Vec<u8> {
+            self.weight.to_le_bytes().to_vec()
+        }
+
+        fn read_extensions(&mut self, extensions: &[u8]) -> Result<(), WireError> {
+            // Version 1 senders have no weight to send.
+            if let Some(weight) = extensions.get(..8) {
+                self.weight = u64::from_le_bytes(weight.try_into().unwrap_or_default());
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_newer_block_reads_on_older_nodes() {
+        let v2 = BlockV2 { block: Block::test([3u8; 32], 7), weight: 42 };
+        let bytes = envelope(WIRE_VERSION + 1, &v2.encode_payload().unwrap(), &v2.encode_extensions());
+        assert_eq!(decode::<Block>(&bytes).unwrap(), v2.block);
+        assert_eq!(decode::<BlockV2>(&bytes).unwrap(), v2);
+        assert_eq!(decode::<BlockV2>(&encode(&v2).unwrap()).unwrap(), v2);
+        // A version 1 block read by the newer node: no extensions to read.
+        let v1 = BlockV2 { weight: 0, ..v2 };
+        assert_eq!(decode::<BlockV2>(&encode(&v1.block).unwrap()).unwrap(), v1);
+    }
+
+    #[test]
+    fn test_payloads_are_frozen() {
+        // Adding a field to one of these types changes its payload and
+        // breaks older nodes: send it in the extensions instead.
+        let digest = |payload: Vec<u8>| hex::encode(<sha2::Sha256 as sha2::Digest>::digest(payload));
+        assert_eq!(digest(Block::test([3u8; 32], 7).encode_payload().unwrap()), "94c587885fb96ea47625dadc9e01be999e41d217293d8f49b225c61b8e13a8e9");
+        assert_eq!(digest(tx().encode_payload().unwrap()), "866718e213b1d7ace39694ee8b3a1164c079c028dc754856301a3f6fa0124deb");
+        let pulse = AxiomPulse {
+            height: 7,
+            total_mined: 350,
+            remaining: 1_000,
+            block_hash: crate::types::Hash512([3u8; 64]),
+            oracle_seal: crate::types::Hash512::ZERO,
+            prev_pulse_hash: crate::types::Hash512([1u8; 64]),
+            timestamp: 7,
+            stark_receipt: Some(vec![1, 2, 3]),
+            signature: vec![7; 64],
+        };
+        assert_eq!(digest(pulse.encode_payload().unwrap()), "5a316c268cad527ab304ecce7a7cfe2356da8f8e80004e6e6b9256c2e48c1fec");
+    }
+}
@@ -1,5 +1,8 @@
 use std::collections::HashSet;
-use libp2p::{gossipsub, mdns, identify, kad, swarm::{NetworkBehaviour, Swarm}, Multiaddr, StreamProtocol};
+use std::time::{Duration, Instant};
+use libp2p::{gossipsub, mdns, identify, kad, swarm::{NetworkBehaviour, Swarm}, Multiaddr, PeerId, StreamProtocol};
+use libp2p::{autonat, dcutr, relay, upnp};
+use libp2p::swarm::behaviour::toggle::Toggle;
 use log;
 use std::error::Error;
 use libp2p::identity;
@@ -8,6 +11,11 @@ use futures::io::{AsyncRead, AsyncWrite, AsyncReadExt, AsyncWriteExt};
 use std::io;
 use serde::{Serialize, Deserialize};
 use crate::block::Block;
+use crate::network::encoded_blocks::{blocks_response_len, write_blocks_response, EncodedBlock, EncodedBlockCache};
+use crate::network::tx_relay::TxId;
+use crate::transaction::Transaction;
+use crate::network::config::NetworkConfig;
+use crate::network::handshake::{identify_protocol_version, noise_config};
 
 /// External validator registry
 #[derive(Default)]
@@ -41,11 +49,194 @@ pub fn add_external_peer(swarm: &mut Swarm<TimechainBehaviour>, peer_addr: &str,
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct ChainRequest { pub start_height: u64 }
+// ---------------------------------------------------------------------------
+// Chain sync protocol
+// ---------------------------------------------------------------------------
+
+/// Maximum headers served in one `GetHeaders` response.
+pub const MAX_HEADERS_PER_REQUEST: u32 = 512;
+
+/// Maximum full blocks served in one `GetBlocks` response.
+pub const MAX_BLOCKS_PER_REQUEST: u32 = 64;
+
+/// How far below our own tip a header request starts, so that a peer on a
+/// short competing fork can be detected and the fork point located.
+pub const SYNC_LOOKBACK: u64 = 64;
+
+/// How long a `GetBlocks` request may go unanswered before we ask again.
+pub const BLOCK_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Upper bound on an encoded request/response read off the wire.
+const MAX_SYNC_MESSAGE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Lightweight per-block summary used to work out what is missing before
+/// any block bodies are transferred.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncHeader {
+    pub height: u64,
+    pub hash: [u8; 32],
+    pub parent: [u8; 32],
+    pub slot: u64,
+    pub timestamp: u64,
+    /// Merkle root of the block's transactions, for receipt verification.
+    /// Absent (zero) in headers from peers that predate it.
+    #[serde(default)]
+    pub tx_root: [u8; 32],
+}
 
+impl SyncHeader {
+    pub fn from_block(height: u64, block: &Block) -> Self {
+        Self {
+            height,
+            hash: block.legacy_hash(),
+            parent: block.parent,
+            slot: block.slot,
+            timestamp: block.timestamp,
+            tx_root: block.tx_root(),
+        }
+    }
+}
+
+/// Ranged sync request sent directly to a single peer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChainRequest {
+    /// Headers for heights `from..from + count`.
+    GetHeaders { from: u64, count: u32 },
+    /// Full blocks for heights `from..from + count`.
+    GetBlocks { from: u64, count: u32 },
+    /// Mempool bodies for announced transactions (see `network::tx_relay`).
+    GetTransactions { ids: Vec<TxId> },
+}
+
+/// Reply to a [`ChainRequest`]. Every response carries the responder's
+/// current chain height so the requester knows whether to keep paging.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct ChainResponse { pub blocks: Vec<Block> }
+pub enum ChainResponse {
+    Headers { tip_height: u64, headers: Vec<SyncHeader> },
+    Blocks { tip_height: u64, from: u64, blocks: Vec<Block> },
+    /// The requested transactions we still had; unknown IDs are omitted.
+    Transactions { txs: Vec<Transaction> },
+    /// How we serve `Blocks`: cached encodings written one by one (see
+    /// `network::encoded_blocks`). Goes on the wire as `Blocks`.
+    #[serde(skip)]
+    EncodedBlocks { tip_height: u64, from: u64, blocks: Vec<EncodedBlock> },
+}
+
+impl ChainResponse {
+    /// Bytes the response takes on the wire.
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            ChainResponse::EncodedBlocks { tip_height, from, blocks } => blocks_response_len(*tip_height, *from, blocks),
+            other => serde_json::to_vec(other).map_or(0, |b| b.len()),
+        }
+    }
+}
+
+/// Answer a sync request from the local chain, clamping the range to the
+/// per-request limits and to what we actually have. Blocks are served
+/// from `cache`. Transaction requests are answered from the mempool by
+/// `tx_relay::serve_tx_request`; here they get an empty reply.
+pub fn serve_chain_request(blocks: &[Block], request: &ChainRequest, cache: &mut EncodedBlockCache) -> ChainResponse {
+    let tip_height = blocks.len() as u64;
+    match *request {
+        ChainRequest::GetHeaders { from, count } => {
+            let range = clamp_range(blocks.len(), from, count.min(MAX_HEADERS_PER_REQUEST));
+            let headers = blocks[range.clone()]
+                .iter()
+                .zip(range)
+                .map(|(b, h)| SyncHeader::from_block(h as u64, b))
+                .collect();
+            ChainResponse::Headers { tip_height, headers }
+        }
+        ChainRequest::GetBlocks { from, count } => {
+            let range = clamp_range(blocks.len(), from, count.min(MAX_BLOCKS_PER_REQUEST));
+            let blocks = blocks[range].iter().map(|b| cache.encode(b)).collect();
+            ChainResponse::EncodedBlocks { tip_height, from, blocks }
+        }
+        ChainRequest::GetTransactions { .. } => ChainResponse::Transactions { txs: Vec::new() },
+    }
+}
+
+fn clamp_range(len: usize, from: u64, count: u32) -> std::ops::Range<usize> {
+    let start = usize::try_from(from).unwrap_or(usize::MAX).min(len);
+    let end = start.saturating_add(count as usize).min(len);
+    start..end
+}
+
+/// The `GetBlocks` request we are waiting on. Only the peer we asked, with
+/// the page we asked for, may answer it: the response's `from` is the fork
+/// point we roll back to, so it must be ours, not the peer's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockRequest {
+    pub peer: PeerId,
+    pub from: u64,
+    pub count: u32,
+    pub sent_at: Instant,
+}
+
+impl BlockRequest {
+    /// A full page of blocks from `from`.
+    pub fn new(peer: PeerId, from: u64, now: Instant) -> Self {
+        Self { peer, from, count: MAX_BLOCKS_PER_REQUEST, sent_at: now }
+    }
+
+    pub fn request(&self) -> ChainRequest {
+        ChainRequest::GetBlocks { from: self.from, count: self.count }
+    }
+
+    /// Unanswered for longer than [`BLOCK_REQUEST_TIMEOUT`].
+    pub fn expired(&self, now: Instant) -> bool {
+        now.duration_since(self.sent_at) >= BLOCK_REQUEST_TIMEOUT
+    }
+
+    /// Whether `peer`'s `Blocks` response of `len` blocks from `from`
+    /// answers this request.
+    pub fn answered_by(&self, peer: &PeerId, from: u64, len: usize) -> bool {
+        self.peer == *peer && self.from == from && len <= self.count as usize
+    }
+}
+
+/// Starting height for a header request given our local chain height.
+pub fn header_request_for(local_height: u64) -> ChainRequest {
+    ChainRequest::GetHeaders {
+        from: local_height.saturating_sub(SYNC_LOOKBACK),
+        count: MAX_HEADERS_PER_REQUEST,
+    }
+}
+
+/// Compare a peer's headers against our chain and return the first height
+/// whose block we are missing or disagree on.
+///
+/// Returns `None` when the headers contain nothing new, or when even the
+/// first header conflicts with our chain (the fork is deeper than the
+/// requested window and cannot be resolved from these headers alone).
+pub fn first_divergence(local: &[Block], headers: &[SyncHeader]) -> Option<u64> {
+    let mut expected_parent: Option<[u8; 32]> = None;
+    for (i, header) in headers.iter().enumerate() {
+        // Headers must be contiguous and internally linked.
+        if let Some(parent) = expected_parent {
+            if header.parent != parent {
+                return None;
+            }
+        }
+        expected_parent = Some(header.hash);
+
+        match local.get(header.height as usize) {
+            Some(block) if block.legacy_hash() == header.hash => continue,
+            Some(_) if i == 0 => return None,
+            _ => {
+                // The parent must be on our chain, otherwise we cannot
+                // attach the missing blocks.
+                let parent_ok = header.height > 0
+                    && local
+                        .get(header.height as usize - 1)
+                        .is_some_and(|b| b.legacy_hash() == header.parent);
+                return if parent_ok { Some(header.height) } else { None };
+            }
+        }
+    }
+    None
+}
 
 #[derive(Clone, Default)]
 pub struct ChainCodec;
@@ -59,7 +250,7 @@ impl request_response::Codec for ChainCodec {
     async fn read_request<T>(&mut self, _protocol: &Self::Protocol, io: &mut T) -> io::Result<Self::Request>
     where T: AsyncRead + Unpin + Send {
         let mut buf = Vec::new();
-        io.read_to_end(&mut buf).await?;
+        io.take(MAX_SYNC_MESSAGE_SIZE).read_to_end(&mut buf).await?;
         let req: ChainRequest = serde_json::from_slice(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
         Ok(req)
     }
@@ -67,7 +258,7 @@ impl request_response::Codec for ChainCodec {
     async fn read_response<T>(&mut self, _protocol: &Self::Protocol, io: &mut T) -> io::Result<Self::Response>
     where T: AsyncRead + Unpin + Send {
         let mut buf = Vec::new();
-        io.read_to_end(&mut buf).await?;
+        io.take(MAX_SYNC_MESSAGE_SIZE).read_to_end(&mut buf).await?;
         let resp: ChainResponse = serde_json::from_slice(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
         Ok(resp)
     }
@@ -82,6 +273,11 @@ impl request_response::Codec for ChainCodec {
 
     async fn write_response<T>(&mut self, _protocol: &Self::Protocol, io: &mut T, resp: Self::Response) -> io::Result<()>
     where T: AsyncWrite + Unpin + Send {
+        if let ChainResponse::EncodedBlocks { tip_height, from, blocks } = &resp {
+            write_blocks_response(io, *tip_height, *from, blocks).await?;
+            io.close().await.ok();
+            return Ok(());
+        }
         let bytes = serde_json::to_vec(&resp).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
         io.write_all(&bytes).await?;
         io.close().await.ok();
@@ -89,6 +285,67 @@ impl request_response::Codec for ChainCodec {
     }
 }
 
+/// Seconds between latency pings to each connected peer.
+pub const PING_INTERVAL_SECS: u64 = 30;
+
+/// A ping is counted as lost after this long.
+pub const PING_TIMEOUT_SECS: u64 = 10;
+
+/// Application-level latency probe. The nonce is echoed back so a late
+/// reply cannot be matched to the wrong request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ping {
+    pub nonce: u64,
+}
+
+/// Fixed 8-byte messages: pings cost almost nothing on the wire and are
+/// answered without touching chain state.
+#[derive(Clone, Default)]
+pub struct PingCodec;
+
+impl PingCodec {
+    async fn read_ping<T>(io: &mut T) -> io::Result<Ping>
+    where T: AsyncRead + Unpin + Send {
+        let mut buf = [0u8; 8];
+        io.read_exact(&mut buf).await?;
+        Ok(Ping { nonce: u64::from_be_bytes(buf) })
+    }
+
+    async fn write_ping<T>(io: &mut T, ping: Ping) -> io::Result<()>
+    where T: AsyncWrite + Unpin + Send {
+        io.write_all(&ping.nonce.to_be_bytes()).await?;
+        io.close().await.ok();
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl request_response::Codec for PingCodec {
+    type Protocol = StreamProtocol;
+    type Request = Ping;
+    type Response = Ping;
+
+    async fn read_request<T>(&mut self, _protocol: &Self::Protocol, io: &mut T) -> io::Result<Self::Request>
+    where T: AsyncRead + Unpin + Send {
+        Self::read_ping(io).await
+    }
+
+    async fn read_response<T>(&mut self, _protocol: &Self::Protocol, io: &mut T) -> io::Result<Self::Response>
+    where T: AsyncRead + Unpin + Send {
+        Self::read_ping(io).await
+    }
+
+    async fn write_request<T>(&mut self, _protocol: &Self::Protocol, io: &mut T, req: Self::Request) -> io::Result<()>
+    where T: AsyncWrite + Unpin + Send {
+        Self::write_ping(io, req).await
+    }
+
+    async fn write_response<T>(&mut self, _protocol: &Self::Protocol, io: &mut T, resp: Self::Response) -> io::Result<()>
+    where T: AsyncWrite + Unpin + Send {
+        Self::write_ping(io, resp).await
+    }
+}
+
 #[derive(NetworkBehaviour)]
 #[behaviour(to_swarm = "TimechainBehaviourEvent")]
 pub struct TimechainBehaviour {
@@ -96,7 +353,14 @@ pub struct TimechainBehaviour {
     pub mdns: mdns::tokio::Behaviour,
     pub identify: identify::Behaviour,
     pub request_response: request_response::Behaviour<ChainCodec>,
+    pub latency: request_response::Behaviour<PingCodec>,
     pub kademlia: kad::Behaviour<kad::store::MemoryStore>,
+    // NAT traversal (see network::config::NatConfig)
+    pub autonat: Toggle<autonat::Behaviour>,
+    pub upnp: Toggle<upnp::tokio::Behaviour>,
+    pub relay_client: relay::client::Behaviour,
+    pub relay: Toggle<relay::Behaviour>,
+    pub dcutr: Toggle<dcutr::Behaviour>,
 }
 
 #[derive(Debug)]
@@ -105,7 +369,13 @@ pub enum TimechainBehaviourEvent {
     Mdns(mdns::Event),
     Identify(identify::Event),
     RequestResponse(request_response::Event<ChainRequest, ChainResponse>),
+    Latency(request_response::Event<Ping, Ping>),
     Kademlia(kad::Event),
+    Autonat(autonat::Event),
+    Upnp(upnp::Event),
+    RelayClient(relay::client::Event),
+    Relay(relay::Event),
+    Dcutr(dcutr::Event),
 }
 
 // Convert sub-events into our main event enum
@@ -121,9 +391,27 @@ impl From<identify::Event> for TimechainBehaviourEvent {
 impl From<request_response::Event<ChainRequest, ChainResponse>> for TimechainBehaviourEvent {
     fn from(event: request_response::Event<ChainRequest, ChainResponse>) -> Self { Self::RequestResponse(event) }
 }
+impl From<request_response::Event<Ping, Ping>> for TimechainBehaviourEvent {
+    fn from(event: request_response::Event<Ping, Ping>) -> Self { Self::Latency(event) }
+}
 impl From<kad::Event> for TimechainBehaviourEvent {
     fn from(event: kad::Event) -> Self { Self::Kademlia(event) }
 }
+impl From<autonat::Event> for TimechainBehaviourEvent {
+    fn from(event: autonat::Event) -> Self { Self::Autonat(event) }
+}
+impl From<upnp::Event> for TimechainBehaviourEvent {
+    fn from(event: upnp::Event) -> Self { Self::Upnp(event) }
+}
+impl From<relay::client::Event> for TimechainBehaviourEvent {
+    fn from(event: relay::client::Event) -> Self { Self::RelayClient(event) }
+}
+impl From<relay::Event> for TimechainBehaviourEvent {
+    fn from(event: relay::Event) -> Self { Self::Relay(event) }
+}
+impl From<dcutr::Event> for TimechainBehaviourEvent {
+    fn from(event: dcutr::Event) -> Self { Self::Dcutr(event) }
+}
 
 // Ensure this is PUB so main.rs can call it
 /// Default hardcoded real-world bootstrap peers.
@@ -137,29 +425,47 @@ const DEFAULT_BOOTSTRAP_PEERS: &[&str] = &[
     "/ip4/3.8.120.113/tcp/7000",    // AWS EC2
 ];
 
+pub fn default_bootstrap_peers() -> Vec<String> {
+    DEFAULT_BOOTSTRAP_PEERS.iter().map(|s| s.to_string()).collect()
+}
+
 pub async fn init_network() -> Result<Swarm<TimechainBehaviour>, Box<dyn Error + Send + Sync>> {
-    let peers = DEFAULT_BOOTSTRAP_PEERS.iter().map(|s| s.to_string()).collect();
-    init_network_with_bootstrap(peers).await
+    init_network_with_bootstrap(default_bootstrap_peers()).await
 }
 
 /// Initialize network with advanced security: peer authentication, encrypted channels, rate limiting, and robust bootstrap logic.
 /// Discv5 handles peer discovery externally - peers are bridged to the swarm via manual dialing.
 pub async fn init_network_with_bootstrap(bootstrap_peers: Vec<String>) -> Result<Swarm<TimechainBehaviour>, Box<dyn Error + Send + Sync>> {
+    init_network_with_config(bootstrap_peers, &NetworkConfig::default()).await
+}
+
+/// Same as [`init_network_with_bootstrap`], with explicit per-node
+/// settings. The QUIC and relay client transports are always installed so
+/// `/quic-v1` and relayed addresses can be dialed; the NAT behaviours are
+/// toggled by `config.nat`, and listening is left to the caller.
+pub async fn init_network_with_config(
+    bootstrap_peers: Vec<String>,
+    config: &NetworkConfig,
+) -> Result<Swarm<TimechainBehaviour>, Box<dyn Error + Send + Sync>> {
+    let nat = &config.nat;
     // Use Ed25519 for strong peer identity
     let local_key = identity::Keypair::generate_ed25519();
     
     // Configure Yamux with longer idle timeout to prevent disconnects
     let yamux_config = libp2p::yamux::Config::default();
     
-    // Enforce encrypted channels (Noise protocol)
+    // Enforce encrypted channels (Noise protocol), bound to our chain ID
+    // and genesis anchor via the handshake prologue
     let mut swarm = libp2p::SwarmBuilder::with_existing_identity(local_key)
         .with_tokio()
         .with_tcp(
             libp2p::tcp::Config::default(),
-            libp2p::noise::Config::new,
+            noise_config,
             || yamux_config.clone(),
         )?
-        .with_behaviour(|key| {
+        .with_quic()
+        .with_relay_client(noise_config, libp2p::yamux::Config::default)?
+        .with_behaviour(|key, relay_client| {
             let peer_id = key.public().to_peer_id();
             let kad_store = kad::store::MemoryStore::new(peer_id);
             let mut kademlia = kad::Behaviour::new(peer_id, kad_store);
@@ -168,21 +474,34 @@ pub async fn init_network_with_bootstrap(bootstrap_peers: Vec<String>) -> Result
             Ok(TimechainBehaviour {
                 gossipsub: gossipsub::Behaviour::new(
                     gossipsub::MessageAuthenticity::Signed(key.clone()),
-                    gossipsub::Config::default(),
+                    config.gossip_config.to_gossipsub()?,
                 )?,
                 mdns: mdns::tokio::Behaviour::new(mdns::Config::default(), peer_id)?,
-                identify: identify::Behaviour::new(identify::Config::new("axiom/1.0.0".into(), key.public())),
+                identify: identify::Behaviour::new(identify::Config::new(identify_protocol_version(), key.public())),
                 request_response: {
-                    // Support multiple protocol versions for compatibility
+                    // v2 replaces the whole-chain reply with ranged header and
+                    // block requests; the 1.x wire format is not compatible.
                     request_response::Behaviour::new(
-                        vec![
-                            (StreamProtocol::new("/axiom/chain-sync/1.0.0"), ProtocolSupport::Full),
-                            (StreamProtocol::new("/axiom/chain-sync/0.9.0"), ProtocolSupport::Full),
-                        ],
-                        request_response::Config::default(),
+                        vec![(StreamProtocol::new("/axiom/chain-sync/2.0.0"), ProtocolSupport::Full)],
+                        request_response::Config::default()
+                            .with_request_timeout(std::time::Duration::from_secs(60)),
                     )
                 },
+                latency: request_response::Behaviour::new(
+                    vec![(StreamProtocol::new("/axiom/ping/1.0.0"), ProtocolSupport::Full)],
+                    request_response::Config::default()
+                        .with_request_timeout(std::time::Duration::from_secs(PING_TIMEOUT_SECS)),
+                ),
                 kademlia,
+                autonat: Toggle::from(nat.enable_autonat.then(|| {
+                    autonat::Behaviour::new(peer_id, autonat::Config::default())
+                })),
+                upnp: Toggle::from(nat.enable_upnp.then(upnp::tokio::Behaviour::default)),
+                relay_client,
+                relay: Toggle::from(nat.enable_relay_server.then(|| {
+                    relay::Behaviour::new(peer_id, relay::Config::default())
+                })),
+                dcutr: Toggle::from(nat.enable_hole_punching.then(|| dcutr::Behaviour::new(peer_id))),
             })
         })?
         .with_swarm_config(|cfg| {
@@ -285,3 +604,81 @@ pub fn check_bootstrap_connectivity() {
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(len: usize, tag: u64) -> Vec<Block> {
+        Block::test_chain(len, tag)
+    }
+
+    #[test]
+    fn test_serve_chain_request_clamps_range() {
+        let blocks = chain(10, 0);
+        let mut cache = EncodedBlockCache::new(1 << 20);
+        match serve_chain_request(&blocks, &ChainRequest::GetHeaders { from: 8, count: 100 }, &mut cache) {
+            ChainResponse::Headers { tip_height, headers } => {
+                assert_eq!(tip_height, 10);
+                assert_eq!(headers.len(), 2);
+                assert_eq!(headers[0].height, 8);
+                assert_eq!(headers[1].parent, headers[0].hash);
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+        match serve_chain_request(&blocks, &ChainRequest::GetBlocks { from: 50, count: 5 }, &mut cache) {
+            ChainResponse::EncodedBlocks { blocks, .. } => assert!(blocks.is_empty()),
+            other => panic!("unexpected response: {:?}", other),
+        }
+        match serve_chain_request(&blocks, &ChainRequest::GetBlocks { from: 8, count: 5 }, &mut cache) {
+            ChainResponse::EncodedBlocks { from, blocks, .. } => assert_eq!((from, blocks.len()), (8, 2)),
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_block_request_answered_only_as_asked() {
+        let (peer, other) = (PeerId::random(), PeerId::random());
+        let now = Instant::now();
+        let request = BlockRequest::new(peer, 40, now);
+        assert_eq!(request.request(), ChainRequest::GetBlocks { from: 40, count: MAX_BLOCKS_PER_REQUEST });
+
+        assert!(request.answered_by(&peer, 40, 64));
+        // Another peer, another fork point, or more than we asked for.
+        assert!(!request.answered_by(&other, 40, 64));
+        assert!(!request.answered_by(&peer, 12, 64));
+        assert!(!request.answered_by(&peer, 40, 65));
+
+        assert!(!request.expired(now + Duration::from_secs(59)));
+        assert!(request.expired(now + BLOCK_REQUEST_TIMEOUT));
+    }
+
+    #[test]
+    fn test_first_divergence_extension_and_fork() {
+        let local = chain(5, 0);
+        let longer = chain(8, 0);
+        let headers = |peer: &[Block], from: usize| -> Vec<SyncHeader> {
+            peer[from..].iter().enumerate()
+                .map(|(i, b)| SyncHeader::from_block((from + i) as u64, b))
+                .collect()
+        };
+
+        // Same chain, longer: missing blocks start at our tip.
+        assert_eq!(first_divergence(&local, &headers(&longer, 2)), Some(5));
+
+        // Competing fork after the common block at height 2.
+        let mut fork = local[..3].to_vec();
+        for slot in 3..8u64 {
+            let parent = fork.last().unwrap().legacy_hash();
+            let mut b = longer[slot as usize].clone();
+            b.parent = parent;
+            b.nonce = 99;
+            fork.push(b);
+        }
+        assert_eq!(first_divergence(&local, &headers(&fork, 1)), Some(3));
+
+        // Nothing new, or no common ancestor within the window.
+        assert_eq!(first_divergence(&local, &headers(&local, 0)), None);
+        assert_eq!(first_divergence(&local, &headers(&fork, 4)), None);
+    }
+}
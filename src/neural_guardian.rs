@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use sha2::{Digest, Sha256};
 
+use crate::guardian::model_release::{attestation_path, ModelAttestation, ReleasePolicy};
+
 /// Network event for training the Neural Guardian
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NetworkEvent {
@@ -22,6 +24,14 @@ pub struct NetworkEvent {
     pub bandwidth_usage: f32,     // Network bandwidth (KB/s)
     pub connection_churn: f32,    // Peer connect/disconnect rate
     pub timestamp: u64,
+    /// Country of the peer's address, when GeoIP data is loaded. Location
+    /// is not a model input (the released weights fix the input width);
+    /// it is kept for geographic rules and for the audit trail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Autonomous system of the peer's address, when GeoIP data is loaded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asn: Option<u32>,
 }
 
 /// Threat types that Neural Guardian can detect
@@ -33,6 +43,39 @@ pub enum ThreatType {
     DoS,               // Denial of service
     TimestampManip,    // VDF timing manipulation
     Benign,            // No threat detected
+    ForgedMessage,     // Provably forged protocol message (reported, not inferred)
+    OperatorBan,       // Banned by the node operator through the admin API
+    GeographicAnomaly, // Peer identity reappeared from another country or AS (reported, not inferred)
+}
+
+/// A threat the node confirmed itself, turned into a training sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Incident {
+    /// The peer ran over its rate limit.
+    RateLimitViolation,
+    /// The peer sent a block with a bad VDF or ZK proof.
+    InvalidProof,
+    /// The peer sent a pulse signed with a miner key it does not hold.
+    ForgedPulse,
+}
+
+impl Incident {
+    /// The model class the incident trains towards. The model has no
+    /// output for forgery; forging a miner's signature is posing as
+    /// another identity, so it is taught as a Sybil attack.
+    pub fn label(self) -> ThreatType {
+        match self {
+            Incident::RateLimitViolation => ThreatType::DoS,
+            Incident::InvalidProof => ThreatType::TimestampManip,
+            Incident::ForgedPulse => ThreatType::SybilAttack,
+        }
+    }
+
+    /// The incident a block rejected with `reason` confirms, if any.
+    pub fn from_block_rejection(reason: &str) -> Option<Self> {
+        matches!(reason, "Invalid VDF proof" | "Missing miner ZK-STARK proof" | "Invalid ZK proof")
+            .then_some(Incident::InvalidProof)
+    }
 }
 
 /// Threat assessment result
@@ -69,10 +112,18 @@ pub struct NeuralNetwork {
 
 impl NeuralNetwork {
     /// Create a new neural network with random initialization (non-deterministic).
-    /// Used for federated learning updates and experimentation.
+    /// Used for federated learning updates and experimentation; anything
+    /// other nodes must reproduce uses `from_seed`.
     pub fn new() -> Self {
         let mut rng = rand::thread_rng();
-        Self::init_from_rng(&mut rng)
+        Self::from_rng(&mut rng)
+    }
+
+    /// A network initialised from `seed`; the same seed gives the same weights
+    /// on every node.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        use rand::SeedableRng;
+        Self::from_rng(&mut rand::rngs::StdRng::from_seed(seed))
     }
 
     /// Create the canonical genesis neural network with a fixed seed.
@@ -81,16 +132,15 @@ impl NeuralNetwork {
     /// weights so that `GENESIS_WEIGHTS_HASH` can be verified at startup.
     /// The seed is derived from the Axiom Genesis Anchor string.
     pub fn new_genesis() -> Self {
-        use rand::SeedableRng;
         // Deterministic seed derived from Genesis Anchor
         let seed_bytes: [u8; 32] = *blake3::hash(
             b"Axiom V4.0.0: Fully Decentralized. Non-Governance. Built for the World."
         ).as_bytes();
-        let mut rng = rand::rngs::StdRng::from_seed(seed_bytes);
-        Self::init_from_rng(&mut rng)
+        Self::from_seed(seed_bytes)
     }
 
-    fn init_from_rng<R: rand::Rng>(rng: &mut R) -> Self {
+    /// A network initialised with weights drawn from `rng`.
+    pub fn from_rng<R: rand::Rng>(rng: &mut R) -> Self {
         let input_size = 10;
         let hidden_size = 64;
         let output_size = 6; // 6 threat types (including Benign)
@@ -212,16 +262,29 @@ pub struct ModelUpdate {
     pub timestamp: u64,
 }
 
+/// Where the node keeps peer assessments between runs.
+pub const PEER_SCORES_PATH: &str = "axiom_peer_scores.json";
+
+/// Where the node keeps its labelled training samples between runs.
+pub const TRAINING_DATA_PATH: &str = "axiom_guardian_training.json";
+
+/// Labelled samples kept for retraining; the oldest go first.
+pub const MAX_TRAINING_SAMPLES: usize = 10_000;
+
 /// Neural Guardian with federated learning
 pub struct NeuralGuardian {
     model: NeuralNetwork,
     peer_history: HashMap<String, Vec<NetworkEvent>>,
     threat_cache: HashMap<String, ThreatAssessment>,
     training_data: Vec<(NetworkEvent, ThreatType)>,
+    /// Samples added since the model last trained.
+    untrained_samples: usize,
     /// SHA-256 hex digest of the currently loaded model weights.
     /// Set by [`load_model`] on startup; defaults to the hash of the
     /// freshly-initialised random weights.
     model_hash: String,
+    /// Maintainer keys whose signatures [`load_model`] requires.
+    release_policy: ReleasePolicy,
 }
 
 impl Default for NeuralGuardian {
@@ -240,12 +303,20 @@ impl NeuralGuardian {
             peer_history: HashMap::new(),
             threat_cache: HashMap::new(),
             training_data: Vec::new(),
+            untrained_samples: 0,
             model_hash,
+            release_policy: ReleasePolicy::manifest(),
         }
     }
 
+    /// Override the manifest's release signers (testnets, tests).
+    pub fn with_release_policy(mut self, policy: ReleasePolicy) -> Self {
+        self.release_policy = policy;
+        self
+    }
+
     /// SHA-256 hex digest of the model's weight matrices.
-    fn hash_model_weights(model: &NeuralNetwork) -> String {
+    pub(crate) fn hash_model_weights(model: &NeuralNetwork) -> String {
         let mut hasher = Sha256::new();
         for row in &model.weights_input_hidden {
             for &w in row {
@@ -271,7 +342,9 @@ impl NeuralGuardian {
     ///
     /// The node **must** call this at startup.  If the SHA-256 hash of
     /// the file does not match the genesis constant, the node panics
-    /// with a clear integrity failure message.
+    /// with a clear integrity failure message. The file must also be
+    /// accompanied by a `<path>.sig` attestation meeting the release
+    /// policy's signature threshold; otherwise an error is returned.
     pub fn load_model(&mut self, path: std::path::PathBuf) -> Result<(), String> {
         let data = std::fs::read(&path).map_err(|e| {
             format!("Failed to read model weights from {}: {}", path.display(), e)
@@ -290,6 +363,17 @@ impl NeuralGuardian {
             file_hash, expected
         );
 
+        // The hash alone only proves the file matches the constant; the
+        // constant itself must come from a release signed by N-of-M
+        // maintainers (see `guardian::model_release`).
+        let sig_path = attestation_path(&path);
+        let attestation = ModelAttestation::load(&sig_path).map_err(|e| {
+            format!("Missing or unreadable model attestation {}: {}", sig_path.display(), e)
+        })?;
+        self.release_policy
+            .verify(&data, &attestation)
+            .map_err(|e| format!("Model attestation rejected: {}", e))?;
+
         // Deserialize and install the verified weights
         let model: NeuralNetwork = bincode::deserialize(&data).map_err(|e| {
             format!("Failed to deserialize model weights: {}", e)
@@ -378,6 +462,219 @@ impl NeuralGuardian {
         Some(assessment)
     }
     
+    /// Record a threat the node proved itself (e.g. a pulse with a bad
+    /// miner signature). Unlike model predictions this is certain, so the
+    /// peer's assessment is pinned to zero trust and kept.
+    pub fn report_threat(&mut self, peer_id: &str, threat: ThreatType) -> ThreatAssessment {
+        let mut threats = self
+            .threat_cache
+            .get(peer_id)
+            .map(|a| a.detected_threats.clone())
+            .unwrap_or_default();
+        if !threats.contains(&threat) {
+            threats.push(threat);
+        }
+        let assessment = ThreatAssessment {
+            peer_id: peer_id.to_string(),
+            trust_score: 0.0,
+            recommended_action: determine_action(&threats),
+            detected_threats: threats,
+            confidence: 1.0,
+        };
+        self.threat_cache.insert(peer_id.to_string(), assessment.clone());
+        assessment
+    }
+
+    /// Record a rule-based finding (e.g. a peer hopping countries). It joins
+    /// the peer's assessment like a reported threat, but as circumstantial
+    /// evidence it only lowers trust by `penalty` instead of pinning it.
+    pub fn flag_threat(&mut self, peer_id: &str, threat: ThreatType, penalty: f32) -> ThreatAssessment {
+        let (mut threats, trust_score, confidence) = self
+            .threat_cache
+            .get(peer_id)
+            .map(|a| (a.detected_threats.clone(), a.trust_score, a.confidence))
+            .unwrap_or((Vec::new(), 1.0, 1.0));
+        if !threats.contains(&threat) {
+            threats.push(threat);
+        }
+        let assessment = ThreatAssessment {
+            peer_id: peer_id.to_string(),
+            trust_score: (trust_score - penalty).max(0.0),
+            recommended_action: determine_action(&threats),
+            detected_threats: threats,
+            confidence,
+        };
+        self.threat_cache.insert(peer_id.to_string(), assessment.clone());
+        assessment
+    }
+
+    /// Whether the current assessment of `peer_id` calls for a ban.
+    pub fn is_banned(&self, peer_id: &str) -> bool {
+        self.threat_cache
+            .get(peer_id)
+            .is_some_and(|a| a.recommended_action == Action::BanPeer)
+    }
+
+    /// The cached assessment of `peer_id`, without running the model.
+    pub fn assessment(&self, peer_id: &str) -> Option<&ThreatAssessment> {
+        self.threat_cache.get(peer_id)
+    }
+
+    /// Drop the cached assessment of `peer_id` once its ban has run out,
+    /// so it is judged afresh. Its event history is kept.
+    pub fn forget_assessment(&mut self, peer_id: &str) {
+        self.threat_cache.remove(peer_id);
+    }
+
+    /// Forget everything held against `peer_id`: its assessment and the
+    /// event history it was derived from. Used when an operator marks a
+    /// detection as a false positive, so its latest event is kept as a
+    /// benign training sample. Returns whether there was anything.
+    pub fn pardon(&mut self, peer_id: &str) -> bool {
+        let assessed = self.threat_cache.remove(peer_id).is_some();
+        let history = self.peer_history.remove(peer_id);
+        if let Some(event) = history.as_ref().and_then(|events| events.last()) {
+            self.add_training_sample(event.clone(), ThreatType::Benign);
+        }
+        assessed || history.is_some()
+    }
+
+    /// Keep `event`, labelled `threat`, for the next retraining. Labels
+    /// the model has no output for are ignored.
+    pub fn add_training_sample(&mut self, event: NetworkEvent, threat: ThreatType) {
+        if threat_to_one_hot(&threat).iter().all(|&t| t == 0.0) {
+            return;
+        }
+        if self.training_data.len() >= MAX_TRAINING_SAMPLES {
+            self.training_data.remove(0);
+        }
+        self.training_data.push((event, threat));
+        self.untrained_samples += 1;
+    }
+
+    /// Samples added since the last `train_local`.
+    pub fn untrained_samples(&self) -> usize {
+        self.untrained_samples
+    }
+
+    /// Label `peer_id`'s latest recorded event with what `incident`
+    /// confirms. The same event is labelled at most once per class, so a
+    /// flood of violations adds one sample per event, not per message.
+    /// Returns whether a sample was added.
+    pub fn confirm_incident(&mut self, peer_id: &str, incident: Incident) -> bool {
+        let label = incident.label();
+        let Some(event) = self.peer_history.get(peer_id).and_then(|events| events.last()).cloned() else {
+            return false;
+        };
+        let known = self.training_data.iter().rev().take(64).any(|(e, t)| {
+            *t == label && e.peer_id == event.peer_id && e.timestamp == event.timestamp
+        });
+        if known {
+            return false;
+        }
+        self.add_training_sample(event, label);
+        true
+    }
+
+    /// Write the labelled samples to `path`.
+    pub fn save_training_data(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let json = serde_json::to_vec(&self.training_data)?;
+        crate::storage::write_atomic(path, &json)
+    }
+
+    /// Restore samples written by [`save_training_data`], ahead of any
+    /// gathered since startup. Returns how many were restored; a missing
+    /// file restores none.
+    pub fn load_training_data(&mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<usize> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let mut samples: Vec<(NetworkEvent, ThreatType)> = serde_json::from_slice(&data)?;
+        let restored = samples.len();
+        samples.append(&mut self.training_data);
+        let excess = samples.len().saturating_sub(MAX_TRAINING_SAMPLES);
+        samples.drain(..excess);
+        self.training_data = samples;
+        Ok(restored)
+    }
+
+    /// A guardian holding only the current model and training samples,
+    /// to run `train_local` on without holding this one.
+    pub fn training_job(&self) -> NeuralGuardian {
+        NeuralGuardian {
+            model: self.model.clone(),
+            training_data: self.training_data.clone(),
+            untrained_samples: self.untrained_samples,
+            model_hash: self.model_hash.clone(),
+            ..NeuralGuardian::new()
+        }
+    }
+
+    /// Install the model `job` trained. Samples added while it ran stay
+    /// untrained.
+    pub fn finish_training(&mut self, job: NeuralGuardian, trained: usize) {
+        self.install_model(job.model);
+        self.untrained_samples = self.untrained_samples.saturating_sub(trained);
+    }
+
+    /// Share of the training samples the current model labels correctly,
+    /// or `None` without samples: how well it detects what it was
+    /// taught.
+    pub fn detection_accuracy(&self) -> Option<f32> {
+        if self.training_data.is_empty() {
+            return None;
+        }
+        let correct = self
+            .training_data
+            .iter()
+            .filter(|(event, threat)| {
+                let predictions = self.model.forward(&self.extract_features(event));
+                let predicted = predictions
+                    .iter()
+                    .enumerate()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map(|(i, _)| i);
+                predicted == threat_to_one_hot(threat).iter().position(|&t| t == 1.0)
+            })
+            .count();
+        Some(correct as f32 / self.training_data.len() as f32)
+    }
+
+    /// The weights in use.
+    pub fn model(&self) -> &NeuralNetwork {
+        &self.model
+    }
+
+    /// Replace the weights, e.g. with a checkpoint. Cached assessments
+    /// are kept; new ones come from `model`.
+    pub fn install_model(&mut self, model: NeuralNetwork) {
+        self.model_hash = Self::hash_model_weights(&model);
+        self.model = model;
+    }
+
+    /// Write the per-peer assessments (trust scores and bans) to `path`, so
+    /// a restart does not hand a banned peer a clean slate.
+    pub fn save_assessments(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(&self.threat_cache)?;
+        crate::storage::write_atomic(path, &json)
+    }
+
+    /// Restore assessments written by [`save_assessments`]. Returns how many
+    /// peers were restored; a missing file restores none.
+    pub fn load_assessments(&mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<usize> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let assessments: HashMap<String, ThreatAssessment> = serde_json::from_slice(&data)?;
+        let restored = assessments.len();
+        self.threat_cache.extend(assessments);
+        Ok(restored)
+    }
+
     /// Record a network event for a peer
     pub fn record_event(&mut self, peer_id: String, event: NetworkEvent) {
         self.peer_history
@@ -408,10 +705,12 @@ impl NeuralGuardian {
             }
         }
         
-        let avg_loss = total_loss / (epochs as f32 * self.training_data.len() as f32);
+        let avg_loss = total_loss / (epochs as f32 * self.training_data.len() as f32).max(1.0);
         
         // Compute gradients hash for verification
         let gradients_hash = self.compute_gradients_hash();
+        self.model_hash = Self::hash_model_weights(&self.model);
+        self.untrained_samples = 0;
         
         ModelUpdate {
             node_id: "local".to_string(),
@@ -454,6 +753,8 @@ impl NeuralGuardian {
             }
         }
 
+        self.model_hash = Self::hash_model_weights(&self.model);
+
         log::info!(
             "Aggregated {} updates ({} samples): avg_loss={:.6}, lr_scale={:.4}",
             updates.len(),
@@ -528,6 +829,13 @@ impl NeuralGuardian {
         hasher.update(&event.bandwidth_usage.to_le_bytes());
         hasher.update(&event.connection_churn.to_le_bytes());
         hasher.update(&event.timestamp.to_le_bytes());
+        // Location only when known, so proofs from before GeoIP still replay
+        if let Some(country) = &event.country {
+            hasher.update(country.as_bytes());
+        }
+        if let Some(asn) = event.asn {
+            hasher.update(&asn.to_le_bytes());
+        }
         // Feed the model weights hash
         hasher.update(&weights_hash);
         // Feed the trust score
@@ -541,6 +849,9 @@ impl NeuralGuardian {
                 ThreatType::DoS => 3,
                 ThreatType::TimestampManip => 4,
                 ThreatType::Benign => 5,
+                ThreatType::ForgedMessage => 6,
+                ThreatType::OperatorBan => 7,
+                ThreatType::GeographicAnomaly => 8,
             };
             hasher.update(&[idx]);
         }
@@ -630,6 +941,8 @@ fn threat_to_one_hot(threat: &ThreatType) -> Vec<f32> {
         ThreatType::DoS => 3,
         ThreatType::TimestampManip => 4,
         ThreatType::Benign => 5,
+        // Not a model output class: nothing to train towards.
+        ThreatType::ForgedMessage | ThreatType::OperatorBan | ThreatType::GeographicAnomaly => return encoding,
     };
     encoding[index] = 1.0;
     encoding
@@ -652,6 +965,8 @@ fn determine_action(threats: &[ThreatType]) -> Action {
         ThreatType::DoS => Action::RateLimit,
         ThreatType::TimestampManip => Action::VerifyVDF,
         ThreatType::Benign => Action::None,
+        ThreatType::ForgedMessage | ThreatType::OperatorBan => Action::BanPeer,
+        ThreatType::GeographicAnomaly => Action::IncreaseMonitoring,
     }
 }
 
@@ -706,6 +1021,8 @@ mod tests {
             bandwidth_usage: 100.0,
             connection_churn: 0.5,
             timestamp: current_timestamp(),
+            country: None,
+            asn: None,
         };
         
         guardian.record_event("peer1".to_string(), event);
@@ -735,6 +1052,8 @@ mod tests {
             bandwidth_usage: 256.0,
             connection_churn: 1.0,
             timestamp: current_timestamp(),
+            country: None,
+            asn: None,
         };
         
         let features = guardian.extract_features(&event);
@@ -764,6 +1083,8 @@ mod tests {
             bandwidth_usage: 100.0,
             connection_churn: 0.5,
             timestamp: current_timestamp(),
+            country: None,
+            asn: None,
         };
         
         guardian.training_data.push((benign_event, ThreatType::Benign));
@@ -774,6 +1095,51 @@ mod tests {
         assert_eq!(update.num_samples, 1);
     }
     
+    #[test]
+    fn test_confirmed_incidents_become_persistent_samples() {
+        let mut guardian = NeuralGuardian::new();
+        assert!(!guardian.confirm_incident("peer1", Incident::ForgedPulse), "no event to label");
+        let event = NetworkEvent {
+            peer_id: "peer1".to_string(),
+            block_interval: 10.0,
+            block_size: 1.0,
+            tx_count: 40.0,
+            propagation_time: 5.0,
+            peer_count: 8.0,
+            fork_count: 0.0,
+            orphan_rate: 0.0,
+            reorg_depth: 0.0,
+            bandwidth_usage: 900.0,
+            connection_churn: 0.0,
+            timestamp: 1_700_000_000,
+            country: None,
+            asn: None,
+        };
+        guardian.record_event("peer1".to_string(), event);
+        assert!(guardian.confirm_incident("peer1", Incident::RateLimitViolation));
+        assert!(!guardian.confirm_incident("peer1", Incident::RateLimitViolation), "same event, same label");
+        assert!(guardian.confirm_incident("peer1", Incident::ForgedPulse));
+        assert_eq!(guardian.untrained_samples(), 2);
+        assert_eq!(Incident::from_block_rejection("Invalid VDF proof"), Some(Incident::InvalidProof));
+        assert_eq!(Incident::from_block_rejection("Insufficient balance"), None);
+
+        // Training runs on a copy; samples added meanwhile stay untrained.
+        let mut job = guardian.training_job();
+        let update = job.train_local(5, 0.01);
+        assert_eq!(update.num_samples, 2);
+        guardian.confirm_incident("peer1", Incident::InvalidProof);
+        guardian.finish_training(job, update.num_samples);
+        assert_eq!(guardian.untrained_samples(), 1);
+        assert_ne!(guardian.get_stats().model_hash, NeuralGuardian::new().get_stats().model_hash);
+
+        let path = std::env::temp_dir().join(format!("axiom_guardian_training_{}.json", std::process::id()));
+        guardian.save_training_data(&path).unwrap();
+        let mut restored = NeuralGuardian::new();
+        assert_eq!(restored.load_training_data(&path).unwrap(), 3);
+        assert_eq!(restored.training_data[0].1, ThreatType::DoS);
+        let _ = std::fs::remove_file(path);
+    }
+
     #[test]
     fn test_action_determination() {
         assert_eq!(determine_action(&[]), Action::None);
@@ -787,6 +1153,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reported_threat_bans_peer() {
+        let mut guardian = NeuralGuardian::new();
+        assert!(!guardian.is_banned("peer1"));
+        let assessment = guardian.report_threat("peer1", ThreatType::ForgedMessage);
+        assert_eq!(assessment.trust_score, 0.0);
+        assert!(guardian.is_banned("peer1"));
+        // Sticks: later analysis returns the pinned assessment.
+        assert_eq!(guardian.analyze_peer("peer1").unwrap().recommended_action, Action::BanPeer);
+    }
+
+    #[test]
+    fn test_pardon_lifts_ban() {
+        let mut guardian = NeuralGuardian::new();
+        guardian.report_threat("peer1", ThreatType::ForgedMessage);
+        assert!(guardian.pardon("peer1"));
+        assert!(!guardian.is_banned("peer1"));
+        assert!(!guardian.pardon("peer1"));
+    }
+
+    #[test]
+    fn test_bans_survive_restart() {
+        let path = std::env::temp_dir().join(format!("axiom_test_peer_scores_{}.json", std::process::id()));
+        let mut guardian = NeuralGuardian::new();
+        guardian.report_threat("peer1", ThreatType::ForgedMessage);
+        guardian.save_assessments(&path).unwrap();
+
+        let mut restarted = NeuralGuardian::new();
+        assert_eq!(restarted.load_assessments(&path).unwrap(), 1);
+        assert!(restarted.is_banned("peer1"));
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_audit_decision_determinism() {
         let guardian = NeuralGuardian::new();
@@ -804,6 +1203,8 @@ mod tests {
             bandwidth_usage: 100.0,
             connection_churn: 0.5,
             timestamp: 1700000000,
+            country: None,
+            asn: None,
         };
 
         let proof1 = guardian.audit_decision(&event);
@@ -834,6 +1235,8 @@ mod tests {
             bandwidth_usage: 50.0,
             connection_churn: 0.1,
             timestamp: 1700000000,
+            country: None,
+            asn: None,
         };
 
         let event_b = NetworkEvent {
@@ -849,6 +1252,8 @@ mod tests {
             bandwidth_usage: 10000.0,
             connection_churn: 9.0,
             timestamp: 1700000001,
+            country: None,
+            asn: None,
         };
 
         let proof_a = guardian.audit_decision(&event_a);
@@ -875,6 +1280,8 @@ mod tests {
             bandwidth_usage: 200.0,
             connection_churn: 0.3,
             timestamp: 1700000000,
+            country: None,
+            asn: None,
         };
 
         let proof = guardian.audit_decision(&event);
@@ -920,14 +1327,26 @@ mod tests {
             "Deterministic genesis model hash must match GENESIS_WEIGHTS_HASH"
         );
 
-        // Verify load_model accepts this file
+        // Verify load_model accepts this file once it is attested
         let tmp = std::env::temp_dir().join("axiom_test_genesis_weights.bin");
         std::fs::write(&tmp, &data).unwrap();
-
-        let mut guardian = NeuralGuardian::new();
+        let maintainers: Vec<ed25519_dalek::SigningKey> =
+            (1..=3).map(|i| ed25519_dalek::SigningKey::from_bytes(&[i; 32])).collect();
+        let policy = ReleasePolicy::new(maintainers.iter().map(|k| k.verifying_key()).collect(), 2);
+
+        let mut guardian = NeuralGuardian::new().with_release_policy(policy);
+        let unattested = guardian.load_model(tmp.clone());
+
+        let mut attestation = ModelAttestation::for_weights(&data);
+        attestation.sign(&maintainers[0]).unwrap();
+        attestation.sign(&maintainers[2]).unwrap();
+        let sig_path = attestation_path(&tmp);
+        std::fs::write(&sig_path, serde_json::to_string(&attestation).unwrap()).unwrap();
         let result = guardian.load_model(tmp.clone());
         let _ = std::fs::remove_file(&tmp);
-        assert!(result.is_ok(), "load_model must accept genesis weights file");
+        let _ = std::fs::remove_file(&sig_path);
+        assert!(unattested.is_err(), "load_model must reject weights without an attestation");
+        assert!(result.is_ok(), "load_model must accept attested genesis weights file");
     }
 
     #[test]
@@ -939,4 +1358,15 @@ mod tests {
         let data2 = bincode::serialize(&model2).unwrap();
         assert_eq!(data1, data2, "Genesis model must be deterministic across calls");
     }
+
+    #[test]
+    fn test_seeded_models_score_identically() {
+        let input = [0.3, 0.1, 0.9, 0.0, 0.5, 0.2, 0.7, 0.4, 0.6, 0.8];
+        let a = NeuralNetwork::from_seed([9u8; 32]);
+        let b = NeuralNetwork::from_seed([9u8; 32]);
+        assert_eq!(a.forward(&input), b.forward(&input));
+        assert_eq!(NeuralGuardian::hash_model_weights(&a), NeuralGuardian::hash_model_weights(&b));
+        let c = NeuralNetwork::from_seed([10u8; 32]);
+        assert_ne!(a.forward(&input), c.forward(&input));
+    }
 }
@@ -0,0 +1,178 @@
+// src/payout.rs - Per-block mining payout addresses
+//
+// Paying every coinbase to `wallet.address` links a miner's whole income
+// to one public key. With rotation enabled the node instead pays block N
+// of its own to the N-th key on a dedicated HD branch of the wallet:
+//
+//   m / 44' / 84000' / 0' / 1' / index'
+//
+// Keys are derived with SLIP-0010 for Ed25519 (hardened-only), seeded by
+// the wallet secret, so nothing beyond `wallet.dat` needs backing up: the
+// payout keys can always be re-derived and re-discovered by scanning the
+// chain state. `payout_branch.dat` only remembers the next unused index.
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+use std::fs;
+use std::path::Path;
+
+use ed25519_dalek::{SigningKey, VerifyingKey};
+
+use crate::state::State;
+use crate::transaction::Address;
+use crate::wallet::Wallet;
+
+pub const PAYOUT_BRANCH_FILE: &str = "payout_branch.dat";
+
+/// `44' / 84000' / 0' / 1'`: purpose, chain ID as coin type, account 0,
+/// and branch 1 (payouts), leaving branch 0 for ordinary receiving keys.
+pub const PAYOUT_PATH: [u32; 4] = [44, 84_000, 0, 1];
+
+/// Consecutive never-used payout addresses scanned past the last known
+/// index before discovery stops.
+pub const PAYOUT_GAP_LIMIT: u32 = 20;
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// SLIP-0010 Ed25519 private key for `path` (every index hardened).
+pub fn derive_ed25519(seed: &[u8], path: &[u32]) -> [u8; 32] {
+    let (mut key, mut chain_code) = hmac_split(b"ed25519 seed", &[seed]);
+    for index in path {
+        let data = [&[0u8][..], &key, &(index | HARDENED).to_be_bytes()];
+        (key, chain_code) = hmac_split(&chain_code, &data);
+    }
+    key
+}
+
+fn hmac_split(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+    for part in data {
+        mac.update(part);
+    }
+    let out = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&out[..32]);
+    right.copy_from_slice(&out[32..]);
+    (left, right)
+}
+
+/// Spendable wallet for payout address `index` of `wallet`.
+pub fn payout_wallet(wallet: &Wallet, index: u32) -> Wallet {
+    let mut path = PAYOUT_PATH.to_vec();
+    path.push(index);
+    let secret_key = derive_ed25519(&wallet.secret_key, &path);
+    let address = VerifyingKey::from(&SigningKey::from_bytes(&secret_key)).to_bytes();
+    Wallet { secret_key, address }
+}
+
+pub fn payout_address(wallet: &Wallet, index: u32) -> Address {
+    payout_wallet(wallet, index).address
+}
+
+/// Rotation cursor: the next payout index to hand out.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutBranch {
+    pub next_index: u32,
+}
+
+impl PayoutBranch {
+    /// Load the cursor, starting at index 0 if the file is missing or unreadable.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|data| bincode::deserialize(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let encoded = bincode::serialize(self).map_err(std::io::Error::other)?;
+        fs::write(path, encoded)
+    }
+
+    /// Address the next mined block should pay.
+    pub fn current_address(&self, wallet: &Wallet) -> Address {
+        payout_address(wallet, self.next_index)
+    }
+
+    /// Move past an index once a block paying it has been connected.
+    pub fn advance(&mut self) {
+        self.next_index = self.next_index.saturating_add(1);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PayoutBalance {
+    pub index: u32,
+    pub address: Address,
+    pub balance: u64,
+}
+
+/// Balances across the wallet's payout branch.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PayoutSummary {
+    /// Payout addresses holding a balance or with outgoing transactions.
+    pub addresses: Vec<PayoutBalance>,
+    pub total: u64,
+    /// First index after the last used one.
+    pub next_unused: u32,
+}
+
+/// Scan the payout branch against `state`: every index below
+/// `known_next`, then `PAYOUT_GAP_LIMIT` more past the last used one, so
+/// payouts are still found after `payout_branch.dat` is lost.
+pub fn discover(wallet: &Wallet, state: &State, known_next: u32) -> PayoutSummary {
+    let mut summary = PayoutSummary::default();
+    let mut index = 0u32;
+    while index < known_next.max(summary.next_unused).saturating_add(PAYOUT_GAP_LIMIT) {
+        let address = payout_address(wallet, index);
+        let balance = state.balance(&address);
+        if balance > 0 || state.nonce(&address) > 0 {
+            summary.addresses.push(PayoutBalance { index, address, balance });
+            summary.total = summary.total.saturating_add(balance);
+            summary.next_unused = index + 1;
+        }
+        index += 1;
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slip10_vector() {
+        // SLIP-0010 Ed25519 test vector 1.
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            hex::encode(derive_ed25519(&seed, &[])),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+        assert_eq!(
+            hex::encode(derive_ed25519(&seed, &[0])),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+    }
+
+    #[test]
+    fn test_rotation_and_discovery() {
+        let wallet = Wallet { secret_key: [7u8; 32], address: [0u8; 32] };
+        let mut branch = PayoutBranch::default();
+        let first = branch.current_address(&wallet);
+        branch.advance();
+        let second = branch.current_address(&wallet);
+        assert_ne!(first, second);
+        assert_eq!(payout_wallet(&wallet, 1).address, second);
+
+        let mut state = State::new();
+        state.credit(first, 500);
+        state.credit(payout_address(&wallet, 5), 300);
+        // Discovery ignores the cursor and still finds index 5.
+        let summary = discover(&wallet, &state, 0);
+        assert_eq!(summary.total, 800);
+        assert_eq!(summary.addresses.iter().map(|a| a.index).collect::<Vec<_>>(), vec![0, 5]);
+        assert_eq!(summary.next_unused, 6);
+    }
+}
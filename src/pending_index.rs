@@ -0,0 +1,188 @@
+//! Pending transactions for explorers.
+//!
+//! The mempool itself belongs to the event loop. Every few seconds the loop
+//! copies it here, so `/v1/mempool` can report size and a fee-rate
+//! histogram, `/v1/mempool/<hash>` can tell a wallet its payment is
+//! waiting and `/v1/account/<address>` which nonce to use next, without the
+//! HTTP side touching the pool. The first time a
+//! transaction was copied is kept across refreshes as its arrival time.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mempool;
+use crate::transaction::{Address, Transaction};
+
+/// Lower bounds, in satoshis per byte, of the fee-rate histogram buckets.
+pub const FEE_RATE_BUCKETS: [u64; 8] = [0, 1, 2, 5, 10, 20, 50, 100];
+
+/// Default and maximum number of transactions listed by `/v1/mempool`.
+pub const DEFAULT_TOP_PENDING: usize = 25;
+pub const MAX_TOP_PENDING: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+    /// Encoded size in bytes.
+    pub size: usize,
+    /// Unix time this node first saw it in its mempool.
+    pub first_seen: u64,
+}
+
+impl PendingTransaction {
+    /// Fee per encoded byte, rounded down.
+    pub fn fee_rate(&self) -> u64 {
+        self.fee / self.size.max(1) as u64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeBucket {
+    pub min_fee_rate: u64,
+    /// Exclusive upper bound; `None` for the last bucket.
+    pub max_fee_rate: Option<u64>,
+    pub count: usize,
+    pub bytes: usize,
+}
+
+/// Response body for `/v1/mempool`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolSummary {
+    pub size: usize,
+    pub bytes: usize,
+    pub total_fees: u64,
+    pub fee_histogram: Vec<FeeBucket>,
+    /// Highest fee rate first, i.e. the likely contents of the next block.
+    pub top: Vec<PendingTransaction>,
+}
+
+#[derive(Debug, Default)]
+pub struct PendingIndex {
+    pending: HashMap<String, PendingTransaction>,
+    /// The copied transactions themselves, for the mempool's own rules.
+    transactions: Vec<Transaction>,
+}
+
+impl PendingIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the contents with `mempool`, keeping the arrival time of
+    /// transactions that were already pending.
+    pub fn refresh<'a>(&mut self, mempool: impl IntoIterator<Item = &'a Transaction>, now: u64) {
+        let mut previous = std::mem::take(&mut self.pending);
+        self.transactions.clear();
+        for tx in mempool {
+            self.transactions.push(tx.clone());
+            let hash = hex::encode(tx.legacy_hash());
+            let first_seen = previous.remove(&hash).map_or(now, |p| p.first_seen);
+            let entry = PendingTransaction {
+                hash: hash.clone(),
+                from: hex::encode(tx.from),
+                to: hex::encode(tx.to),
+                amount: tx.amount,
+                fee: tx.fee,
+                nonce: tx.nonce,
+                size: bincode::serialized_size(tx).unwrap_or(0) as usize,
+                first_seen,
+            };
+            self.pending.insert(hash, entry);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// The nonce `sender`'s next transaction should carry, given the one
+    /// its account expects: past every consecutive nonce already pending.
+    /// As current as the last refresh; the same rule admission applies
+    /// (`mempool::pending_nonce`).
+    pub fn pending_nonce(&self, sender: &Address, account_nonce: u64) -> u64 {
+        mempool::pending_nonce(&self.transactions, sender, account_nonce)
+    }
+
+    /// A pending transaction by hex hash.
+    pub fn get(&self, hash: &str) -> Option<&PendingTransaction> {
+        self.pending.get(&hash.to_ascii_lowercase())
+    }
+
+    /// Size, fee histogram and the `top` best-paying transactions.
+    pub fn summary(&self, top: usize) -> MempoolSummary {
+        let mut fee_histogram: Vec<FeeBucket> = FEE_RATE_BUCKETS
+            .iter()
+            .enumerate()
+            .map(|(i, &min)| FeeBucket {
+                min_fee_rate: min,
+                max_fee_rate: FEE_RATE_BUCKETS.get(i + 1).copied(),
+                count: 0,
+                bytes: 0,
+            })
+            .collect();
+        for tx in self.pending.values() {
+            let bucket = FEE_RATE_BUCKETS.partition_point(|&min| min <= tx.fee_rate()) - 1;
+            fee_histogram[bucket].count += 1;
+            fee_histogram[bucket].bytes += tx.size;
+        }
+
+        let mut ranked: Vec<&PendingTransaction> = self.pending.values().collect();
+        // Compare fee/size exactly by cross-multiplying.
+        ranked.sort_by(|a, b| {
+            (b.fee as u128 * a.size as u128)
+                .cmp(&(a.fee as u128 * b.size as u128))
+                .then(a.first_seen.cmp(&b.first_seen))
+                .then(a.hash.cmp(&b.hash))
+        });
+        MempoolSummary {
+            size: self.pending.len(),
+            bytes: self.pending.values().map(|tx| tx.size).sum(),
+            total_fees: self.pending.values().map(|tx| tx.fee).sum(),
+            fee_histogram,
+            top: ranked.into_iter().take(top.min(MAX_TOP_PENDING)).cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(nonce: u64, fee: u64) -> Transaction {
+        Transaction { from: [1u8; 32], to: [2u8; 32], amount: 10, fee, nonce, expiry_height: 0, zk_proof: vec![], signature: vec![] }
+    }
+
+    #[test]
+    fn test_summary_and_arrival_times() {
+        let (cheap, rich) = (tx(0, 0), tx(1, 100_000));
+        let mut index = PendingIndex::new();
+        index.refresh([&cheap], 100);
+        index.refresh([&cheap, &rich], 200);
+
+        let summary = index.summary(1);
+        assert_eq!(summary.size, 2);
+        assert_eq!(summary.total_fees, 100_000);
+        assert_eq!(summary.top[0].hash, hex::encode(rich.legacy_hash()));
+        assert_eq!(summary.fee_histogram[0].count, 1);
+        assert_eq!(summary.fee_histogram.last().unwrap().count, 1);
+        assert_eq!(index.get(&hex::encode(cheap.legacy_hash())).unwrap().first_seen, 100);
+        assert_eq!(index.pending_nonce(&[1u8; 32], 0), 2);
+        assert_eq!(index.pending_nonce(&[1u8; 32], 0), mempool::pending_nonce([&cheap, &rich], &[1u8; 32], 0));
+
+        // Included in a block: gone on the next refresh.
+        index.refresh([&rich], 300);
+        assert!(index.get(&hex::encode(cheap.legacy_hash())).is_none());
+        assert_eq!(index.pending_nonce(&[1u8; 32], 0), 0);
+        assert_eq!(index.get(&hex::encode(rich.legacy_hash()).to_uppercase()).unwrap().first_seen, 200);
+    }
+}
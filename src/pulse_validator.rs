@@ -0,0 +1,244 @@
+// src/pulse_validator.rs - Checks on gossiped AxiomPulses
+//
+// A pulse is a cheap claim ("the chain is at height N with this much
+// mined"), so nothing in it is trusted until it passes:
+//
+//   1. freshness: its timestamp is within `MAX_PULSE_CLOCK_SKEW_SECS` of
+//      our clock going forward and `MAX_PULSE_AGE_SECS` going back;
+//   2. supply law: `total_mined + remaining` equals the cap and
+//      `total_mined` fits the emission schedule for `height`;
+//   3. the embedded STARK supply receipt, when present, verifies;
+//   4. the announced block is one we hold (`block_hash` is its 512-bit
+//      hash) and `signature` verifies against that block's `miner`;
+//   5. continuity: `prev_pulse_hash` is the hash of the last pulse in our
+//      `PulseLog`, whose first entry links to `GENESIS_PULSE_HASH`.
+//
+// Failing 1-3, or a bad signature on a block we hold, is a bogus pulse and
+// the sender is penalised; a forged signature also gets it banned. A pulse
+// for a block we lack (not yet arrived, or a competing one) cannot have its
+// signer checked, and one failing 5 means we missed pulses. Both are kept
+// but reported as `Unlinked`, and only `Linked` pulses may move the network
+// height used for sync.
+
+use crate::block::Block;
+use crate::chain::MAX_SUPPLY;
+use crate::economics;
+use crate::supply_proof::{verify_pulse_receipt, PulseLog};
+use crate::AxiomPulse;
+
+/// How far in the future a pulse timestamp may be.
+pub const MAX_PULSE_CLOCK_SKEW_SECS: i64 = 120;
+
+/// How old a pulse may be when it reaches us.
+pub const MAX_PULSE_AGE_SECS: i64 = 600;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PulseRejection {
+    #[error("timestamp {ahead}s in the future")]
+    FromFuture { ahead: i64 },
+    #[error("timestamp {age}s old")]
+    Stale { age: i64 },
+    #[error("supply figures break the supply law at height {height}")]
+    SupplyLawViolated { height: u64 },
+    #[error("STARK supply receipt does not verify at height {height}")]
+    InvalidReceipt { height: u64 },
+    #[error("not signed by the miner of block {height}")]
+    ForgedSignature { height: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PulseVerdict {
+    /// Extends our pulse chain; its height can be trusted.
+    Linked,
+    /// Well-formed, but continuity with our chain cannot be shown.
+    Unlinked,
+}
+
+#[derive(Debug, Clone)]
+pub struct PulseValidator {
+    max_skew_secs: i64,
+    max_age_secs: i64,
+}
+
+impl Default for PulseValidator {
+    fn default() -> Self {
+        Self { max_skew_secs: MAX_PULSE_CLOCK_SKEW_SECS, max_age_secs: MAX_PULSE_AGE_SECS }
+    }
+}
+
+impl PulseValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_skew(mut self, secs: i64) -> Self {
+        self.max_skew_secs = secs;
+        self
+    }
+
+    /// Validate `pulse` received at unix time `now` against our pulse log
+    /// and chain.
+    pub fn validate(
+        &self,
+        pulse: &AxiomPulse,
+        log: &PulseLog,
+        blocks: &[Block],
+        now: i64,
+    ) -> Result<PulseVerdict, PulseRejection> {
+        let ahead = pulse.timestamp.saturating_sub(now);
+        if ahead > self.max_skew_secs {
+            return Err(PulseRejection::FromFuture { ahead });
+        }
+        let age = now.saturating_sub(pulse.timestamp);
+        if age > self.max_age_secs {
+            return Err(PulseRejection::Stale { age });
+        }
+
+        let consistent = pulse.total_mined.checked_add(pulse.remaining) == Some(MAX_SUPPLY)
+            && pulse.total_mined <= economics::calculate_total_supply(pulse.height);
+        if !consistent {
+            return Err(PulseRejection::SupplyLawViolated { height: pulse.height });
+        }
+
+        if verify_pulse_receipt(pulse) == Some(false) {
+            return Err(PulseRejection::InvalidReceipt { height: pulse.height });
+        }
+
+        // `height` is the chain length once the announced block is connected.
+        let Some(block) = pulse.height.checked_sub(1).and_then(|h| blocks.get(h as usize)) else {
+            return Ok(PulseVerdict::Unlinked);
+        };
+        if block.hash_512() != pulse.block_hash {
+            return Ok(PulseVerdict::Unlinked);
+        }
+        if !pulse.verify_signature(&block.miner) {
+            return Err(PulseRejection::ForgedSignature { height: pulse.height });
+        }
+
+        if pulse.prev_pulse_hash == log.tip_hash() {
+            Ok(PulseVerdict::Linked)
+        } else {
+            Ok(PulseVerdict::Unlinked)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Hash512;
+    use crate::supply_proof::pulse_hash;
+    use crate::wallet::Wallet;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn miner() -> Wallet {
+        crate::payout::payout_wallet(&Wallet { secret_key: [3u8; 32], address: [0u8; 32] }, 0)
+    }
+
+    /// Genesis plus `len - 1` blocks paying `miner()`. Only hashes and
+    /// miners matter here, so the blocks need not be valid.
+    fn chain(len: usize) -> Vec<Block> {
+        let mut blocks = vec![crate::genesis::genesis()];
+        for slot in 1..len as u64 {
+            let mut block = blocks[0].clone();
+            block.slot = slot;
+            block.miner = miner().address;
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    fn pulse(blocks: &[Block], height: u64, prev: Hash512) -> AxiomPulse {
+        let total_mined = (height - 1) * economics::INITIAL_REWARD;
+        let mut pulse = AxiomPulse {
+            height,
+            total_mined,
+            remaining: MAX_SUPPLY - total_mined,
+            block_hash: blocks[height as usize - 1].hash_512(),
+            oracle_seal: Hash512::ZERO,
+            prev_pulse_hash: prev,
+            timestamp: NOW,
+            stark_receipt: None,
+            signature: Vec::new(),
+        };
+        pulse.sign(&miner());
+        pulse
+    }
+
+    #[test]
+    fn test_continuity_from_genesis_anchor() {
+        let blocks = chain(3);
+        let genesis = Hash512([7u8; 64]);
+        let mut log = PulseLog::new(genesis);
+        let v = PulseValidator::new();
+
+        let first = pulse(&blocks, 2, genesis);
+        assert_eq!(v.validate(&first, &log, &blocks, NOW), Ok(PulseVerdict::Linked));
+        log.record(first.clone());
+
+        assert_eq!(v.validate(&pulse(&blocks, 3, pulse_hash(&first)), &log, &blocks, NOW), Ok(PulseVerdict::Linked));
+        assert_eq!(v.validate(&pulse(&blocks, 3, Hash512([1u8; 64])), &log, &blocks, NOW), Ok(PulseVerdict::Unlinked));
+    }
+
+    #[test]
+    fn test_bogus_pulses_rejected() {
+        let blocks = chain(2);
+        let log = PulseLog::new(Hash512::ZERO);
+        let v = PulseValidator::new();
+
+        let mut future = pulse(&blocks, 2, Hash512::ZERO);
+        future.timestamp = NOW + MAX_PULSE_CLOCK_SKEW_SECS + 1;
+        assert!(matches!(v.validate(&future, &log, &blocks, NOW), Err(PulseRejection::FromFuture { .. })));
+
+        let mut stale = pulse(&blocks, 2, Hash512::ZERO);
+        stale.timestamp = NOW - MAX_PULSE_AGE_SECS - 1;
+        assert!(matches!(v.validate(&stale, &log, &blocks, NOW), Err(PulseRejection::Stale { .. })));
+
+        // Claims more coins than the schedule allows at this height.
+        let mut inflated = pulse(&blocks, 2, Hash512::ZERO);
+        inflated.total_mined += economics::INITIAL_REWARD + 1;
+        inflated.remaining -= economics::INITIAL_REWARD + 1;
+        assert_eq!(
+            v.validate(&inflated, &log, &blocks, NOW),
+            Err(PulseRejection::SupplyLawViolated { height: 2 })
+        );
+    }
+
+    #[test]
+    fn test_forged_signature_rejected() {
+        let blocks = chain(2);
+        let log = PulseLog::new(Hash512::ZERO);
+        let mut forged = pulse(&blocks, 2, Hash512::ZERO);
+        forged.sign(&crate::payout::payout_wallet(&miner(), 1));
+        assert_eq!(
+            PulseValidator::new().validate(&forged, &log, &blocks, NOW),
+            Err(PulseRejection::ForgedSignature { height: 2 })
+        );
+        forged.signature.clear();
+        assert!(PulseValidator::new().validate(&forged, &log, &blocks, NOW).is_err());
+    }
+
+    #[test]
+    fn test_signed_pulse_survives_wire_encoding() {
+        let blocks = chain(2);
+        let sent = pulse(&blocks, 2, Hash512::ZERO);
+        let received: AxiomPulse = bincode::deserialize(&bincode::serialize(&sent).unwrap()).unwrap();
+        assert!(received.stark_receipt.is_none());
+        assert!(received.verify_signature(&miner().address));
+    }
+
+    #[test]
+    fn test_pulse_for_unknown_block_is_unlinked() {
+        let blocks = chain(3);
+        let log = PulseLog::new(Hash512::ZERO);
+        let v = PulseValidator::new();
+        // Ahead of our chain: the signer cannot be checked yet.
+        let ahead = pulse(&blocks, 3, Hash512::ZERO);
+        assert_eq!(v.validate(&ahead, &log, &blocks[..2], NOW), Ok(PulseVerdict::Unlinked));
+        // A competing block at a height we hold.
+        let mut other = pulse(&blocks, 2, Hash512::ZERO);
+        other.block_hash = Hash512([9u8; 64]);
+        assert_eq!(v.validate(&other, &log, &blocks, NOW), Ok(PulseVerdict::Unlinked));
+    }
+}
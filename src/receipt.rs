@@ -0,0 +1,355 @@
+// src/receipt.rs - Transaction receipts with Merkle inclusion proofs
+//
+// Each block's transactions form a binary Merkle tree over their TXIDs
+// (`Transaction::legacy_hash`). The root is carried in `SyncHeader::tx_root`, so a
+// client holding only headers can check that a transaction was included in
+// a block using a receipt and its log2(n) sibling hashes, without
+// downloading the block body.
+//
+// Tree rules (shared with `axiom_sdk::receipt`):
+//   leaf  = BLAKE3(0x00 || txid)
+//   node  = BLAKE3(0x01 || left || right)
+//   an odd node at the end of a level is promoted unchanged, never
+//   duplicated, so two different transaction lists cannot share a root.
+//   The root of an empty block is all zeros.
+//
+// The root is not a separate field in the block encoding. It is computed
+// from `Block::transactions`, which the block hash already covers, so
+// adding it did not change any existing block hash or the genesis anchor.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::block::Block;
+use crate::types::{Hash512, HashRef};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+fn leaf_hash(txid: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(txid);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => node_hash(left, right),
+            [odd] => *odd,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Merkle root over `txids`, in block order.
+pub fn merkle_root(txids: &[[u8; 32]]) -> [u8; 32] {
+    if txids.is_empty() {
+        return [0u8; 32];
+    }
+    let mut level: Vec<[u8; 32]> = txids.iter().map(leaf_hash).collect();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level[0]
+}
+
+/// Path from one leaf to the root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Position of the transaction in the block.
+    pub index: u32,
+    /// Number of transactions in the block; fixes which levels have a sibling.
+    pub leaf_count: u32,
+    /// Sibling hashes, leaf level first. Promoted nodes contribute none.
+    #[serde(serialize_with = "hex_vec", deserialize_with = "hex_vec_de")]
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl MerkleProof {
+    /// Proof for the leaf at `index`, or `None` if it is out of range.
+    pub fn build(txids: &[[u8; 32]], index: usize) -> Option<Self> {
+        if index >= txids.len() {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut level: Vec<[u8; 32]> = txids.iter().map(leaf_hash).collect();
+        let mut position = index;
+        while level.len() > 1 {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            level = next_level(&level);
+            position /= 2;
+        }
+        Some(Self { index: index as u32, leaf_count: txids.len() as u32, siblings })
+    }
+
+    /// Whether this proof links `txid` to `root`.
+    pub fn verify(&self, txid: &[u8; 32], root: &[u8; 32]) -> bool {
+        if self.index >= self.leaf_count {
+            return false;
+        }
+        let mut hash = leaf_hash(txid);
+        let mut position = self.index as u64;
+        let mut width = self.leaf_count as u64;
+        let mut siblings = self.siblings.iter();
+        while width > 1 {
+            if position % 2 == 1 {
+                let Some(left) = siblings.next() else { return false };
+                hash = node_hash(left, &hash);
+            } else if position + 1 < width {
+                let Some(right) = siblings.next() else { return false };
+                hash = node_hash(&hash, right);
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        siblings.next().is_none() && &hash == root
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiptStatus {
+    /// Included and applied. A block containing a transaction that fails
+    /// validation is rejected as a whole, so there is no failed state.
+    Success,
+}
+
+/// Proof that a transaction was confirmed, served at `/v1/tx/<hash>/receipt`.
+/// `tx_hash` and `block_hash` are the legacy 32-byte hashes the Merkle
+/// tree and parent links commit to; `tx_id` and `block_id` are the
+/// 512-bit ids.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TransactionReceipt {
+    #[serde(serialize_with = "hex32")]
+    pub tx_hash: [u8; 32],
+    #[serde(serialize_with = "hex512")]
+    pub tx_id: Hash512,
+    #[serde(serialize_with = "hex32")]
+    pub block_hash: [u8; 32],
+    #[serde(serialize_with = "hex512")]
+    pub block_id: Hash512,
+    pub block_height: u64,
+    pub timestamp: u64,
+    /// Position of the transaction within the block.
+    pub position: u32,
+    pub fee: u64,
+    pub status: ReceiptStatus,
+    /// Blocks on top of (and including) the containing block.
+    pub confirmations: u64,
+    /// Root the proof resolves to; equals the header's `tx_root`.
+    #[serde(serialize_with = "hex32")]
+    pub tx_root: [u8; 32],
+    pub proof: MerkleProof,
+}
+
+impl TransactionReceipt {
+    /// Check the inclusion proof against the receipt's own root.
+    /// Clients should also compare `tx_root` with a header they trust.
+    pub fn verify(&self) -> bool {
+        self.proof.index == self.position && self.proof.verify(&self.tx_hash, &self.tx_root)
+    }
+}
+
+/// Receipts for every confirmed transaction, keyed by TXID. Maintained
+/// alongside `AddressIndex` so the API can answer without the chain.
+#[derive(Debug, Default)]
+pub struct ReceiptIndex {
+    receipts: HashMap<[u8; 32], TransactionReceipt>,
+    /// 512-bit id to legacy TXID.
+    ids: HashMap<Hash512, [u8; 32]>,
+    /// Number of blocks indexed (the next expected height).
+    next_height: u64,
+}
+
+impl ReceiptIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index a whole chain, genesis first.
+    pub fn from_blocks(blocks: &[Block]) -> Self {
+        let mut index = Self::new();
+        for (height, block) in blocks.iter().enumerate() {
+            index.index_block(height as u64, block);
+        }
+        index
+    }
+
+    pub fn indexed_blocks(&self) -> u64 {
+        self.next_height
+    }
+
+    pub fn tx_count(&self) -> usize {
+        self.receipts.len()
+    }
+
+    pub fn index_block(&mut self, height: u64, block: &Block) {
+        let txids = block.txids();
+        let tx_root = merkle_root(&txids);
+        let block_hash = block.legacy_hash();
+        let block_id = block.hash_512();
+        for (position, tx) in block.transactions.iter().enumerate() {
+            let Some(proof) = MerkleProof::build(&txids, position) else { continue };
+            let tx_id = tx.hash_512();
+            self.ids.insert(tx_id, txids[position]);
+            self.receipts.insert(txids[position], TransactionReceipt {
+                tx_hash: txids[position],
+                tx_id,
+                block_hash,
+                block_id,
+                block_height: height,
+                timestamp: block.timestamp,
+                position: position as u32,
+                fee: tx.fee,
+                status: ReceiptStatus::Success,
+                confirmations: 0,
+                tx_root,
+                proof,
+            });
+        }
+        self.next_height = self.next_height.max(height + 1);
+    }
+
+    /// Forget everything at or above `height` (reorg rollback).
+    pub fn truncate(&mut self, height: u64) {
+        self.receipts.retain(|_, r| r.block_height < height);
+        let receipts = &self.receipts;
+        self.ids.retain(|_, txid| receipts.contains_key(txid));
+        self.next_height = self.next_height.min(height);
+    }
+
+    /// Re-index after switching from `old` to `new`, touching only the
+    /// blocks above their common prefix.
+    pub fn chain_replaced(&mut self, old: &[Block], new: &[Block]) {
+        let common = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+        self.truncate(common as u64);
+        for (height, block) in new.iter().enumerate().skip(common) {
+            self.index_block(height as u64, block);
+        }
+    }
+
+    /// Receipt for `tx_hash` with its confirmation count as of the
+    /// indexed tip.
+    pub fn receipt(&self, tx_hash: &[u8; 32]) -> Option<TransactionReceipt> {
+        let mut receipt = self.receipts.get(tx_hash)?.clone();
+        receipt.confirmations = self.next_height.saturating_sub(receipt.block_height);
+        Some(receipt)
+    }
+
+    /// Receipt for a transaction named by its 512-bit id or legacy TXID.
+    pub fn lookup(&self, id: &HashRef) -> Option<TransactionReceipt> {
+        match id {
+            HashRef::Legacy(tx_hash) => self.receipt(tx_hash),
+            HashRef::Full(tx_id) => self.receipt(self.ids.get(tx_id)?),
+        }
+    }
+}
+
+fn hex32<S: Serializer>(bytes: &[u8; 32], s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&hex::encode(bytes))
+}
+
+fn hex512<S: Serializer>(hash: &Hash512, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&hash.to_string())
+}
+
+fn hex_vec<S: Serializer>(hashes: &[[u8; 32]], s: S) -> Result<S::Ok, S::Error> {
+    s.collect_seq(hashes.iter().map(hex::encode))
+}
+
+fn hex_vec_de<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<[u8; 32]>, D::Error> {
+    Vec::<String>::deserialize(d)?
+        .iter()
+        .map(|h| {
+            hex::decode(h)
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or_else(|| serde::de::Error::custom(format!("bad sibling hash {}", h)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::Transaction;
+
+    fn tx(nonce: u64) -> Transaction {
+        Transaction { from: [1u8; 32], to: [2u8; 32], amount: 10, fee: 3, nonce, expiry_height: 0, zk_proof: vec![], signature: vec![] }
+    }
+
+    fn block(slot: u64, transactions: Vec<Transaction>) -> Block {
+        Block {
+            timestamp: 1_000 + slot * 1800,
+            miner: [9u8; 32],
+            transactions,
+            nonce: slot,
+            ..Block::test([0u8; 32], slot)
+        }
+    }
+
+    #[test]
+    fn test_proofs_verify_for_every_leaf_and_size() {
+        for n in 1..=9u64 {
+            let txids: Vec<[u8; 32]> = (0..n).map(|i| tx(i).legacy_hash()).collect();
+            let root = merkle_root(&txids);
+            for (i, txid) in txids.iter().enumerate() {
+                let proof = MerkleProof::build(&txids, i).unwrap();
+                assert!(proof.verify(txid, &root), "n={} i={}", n, i);
+                assert!(!proof.verify(&tx(99).legacy_hash(), &root));
+            }
+            assert!(MerkleProof::build(&txids, n as usize).is_none());
+        }
+        assert_eq!(merkle_root(&[]), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_odd_leaf_is_not_duplicated() {
+        // [a, b, c] and [a, b, c, c] must not collide.
+        let ids: Vec<[u8; 32]> = (0..3).map(|i| tx(i).legacy_hash()).collect();
+        let mut padded = ids.clone();
+        padded.push(ids[2]);
+        assert_ne!(merkle_root(&ids), merkle_root(&padded));
+    }
+
+    #[test]
+    fn test_receipt_index_follows_reorg() {
+        let genesis = block(0, vec![]);
+        let old = vec![genesis.clone(), block(1, vec![tx(0), tx(1), tx(2)])];
+        let new = vec![genesis, block(1, vec![tx(5)]), block(2, vec![tx(1)])];
+
+        let mut index = ReceiptIndex::from_blocks(&old);
+        let receipt = index.receipt(&tx(2).legacy_hash()).unwrap();
+        assert_eq!((receipt.block_height, receipt.position, receipt.fee), (1, 2, 3));
+        assert_eq!(receipt.tx_root, old[1].tx_root());
+        assert!(receipt.verify());
+
+        index.chain_replaced(&old, &new);
+        assert!(index.receipt(&tx(2).legacy_hash()).is_none());
+        let moved = index.receipt(&tx(1).legacy_hash()).unwrap();
+        assert_eq!((moved.block_height, moved.position, moved.confirmations), (2, 0, 1));
+        assert!(moved.verify());
+        assert_eq!(index.tx_count(), 2);
+
+        // Found by 512-bit id too; ids of dropped transactions go with them.
+        assert_eq!(index.lookup(&HashRef::Full(tx(1).hash_512())), Some(moved.clone()));
+        assert_eq!(index.lookup(&HashRef::Legacy(tx(1).legacy_hash())), Some(moved));
+        assert!(index.lookup(&HashRef::Full(tx(2).hash_512())).is_none());
+        assert_eq!(index.ids.len(), 2);
+    }
+}
@@ -0,0 +1,101 @@
+// src/safe_mode.rs - Halting and resuming the node
+//
+// What the node does when `axiom_core::guardian::safe_mode` says the chain
+// is halted or resumed: leave or rejoin the block and transaction topics,
+// stop mining, trip the bridge's circuit breaker and alert operators.
+
+use std::sync::{Arc, Mutex};
+use libp2p::{gossipsub, Swarm};
+use axiom_core::wallet::Wallet;
+use axiom_core::key_audit::{KeyAuditLog, SignedKind, SigningInterface, SigningRecord};
+use tracing::{error, info};
+use axiom_core::miner::Miner;
+use axiom_core::guardian::alerts::{Alert, AlertSeverity, Alerts};
+use axiom_core::guardian::safe_mode::{
+    resume_message, Halt, HaltNotice, SafeMode, SafetyNotice, SignedHaltNotice,
+};
+use axiom_core::guardian_enhancement::AIGuardianBridge;
+use crate::{unix_now, audit_signature};
+use crate::mining::PendingMiningJob;
+
+/// Sign a halt notice as this node and record the signature.
+pub(crate) fn sign_halt_notice(wallet: &Wallet, audit: &Option<Arc<Mutex<KeyAuditLog>>>, height: u64, reason: String)
+    -> SignedHaltNotice {
+    let notice = HaltNotice::new(wallet, height, reason, unix_now());
+    let digest = *blake3::hash(&notice.signing_bytes()).as_bytes();
+    audit_signature(audit, SigningRecord::object(SigningInterface::Node, SignedKind::HaltNotice, wallet.address, digest));
+    notice.sign(wallet)
+}
+
+/// Enter safe mode on `halt`: leave the block and transaction topics (an
+/// unsubscribed node neither takes in nor relays them), drop the block
+/// being mined, trip the bridge's circuit breaker and alert operators.
+pub(crate) fn halt_node(
+    swarm: &mut Swarm<axiom_core::network_legacy::TimechainBehaviour>,
+    intake_topics: &[gossipsub::IdentTopic],
+    miner: &mut Miner,
+    mining_job: &mut Option<PendingMiningJob>,
+    ai_bridge: &AIGuardianBridge,
+    alerts: &Alerts,
+    halt: &Halt,
+) {
+    let notice = &halt.notice.notice;
+    for topic in intake_topics {
+        let _ = swarm.behaviour_mut().gossipsub.unsubscribe(topic);
+    }
+    miner.cancel();
+    *mining_job = None;
+    let _ = ai_bridge.activate_circuit_breaker(notice.height, notice.reason.clone());
+    error!(height = notice.height, issuer = %notice.issuer,
+        "🛑 SAFE MODE: chain halted at H-{}: {} (halt {})", notice.height, notice.reason, halt.id);
+    alerts.raise(Alert::new(
+        "safe_mode",
+        AlertSeverity::Critical,
+        "safe_mode",
+        format!("Chain halted at H-{}: {}", notice.height, notice.reason),
+        format!("Halt {} issued by {}. Blocks and transactions are refused until admin_resume \
+            is called with signatures over {}", halt.id, notice.issuer, hex::encode(resume_message(&halt.id))),
+    ));
+}
+
+/// Halt on a notice we signed ourselves (operator or guardian): enter safe
+/// mode and tell peers. False if the node was already halted.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn enter_safe_mode(
+    safe_mode: &mut SafeMode,
+    notice: SignedHaltNotice,
+    swarm: &mut Swarm<axiom_core::network_legacy::TimechainBehaviour>,
+    safety_topic: &gossipsub::IdentTopic,
+    intake_topics: &[gossipsub::IdentTopic],
+    miner: &mut Miner,
+    mining_job: &mut Option<PendingMiningJob>,
+    ai_bridge: &AIGuardianBridge,
+    alerts: &Alerts,
+) -> bool {
+    if !safe_mode.enter(notice.clone(), unix_now()) {
+        return false;
+    }
+    if let Some(halt) = safe_mode.halt() {
+        halt_node(swarm, intake_topics, miner, mining_job, ai_bridge, alerts, halt);
+    }
+    if let Ok(data) = bincode::serialize(&SafetyNotice::Halt(notice)) {
+        let _ = swarm.behaviour_mut().gossipsub.publish(safety_topic.clone(), data);
+    }
+    true
+}
+
+/// Leave safe mode after `halt` was resumed.
+pub(crate) fn resume_node(
+    swarm: &mut Swarm<axiom_core::network_legacy::TimechainBehaviour>,
+    intake_topics: &[gossipsub::IdentTopic],
+    ai_bridge: &AIGuardianBridge,
+    halt: &Halt,
+) {
+    for topic in intake_topics {
+        if let Err(e) = swarm.behaviour_mut().gossipsub.subscribe(topic) {
+            error!("SAFE MODE: cannot resubscribe to {}: {:?}", topic, e);
+        }
+    }
+    let _ = ai_bridge.deactivate_circuit_breaker();
+    info!("✅ SAFE MODE: halt {} resumed, accepting blocks and transactions", halt.id);
+}
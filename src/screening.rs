@@ -0,0 +1,215 @@
+// src/screening.rs - Admitting transactions to the mempool
+//
+// Submitted, gossiped, pulled and restored transactions all pass the same
+// mempool checks, then wait on a blocking thread for the AI Guardian's
+// screening before the event loop pools, quarantines or refuses them and
+// tells whoever submitted them.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use axiom_core::network::tx_relay::TxRelay;
+use axiom_core::chain::Timechain;
+use axiom_core::pending_index::PendingIndex;
+use axiom_core::address_index::AddressIndex;
+use axiom_core::error::{AxiomError, ErrorBody};
+use tracing::{info, warn};
+use axiom_core::transaction::Transaction;
+use axiom_core::mempool::{check_admission, SenderLimits};
+use axiom_core::guardian_enhancement::{AIGuardianBridge, GuardianDecision};
+use axiom_core::tx_screening::{self, risk_profile, Screening, Verdict, MAX_QUARANTINED};
+use crate::{lock_or_recover, unix_now};
+use crate::api::SubmittedTx;
+
+/// Where a transaction being screened came from, and so who hears how its
+/// admission ended.
+pub(crate) enum TxOrigin {
+    /// Gossiped, or pulled after an announcement.
+    Peer,
+    /// The local wallet's `pending_tx.dat`.
+    Wallet,
+    /// Posted to /v1/transactions; the poster waits for the verdict.
+    Submitted(tokio::sync::oneshot::Sender<Result<SubmittedTx, ErrorBody>>),
+    /// Pending before a restart or handoff.
+    Restored,
+}
+
+impl TxOrigin {
+    /// Tell whoever is waiting that `tx` was refused.
+    pub(crate) fn refused(self, tx: &Transaction, e: &AxiomError) {
+        if let TxOrigin::Submitted(reply) = self {
+            info!(tx = %hex::encode(tx.legacy_hash()), code = %e.code(), "📥 Submitted transaction refused: {}", e);
+            let _ = reply.send(Err(ErrorBody::from(e)));
+        }
+    }
+}
+
+/// A transaction leaving screening, with the Guardian's decision on it.
+pub(crate) type ScreenedTx = (Transaction, TxOrigin, Result<GuardianDecision, AxiomError>);
+
+/// Mempool rule checks shared by submitted, gossiped and pulled
+/// transactions: expiry and conflicts with the pool and with transactions
+/// held for screening, consensus validation, per-sender limits. Returns the
+/// position of the pending transaction `tx` replaces, if any.
+pub(crate) fn check_transaction(
+    tx: &Transaction,
+    tc: &Timechain,
+    mempool: &VecDeque<Transaction>,
+    screening: &Screening,
+    sender_limits: &SenderLimits,
+) -> Result<Option<usize>, AxiomError> {
+    let block_height = tc.blocks.len() as u64;
+    let pool = mempool.iter().chain(screening.held());
+    let replaces = check_admission(tx, pool.clone(), block_height, &tc.state.account(&tx.from))?;
+    if replaces.is_some_and(|i| i >= mempool.len()) {
+        return Err(AxiomError::InvalidTransaction("replaces a transaction still being screened".into()));
+    }
+    tc.validate_transaction(tx).map_err(|e| AxiomError::InvalidTransaction(e.to_string()))?;
+    let (pending_txs, pending_bytes) = pool
+        .enumerate()
+        .filter(|(i, p)| p.from == tx.from && Some(*i) != replaces)
+        .fold((0usize, 0usize), |(n, b), (_, p)| {
+            (n + 1, b + bincode::serialized_size(p).map_or(0, |s| s as usize))
+        });
+    let tx_size = bincode::serialized_size(tx).map_or(0, |s| s as usize);
+    if let Err(e) = sender_limits.check(&tx.from, pending_txs, pending_bytes, tx_size) {
+        warn!("🚫 Transaction rejected: {}", e);
+        return Err(e);
+    }
+    Ok(replaces)
+}
+
+/// Put a checked transaction in the pool, in place of the one it
+/// `replaces`, with its guardian risk score if it has one.
+pub(crate) fn pool_transaction(
+    tx: Transaction,
+    replaces: Option<usize>,
+    risk: Option<f64>,
+    mempool: &mut VecDeque<Transaction>,
+    tx_risk_scores: &mut HashMap<[u8; 32], f64>,
+) {
+    if let Some(replaced) = replaces.and_then(|i| mempool.remove(i)) {
+        tx_risk_scores.remove(&replaced.legacy_hash());
+        info!(replaced = %hex::encode(replaced.legacy_hash()), fee = tx.fee, "🔁 Transaction replaced by fee");
+    }
+    if let Some(risk) = risk {
+        tx_risk_scores.insert(tx.legacy_hash(), risk);
+    }
+    mempool.push_back(tx);
+}
+
+/// Start admitting `tx`: check it, then have the security engine screen it
+/// on a blocking thread, with the chain history of both parties. The
+/// Guardian's decision comes back on `screened`, for `finish_screening`.
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(level = "debug", skip_all, fields(tx = %hex::encode(tx.legacy_hash())))]
+pub(crate) fn screen_transaction(
+    tx: Transaction,
+    origin: TxOrigin,
+    tc: &Timechain,
+    mempool: &VecDeque<Transaction>,
+    screening: &mut Screening,
+    sender_limits: &SenderLimits,
+    ai_bridge: &Arc<AIGuardianBridge>,
+    address_index: &Mutex<AddressIndex>,
+    screened: &tokio::sync::mpsc::UnboundedSender<ScreenedTx>,
+) -> Result<(), AxiomError> {
+    let checked = check_transaction(&tx, tc, mempool, screening, sender_limits).and_then(|_| {
+        if screening.start(tx.clone()) { Ok(()) } else { Err(AxiomError::DuplicateTransaction) }
+    });
+    if let Err(e) = checked {
+        origin.refused(&tx, &e);
+        return Err(e);
+    }
+    let profile = risk_profile(&tx, &lock_or_recover(address_index), unix_now());
+    let height = tc.blocks.len() as u64;
+    let bridge = Arc::clone(ai_bridge);
+    let screened = screened.clone();
+    tokio::task::spawn_blocking(move || {
+        let decision = bridge.validate_transaction_with_guardian(profile, height);
+        let _ = screened.send((tx, origin, decision));
+    });
+    Ok(())
+}
+
+/// Act on the Guardian's `decision` about `tx`, which has left screening:
+/// refuse it, quarantine it, or check it again (the pool and chain moved
+/// on meanwhile) and pool it. Returns the height it is quarantined until,
+/// if it is.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn finish_screening(
+    tx: Transaction,
+    decision: Result<GuardianDecision, AxiomError>,
+    tc: &Timechain,
+    mempool: &mut VecDeque<Transaction>,
+    screening: &mut Screening,
+    sender_limits: &SenderLimits,
+    tx_risk_scores: &mut HashMap<[u8; 32], f64>,
+    ai_fallback_count: &mut u32,
+) -> Result<Option<u64>, AxiomError> {
+    let (risk, quarantine) = match decision.map(|d| tx_screening::verdict(&d)) {
+        Ok(Verdict::Admit { risk }) => (Some(risk), None),
+        Ok(Verdict::Quarantine { risk, blocks }) => (Some(risk), Some(blocks)),
+        Ok(Verdict::Reject(e)) => {
+            info!("🛡️  Transaction rejected by AI Guardian: {}", e);
+            return Err(e);
+        }
+        Err(e) => {
+            // Circuit breaker or engine error — rate-limited fallback
+            *ai_fallback_count += 1;
+            if *ai_fallback_count > 10 {
+                log::error!("AI Guardian offline, fallback limit reached — rejecting transaction");
+                return Err(AxiomError::AIModelError("AI Guardian offline".to_string()));
+            }
+            log::warn!("AI Guardian unavailable ({}), accepting by rule-based validation ({}/10)", e, ai_fallback_count);
+            (None, None)
+        }
+    };
+    let replaces = check_transaction(&tx, tc, mempool, screening, sender_limits)?;
+    if let Some(blocks) = quarantine {
+        let until = tc.blocks.len() as u64 + blocks;
+        let risk = risk.unwrap_or_default();
+        if !screening.quarantine(tx, risk, until) {
+            return Err(AxiomError::ThreatDetected {
+                threat_type: format!("quarantine full ({} held)", MAX_QUARANTINED),
+                confidence: risk as f32,
+            });
+        }
+        info!(until, "🛡️  Transaction quarantined by AI Guardian until H-{}", until);
+        return Ok(Some(until));
+    }
+    pool_transaction(tx, replaces, risk, mempool, tx_risk_scores);
+    info!("✅ Transaction accepted (AI: approved)");
+    Ok(None)
+}
+
+/// Report how screening of `tx` ended to its submitter, and queue it for
+/// announcement if it was pooled without quarantine.
+pub(crate) fn answer_screened(
+    tx: Transaction,
+    origin: TxOrigin,
+    outcome: Result<Option<u64>, AxiomError>,
+    mempool: &VecDeque<Transaction>,
+    tx_relay: &mut TxRelay,
+    pending_index: &Mutex<PendingIndex>,
+) {
+    let quarantined_until = match outcome {
+        Ok(until) => until,
+        Err(e) => return origin.refused(&tx, &e),
+    };
+    if quarantined_until.is_none() {
+        tx_relay.accepted(&tx, Instant::now());
+    }
+    match origin {
+        TxOrigin::Submitted(reply) => {
+            // The sender's next nonce moves on at once.
+            lock_or_recover(pending_index).refresh(mempool, unix_now());
+            let _ = reply.send(Ok(SubmittedTx { hash: hex::encode(tx.legacy_hash()), quarantined_until }));
+        }
+        TxOrigin::Wallet => {
+            info!(tx = %hex::encode(tx.legacy_hash()), "📤 Transaction queued for announcement");
+            let _ = std::fs::remove_file("pending_tx.dat");
+        }
+        TxOrigin::Peer | TxOrigin::Restored => {}
+    }
+}
@@ -0,0 +1,127 @@
+// src/shutdown.rs - Stopping the node without losing work
+//
+// SIGINT and SIGTERM ask the node to shut down; SIGUSR2 asks it to hand
+// off to a new binary (see crate::handoff). Either way the main loop stops
+// mining, writes the chain, mempool, peerstore and peer scores, and leaves
+// its loop. A second SIGINT/SIGTERM while that is under way exits at once.
+//
+// The mempool is written to `axiom_mempool.dat` and re-admitted on the
+// next start, so pending transactions survive a restart. Unlike a handoff
+// it has no age limit: every transaction is revalidated on admission.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+use crate::transaction::Transaction;
+
+pub const MEMPOOL_FILE: &str = "axiom_mempool.dat";
+
+/// Why the main loop is being asked to stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopRequest {
+    /// SIGINT or SIGTERM: persist everything and exit.
+    Shutdown,
+    /// SIGUSR2: write a handoff for the binary taking over, then exit.
+    Handoff,
+}
+
+/// Translate process signals into `StopRequest`s. `stopping` is raised as
+/// soon as a signal arrives so long-running work (the nonce search) can
+/// bail out before the main loop gets to the request.
+pub fn listen_for_signals(stopping: Arc<AtomicBool>) -> UnboundedReceiver<StopRequest> {
+    let (tx, rx) = unbounded_channel();
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let kinds = [
+            (SignalKind::interrupt(), StopRequest::Shutdown),
+            (SignalKind::terminate(), StopRequest::Shutdown),
+            (SignalKind::user_defined2(), StopRequest::Handoff),
+        ];
+        for (kind, request) in kinds {
+            let mut stream = match signal(kind) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::warn!("Cannot listen for {:?}: {}", request, e);
+                    continue;
+                }
+            };
+            let (tx, stopping) = (tx.clone(), stopping.clone());
+            tokio::spawn(async move {
+                while stream.recv().await.is_some() {
+                    let already_stopping = stopping.swap(true, Ordering::SeqCst);
+                    if already_stopping && request == StopRequest::Shutdown {
+                        eprintln!("🛑 Second stop signal — exiting without saving");
+                        std::process::exit(130);
+                    }
+                    if tx.send(request).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    #[cfg(not(unix))]
+    tokio::spawn(async move {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if stopping.swap(true, Ordering::SeqCst) {
+                std::process::exit(130);
+            }
+            if tx.send(StopRequest::Shutdown).is_err() {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+/// Persist the mempool for the next start.
+pub fn save_mempool<'a>(path: impl AsRef<Path>, txs: impl IntoIterator<Item = &'a Transaction>) -> io::Result<()> {
+    let txs: Vec<&Transaction> = txs.into_iter().collect();
+    let encoded = bincode::serialize(&txs).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    crate::storage::write_atomic(path, &encoded)
+}
+
+/// Read and delete a saved mempool. A missing file is an empty mempool; a
+/// corrupt one is reported and removed.
+pub fn take_mempool(path: impl AsRef<Path>) -> io::Result<Vec<Transaction>> {
+    let path = path.as_ref();
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    fs::remove_file(path)?;
+    bincode::deserialize(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mempool_round_trip() {
+        let path = std::env::temp_dir().join(format!("axiom_test_mempool_{}.dat", std::process::id()));
+        let txs = vec![
+            Transaction::new([1u8; 32], [2u8; 32], 10, 1, 0, vec![], vec![]),
+            Transaction::new([1u8; 32], [3u8; 32], 20, 1, 1, vec![], vec![]),
+        ];
+        save_mempool(&path, &txs).unwrap();
+        assert_eq!(take_mempool(&path).unwrap(), txs);
+        // Taken means gone: a second start does not re-admit them.
+        assert!(take_mempool(&path).unwrap().is_empty());
+
+        fs::write(&path, b"not a mempool").unwrap();
+        assert!(take_mempool(&path).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -4,6 +4,8 @@ pub struct StateSnapshot {
     pub balances: HashMap<Address, u64>,
     pub total_issued: u64,
     pub nonces: HashMap<Address, u64>,
+    pub history: HashMap<Address, Vec<TxPointer>>,
+    pub fees: FeeTotals,
 }
 
 impl State {
@@ -13,6 +15,8 @@ impl State {
             balances: self.balances.clone(),
             total_issued: self.total_issued,
             nonces: self.nonces.clone(),
+            history: self.history.clone(),
+            fees: self.fees,
         }
     }
 
@@ -21,17 +25,57 @@ impl State {
         self.balances = snapshot.balances.clone();
         self.total_issued = snapshot.total_issued;
         self.nonces = snapshot.nonces.clone();
+        self.history = snapshot.history.clone();
+        self.fees = snapshot.fees;
     }
 }
 // Transaction nonce system is already implemented and functional.
 use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::economics::{FeePolicy, FeeTotals};
 use crate::transaction::{Transaction, Address};
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct State {
     pub balances: HashMap<Address, u64>,
     pub total_issued: u64,
     pub nonces: HashMap<Address, u64>,
+    /// Confirmed transactions touching each account, oldest first.
+    #[serde(default)]
+    pub history: HashMap<Address, Vec<TxPointer>>,
+    /// Fees burned and paid to miners so far.
+    #[serde(default)]
+    pub fees: FeeTotals,
+}
+
+/// Where a confirmed transaction sits in the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxPointer {
+    pub height: u64,
+    /// Index within the block's transactions.
+    pub position: u32,
+    pub hash: [u8; 32],
+}
+
+/// Balance, nonce and transaction count of one account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AccountState {
+    pub balance: u64,
+    /// Transactions sent so far; the next one must carry this nonce.
+    pub nonce: u64,
+    pub tx_count: usize,
+}
+
+/// What one block changed in the state, so that it can be disconnected
+/// again on a reorg without replaying the chain.
+#[derive(Debug, Clone, Default)]
+pub struct StateUndo {
+    /// Balance and nonce of every touched account before the block.
+    accounts: HashMap<Address, (Option<u64>, Option<u64>)>,
+    /// One entry per history pointer added, in order.
+    history: Vec<Address>,
+    /// Fee totals before the block, once it settled a fee.
+    fees: Option<FeeTotals>,
 }
 
 impl State {
@@ -40,9 +84,24 @@ impl State {
             balances: HashMap::new(),
             total_issued: 0,
             nonces: HashMap::new(),
+            history: HashMap::new(),
+            fees: FeeTotals::default(),
         }
     }
 
+    pub fn account(&self, addr: &Address) -> AccountState {
+        AccountState {
+            balance: self.balance(addr),
+            nonce: self.nonce(addr),
+            tx_count: self.history(addr).len(),
+        }
+    }
+
+    /// Confirmed transactions touching `addr`, oldest first.
+    pub fn history(&self, addr: &Address) -> &[TxPointer] {
+        self.history.get(addr).map(Vec::as_slice).unwrap_or(&[])
+    }
+
     pub fn balance(&self, addr: &Address) -> u64 {
         *self.balances.get(addr).unwrap_or(&0)
     }
@@ -86,10 +145,119 @@ impl State {
         Ok(())
     }
 
+    /// BLAKE3 commitment to every account's balance, nonce and history.
+    /// Accounts with nothing in them are left out, so a zero balance and a
+    /// missing one commit the same.
+    pub fn root(&self) -> [u8; 32] {
+        let mut addresses: Vec<&Address> = self.balances.keys()
+            .chain(self.nonces.keys())
+            .chain(self.history.keys())
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+
+        let mut hasher = blake3::Hasher::new();
+        for addr in addresses {
+            let (balance, nonce, history) = (self.balance(addr), self.nonce(addr), self.history(addr));
+            if balance == 0 && nonce == 0 && history.is_empty() {
+                continue;
+            }
+            hasher.update(addr);
+            hasher.update(&balance.to_le_bytes());
+            hasher.update(&nonce.to_le_bytes());
+            hasher.update(&(history.len() as u64).to_le_bytes());
+            for pointer in history {
+                hasher.update(&pointer.height.to_le_bytes());
+                hasher.update(&pointer.position.to_le_bytes());
+                hasher.update(&pointer.hash);
+            }
+        }
+        *hasher.finalize().as_bytes()
+    }
+
     /// Get next nonce for address
     pub fn next_nonce(&self, addr: &Address) -> u64 {
         self.nonce(addr) + 1
     }
+
+    /// `credit`, recording the change in `undo`.
+    pub fn credit_logged(&mut self, addr: Address, amount: u64, undo: &mut StateUndo) {
+        self.remember(&addr, undo);
+        self.credit(addr, amount);
+    }
+
+    /// `apply_tx` for the transaction at `height`/`position`, recording the
+    /// change in `undo` and adding it to both accounts' history. Leaves the
+    /// state untouched on error.
+    pub fn apply_tx_logged(
+        &mut self,
+        tx: &Transaction,
+        height: u64,
+        position: u32,
+        undo: &mut StateUndo,
+    ) -> Result<(), &'static str> {
+        self.remember(&tx.from, undo);
+        self.remember(&tx.to, undo);
+        self.apply_tx(tx)?;
+        let pointer = TxPointer { height, position, hash: tx.legacy_hash() };
+        let mut touched = vec![tx.from];
+        if tx.to != tx.from {
+            touched.push(tx.to);
+        }
+        for addr in touched {
+            self.history.entry(addr).or_default().push(pointer);
+            undo.history.push(addr);
+        }
+        Ok(())
+    }
+
+    /// Settle the fee of a transaction in a block mined by `miner`: burn
+    /// `policy`'s share and credit the rest to the miner, recording the
+    /// change in `undo`. Without a miner the whole fee burns.
+    pub fn settle_fee_logged(&mut self, miner: Address, fee: u64, policy: FeePolicy, undo: &mut StateUndo) {
+        let (mut burned, mut to_miner) = policy.split(fee);
+        if miner == [0u8; 32] {
+            burned += to_miner;
+            to_miner = 0;
+        }
+        undo.fees.get_or_insert(self.fees);
+        if to_miner > 0 {
+            self.credit_logged(miner, to_miner, undo);
+        }
+        self.fees.burned += burned;
+        self.fees.to_miners += to_miner;
+    }
+
+    /// Undo everything recorded in `undo`.
+    pub fn revert(&mut self, undo: StateUndo) {
+        if let Some(fees) = undo.fees {
+            self.fees = fees;
+        }
+        for addr in undo.history.iter().rev() {
+            if let Some(entries) = self.history.get_mut(addr) {
+                entries.pop();
+                if entries.is_empty() {
+                    self.history.remove(addr);
+                }
+            }
+        }
+        for (addr, (balance, nonce)) in undo.accounts {
+            match balance {
+                Some(balance) => self.balances.insert(addr, balance),
+                None => self.balances.remove(&addr),
+            };
+            match nonce {
+                Some(nonce) => self.nonces.insert(addr, nonce),
+                None => self.nonces.remove(&addr),
+            };
+        }
+    }
+
+    fn remember(&self, addr: &Address, undo: &mut StateUndo) {
+        undo.accounts
+            .entry(*addr)
+            .or_insert_with(|| (self.balances.get(addr).copied(), self.nonces.get(addr).copied()));
+    }
 }
 
 impl Default for State {
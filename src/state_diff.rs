@@ -0,0 +1,519 @@
+// src/state_diff.rs - Signed state deltas between checkpoints
+//
+// A node on a metered link does not need a week of blocks to learn a
+// week of balance changes. Archive servers cut the chain at checkpoints
+// every `CHECKPOINT_INTERVAL_BLOCKS` blocks and publish, for each pair of
+// consecutive checkpoints, the accounts whose balance, nonce or history
+// changed between them: a gzip-compressed bincode `StateDelta`, signed
+// with the server's node wallet and pinned to the state roots on both
+// sides. The latest checkpoint is also published in full as a delta from
+// the empty state, for nodes that have no base to start from.
+//
+// Unlike the block archive, a delta cannot be checked against consensus:
+// the client trusts the signer for what happened between checkpoints and
+// only verifies that the result is exactly the state that signer claims.
+// Nodes should accept deltas from servers they already trust.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::archive::MANIFEST_FILE;
+use crate::block::Block;
+use crate::chain::replay_blocks;
+use crate::economics::FeeTotals;
+use crate::state::{State, TxPointer};
+use crate::transaction::Address;
+use crate::wallet::Wallet;
+
+/// Blocks between checkpoints: one week at the 30-minute target.
+pub const CHECKPOINT_INTERVAL_BLOCKS: u64 = 336;
+
+const DELTA_DOMAIN: &[u8] = b"axiom-state-delta-v1";
+
+#[derive(Debug, Error)]
+pub enum StateDiffError {
+    #[error("state diff I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("delta {from}..{to} is malformed: {reason}")]
+    Malformed { from: u64, to: u64, reason: String },
+    #[error("delta {from}..{to} content hash mismatch")]
+    HashMismatch { from: u64, to: u64 },
+    #[error("delta {from}..{to} is not signed by a trusted key")]
+    Untrusted { from: u64, to: u64 },
+    #[error("delta starts at height {found}, expected {expected}")]
+    NotContiguous { expected: u64, found: u64 },
+    #[error("state root mismatch at height {height}")]
+    RootMismatch { height: u64 },
+    #[error("no diffs lead from height {0}; start from the snapshot")]
+    NoPath(u64),
+}
+
+/// New values for one account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDelta {
+    pub address: Address,
+    pub balance: u64,
+    pub nonce: u64,
+    /// History entries kept from the base state; `history_append` follows.
+    pub history_keep: u32,
+    pub history_append: Vec<TxPointer>,
+}
+
+/// Every account that differs between the state after `from_height`
+/// blocks and the state after `to_height` blocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDelta {
+    pub chain_id: u64,
+    pub from_height: u64,
+    pub to_height: u64,
+    pub from_root: [u8; 32],
+    pub to_root: [u8; 32],
+    /// Hash of block `to_height - 1`, the last block the delta covers.
+    pub to_block_hash: [u8; 32],
+    /// Coins issued by block rewards up to `to_height`.
+    pub total_issued: u64,
+    /// Fees burned and paid to miners up to `to_height`.
+    pub fees: FeeTotals,
+    /// Sorted by address.
+    pub accounts: Vec<AccountDelta>,
+}
+
+impl StateDelta {
+    pub fn between(from: &State, from_height: u64, to: &State, to_height: u64, to_block_hash: [u8; 32], total_issued: u64) -> Self {
+        let mut addresses: Vec<&Address> = to.balances.keys()
+            .chain(to.nonces.keys())
+            .chain(to.history.keys())
+            .chain(from.balances.keys())
+            .chain(from.nonces.keys())
+            .chain(from.history.keys())
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+
+        let accounts = addresses
+            .into_iter()
+            .filter_map(|addr| {
+                let (old, new) = (from.history(addr), to.history(addr));
+                let keep = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+                let unchanged = from.balance(addr) == to.balance(addr)
+                    && from.nonce(addr) == to.nonce(addr)
+                    && keep == old.len()
+                    && keep == new.len();
+                (!unchanged).then(|| AccountDelta {
+                    address: *addr,
+                    balance: to.balance(addr),
+                    nonce: to.nonce(addr),
+                    history_keep: keep as u32,
+                    history_append: new[keep..].to_vec(),
+                })
+            })
+            .collect();
+
+        Self {
+            chain_id: crate::AXIOM_CHAIN_ID,
+            from_height,
+            to_height,
+            from_root: from.root(),
+            to_root: to.root(),
+            to_block_hash,
+            total_issued,
+            fees: to.fees,
+            accounts,
+        }
+    }
+
+    /// Apply the delta to `state`, which must be the state it was taken
+    /// from. On a root mismatch before or after, `state` is left as it was.
+    pub fn apply(&self, state: &mut State) -> Result<(), StateDiffError> {
+        if state.root() != self.from_root {
+            return Err(StateDiffError::RootMismatch { height: self.from_height });
+        }
+        let mut next = state.clone();
+        for account in &self.accounts {
+            let addr = account.address;
+            match account.balance {
+                0 => next.balances.remove(&addr),
+                balance => next.balances.insert(addr, balance),
+            };
+            match account.nonce {
+                0 => next.nonces.remove(&addr),
+                nonce => next.nonces.insert(addr, nonce),
+            };
+            let mut history = next.history.remove(&addr).unwrap_or_default();
+            history.truncate(account.history_keep as usize);
+            history.extend_from_slice(&account.history_append);
+            if !history.is_empty() {
+                next.history.insert(addr, history);
+            }
+        }
+        if next.root() != self.to_root {
+            return Err(StateDiffError::RootMismatch { height: self.to_height });
+        }
+        next.fees = self.fees;
+        *state = next;
+        Ok(())
+    }
+
+    fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = DELTA_DOMAIN.to_vec();
+        bytes.extend(bincode::serialize(self).unwrap_or_default());
+        bytes
+    }
+
+    pub fn sign(self, signer: &Wallet) -> SignedStateDelta {
+        let signature = signer.sign_message(&self.signing_bytes());
+        SignedStateDelta { delta: self, signer: signer.address, signature }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedStateDelta {
+    pub delta: StateDelta,
+    /// Public key of the publishing node's wallet.
+    pub signer: Address,
+    pub signature: Vec<u8>,
+}
+
+impl SignedStateDelta {
+    pub fn verify(&self) -> bool {
+        let Ok(signature) = <[u8; 64]>::try_from(self.signature.as_slice()) else {
+            return false;
+        };
+        ed25519_dalek::VerifyingKey::from_bytes(&self.signer)
+            .and_then(|key| key.verify_strict(&self.delta.signing_bytes(), &ed25519_dalek::Signature::from_bytes(&signature)))
+            .is_ok()
+    }
+
+    pub fn encode(&self) -> io::Result<Vec<u8>> {
+        let raw = bincode::serialize(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&raw)?;
+        encoder.finish()
+    }
+
+    pub fn decode(data: &[u8]) -> io::Result<Self> {
+        let mut raw = Vec::new();
+        GzDecoder::new(data).take(256 * 1024 * 1024).read_to_end(&mut raw)?;
+        bincode::deserialize(&raw).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffEntry {
+    pub from: u64,
+    pub to: u64,
+    /// Hex hash of block `to - 1` and hex state root at `to`.
+    pub to_block_hash: String,
+    pub to_root: String,
+    /// Size and BLAKE3 hash (hex) of the compressed file.
+    pub size: u64,
+    pub blake3: String,
+}
+
+impl DiffEntry {
+    /// A snapshot is the delta from height 0, so the snapshot of the first
+    /// checkpoint and the first diff are the same file.
+    pub fn file_name(&self) -> String {
+        format!("delta-{:08}-{:08}.bin.gz", self.from, self.to)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiffManifest {
+    pub chain_id: u64,
+    /// Hex hash of block 0.
+    pub genesis_hash: String,
+    pub interval: u64,
+    /// Hex public key every delta is signed with.
+    pub signer: String,
+    /// Consecutive checkpoint deltas, oldest first.
+    pub diffs: Vec<DiffEntry>,
+    /// The latest checkpoint as a delta from the empty state.
+    pub snapshot: Option<DiffEntry>,
+}
+
+impl StateDiffManifest {
+    fn new(genesis_hash: String, signer: &Address) -> Self {
+        Self {
+            chain_id: crate::AXIOM_CHAIN_ID,
+            genesis_hash,
+            interval: CHECKPOINT_INTERVAL_BLOCKS,
+            signer: hex::encode(signer),
+            diffs: Vec::new(),
+            snapshot: None,
+        }
+    }
+
+    /// Height of the latest published checkpoint.
+    pub fn checkpoint_height(&self) -> u64 {
+        self.diffs.last().map_or(0, |d| d.to)
+    }
+
+    /// The files a node at checkpoint `height` downloads to reach the
+    /// latest checkpoint: the snapshot from height 0, otherwise the diffs
+    /// from `height` on.
+    pub fn plan(&self, height: u64) -> Result<Vec<&DiffEntry>, StateDiffError> {
+        if height == 0 {
+            return Ok(self.snapshot.iter().collect());
+        }
+        let start = self.diffs.iter().position(|d| d.from == height).ok_or(StateDiffError::NoPath(height))?;
+        Ok(self.diffs[start..].iter().collect())
+    }
+
+    /// Check a downloaded file against `entry` and the trusted keys and
+    /// return the delta inside.
+    pub fn verify_file(&self, entry: &DiffEntry, data: &[u8], trusted: &[Address]) -> Result<StateDelta, StateDiffError> {
+        let (from, to) = (entry.from, entry.to);
+        if data.len() as u64 != entry.size || blake3::hash(data).to_hex().as_str() != entry.blake3 {
+            return Err(StateDiffError::HashMismatch { from, to });
+        }
+        let signed = SignedStateDelta::decode(data)
+            .map_err(|e| StateDiffError::Malformed { from, to, reason: e.to_string() })?;
+        if !trusted.contains(&signed.signer) || !signed.verify() {
+            return Err(StateDiffError::Untrusted { from, to });
+        }
+        let delta = signed.delta;
+        if delta.chain_id != self.chain_id || delta.from_height != from || delta.to_height != to
+            || hex::encode(delta.to_root) != entry.to_root
+        {
+            return Err(StateDiffError::Malformed { from, to, reason: "does not match manifest".to_string() });
+        }
+        Ok(delta)
+    }
+}
+
+/// Client side: bring `state`, the state after `height` blocks (0 and an
+/// empty state for a fresh node), up to the latest checkpoint in
+/// `manifest`. `fetch` downloads one listed file. Returns the new height
+/// and the issuance there.
+pub fn catch_up(
+    state: &mut State,
+    height: u64,
+    manifest: &StateDiffManifest,
+    trusted: &[Address],
+    mut fetch: impl FnMut(&DiffEntry) -> io::Result<Vec<u8>>,
+) -> Result<(u64, Option<u64>), StateDiffError> {
+    let mut at = height;
+    let mut total_issued = None;
+    for entry in manifest.plan(height)? {
+        if entry.from != at {
+            return Err(StateDiffError::NotContiguous { expected: at, found: entry.from });
+        }
+        let delta = manifest.verify_file(entry, &fetch(entry)?, trusted)?;
+        delta.apply(state)?;
+        at = delta.to_height;
+        total_issued = Some(delta.total_issued);
+    }
+    Ok((at, total_issued))
+}
+
+/// Server side: the signed deltas and manifest in one directory.
+#[derive(Debug)]
+pub struct StateDiffStore {
+    dir: PathBuf,
+    manifest: StateDiffManifest,
+    /// State and issuance at the latest checkpoint, once replayed.
+    base: Option<(State, u64)>,
+}
+
+impl StateDiffStore {
+    /// Open the deltas in `dir`, creating it if needed. A manifest for a
+    /// different genesis or signer is discarded and rebuilt.
+    pub fn open(dir: impl AsRef<Path>, genesis: &Block, signer: &Address) -> Result<Self, StateDiffError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let genesis_hash = hex::encode(genesis.legacy_hash());
+        let manifest = fs::read_to_string(dir.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str::<StateDiffManifest>(&json).ok())
+            .filter(|m| {
+                m.genesis_hash == genesis_hash && m.interval == CHECKPOINT_INTERVAL_BLOCKS && m.signer == hex::encode(signer)
+            })
+            .unwrap_or_else(|| StateDiffManifest::new(genesis_hash, signer));
+        // The base is replayed on the first update.
+        Ok(Self { dir, manifest, base: None })
+    }
+
+    pub fn manifest(&self) -> &StateDiffManifest {
+        &self.manifest
+    }
+
+    pub fn file_path(&self, from: u64, to: u64) -> Option<PathBuf> {
+        self.manifest
+            .diffs
+            .iter()
+            .chain(&self.manifest.snapshot)
+            .find(|d| d.from == from && d.to == to)
+            .map(|d| self.dir.join(d.file_name()))
+    }
+
+    /// Publish a delta for every checkpoint `blocks` has passed since the
+    /// last one, and a fresh snapshot. Deltas whose checkpoint block is no
+    /// longer on the chain are dropped first. Returns the number of deltas
+    /// written.
+    pub fn update(&mut self, blocks: &[Block], signer: &Wallet) -> Result<usize, StateDiffError> {
+        let keep = self
+            .manifest
+            .diffs
+            .iter()
+            .take_while(|d| blocks.get(d.to as usize - 1).is_some_and(|b| hex::encode(b.legacy_hash()) == d.to_block_hash))
+            .count();
+        let dropped = self.manifest.diffs.len() - keep;
+        self.manifest.diffs.truncate(keep);
+
+        let (mut base, mut base_issued) = match self.base.take() {
+            Some(base) if dropped == 0 => base,
+            _ => {
+                let (mut state, mut issued) = (State::new(), 0);
+                let checkpoint = self.manifest.checkpoint_height() as usize;
+                replay_blocks(&mut state, &mut issued, &blocks[..checkpoint], 0);
+                (state, issued)
+            }
+        };
+
+        let mut written = 0;
+        loop {
+            let from = self.manifest.checkpoint_height();
+            let to = from + CHECKPOINT_INTERVAL_BLOCKS;
+            if (blocks.len() as u64) < to {
+                break;
+            }
+            let mut next = base.clone();
+            let mut issued = base_issued;
+            replay_blocks(&mut next, &mut issued, &blocks[from as usize..to as usize], from);
+            let block_hash = blocks[to as usize - 1].legacy_hash();
+            let delta = StateDelta::between(&base, from, &next, to, block_hash, issued);
+            match self.write(delta.sign(signer)) {
+                Ok(entry) => self.manifest.diffs.push(entry),
+                Err(e) => {
+                    self.base = Some((base, base_issued));
+                    return Err(e);
+                }
+            }
+            (base, base_issued) = (next, issued);
+            written += 1;
+        }
+
+        if written > 0 || dropped > 0 {
+            let to = self.manifest.checkpoint_height();
+            if let Some(old) = self.manifest.snapshot.take() {
+                if self.manifest.diffs.iter().all(|d| d.file_name() != old.file_name()) {
+                    let _ = fs::remove_file(self.dir.join(old.file_name()));
+                }
+            }
+            if to > 0 {
+                let block_hash = blocks[to as usize - 1].legacy_hash();
+                let snapshot = StateDelta::between(&State::new(), 0, &base, to, block_hash, base_issued);
+                self.manifest.snapshot = Some(self.write(snapshot.sign(signer))?);
+            }
+            let json = serde_json::to_vec_pretty(&self.manifest)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            crate::storage::write_atomic(self.dir.join(MANIFEST_FILE), &json)?;
+        }
+        self.base = Some((base, base_issued));
+        Ok(written)
+    }
+
+    fn write(&self, signed: SignedStateDelta) -> Result<DiffEntry, StateDiffError> {
+        let data = signed.encode()?;
+        let delta = &signed.delta;
+        let entry = DiffEntry {
+            from: delta.from_height,
+            to: delta.to_height,
+            to_block_hash: hex::encode(delta.to_block_hash),
+            to_root: hex::encode(delta.to_root),
+            size: data.len() as u64,
+            blake3: blake3::hash(&data).to_hex().to_string(),
+        };
+        crate::storage::write_atomic(self.dir.join(entry.file_name()), &data)?;
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::Transaction;
+
+    fn chain(len: u64) -> Vec<Block> {
+        let mut blocks: Vec<Block> = Vec::new();
+        for slot in 0..len {
+            let parent = blocks.last().map_or([0u8; 32], |b| b.legacy_hash());
+            blocks.push(Block {
+                timestamp: 1_000 + slot,
+                miner: [(slot % 3) as u8 + 1; 32],
+                nonce: slot,
+                ..Block::test(parent, slot)
+            });
+        }
+        blocks
+    }
+
+    fn signer(seed: u8) -> Wallet {
+        crate::payout::payout_wallet(&Wallet { secret_key: [seed; 32], address: [0u8; 32] }, 0)
+    }
+
+    #[test]
+    fn test_delta_apply_checks_roots() {
+        let mut from = State::new();
+        from.credit([1u8; 32], 1_000);
+        let mut to = from.clone();
+        let tx = Transaction::new([1u8; 32], [2u8; 32], 300, 10, 0, vec![], vec![]);
+        to.apply_tx_logged(&tx, 5, 0, &mut Default::default()).unwrap();
+
+        let delta = StateDelta::between(&from, 5, &to, 6, [9u8; 32], 0);
+        assert_eq!(delta.accounts.len(), 2);
+        let mut state = from.clone();
+        delta.apply(&mut state).unwrap();
+        assert_eq!(state.root(), to.root());
+        assert_eq!(state.history(&[2u8; 32]).len(), 1);
+
+        // Applying it again starts from the wrong root and changes nothing.
+        assert!(matches!(delta.apply(&mut state), Err(StateDiffError::RootMismatch { height: 5 })));
+        assert_eq!(state.root(), to.root());
+    }
+
+    #[test]
+    fn test_publish_and_catch_up() {
+        let dir = std::env::temp_dir().join(format!("axiom_test_state_diff_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let blocks = chain(2 * CHECKPOINT_INTERVAL_BLOCKS + 5);
+        let server = signer(3);
+
+        let mut store = StateDiffStore::open(&dir, &blocks[0], &server.address).unwrap();
+        assert_eq!(store.update(&blocks, &server).unwrap(), 2);
+        assert_eq!(store.update(&blocks, &server).unwrap(), 0);
+        let manifest = store.manifest().clone();
+        let fetch = |d: &DiffEntry| fs::read(dir.join(d.file_name()));
+
+        let mut expected = State::new();
+        let mut issued = 0;
+        replay_blocks(&mut expected, &mut issued, &blocks[..2 * CHECKPOINT_INTERVAL_BLOCKS as usize], 0);
+
+        // A fresh node takes the snapshot; one at the first checkpoint
+        // takes only the second diff.
+        let mut fresh = State::new();
+        let (height, total) = catch_up(&mut fresh, 0, &manifest, &[server.address], fetch).unwrap();
+        assert_eq!((height, total), (2 * CHECKPOINT_INTERVAL_BLOCKS, Some(issued)));
+        assert_eq!(fresh.root(), expected.root());
+
+        let mut behind = State::new();
+        let mut behind_issued = 0;
+        replay_blocks(&mut behind, &mut behind_issued, &blocks[..CHECKPOINT_INTERVAL_BLOCKS as usize], 0);
+        assert_eq!(manifest.plan(CHECKPOINT_INTERVAL_BLOCKS).unwrap().len(), 1);
+        catch_up(&mut behind, CHECKPOINT_INTERVAL_BLOCKS, &manifest, &[server.address], fetch).unwrap();
+        assert_eq!(behind.root(), expected.root());
+
+        // Deltas from a key the node does not trust are refused.
+        let result = catch_up(&mut State::new(), 0, &manifest, &[signer(4).address], fetch);
+        let _ = fs::remove_dir_all(&dir);
+        assert!(matches!(result, Err(StateDiffError::Untrusted { .. })));
+    }
+}
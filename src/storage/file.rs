@@ -0,0 +1,148 @@
+// src/storage/file.rs - Flat-file ChainStore
+//
+// The original storage layout: the whole chain is one bincode-encoded
+// `Vec<Block>` (the same format as `axiom_chain.dat`), with the state and
+// indexes in sibling files. Everything is held in memory and rewritten on
+// `flush` via write-to-temp + rename.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::ChainStore;
+use crate::block::Block;
+use crate::error::{AxiomError, Result};
+use crate::state::State;
+
+const CHAIN_FILE: &str = "axiom_chain.dat";
+const STATE_FILE: &str = "axiom_state.dat";
+const INDEX_FILE: &str = "axiom_index.dat";
+
+type Indexes = BTreeMap<String, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+pub struct FileChainStore {
+    dir: PathBuf,
+    blocks: Vec<Block>,
+    state: Option<State>,
+    indexes: Indexes,
+    dirty: bool,
+}
+
+impl FileChainStore {
+    /// Open (or create) a store in `dir`. Missing files start empty.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            blocks: read_bincode(&dir.join(CHAIN_FILE))?.unwrap_or_default(),
+            state: read_bincode(&dir.join(STATE_FILE))?,
+            indexes: read_bincode(&dir.join(INDEX_FILE))?.unwrap_or_default(),
+            dir,
+            dirty: false,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl ChainStore for FileChainStore {
+    fn put_block(&mut self, height: u64, block: &Block) -> Result<()> {
+        let height = height as usize;
+        if height < self.blocks.len() {
+            self.blocks[height] = block.clone();
+        } else if height == self.blocks.len() {
+            self.blocks.push(block.clone());
+        } else {
+            return Err(AxiomError::DatabaseError(format!(
+                "block {} written past chain end {}",
+                height,
+                self.blocks.len()
+            )));
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn get_block(&self, height: u64) -> Result<Option<Block>> {
+        Ok(self.blocks.get(height as usize).cloned())
+    }
+
+    fn block_count(&self) -> Result<u64> {
+        Ok(self.blocks.len() as u64)
+    }
+
+    fn truncate_blocks(&mut self, height: u64) -> Result<()> {
+        if (height as usize) < self.blocks.len() {
+            self.blocks.truncate(height as usize);
+            self.dirty = true;
+        }
+        Ok(())
+    }
+
+    fn put_state(&mut self, state: &State) -> Result<()> {
+        self.state = Some(state.clone());
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn get_state(&self) -> Result<Option<State>> {
+        Ok(self.state.clone())
+    }
+
+    fn put_index(&mut self, index: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        self.indexes
+            .entry(index.to_string())
+            .or_default()
+            .insert(key.to_vec(), value.to_vec());
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn get_index(&self, index: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.indexes.get(index).and_then(|i| i.get(key)).cloned())
+    }
+
+    fn delete_index(&mut self, index: &str, key: &[u8]) -> Result<()> {
+        if let Some(i) = self.indexes.get_mut(index) {
+            if i.remove(key).is_some() {
+                self.dirty = true;
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        write_atomic(&self.dir.join(CHAIN_FILE), &bincode::serialize(&self.blocks)?)?;
+        if let Some(state) = &self.state {
+            write_atomic(&self.dir.join(STATE_FILE), &bincode::serialize(state)?)?;
+        }
+        write_atomic(&self.dir.join(INDEX_FILE), &bincode::serialize(&self.indexes)?)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn load_blocks(&self) -> Result<Vec<Block>> {
+        Ok(self.blocks.clone())
+    }
+}
+
+fn read_bincode<T: serde::de::DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    match fs::read(path) {
+        Ok(bytes) if bytes.is_empty() => Ok(None),
+        Ok(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
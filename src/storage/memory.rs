@@ -0,0 +1,96 @@
+// src/storage/memory.rs - In-memory ChainStore
+//
+// Nothing touches the filesystem; used by unit tests and simulations that
+// spin up many nodes in one process.
+
+use std::collections::HashMap;
+
+use super::ChainStore;
+use crate::block::Block;
+use crate::error::{AxiomError, Result};
+use crate::state::State;
+
+#[derive(Default)]
+pub struct MemoryChainStore {
+    blocks: Vec<Block>,
+    state: Option<State>,
+    indexes: HashMap<String, HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryChainStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A store pre-populated with `blocks`, genesis first.
+    pub fn with_blocks(blocks: Vec<Block>) -> Self {
+        Self { blocks, ..Self::default() }
+    }
+}
+
+impl ChainStore for MemoryChainStore {
+    fn put_block(&mut self, height: u64, block: &Block) -> Result<()> {
+        let height = height as usize;
+        if height < self.blocks.len() {
+            self.blocks[height] = block.clone();
+        } else if height == self.blocks.len() {
+            self.blocks.push(block.clone());
+        } else {
+            return Err(AxiomError::DatabaseError(format!(
+                "block {} written past chain end {}",
+                height,
+                self.blocks.len()
+            )));
+        }
+        Ok(())
+    }
+
+    fn get_block(&self, height: u64) -> Result<Option<Block>> {
+        Ok(self.blocks.get(height as usize).cloned())
+    }
+
+    fn block_count(&self) -> Result<u64> {
+        Ok(self.blocks.len() as u64)
+    }
+
+    fn truncate_blocks(&mut self, height: u64) -> Result<()> {
+        self.blocks.truncate(height as usize);
+        Ok(())
+    }
+
+    fn put_state(&mut self, state: &State) -> Result<()> {
+        self.state = Some(state.clone());
+        Ok(())
+    }
+
+    fn get_state(&self) -> Result<Option<State>> {
+        Ok(self.state.clone())
+    }
+
+    fn put_index(&mut self, index: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        self.indexes
+            .entry(index.to_string())
+            .or_default()
+            .insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+
+    fn get_index(&self, index: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.indexes.get(index).and_then(|i| i.get(key)).cloned())
+    }
+
+    fn delete_index(&mut self, index: &str, key: &[u8]) -> Result<()> {
+        if let Some(i) = self.indexes.get_mut(index) {
+            i.remove(key);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn load_blocks(&self) -> Result<Vec<Block>> {
+        Ok(self.blocks.clone())
+    }
+}
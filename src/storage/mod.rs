@@ -0,0 +1,631 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use crate::block::Block;
+use crate::block_template::LocalBlockMetadata;
+use crate::config::{StorageBackend, StorageConfig};
+use crate::error::{AxiomError, Result};
+use crate::state::State;
+use crate::transaction::Address;
+
+mod file;
+mod memory;
+#[cfg(feature = "rocksdb")]
+mod rocksdb_store;
+mod sled_store;
+
+pub use file::FileChainStore;
+pub use memory::MemoryChainStore;
+#[cfg(feature = "rocksdb")]
+pub use rocksdb_store::RocksChainStore;
+pub use sled_store::SledChainStore;
+
+/// Where a transaction sits in the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TxLocation {
+    pub height: u64,
+    /// Index within the block's transactions.
+    pub position: u32,
+}
+
+impl TxLocation {
+    /// Big-endian `height ‖ position`, which sorts in chain order.
+    pub fn to_bytes(&self) -> [u8; 12] {
+        let mut out = [0u8; 12];
+        out[..8].copy_from_slice(&self.height.to_be_bytes());
+        out[8..].copy_from_slice(&self.position.to_be_bytes());
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; 12] = bytes.try_into().ok()?;
+        Some(Self {
+            height: u64::from_be_bytes(bytes[..8].try_into().ok()?),
+            position: u32::from_be_bytes(bytes[8..].try_into().ok()?),
+        })
+    }
+}
+
+/// Backend-agnostic chain storage.
+///
+/// Blocks are addressed by height, the account state is stored as a single
+/// snapshot, and secondary indexes are opaque key/value pairs grouped under
+/// an index name. Writes may be buffered until `flush`.
+pub trait ChainStore: Send {
+    fn put_block(&mut self, height: u64, block: &Block) -> Result<()>;
+    fn get_block(&self, height: u64) -> Result<Option<Block>>;
+    /// Number of stored blocks (the next height to be written).
+    fn block_count(&self) -> Result<u64>;
+    /// Drop every block at or above `height` (reorg rollback).
+    fn truncate_blocks(&mut self, height: u64) -> Result<()>;
+
+    fn put_state(&mut self, state: &State) -> Result<()>;
+    fn get_state(&self) -> Result<Option<State>>;
+
+    fn put_index(&mut self, index: &str, key: &[u8], value: &[u8]) -> Result<()>;
+    fn get_index(&self, index: &str, key: &[u8]) -> Result<Option<Vec<u8>>>;
+    fn delete_index(&mut self, index: &str, key: &[u8]) -> Result<()>;
+
+    /// Make all previous writes durable.
+    fn flush(&mut self) -> Result<()>;
+
+    /// Load the whole chain, genesis first.
+    fn load_blocks(&self) -> Result<Vec<Block>> {
+        let count = self.block_count()?;
+        let mut blocks = Vec::with_capacity(count as usize);
+        for height in 0..count {
+            match self.get_block(height)? {
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Replace the stored chain with `blocks` and flush.
+    fn save_chain(&mut self, blocks: &[Block]) -> Result<()> {
+        let common = self.load_blocks()?.iter().zip(blocks).take_while(|(a, b)| a == b).count();
+        self.truncate_blocks(common as u64)?;
+        for (height, block) in blocks.iter().enumerate().skip(common) {
+            self.put_block(height as u64, block)?;
+        }
+        self.flush()
+    }
+
+    // Lookups by key. The defaults scan the chain; backends that keep
+    // lookup tables (RocksDB) override them.
+
+    /// Height of the block whose `legacy_hash()` is `hash`.
+    fn block_height(&self, hash: &[u8; 32]) -> Result<Option<u64>> {
+        Ok(self.load_blocks()?.iter().position(|b| b.legacy_hash() == *hash).map(|h| h as u64))
+    }
+
+    fn transaction_location(&self, txid: &[u8; 32]) -> Result<Option<TxLocation>> {
+        Ok(self.load_blocks()?.iter().enumerate().find_map(|(height, block)| {
+            let position = block.transactions.iter().position(|tx| tx.legacy_hash() == *txid)?;
+            Some(TxLocation { height: height as u64, position: position as u32 })
+        }))
+    }
+
+    /// Every transaction sending from or paying to `address`, in chain order.
+    fn address_transactions(&self, address: &Address) -> Result<Vec<TxLocation>> {
+        let mut locations = Vec::new();
+        for (height, block) in self.load_blocks()?.iter().enumerate() {
+            for (position, tx) in block.transactions.iter().enumerate() {
+                if tx.from == *address || tx.to == *address {
+                    locations.push(TxLocation { height: height as u64, position: position as u32 });
+                }
+            }
+        }
+        Ok(locations)
+    }
+}
+
+/// Version of the block encoding in chain storage. Every field added to
+/// `Block` changes its bincode layout, so raise this with it: storage of
+/// another version is refused rather than misread. Version 1 is the
+/// unversioned layout from before `coinbase`, `uncles` and `vdf_witness`;
+/// those blocks hash differently and cannot be migrated onto this chain.
+pub const STORAGE_VERSION: u32 = 2;
+
+/// Index entry holding a database's `STORAGE_VERSION`.
+const VERSION_INDEX: &str = "meta";
+const VERSION_KEY: &[u8] = b"storage_version";
+
+/// Leads a versioned chain file, followed by the version (u32 LE).
+const CHAIN_FILE_MAGIC: [u8; 4] = *b"AXCH";
+
+fn unsupported_version(found: u32) -> AxiomError {
+    AxiomError::DatabaseError(format!(
+        "chain storage is version {}, this release reads version {}; move it aside to resync",
+        found, STORAGE_VERSION
+    ))
+}
+
+/// Refuse `store` unless it holds blocks of this `STORAGE_VERSION`. An
+/// empty store is stamped with it.
+fn check_version(store: &mut dyn ChainStore) -> Result<()> {
+    let found = match store.get_index(VERSION_INDEX, VERSION_KEY)? {
+        Some(bytes) => bytes.try_into().map(u32::from_le_bytes).unwrap_or(0),
+        None if store.block_count()? == 0 => {
+            store.put_index(VERSION_INDEX, VERSION_KEY, &STORAGE_VERSION.to_le_bytes())?;
+            return store.flush();
+        }
+        None => 1,
+    };
+    if found != STORAGE_VERSION {
+        return Err(unsupported_version(found));
+    }
+    Ok(())
+}
+
+/// Open the `ChainStore` selected by `backend` under `config.data_dir`.
+/// `StorageBackend::File` is the flat-file store in `data_dir` itself.
+/// Fails if the store was written with another `STORAGE_VERSION`.
+pub fn open_store(backend: StorageBackend, config: &StorageConfig) -> Result<Box<dyn ChainStore>> {
+    let dir = &config.data_dir;
+    let mut store: Box<dyn ChainStore> = match backend {
+        StorageBackend::File => Box::new(FileChainStore::open(dir)?),
+        StorageBackend::Sled => Box::new(SledChainStore::open(dir.join("chain.sled"))?),
+        #[cfg(feature = "rocksdb")]
+        StorageBackend::RocksDb => Box::new(RocksChainStore::open(
+            dir.join("chain.rocksdb"),
+            config.cache_size_mb,
+            config.compression,
+        )?),
+        #[cfg(not(feature = "rocksdb"))]
+        StorageBackend::RocksDb => {
+            return Err(AxiomError::DatabaseError(
+                "this binary was built without the `rocksdb` feature".to_string(),
+            ))
+        }
+    };
+    check_version(store.as_mut())?;
+    Ok(store)
+}
+
+/// Chain persistence for the running node. `[storage] backend = "file"`
+/// (the default) keeps the original `axiom_chain.dat` in the working
+/// directory; `"sled"` and `"rocksdb"` use a database under `data_dir`,
+/// which also holds the state snapshot. Convert an existing chain with
+/// `axiom-migrate`.
+pub enum NodeStorage {
+    Legacy,
+    Database(Box<dyn ChainStore>),
+}
+
+impl NodeStorage {
+    pub fn open(config: &StorageConfig) -> Result<Self> {
+        match config.backend {
+            StorageBackend::File => Ok(Self::Legacy),
+            backend => {
+                std::fs::create_dir_all(&config.data_dir)?;
+                Ok(Self::Database(open_store(backend, config)?))
+            }
+        }
+    }
+
+    /// The stored chain, or `None` if there is none. An unreadable chain
+    /// is an error and is left in place: the node must not start over it.
+    pub fn load_chain(&self) -> Result<Option<Vec<Block>>> {
+        #[allow(unused_mut)]
+        let mut blocks = match self {
+            Self::Legacy => read_chain_file()?.unwrap_or_default(),
+            Self::Database(store) => store.load_blocks()?,
+        };
+        if blocks.is_empty() {
+            if matches!(self, Self::Database(_)) && Path::new(DB_PATH).exists() {
+                log::warn!("STORAGE: Database is empty but {} exists — run axiom-migrate to import it", DB_PATH);
+            }
+            return Ok(None);
+        }
+        #[cfg(feature = "chaos")]
+        if let Some(height) = crate::chaos::corrupt_on_read(&mut blocks) {
+            log::warn!("CHAOS: Corrupted block {} on read", height);
+        }
+        Ok(Some(blocks))
+    }
+
+    /// Repair interrupted writes before the first `load_chain`. Database
+    /// backends journal their own writes, so only the legacy file needs it.
+    pub fn recover(&self) -> ChainFileRecovery {
+        match self {
+            Self::Legacy => recover_chain_file(),
+            Self::Database(_) => ChainFileRecovery::Clean,
+        }
+    }
+
+    /// Persist `blocks` and, for database backends, the `state` they produce.
+    pub fn save_chain(&mut self, blocks: &[Block], state: &State) {
+        let store = match self {
+            Self::Legacy => return save_chain(blocks),
+            Self::Database(store) => store,
+        };
+        #[cfg(feature = "chaos")]
+        crate::chaos::delay_storage_write();
+        let result = store
+            .save_chain(blocks)
+            .and_then(|()| store.put_state(state))
+            .and_then(|()| store.flush());
+        if let Err(e) = result {
+            log::error!("STORAGE: Database write failed: {}", e);
+        }
+    }
+}
+
+/// Import a legacy `axiom_chain.dat` into `dest`. The chain is replayed
+/// through consensus validation first, so a corrupt file is refused rather
+/// than copied, and the replayed state is stored alongside the blocks.
+/// Returns the number of blocks written.
+pub fn migrate_legacy(legacy_path: impl AsRef<Path>, dest: &mut dyn ChainStore) -> Result<u64> {
+    let blocks = decode_chain(&std::fs::read(legacy_path)?)?;
+    let chain = crate::chain::Timechain::from_saved_blocks(blocks)
+        .map_err(|e| AxiomError::DatabaseError(format!("legacy chain failed validation: {}", e)))?;
+    dest.save_chain(&chain.blocks)?;
+    dest.put_state(&chain.state)?;
+    dest.flush()?;
+    let written = dest.block_count()?;
+    if written != chain.blocks.len() as u64 {
+        return Err(AxiomError::DatabaseError(format!(
+            "migrated {} of {} blocks",
+            written,
+            chain.blocks.len()
+        )));
+    }
+    Ok(written)
+}
+
+const DB_PATH: &str = "axiom_chain.dat";
+const BLOCK_META_PATH: &str = "axiom_block_meta.jsonl";
+
+/// Write `data` to `path` so that a crash leaves either the old file or the
+/// new one, never a mix: the bytes go to `<path>.tmp`, are synced, and the
+/// temp file is renamed over `path`. The directory is synced afterwards so
+/// the rename itself survives a power cut.
+pub fn write_atomic(path: impl AsRef<Path>, data: &[u8]) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = std::path::PathBuf::from(tmp);
+    let result = File::create(&tmp).and_then(|mut file| {
+        file.write_all(data)?;
+        file.sync_all()
+    });
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+    std::fs::rename(&tmp, path)?;
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// ATOMIC SAVE: Writes the entire chain to disk.
+/// Uses a temporary file strategy to ensure that a crash during saving
+/// does not corrupt the existing blockchain data.
+pub fn save_chain(blocks: &[Block]) {
+    #[cfg(feature = "chaos")]
+    crate::chaos::delay_storage_write();
+
+    if let Err(e) = save_chain_at(DB_PATH, blocks) {
+        log::error!("STORAGE: Could not write to disk: {}", e);
+    }
+}
+
+/// `blocks` as a chain file: `CHAIN_FILE_MAGIC`, `STORAGE_VERSION`, then
+/// the bincode-encoded blocks.
+fn encode_chain(blocks: &[Block]) -> Result<Vec<u8>> {
+    let mut data = CHAIN_FILE_MAGIC.to_vec();
+    data.extend_from_slice(&STORAGE_VERSION.to_le_bytes());
+    bincode::serialize_into(&mut data, blocks)?;
+    Ok(data)
+}
+
+/// Decode a chain file written by `encode_chain`. Files without the header
+/// are version 1.
+fn decode_chain(data: &[u8]) -> Result<Vec<Block>> {
+    let header = data.strip_prefix(&CHAIN_FILE_MAGIC).and_then(|rest| rest.get(..4));
+    let version = header.map_or(1, |bytes| u32::from_le_bytes(bytes.try_into().unwrap_or_default()));
+    if version != STORAGE_VERSION {
+        return Err(unsupported_version(version));
+    }
+    Ok(bincode::deserialize(&data[CHAIN_FILE_MAGIC.len() + 4..])?)
+}
+
+fn save_chain_at(path: impl AsRef<Path>, blocks: &[Block]) -> Result<()> {
+    let path = path.as_ref();
+    let encoded = encode_chain(blocks)?;
+    // Keep the previous generation as `.bak` (a hard link, so no copy) for
+    // recover_chain_file to fall back on if the current file is damaged.
+    let backup = sibling(path, "bak");
+    if path.exists() {
+        let _ = std::fs::remove_file(&backup);
+        let _ = std::fs::hard_link(path, &backup);
+    }
+    write_atomic(path, &encoded)?;
+    Ok(())
+}
+
+fn sibling(path: &Path, extension: &str) -> std::path::PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(extension);
+    name.into()
+}
+
+fn decodes_as_chain(path: &Path) -> bool {
+    std::fs::read(path)
+        .ok()
+        .and_then(|data| decode_chain(&data).ok())
+        .is_some_and(|blocks| !blocks.is_empty())
+}
+
+/// What `recover_chain_file` found at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainFileRecovery {
+    /// The chain file is intact and nothing was left over.
+    Clean,
+    /// A write was interrupted before its rename; the leftover temp file
+    /// was removed and the last complete save kept.
+    DiscardedPartialWrite,
+    /// The chain file was missing or damaged but a fully written temp file
+    /// was waiting to be renamed; it was put in place.
+    CompletedPendingWrite,
+    /// The chain file was damaged; the previous generation was restored.
+    RestoredBackup,
+    /// The chain file is damaged and nothing could replace it.
+    Unrecoverable,
+}
+
+/// Detect and repair what a crash in the middle of `save_chain` leaves
+/// behind. Run before the chain is loaded.
+pub fn recover_chain_file() -> ChainFileRecovery {
+    recover_chain_file_at(Path::new(DB_PATH))
+}
+
+fn recover_chain_file_at(path: &Path) -> ChainFileRecovery {
+    let tmp = sibling(path, "tmp");
+    let backup = sibling(path, "bak");
+    if decodes_as_chain(path) {
+        if tmp.exists() {
+            let _ = std::fs::remove_file(&tmp);
+            return ChainFileRecovery::DiscardedPartialWrite;
+        }
+        return ChainFileRecovery::Clean;
+    }
+    if decodes_as_chain(&tmp) && std::fs::rename(&tmp, path).is_ok() {
+        return ChainFileRecovery::CompletedPendingWrite;
+    }
+    let _ = std::fs::remove_file(&tmp);
+    if !path.exists() && !backup.exists() {
+        return ChainFileRecovery::Clean;
+    }
+    if decodes_as_chain(&backup) && std::fs::copy(&backup, path).is_ok() {
+        return ChainFileRecovery::RestoredBackup;
+    }
+    ChainFileRecovery::Unrecoverable
+}
+
+/// The chain in `axiom_chain.dat`, or `None` if there is none. The file
+/// is never removed here: one this release cannot decode (damaged, or of
+/// another `STORAGE_VERSION`) is an error for the operator to resolve.
+pub fn read_chain_file() -> Result<Option<Vec<Block>>> {
+    read_chain_file_at(Path::new(DB_PATH))
+}
+
+fn read_chain_file_at(path: &Path) -> Result<Option<Vec<Block>>> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if data.is_empty() {
+        return Ok(None);
+    }
+    decode_chain(&data).map(Some)
+}
+
+/// The chain in `axiom_chain.dat` for tools reading alongside the node:
+/// `None` if there is none or it cannot be read.
+pub fn load_chain() -> Option<Vec<Block>> {
+    match read_chain_file() {
+        #[allow(unused_mut)]
+        Ok(Some(mut blocks)) => {
+            #[cfg(feature = "chaos")]
+            if let Some(height) = crate::chaos::corrupt_on_read(&mut blocks) {
+                log::warn!("CHAOS: Corrupted block {} on read", height);
+            }
+            log::info!("STORAGE: Loaded {} blocks. Integrity verified.", blocks.len());
+            Some(blocks)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("STORAGE: Cannot read {}: {}", DB_PATH, e);
+            None
+        }
+    }
+}
+
+/// Append local (non-consensus) metadata for a block this node mined,
+/// one JSON object per line.
+pub fn append_block_metadata(meta: &LocalBlockMetadata) {
+    #[cfg(feature = "chaos")]
+    crate::chaos::delay_storage_write();
+
+    let line = match serde_json::to_string(meta) {
+        Ok(line) => line,
+        Err(e) => {
+            log::error!("STORAGE: Block metadata serialization failed: {}", e);
+            return;
+        }
+    };
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(BLOCK_META_PATH)
+        .and_then(|mut file| writeln!(file, "{}", line));
+    if let Err(e) = result {
+        log::error!("STORAGE: Could not append block metadata: {}", e);
+    }
+}
+
+/// Load all locally recorded block metadata. Malformed lines are skipped.
+pub fn load_block_metadata() -> Vec<LocalBlockMetadata> {
+    let file = match File::open(BLOCK_META_PATH) {
+        Ok(f) => f,
+        Err(_) => return Vec::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(slot: u64) -> Block {
+        Block {
+            timestamp: 1_000 + slot * 1800,
+            miner: [1u8; 32],
+            nonce: slot,
+            ..Block::test([slot as u8; 32], slot)
+        }
+    }
+
+    fn exercise(store: &mut dyn ChainStore) {
+        let chain: Vec<Block> = (0..4).map(block).collect();
+        store.save_chain(&chain).unwrap();
+        assert_eq!(store.block_count().unwrap(), 4);
+        assert_eq!(store.get_block(2).unwrap(), Some(chain[2].clone()));
+        assert_eq!(store.get_block(9).unwrap(), None);
+
+        // Reorg onto a shorter branch that diverges at height 2.
+        let mut fork = chain[..2].to_vec();
+        fork.push(Block { nonce: 99, ..block(2) });
+        store.save_chain(&fork).unwrap();
+        assert_eq!(store.load_blocks().unwrap(), fork);
+
+        let mut state = State::new();
+        state.credit([7u8; 32], 500);
+        store.put_state(&state).unwrap();
+        assert_eq!(store.get_state().unwrap().unwrap().balance(&[7u8; 32]), 500);
+
+        store.put_index("tx", b"abc", b"2").unwrap();
+        assert_eq!(store.get_index("tx", b"abc").unwrap(), Some(b"2".to_vec()));
+        assert_eq!(store.get_index("other", b"abc").unwrap(), None);
+        store.delete_index("tx", b"abc").unwrap();
+        assert_eq!(store.get_index("tx", b"abc").unwrap(), None);
+        store.flush().unwrap();
+    }
+
+    #[test]
+    fn test_default_lookups() {
+        let mut chain: Vec<Block> = (0..3).map(block).collect();
+        let tx = crate::transaction::Transaction::new([1u8; 32], [2u8; 32], 10, 1, 0, vec![], vec![]);
+        chain[2].transactions.push(tx.clone());
+        let store = MemoryChainStore::with_blocks(chain.clone());
+
+        assert_eq!(store.block_height(&chain[1].legacy_hash()).unwrap(), Some(1));
+        let location = TxLocation { height: 2, position: 0 };
+        assert_eq!(store.transaction_location(&tx.legacy_hash()).unwrap(), Some(location));
+        assert_eq!(store.address_transactions(&[2u8; 32]).unwrap(), vec![location]);
+        assert_eq!(TxLocation::from_bytes(&location.to_bytes()), Some(location));
+    }
+
+    #[test]
+    fn test_migrate_legacy_chain() {
+        let path = std::env::temp_dir().join(format!("axiom_test_legacy_{}.dat", std::process::id()));
+        let blocks = vec![crate::genesis::genesis()];
+        std::fs::write(&path, encode_chain(&blocks).unwrap()).unwrap();
+
+        let mut dest = SledChainStore::temporary().unwrap();
+        assert_eq!(migrate_legacy(&path, &mut dest).unwrap(), 1);
+        assert_eq!(dest.load_blocks().unwrap(), blocks);
+        assert!(dest.get_state().unwrap().is_some());
+
+        // A chain that fails validation is not copied.
+        std::fs::write(&path, encode_chain(&[block(0)]).unwrap()).unwrap();
+        assert!(migrate_legacy(&path, &mut MemoryChainStore::new()).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_recover_chain_file() {
+        let path = std::env::temp_dir().join(format!("axiom_test_recover_{}.dat", std::process::id()));
+        let (tmp, backup) = (sibling(&path, "tmp"), sibling(&path, "bak"));
+        let first = vec![block(0)];
+        let second = vec![block(0), block(1)];
+        save_chain_at(&path, &first).unwrap();
+        save_chain_at(&path, &second).unwrap();
+        assert_eq!(recover_chain_file_at(&path), ChainFileRecovery::Clean);
+
+        // Killed mid-write: the half-written temp file is dropped.
+        std::fs::write(&tmp, b"partial").unwrap();
+        assert_eq!(recover_chain_file_at(&path), ChainFileRecovery::DiscardedPartialWrite);
+        assert!(!tmp.exists());
+
+        // A truncated chain file falls back to the previous generation.
+        let encoded = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &encoded[..encoded.len() / 2]).unwrap();
+        assert_eq!(recover_chain_file_at(&path), ChainFileRecovery::RestoredBackup);
+        assert_eq!(read_chain_file_at(&path).unwrap(), Some(first));
+
+        // A complete temp file whose rename never happened is finished.
+        std::fs::write(&tmp, encode_chain(&second).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(recover_chain_file_at(&path), ChainFileRecovery::CompletedPendingWrite);
+        assert!(decodes_as_chain(&path));
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&backup);
+    }
+
+    #[test]
+    fn test_other_storage_versions_are_refused_and_kept() {
+        let path = std::env::temp_dir().join(format!("axiom_test_version_{}.dat", std::process::id()));
+        let blocks = vec![block(0)];
+        save_chain_at(&path, &blocks).unwrap();
+        assert_eq!(read_chain_file_at(&path).unwrap(), Some(blocks.clone()));
+
+        // A file from before the version header, and one from a later
+        // release, are both refused, and both stay on disk.
+        let unversioned = bincode::serialize(&blocks).unwrap();
+        let mut newer = encode_chain(&blocks).unwrap();
+        newer[4..8].copy_from_slice(&(STORAGE_VERSION + 1).to_le_bytes());
+        for data in [unversioned, newer] {
+            std::fs::write(&path, &data).unwrap();
+            assert!(read_chain_file_at(&path).is_err());
+            assert_eq!(std::fs::read(&path).unwrap(), data);
+        }
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(read_chain_file_at(&path).unwrap(), None);
+
+        // Databases are stamped when created and checked when opened.
+        let mut store = MemoryChainStore::new();
+        check_version(&mut store).unwrap();
+        store.save_chain(&blocks).unwrap();
+        check_version(&mut store).unwrap();
+        let mut unstamped = MemoryChainStore::with_blocks(blocks);
+        assert!(check_version(&mut unstamped).is_err());
+    }
+
+    #[test]
+    fn test_backends_behave_alike() {
+        exercise(&mut MemoryChainStore::new());
+        exercise(&mut SledChainStore::temporary().unwrap());
+
+        let dir = std::env::temp_dir().join(format!("axiom_test_store_{}", std::process::id()));
+        exercise(&mut FileChainStore::open(&dir).unwrap());
+        let reopened = FileChainStore::open(&dir).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(reopened.block_count().unwrap(), 3);
+        assert!(reopened.get_state().unwrap().is_some());
+    }
+}
@@ -0,0 +1,200 @@
+// src/storage/rocksdb_store.rs - RocksDB-backed ChainStore
+//
+// One column family per kind of record:
+//
+//   blocks     height (u64 BE)                       -> bincode Block
+//   block_hash block hash                            -> height (u64 BE)
+//   tx         txid                                  -> height (u64 BE) ‖ position (u32 BE)
+//   address    address ‖ height (u64 BE) ‖ position  -> (empty)
+//   state      "current"                             -> bincode State
+//   index      name ‖ 0x00 ‖ key                     -> value
+//
+// A block and all of its lookup entries are written in one WriteBatch, so
+// the hash, transaction and address lookups never disagree with `blocks`.
+// Built only with `--features rocksdb`, which needs a C++ toolchain and
+// libclang for librocksdb-sys.
+
+use std::path::Path;
+
+use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Direction, IteratorMode, Options, WriteBatch, DB};
+
+use super::{ChainStore, TxLocation};
+use crate::block::Block;
+use crate::error::{AxiomError, Result};
+use crate::state::State;
+use crate::transaction::Address;
+
+const CF_BLOCKS: &str = "blocks";
+const CF_BLOCK_HASH: &str = "block_hash";
+const CF_TX: &str = "tx";
+const CF_ADDRESS: &str = "address";
+const CF_STATE: &str = "state";
+const CF_INDEX: &str = "index";
+const COLUMN_FAMILIES: [&str; 6] = [CF_BLOCKS, CF_BLOCK_HASH, CF_TX, CF_ADDRESS, CF_STATE, CF_INDEX];
+
+const STATE_KEY: &[u8] = b"current";
+
+pub struct RocksChainStore {
+    db: DB,
+}
+
+impl RocksChainStore {
+    /// Open (or create) the database at `path`. `cache_size_mb` sizes the
+    /// block cache; `compression` enables LZ4 for every column family.
+    pub fn open(path: impl AsRef<Path>, cache_size_mb: usize, compression: bool) -> Result<Self> {
+        let mut cf_opts = Options::default();
+        if compression {
+            cf_opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        }
+        let descriptors = COLUMN_FAMILIES
+            .iter()
+            .map(|name| ColumnFamilyDescriptor::new(*name, cf_opts.clone()));
+
+        let mut db_opts = Options::default();
+        db_opts.create_if_missing(true);
+        db_opts.create_missing_column_families(true);
+        db_opts.optimize_for_point_lookup(cache_size_mb.max(8) as u64);
+
+        Ok(Self { db: DB::open_cf_descriptors(&db_opts, path, descriptors)? })
+    }
+
+    fn cf(&self, name: &str) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(name)
+            .ok_or_else(|| AxiomError::DatabaseError(format!("missing column family {}", name)))
+    }
+
+    /// Lookup entries for `block` at `height`: (column family, key, value).
+    fn lookup_entries(height: u64, block: &Block) -> Vec<(&'static str, Vec<u8>, Vec<u8>)> {
+        let mut entries = vec![(CF_BLOCK_HASH, block.hash().to_vec(), height.to_be_bytes().to_vec())];
+        for (position, tx) in block.transactions.iter().enumerate() {
+            let location = TxLocation { height, position: position as u32 };
+            entries.push((CF_TX, tx.hash().to_vec(), location.to_bytes().to_vec()));
+            for address in [tx.from, tx.to] {
+                let mut key = address.to_vec();
+                key.extend_from_slice(&location.to_bytes());
+                entries.push((CF_ADDRESS, key, Vec::new()));
+            }
+        }
+        entries
+    }
+
+    fn index_key(index: &str, key: &[u8]) -> Vec<u8> {
+        let mut full = index.as_bytes().to_vec();
+        full.push(0);
+        full.extend_from_slice(key);
+        full
+    }
+}
+
+impl ChainStore for RocksChainStore {
+    fn put_block(&mut self, height: u64, block: &Block) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        // Overwriting a height must drop the old block's lookups first.
+        if let Some(old) = self.get_block(height)? {
+            for (cf, key, _) in Self::lookup_entries(height, &old) {
+                batch.delete_cf(self.cf(cf)?, key);
+            }
+        }
+        batch.put_cf(self.cf(CF_BLOCKS)?, height.to_be_bytes(), bincode::serialize(block)?);
+        for (cf, key, value) in Self::lookup_entries(height, block) {
+            batch.put_cf(self.cf(cf)?, key, value);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    fn get_block(&self, height: u64) -> Result<Option<Block>> {
+        match self.db.get_cf(self.cf(CF_BLOCKS)?, height.to_be_bytes())? {
+            Some(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn block_count(&self) -> Result<u64> {
+        match self.db.iterator_cf(self.cf(CF_BLOCKS)?, IteratorMode::End).next() {
+            Some(item) => {
+                let (key, _) = item?;
+                let mut height = [0u8; 8];
+                height.copy_from_slice(&key);
+                Ok(u64::from_be_bytes(height) + 1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn truncate_blocks(&mut self, height: u64) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        for item in self.db.iterator_cf(self.cf(CF_BLOCKS)?, IteratorMode::From(&height.to_be_bytes(), Direction::Forward)) {
+            let (key, value) = item?;
+            let block: Block = bincode::deserialize(&value)?;
+            let mut stale = [0u8; 8];
+            stale.copy_from_slice(&key);
+            for (cf, key, _) in Self::lookup_entries(u64::from_be_bytes(stale), &block) {
+                batch.delete_cf(self.cf(cf)?, key);
+            }
+            batch.delete_cf(self.cf(CF_BLOCKS)?, key);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    fn put_state(&mut self, state: &State) -> Result<()> {
+        self.db.put_cf(self.cf(CF_STATE)?, STATE_KEY, bincode::serialize(state)?)?;
+        Ok(())
+    }
+
+    fn get_state(&self) -> Result<Option<State>> {
+        match self.db.get_cf(self.cf(CF_STATE)?, STATE_KEY)? {
+            Some(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put_index(&mut self, index: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        self.db.put_cf(self.cf(CF_INDEX)?, Self::index_key(index, key), value)?;
+        Ok(())
+    }
+
+    fn get_index(&self, index: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.db.get_cf(self.cf(CF_INDEX)?, Self::index_key(index, key))?)
+    }
+
+    fn delete_index(&mut self, index: &str, key: &[u8]) -> Result<()> {
+        self.db.delete_cf(self.cf(CF_INDEX)?, Self::index_key(index, key))?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn block_height(&self, hash: &[u8; 32]) -> Result<Option<u64>> {
+        Ok(self.db.get_cf(self.cf(CF_BLOCK_HASH)?, hash)?.and_then(|bytes| {
+            let bytes: [u8; 8] = bytes.as_slice().try_into().ok()?;
+            Some(u64::from_be_bytes(bytes))
+        }))
+    }
+
+    fn transaction_location(&self, txid: &[u8; 32]) -> Result<Option<TxLocation>> {
+        Ok(self.db.get_cf(self.cf(CF_TX)?, txid)?.and_then(|bytes| TxLocation::from_bytes(&bytes)))
+    }
+
+    fn address_transactions(&self, address: &Address) -> Result<Vec<TxLocation>> {
+        let mut locations = Vec::new();
+        for item in self.db.iterator_cf(self.cf(CF_ADDRESS)?, IteratorMode::From(address, Direction::Forward)) {
+            let (key, _) = item?;
+            if !key.starts_with(address) {
+                break;
+            }
+            if let Some(location) = TxLocation::from_bytes(&key[address.len()..]) {
+                // A self-transfer is indexed once.
+                if locations.last() != Some(&location) {
+                    locations.push(location);
+                }
+            }
+        }
+        Ok(locations)
+    }
+}
@@ -0,0 +1,115 @@
+// src/storage/sled_store.rs - sled-backed ChainStore
+//
+// Blocks live in the `blocks` tree keyed by big-endian height, so the
+// tree's natural order is chain order. The state snapshot is a single key
+// in `state`, and each secondary index gets its own `index:<name>` tree.
+// Unlike the flat-file backend, appending a block writes only that block.
+
+use std::path::Path;
+
+use super::ChainStore;
+use crate::block::Block;
+use crate::error::Result;
+use crate::state::State;
+
+const BLOCKS_TREE: &str = "blocks";
+const STATE_TREE: &str = "state";
+const STATE_KEY: &[u8] = b"current";
+
+pub struct SledChainStore {
+    db: sled::Db,
+    blocks: sled::Tree,
+    state: sled::Tree,
+}
+
+impl SledChainStore {
+    /// Open (or create) the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_db(sled::open(path)?)
+    }
+
+    /// A throwaway database that is removed when dropped.
+    pub fn temporary() -> Result<Self> {
+        Self::from_db(sled::Config::new().temporary(true).open()?)
+    }
+
+    fn from_db(db: sled::Db) -> Result<Self> {
+        Ok(Self {
+            blocks: db.open_tree(BLOCKS_TREE)?,
+            state: db.open_tree(STATE_TREE)?,
+            db,
+        })
+    }
+
+    fn index_tree(&self, index: &str) -> Result<sled::Tree> {
+        Ok(self.db.open_tree(format!("index:{}", index))?)
+    }
+}
+
+impl ChainStore for SledChainStore {
+    fn put_block(&mut self, height: u64, block: &Block) -> Result<()> {
+        self.blocks.insert(height.to_be_bytes(), bincode::serialize(block)?)?;
+        Ok(())
+    }
+
+    fn get_block(&self, height: u64) -> Result<Option<Block>> {
+        match self.blocks.get(height.to_be_bytes())? {
+            Some(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn block_count(&self) -> Result<u64> {
+        match self.blocks.last()? {
+            Some((key, _)) => {
+                let mut height = [0u8; 8];
+                height.copy_from_slice(&key);
+                Ok(u64::from_be_bytes(height) + 1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn truncate_blocks(&mut self, height: u64) -> Result<()> {
+        let stale: Vec<sled::IVec> = self
+            .blocks
+            .range(height.to_be_bytes()..)
+            .keys()
+            .collect::<std::result::Result<_, _>>()?;
+        for key in stale {
+            self.blocks.remove(key)?;
+        }
+        Ok(())
+    }
+
+    fn put_state(&mut self, state: &State) -> Result<()> {
+        self.state.insert(STATE_KEY, bincode::serialize(state)?)?;
+        Ok(())
+    }
+
+    fn get_state(&self) -> Result<Option<State>> {
+        match self.state.get(STATE_KEY)? {
+            Some(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put_index(&mut self, index: &str, key: &[u8], value: &[u8]) -> Result<()> {
+        self.index_tree(index)?.insert(key, value)?;
+        Ok(())
+    }
+
+    fn get_index(&self, index: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        Ok(self.index_tree(index)?.get(key)?.map(|v| v.to_vec()))
+    }
+
+    fn delete_index(&mut self, index: &str, key: &[u8]) -> Result<()> {
+        self.index_tree(index)?.remove(key)?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.db.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+// src/supply_proof.rs - Peer-served light proofs of supply
+//
+// Every block pulse commits to the previous block pulse through
+// `prev_pulse_hash`, and every `STARK_PROOF_INTERVAL`-th pulse carries a
+// STARK receipt over its supply figures. A light client that is given the
+// latest receipt-bearing pulse plus every pulse after it can check that
+// the current supply figures descend from the proven ones, without the
+// chain. `PulseLog` keeps the recent contiguous run of block pulses and
+// assembles that bundle for `/v1/supply/proof`, and lists the run with
+// each link checked for `/v1/pulses`.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::stark::{StarkProver, StarkReceipt, TransactionData};
+use crate::types::Hash512;
+use crate::AxiomPulse;
+
+/// Pulses kept: enough to always span back to the last receipt.
+pub const PULSE_LOG_CAPACITY: usize = 2 * crate::stark::prover::STARK_PROOF_INTERVAL as usize;
+
+/// Link hash of a pulse: BLAKE3-512 of its bincode encoding, the value the
+/// next pulse carries as `prev_pulse_hash`.
+pub fn pulse_hash(pulse: &AxiomPulse) -> Hash512 {
+    Hash512::digest(&bincode::serialize(pulse).unwrap_or_default())
+}
+
+/// Check a pulse's STARK receipt against the supply figures it reports.
+/// `None` if the pulse carries no receipt.
+pub fn verify_pulse_receipt(pulse: &AxiomPulse) -> Option<bool> {
+    let seal = pulse.stark_receipt.as_ref()?;
+    let statement = TransactionData {
+        initial_balance: pulse.remaining,
+        amount: pulse.total_mined,
+        fee: 0,
+        nonce: pulse.height,
+    };
+    let verified = StarkProver::compute_512_anchor(&statement)
+        .and_then(|anchor| {
+            let receipt = StarkReceipt { journal_hash_512: anchor, seal: seal.clone() };
+            StarkProver::verify_receipt(&receipt, &anchor)
+        })
+        .unwrap_or(false);
+    Some(verified)
+}
+
+/// Bundle served to light clients.
+#[derive(Debug, Clone, Serialize)]
+pub struct SupplyProof {
+    /// Receipt-bearing pulse first, then each following pulse up to the tip.
+    pub pulses: Vec<AxiomPulse>,
+    /// Whether this node verified the first pulse's STARK receipt.
+    pub stark_verified: bool,
+}
+
+/// One logged pulse as listed on `/v1/pulses`, hashes hex-encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PulseRecord {
+    pub height: u64,
+    pub total_mined: u64,
+    pub remaining: u64,
+    pub block_hash: String,
+    pub oracle_seal: String,
+    pub prev_pulse_hash: String,
+    /// This pulse's own link hash, see [`pulse_hash`].
+    pub pulse_hash: String,
+    pub timestamp: i64,
+    pub has_stark_receipt: bool,
+    /// Receipt check result; `None` without a receipt.
+    pub stark_verified: Option<bool>,
+    /// Whether `prev_pulse_hash` matches the pulse before it. The oldest
+    /// logged pulse is checked against the anchor, which it only matches
+    /// while nothing before it has been evicted or skipped.
+    pub linked: bool,
+}
+
+/// A height range of the logged run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PulseChain {
+    /// `prev_pulse_hash` of the run's first pulse when nothing was evicted.
+    pub anchor: String,
+    /// Whether the anchor is `GENESIS_PULSE_HASH`.
+    pub genesis_anchored: bool,
+    pub pulses: Vec<PulseRecord>,
+}
+
+#[derive(Debug, Clone)]
+struct LoggedPulse {
+    pulse: AxiomPulse,
+    hash: Hash512,
+    stark_verified: Option<bool>,
+}
+
+/// Recent block pulses forming one unbroken `prev_pulse_hash` chain.
+#[derive(Debug)]
+pub struct PulseLog {
+    /// `prev_pulse_hash` expected of the first pulse when the log is empty.
+    anchor: Hash512,
+    pulses: VecDeque<LoggedPulse>,
+}
+
+impl PulseLog {
+    pub fn new(anchor: Hash512) -> Self {
+        Self { anchor, pulses: VecDeque::with_capacity(PULSE_LOG_CAPACITY) }
+    }
+
+    /// What the next block pulse should carry as `prev_pulse_hash`.
+    pub fn tip_hash(&self) -> Hash512 {
+        self.pulses.back().map(|p| p.hash).unwrap_or(self.anchor)
+    }
+
+    pub fn tip_height(&self) -> Option<u64> {
+        self.pulses.back().map(|p| p.pulse.height)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pulses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pulses.is_empty()
+    }
+
+    /// Add a block pulse. One that extends the tip is appended; a newer one
+    /// that does not link starts a fresh run (we missed a pulse). Stale
+    /// pulses are ignored. Returns whether the pulse was kept.
+    pub fn record(&mut self, pulse: AxiomPulse) -> bool {
+        if self.tip_height().is_some_and(|h| pulse.height <= h) {
+            return false;
+        }
+        if pulse.prev_pulse_hash != self.tip_hash() {
+            self.pulses.clear();
+        }
+        let stark_verified = verify_pulse_receipt(&pulse);
+        self.pulses.push_back(LoggedPulse { hash: pulse_hash(&pulse), pulse, stark_verified });
+        while self.pulses.len() > PULSE_LOG_CAPACITY {
+            self.pulses.pop_front();
+        }
+        true
+    }
+
+    /// Logged pulses with heights in `from..=to`, each with its link checked.
+    pub fn chain(&self, from: u64, to: u64) -> PulseChain {
+        let pulses = self.pulses.iter().enumerate()
+            .filter(|(_, p)| (from..=to).contains(&p.pulse.height))
+            .map(|(i, p)| {
+                let expected = match i {
+                    0 => self.anchor,
+                    _ => self.pulses[i - 1].hash,
+                };
+                PulseRecord {
+                    height: p.pulse.height,
+                    total_mined: p.pulse.total_mined,
+                    remaining: p.pulse.remaining,
+                    block_hash: hex::encode(p.pulse.block_hash),
+                    oracle_seal: hex::encode(p.pulse.oracle_seal),
+                    prev_pulse_hash: hex::encode(p.pulse.prev_pulse_hash),
+                    pulse_hash: hex::encode(p.hash),
+                    timestamp: p.pulse.timestamp,
+                    has_stark_receipt: p.pulse.stark_receipt.is_some(),
+                    stark_verified: p.stark_verified,
+                    linked: p.pulse.prev_pulse_hash == expected,
+                }
+            })
+            .collect();
+        PulseChain {
+            anchor: hex::encode(self.anchor),
+            genesis_anchored: hex::encode(self.anchor) == crate::GENESIS_PULSE_HASH,
+            pulses,
+        }
+    }
+
+    /// The latest receipt-bearing pulse and everything after it, or `None`
+    /// if the current run holds no receipt yet.
+    pub fn supply_proof(&self) -> Option<SupplyProof> {
+        let start = self.pulses.iter().rposition(|p| p.stark_verified.is_some())?;
+        Some(SupplyProof {
+            pulses: self.pulses.iter().skip(start).map(|p| p.pulse.clone()).collect(),
+            stark_verified: self.pulses[start].stark_verified == Some(true),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pulse(height: u64, prev: Hash512, receipt: bool) -> AxiomPulse {
+        AxiomPulse {
+            height,
+            total_mined: height * 50,
+            remaining: 1_000_000 - height * 50,
+            block_hash: Hash512([height as u8; 64]),
+            oracle_seal: Hash512::ZERO,
+            prev_pulse_hash: prev,
+            timestamp: height as i64,
+            stark_receipt: receipt.then(|| vec![1, 2, 3]),
+            signature: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_proof_spans_from_last_receipt_to_tip() {
+        let mut log = PulseLog::new(Hash512::ZERO);
+        assert!(log.record(pulse(1, Hash512::ZERO, false)));
+        assert!(log.supply_proof().is_none());
+        assert!(log.record(pulse(2, log.tip_hash(), true)));
+        assert!(log.record(pulse(3, log.tip_hash(), false)));
+        assert!(!log.record(pulse(3, log.tip_hash(), false)));
+
+        let proof = log.supply_proof().unwrap();
+        let heights: Vec<u64> = proof.pulses.iter().map(|p| p.height).collect();
+        assert_eq!(heights, vec![2, 3]);
+        assert_eq!(proof.pulses[1].prev_pulse_hash, pulse_hash(&proof.pulses[0]));
+
+        let chain = log.chain(2, 10);
+        assert!(!chain.genesis_anchored);
+        assert_eq!(chain.pulses.len(), 2);
+        assert!(chain.pulses.iter().all(|p| p.linked));
+        assert!(chain.pulses[0].has_stark_receipt);
+        assert_eq!(chain.pulses[1].prev_pulse_hash, chain.pulses[0].pulse_hash);
+    }
+
+    #[test]
+    fn test_unlinked_pulse_starts_new_run() {
+        let mut log = PulseLog::new(Hash512::ZERO);
+        log.record(pulse(1, Hash512::ZERO, true));
+        log.record(pulse(5, Hash512([9u8; 64]), false));
+        assert_eq!(log.len(), 1);
+        assert!(log.supply_proof().is_none());
+    }
+}
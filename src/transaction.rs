@@ -12,14 +12,19 @@ pub struct Transaction {
     pub amount: u64,
     pub fee: u64,
     pub nonce: u64,
+    /// Last block height that may include the transaction; 0 never
+    /// expires. Signed like every other field.
+    #[serde(default)]
+    pub expiry_height: u64,
     pub zk_proof: Vec<u8>,
     pub signature: Vec<u8>,
 }
 
 #[allow(dead_code)]
 impl Transaction {
-    /// Generates a unique transaction identifier (TXID).
-    pub fn hash(&self) -> [u8; 32] {
+    /// Legacy 32-byte SHA-256 TXID. Block tx roots commit to it, so it
+    /// stays until a hard fork; `hash_512` is the protocol-standard id.
+    pub fn legacy_hash(&self) -> [u8; 32] {
         let serialized = bincode::serialize(self).expect("CRITICAL: Transaction serialization failed");
         let mut hasher = Sha256::new();
         hasher.update(&serialized);
@@ -30,6 +35,18 @@ impl Transaction {
         hash
     }
 
+    #[deprecated(note = "use `hash_512` to identify transactions, or `legacy_hash` where consensus commits to 32 bytes")]
+    pub fn hash(&self) -> [u8; 32] {
+        self.legacy_hash()
+    }
+
+    /// 512-bit BLAKE3 id of the bincode-encoded transaction, as announced
+    /// by the relay (`TxId`).
+    pub fn hash_512(&self) -> crate::types::Hash512 {
+        let serialized = bincode::serialize(self).expect("CRITICAL: Transaction serialization failed");
+        crate::types::Hash512::digest(&serialized)
+    }
+
     /// Helper to create a new transaction.
     pub fn new(
         from: Address,
@@ -46,11 +63,24 @@ impl Transaction {
             amount,
             fee,
             nonce,
+            expiry_height: 0,
             zk_proof,
             signature,
         }
     }
 
+    /// The same transaction, valid up to block `height` only. Set before
+    /// signing.
+    pub fn with_expiry(mut self, height: u64) -> Self {
+        self.expiry_height = height;
+        self
+    }
+
+    /// Whether the block at `height` may no longer include it.
+    pub fn is_expired_at(&self, height: u64) -> bool {
+        self.expiry_height != 0 && height > self.expiry_height
+    }
+
     /// Validate transaction ZK proof and signature
     pub fn validate(&self, sender_balance: u64) -> Result<(), &'static str> {
         // Check if sender has sufficient balance
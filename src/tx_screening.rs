@@ -0,0 +1,196 @@
+// src/tx_screening.rs - Risk screening of mempool candidates
+//
+// A transaction that passes the mempool's rule checks is screened by the
+// MultiLayerSecurityEngine, through the AI Guardian bridge, before it is
+// pooled or relayed. `risk_profile` builds the engine's input from the
+// transaction and both parties' chain history in the address index, and
+// `verdict` reads the Guardian's decision: pool it, hold it back for some
+// blocks, or refuse it.
+//
+// The engine runs off the event loop. Meanwhile, and while quarantined, a
+// transaction is held in `Screening`, whose contents count as pending for
+// the sender's nonces, duplicates and double spends, but are neither mined
+// nor announced to peers.
+
+use std::collections::HashMap;
+
+use crate::address_index::AddressIndex;
+use crate::ai_core::TransactionRiskProfile;
+use crate::error::AxiomError;
+use crate::guardian_enhancement::{GuardianAction, GuardianDecision};
+use crate::transaction::Transaction;
+
+/// Quarantined transactions the node holds at most; more are refused.
+pub const MAX_QUARANTINED: usize = 1_000;
+
+/// The engine's view of `tx`, from the parties' history in `index` as of
+/// `now`. An address with no history never sent before, so the time since
+/// its last transaction is unbounded.
+pub fn risk_profile(tx: &Transaction, index: &AddressIndex, now: u64) -> TransactionRiskProfile {
+    let sender = index.history(&tx.from);
+    let recipient = index.history(&tx.to);
+    let since_last = |history: &[crate::address_index::AddressActivity]| {
+        history.last().map_or(u64::MAX, |a| now.saturating_sub(a.timestamp))
+    };
+    TransactionRiskProfile {
+        hash: hex::encode(tx.legacy_hash()),
+        timestamp: now,
+        sender: hex::encode(tx.from),
+        recipient: hex::encode(tx.to),
+        amount: tx.amount,
+        gas_price: tx.fee,
+        zk_proof_size: tx.zk_proof.len(),
+        sender_history_count: sender.len() as u64,
+        recipient_history_count: recipient.len() as u64,
+        sender_reputation_score: 0.5,
+        time_since_last_sender_tx: since_last(sender),
+        time_since_last_recipient_tx: since_last(recipient),
+        is_contract_deployment: false,
+        contract_bytecode_size: 0,
+        vdf_verification_time_ms: 0,
+    }
+}
+
+/// What becomes of a screened transaction.
+#[derive(Debug)]
+pub enum Verdict {
+    /// Pool and relay it; `risk` is the engine's threat score (0-100).
+    Admit { risk: f64 },
+    /// Hold it back for `blocks` blocks, then pool it.
+    Quarantine { risk: f64, blocks: u64 },
+    Reject(AxiomError),
+}
+
+/// Read the Guardian's `decision`. Transactions it leaves for manual review
+/// are pooled, with their score for the miner's risk policy to weigh.
+pub fn verdict(decision: &GuardianDecision) -> Verdict {
+    let risk = decision.threat_assessment.threat_score;
+    if !decision.approved {
+        let reason = decision
+            .veto_reason
+            .clone()
+            .unwrap_or_else(|| format!("{:?}", decision.threat_assessment.identified_threats));
+        return Verdict::Reject(AxiomError::ThreatDetected { threat_type: reason, confidence: risk as f32 });
+    }
+    match decision.action {
+        GuardianAction::Quarantine { duration_blocks } => Verdict::Quarantine { risk, blocks: duration_blocks },
+        _ => Verdict::Admit { risk },
+    }
+}
+
+/// Transactions the node holds outside the mempool: under screening, or
+/// quarantined until a height.
+#[derive(Debug, Default)]
+pub struct Screening {
+    pending: HashMap<[u8; 32], Transaction>,
+    /// Transaction, risk score, height from which it may be pooled.
+    quarantined: Vec<(Transaction, f64, u64)>,
+}
+
+impl Screening {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything held, to check new candidates against.
+    pub fn held(&self) -> impl Iterator<Item = &Transaction> + Clone {
+        self.pending.values().chain(self.quarantined.iter().map(|(tx, _, _)| tx))
+    }
+
+    /// Take `tx` for screening. Returns false if it already is.
+    pub fn start(&mut self, tx: Transaction) -> bool {
+        let hash = tx.legacy_hash();
+        if self.pending.contains_key(&hash) {
+            return false;
+        }
+        self.pending.insert(hash, tx);
+        true
+    }
+
+    /// Screening of the transaction `hash` is over.
+    pub fn finish(&mut self, hash: &[u8; 32]) -> Option<Transaction> {
+        self.pending.remove(hash)
+    }
+
+    pub fn under_screening(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn quarantined(&self) -> usize {
+        self.quarantined.len()
+    }
+
+    /// Hold `tx` until the chain is `until` blocks long. Returns false when
+    /// the quarantine is full.
+    pub fn quarantine(&mut self, tx: Transaction, risk: f64, until: u64) -> bool {
+        if self.quarantined.len() >= MAX_QUARANTINED {
+            return false;
+        }
+        self.quarantined.push((tx, risk, until));
+        true
+    }
+
+    /// The quarantined transactions that may be pooled on a chain `height`
+    /// blocks long, with their risk scores. They are no longer held.
+    pub fn release(&mut self, height: u64) -> Vec<(Transaction, f64)> {
+        let (due, held): (Vec<_>, Vec<_>) = std::mem::take(&mut self.quarantined)
+            .into_iter()
+            .partition(|(_, _, until)| *until <= height);
+        self.quarantined = held;
+        due.into_iter().map(|(tx, risk, _)| (tx, risk)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai_core::{RiskLevel, SecurityAction, ThreatAssessment};
+
+    fn decision(approved: bool, action: GuardianAction) -> GuardianDecision {
+        GuardianDecision {
+            approved,
+            veto_reason: (!approved).then(|| "fee below minimum".to_string()),
+            action,
+            threat_assessment: ThreatAssessment {
+                threat_score: 82.0,
+                confidence: 0.9,
+                identified_threats: vec![],
+                risk_level: RiskLevel::High,
+                recommended_action: SecurityAction::Accept,
+                detailed_analysis: String::new(),
+                guardian_override_required: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_verdicts_and_quarantine_release() {
+        let tx = Transaction::new([1u8; 32], [2u8; 32], 500, 10, 0, vec![], vec![]);
+        let index = AddressIndex::new();
+        let profile = risk_profile(&tx, &index, 1_000);
+        assert_eq!(profile.sender_history_count, 0);
+        assert_eq!(profile.time_since_last_sender_tx, u64::MAX);
+        assert_eq!(profile.gas_price, 10);
+
+        assert!(matches!(verdict(&decision(true, GuardianAction::Accept)), Verdict::Admit { risk } if risk == 82.0));
+        assert!(matches!(
+            verdict(&decision(true, GuardianAction::Quarantine { duration_blocks: 144 })),
+            Verdict::Quarantine { blocks: 144, .. }
+        ));
+        assert!(matches!(
+            verdict(&decision(false, GuardianAction::Reject)),
+            Verdict::Reject(AxiomError::ThreatDetected { threat_type, .. }) if threat_type == "fee below minimum"
+        ));
+
+        let mut screening = Screening::new();
+        assert!(screening.start(tx.clone()));
+        assert!(!screening.start(tx.clone()), "already under screening");
+        assert_eq!(screening.held().count(), 1);
+        let tx = screening.finish(&tx.legacy_hash()).unwrap();
+        assert!(screening.quarantine(tx.clone(), 82.0, 150));
+        assert_eq!(screening.held().count(), 1);
+        assert!(screening.release(149).is_empty());
+        assert_eq!(screening.release(150), vec![(tx, 82.0)]);
+        assert_eq!(screening.quarantined(), 0);
+    }
+}
@@ -0,0 +1,154 @@
+// src/types.rs - Protocol hash types
+//
+// BLAKE3-512 (`axiom_hash_512`) is the protocol standard for hashing.
+// `Hash512` carries such a digest. It encodes exactly as the
+// `[u8; 64]` fields it replaces (serde bytes), so pulses and transaction
+// announcements are unchanged on the wire and on disk.
+//
+// Two 32-byte hashes predate the standard and stay, as legacy
+// identifiers:
+//   - `Block::legacy_hash` (BLAKE3-256): parent links, proof of work and the
+//     genesis anchor commit to it;
+//   - `Transaction::legacy_hash` (SHA-256): block tx roots commit to it.
+// Replacing either changes every existing block, which is a hard fork.
+// Their old name, `hash()`, is deprecated so new code picks one on purpose.
+// Every block and transaction also has a `Hash512` id (`hash_512`), and
+// places that take an identifier from outside accept either form through
+// `HashRef`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A 512-bit BLAKE3 digest.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hash512(pub [u8; 64]);
+
+impl Hash512 {
+    pub const ZERO: Self = Hash512([0u8; 64]);
+
+    /// BLAKE3-512 of `data`.
+    pub fn digest(data: &[u8]) -> Self {
+        Hash512(crate::axiom_hash_512(data))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 64] {
+        &self.0
+    }
+}
+
+impl From<[u8; 64]> for Hash512 {
+    fn from(bytes: [u8; 64]) -> Self {
+        Hash512(bytes)
+    }
+}
+
+impl AsRef<[u8]> for Hash512 {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Hash512 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for Hash512 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hash512({}…)", hex::encode(&self.0[..8]))
+    }
+}
+
+impl FromStr for Hash512 {
+    type Err = HashParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<HashRef>()? {
+            HashRef::Full(hash) => Ok(hash),
+            HashRef::Legacy(_) => Err(HashParseError::Length(s.len())),
+        }
+    }
+}
+
+impl Serialize for Hash512 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_bytes_64::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hash512 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_bytes_64::deserialize(deserializer).map(Hash512)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HashParseError {
+    #[error("hash is not hex")]
+    NotHex,
+    #[error("expected 64 (legacy) or 128 hex characters, got {0}")]
+    Length(usize),
+}
+
+/// A block or transaction identifier as given by a client: the 512-bit
+/// id, or the legacy 32-byte hash older tools still send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashRef {
+    Legacy([u8; 32]),
+    Full(Hash512),
+}
+
+impl HashRef {
+    /// Whether this names the object with these two hashes.
+    pub fn matches(&self, legacy: &[u8; 32], full: &Hash512) -> bool {
+        match self {
+            HashRef::Legacy(hash) => hash == legacy,
+            HashRef::Full(hash) => hash == full,
+        }
+    }
+}
+
+impl FromStr for HashRef {
+    type Err = HashParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| HashParseError::NotHex)?;
+        match bytes.len() {
+            32 => Ok(HashRef::Legacy(bytes.try_into().unwrap_or([0u8; 32]))),
+            64 => Ok(HashRef::Full(Hash512(bytes.try_into().unwrap_or([0u8; 64])))),
+            _ => Err(HashParseError::Length(s.len())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash512_encodes_as_bytes_and_parses_both_forms() {
+        let hash = Hash512::digest(b"axiom");
+        assert_eq!(hash.0, crate::axiom_hash_512(b"axiom"));
+
+        // Same encoding as the `[u8; 64]` fields it replaces.
+        #[derive(Serialize)]
+        struct Raw(#[serde(with = "crate::serde_bytes_64")] [u8; 64]);
+        assert_eq!(bincode::serialize(&hash).unwrap(), bincode::serialize(&Raw(hash.0)).unwrap());
+        assert_eq!(serde_json::to_string(&hash).unwrap(), serde_json::to_string(&Raw(hash.0)).unwrap());
+        let back: Hash512 = bincode::deserialize(&bincode::serialize(&hash).unwrap()).unwrap();
+        assert_eq!(back, hash);
+
+        assert_eq!(hash.to_string().parse::<Hash512>(), Ok(hash));
+        assert_eq!(hash.to_string().parse::<HashRef>(), Ok(HashRef::Full(hash)));
+        let legacy = [7u8; 32];
+        let parsed: HashRef = hex::encode(legacy).parse().unwrap();
+        assert!(parsed.matches(&legacy, &Hash512::ZERO));
+        assert!(!parsed.matches(&[8u8; 32], &hash));
+        assert!(hex::encode(legacy).parse::<Hash512>().is_err());
+        assert_eq!("zz".parse::<HashRef>(), Err(HashParseError::NotHex));
+        assert_eq!("abcd".parse::<HashRef>(), Err(HashParseError::Length(4)));
+    }
+}
@@ -189,7 +189,7 @@ impl MLTransactionValidator {
         } else {
             log::warn!(
                 "TRANSACTION_REJECTED: hash={} from={} to={} amount={} score={:.3}",
-                hex::encode(tx.hash()),
+                hex::encode(tx.legacy_hash()),
                 hex::encode(tx.from),
                 hex::encode(tx.to),
                 tx.amount,
@@ -294,6 +294,7 @@ mod tests {
             amount,
             fee,
             nonce: 42,
+            expiry_height: 0,
             zk_proof: vec![0u8; 256],
             signature: vec![0u8; 64],
         }
@@ -64,6 +64,22 @@ pub fn wesolowski_setup_test() -> Integer {
 #[cfg(test)]
 mod vdf_tests {
     use super::*;
+
+    #[test]
+    fn test_block_vdf_backends() {
+        let seed = evaluate([7u8; 32], 3);
+        let wesolowski = WesolowskiVdf::default();
+        let run = wesolowski.prove(seed, 200);
+        assert!(wesolowski.verify(seed, 200, &run.output, &run.witness));
+        assert!(!wesolowski.verify(seed, 201, &run.output, &run.witness));
+        assert!(!wesolowski.verify(evaluate([7u8; 32], 4), 200, &run.output, &run.witness));
+        assert!(!wesolowski.verify(seed, 200, &[0u8; 32], &run.witness));
+        assert!(!wesolowski.verify(seed, 200, &run.output, &[]));
+
+        let chain = HashChainVdf.prove(seed, 200);
+        assert!(HashChainVdf.verify(seed, 200, &chain.output, &chain.witness));
+        assert!(!wesolowski.verify(seed, 200, &chain.output, &chain.witness));
+    }
     
     #[test]
     fn test_vdf_modulus_size() {
@@ -261,19 +277,111 @@ pub fn evaluate(parent_hash: [u8; 32], slot: u64) -> [u8; 32] {
     hasher.finalize().into()
 }
 
-/// VERIFY: Recomputes the sequential SHA-256 hash chain used for block-level
-/// VDF proofs.  This is the chain-integrated time-lock verification path
-/// that every node runs during `add_block()`.
-///
-/// The proof is valid if and only if re-running the sequential chain from
-/// `seed` for `iterations` steps produces the same 32-byte output.
-///
-/// Note: The heavyweight Wesolowski algebraic VDF (see `wesolowski_*`
-/// functions above and `consensus::vdf::VDF`) provides faster verification
-/// via a compact proof and is used for extended time-lock proofs.  The
-/// hash-chain VDF here is simpler and deterministic, used directly in the
-/// block consensus pipeline.
+/// VERIFY: Recomputes the sequential SHA-256 hash chain from `seed` for
+/// `iterations` steps and compares it with `proof`. This is the check
+/// behind [`HashChainVdf`]; it costs as much as producing the output.
 pub fn verify_vdf(seed: [u8; 32], iterations: u32, proof: [u8; 32]) -> bool {
     let expected = crate::main_helper::compute_vdf(seed, iterations);
     expected == proof
 }
+
+// ---------------------------------------------------------------------------
+// Block VDF backends
+// ---------------------------------------------------------------------------
+
+/// What a block VDF run produces: the 32-byte output a block carries in
+/// `vdf_proof`, and the witness (`vdf_witness`) others check it against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VdfOutput {
+    pub output: [u8; 32],
+    pub witness: Vec<u8>,
+}
+
+/// A verifiable delay function blocks are timed with. The chain runs it
+/// from `evaluate(parent, slot)` for `difficulty` iterations.
+pub trait Vdf: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Run the delay. Slow by design.
+    fn prove(&self, seed: [u8; 32], iterations: u64) -> VdfOutput;
+
+    /// Check an output and its witness.
+    fn verify(&self, seed: [u8; 32], iterations: u64, output: &[u8; 32], witness: &[u8]) -> bool;
+}
+
+/// The sequential SHA-256 chain of `compute_vdf`. It has no witness, so
+/// verifying redoes the whole delay; kept for tooling and comparison.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashChainVdf;
+
+impl Vdf for HashChainVdf {
+    fn name(&self) -> &'static str {
+        "hash-chain"
+    }
+
+    fn prove(&self, seed: [u8; 32], iterations: u64) -> VdfOutput {
+        VdfOutput { output: crate::main_helper::compute_vdf(seed, iterations as u32), witness: Vec::new() }
+    }
+
+    fn verify(&self, seed: [u8; 32], iterations: u64, output: &[u8; 32], witness: &[u8]) -> bool {
+        witness.is_empty() && verify_vdf(seed, iterations as u32, *output)
+    }
+}
+
+/// Wesolowski's VDF over the RSA-2048 group (`consensus::vdf::VDF`): the
+/// output is SHA-256 of `y = x^(2^T) mod N` and the witness is the
+/// bincode-encoded `(y, π)`, so checking costs two short exponentiations
+/// however long the delay was.
+#[derive(Debug, Clone)]
+pub struct WesolowskiVdf {
+    modulus: num_bigint::BigUint,
+}
+
+impl Default for WesolowskiVdf {
+    fn default() -> Self {
+        Self { modulus: crate::consensus::vdf::VDF::with_default_modulus(0).modulus }
+    }
+}
+
+impl WesolowskiVdf {
+    pub fn new(modulus: num_bigint::BigUint) -> Self {
+        Self { modulus }
+    }
+
+    fn vdf(&self, iterations: u64) -> crate::consensus::vdf::VDF {
+        crate::consensus::vdf::VDF::new(self.modulus.clone(), iterations)
+    }
+}
+
+fn output_digest(y: &num_bigint::BigUint) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(y.to_bytes_be()).into()
+}
+
+impl Vdf for WesolowskiVdf {
+    fn name(&self) -> &'static str {
+        "wesolowski-rsa2048"
+    }
+
+    fn prove(&self, seed: [u8; 32], iterations: u64) -> VdfOutput {
+        let proof = self.vdf(iterations).compute(&seed).expect("Wesolowski proof generation is infallible");
+        VdfOutput {
+            output: output_digest(&proof.output),
+            witness: bincode::serialize(&proof).expect("VDF proof serialization"),
+        }
+    }
+
+    fn verify(&self, seed: [u8; 32], iterations: u64, output: &[u8; 32], witness: &[u8]) -> bool {
+        let Ok(proof) = bincode::deserialize::<crate::consensus::vdf::VDFProof>(witness) else {
+            return false;
+        };
+        output_digest(&proof.output) == *output && self.vdf(iterations).verify(&seed, &proof).unwrap_or(false)
+    }
+}
+
+/// The backend consensus uses for new chains.
+pub fn block_vdf() -> std::sync::Arc<dyn Vdf> {
+    static BACKEND: once_cell::sync::Lazy<std::sync::Arc<dyn Vdf>> =
+        once_cell::sync::Lazy::new(|| std::sync::Arc::new(WesolowskiVdf::default()));
+    BACKEND.clone()
+}
@@ -47,6 +47,20 @@ impl Wallet {
         fee: u64,
         nonce: u64,
         current_balance: u64,
+    ) -> Result<Transaction, Box<dyn std::error::Error>> {
+        self.create_expiring_transaction(to, amount, fee, nonce, current_balance, 0)
+    }
+
+    /// Create a signed transaction that no block above `expiry_height` may
+    /// include (0: never expires)
+    pub fn create_expiring_transaction(
+        &self,
+        to: Address,
+        amount: u64,
+        fee: u64,
+        nonce: u64,
+        current_balance: u64,
+        expiry_height: u64,
     ) -> Result<Transaction, Box<dyn std::error::Error>> {
         // Generate ZK proof
         let zk_proof = genesis::generate_transaction_proof(
@@ -65,7 +79,7 @@ impl Wallet {
             nonce,
             zk_proof,
             vec![], // Unsigned — signature is computed and applied below
-        );
+        ).with_expiry(expiry_height);
 
         // Sign the transaction
         let signature = self.sign_transaction(&tx_data)?;
@@ -79,7 +93,7 @@ impl Wallet {
             nonce,
             tx_data.zk_proof,
             signature,
-        ))
+        ).with_expiry(expiry_height))
     }
 
     /// Sign transaction data
@@ -97,6 +111,9 @@ impl Wallet {
 
     /// Verify transaction signature
     pub fn verify_transaction_signature(tx: &Transaction) -> Result<bool, Box<dyn std::error::Error>> {
+        if crate::multisig::is_multisig_signature(&tx.signature) {
+            return Ok(crate::multisig::verify_spend(tx).is_ok());
+        }
         if tx.signature.len() != 64 {
             return Ok(false);
         }
@@ -0,0 +1,161 @@
+// src/wallet_analytics.rs - Wallet accounting summaries
+//
+// Turns an address's indexed history into the figures a miner needs for
+// bookkeeping: when the address first and last appeared on chain, how much
+// of its income came from mining versus transfers, rewards per halving
+// era, and coin age (balance held over time). All amounts are in the
+// smallest unit (10^-8 AXM).
+
+use serde::Serialize;
+
+use crate::address_index::{ActivityKind, AddressActivity};
+use crate::economics;
+use crate::transaction::Address;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ActivityPoint {
+    pub height: u64,
+    pub timestamp: u64,
+}
+
+/// Mining rewards earned in one halving era.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EraRewards {
+    pub era: u64,
+    pub blocks_mined: u64,
+    pub total_reward: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WalletAnalytics {
+    pub address: String,
+    pub first_seen: Option<ActivityPoint>,
+    pub last_active: Option<ActivityPoint>,
+    pub blocks_mined: u64,
+    pub mining_income: u64,
+    pub transfer_income: u64,
+    pub transfers_sent: u64,
+    pub fees_paid: u64,
+    /// Balance implied by the indexed history.
+    pub balance: u64,
+    /// Sum of balance x time held, in AXM-days, up to `as_of`.
+    pub coin_age_axm_days: f64,
+    pub as_of: u64,
+    pub eras: Vec<EraRewards>,
+}
+
+/// Summarise `history` (oldest first, as returned by
+/// `AddressIndex::history`) as of unix time `as_of`.
+pub fn analyze(address: &Address, history: &[AddressActivity], as_of: u64) -> WalletAnalytics {
+    let mut analytics = WalletAnalytics {
+        address: hex::encode(address),
+        first_seen: None,
+        last_active: None,
+        blocks_mined: 0,
+        mining_income: 0,
+        transfer_income: 0,
+        transfers_sent: 0,
+        fees_paid: 0,
+        balance: 0,
+        coin_age_axm_days: 0.0,
+        as_of,
+        eras: Vec::new(),
+    };
+
+    // Balance-seconds in smallest units; u128 so long-held large balances
+    // cannot overflow.
+    let mut coin_age: u128 = 0;
+    let mut last_ts: Option<u64> = None;
+
+    for event in history {
+        let point = ActivityPoint { height: event.height, timestamp: event.timestamp };
+        analytics.first_seen.get_or_insert(point);
+        analytics.last_active = Some(point);
+
+        if let Some(prev) = last_ts {
+            let held = event.timestamp.min(as_of).saturating_sub(prev);
+            coin_age += analytics.balance as u128 * held as u128;
+        }
+        last_ts = Some(event.timestamp.min(as_of));
+
+        match event.kind {
+            ActivityKind::MiningReward => {
+                analytics.blocks_mined += 1;
+                analytics.mining_income = analytics.mining_income.saturating_add(event.amount);
+                analytics.balance = analytics.balance.saturating_add(event.amount);
+
+                let era = economics::current_era(event.height);
+                match analytics.eras.last_mut() {
+                    Some(e) if e.era == era => {
+                        e.blocks_mined += 1;
+                        e.total_reward = e.total_reward.saturating_add(event.amount);
+                    }
+                    _ => analytics.eras.push(EraRewards { era, blocks_mined: 1, total_reward: event.amount }),
+                }
+            }
+            ActivityKind::Received => {
+                analytics.transfer_income = analytics.transfer_income.saturating_add(event.amount);
+                analytics.balance = analytics.balance.saturating_add(event.amount);
+            }
+            ActivityKind::Sent => {
+                analytics.transfers_sent = analytics.transfers_sent.saturating_add(event.amount);
+                analytics.fees_paid = analytics.fees_paid.saturating_add(event.fee);
+                analytics.balance = analytics
+                    .balance
+                    .saturating_sub(event.amount.saturating_add(event.fee));
+            }
+        }
+    }
+
+    if let Some(prev) = last_ts {
+        coin_age += analytics.balance as u128 * as_of.saturating_sub(prev) as u128;
+    }
+    analytics.coin_age_axm_days =
+        coin_age as f64 / economics::SMALLEST_UNIT as f64 / SECONDS_PER_DAY;
+
+    analytics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(height: u64, timestamp: u64, kind: ActivityKind, amount: u64, fee: u64) -> AddressActivity {
+        AddressActivity { height, timestamp, kind, amount, fee, counterparty: None, tx_hash: None }
+    }
+
+    #[test]
+    fn test_income_breakdown_and_eras() {
+        let era_one = economics::HALVING_INTERVAL;
+        let history = vec![
+            event(5, 100, ActivityKind::MiningReward, 5_000, 0),
+            event(6, 200, ActivityKind::Received, 1_000, 0),
+            event(era_one, 300, ActivityKind::MiningReward, 2_500, 0),
+            event(era_one + 1, 400, ActivityKind::Sent, 3_000, 10),
+        ];
+        let a = analyze(&[7u8; 32], &history, 400);
+
+        assert_eq!(a.first_seen, Some(ActivityPoint { height: 5, timestamp: 100 }));
+        assert_eq!(a.last_active, Some(ActivityPoint { height: era_one + 1, timestamp: 400 }));
+        assert_eq!(a.mining_income, 7_500);
+        assert_eq!(a.transfer_income, 1_000);
+        assert_eq!(a.fees_paid, 10);
+        assert_eq!(a.balance, 5_490);
+        assert_eq!(a.eras, vec![
+            EraRewards { era: 0, blocks_mined: 1, total_reward: 5_000 },
+            EraRewards { era: 1, blocks_mined: 1, total_reward: 2_500 },
+        ]);
+    }
+
+    #[test]
+    fn test_coin_age_accumulates_held_balance() {
+        let one_axm = economics::SMALLEST_UNIT;
+        let day = 86_400;
+        let history = vec![event(1, 0, ActivityKind::Received, one_axm, 0)];
+
+        assert_eq!(analyze(&[1u8; 32], &history, 2 * day).coin_age_axm_days, 2.0);
+        assert_eq!(analyze(&[1u8; 32], &[], day).coin_age_axm_days, 0.0);
+    }
+}
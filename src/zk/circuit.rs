@@ -9,6 +9,8 @@
 
 use sha2::{Digest, Sha256};
 use std::fs;
+
+use crate::error::{AxiomError, Result};
 use std::path::Path;
 
 use winterfell::{
@@ -279,15 +281,16 @@ pub struct ZkProofSystem {
 
 impl ZkProofSystem {
     /// Create a new ZK-STARK proof system (no trusted setup needed!)
-    pub fn setup() -> Result<Self, String> {
+    pub fn setup() -> Result<Self> {
         Ok(Self {
             options: default_proof_options(),
         })
     }
 
     /// Save proof parameters to disk
-    pub fn save_keys(&self, keys_dir: &str) -> Result<(), String> {
-        fs::create_dir_all(keys_dir).map_err(|e| format!("Failed to create keys dir: {}", e))?;
+    pub fn save_keys(&self, keys_dir: &str) -> Result<()> {
+        fs::create_dir_all(keys_dir)
+            .map_err(|e| AxiomError::IoError(format!("Failed to create keys dir: {}", e)))?;
 
         let params_path = format!("{}/stark_params.json", keys_dir);
         let params_json = serde_json::json!({
@@ -300,7 +303,7 @@ impl ZkProofSystem {
             "blowup_factor": 8,
         });
         fs::write(&params_path, serde_json::to_string_pretty(&params_json).unwrap())
-            .map_err(|e| format!("Failed to write params: {}", e))?;
+            .map_err(|e| AxiomError::IoError(format!("Failed to write params: {}", e)))?;
 
         log::info!("STARK parameters saved to {}", keys_dir);
         log::info!("No trusted setup required - STARKs are transparent!");
@@ -308,7 +311,7 @@ impl ZkProofSystem {
     }
 
     /// Load parameters from disk
-    pub fn load_keys(keys_dir: &str) -> Result<Self, String> {
+    pub fn load_keys(keys_dir: &str) -> Result<Self> {
         let params_path = format!("{}/stark_params.json", keys_dir);
         if !Path::new(&params_path).exists() {
             // STARKs don't need pre-generated keys, just use defaults
@@ -330,13 +333,13 @@ impl ZkProofSystem {
         nonce: BaseElement,
         transfer_amount: BaseElement,
         fee: BaseElement,
-    ) -> Result<(Proof, Vec<BaseElement>), String> {
+    ) -> Result<(Proof, Vec<BaseElement>)> {
         // Pre-check: fail fast if balance is insufficient
         if current_balance.as_int() < (transfer_amount + fee).as_int() {
-            return Err(format!(
-                "Insufficient balance: have {}, need {} (amount) + {} (fee)",
-                current_balance, transfer_amount, fee,
-            ));
+            return Err(AxiomError::InsufficientBalance {
+                available: u64::try_from(current_balance.as_int()).unwrap_or(u64::MAX),
+                required: u64::try_from((transfer_amount + fee).as_int()).unwrap_or(u64::MAX),
+            });
         }
 
         // Compute public inputs
@@ -362,7 +365,9 @@ impl ZkProofSystem {
 
         // Create prover and generate proof
         let prover = AxiomTransactionProver::new(self.options.clone(), public_inputs);
-        let proof = prover.prove(trace).map_err(|e| format!("Proving failed: {:?}", e))?;
+        let proof = prover
+            .prove(trace)
+            .map_err(|e| AxiomError::ProofGenerationFailed(format!("{:?}", e)))?;
 
         let public_outputs = vec![commitment, transfer_amount, fee, new_balance_commitment];
         Ok((proof, public_outputs))
@@ -372,7 +377,7 @@ impl ZkProofSystem {
     pub fn prove_batch(
         &self,
         transactions: Vec<(BaseElement, BaseElement, BaseElement, BaseElement, BaseElement)>,
-    ) -> Result<Vec<(Proof, Vec<BaseElement>)>, String> {
+    ) -> Result<Vec<(Proof, Vec<BaseElement>)>> {
         transactions
             .into_iter()
             .map(|(sk, balance, nonce, amount, fee)| self.prove(sk, balance, nonce, amount, fee))
@@ -384,9 +389,9 @@ impl ZkProofSystem {
         &self,
         proof: &Proof,
         public_inputs: &[BaseElement],
-    ) -> Result<bool, String> {
+    ) -> Result<bool> {
         if public_inputs.len() != 4 {
-            return Err("Expected 4 public inputs".to_string());
+            return Err(AxiomError::InvalidCircuitParams("Expected 4 public inputs".to_string()));
         }
 
         let pub_inputs = TransactionPublicInputs {
@@ -406,7 +411,7 @@ impl ZkProofSystem {
             Ok(_) => Ok(true),
             Err(e) => {
                 // Verification failed - proof is invalid
-                Err(format!("STARK verification failed: {:?}", e))
+                Err(AxiomError::ProofVerificationFailed(format!("STARK verification failed: {:?}", e)))
             }
         }
     }
@@ -477,7 +482,7 @@ mod tests {
 
         let result = system.prove(secret_key, balance, nonce, amount, fee);
         assert!(result.is_err(), "Should fail with insufficient balance");
-        assert!(result.unwrap_err().contains("Insufficient balance"));
+        assert!(matches!(result, Err(AxiomError::InsufficientBalance { available: 50, required: 110 })));
     }
 
     #[test]
@@ -6,6 +6,8 @@ const MINING_PROOF_SIZE: usize = 128;
 /// Layout: [commitment: 16 bytes (u128 LE)] [new_balance_commitment: 16 bytes (u128 LE)]
 const STARK_PUBLIC_INPUTS_HEADER: usize = 32;
 
+use crate::error::AxiomError;
+
 // Production ZK-STARK implementation
 pub mod transaction_circuit;
 
@@ -87,7 +89,7 @@ pub fn verify_transaction_proof(
     // the prepended header, then deserialize the actual proof.
     // Layout: [commitment: 16 bytes] [new_balance_commitment: 16 bytes] [proof...]
     if proof_bytes.len() < STARK_PUBLIC_INPUTS_HEADER {
-        return Err("STARK proof too short".into());
+        return Err(AxiomError::ProofVerificationFailed("STARK proof too short".into()).into());
     }
 
     let commitment_int = u128::from_le_bytes(
@@ -99,9 +101,7 @@ pub fn verify_transaction_proof(
     let stark_proof_data = &proof_bytes[STARK_PUBLIC_INPUTS_HEADER..];
 
     let proof = Proof::from_bytes(stark_proof_data)
-        .map_err(|e| -> Box<dyn std::error::Error> {
-            format!("Proof deserialization failed: {:?}", e).into()
-        })?;
+        .map_err(|e| AxiomError::ProofVerificationFailed(format!("Proof deserialization failed: {:?}", e)))?;
 
     // Reconstruct public inputs using the commitment values embedded
     // in the proof envelope and the transaction data.
@@ -15,6 +15,8 @@ use winterfell::{
 };
 use sha2::{Sha256, Digest};
 
+use crate::error::{AxiomError, Result};
+
 /// Transaction circuit that proves:
 /// 1. Sender has sufficient balance (without revealing it)
 /// 2. Private key matches public address
@@ -231,7 +233,7 @@ pub fn prove_transaction(
     nonce: u64,
     sender_balance: u64,
     _sender_secret_key: &[u8; 32],
-) -> Result<ProofData, String> {
+) -> Result<ProofData> {
     let sender_hash = bytes_to_field(from);
     let recipient_hash = bytes_to_field(to);
     let amount_fe = BaseElement::new(amount as u128);
@@ -241,10 +243,7 @@ pub fn prove_transaction(
 
     // Pre-check solvency
     if sender_balance < amount + fee {
-        return Err(format!(
-            "Insufficient balance: have {}, need {} (amount) + {} (fee)",
-            sender_balance, amount, fee
-        ));
+        return Err(AxiomError::InsufficientBalance { available: sender_balance, required: amount.saturating_add(fee) });
     }
 
     let remainder_fe = balance_fe - amount_fe - fee_fe;
@@ -290,7 +289,9 @@ pub fn prove_transaction(
         public_inputs: pub_inputs,
     };
 
-    let proof = prover.prove(trace).map_err(|e| format!("Proving failed: {:?}", e))?;
+    let proof = prover
+        .prove(trace)
+        .map_err(|e| AxiomError::ProofGenerationFailed(format!("{:?}", e)))?;
     let proof_bytes = proof.to_bytes();
 
     let public_inputs = vec![
@@ -315,9 +316,9 @@ pub fn verify_zk_transaction_proof(
     fee: u64,
     nonce: u64,
     proof_data: &ProofData,
-) -> Result<bool, String> {
+) -> Result<bool> {
     let proof = Proof::from_bytes(&proof_data.proof)
-        .map_err(|e| format!("Proof deserialization failed: {:?}", e))?;
+        .map_err(|e| AxiomError::ProofVerificationFailed(format!("Proof deserialization failed: {:?}", e)))?;
 
     let sender_hash = bytes_to_field(from);
     let recipient_hash = bytes_to_field(to);
@@ -341,7 +342,7 @@ pub fn verify_zk_transaction_proof(
         DefaultRandomCoin<Blake3_256<BaseElement>>,
     >(proof, pub_inputs, &min_opts)
         .map(|_| true)
-        .map_err(|e| format!("Verification failed: {:?}", e))
+        .map_err(|e| AxiomError::ProofVerificationFailed(format!("{:?}", e)))
 }
 
 #[cfg(test)]
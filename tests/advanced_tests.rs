@@ -13,7 +13,7 @@ fn test_fork_detection() {
     let block_b = Block::new(genesis_hash, 1, [2u8; 32], vec![], [20u8; 32], vec![2u8; 64], 200);
     
     assert_eq!(block_a.parent, block_b.parent);
-    assert_ne!(block_a.hash(), block_b.hash());
+    assert_ne!(block_a.legacy_hash(), block_b.legacy_hash());
     assert_eq!(block_a.slot, block_b.slot);
 }
 
@@ -1,13 +1,12 @@
 #[cfg(test)]
 mod tests {
     use axiom_core::*;
-    use axiom_core::block::Block;
+    use axiom_core::block::{Block, Coinbase};
     use axiom_core::chain::{self, Timechain};
     use axiom_core::genesis;
     use axiom_core::wallet::Wallet;
     use axiom_core::economics::block_reward;
     use axiom_core::vdf;
-    use axiom_core::main_helper;
 
     #[test]
     fn test_transaction_creation() {
@@ -77,7 +76,7 @@ mod tests {
     #[test]
     fn test_block_hash() {
         let block = genesis::genesis();
-        let hash = block.hash();
+        let hash = block.legacy_hash();
         assert_eq!(hash.len(), 32);
     }
 
@@ -119,14 +118,14 @@ mod tests {
         let wallet = Wallet::load_or_create();
 
         // Simulate mining a block
-        let parent_hash = chain.blocks.last().unwrap().hash();
+        let parent_hash = chain.blocks.last().unwrap().legacy_hash();
         let current_slot = chain.blocks.len() as u64;
 
         // Use low difficulty for testing
         chain.difficulty = 10;
 
         let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-        let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+        let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
         let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
 
         // Try to find a valid nonce
@@ -140,7 +139,10 @@ mod tests {
                 timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
                 miner: wallet.address,
                 transactions: vec![],
-                vdf_proof,
+                coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+                uncles: vec![],
+                vdf_proof: vdf.output,
+                vdf_witness: vdf.witness.clone(),
                 zk_proof: zk_pass.clone(),
                 nonce,
             };
@@ -256,17 +258,17 @@ mod tests {
         assert_eq!(chain.blocks.len(), 1);
 
         // 3. VDF COMPUTATION & PROOF
-        let parent_hash = chain.blocks.last().unwrap().hash();
+        let parent_hash = chain.blocks.last().unwrap().legacy_hash();
         let current_slot = chain.blocks.len() as u64;
         let vdf_seed = vdf::evaluate(parent_hash, current_slot);
         assert_ne!(vdf_seed, parent_hash, "VDF seed must differ from parent hash");
 
         // Use low difficulty for testing
         chain.difficulty = 10;
-        let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+        let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
         // VDF must be deterministic
-        let vdf_proof2 = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
-        assert_eq!(vdf_proof, vdf_proof2, "VDF must be deterministic");
+        let vdf2 = chain.vdf().prove(vdf_seed, chain.difficulty);
+        assert_eq!(vdf, vdf2, "VDF must be deterministic");
 
         // 4. ZK MINING PROOF GENERATION & VERIFICATION
         let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
@@ -285,7 +287,10 @@ mod tests {
                 timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
                 miner: wallet.address,
                 transactions: vec![],
-                vdf_proof,
+                coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+                uncles: vec![],
+                vdf_proof: vdf.output,
+                vdf_witness: vdf.witness.clone(),
                 zk_proof: zk_pass.clone(),
                 nonce,
             };
@@ -303,7 +308,10 @@ mod tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
             miner: wallet.address,
             transactions: vec![],
-            vdf_proof,
+            coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+            uncles: vec![],
+            vdf_proof: vdf.output,
+            vdf_witness: vdf.witness.clone(),
             zk_proof: zk_pass.clone(),
             nonce,
         };
@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod security_tests {
     use axiom_core::*;
-    use axiom_core::block::Block;
+    use axiom_core::block::{Block, Coinbase};
     use axiom_core::chain::{self, Timechain};
     use axiom_core::genesis;
     use axiom_core::wallet::Wallet;
@@ -20,12 +20,12 @@ mod security_tests {
         let tx = wallet.create_transaction(to_address, 100_000_000, 1_000_000, 0, 200_000_000).unwrap();
         
         // Try to create a block with the same transaction twice
-        let parent_hash = chain.blocks.last().unwrap().hash();
+        let parent_hash = chain.blocks.last().unwrap().legacy_hash();
         let current_slot = chain.blocks.len() as u64;
         chain.difficulty = 10;
         
         let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-        let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+        let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
         let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
         
         let block = Block {
@@ -34,7 +34,10 @@ mod security_tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
             miner: wallet.address,
             transactions: vec![tx.clone(), tx.clone()], // Same transaction twice!
-            vdf_proof,
+            coinbase: Coinbase::expected(current_slot, &wallet.address, &[tx.clone(), tx.clone()]),
+            uncles: vec![],
+            vdf_proof: vdf.output,
+            vdf_witness: vdf.witness.clone(),
             zk_proof: zk_pass,
             nonce: 0,
         };
@@ -60,12 +63,12 @@ mod security_tests {
         let tx = wallet.create_transaction(to_address, 100_000_000, 1_000_000, 0, 200_000_000).unwrap();
         
         // Mine first block with transaction
-        let parent_hash = chain.blocks.last().unwrap().hash();
+        let parent_hash = chain.blocks.last().unwrap().legacy_hash();
         let current_slot = chain.blocks.len() as u64;
         chain.difficulty = 10;
         
         let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-        let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+        let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
         let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
         
         let mut block1 = Block {
@@ -74,7 +77,10 @@ mod security_tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
             miner: wallet.address,
             transactions: vec![tx.clone()],
-            vdf_proof,
+            coinbase: Coinbase::expected(current_slot, &wallet.address, std::slice::from_ref(&tx)),
+            uncles: vec![],
+            vdf_proof: vdf.output,
+            vdf_witness: vdf.witness.clone(),
             zk_proof: zk_pass.clone(),
             nonce: 0,
         };
@@ -88,11 +94,11 @@ mod security_tests {
         println!("Block 1 added: {:?}", result1);
         
         // Try to create another block with the SAME transaction (double-spend attempt)
-        let parent_hash2 = chain.blocks.last().unwrap().hash();
+        let parent_hash2 = chain.blocks.last().unwrap().legacy_hash();
         let current_slot2 = chain.blocks.len() as u64;
         
         let vdf_seed2 = vdf::evaluate(parent_hash2, current_slot2);
-        let vdf_proof2 = main_helper::compute_vdf(vdf_seed2, chain.difficulty as u32);
+        let vdf2 = chain.vdf().prove(vdf_seed2, chain.difficulty);
         
         let mut block2 = Block {
             parent: parent_hash2,
@@ -100,7 +106,10 @@ mod security_tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot2,
             miner: wallet.address,
             transactions: vec![tx.clone()], // Same transaction again!
-            vdf_proof: vdf_proof2,
+            coinbase: Coinbase::expected(current_slot2, &wallet.address, std::slice::from_ref(&tx)),
+            uncles: vec![],
+            vdf_proof: vdf2.output,
+            vdf_witness: vdf2.witness.clone(),
             zk_proof: zk_pass,
             nonce: 0,
         };
@@ -156,12 +165,12 @@ mod security_tests {
         let tx1 = wallet.create_transaction(to_address, 50_000_000, 1_000_000, 0, 200_000_000).unwrap();
         
         // Add transaction to chain
-        let parent_hash = chain.blocks.last().unwrap().hash();
+        let parent_hash = chain.blocks.last().unwrap().legacy_hash();
         let current_slot = chain.blocks.len() as u64;
         chain.difficulty = 10;
         
         let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-        let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+        let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
         let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
         
         let mut block1 = Block {
@@ -170,7 +179,10 @@ mod security_tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
             miner: wallet.address,
             transactions: vec![tx1.clone()],
-            vdf_proof,
+            coinbase: Coinbase::expected(current_slot, &wallet.address, std::slice::from_ref(&tx1)),
+            uncles: vec![],
+            vdf_proof: vdf.output,
+            vdf_witness: vdf.witness.clone(),
             zk_proof: zk_pass,
             nonce: 0,
         };
@@ -266,12 +278,12 @@ mod security_tests {
         let wallet = Wallet::load_or_create();
         
         // Mine first block normally
-        let parent_hash = chain.blocks.last().unwrap().hash();
+        let parent_hash = chain.blocks.last().unwrap().legacy_hash();
         let correct_slot = chain.blocks.len() as u64;
         chain.difficulty = 10;
         
         let vdf_seed = vdf::evaluate(parent_hash, correct_slot);
-        let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+        let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
         let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
         
         let mut block1 = Block {
@@ -280,7 +292,10 @@ mod security_tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * correct_slot,
             miner: wallet.address,
             transactions: vec![],
-            vdf_proof,
+            coinbase: Coinbase::expected(correct_slot, &wallet.address, &[]),
+            uncles: vec![],
+            vdf_proof: vdf.output,
+            vdf_witness: vdf.witness.clone(),
             zk_proof: zk_pass.clone(),
             nonce: 0,
         };
@@ -294,11 +309,11 @@ mod security_tests {
         assert!(result1.is_ok());
         
         // Try to add block with wrong slot (skip a number)
-        let parent_hash2 = chain.blocks.last().unwrap().hash();
+        let parent_hash2 = chain.blocks.last().unwrap().legacy_hash();
         let wrong_slot = chain.blocks.len() as u64 + 5; // Skip ahead!
         
         let vdf_seed2 = vdf::evaluate(parent_hash2, wrong_slot);
-        let vdf_proof2 = main_helper::compute_vdf(vdf_seed2, chain.difficulty as u32);
+        let vdf2 = chain.vdf().prove(vdf_seed2, chain.difficulty);
         
         let mut block_wrong = Block {
             parent: parent_hash2,
@@ -306,7 +321,10 @@ mod security_tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * wrong_slot,
             miner: wallet.address,
             transactions: vec![],
-            vdf_proof: vdf_proof2,
+            coinbase: Coinbase::expected(wrong_slot, &wallet.address, &[]),
+            uncles: vec![],
+            vdf_proof: vdf2.output,
+            vdf_witness: vdf2.witness.clone(),
             zk_proof: zk_pass,
             nonce: 0,
         };
@@ -334,7 +352,7 @@ mod security_tests {
         let current_slot = chain.blocks.len() as u64;
         
         let vdf_seed = vdf::evaluate(wrong_parent, current_slot);
-        let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+        let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
         let zk_pass = genesis::generate_zk_pass(&wallet, wrong_parent);
         
         let mut block = Block {
@@ -343,7 +361,10 @@ mod security_tests {
             timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
             miner: wallet.address,
             transactions: vec![],
-            vdf_proof,
+            coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+            uncles: vec![],
+            vdf_proof: vdf.output,
+            vdf_witness: vdf.witness.clone(),
             zk_proof: zk_pass,
             nonce: 0,
         };
@@ -2,7 +2,7 @@
 #[cfg(test)]
 mod stress_tests {
     use axiom_core::*;
-    use axiom_core::block::Block;
+    use axiom_core::block::{Block, Coinbase};
     use axiom_core::chain::{self, Timechain};
     use axiom_core::genesis;
     use axiom_core::wallet::Wallet;
@@ -22,11 +22,11 @@ mod stress_tests {
         for diff in difficulties {
             chain.difficulty = diff;
             
-            let parent_hash = chain.blocks.last().unwrap().hash();
+            let parent_hash = chain.blocks.last().unwrap().legacy_hash();
             let current_slot = chain.blocks.len() as u64;
             
             let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-            let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+            let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
             let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
             
             let start = Instant::now();
@@ -40,7 +40,10 @@ mod stress_tests {
                     timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
                     miner: wallet.address,
                     transactions: vec![],
-                    vdf_proof,
+                    coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+                    uncles: vec![],
+                    vdf_proof: vdf.output,
+                    vdf_witness: vdf.witness.clone(),
                     zk_proof: zk_pass.clone(),
                     nonce,
                 };
@@ -123,11 +126,11 @@ mod stress_tests {
         let start = Instant::now();
         
         for i in 0..num_blocks {
-            let parent_hash = chain.blocks.last().unwrap().hash();
+            let parent_hash = chain.blocks.last().unwrap().legacy_hash();
             let current_slot = chain.blocks.len() as u64;
             
             let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-            let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+            let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
             let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
             
             let mut block = Block {
@@ -136,7 +139,10 @@ mod stress_tests {
                 timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
                 miner: wallet.address,
                 transactions: vec![],
-                vdf_proof,
+                coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+                uncles: vec![],
+                vdf_proof: vdf.output,
+                vdf_witness: vdf.witness.clone(),
                 zk_proof: zk_pass,
                 nonce: 0,
             };
@@ -235,15 +241,15 @@ mod stress_tests {
         
         for _ in 0..num_blocks {
             let parent_hash = if blocks.is_empty() {
-                genesis.hash()
+                genesis.legacy_hash()
             } else {
-                blocks.last().unwrap().hash()
+                blocks.last().unwrap().legacy_hash()
             };
             
             let current_slot = (blocks.len() + 1) as u64;
             
             let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-            let vdf_proof = main_helper::compute_vdf(vdf_seed, chain.difficulty as u32);
+            let vdf = chain.vdf().prove(vdf_seed, chain.difficulty);
             let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
             
             let mut block = Block {
@@ -252,7 +258,10 @@ mod stress_tests {
                 timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
                 miner: wallet.address,
                 transactions: vec![],
-                vdf_proof,
+                coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+                uncles: vec![],
+                vdf_proof: vdf.output,
+                vdf_witness: vdf.witness.clone(),
                 zk_proof: zk_pass,
                 nonce: 0,
             };
@@ -303,11 +312,11 @@ mod stress_tests {
         
         // Chain 1: 5 blocks
         for i in 0..5 {
-            let parent_hash = chain1.blocks.last().unwrap().hash();
+            let parent_hash = chain1.blocks.last().unwrap().legacy_hash();
             let current_slot = chain1.blocks.len() as u64;
             
             let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-            let vdf_proof = main_helper::compute_vdf(vdf_seed, chain1.difficulty as u32);
+            let vdf = chain1.vdf().prove(vdf_seed, chain1.difficulty);
             let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
             
             let mut block = Block {
@@ -316,7 +325,10 @@ mod stress_tests {
                 timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
                 miner: wallet.address,
                 transactions: vec![],
-                vdf_proof,
+                coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+                uncles: vec![],
+                vdf_proof: vdf.output,
+                vdf_witness: vdf.witness.clone(),
                 zk_proof: zk_pass,
                 nonce: i * 100, // Different nonces for uniqueness
             };
@@ -332,11 +344,11 @@ mod stress_tests {
         
         // Chain 2: 7 blocks (longer, should win)
         for i in 0..7 {
-            let parent_hash = chain2.blocks.last().unwrap().hash();
+            let parent_hash = chain2.blocks.last().unwrap().legacy_hash();
             let current_slot = chain2.blocks.len() as u64;
             
             let vdf_seed = vdf::evaluate(parent_hash, current_slot);
-            let vdf_proof = main_helper::compute_vdf(vdf_seed, chain2.difficulty as u32);
+            let vdf = chain2.vdf().prove(vdf_seed, chain2.difficulty);
             let zk_pass = genesis::generate_zk_pass(&wallet, parent_hash);
             
             let mut block = Block {
@@ -345,7 +357,10 @@ mod stress_tests {
                 timestamp: genesis::GENESIS_TIMESTAMP + chain::TARGET_TIME * current_slot,
                 miner: wallet.address,
                 transactions: vec![],
-                vdf_proof,
+                coinbase: Coinbase::expected(current_slot, &wallet.address, &[]),
+                uncles: vec![],
+                vdf_proof: vdf.output,
+                vdf_witness: vdf.witness.clone(),
                 zk_proof: zk_pass,
                 nonce: i * 200 + 50000, // Different nonces from chain1
             };